@@ -0,0 +1,198 @@
+use std::{
+    path::PathBuf,
+    sync::{Arc, RwLock},
+    time::Duration,
+};
+
+use {
+    chrono::{DateTime, Utc},
+    icann_rdap_cli::{
+        dirs,
+        rt::{
+            badge::{badge_svg, BadgeStatus},
+            exec::{execute_tests, TestOptions},
+            history::{append_history, history_file, last_history, RunSummary},
+        },
+    },
+    icann_rdap_client::{http::ClientConfig, iana::BootstrapStore, rdap::QueryType},
+    icann_rdap_common::check::CheckClass,
+    serde::Serialize,
+    tokio::{
+        io::{AsyncReadExt, AsyncWriteExt},
+        net::{TcpListener, TcpStream},
+        time::timeout,
+    },
+    tracing::{debug, error, info, warn},
+};
+
+use crate::error::RdapTestError;
+
+/// The time a client of the status endpoint has to send its request.
+const STATUS_REQUEST_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Options for running the tests repeatedly.
+pub(crate) struct DaemonOptions {
+    pub interval: Duration,
+    pub status_addr: Option<String>,
+    pub alert_webhook: Option<String>,
+    pub alert_file: Option<PathBuf>,
+    pub badge: Option<PathBuf>,
+}
+
+/// The status of the daemon, as served by the status endpoint.
+#[derive(Debug, Serialize)]
+struct DaemonStatus {
+    started: DateTime<Utc>,
+    runs: usize,
+    regressions: usize,
+    last_is_regression: bool,
+    last: Option<RunSummary>,
+}
+
+/// The payload of an alert sent when conformance regresses.
+#[derive(Debug, Serialize)]
+struct RegressionAlert<'a> {
+    current: &'a RunSummary,
+    previous: &'a RunSummary,
+}
+
+pub(crate) async fn run_daemon<BS: BootstrapStore>(
+    bs: &BS,
+    query_type: &QueryType,
+    test_options: &TestOptions,
+    client_config: &ClientConfig,
+    check_classes: &[CheckClass],
+    daemon_options: &DaemonOptions,
+) -> Result<(), RdapTestError> {
+    let status = Arc::new(RwLock::new(DaemonStatus {
+        started: Utc::now(),
+        runs: 0,
+        regressions: 0,
+        last_is_regression: false,
+        last: None,
+    }));
+
+    if let Some(status_addr) = &daemon_options.status_addr {
+        let listener = TcpListener::bind(status_addr).await?;
+        info!("Serving status on {}", listener.local_addr()?);
+        tokio::spawn(serve_status(listener, status.clone()));
+    }
+
+    info!(
+        "Running tests every {}",
+        humantime::format_duration(daemon_options.interval)
+    );
+    loop {
+        match execute_tests(bs, query_type, test_options, client_config).await {
+            Ok(test_results) => {
+                let summary = RunSummary::from_results(&test_results, check_classes);
+                let path = history_file(&dirs::rdap_test_history_path(), &summary.query_url);
+                let previous = last_history(&path)?;
+                append_history(&path, &summary)?;
+                info!(
+                    "Tested {} of {} with {} error checks, {} warning checks, and {} execution errors.",
+                    summary.tested,
+                    summary.test_runs,
+                    summary.error_checks,
+                    summary.warning_checks,
+                    summary.execution_errors
+                );
+                if let Some(badge) = &daemon_options.badge {
+                    let svg = badge_svg(BadgeStatus::from_summary(&summary));
+                    if let Err(e) = std::fs::write(badge, svg) {
+                        error!("Unable to write badge {}: {e}", badge.display());
+                    }
+                }
+                let regression = previous
+                    .as_ref()
+                    .is_some_and(|previous| summary.is_regression_of(previous));
+                if let (true, Some(previous)) = (regression, &previous) {
+                    warn!("Conformance has regressed since the previous run.");
+                    let alert = RegressionAlert {
+                        current: &summary,
+                        previous,
+                    };
+                    send_alert(&alert, daemon_options).await;
+                }
+                if let Ok(mut status) = status.write() {
+                    status.runs += 1;
+                    status.last_is_regression = regression;
+                    if regression {
+                        status.regressions += 1;
+                    }
+                    status.last = Some(summary);
+                }
+            }
+            Err(e) => error!("Unable to execute tests: {e}"),
+        }
+
+        tokio::select! {
+            _ = tokio::time::sleep(daemon_options.interval) => {},
+            _ = tokio::signal::ctrl_c() => {
+                info!("Stopping.");
+                return Ok(());
+            }
+        }
+    }
+}
+
+async fn send_alert(alert: &RegressionAlert<'_>, daemon_options: &DaemonOptions) {
+    if let Some(alert_file) = &daemon_options.alert_file {
+        let result = serde_json::to_string_pretty(alert)
+            .map_err(std::io::Error::from)
+            .and_then(|json| std::fs::write(alert_file, json));
+        if let Err(e) = result {
+            error!("Unable to write alert file {}: {e}", alert_file.display());
+        }
+    }
+    if let Some(alert_webhook) = &daemon_options.alert_webhook {
+        let result = reqwest::Client::new()
+            .post(alert_webhook)
+            .json(alert)
+            .send()
+            .await
+            .and_then(|response| response.error_for_status());
+        if let Err(e) = result {
+            error!("Unable to send alert to {alert_webhook}: {e}");
+        }
+    }
+}
+
+async fn serve_status(listener: TcpListener, status: Arc<RwLock<DaemonStatus>>) {
+    loop {
+        let (stream, addr) = match listener.accept().await {
+            Ok(accepted) => accepted,
+            Err(e) => {
+                warn!("Unable to accept status connection: {e}");
+                continue;
+            }
+        };
+        let status = status.clone();
+        tokio::spawn(async move {
+            if let Err(e) = answer_status(stream, &status).await {
+                debug!("Status connection from {addr}: {e}");
+            }
+        });
+    }
+}
+
+/// Reads the request of a connection, and writes the status.
+async fn answer_status(
+    mut stream: TcpStream,
+    status: &RwLock<DaemonStatus>,
+) -> Result<(), std::io::Error> {
+    // the request itself does not matter, every request gets the status.
+    let mut buf = [0; 1024];
+    timeout(STATUS_REQUEST_TIMEOUT, stream.read(&mut buf))
+        .await
+        .map_err(|_| std::io::Error::from(std::io::ErrorKind::TimedOut))??;
+    let body = match status.read() {
+        Ok(status) => serde_json::to_string(&*status).unwrap_or_default(),
+        Err(_) => String::default(),
+    };
+    let response = format!(
+        "HTTP/1.1 200 OK\r\ncontent-type: application/json\r\ncontent-length: {}\r\nconnection: close\r\n\r\n{body}",
+        body.len()
+    );
+    stream.write_all(response.as_bytes()).await
+}
@@ -16,6 +16,8 @@ pub enum RdapTestError {
     TestsCompletedWarningsFound,
     #[error("Tests completed, error checks found.")]
     TestsCompletedErrorsFound,
+    #[error("Bootstrap files differ.")]
+    BootstrapsDiffer,
     #[error(transparent)]
     RdapClient(#[from] RdapClientError),
     #[error(transparent)]
@@ -48,6 +50,7 @@ impl Termination for RdapTestError {
             Self::TestsCompletedExecutionErrors => 1,
             Self::TestsCompletedWarningsFound => 2,
             Self::TestsCompletedErrorsFound => 3,
+            Self::BootstrapsDiffer => 4,
 
             // Internal Errors
             Self::Termimad(_) => 10,
@@ -67,30 +70,7 @@ impl Termination for RdapTestError {
             Self::UnknownOutputType => 200,
 
             // RDAP Client Errrors
-            Self::RdapClient(e) => match e {
-                // I/O Errors
-                RdapClientError::Client(_) => 42,
-                RdapClientError::IoError(_) => 43,
-
-                // RDAP Server Errors
-                RdapClientError::Response(_) => 60,
-                RdapClientError::ParsingError(_) => 62,
-                RdapClientError::Json(_) => 63,
-
-                // Bootstrap Errors
-                RdapClientError::BootstrapUnavailable => 70,
-                RdapClientError::BootstrapError(_) => 71,
-                RdapClientError::IanaResponse(_) => 72,
-
-                // User Errors
-                RdapClientError::InvalidQueryValue => 202,
-                RdapClientError::AmbiquousQueryType => 203,
-                RdapClientError::DomainNameError(_) => 204,
-
-                // Internal Errors
-                RdapClientError::Poison => 250,
-                // _ => 255,
-            },
+            Self::RdapClient(e) => e.code(),
         };
         ExitCode::from(exit_code)
     }
@@ -1,20 +1,38 @@
-use std::{io::stdout, str::FromStr};
+use std::{
+    fs,
+    io::stdout,
+    path::{Path, PathBuf},
+    str::FromStr,
+    time::Duration,
+};
 
 #[cfg(debug_assertions)]
 use tracing::warn;
 use {
     clap::builder::{styling::AnsiColor, Styles},
+    daemon::{run_daemon, DaemonOptions},
     error::RdapTestError,
     icann_rdap_cli::{
         dirs,
-        dirs::fcbs::FileCacheBootstrapStore,
         rt::{
+            badge::{badge_svg, BadgeStatus},
             exec::{execute_tests, ExtensionGroup, TestOptions},
+            history::RunSummary,
             results::{RunOutcome, TestResults},
+            scan::{scan_bootstrap, to_csv, ScanOptions},
+            tls::TlsInspection,
         },
     },
-    icann_rdap_client::{http::ClientConfig, md::MdOptions, rdap::QueryType},
-    icann_rdap_common::check::{traverse_checks, CheckClass},
+    icann_rdap_client::{
+        http::{create_client, ClientConfig, SpkiPin},
+        iana::{iana_request, FileBootstrapStore},
+        md::MdOptions,
+        rdap::QueryType,
+    },
+    icann_rdap_common::{
+        check::{traverse_checks, CheckClass, ProfileId},
+        iana::{IanaRegistry, IanaRegistryType},
+    },
     termimad::{crossterm::style::Color::*, Alignment, MadSkin},
     tracing::info,
     tracing_subscriber::filter::LevelFilter,
@@ -25,6 +43,7 @@ use {
     icann_rdap_common::VERSION,
 };
 
+pub mod daemon;
 pub mod error;
 
 struct CliStyles;
@@ -46,8 +65,8 @@ struct Cli {
     /// Value to be queried in RDAP.
     ///
     /// This is the value to query. For example, a domain name or IP address.
-    #[arg()]
-    query_value: String,
+    #[arg(required_unless_present_any = ["compare_bootstrap", "scan_bootstrap"])]
+    query_value: Option<String>,
 
     /// Output format.
     ///
@@ -55,6 +74,7 @@ struct Cli {
     #[arg(
         short = 'O',
         long,
+        visible_alias = "format",
         required = false,
         env = "RDAP_TEST_OUTPUT",
         value_enum,
@@ -127,11 +147,27 @@ struct Cli {
     )]
     allow_invalid_certificates: bool,
 
+    /// Pin the public key of servers.
+    ///
+    /// Given as BASE_URL=PIN, where PIN is the base64 of the SHA-256 hash of
+    /// the public key (SubjectPublicKeyInfo) of the certificate of the server,
+    /// as with the --pinnedpubkey option of curl. Responses from URLs starting
+    /// with BASE_URL fail unless the server has one of the pinned keys.
+    /// This argument may be given multiple times, or as a comma separated list.
+    #[arg(
+        long,
+        required = false,
+        value_name = "BASE_URL=PIN",
+        value_delimiter = ',',
+        env = "RDAP_TEST_SPKI_PIN"
+    )]
+    spki_pin: Vec<SpkiPin>,
+
     /// Maximum retry wait time.
     ///
     /// Sets the maximum number of seconds to wait before retrying a query when
-    /// a server has sent an HTTP 429 status code with a retry-after value.
-    /// That is, the value to used is no greater than this setting.
+    /// a server has sent an HTTP 429 or 503 status code, with or without a
+    /// retry-after value. That is, the value to used is no greater than this setting.
     #[arg(
         long,
         required = false,
@@ -142,9 +178,10 @@ struct Cli {
 
     /// Default retry wait time.
     ///
-    /// Sets the number of seconds to wait before retrying a query when
-    /// a server has sent an HTTP 429 status code without a retry-after value
-    /// or when the retry-after value does not make sense.
+    /// Sets the number of seconds to wait before the first retry of a query when
+    /// a server has sent an HTTP 429 or 503 status code without a retry-after value
+    /// or when the retry-after value does not make sense. The wait is doubled
+    /// for each retry after, less a random jitter of up to half.
     #[arg(
         long,
         required = false,
@@ -156,7 +193,8 @@ struct Cli {
     /// Maximum number of retries.
     ///
     /// This sets the maximum number of retries when a server signals too many
-    /// requests have been sent using an HTTP 429 status code.
+    /// requests have been sent using an HTTP 429 status code, or that it is
+    /// unavailable using an HTTP 503 status code.
     #[arg(
         long,
         required = false,
@@ -201,6 +239,55 @@ struct Cli {
     #[arg(long, required = false, env = "RDAP_TEST_ONE_ADDR")]
     one_addr: bool,
 
+    /// Parallel test runs.
+    ///
+    /// The number of test runs against the addresses of the server,
+    /// with and without the origin header, executed at the same time.
+    #[arg(
+        long,
+        default_value = "1",
+        value_parser = clap::value_parser!(u16).range(1..),
+        env = "RDAP_TEST_PARALLEL"
+    )]
+    parallel: u16,
+
+    /// Inspect TLS.
+    ///
+    /// When given, the TLS connection of each HTTPS test run is inspected
+    /// for the TLS version, the cipher suite, and the validity of the
+    /// certificate chain, its expiration, and its coverage of the host name.
+    #[arg(long, required = false, env = "RDAP_TEST_INSPECT_TLS")]
+    inspect_tls: bool,
+
+    /// Certificate expiry window.
+    ///
+    /// When inspecting TLS, certificates expiring within this amount of
+    /// time, such as "30d", are reported as expiring soon.
+    #[arg(
+        long,
+        required = false,
+        env = "RDAP_TEST_CERT_EXPIRY_WINDOW",
+        default_value = "30d",
+        value_parser = humantime::parse_duration
+    )]
+    cert_expiry_window: Duration,
+
+    /// TLS SNI.
+    ///
+    /// The name sent in the TLS SNI, and against which the certificate
+    /// is verified, instead of the host of the query URL. The Host header
+    /// is still the host of the query URL unless the 'host-header' option
+    /// is given. This implies the 'inspect-tls' option.
+    #[arg(long, required = false, env = "RDAP_TEST_SNI")]
+    sni: Option<String>,
+
+    /// Host header.
+    ///
+    /// The value of the HTTP Host header instead of the host of the query URL,
+    /// such as for testing virtual hosts behind a CDN.
+    #[arg(long, required = false, env = "RDAP_TEST_HOST_HEADER")]
+    host_header: Option<String>,
+
     /// Origin header value.
     ///
     /// Specifies the origin header value.
@@ -273,6 +360,113 @@ struct Cli {
         env = "RDAP_TEST_ALLOW_UNREGISTERED_EXTENSIONS"
     )]
     allow_unregistered_extensions: bool,
+
+    /// Check profile.
+    ///
+    /// The profiles to check the responses against, separated by
+    /// commas, such as "gtld-registry,nro". By default, these are the
+    /// profiles declared by the responses and the NRO profile if an
+    /// NRO extension group is expected. "none" checks against no
+    /// profile.
+    #[arg(
+        long,
+        required = false,
+        value_enum,
+        value_delimiter = ',',
+        env = "RDAP_TEST_CHECK_PROFILE"
+    )]
+    check_profile: Vec<CheckProfileArg>,
+
+    /// Run as a daemon.
+    ///
+    /// When given, the tests are run repeatedly at the interval given by
+    /// the 'interval' option until the process is stopped. The summary of
+    /// each run is kept in a history, and an alert is raised when a run
+    /// has more errors or warnings than the previous run.
+    #[arg(long, required = false, env = "RDAP_TEST_DAEMON")]
+    daemon: bool,
+
+    /// Daemon interval.
+    ///
+    /// The amount of time to wait between runs when running as a daemon,
+    /// such as "30m" or "1h".
+    #[arg(
+        long,
+        required = false,
+        env = "RDAP_TEST_INTERVAL",
+        default_value = "1h",
+        value_parser = humantime::parse_duration
+    )]
+    interval: Duration,
+
+    /// Daemon status address.
+    ///
+    /// The address and port on which to serve the status of the daemon
+    /// as JSON. If not given, the status is not served.
+    #[arg(long, required = false, env = "RDAP_TEST_STATUS_ADDR")]
+    status_addr: Option<String>,
+
+    /// Alert webhook.
+    ///
+    /// A URL to which a JSON alert is posted when a daemon run regresses.
+    #[arg(long, required = false, env = "RDAP_TEST_ALERT_WEBHOOK")]
+    alert_webhook: Option<String>,
+
+    /// Alert file.
+    ///
+    /// A file to which a JSON alert is written when a daemon run regresses.
+    #[arg(long, required = false, env = "RDAP_TEST_ALERT_FILE")]
+    alert_file: Option<PathBuf>,
+
+    /// Conformance badge.
+    ///
+    /// An SVG file to which a badge showing whether the tests pass, pass
+    /// with warnings, or fail is written, such as for a status page.
+    /// When running as a daemon, the badge is written after each run.
+    #[arg(long, required = false, env = "RDAP_TEST_BADGE")]
+    badge: Option<PathBuf>,
+
+    /// Compare bootstrap files.
+    ///
+    /// Compares two copies of an IANA RDAP bootstrap file, such as an
+    /// old and a new copy of a mirror, and outputs the service URLs that
+    /// have been added or removed and the entries that have been added,
+    /// removed, or changed. No tests are run. The exit code is 4 if the
+    /// files differ.
+    #[arg(
+        long,
+        required = false,
+        num_args = 2,
+        value_names = ["OLD", "NEW"],
+        conflicts_with_all = ["query_value", "daemon"]
+    )]
+    compare_bootstrap: Option<Vec<PathBuf>>,
+
+    /// Scan bootstrap registry.
+    ///
+    /// Fetches an IANA RDAP bootstrap registry and probes the help of
+    /// every service URL in it, outputting the health of each service
+    /// as CSV, or as JSON with the JSON output types. No tests are run.
+    /// The exit code is 1 if any service could not be reached.
+    #[arg(
+        long,
+        required = false,
+        value_enum,
+        conflicts_with_all = ["query_value", "daemon", "compare_bootstrap"]
+    )]
+    scan_bootstrap: Option<BootstrapRegistryArg>,
+
+    /// Scan concurrency.
+    ///
+    /// The number of services probed at the same time when scanning
+    /// a bootstrap registry.
+    #[arg(
+        long,
+        default_value = "8",
+        value_parser = clap::value_parser!(u16).range(1..),
+        env = "RDAP_TEST_SCAN_CONCURRENCY"
+    )]
+    scan_concurrency: u16,
 }
 
 /// Represents the output type possibilities.
@@ -289,6 +483,12 @@ enum OtypeArg {
 
     /// Results are output as Pretty RDAP JSON.
     PrettyJson,
+
+    /// Results are output as a self-contained HTML report.
+    Html,
+
+    /// Results are output as JUnit XML, for continuous integration servers.
+    Junit,
 }
 
 #[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, ValueEnum)]
@@ -313,6 +513,75 @@ enum CheckTypeArg {
 
     /// ICANN Profile errors.
     IcannError,
+
+    /// NRO Profile errors, checked when the NRO profiles are expected.
+    NroError,
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, ValueEnum)]
+enum CheckProfileArg {
+    /// The gTLD RDAP profile for registries.
+    GtldRegistry,
+
+    /// The gTLD RDAP profile for registrars.
+    GtldRegistrar,
+
+    /// The NRO RDAP profile.
+    Nro,
+
+    /// No profile.
+    None,
+}
+
+impl CheckProfileArg {
+    /// Gets the profiles, or `None` if the profiles are to be determined from the responses.
+    fn to_profiles(profiles: &[Self]) -> Option<Vec<ProfileId>> {
+        if profiles.is_empty() {
+            None
+        } else {
+            Some(
+                profiles
+                    .iter()
+                    .filter_map(|profile| match profile {
+                        Self::GtldRegistry => Some(ProfileId::GtldRegistry),
+                        Self::GtldRegistrar => Some(ProfileId::GtldRegistrar),
+                        Self::Nro => Some(ProfileId::Nro),
+                        Self::None => None,
+                    })
+                    .collect(),
+            )
+        }
+    }
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, ValueEnum)]
+enum BootstrapRegistryArg {
+    /// The DNS bootstrap registry.
+    Dns,
+
+    /// The autonomous system number bootstrap registry.
+    Asn,
+
+    /// The IPv4 bootstrap registry.
+    Ipv4,
+
+    /// The IPv6 bootstrap registry.
+    Ipv6,
+
+    /// The object tags bootstrap registry.
+    ObjectTags,
+}
+
+impl From<BootstrapRegistryArg> for IanaRegistryType {
+    fn from(value: BootstrapRegistryArg) -> Self {
+        match value {
+            BootstrapRegistryArg::Dns => Self::RdapBootstrapDns,
+            BootstrapRegistryArg::Asn => Self::RdapBootstrapAsn,
+            BootstrapRegistryArg::Ipv4 => Self::RdapBootstrapIpv4,
+            BootstrapRegistryArg::Ipv6 => Self::RdapBootstrapIpv6,
+            BootstrapRegistryArg::ObjectTags => Self::RdapObjectTags,
+        }
+    }
 }
 
 #[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, ValueEnum)]
@@ -387,7 +656,41 @@ pub async fn wrapped_main() -> Result<(), RdapTestError> {
     #[cfg(debug_assertions)]
     warn!("This is a development build of this software.");
 
-    let query_type = QueryType::from_str(&cli.query_value)?;
+    if let Some(files) = cli.compare_bootstrap {
+        return compare_bootstrap(&files[0], &files[1], cli.output_type);
+    }
+
+    let client_config = ClientConfig::builder()
+        .user_agent_suffix("RT")
+        .https_only(!cli.allow_http)
+        .accept_invalid_host_names(cli.allow_invalid_host_names)
+        .accept_invalid_certificates(cli.allow_invalid_certificates)
+        .follow_redirects(cli.follow_redirects)
+        .spki_pins(cli.spki_pin)
+        .timeout_secs(cli.timeout_secs)
+        .max_retry_secs(cli.max_retry_secs)
+        .def_retry_secs(cli.def_retry_secs)
+        .max_retries(cli.max_retries)
+        .build();
+
+    if let Some(registry) = cli.scan_bootstrap {
+        let scan_options = ScanOptions {
+            concurrency: cli.scan_concurrency as usize,
+        };
+        return scan(
+            registry.into(),
+            &client_config,
+            &scan_options,
+            cli.output_type,
+        )
+        .await;
+    }
+
+    let query_type = QueryType::from_str(
+        cli.query_value
+            .as_deref()
+            .expect("query value is required without compare or scan bootstrap"),
+    )?;
 
     let check_classes = if cli.check_type.is_empty() {
         vec![
@@ -395,6 +698,7 @@ pub async fn wrapped_main() -> Result<(), RdapTestError> {
             CheckClass::StdError,
             CheckClass::Cidr0Error,
             CheckClass::IcannError,
+            CheckClass::NroError,
         ]
     } else if cli.check_type.contains(&CheckTypeArg::All) {
         vec![
@@ -404,6 +708,7 @@ pub async fn wrapped_main() -> Result<(), RdapTestError> {
             CheckClass::StdError,
             CheckClass::Cidr0Error,
             CheckClass::IcannError,
+            CheckClass::NroError,
         ]
     } else {
         cli.check_type
@@ -415,6 +720,7 @@ pub async fn wrapped_main() -> Result<(), RdapTestError> {
                 CheckTypeArg::StdError => CheckClass::StdError,
                 CheckTypeArg::Cidr0Error => CheckClass::Cidr0Error,
                 CheckTypeArg::IcannError => CheckClass::IcannError,
+                CheckTypeArg::NroError => CheckClass::NroError,
                 CheckTypeArg::All => panic!("check type for all should have been handled."),
             })
             .collect::<Vec<CheckClass>>()
@@ -429,7 +735,7 @@ pub async fn wrapped_main() -> Result<(), RdapTestError> {
         }
     }
 
-    let bs = FileCacheBootstrapStore;
+    let bs = FileBootstrapStore::new(dirs::bootstrap_cache_path());
 
     let options = TestOptions {
         skip_v4: cli.skip_v4,
@@ -440,21 +746,36 @@ pub async fn wrapped_main() -> Result<(), RdapTestError> {
         expect_extensions: cli.expect_extensions,
         expect_groups,
         allow_unregistered_extensions: cli.allow_unregistered_extensions,
+        profiles: CheckProfileArg::to_profiles(&cli.check_profile),
         one_addr: cli.one_addr,
         dns_resolver: Some(cli.dns_resolver),
+        parallel: cli.parallel as usize,
+        tls_inspection: (cli.inspect_tls || cli.sni.is_some()).then_some(TlsInspection {
+            expiry_window: cli.cert_expiry_window,
+            timeout: Duration::from_secs(cli.timeout_secs),
+        }),
+        sni: cli.sni,
+        host_header: cli.host_header,
     };
 
-    let client_config = ClientConfig::builder()
-        .user_agent_suffix("RT")
-        .https_only(!cli.allow_http)
-        .accept_invalid_host_names(cli.allow_invalid_host_names)
-        .accept_invalid_certificates(cli.allow_invalid_certificates)
-        .follow_redirects(cli.follow_redirects)
-        .timeout_secs(cli.timeout_secs)
-        .max_retry_secs(cli.max_retry_secs)
-        .def_retry_secs(cli.def_retry_secs)
-        .max_retries(cli.max_retries)
-        .build();
+    if cli.daemon {
+        let daemon_options = DaemonOptions {
+            interval: cli.interval,
+            status_addr: cli.status_addr,
+            alert_webhook: cli.alert_webhook,
+            alert_file: cli.alert_file,
+            badge: cli.badge,
+        };
+        return run_daemon(
+            &bs,
+            &query_type,
+            &options,
+            &client_config,
+            &check_classes,
+            &daemon_options,
+        )
+        .await;
+    }
 
     // execute tests
     let test_results = execute_tests(&bs, &query_type, &options, &client_config).await?;
@@ -492,6 +813,18 @@ pub async fn wrapped_main() -> Result<(), RdapTestError> {
         OtypeArg::PrettyJson => {
             println!("{}", serde_json::to_string_pretty(&test_results).unwrap());
         }
+        OtypeArg::Html => {
+            println!("{}", test_results.to_html(&check_classes));
+        }
+        OtypeArg::Junit => {
+            print!("{}", test_results.to_junit(&check_classes));
+        }
+    }
+
+    // write the badge
+    if let Some(badge) = &cli.badge {
+        let summary = RunSummary::from_results(&test_results, &check_classes);
+        std::fs::write(badge, badge_svg(BadgeStatus::from_summary(&summary)))?;
     }
 
     // if some tests could not execute
@@ -513,7 +846,10 @@ pub async fn wrapped_main() -> Result<(), RdapTestError> {
         .filter(|c| {
             matches!(
                 c,
-                CheckClass::StdError | CheckClass::Cidr0Error | CheckClass::IcannError
+                CheckClass::StdError
+                    | CheckClass::Cidr0Error
+                    | CheckClass::IcannError
+                    | CheckClass::NroError
             )
         })
         .copied()
@@ -539,6 +875,48 @@ pub async fn wrapped_main() -> Result<(), RdapTestError> {
     Ok(())
 }
 
+fn compare_bootstrap(old: &Path, new: &Path, output_type: OtypeArg) -> Result<(), RdapTestError> {
+    let read_registry = |path: &Path| -> Result<IanaRegistry, RdapTestError> {
+        serde_json::from_str::<IanaRegistry>(&fs::read_to_string(path)?)
+            .map_err(|_| RdapTestError::InvalidBootstrap)
+    };
+    let diff = read_registry(old)?.diff(&read_registry(new)?);
+    match output_type {
+        OtypeArg::Json => println!("{}", serde_json::to_string(&diff)?),
+        OtypeArg::PrettyJson => println!("{}", serde_json::to_string_pretty(&diff)?),
+        _ => print!("{diff}"),
+    }
+    if diff.is_empty() {
+        Ok(())
+    } else {
+        Err(RdapTestError::BootstrapsDiffer)
+    }
+}
+
+async fn scan(
+    registry_type: IanaRegistryType,
+    client_config: &ClientConfig,
+    scan_options: &ScanOptions,
+    output_type: OtypeArg,
+) -> Result<(), RdapTestError> {
+    let client = create_client(client_config)?;
+    let registry = iana_request(registry_type, &client).await?.registry;
+    let health = scan_bootstrap(&registry, &client, scan_options).await;
+    match output_type {
+        OtypeArg::Json => println!("{}", serde_json::to_string(&health)?),
+        OtypeArg::PrettyJson => println!("{}", serde_json::to_string_pretty(&health)?),
+        _ => print!("{}", to_csv(&health)),
+    }
+    if health
+        .iter()
+        .all(|service| matches!(service.outcome, RunOutcome::Tested))
+    {
+        Ok(())
+    } else {
+        Err(RdapTestError::TestsCompletedExecutionErrors)
+    }
+}
+
 fn are_there_checks(classes: Vec<CheckClass>, test_results: &TestResults) -> bool {
     // see if there are any checks in the test runs
     let run_count = test_results
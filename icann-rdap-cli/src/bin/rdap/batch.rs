@@ -0,0 +1,293 @@
+use std::{
+    collections::HashMap,
+    io::Read,
+    path::{Path, PathBuf},
+    str::FromStr,
+    sync::Mutex,
+    time::Duration,
+};
+
+use {
+    futures::{stream, StreamExt},
+    icann_rdap_client::{http::Client, rdap::QueryType},
+    serde_json::Value,
+    tokio::time::{sleep_until, Instant},
+    tracing::{error, info},
+};
+
+use crate::{
+    error::RdapCliError,
+    query::{do_query, OutputType, ProcessingParams},
+};
+
+/// The parameters of a batch of queries.
+pub(crate) struct BatchParams {
+    /// The number of queries made at the same time.
+    pub concurrency: usize,
+    /// The directory to write the results of each query to a file of its own,
+    /// or `None` if the results are all output together.
+    pub output_dir: Option<PathBuf>,
+}
+
+/// Limits the rate of requests to each server, by the host of the URL requested.
+pub(crate) struct ServerRateLimiter {
+    interval: Duration,
+    next_requests: Mutex<HashMap<String, Instant>>,
+}
+
+impl ServerRateLimiter {
+    /// Creates a limiter of the requests per second to each server, or `None` if
+    /// the rate is not a limit.
+    pub(crate) fn new(rate: f64) -> Option<Self> {
+        (rate.is_finite() && rate > 0.0).then(|| Self {
+            interval: Duration::from_secs_f64(1.0 / rate),
+            next_requests: Mutex::default(),
+        })
+    }
+
+    /// Waits until a request to the server of the URL is within the rate.
+    pub(crate) async fn wait(&self, url: &str) {
+        let Some(host) = url::Url::parse(url)
+            .ok()
+            .and_then(|url| url.host_str().map(str::to_ascii_lowercase))
+        else {
+            return;
+        };
+        let at = self.reserve(&host, Instant::now());
+        sleep_until(at).await;
+    }
+
+    /// Reserves the next time a request to a host is within the rate.
+    fn reserve(&self, host: &str, now: Instant) -> Instant {
+        let mut next_requests = self
+            .next_requests
+            .lock()
+            .expect("rate limiter lock poisoned");
+        let next = next_requests.entry(host.to_string()).or_insert(now);
+        let at = (*next).max(now);
+        *next = at + self.interval;
+        at
+    }
+}
+
+/// Does the queries read from a file, or from stdin if the path is "-".
+///
+/// The queries are made concurrently, and the results of each are output in the
+/// order of the queries. The failure of one query does not stop the others.
+pub(crate) async fn do_batch<W: std::io::Write>(
+    input: &Path,
+    batch_params: &BatchParams,
+    processing_params: &ProcessingParams,
+    client: &Client,
+    write: &mut W,
+) -> Result<(), RdapCliError> {
+    let source = input.display().to_string();
+    let text = if source == "-" {
+        let mut text = String::new();
+        std::io::stdin().read_to_string(&mut text)?;
+        text
+    } else {
+        std::fs::read_to_string(input)?
+    };
+    let queries = batch_queries(&text);
+    info!("batch of {} queries from '{source}'", queries.len());
+    if let Some(output_dir) = &batch_params.output_dir {
+        std::fs::create_dir_all(output_dir)?;
+    }
+
+    let mut results = stream::iter(queries)
+        .map(|query| async move {
+            let mut output = vec![];
+            let result = match QueryType::from_str(query) {
+                Ok(query_type) => {
+                    do_query(&query_type, processing_params, client, &mut output).await
+                }
+                Err(e) => Err(e.into()),
+            };
+            (query, output, result)
+        })
+        .buffered(batch_params.concurrency.max(1));
+
+    let mut failed = 0;
+    while let Some((query, output, result)) = results.next().await {
+        if let Err(e) = &result {
+            error!("query '{query}': {e}");
+            failed += 1;
+        }
+        match &batch_params.output_dir {
+            Some(output_dir) => {
+                write_query_files(output_dir, query, &output, result.err(), processing_params)?
+            }
+            None => write_query_output(write, query, &output, result.err(), processing_params)?,
+        }
+    }
+    if failed > 0 {
+        return Err(RdapCliError::BatchQueriesFailed(failed));
+    }
+    Ok(())
+}
+
+/// Gets the queries of a batch, one per line, skipping blank lines and comments.
+fn batch_queries(text: &str) -> Vec<&str> {
+    text.lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty() && !line.starts_with('#'))
+        .collect()
+}
+
+/// Writes the output of a query, in which each NDJSON line is given the query.
+fn write_query_output<W: std::io::Write>(
+    write: &mut W,
+    query: &str,
+    output: &[u8],
+    error: Option<RdapCliError>,
+    processing_params: &ProcessingParams,
+) -> Result<(), RdapCliError> {
+    if processing_params.output_type == OutputType::Ndjson {
+        for line in String::from_utf8_lossy(output).lines() {
+            let json = serde_json::from_str(line)?;
+            writeln!(write, "{}", with_query(query, json))?;
+        }
+        if let Some(error) = error {
+            writeln!(write, "{}", with_query(query, error.to_json()))?;
+        }
+    } else {
+        write.write_all(output)?;
+    }
+    write.flush()?;
+    Ok(())
+}
+
+/// Writes the output of a query to a file named from the query, and its error,
+/// if it has one, to a JSON file beside it.
+fn write_query_files(
+    output_dir: &Path,
+    query: &str,
+    output: &[u8],
+    error: Option<RdapCliError>,
+    processing_params: &ProcessingParams,
+) -> Result<(), RdapCliError> {
+    let name = file_name(query);
+    if !output.is_empty() {
+        let extension = file_extension(processing_params.output_type);
+        std::fs::write(output_dir.join(format!("{name}.{extension}")), output)?;
+    }
+    if let Some(error) = error {
+        let json = with_query(query, error.to_json());
+        std::fs::write(
+            output_dir.join(format!("{name}.error.json")),
+            format!("{json}\n"),
+        )?;
+    }
+    Ok(())
+}
+
+fn with_query(query: &str, mut json: Value) -> Value {
+    if let Value::Object(object) = &mut json {
+        object.insert("query".to_string(), Value::String(query.to_string()));
+    }
+    json
+}
+
+/// Makes a file name from a query, replacing the characters not safe in file names.
+fn file_name(query: &str) -> String {
+    query
+        .chars()
+        .map(|c| {
+            if c.is_alphanumeric() || matches!(c, '.' | '-' | '_') {
+                c
+            } else {
+                '_'
+            }
+        })
+        .collect()
+}
+
+fn file_extension(output_type: OutputType) -> &'static str {
+    match output_type {
+        OutputType::Json
+        | OutputType::PrettyJson
+        | OutputType::JsonExtra
+        | OutputType::ChecksJson
+        | OutputType::TraceJson => "json",
+        OutputType::Ndjson => "ndjson",
+        OutputType::RenderedMarkdown | OutputType::Markdown => "md",
+        OutputType::Vcf => "vcf",
+        OutputType::TextWrapped
+        | OutputType::GtldWhois
+        | OutputType::Whois
+        | OutputType::Checks
+        | OutputType::Url
+        | OutputType::Summary => "txt",
+    }
+}
+
+#[cfg(test)]
+#[allow(non_snake_case)]
+mod tests {
+    use std::time::Duration;
+
+    use {rstest::rstest, tokio::time::Instant};
+
+    use super::{batch_queries, file_name, ServerRateLimiter};
+
+    #[test]
+    fn GIVEN_batch_file_WHEN_batch_queries_THEN_blank_lines_and_comments_skipped() {
+        // GIVEN
+        let text = "# registries\nexample.com\n\n  192.0.2.1  \r\n# ASNs\nAS64496\n";
+
+        // WHEN
+        let actual = batch_queries(text);
+
+        // THEN
+        assert_eq!(actual, vec!["example.com", "192.0.2.1", "AS64496"]);
+    }
+
+    #[rstest]
+    #[case("example.com", "example.com")]
+    #[case("192.0.2.0/24", "192.0.2.0_24")]
+    #[case("2001:db8::/32", "2001_db8___32")]
+    #[case("https://rdap.example/domain/x", "https___rdap.example_domain_x")]
+    fn GIVEN_query_WHEN_file_name_THEN_safe_name(#[case] query: &str, #[case] expected: &str) {
+        // GIVEN in parameters
+
+        // WHEN
+        let actual = file_name(query);
+
+        // THEN
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn GIVEN_rate_WHEN_reserve_THEN_requests_to_each_host_spaced() {
+        // GIVEN
+        let limiter = ServerRateLimiter::new(2.0).expect("rate limiter");
+        let now = Instant::now();
+
+        // WHEN
+        let first = limiter.reserve("rdap.example.com", now);
+        let second = limiter.reserve("rdap.example.com", now);
+        let other = limiter.reserve("rdap.example.net", now);
+        let later = limiter.reserve("rdap.example.com", now + Duration::from_secs(5));
+
+        // THEN
+        assert_eq!(first, now);
+        assert_eq!(second, now + Duration::from_millis(500));
+        assert_eq!(other, now);
+        assert_eq!(later, now + Duration::from_secs(5));
+    }
+
+    #[rstest]
+    #[case(0.0)]
+    #[case(-1.0)]
+    #[case(f64::INFINITY)]
+    fn GIVEN_no_rate_WHEN_new_THEN_no_limiter(#[case] rate: f64) {
+        // GIVEN in parameters
+
+        // WHEN
+        let actual = ServerRateLimiter::new(rate);
+
+        // THEN
+        assert!(actual.is_none());
+    }
+}
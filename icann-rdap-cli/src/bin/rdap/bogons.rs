@@ -0,0 +1,53 @@
+use {
+    icann_rdap_cli::dirs::{
+        bootstrap_cache_path,
+        lock::{locked_read_lines, locked_write},
+    },
+    icann_rdap_client::{
+        http::Client,
+        iana::{bogon_list_request, BogonTable},
+    },
+    icann_rdap_common::httpdata::HttpData,
+    pct_str::{PctString, URIReserved},
+    tracing::{debug, warn},
+};
+
+use crate::error::RdapCliError;
+
+/// The maximum age in seconds of a fetched bogon list before it is fetched again.
+const BOGON_LIST_MAX_AGE: i64 = 86400;
+
+/// Gets the bogon table, adding the bogon list at the URL if one is given.
+///
+/// A bogon list that cannot be fetched is logged and otherwise ignored, so that
+/// the built-in bogons are still used.
+pub(crate) async fn get_bogons(bogons_url: Option<&str>, client: &Client) -> BogonTable {
+    let mut bogons = BogonTable::builtin();
+    if let Some(url) = bogons_url {
+        match get_bogon_list(url, client).await {
+            Ok(list) => {
+                let added = bogons.add_list(&list, &format!("Listed in {url}"));
+                debug!("Added {added} bogon prefixes from {url}");
+            }
+            Err(e) => warn!("Unable to get bogon list from {url}: {e}"),
+        }
+    }
+    bogons
+}
+
+async fn get_bogon_list(url: &str, client: &Client) -> Result<String, RdapCliError> {
+    let file_name = format!("{}.bogons", PctString::encode(url.chars(), URIReserved));
+    let path = bootstrap_cache_path().join(&file_name);
+    if path.exists() {
+        let lines = locked_read_lines(&path)?;
+        let (http_data, list) = HttpData::from_lines(&lines)?;
+        if !http_data.is_expired(BOGON_LIST_MAX_AGE) {
+            debug!("Returning bogon list from cache file {file_name}");
+            return Ok(list.join("\n"));
+        }
+    }
+    debug!("Fetching bogon list {url}");
+    let response = bogon_list_request(url, client).await?;
+    locked_write(&path, response.http_data.to_lines(&response.list)?)?;
+    Ok(response.list)
+}
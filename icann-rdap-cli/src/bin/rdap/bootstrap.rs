@@ -1,9 +1,12 @@
 use {
-    crate::error::RdapCliError,
-    icann_rdap_cli::dirs::fcbs::FileCacheBootstrapStore,
+    crate::{error::RdapCliError, query::ProcessingParams},
+    icann_rdap_cli::dirs::bootstrap_cache_path,
     icann_rdap_client::{
         http::Client,
-        iana::{fetch_bootstrap, qtype_to_bootstrap_url, BootstrapStore, PreferredUrl},
+        iana::{
+            fetch_bootstrap, qtype_to_bootstrap_trace, BootstrapStore, FileBootstrapStore,
+            PreferredUrl,
+        },
         rdap::QueryType,
     },
     icann_rdap_common::iana::IanaRegistryType,
@@ -32,7 +35,7 @@ pub(crate) enum BootstrapType {
 }
 
 pub(crate) async fn get_base_url(
-    bootstrap_type: &BootstrapType,
+    processing_params: &ProcessingParams,
     client: &Client,
     query_type: &QueryType,
 ) -> Result<String, RdapCliError> {
@@ -42,13 +45,19 @@ pub(crate) async fn get_base_url(
         return Ok(url.to_owned());
     }
 
-    let store = FileCacheBootstrapStore;
+    let store = FileBootstrapStore::new(bootstrap_cache_path())
+        .with_max_stale(processing_params.bootstrap_max_stale);
 
-    match bootstrap_type {
-        BootstrapType::Rfc9224 => Ok(qtype_to_bootstrap_url(client, &store, query_type, |reg| {
-            debug!("Fetching IANA registry {}", reg.url())
-        })
-        .await?),
+    match &processing_params.bootstrap_type {
+        BootstrapType::Rfc9224 => {
+            let bootstrap = qtype_to_bootstrap_trace(client, &store, query_type, |reg| {
+                debug!("Fetching IANA registry {}", reg.url())
+            })
+            .await?;
+            let base_url = bootstrap.chosen_url.clone();
+            processing_params.record_trace(|trace| trace.bootstrap = Some(bootstrap));
+            Ok(base_url)
+        }
         BootstrapType::Url(url) => Ok(url.to_owned()),
         BootstrapType::Hint(hint) => {
             fetch_bootstrap(&IanaRegistryType::RdapObjectTags, client, &store, |_reg| {
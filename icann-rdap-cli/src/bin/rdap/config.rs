@@ -0,0 +1,253 @@
+use std::{collections::HashMap, ffi::OsStr, fs, io::ErrorKind};
+
+use {
+    clap::{Arg, Command, Subcommand},
+    icann_rdap_cli::dirs,
+};
+
+use crate::error::RdapCliError;
+
+/// Commands for managing the configuration file.
+#[derive(Subcommand, Debug)]
+pub(crate) enum ConfigCommand {
+    /// Show the effective configuration.
+    ///
+    /// Shows the value of each configuration variable and whether it
+    /// comes from the environment, the configuration file, or the default.
+    Show,
+
+    /// Set a variable in the configuration file.
+    Set {
+        /// Name of the variable, such as RDAP_OUTPUT.
+        name: String,
+
+        /// Value of the variable.
+        value: String,
+    },
+
+    /// Unset a variable in the configuration file.
+    Unset {
+        /// Name of the variable, such as RDAP_OUTPUT.
+        name: String,
+    },
+
+    /// Validate the variables in the configuration file.
+    Validate,
+}
+
+pub(crate) fn do_config(cmd: &Command, config_cmd: &ConfigCommand) -> Result<(), RdapCliError> {
+    match config_cmd {
+        ConfigCommand::Show => show(cmd),
+        ConfigCommand::Set { name, value } => {
+            if is_rdap_var(name) {
+                validate_value(find_arg(cmd, name)?, name, value)?;
+            }
+            let contents = read_config()?;
+            fs::write(dirs::config_path(), set_var(&contents, name, value))?;
+            Ok(())
+        }
+        ConfigCommand::Unset { name } => {
+            if is_rdap_var(name) {
+                find_arg(cmd, name)?;
+            }
+            let contents = read_config()?;
+            fs::write(dirs::config_path(), unset_var(&contents, name))?;
+            Ok(())
+        }
+        ConfigCommand::Validate => {
+            let mut errors = 0;
+            for (name, value) in config_vars(&read_config()?)
+                .into_iter()
+                .filter(|(name, _)| is_rdap_var(name))
+            {
+                let result =
+                    find_arg(cmd, &name).and_then(|arg| validate_value(arg, &name, &value));
+                if let Err(e) = result {
+                    println!("{e}");
+                    errors += 1;
+                }
+            }
+            if errors != 0 {
+                return Err(RdapCliError::InvalidConfig(format!(
+                    "{errors} invalid variables in {}",
+                    dirs::config_path().display()
+                )));
+            }
+            println!("{} is valid.", dirs::config_path().display());
+            Ok(())
+        }
+    }
+}
+
+fn show(cmd: &Command) -> Result<(), RdapCliError> {
+    let file_vars = config_vars(&read_config()?)
+        .into_iter()
+        .collect::<HashMap<String, String>>();
+    for arg in cmd.get_arguments() {
+        let Some(name) = arg.get_env().and_then(|name| name.to_str()) else {
+            continue;
+        };
+        let (value, source) = match (std::env::var(name).ok(), file_vars.get(name)) {
+            (Some(env), Some(file)) if env == *file => (Some(env), "config file"),
+            (Some(env), _) => (Some(env), "environment"),
+            (None, Some(file)) => (Some(file.clone()), "config file"),
+            (None, None) => (
+                arg.get_default_values()
+                    .first()
+                    .map(|v| v.to_string_lossy().to_string()),
+                "default",
+            ),
+        };
+        let value = match value {
+            Some(_) if arg.is_hide_env_values_set() => "********".to_string(),
+            Some(value) => value,
+            None => String::default(),
+        };
+        println!("{name}={value} ({source})");
+    }
+    Ok(())
+}
+
+/// Reads the configuration file, or the template if it does not exist.
+pub(crate) fn read_config() -> Result<String, RdapCliError> {
+    match fs::read_to_string(dirs::config_path()) {
+        Ok(contents) => Ok(contents),
+        Err(e) if e.kind() == ErrorKind::NotFound => Ok(dirs::EXAMPLE_CONFIG.to_string()),
+        Err(e) => Err(e.into()),
+    }
+}
+
+/// Returns true if the variable is one of the variables of this command.
+///
+/// The configuration file is shared with other commands such as `rdap-test`,
+/// and may also set variables such as `HTTPS_PROXY`.
+fn is_rdap_var(name: &str) -> bool {
+    name.starts_with("RDAP_") && !name.starts_with("RDAP_TEST_")
+}
+
+pub(crate) fn find_arg<'a>(cmd: &'a Command, name: &str) -> Result<&'a Arg, RdapCliError> {
+    cmd.get_arguments()
+        .find(|arg| arg.get_env() == Some(OsStr::new(name)))
+        .ok_or_else(|| RdapCliError::InvalidConfig(format!("{name} is not a known variable")))
+}
+
+pub(crate) fn validate_value(arg: &Arg, name: &str, value: &str) -> Result<(), RdapCliError> {
+    let possible_values = arg.get_possible_values();
+    if !possible_values.is_empty() {
+        if possible_values.iter().any(|p| p.matches(value, false)) {
+            return Ok(());
+        }
+        let possible_values = possible_values
+            .iter()
+            .map(|p| p.get_name())
+            .collect::<Vec<&str>>()
+            .join(", ");
+        return Err(RdapCliError::InvalidConfig(format!(
+            "{name}={value} is invalid, valid values are {possible_values}"
+        )));
+    }
+    Command::new("config")
+        .no_binary_name(true)
+        .arg(Arg::new("value").value_parser(arg.get_value_parser().clone()))
+        .try_get_matches_from([value])
+        .map(|_| ())
+        .map_err(|_| RdapCliError::InvalidConfig(format!("{name}={value} is invalid")))
+}
+
+/// Gets the variables set in the contents of a configuration file.
+pub(crate) fn config_vars(contents: &str) -> Vec<(String, String)> {
+    contents
+        .lines()
+        .map(|line| line.trim())
+        .filter(|line| !line.starts_with('#'))
+        .filter_map(|line| line.split_once('='))
+        .map(|(name, value)| {
+            (
+                name.trim().to_string(),
+                value.trim().trim_matches('"').to_string(),
+            )
+        })
+        .collect()
+}
+
+/// Sets a variable in the contents of a configuration file.
+///
+/// If the variable is set, it is replaced. If it is commented out, it is
+/// uncommented. Otherwise it is appended.
+pub(crate) fn set_var(contents: &str, name: &str, value: &str) -> String {
+    let prefix = format!("{name}=");
+    let commented = format!("#{name}=");
+    let new_line = format!("{name}={value}");
+    let mut lines = contents.lines().map(str::to_string).collect::<Vec<_>>();
+    if let Some(i) = lines.iter().position(|l| l.trim().starts_with(&prefix)) {
+        lines[i] = new_line;
+    } else if let Some(i) = lines.iter().position(|l| l.trim().starts_with(&commented)) {
+        lines[i] = new_line;
+    } else {
+        lines.push(new_line);
+    }
+    lines.join("\n") + "\n"
+}
+
+/// Unsets a variable in the contents of a configuration file by commenting it out.
+fn unset_var(contents: &str, name: &str) -> String {
+    let prefix = format!("{name}=");
+    contents
+        .lines()
+        .map(|l| {
+            if l.trim().starts_with(&prefix) {
+                format!("#{}", l.trim())
+            } else {
+                l.to_string()
+            }
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+        + "\n"
+}
+
+#[cfg(test)]
+#[allow(non_snake_case)]
+mod tests {
+    use super::{config_vars, set_var, unset_var};
+
+    #[test]
+    fn GIVEN_commented_var_WHEN_set_var_THEN_uncommented() {
+        // GIVEN
+        let contents = "# Output.\n#RDAP_OUTPUT=auto\n";
+
+        // WHEN
+        let actual = set_var(contents, "RDAP_OUTPUT", "json");
+
+        // THEN
+        assert_eq!(actual, "# Output.\nRDAP_OUTPUT=json\n");
+    }
+
+    #[test]
+    fn GIVEN_missing_var_WHEN_set_var_THEN_appended() {
+        // GIVEN
+        let contents = "# Output.\n";
+
+        // WHEN
+        let actual = set_var(contents, "RDAP_OUTPUT", "json");
+
+        // THEN
+        assert_eq!(actual, "# Output.\nRDAP_OUTPUT=json\n");
+    }
+
+    #[test]
+    fn GIVEN_set_var_WHEN_unset_var_THEN_commented() {
+        // GIVEN
+        let contents = "RDAP_OUTPUT=json\nRDAP_LOG=info\n";
+
+        // WHEN
+        let actual = unset_var(contents, "RDAP_OUTPUT");
+
+        // THEN
+        assert_eq!(actual, "#RDAP_OUTPUT=json\nRDAP_LOG=info\n");
+        assert_eq!(
+            config_vars(&actual),
+            vec![("RDAP_LOG".to_string(), "info".to_string())]
+        );
+    }
+}
@@ -0,0 +1,84 @@
+//! Defanging of the indicators in text output.
+//!
+//! Defanged URLs, domain names and IP addresses cannot be followed or resolved
+//! by mistake when pasted into tickets and reports, such as `hxxps://example[.]com/`
+//! and `192[.]0[.]2[.]1`.
+
+use std::{
+    net::{Ipv4Addr, Ipv6Addr},
+    sync::LazyLock,
+};
+
+use regex::{Captures, Regex};
+
+static URL_SCHEME_RE: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"(?i)\b(h)tt(ps?)://").unwrap());
+
+static IPV6_RE: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"(?i)[0-9a-f]*:[0-9a-f:]+").unwrap());
+
+static IPV4_RE: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"\b\d{1,3}\.\d{1,3}\.\d{1,3}\.\d{1,3}\b").unwrap());
+
+/// Names with a last label that is alphabetic or an IDN A-label, so that numbers are not matched.
+static DOMAIN_RE: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"(?i)\b(?:[a-z0-9](?:[a-z0-9-]*[a-z0-9])?\.)+(?:[a-z]{2,63}|xn--[a-z0-9-]+)\b")
+        .unwrap()
+});
+
+/// Defangs the URLs, domain names and IP addresses in text.
+pub(crate) fn defang(text: &str) -> String {
+    let text = URL_SCHEME_RE.replace_all(text, "${1}xx${2}://");
+    let text = IPV6_RE.replace_all(&text, |caps: &Captures| {
+        let candidate = &caps[0];
+        if candidate.parse::<Ipv6Addr>().is_ok() {
+            candidate.replace(':', "[:]")
+        } else {
+            candidate.to_string()
+        }
+    });
+    let text = IPV4_RE.replace_all(&text, |caps: &Captures| {
+        let candidate = &caps[0];
+        if candidate.parse::<Ipv4Addr>().is_ok() {
+            candidate.replace('.', "[.]")
+        } else {
+            candidate.to_string()
+        }
+    });
+    DOMAIN_RE
+        .replace_all(&text, |caps: &Captures| caps[0].replace('.', "[.]"))
+        .into_owned()
+}
+
+#[cfg(test)]
+#[allow(non_snake_case)]
+mod tests {
+    use rstest::rstest;
+
+    use super::defang;
+
+    #[rstest]
+    #[case(
+        "https://rdap.example.com/domain/foo.example",
+        "hxxps://rdap[.]example[.]com/domain/foo[.]example"
+    )]
+    #[case("http://example.net", "hxxp://example[.]net")]
+    #[case("Handle: foo.example", "Handle: foo[.]example")]
+    #[case("email joe@xn--bcher-kva.example", "email joe@xn--bcher-kva[.]example")]
+    #[case("192.0.2.0/24", "192[.]0[.]2[.]0/24")]
+    #[case("2001:db8::1", "2001[:]db8[:][:]1")]
+    #[case("start 2001:db8:: end", "start 2001[:]db8[:][:] end")]
+    #[case("2024-01-07T10:11:12Z", "2024-01-07T10:11:12Z")]
+    #[case("version 1.0", "version 1.0")]
+    #[case("999.1.1.1", "999.1.1.1")]
+    #[case("Note: see e.g. this", "Note: see e.g. this")]
+    fn GIVEN_text_WHEN_defang_THEN_indicators_defanged(#[case] text: &str, #[case] expected: &str) {
+        // GIVEN text
+
+        // WHEN
+        let actual = defang(text);
+
+        // THEN
+        assert_eq!(actual, expected);
+    }
+}
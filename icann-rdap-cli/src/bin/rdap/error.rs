@@ -3,6 +3,7 @@ use std::process::{ExitCode, Termination};
 use {
     icann_rdap_client::{iana::IanaResponseError, RdapClientError},
     minus::MinusError,
+    serde_json::{json, Value},
     thiserror::Error,
     tracing::error,
 };
@@ -35,6 +36,24 @@ pub enum RdapCliError {
     NoRegistrarFound,
     #[error("No registry found")]
     NoRegistryFound,
+    #[error("No network matches the query")]
+    NoNetworkMatch,
+    #[error("Query is bogon space with no RDAP server: {0}")]
+    BogonQuery(String),
+    #[error("{0} queries of the batch failed")]
+    BatchQueriesFailed(usize),
+    #[error("Cache encryption requires a passphrase")]
+    NoCachePassphrase,
+    #[error("Invalid configuration: {0}")]
+    InvalidConfig(String),
+    #[error("Invalid check policy: {0}")]
+    InvalidCheckPolicy(String),
+    #[error("Invalid response filter: {0}")]
+    InvalidResponseFilter(String),
+    #[error("Unable to determine the latest version")]
+    UpdateCheckFailed,
+    #[error("No check has the code {0}")]
+    UnknownCheck(u32),
 }
 
 impl RdapCliError {
@@ -49,6 +68,7 @@ impl RdapCliError {
 
             // I/O Errors
             Self::IoError(_) => 40,
+            Self::UpdateCheckFailed => 44,
 
             // RDAP Errors
             Self::Json(_) => 100,
@@ -57,46 +77,92 @@ impl RdapCliError {
             Self::BootstrapNotFound => 103,
             Self::NoRegistrarFound => 104,
             Self::NoRegistryFound => 105,
+            Self::NoNetworkMatch => 106,
+            Self::BogonQuery(_) => 107,
+            Self::BatchQueriesFailed(_) => 108,
 
             // User Errors
             Self::UnknownOutputType => 200,
             Self::ErrorOnChecks => 201,
+            Self::NoCachePassphrase => 205,
+            Self::InvalidConfig(_) => 206,
+            Self::InvalidCheckPolicy(_) => 207,
+            Self::InvalidResponseFilter(_) => 208,
+            Self::UnknownCheck(_) => 209,
 
             // RDAP Client Errrors
             Self::RdapClient(e) => match e {
-                // I/O Errors
+                RdapClientError::Client(ce)
+                    if ce.is_builder() && ce.url().is_some_and(|url| url.scheme() == "http") =>
+                {
+                    202
+                }
+                _ => e.code(),
+            },
+        }
+    }
+
+    /// A stable name for the kind of error, used in structured error output.
+    pub(crate) fn kind(&self) -> &'static str {
+        match self {
+            Self::Success => "success",
+            Self::Termimad(_) => "terminal_rendering",
+            Self::Minus(_) => "pager",
+            Self::IoError(_) => "io",
+            Self::UpdateCheckFailed => "update_check_failed",
+            Self::Json(_) => "json",
+            Self::Iana(_) => "iana_response",
+            Self::InvalidBootstrap => "invalid_bootstrap",
+            Self::BootstrapNotFound => "bootstrap_not_found",
+            Self::NoRegistrarFound => "no_registrar_found",
+            Self::NoRegistryFound => "no_registry_found",
+            Self::NoNetworkMatch => "no_network_match",
+            Self::BogonQuery(_) => "bogon_query",
+            Self::BatchQueriesFailed(_) => "batch_queries_failed",
+            Self::UnknownOutputType => "unknown_output_type",
+            Self::ErrorOnChecks => "error_on_checks",
+            Self::NoCachePassphrase => "no_cache_passphrase",
+            Self::InvalidConfig(_) => "invalid_config",
+            Self::InvalidCheckPolicy(_) => "invalid_check_policy",
+            Self::InvalidResponseFilter(_) => "invalid_response_filter",
+            Self::UnknownCheck(_) => "unknown_check",
+            Self::RdapClient(e) => client_error_kind(e),
+        }
+    }
+
+    /// The error as JSON for structured error output.
+    ///
+    /// The code is the exit code of the program.
+    pub(crate) fn to_json(&self) -> Value {
+        let mut error = json!({
+            "code": self.exit_code(),
+            "kind": self.kind(),
+            "message": self.to_string(),
+        });
+        if let Self::RdapClient(e) = self {
+            error["phase"] = json!(e.phase().to_string());
+            error["retryable"] = json!(e.is_retryable());
+            if let Some(url) = e.url() {
+                error["url"] = json!(url);
+            }
+            let source = match e {
+                RdapClientError::SharedRequest(shared) => shared.as_ref(),
+                _ => e,
+            };
+            match source {
                 RdapClientError::Client(ce) => {
-                    if ce.is_builder() {
-                        match ce.url() {
-                            Some(url) if url.scheme() == "http" => 202,
-                            _ => 42,
-                        }
-                    } else {
-                        42
+                    if let Some(status) = ce.status() {
+                        error["httpStatus"] = json!(status.as_u16());
                     }
                 }
-                RdapClientError::IoError(_) => 43,
-
-                // RDAP Server Errors
-                RdapClientError::Response(_) => 60,
-                RdapClientError::ParsingError(_) => 62,
-                RdapClientError::Json(_) => 63,
-
-                // Bootstrap Errors
-                RdapClientError::BootstrapUnavailable => 70,
-                RdapClientError::BootstrapError(_) => 71,
-                RdapClientError::IanaResponse(_) => 72,
-
-                // User Errors
-                RdapClientError::InvalidQueryValue => 202,
-                RdapClientError::AmbiquousQueryType => 203,
-                RdapClientError::DomainNameError(_) => 204,
-
-                // Internal Errors
-                RdapClientError::Poison => 250,
-                // _ => 255,
-            },
+                RdapClientError::RetriesExhausted(re) => {
+                    error["httpStatus"] = json!(re.http_data.status_code());
+                    error["retries"] = json!(re.http_data.retries().unwrap_or_default());
+                }
+                _ => {}
+            }
         }
+        json!({ "error": error })
     }
 }
 
@@ -106,3 +172,132 @@ impl Termination for RdapCliError {
         ExitCode::from(exit_code)
     }
 }
+
+/// A stable name for the kind of a client error, which for a shared request
+/// is the kind of the error shared.
+fn client_error_kind(e: &RdapClientError) -> &'static str {
+    match e {
+        RdapClientError::Client(ce) if ce.is_builder() => "http_client",
+        RdapClientError::Client(_) => "http",
+        RdapClientError::RetriesExhausted(_) => "http_retries_exhausted",
+        RdapClientError::SpkiPinMismatch(_) => "spki_pin_mismatch",
+        RdapClientError::IoError(_) => "io",
+        RdapClientError::Response(_) => "response",
+        RdapClientError::InvalidMediaType { .. } => "invalid_media_type",
+        RdapClientError::ParsingError(_) => "response_parsing",
+        RdapClientError::Json(_) => "response_json",
+        RdapClientError::UnexpectedResponse { .. } => "unexpected_response",
+        RdapClientError::ResponseTooLarge(_) => "response_too_large",
+        RdapClientError::BootstrapUnavailable => "bootstrap_unavailable",
+        RdapClientError::BootstrapError(_) => "bootstrap",
+        RdapClientError::IanaResponse(_) => "iana_response",
+        RdapClientError::InvalidQueryValue => "invalid_query_value",
+        RdapClientError::AmbiquousQueryType => "ambiguous_query_type",
+        RdapClientError::DomainNameError(_) => "domain_name",
+        RdapClientError::Poison => "poison",
+        RdapClientError::SharedRequest(e) => client_error_kind(e),
+    }
+}
+
+#[cfg(test)]
+#[allow(non_snake_case)]
+mod tests {
+    use std::collections::HashMap;
+
+    use {
+        icann_rdap_client::{
+            http::{ResponseTooLargeError, SpkiPinMismatchError},
+            RdapClientError,
+        },
+        serde_json::json,
+    };
+
+    use super::RdapCliError;
+
+    #[test]
+    fn GIVEN_error_on_checks_WHEN_to_json_THEN_code_and_kind() {
+        // GIVEN
+        let error = RdapCliError::ErrorOnChecks;
+
+        // WHEN
+        let actual = error.to_json();
+
+        // THEN
+        assert_eq!(
+            actual,
+            json!({
+                "error": {
+                    "code": 201,
+                    "kind": "error_on_checks",
+                    "message": "RDAP response failed checks."
+                }
+            })
+        );
+    }
+
+    #[test]
+    fn GIVEN_each_error_WHEN_exit_code_THEN_codes_distinct() {
+        // GIVEN
+        let errors = vec![
+            RdapCliError::Success,
+            RdapCliError::IoError(std::io::Error::other("io")),
+            RdapCliError::UnknownOutputType,
+            RdapCliError::ErrorOnChecks,
+            RdapCliError::Json(serde_json::from_str::<u8>("").unwrap_err()),
+            RdapCliError::InvalidBootstrap,
+            RdapCliError::BootstrapNotFound,
+            RdapCliError::NoRegistrarFound,
+            RdapCliError::NoRegistryFound,
+            RdapCliError::NoNetworkMatch,
+            RdapCliError::BogonQuery("192.0.2.1".to_string()),
+            RdapCliError::BatchQueriesFailed(1),
+            RdapCliError::NoCachePassphrase,
+            RdapCliError::InvalidConfig("config".to_string()),
+            RdapCliError::InvalidCheckPolicy("policy".to_string()),
+            RdapCliError::InvalidResponseFilter("filter".to_string()),
+            RdapCliError::UpdateCheckFailed,
+            RdapCliError::UnknownCheck(0),
+            RdapClientError::IoError(std::io::Error::other("io")).into(),
+            RdapClientError::SpkiPinMismatch(SpkiPinMismatchError {
+                url: "https://rdap.example".to_string(),
+                actual: None,
+            })
+            .into(),
+            RdapClientError::InvalidMediaType {
+                media_type: None,
+                url: None,
+            }
+            .into(),
+            RdapClientError::Json(serde_json::from_str::<u8>("").unwrap_err()).into(),
+            RdapClientError::UnexpectedResponse {
+                expected: "domain".to_string(),
+                actual: "entity".to_string(),
+            }
+            .into(),
+            RdapClientError::ResponseTooLarge(ResponseTooLargeError {
+                url: "https://rdap.example".to_string(),
+                max_bytes: 1,
+            })
+            .into(),
+            RdapClientError::BootstrapUnavailable.into(),
+            RdapClientError::InvalidQueryValue.into(),
+            RdapClientError::AmbiquousQueryType.into(),
+            RdapClientError::Poison.into(),
+        ];
+
+        // WHEN
+        let mut kinds_by_code = HashMap::new();
+        for error in &errors {
+            let previous = kinds_by_code.insert(error.exit_code(), error.kind());
+
+            // THEN
+            assert!(
+                previous.is_none(),
+                "{} and {} both exit with {}",
+                previous.unwrap_or_default(),
+                error.kind(),
+                error.exit_code()
+            );
+        }
+    }
+}
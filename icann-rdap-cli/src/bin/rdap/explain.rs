@@ -0,0 +1,53 @@
+use icann_rdap_common::check::{Check, CheckDescription};
+
+use crate::error::RdapCliError;
+
+/// Prints the description of the check with a code, as text or as JSON.
+pub(crate) fn explain_check(code: u32, json: bool) -> Result<(), RdapCliError> {
+    let description = Check::from_code(code)
+        .ok_or(RdapCliError::UnknownCheck(code))?
+        .describe();
+    if json {
+        println!("{}", serde_json::to_string_pretty(&description)?);
+    } else {
+        print!("{}", explain_text(&description));
+    }
+    Ok(())
+}
+
+fn explain_text(description: &CheckDescription) -> String {
+    format!(
+        "Check:    {:0>4} {}\nClass:    {}\nMessage:  {}\nCitation: {}\n",
+        description.code,
+        description.name,
+        description.class,
+        description.message,
+        description.citation,
+    )
+}
+
+#[cfg(test)]
+#[allow(non_snake_case)]
+mod tests {
+    use icann_rdap_common::check::Check;
+
+    use super::explain_text;
+
+    #[test]
+    fn GIVEN_check_WHEN_explain_text_THEN_code_name_class_message_and_citation() {
+        // GIVEN
+        let description = Check::IpAddressEndBeforeStart.describe();
+
+        // WHEN
+        let actual = explain_text(&description);
+
+        // THEN
+        assert_eq!(
+            actual,
+            "Check:    1302 ip_address_end_before_start\n\
+             Class:    StdWarn\n\
+             Message:  end IP address comes before start IP address\n\
+             Citation: RFC 9083 Section 5.4\n"
+        );
+    }
+}
@@ -0,0 +1,160 @@
+use std::{
+    collections::HashMap,
+    fs,
+    io::{BufRead, Write},
+};
+
+use {clap::Command, icann_rdap_cli::dirs};
+
+use crate::{
+    config::{config_vars, find_arg, read_config, set_var, validate_value},
+    error::RdapCliError,
+};
+
+/// A question asked when setting up the configuration file.
+struct Question {
+    name: &'static str,
+    prompt: &'static str,
+    validate: fn(&Command, &str, &str) -> Result<(), RdapCliError>,
+}
+
+const QUESTIONS: &[Question] = &[
+    Question {
+        name: "HTTPS_PROXY",
+        prompt: "Proxy URL for HTTPS connections, if any",
+        validate: validate_url,
+    },
+    Question {
+        name: "RDAP_OUTPUT",
+        prompt: "Preferred output (rendered-markdown, markdown, json, pretty-json, json-extra, checks-json, gtld-whois, whois, url, auto)",
+        validate: validate_rdap_var,
+    },
+    Question {
+        name: "RDAP_MAX_CACHE_AGE",
+        prompt: "Maximum age in seconds of cached responses",
+        validate: validate_rdap_var,
+    },
+    Question {
+        name: "RDAP_MAX_CACHE_SIZE",
+        prompt: "Maximum size in bytes of the cache, 0 for no limit",
+        validate: validate_rdap_var,
+    },
+    Question {
+        name: "RDAP_TEST_INTERVAL",
+        prompt: "Time between rdap-test daemon runs, such as 30m or 1h",
+        validate: validate_duration,
+    },
+    Question {
+        name: "RDAP_TEST_ALERT_WEBHOOK",
+        prompt: "URL to which rdap-test daemon alerts are posted, if any",
+        validate: validate_url,
+    },
+];
+
+pub(crate) fn do_init(cmd: &Command) -> Result<(), RdapCliError> {
+    let contents = read_config()?;
+    let stdin = std::io::stdin();
+    let contents = ask_questions(cmd, &mut stdin.lock(), &mut std::io::stdout(), contents)?;
+    fs::write(dirs::config_path(), contents)?;
+    println!("Configuration written to {}", dirs::config_path().display());
+    Ok(())
+}
+
+/// Asks the setup questions, returning the configuration file contents with the answers.
+///
+/// Empty answers keep the current value.
+fn ask_questions<R: BufRead, W: Write>(
+    cmd: &Command,
+    input: &mut R,
+    output: &mut W,
+    mut contents: String,
+) -> Result<String, RdapCliError> {
+    let current = config_vars(&contents)
+        .into_iter()
+        .collect::<HashMap<String, String>>();
+    writeln!(output, "Press enter to keep the value in brackets.")?;
+    for question in QUESTIONS {
+        loop {
+            match current.get(question.name) {
+                Some(value) => write!(output, "{} [{value}]: ", question.prompt)?,
+                None => write!(output, "{}: ", question.prompt)?,
+            }
+            output.flush()?;
+            let mut line = String::new();
+            if input.read_line(&mut line)? == 0 {
+                return Ok(contents);
+            }
+            let value = line.trim();
+            if value.is_empty() {
+                break;
+            }
+            match (question.validate)(cmd, question.name, value) {
+                Ok(()) => {
+                    contents = set_var(&contents, question.name, value);
+                    break;
+                }
+                Err(e) => writeln!(output, "{e}")?,
+            }
+        }
+    }
+    Ok(contents)
+}
+
+fn validate_rdap_var(cmd: &Command, name: &str, value: &str) -> Result<(), RdapCliError> {
+    validate_value(find_arg(cmd, name)?, name, value)
+}
+
+fn validate_url(_cmd: &Command, name: &str, value: &str) -> Result<(), RdapCliError> {
+    url::Url::parse(value)
+        .map(|_| ())
+        .map_err(|e| RdapCliError::InvalidConfig(format!("{name}={value} is invalid: {e}")))
+}
+
+fn validate_duration(_cmd: &Command, name: &str, value: &str) -> Result<(), RdapCliError> {
+    humantime::parse_duration(value)
+        .map(|_| ())
+        .map_err(|e| RdapCliError::InvalidConfig(format!("{name}={value} is invalid: {e}")))
+}
+
+#[cfg(test)]
+#[allow(non_snake_case)]
+mod tests {
+    use std::io::Cursor;
+
+    use clap::CommandFactory;
+
+    use crate::{config::config_vars, Cli};
+
+    use super::ask_questions;
+
+    #[test]
+    fn GIVEN_answers_WHEN_ask_questions_THEN_vars_set() {
+        // GIVEN
+        let mut input = Cursor::new("http://proxy.example:3128\nbogus\njson\n\n\n30m\n\n");
+        let mut output = vec![];
+
+        // WHEN
+        let actual = ask_questions(
+            &Cli::command(),
+            &mut input,
+            &mut output,
+            "#RDAP_OUTPUT=auto\n".to_string(),
+        )
+        .expect("asking questions");
+
+        // THEN
+        let vars = config_vars(&actual);
+        assert_eq!(
+            vars,
+            vec![
+                ("RDAP_OUTPUT".to_string(), "json".to_string()),
+                (
+                    "HTTPS_PROXY".to_string(),
+                    "http://proxy.example:3128".to_string()
+                ),
+                ("RDAP_TEST_INTERVAL".to_string(), "30m".to_string()),
+            ]
+        );
+        assert!(String::from_utf8_lossy(&output).contains("RDAP_OUTPUT=bogus is invalid"));
+    }
+}
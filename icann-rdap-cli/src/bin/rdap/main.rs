@@ -1,38 +1,74 @@
+#[cfg(feature = "replay")]
+use icann_rdap_client::http::Cassette;
 #[cfg(debug_assertions)]
 use tracing::warn;
 use {
+    batch::{BatchParams, ServerRateLimiter},
     bootstrap::BootstrapType,
     clap::builder::{styling::AnsiColor, Styles},
     error::RdapCliError,
-    icann_rdap_cli::dirs,
-    icann_rdap_client::http::{create_client, Client, ClientConfig},
-    icann_rdap_common::check::CheckClass,
+    icann_rdap_cli::{dirs, dirs::encrypt::CacheCipher},
+    icann_rdap_client::{
+        http::{create_client, Client, ClientConfig, ConnectTo, SpkiPin},
+        md::{AsnFormat, MdLinkStyle, MdTableStyle},
+        rdap::{ContentsCipher, FileResponseCache, NetworkMatch},
+    },
+    icann_rdap_common::{
+        check::{CheckClass, CheckPolicy, ProfileId},
+        response::{filter::ResponseFilter, RirSearchRelation},
+    },
     query::{InrBackupBootstrap, ProcessType, ProcessingParams, TldLookup},
-    std::{io::IsTerminal, str::FromStr},
+    std::{
+        io::IsTerminal,
+        path::{Path, PathBuf},
+        str::FromStr,
+        sync::{Arc, Mutex},
+    },
     tracing::{error, info},
     tracing_subscriber::filter::LevelFilter,
     write::{FmtWrite, PagerWrite},
 };
 
 use {
-    clap::{ArgGroup, Parser, ValueEnum},
+    clap::{ArgGroup, CommandFactory, Parser, Subcommand, ValueEnum},
+    config::{do_config, ConfigCommand},
     icann_rdap_client::rdap::QueryType,
     icann_rdap_common::VERSION,
+    init::do_init,
     query::OutputType,
+    termimad::crossterm::terminal,
     tokio::{join, task::spawn_blocking},
+    update::{auto_check_update, check_update},
 };
 
-use crate::query::do_query;
+use crate::{
+    batch::do_batch,
+    explain::explain_check,
+    query::{do_input, do_query},
+};
 
+pub mod batch;
+pub mod bogons;
 pub mod bootstrap;
+pub mod config;
+pub mod defang;
 pub mod error;
+pub mod explain;
+pub mod init;
 pub mod query;
+pub mod render;
 pub mod request;
+pub mod summary;
+pub mod update;
+pub mod vcf;
 pub mod write;
 
 const BEFORE_LONG_HELP: &str = include_str!("before_long_help.txt");
 const AFTER_LONG_HELP: &str = include_str!("after_long_help.txt");
 
+/// The width of wrapped text when not given and not output to a terminal.
+const DEFAULT_WRAP_WIDTH: usize = 80;
+
 struct CliStyles;
 
 impl CliStyles {
@@ -47,24 +83,34 @@ impl CliStyles {
 
 #[derive(Parser, Debug)]
 #[command(author, version = VERSION, about, long_about, styles = CliStyles::cli_styles())]
+#[command(args_conflicts_with_subcommands = true, subcommand_negates_reqs = true)]
 #[command(group(
             ArgGroup::new("input")
                 .required(true)
-                .args(["query_value", "server_help", "reset"]),
+                .args(["query_value", "input_file", "batch_file", "server_help", "reset", "check_update", "explain_check"]),
         ))]
 #[command(group(
             ArgGroup::new("base_specify")
                 .args(["base", "base_url"]),
         ))]
+#[command(group(
+            ArgGroup::new("relation")
+                .args(["up", "down", "top", "bottom"]),
+        ))]
 #[command(before_long_help(BEFORE_LONG_HELP))]
 #[command(after_long_help(AFTER_LONG_HELP))]
 /// This program queries network registry information from domain name registries and registrars
 /// and Internet number registries (i.e. Regional Internet Registries) using the Registry Data
 /// Access Protocol (RDAP).
 struct Cli {
+    #[command(subcommand)]
+    command: Option<Commands>,
+
     /// Value to be queried in RDAP.
     ///
     /// This is the value to query. For example, a domain name or IP address.
+    /// A value that is the name of a subcommand, such as "config" or "init", is
+    /// taken as the subcommand unless it follows "--", as in "rdap -- init".
     #[arg()]
     query_value: Option<String>,
 
@@ -82,12 +128,92 @@ struct Cli {
     )]
     query_type: Option<QtypeArg>,
 
+    /// Check RDAP JSON in a file instead of querying a server.
+    ///
+    /// The RDAP is read from the given file, or from stdin if the file is "-",
+    /// and is checked and output as with a query. No requests are made, so this
+    /// can be used to check RDAP before it is deployed to a server.
+    #[arg(long = "input", value_name = "FILE", conflicts_with = "query_type")]
+    input_file: Option<PathBuf>,
+
+    /// Query each value in a file.
+    ///
+    /// The values, one per line, are read from the given file, or from stdin if
+    /// the file is "-", and the type of each query is inferred, as with a query
+    /// value. Blank lines and lines starting with '#' are skipped. The queries
+    /// share the bootstrap registries and cache, and are made at the same time as
+    /// given by --batch-concurrency. The results are output in the order of the
+    /// queries as NDJSON, with the query in each line, unless another output
+    /// format is given or they are written to files with --batch-output-dir.
+    #[arg(long = "batch", value_name = "FILE", conflicts_with = "query_type")]
+    batch_file: Option<PathBuf>,
+
+    /// Number of queries of a batch made at the same time.
+    #[arg(
+        long,
+        required = false,
+        env = "RDAP_BATCH_CONCURRENCY",
+        default_value = "4",
+        value_parser = clap::value_parser!(u16).range(1..),
+    )]
+    batch_concurrency: u16,
+
+    /// Maximum requests per second to each server in a batch.
+    ///
+    /// Requests of the queries of a batch to the same server are spaced
+    /// so as to not exceed this rate. Responses from the cache are not limited.
+    /// A rate of 0 does not limit requests.
+    #[arg(
+        long,
+        required = false,
+        env = "RDAP_BATCH_SERVER_RATE",
+        default_value = "2"
+    )]
+    batch_server_rate: f64,
+
+    /// Directory of the results of a batch.
+    ///
+    /// When given, the results of each query of a batch are written to a file of
+    /// their own in this directory, named from the query with an extension of the
+    /// output format, and the error of a query that failed is written to a file
+    /// named from the query ending in '.error.json'.
+    #[arg(long, required = false, requires = "batch_file", value_name = "DIR")]
+    batch_output_dir: Option<PathBuf>,
+
     /// Get an RDAP server's help information.
     ///
     /// Ask for a server's help information.
     #[arg(short = 'S', long, conflicts_with = "query_type")]
     server_help: bool,
 
+    /// Get the next less specific network.
+    ///
+    /// The query value is an IP address or CIDR, and the network is found
+    /// with an RIR search (RFC 9910) for the "up" relation.
+    #[arg(long, requires = "query_value", conflicts_with = "query_type")]
+    up: bool,
+
+    /// Get the next more specific networks.
+    ///
+    /// The query value is an IP address or CIDR, and the networks are found
+    /// with an RIR search (RFC 9910) for the "down" relation.
+    #[arg(long, requires = "query_value", conflicts_with = "query_type")]
+    down: bool,
+
+    /// Get the least specific network.
+    ///
+    /// The query value is an IP address or CIDR, and the network is found
+    /// with an RIR search (RFC 9910) for the "top" relation.
+    #[arg(long, requires = "query_value", conflicts_with = "query_type")]
+    top: bool,
+
+    /// Get the most specific networks.
+    ///
+    /// The query value is an IP address or CIDR, and the networks are found
+    /// with an RIR search (RFC 9910) for the "bottom" relation.
+    #[arg(long, requires = "query_value", conflicts_with = "query_type")]
+    bottom: bool,
+
     /// An RDAP base signifier.
     ///
     /// This option gets a base URL from the RDAP bootstrap registries maintained
@@ -131,6 +257,46 @@ struct Cli {
     )]
     inr_backup_bootstrap: InrBackupBootstrapArg,
 
+    /// Skip bogons.
+    ///
+    /// When given, IP address and CIDR queries for bogon address space, such as
+    /// private-use and documentation addresses, are not sent to any server as
+    /// bogon space has no RDAP server.
+    #[arg(long, required = false, env = "RDAP_SKIP_BOGONS")]
+    skip_bogons: bool,
+
+    /// Bogon list URL.
+    ///
+    /// The URL of a list of bogon prefixes, one prefix per line, such as those
+    /// published by Team Cymru. When given with 'skip-bogons', the prefixes of the
+    /// list are used in addition to the built-in bogon prefixes. The list is
+    /// fetched no more than once a day.
+    #[arg(long, required = false, env = "RDAP_BOGONS_URL")]
+    bogons_url: Option<String>,
+
+    /// Network match.
+    ///
+    /// Determines which network answers an IP address or CIDR query.
+    /// Servers return the most specific network containing the query.
+    /// Other networks are found by following the 'up' links of the
+    /// networks, and each network considered is logged.
+    #[arg(
+        long = "match",
+        required = false,
+        env = "RDAP_NETWORK_MATCH",
+        value_enum,
+        default_value_t = NetworkMatchArg::MostSpecific,
+    )]
+    network_match: NetworkMatchArg,
+
+    /// Maximum search result pages.
+    ///
+    /// Specifies the maximum number of pages of search results to fetch
+    /// from servers supporting the paging extension (RFC 8977). The results
+    /// of the pages are merged. A value of 0 fetches all pages.
+    #[arg(long, required = false, env = "RDAP_MAX_PAGES", default_value = "1")]
+    max_pages: usize,
+
     /// Output format.
     ///
     /// This option determines the format of the result.
@@ -144,6 +310,96 @@ struct Cli {
     )]
     output_type: OtypeArg,
 
+    /// Width of wrapped text.
+    ///
+    /// The width to which lines are wrapped by the 'text-wrapped' output format.
+    /// If not given, it is the width of the terminal, or 80 when the output is
+    /// not to a terminal.
+    #[arg(
+        long,
+        required = false,
+        env = "RDAP_WIDTH",
+        value_parser = clap::value_parser!(u16).range(20..),
+    )]
+    width: Option<u16>,
+
+    /// vCard directory.
+    ///
+    /// When given with the 'vcf' output format, the contact of each entity is
+    /// written to its own file in this directory, named from the handle of the
+    /// entity, instead of all the contacts being output together.
+    #[arg(long, required = false, env = "RDAP_VCF_DIR")]
+    vcf_dir: Option<PathBuf>,
+
+    /// AS number format.
+    ///
+    /// Determines the notation of autonomous system numbers in
+    /// rendered output, as described in RFC 5396.
+    #[arg(
+        long,
+        required = false,
+        env = "RDAP_ASN_FORMAT",
+        value_enum,
+        default_value_t = AsnFormatArg::Asplain,
+    )]
+    asn_format: AsnFormatArg,
+
+    /// Markdown table style.
+    ///
+    /// Determines the tables of markdown output. 'terminal' tables are best
+    /// rendered in a terminal, 'gfm' tables are GitHub-flavored markdown tables
+    /// for wikis and documents, and 'plain-text' is aligned text without tables.
+    #[arg(
+        long,
+        required = false,
+        env = "RDAP_MD_TABLE_STYLE",
+        value_enum,
+        default_value_t = MdTableStyleArg::Terminal,
+    )]
+    md_table_style: MdTableStyleArg,
+
+    /// Markdown heading level.
+    ///
+    /// The level of the top headings of markdown output, so that the output
+    /// can be placed under the headings of another document.
+    #[arg(
+        long,
+        required = false,
+        env = "RDAP_MD_HEADING_LEVEL",
+        default_value = "1",
+        value_parser = clap::value_parser!(u8).range(1..=6),
+    )]
+    md_heading_level: u8,
+
+    /// Markdown link style.
+    ///
+    /// Determines how links are rendered in markdown output.
+    #[arg(
+        long,
+        required = false,
+        env = "RDAP_MD_LINK_STYLE",
+        value_enum,
+        default_value_t = MdLinkStyleArg::Plain,
+    )]
+    md_link_style: MdLinkStyleArg,
+
+    /// Defang indicators.
+    ///
+    /// When given, URLs, domain names and IP addresses in text and
+    /// markdown output are defanged, such as hxxps://example[.]com/
+    /// and 192[.]0[.]2[.]1, so that they are safe to paste into tickets
+    /// and reports. JSON output is not changed.
+    #[arg(long, required = false, env = "RDAP_DEFANG")]
+    defang: bool,
+
+    /// Quiet.
+    ///
+    /// When given, the notices of servers are not shown in markdown output
+    /// and no hint is given when a newer release is available, which suits
+    /// the scanning of the output of many queries.
+    #[arg(short = 'q', long, required = false, env = "RDAP_QUIET")]
+    quiet: bool,
+
     /// Check type.
     ///
     /// Specifies the type of checks to conduct on the RDAP
@@ -154,6 +410,42 @@ struct Cli {
     #[arg(short = 'C', long, required = false, value_enum)]
     check_type: Vec<CheckTypeArg>,
 
+    /// Check policy.
+    ///
+    /// A JSON file that reclassifies or silences RDAP checks,
+    /// such as {"reclassify": {"unknown_extention": "std_error"},
+    /// "silence": ["ldh_name_documentation"]}. The policy is
+    /// applied before checks are output and before determining
+    /// if there are errors on checks.
+    #[arg(long, required = false, env = "RDAP_CHECK_POLICY")]
+    check_policy: Option<PathBuf>,
+
+    /// Response filter.
+    ///
+    /// A JSON file that removes or masks members of the RDAP
+    /// objects in responses before they are checked and output,
+    /// such as {"remove": ["vcardArray"]} to strip all vCards or
+    /// {"keep": ["status", "events"]} to keep only status and
+    /// events. "mask" and "mask_vcard" replace the text of
+    /// members and vCard properties with "mask_with".
+    #[arg(long, required = false, env = "RDAP_FILTER")]
+    filter: Option<PathBuf>,
+
+    /// Check profile.
+    ///
+    /// The profiles to check the RDAP responses against, separated
+    /// by commas, such as "gtld-registrar". By default, these are the
+    /// profiles declared in the rdapConformance of the responses.
+    /// "none" checks against no profile.
+    #[arg(
+        long,
+        required = false,
+        env = "RDAP_CHECK_PROFILE",
+        value_enum,
+        value_delimiter = ','
+    )]
+    check_profile: Vec<CheckProfileArg>,
+
     /// Error if RDAP checks found.
     ///
     /// The program will log error messages for non-info
@@ -162,6 +454,23 @@ struct Cli {
     #[arg(long, env = "RDAP_ERROR_ON_CHECK")]
     error_on_checks: bool,
 
+    /// Error if RDAP checks of these types are found.
+    ///
+    /// The program will log error messages for the checks
+    /// of these types found in the RDAP response(s) and exit
+    /// with a non-zero status, regardless of the check types
+    /// being output. Types are separated by commas, such as
+    /// "std-error,icann-error". This takes precedence over
+    /// --error-on-checks.
+    #[arg(
+        long,
+        required = false,
+        env = "RDAP_ERROR_ON",
+        value_enum,
+        value_delimiter = ','
+    )]
+    error_on: Vec<CheckTypeArg>,
+
     /// Process Type
     ///
     /// Specifies a process for handling the data.
@@ -202,6 +511,34 @@ struct Cli {
     )]
     log_level: LogLevel,
 
+    /// Error format.
+    ///
+    /// Determines how errors are written to stderr. When json is given,
+    /// errors are written as a JSON object with a stable code and kind.
+    /// The code is the same as the exit status of the program.
+    /// Log messages are still written unless the log level is off.
+    #[arg(
+        long,
+        required = false,
+        env = "RDAP_ERROR_FORMAT",
+        value_enum,
+        default_value_t = ErrorFormatArg::Text
+    )]
+    error_format: ErrorFormatArg,
+
+    /// Max staleness of bootstrap registries.
+    ///
+    /// Specifies the number of seconds past their expiration that the IANA
+    /// bootstrap registries are used if they cannot be refreshed, such as
+    /// when IANA cannot be reached.
+    #[arg(
+        long,
+        required = false,
+        env = "RDAP_BOOTSTRAP_MAX_STALE",
+        default_value = "0"
+    )]
+    bootstrap_max_stale: u32,
+
     /// Do not use the cache.
     ///
     /// When given, the cache will be neither read from nor written to.
@@ -219,6 +556,39 @@ struct Cli {
     )]
     max_cache_age: u32,
 
+    /// Max cache size.
+    ///
+    /// Specifies the maximum size in bytes of the RDAP cache. When the
+    /// cache grows larger than this, the least recently used items are
+    /// removed. A value of 0 means the size of the cache is not limited.
+    #[arg(
+        long,
+        required = false,
+        env = "RDAP_MAX_CACHE_SIZE",
+        default_value = "104857600"
+    )]
+    max_cache_size: u64,
+
+    /// Encrypt cache.
+    ///
+    /// When given, RDAP responses are encrypted in the cache with a key
+    /// derived from the passphrase given by 'cache-passphrase', and
+    /// encrypted responses are decrypted when read from the cache.
+    #[arg(long, required = false, env = "RDAP_ENCRYPT_CACHE")]
+    encrypt_cache: bool,
+
+    /// Cache passphrase.
+    ///
+    /// The passphrase used to derive the key for encrypting the cache.
+    /// This is best given with the RDAP_CACHE_PASSPHRASE environment variable.
+    #[arg(
+        long,
+        required = false,
+        env = "RDAP_CACHE_PASSPHRASE",
+        hide_env_values = true
+    )]
+    cache_passphrase: Option<String>,
+
     /// Allow HTTP connections.
     ///
     /// When given, allows connections to RDAP servers using HTTP.
@@ -226,6 +596,15 @@ struct Cli {
     #[arg(short = 'T', long, required = false, env = "RDAP_ALLOW_HTTP")]
     allow_http: bool,
 
+    /// Require the RDAP media type.
+    ///
+    /// When given, responses must have a content type of
+    /// application/rdap+json. Otherwise, responses with a
+    /// content type of application/json are also accepted,
+    /// and are noted by the RDAP checks.
+    #[arg(long, required = false, env = "RDAP_STRICT_MEDIA_TYPES")]
+    strict_media_types: bool,
+
     /// Allow invalid host names.
     ///
     /// When given, allows HTTPS connections to servers where the host name does
@@ -250,6 +629,68 @@ struct Cli {
     )]
     allow_invalid_certificates: bool,
 
+    /// Connect to an address instead of the one from DNS.
+    ///
+    /// Given as HOST:PORT:ADDR, such as rdap.example.com:443:192.0.2.1,
+    /// connections to HOST are made to ADDR instead of the address from DNS.
+    /// This is useful for testing servers before DNS changes.
+    /// This argument may be given multiple times.
+    #[arg(long, required = false, value_name = "HOST:PORT:ADDR")]
+    connect_to: Vec<ConnectTo>,
+
+    /// Pin the public key of servers.
+    ///
+    /// Given as BASE_URL=PIN, where PIN is the base64 of the SHA-256 hash of
+    /// the public key (SubjectPublicKeyInfo) of the certificate of the server,
+    /// as with the --pinnedpubkey option of curl. Responses from URLs starting
+    /// with BASE_URL fail unless the server has one of the pinned keys.
+    /// This argument may be given multiple times, or as a comma separated list.
+    #[arg(
+        long,
+        required = false,
+        value_name = "BASE_URL=PIN",
+        value_delimiter = ',',
+        env = "RDAP_SPKI_PIN"
+    )]
+    spki_pin: Vec<SpkiPin>,
+
+    /// Connect to a unix domain socket.
+    ///
+    /// When given, connections are made to the unix domain socket at this path
+    /// instead of over TCP, such as to query a server running locally.
+    #[arg(long, required = false, env = "RDAP_UNIX_SOCKET")]
+    unix_socket: Option<String>,
+
+    /// Record HTTP interactions.
+    ///
+    /// The HTTP requests and the responses to them are written to this file,
+    /// which can then be given to --replay.
+    #[cfg(feature = "replay")]
+    #[arg(long, required = false, env = "RDAP_RECORD", conflicts_with = "replay")]
+    record: Option<PathBuf>,
+
+    /// Replay HTTP interactions.
+    ///
+    /// Requests are answered with the responses in this file, recorded with
+    /// --record, instead of being sent to the servers.
+    #[cfg(feature = "replay")]
+    #[arg(long, required = false, env = "RDAP_REPLAY")]
+    replay: Option<PathBuf>,
+
+    /// Negotiate RDAP extensions with the RDAP-X media type.
+    ///
+    /// When given, the server is asked for only these RDAP extensions,
+    /// such as rdap_level_0,redacted, using the application/rdap-x+json
+    /// media type. Servers not supporting RDAP-X ignore this.
+    #[arg(
+        long,
+        required = false,
+        value_delimiter = ',',
+        value_name = "EXTENSIONS",
+        env = "RDAP_X_EXTENSIONS"
+    )]
+    rdap_x_extensions: Option<Vec<String>>,
+
     /// Set the query timeout.
     ///
     /// This values specifies, in seconds, the total time to connect and read all
@@ -262,11 +703,19 @@ struct Cli {
     )]
     timeout_secs: u64,
 
+    /// Maximum response size.
+    ///
+    /// The most bytes of a response, both as sent and once decompressed,
+    /// which protects against servers sending decompression bombs.
+    /// If not given, it is 16 MiB.
+    #[arg(long, required = false, env = "RDAP_MAX_RESPONSE_BYTES")]
+    max_response_bytes: Option<u64>,
+
     /// Maximum retry wait time.
     ///
     /// Sets the maximum number of seconds to wait before retrying a query when
-    /// a server has sent an HTTP 429 status code with a retry-after value.
-    /// That is, the value to used is no greater than this setting.
+    /// a server has sent an HTTP 429 or 503 status code, with or without a
+    /// retry-after value. That is, the value to used is no greater than this setting.
     #[arg(
         long,
         required = false,
@@ -277,9 +726,10 @@ struct Cli {
 
     /// Default retry wait time.
     ///
-    /// Sets the number of seconds to wait before retrying a query when
-    /// a server has sent an HTTP 429 status code without a retry-after value
-    /// or when the retry-after value does not make sense.
+    /// Sets the number of seconds to wait before the first retry of a query when
+    /// a server has sent an HTTP 429 or 503 status code without a retry-after value
+    /// or when the retry-after value does not make sense. The wait is doubled
+    /// for each retry after, less a random jitter of up to half.
     #[arg(
         long,
         required = false,
@@ -291,7 +741,8 @@ struct Cli {
     /// Maximum number of retries.
     ///
     /// This sets the maximum number of retries when a server signals too many
-    /// requests have been sent using an HTTP 429 status code.
+    /// requests have been sent using an HTTP 429 status code, or that it is
+    /// unavailable using an HTTP 503 status code.
     #[arg(long, required = false, env = "RDAP_MAX_RETRIES", default_value = "1")]
     max_retries: u16,
 
@@ -300,6 +751,28 @@ struct Cli {
     /// Removes the cache files and resets the config file.
     #[arg(long, required = false)]
     reset: bool,
+
+    /// Check for updates.
+    ///
+    /// Checks if a newer release of this program is available.
+    /// This program is never updated automatically.
+    #[arg(long, required = false)]
+    check_update: bool,
+
+    /// Explain a check.
+    ///
+    /// Prints the name, class, message and the specification cited by the
+    /// check with the given code, such as 1302. Use "-O json" or "-O pretty-json"
+    /// for JSON.
+    #[arg(long, required = false, value_name = "CODE")]
+    explain_check: Option<u32>,
+
+    /// Automatically check for updates.
+    ///
+    /// When given, checks if a newer release of this program is available
+    /// no more than once a day, and if so prints a hint on how to upgrade.
+    #[arg(long, required = false, env = "RDAP_AUTO_UPDATE_CHECK")]
+    auto_update_check: bool,
 }
 
 #[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, ValueEnum)]
@@ -316,7 +789,7 @@ enum QtypeArg {
     /// Ipv6 CIDR Lookup
     V6Cidr,
 
-    /// Autonomous System Number Lookup
+    /// Autonomous System Number Lookup (asplain or asdot)
     Autnum,
 
     /// Domain Lookup
@@ -352,10 +825,32 @@ enum QtypeArg {
     /// Nameserver IP Address Search
     NsIp,
 
+    /// IP Network Name Search
+    IpName,
+
+    /// IP Network Handle Search
+    IpHandle,
+
+    /// Autonomous System Number Name Search
+    AutnumName,
+
+    /// Autonomous System Number Handle Search
+    AutnumHandle,
+
     /// RDAP URL
     Url,
 }
 
+#[derive(Subcommand, Debug)]
+enum Commands {
+    /// Manage the configuration file.
+    #[command(subcommand)]
+    Config(ConfigCommand),
+
+    /// Interactively set up the configuration file.
+    Init,
+}
+
 /// Represents the output type possibilities.
 #[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, ValueEnum)]
 enum OtypeArg {
@@ -365,6 +860,10 @@ enum OtypeArg {
     /// Results are rendered as Markdown in plain text.
     Markdown,
 
+    /// Results are rendered as Markdown in plain text, with long lines wrapped
+    /// to the width given by --width, for email and ticketing systems.
+    TextWrapped,
+
     /// Results are output as RDAP JSON.
     Json,
 
@@ -374,12 +873,37 @@ enum OtypeArg {
     /// RDAP JSON with extra information.
     JsonExtra,
 
+    /// RDAP JSON with extra information, one line per response as it is fetched.
+    Ndjson,
+
+    /// The RDAP checks of each response as JSON.
+    ChecksJson,
+
+    /// The RDAP checks of each response as a tree of text, without the response.
+    Checks,
+
     /// Global Top Level Domain Output
     GtldWhois,
 
+    /// Flat key/value WHOIS text of domains, entities, networks, autnums and
+    /// nameservers, with the contacts of the entities labeled by role.
+    Whois,
+
     /// URL of RDAP servers.
     Url,
 
+    /// The contacts of the entities as vCard text.
+    Vcf,
+
+    /// A single line for each query, with the object class, the identity of the
+    /// object, the registrar, the expiry and the most severe class of checks.
+    Summary,
+
+    /// The trace of how the query was made as JSON, with the bootstrap registry
+    /// consulted, the candidate and chosen URLs, and the requests made with their
+    /// redirects, retries and timing.
+    TraceJson,
+
     /// Automatically determine the output type.
     Auto,
 }
@@ -393,19 +917,97 @@ enum CheckTypeArg {
     Info,
 
     /// Specification Notes
+    #[value(alias = "spec_note")]
     SpecNote,
 
     /// Checks for STD 95 warnings.
+    #[value(alias = "std_warn")]
     StdWarn,
 
     /// Checks for STD 95 errors.
+    #[value(alias = "std_error")]
     StdError,
 
     /// Cidr0 errors.
+    #[value(alias = "cidr0_error")]
     Cidr0Error,
 
     /// ICANN Profile errors.
+    #[value(alias = "icann_error")]
     IcannError,
+
+    /// NRO Profile errors, checked when the NRO profile is given.
+    #[value(alias = "nro_error")]
+    NroError,
+}
+
+impl CheckTypeArg {
+    /// Gets the check classes of the check types.
+    fn to_check_classes(check_types: &[Self]) -> Vec<CheckClass> {
+        if check_types.contains(&Self::All) {
+            vec![
+                CheckClass::Informational,
+                CheckClass::SpecificationNote,
+                CheckClass::StdWarning,
+                CheckClass::StdError,
+                CheckClass::Cidr0Error,
+                CheckClass::IcannError,
+                CheckClass::NroError,
+            ]
+        } else {
+            check_types
+                .iter()
+                .map(|c| match c {
+                    Self::Info => CheckClass::Informational,
+                    Self::SpecNote => CheckClass::SpecificationNote,
+                    Self::StdWarn => CheckClass::StdWarning,
+                    Self::StdError => CheckClass::StdError,
+                    Self::Cidr0Error => CheckClass::Cidr0Error,
+                    Self::IcannError => CheckClass::IcannError,
+                    Self::NroError => CheckClass::NroError,
+                    Self::All => panic!("check type for all should have been handled."),
+                })
+                .collect()
+        }
+    }
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, ValueEnum)]
+enum CheckProfileArg {
+    /// The gTLD RDAP profile for registries.
+    #[value(alias = "gtld_registry")]
+    GtldRegistry,
+
+    /// The gTLD RDAP profile for registrars.
+    #[value(alias = "gtld_registrar")]
+    GtldRegistrar,
+
+    /// The NRO RDAP profile.
+    Nro,
+
+    /// No profile.
+    None,
+}
+
+impl CheckProfileArg {
+    /// Gets the profiles, or `None` if the profiles are those declared by the responses.
+    fn to_profiles(profiles: &[Self]) -> Option<Vec<ProfileId>> {
+        if profiles.is_empty() {
+            None
+        } else {
+            Some(
+                profiles
+                    .iter()
+                    .filter_map(|profile| match profile {
+                        Self::GtldRegistry => Some(ProfileId::GtldRegistry),
+                        Self::GtldRegistrar => Some(ProfileId::GtldRegistrar),
+                        Self::Nro => Some(ProfileId::Nro),
+                        Self::None => None,
+                    })
+                    .collect(),
+            )
+        }
+    }
 }
 
 #[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, ValueEnum)]
@@ -429,6 +1031,63 @@ enum LogLevel {
     Trace,
 }
 
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, ValueEnum)]
+enum ErrorFormatArg {
+    /// Errors as human readable text.
+    Text,
+
+    /// Errors as JSON.
+    Json,
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, ValueEnum)]
+enum AsnFormatArg {
+    /// AS numbers as a single number (e.g. 65546).
+    Asplain,
+
+    /// AS numbers greater than 65535 as two dot separated numbers (e.g. 1.10).
+    Asdot,
+
+    /// All AS numbers as two dot separated numbers (e.g. 0.100).
+    AsdotPlus,
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, ValueEnum)]
+enum MdTableStyleArg {
+    /// Tables rendered in a terminal.
+    Terminal,
+
+    /// GitHub-flavored markdown tables.
+    Gfm,
+
+    /// Aligned text without tables.
+    PlainText,
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, ValueEnum)]
+enum MdLinkStyleArg {
+    /// The URLs of links as text.
+    Plain,
+
+    /// Markdown inline links.
+    Inline,
+
+    /// Links are not shown.
+    Omit,
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, ValueEnum)]
+enum NetworkMatchArg {
+    /// The network with exactly the addresses of the query.
+    Exact,
+
+    /// The most specific network containing the query.
+    MostSpecific,
+
+    /// The network immediately less specific than the most specific network.
+    LessSpecific,
+}
+
 #[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, ValueEnum)]
 enum ProcTypeArg {
     /// Only display the data from the domain registrar.
@@ -483,11 +1142,23 @@ impl From<&LogLevel> for LevelFilter {
 
 #[tokio::main]
 pub async fn main() -> RdapCliError {
-    if let Err(e) = wrapped_main().await {
-        let ec = e.exit_code();
-        match ec {
-            202 => error!("Use -T or --allow-http to allow insecure HTTP connections."),
-            _ => eprintln!("\n{e}\n"),
+    let mut error_format = ErrorFormatArg::Text;
+    let result = match dirs::init() {
+        Ok(()) => {
+            dotenv::from_path(dirs::config_path()).ok();
+            let cli = Cli::parse();
+            error_format = cli.error_format;
+            wrapped_main(cli).await
+        }
+        Err(e) => Err(e.into()),
+    };
+    if let Err(e) = result {
+        match error_format {
+            ErrorFormatArg::Json => eprintln!("{}", e.to_json()),
+            ErrorFormatArg::Text => match e.exit_code() {
+                202 => error!("Use -T or --allow-http to allow insecure HTTP connections."),
+                _ => eprintln!("\n{e}\n"),
+            },
         };
         return e;
     } else {
@@ -495,27 +1166,55 @@ pub async fn main() -> RdapCliError {
     }
 }
 
-pub async fn wrapped_main() -> Result<(), RdapCliError> {
-    dirs::init()?;
-    dotenv::from_path(dirs::config_path()).ok();
-    let cli = Cli::parse();
-
+async fn wrapped_main(cli: Cli) -> Result<(), RdapCliError> {
     if cli.reset {
         dirs::reset()?;
         return Ok(());
     }
 
+    if cli.check_update {
+        return check_update().await;
+    }
+
+    if let Some(code) = cli.explain_check {
+        let json = matches!(cli.output_type, OtypeArg::Json | OtypeArg::PrettyJson);
+        return explain_check(code, json);
+    }
+
+    match &cli.command {
+        Some(Commands::Config(config_cmd)) => return do_config(&Cli::command(), config_cmd),
+        Some(Commands::Init) => return do_init(&Cli::command()),
+        None => {}
+    }
+
+    if cli.auto_update_check && !cli.quiet {
+        auto_check_update().await;
+    }
+
     let level = LevelFilter::from(&cli.log_level);
 
     let query_type = query_type_from_cli(&cli)?;
 
     let use_pager = match cli.page_output {
+        _ if cli.batch_file.is_some() => false,
         PagerType::Embedded => true,
         PagerType::None => false,
         PagerType::Auto => std::io::stdout().is_terminal(),
     };
 
     let output_type = match cli.output_type {
+        OtypeArg::Auto if cli.batch_file.is_some() => {
+            if cli.batch_output_dir.is_some() {
+                OutputType::Json
+            } else {
+                OutputType::Ndjson
+            }
+        }
+        OtypeArg::TraceJson if cli.batch_file.is_some() => {
+            return Err(RdapCliError::InvalidConfig(
+                "the trace-json output format cannot be used with --batch".to_string(),
+            ));
+        }
         OtypeArg::Auto => {
             if std::io::stdout().is_terminal() {
                 OutputType::RenderedMarkdown
@@ -525,11 +1224,43 @@ pub async fn wrapped_main() -> Result<(), RdapCliError> {
         }
         OtypeArg::RenderedMarkdown => OutputType::RenderedMarkdown,
         OtypeArg::Markdown => OutputType::Markdown,
+        OtypeArg::TextWrapped => OutputType::TextWrapped,
         OtypeArg::Json => OutputType::Json,
         OtypeArg::PrettyJson => OutputType::PrettyJson,
         OtypeArg::JsonExtra => OutputType::JsonExtra,
+        OtypeArg::Ndjson => OutputType::Ndjson,
+        OtypeArg::ChecksJson => OutputType::ChecksJson,
+        OtypeArg::Checks => OutputType::Checks,
         OtypeArg::GtldWhois => OutputType::GtldWhois,
+        OtypeArg::Whois => OutputType::Whois,
         OtypeArg::Url => OutputType::Url,
+        OtypeArg::Vcf => OutputType::Vcf,
+        OtypeArg::Summary => OutputType::Summary,
+        OtypeArg::TraceJson => OutputType::TraceJson,
+    };
+
+    let asn_format = match cli.asn_format {
+        AsnFormatArg::Asplain => AsnFormat::AsPlain,
+        AsnFormatArg::Asdot => AsnFormat::AsDot,
+        AsnFormatArg::AsdotPlus => AsnFormat::AsDotPlus,
+    };
+
+    let md_table_style = match cli.md_table_style {
+        MdTableStyleArg::Terminal => MdTableStyle::Terminal,
+        MdTableStyleArg::Gfm => MdTableStyle::Gfm,
+        MdTableStyleArg::PlainText => MdTableStyle::PlainText,
+    };
+
+    let md_link_style = match cli.md_link_style {
+        MdLinkStyleArg::Plain => MdLinkStyle::Plain,
+        MdLinkStyleArg::Inline => MdLinkStyle::Inline,
+        MdLinkStyleArg::Omit => MdLinkStyle::Omit,
+    };
+
+    let network_match = match cli.network_match {
+        NetworkMatchArg::Exact => NetworkMatch::Exact,
+        NetworkMatchArg::MostSpecific => NetworkMatch::MostSpecific,
+        NetworkMatchArg::LessSpecific => NetworkMatch::LessSpecific,
     };
 
     let process_type = match cli.process_type {
@@ -547,29 +1278,23 @@ pub async fn wrapped_main() -> Result<(), RdapCliError> {
             CheckClass::StdError,
             CheckClass::Cidr0Error,
             CheckClass::IcannError,
-        ]
-    } else if cli.check_type.contains(&CheckTypeArg::All) {
-        vec![
-            CheckClass::Informational,
-            CheckClass::SpecificationNote,
-            CheckClass::StdWarning,
-            CheckClass::StdError,
-            CheckClass::Cidr0Error,
-            CheckClass::IcannError,
+            CheckClass::NroError,
         ]
     } else {
-        cli.check_type
+        CheckTypeArg::to_check_classes(&cli.check_type)
+    };
+
+    let error_on_check_types = if !cli.error_on.is_empty() {
+        CheckTypeArg::to_check_classes(&cli.error_on)
+    } else if cli.error_on_checks {
+        // we don't want to error on informational
+        check_types
             .iter()
-            .map(|c| match c {
-                CheckTypeArg::Info => CheckClass::Informational,
-                CheckTypeArg::SpecNote => CheckClass::SpecificationNote,
-                CheckTypeArg::StdWarn => CheckClass::StdWarning,
-                CheckTypeArg::StdError => CheckClass::StdError,
-                CheckTypeArg::Cidr0Error => CheckClass::Cidr0Error,
-                CheckTypeArg::IcannError => CheckClass::IcannError,
-                CheckTypeArg::All => panic!("check type for all should have been handled."),
-            })
-            .collect::<Vec<CheckClass>>()
+            .filter(|ct| *ct != &CheckClass::Informational)
+            .copied()
+            .collect()
+    } else {
+        vec![]
     };
 
     let bootstrap_type = if let Some(ref tag) = cli.base {
@@ -590,19 +1315,104 @@ pub async fn wrapped_main() -> Result<(), RdapCliError> {
         InrBackupBootstrapArg::None => InrBackupBootstrap::None,
     };
 
+    let cache = if cli.no_cache {
+        None
+    } else {
+        let cipher: Option<Arc<dyn ContentsCipher>> = if cli.encrypt_cache {
+            let Some(passphrase) = cli.cache_passphrase else {
+                return Err(RdapCliError::NoCachePassphrase);
+            };
+            Some(Arc::new(CacheCipher::new(
+                &dirs::rdap_cache_path(),
+                &passphrase,
+            )?))
+        } else {
+            None
+        };
+        Some(
+            FileResponseCache::builder()
+                .dir(dirs::rdap_cache_path())
+                .max_age(cli.max_cache_age as i64)
+                .max_size(cli.max_cache_size)
+                .and_cipher(cipher)
+                .build(),
+        )
+    };
+
+    let check_policy = match cli.check_policy {
+        Some(path) => {
+            let json = std::fs::read_to_string(&path).map_err(|e| {
+                RdapCliError::InvalidCheckPolicy(format!("{}: {e}", path.display()))
+            })?;
+            CheckPolicy::from_json(&json)
+                .map_err(|e| RdapCliError::InvalidCheckPolicy(format!("{}: {e}", path.display())))?
+        }
+        None => CheckPolicy::default(),
+    };
+
+    let response_filter = match cli.filter {
+        Some(path) => {
+            let json = std::fs::read_to_string(&path).map_err(|e| {
+                RdapCliError::InvalidResponseFilter(format!("{}: {e}", path.display()))
+            })?;
+            Some(ResponseFilter::from_json(&json).map_err(|e| {
+                RdapCliError::InvalidResponseFilter(format!("{}: {e}", path.display()))
+            })?)
+        }
+        None => None,
+    };
+
     let processing_params = ProcessingParams {
         bootstrap_type,
         output_type,
+        asn_format,
+        md_table_style,
+        md_heading_level: cli.md_heading_level as usize,
+        md_link_style,
+        wrap_width: cli.width.map(usize::from).unwrap_or_else(|| {
+            if std::io::stdout().is_terminal() {
+                terminal::size().map_or(DEFAULT_WRAP_WIDTH, |(columns, _)| columns.into())
+            } else {
+                DEFAULT_WRAP_WIDTH
+            }
+        }),
+        defang: cli.defang,
+        quiet: cli.quiet,
+        vcf_dir: cli.vcf_dir,
         check_types,
+        check_policy,
+        response_filter,
+        check_profiles: CheckProfileArg::to_profiles(&cli.check_profile),
         process_type,
         tld_lookup,
         inr_backup_bootstrap,
-        error_on_checks: cli.error_on_checks,
-        no_cache: cli.no_cache,
-        max_cache_age: cli.max_cache_age,
+        network_match,
+        max_pages: cli.max_pages,
+        skip_bogons: cli.skip_bogons,
+        bogons_url: cli.bogons_url,
+        error_on_check_types,
+        bootstrap_max_stale: cli.bootstrap_max_stale as i64,
+        cache,
+        trace: (output_type == OutputType::TraceJson).then(Mutex::default),
+        server_rate_limiter: cli
+            .batch_file
+            .is_some()
+            .then(|| ServerRateLimiter::new(cli.batch_server_rate))
+            .flatten(),
     };
 
-    let client_config = ClientConfig::builder()
+    let batch = cli.batch_file.map(|batch_file| {
+        (
+            batch_file,
+            BatchParams {
+                concurrency: cli.batch_concurrency.into(),
+                output_dir: cli.batch_output_dir,
+            },
+        )
+    });
+
+    #[allow(unused_mut)]
+    let mut client_config = ClientConfig::builder()
         .user_agent_suffix("CLI")
         .https_only(!cli.allow_http)
         .accept_invalid_host_names(cli.allow_invalid_host_names)
@@ -611,7 +1421,19 @@ pub async fn wrapped_main() -> Result<(), RdapCliError> {
         .max_retry_secs(cli.max_retry_secs)
         .def_retry_secs(cli.def_retry_secs)
         .max_retries(cli.max_retries)
+        .strict_media_types(cli.strict_media_types)
+        .and_max_response_bytes(cli.max_response_bytes)
+        .connect_to(cli.connect_to)
+        .spki_pins(cli.spki_pin)
+        .and_unix_socket(cli.unix_socket)
+        .and_rdap_x_extensions(cli.rdap_x_extensions)
         .build();
+    #[cfg(feature = "replay")]
+    if let Some(replay) = &cli.replay {
+        client_config.set_cassette(Cassette::replay(replay)?);
+    } else if let Some(record) = &cli.record {
+        client_config.set_cassette(Cassette::record(record));
+    }
     let rdap_client = create_client(&client_config);
     if let Ok(client) = rdap_client {
         if !use_pager {
@@ -622,6 +1444,8 @@ pub async fn wrapped_main() -> Result<(), RdapCliError> {
             let output = &mut std::io::stdout();
             let res1 = join!(exec(
                 cli.query_value,
+                cli.input_file.as_deref(),
+                batch.as_ref(),
                 &query_type,
                 &processing_params,
                 &client,
@@ -649,6 +1473,8 @@ pub async fn wrapped_main() -> Result<(), RdapCliError> {
                 spawn_blocking(move || minus::dynamic_paging(pager)),
                 exec(
                     cli.query_value,
+                    cli.input_file.as_deref(),
+                    batch.as_ref(),
                     &query_type,
                     &processing_params,
                     &client,
@@ -666,6 +1492,8 @@ pub async fn wrapped_main() -> Result<(), RdapCliError> {
 
 async fn exec<W: std::io::Write>(
     query_value: Option<String>,
+    input_file: Option<&Path>,
+    batch: Option<&(PathBuf, BatchParams)>,
     query_type: &QueryType,
     processing_params: &ProcessingParams,
     client: &Client,
@@ -676,12 +1504,27 @@ async fn exec<W: std::io::Write>(
     #[cfg(debug_assertions)]
     warn!("This is a development build of this software.");
 
-    if let Some(query_value) = query_value {
-        info!("query type is {query_type} for value '{}'", query_value);
+    let result = if let Some(input_file) = input_file {
+        info!("checking RDAP from '{}'", input_file.display());
+        do_input(input_file, processing_params, &mut output)
+    } else if let Some((batch_file, batch_params)) = batch {
+        info!("querying the values in '{}'", batch_file.display());
+        do_batch(
+            batch_file,
+            batch_params,
+            processing_params,
+            client,
+            &mut output,
+        )
+        .await
     } else {
-        info!("query is {query_type}");
-    }
-    let result = do_query(query_type, processing_params, client, &mut output).await;
+        if let Some(query_value) = query_value {
+            info!("query type is {query_type} for value '{}'", query_value);
+        } else {
+            info!("query is {query_type}");
+        }
+        do_query(query_type, processing_params, client, &mut output).await
+    };
     match result {
         Ok(_) => Ok(()),
         Err(error) => {
@@ -695,6 +1538,9 @@ fn query_type_from_cli(cli: &Cli) -> Result<QueryType, RdapCliError> {
     let Some(query_value) = cli.query_value.clone() else {
         return Ok(QueryType::Help);
     };
+    if let Some(relation) = rir_search_relation(cli) {
+        return Ok(QueryType::ip_relation_search(relation, &query_value)?);
+    }
     let Some(query_type) = cli.query_type else {
         return Ok(QueryType::from_str(&query_value)?);
     };
@@ -706,7 +1552,7 @@ fn query_type_from_cli(cli: &Cli) -> Result<QueryType, RdapCliError> {
         QtypeArg::Autnum => QueryType::autnum(&query_value)?,
         QtypeArg::Domain => QueryType::domain(&query_value)?,
         QtypeArg::ALabel => QueryType::alabel(&query_value)?,
-        QtypeArg::Entity => QueryType::Entity(query_value),
+        QtypeArg::Entity => QueryType::entity(&query_value),
         QtypeArg::Ns => QueryType::ns(&query_value)?,
         QtypeArg::EntityName => QueryType::EntityNameSearch(query_value),
         QtypeArg::EntityHandle => QueryType::EntityHandleSearch(query_value),
@@ -715,11 +1561,29 @@ fn query_type_from_cli(cli: &Cli) -> Result<QueryType, RdapCliError> {
         QtypeArg::DomainNsIp => QueryType::domain_ns_ip_search(&query_value)?,
         QtypeArg::NsName => QueryType::NameserverNameSearch(query_value),
         QtypeArg::NsIp => QueryType::ns_ip_search(&query_value)?,
+        QtypeArg::IpName => QueryType::IpNameSearch(query_value),
+        QtypeArg::IpHandle => QueryType::IpHandleSearch(query_value),
+        QtypeArg::AutnumName => QueryType::AutnumNameSearch(query_value),
+        QtypeArg::AutnumHandle => QueryType::AutnumHandleSearch(query_value),
         QtypeArg::Url => QueryType::Url(query_value),
     };
     Ok(q)
 }
 
+fn rir_search_relation(cli: &Cli) -> Option<RirSearchRelation> {
+    if cli.up {
+        Some(RirSearchRelation::Up)
+    } else if cli.down {
+        Some(RirSearchRelation::Down)
+    } else if cli.top {
+        Some(RirSearchRelation::Top)
+    } else if cli.bottom {
+        Some(RirSearchRelation::Bottom)
+    } else {
+        None
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use crate::Cli;
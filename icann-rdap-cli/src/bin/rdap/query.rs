@@ -1,27 +1,36 @@
+use std::{
+    io::Read,
+    path::{Path, PathBuf},
+    sync::Mutex,
+};
+
 use {
-    icann_rdap_client::http::Client,
+    icann_rdap_client::{http::Client, rdap::FileResponseCache, RdapClientError},
     icann_rdap_common::{
-        check::{traverse_checks, CheckClass, CheckParams, Checks, GetChecks},
-        response::get_related_links,
+        check::{
+            traverse_checks, CheckClass, CheckParams, CheckPolicy, Checks, GetChecks, ProfileId,
+        },
+        httpdata::HttpData,
+        response::{filter::ResponseFilter, get_related_links, get_up_link, RdapResponse},
     },
+    serde_json::Value,
     tracing::{debug, error, info},
 };
 
-use {
-    icann_rdap_client::{
-        gtld::{GtldParams, ToGtldWhois},
-        md::{redacted::replace_redacted_items, MdOptions, MdParams, ToMd},
-        rdap::{
-            QueryType, RequestData, RequestResponse, RequestResponses, ResponseData, SourceType,
-        },
+use icann_rdap_client::{
+    md::{redacted::replace_redacted_items, AsnFormat, MdLinkStyle, MdTableStyle},
+    rdap::{
+        NetworkMatch, QueryTrace, QueryType, RequestData, RequestResponse, RequestResponses,
+        ResponseData, SourceType,
     },
-    termimad::{crossterm::style::Color::*, Alignment, MadSkin},
 };
 
 use crate::{
+    batch::ServerRateLimiter,
+    bogons::get_bogons,
     bootstrap::{get_base_url, BootstrapType},
     error::RdapCliError,
-    request::do_request,
+    request::{do_paged_request, do_request},
 };
 
 #[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord)]
@@ -32,6 +41,9 @@ pub(crate) enum OutputType {
     /// Results are rendered as Markdown in plain text.
     Markdown,
 
+    /// Results are rendered as Markdown in plain text, with long lines wrapped.
+    TextWrapped,
+
     /// Results are output as RDAP JSON.
     Json,
 
@@ -41,11 +53,32 @@ pub(crate) enum OutputType {
     /// Global Top Level Domain Output
     GtldWhois,
 
+    /// Flat key/value WHOIS text.
+    Whois,
+
     /// RDAP JSON with extra information.
     JsonExtra,
 
+    /// RDAP JSON with extra information, one line per response.
+    Ndjson,
+
+    /// The checks of each response as JSON.
+    ChecksJson,
+
+    /// The checks of each response as text.
+    Checks,
+
     /// URL
     Url,
+
+    /// The contacts of the entities as vCard text.
+    Vcf,
+
+    /// A single line summarizing each query.
+    Summary,
+
+    /// The trace of how the query was made as JSON.
+    TraceJson,
 }
 
 #[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord)]
@@ -83,13 +116,52 @@ pub(crate) enum InrBackupBootstrap {
 pub(crate) struct ProcessingParams {
     pub bootstrap_type: BootstrapType,
     pub output_type: OutputType,
+    pub asn_format: AsnFormat,
+    pub md_table_style: MdTableStyle,
+    /// The level of the top headings of markdown output.
+    pub md_heading_level: usize,
+    pub md_link_style: MdLinkStyle,
+    /// The width to which lines of wrapped text output are wrapped.
+    pub wrap_width: usize,
+    /// If true, indicators in text and markdown output are defanged.
+    pub defang: bool,
+    /// If true, notices and hints are not shown.
+    pub quiet: bool,
+    /// The directory to write a vCard file for each entity, for vCard output.
+    pub vcf_dir: Option<PathBuf>,
     pub check_types: Vec<CheckClass>,
+    pub check_policy: CheckPolicy,
+    /// The filter of responses before they are checked and output, if any.
+    pub response_filter: Option<ResponseFilter>,
+    /// The profiles to check against, or if `None` those declared by the responses.
+    pub check_profiles: Option<Vec<ProfileId>>,
     pub process_type: ProcessType,
     pub tld_lookup: TldLookup,
     pub inr_backup_bootstrap: InrBackupBootstrap,
-    pub error_on_checks: bool,
-    pub no_cache: bool,
-    pub max_cache_age: u32,
+    pub network_match: NetworkMatch,
+    pub max_pages: usize,
+    pub skip_bogons: bool,
+    pub bogons_url: Option<String>,
+    /// The check classes that, if found, are an error.
+    pub error_on_check_types: Vec<CheckClass>,
+    /// The seconds past their expiration that bootstrap registries are used
+    /// if they cannot be refreshed.
+    pub bootstrap_max_stale: i64,
+    /// The cache of responses, or `None` if caching is disabled.
+    pub cache: Option<FileResponseCache>,
+    /// The trace of how the query is made, or `None` if it is not traced.
+    pub trace: Option<Mutex<QueryTrace>>,
+    /// The limit of the rate of requests to each server, or `None` if not limited.
+    pub server_rate_limiter: Option<ServerRateLimiter>,
+}
+
+impl ProcessingParams {
+    /// Records in the trace of the query, if it is traced.
+    pub(crate) fn record_trace(&self, record: impl FnOnce(&mut QueryTrace)) {
+        if let Some(Ok(mut trace)) = self.trace.as_ref().map(Mutex::lock) {
+            record(&mut trace);
+        }
+    }
 }
 
 pub(crate) async fn do_query<W: std::io::Write>(
@@ -98,7 +170,8 @@ pub(crate) async fn do_query<W: std::io::Write>(
     client: &Client,
     write: &mut W,
 ) -> Result<(), RdapCliError> {
-    match query_type {
+    processing_params.record_trace(|trace| trace.query_type = Some(query_type.to_string()));
+    let result = match query_type {
         QueryType::IpV4Addr(_)
         | QueryType::IpV6Addr(_)
         | QueryType::IpV4Cidr(_)
@@ -110,7 +183,12 @@ pub(crate) async fn do_query<W: std::io::Write>(
             do_domain_query(query_type, processing_params, client, write).await
         }
         _ => do_basic_query(query_type, processing_params, None, client, write).await,
+    };
+    // the trace is written even if the query failed, as that is when it is most useful
+    if let Some(Ok(trace)) = processing_params.trace.as_ref().map(Mutex::lock) {
+        writeln!(write, "{}", serde_json::to_string(&*trace)?)?;
     }
+    result
 }
 
 async fn do_domain_query<W: std::io::Write>(
@@ -126,13 +204,13 @@ async fn do_domain_query<W: std::io::Write>(
         if domain.is_tld() && matches!(processing_params.tld_lookup, TldLookup::Iana) {
             "https://rdap.iana.org".to_string()
         } else {
-            get_base_url(&processing_params.bootstrap_type, client, query_type).await?
+            get_base_url(processing_params, client, query_type).await?
         }
     } else {
-        get_base_url(&processing_params.bootstrap_type, client, query_type).await?
+        get_base_url(processing_params, client, query_type).await?
     };
 
-    let response = do_request(&base_url, query_type, processing_params, client).await;
+    let response = do_paged_request(&base_url, query_type, processing_params, client).await;
     let registrar_response;
     match response {
         Ok(response) => {
@@ -143,11 +221,14 @@ async fn do_domain_query<W: std::io::Write>(
                 source_type: SourceType::DomainRegistry,
             };
             let replaced_rdap = replace_redacted_items(response.rdap.clone());
-            let replaced_data = ResponseData {
-                rdap: replaced_rdap,
-                // copy other fields from `response`
-                ..response.clone()
-            };
+            let replaced_data = filter_response(
+                processing_params,
+                ResponseData {
+                    rdap: replaced_rdap,
+                    // copy other fields from `response`
+                    ..response.clone()
+                },
+            )?;
             if let ProcessType::Registrar = processing_params.process_type {
                 transactions =
                     do_no_output(processing_params, &req_data, &replaced_data, transactions);
@@ -171,7 +252,7 @@ async fn do_domain_query<W: std::io::Write>(
                         do_request(&base_url, &query_type, processing_params, client).await;
                     match registrar_response_result {
                         Ok(response_data) => {
-                            registrar_response = response_data;
+                            registrar_response = filter_response(processing_params, response_data)?;
                             regr_source_host = registrar_response.http_data.host.to_owned();
                             regr_req_data = RequestData {
                                 req_number: 2,
@@ -220,8 +301,14 @@ async fn do_inr_query<W: std::io::Write>(
     client: &Client,
     write: &mut W,
 ) -> Result<(), RdapCliError> {
+    if processing_params.skip_bogons {
+        let bogons = get_bogons(processing_params.bogons_url.as_deref(), client).await;
+        if let Some(bogon) = bogons.find(query_type) {
+            return Err(RdapCliError::BogonQuery(bogon.to_string()));
+        }
+    }
     let mut transactions = RequestResponses::new();
-    let mut base_url = get_base_url(&processing_params.bootstrap_type, client, query_type).await;
+    let mut base_url = get_base_url(processing_params, client, query_type).await;
     if base_url.is_err()
         && matches!(
             processing_params.inr_backup_bootstrap,
@@ -230,9 +317,13 @@ async fn do_inr_query<W: std::io::Write>(
     {
         base_url = Ok("https://rdap.arin.net/registry".to_string());
     };
-    let response = do_request(&base_url?, query_type, processing_params, client).await;
+    let base_url = base_url?;
+    let response = do_request(&base_url, query_type, processing_params, client).await;
     match response {
         Ok(response) => {
+            let response =
+                do_network_match(&base_url, query_type, response, processing_params, client)
+                    .await?;
             let source_host = response.http_data.host.to_owned();
             let req_data = RequestData {
                 req_number: 1,
@@ -240,11 +331,14 @@ async fn do_inr_query<W: std::io::Write>(
                 source_type: SourceType::RegionalInternetRegistry,
             };
             let replaced_rdap = replace_redacted_items(response.rdap.clone());
-            let replaced_data = ResponseData {
-                rdap: replaced_rdap,
-                // copy other fields from `response`
-                ..response.clone()
-            };
+            let replaced_data = filter_response(
+                processing_params,
+                ResponseData {
+                    rdap: replaced_rdap,
+                    // copy other fields from `response`
+                    ..response.clone()
+                },
+            )?;
             transactions = do_output(
                 processing_params,
                 &req_data,
@@ -259,6 +353,58 @@ async fn do_inr_query<W: std::io::Write>(
     Ok(())
 }
 
+/// The most `up` links followed when looking for a network match.
+const MAX_NETWORK_WALK: usize = 16;
+
+/// Finds the network satisfying the network match by walking the `up` links
+/// of the networks, starting with the most specific network given in the response.
+async fn do_network_match(
+    base_url: &str,
+    query_type: &QueryType,
+    response: ResponseData,
+    processing_params: &ProcessingParams,
+    client: &Client,
+) -> Result<ResponseData, RdapCliError> {
+    let network_match = processing_params.network_match;
+    let most_specific = match &response.rdap {
+        RdapResponse::Network(network) if network_match != NetworkMatch::MostSpecific => {
+            network.as_ref().clone()
+        }
+        _ => return Ok(response),
+    };
+    let mut candidate = response;
+    let mut visited: Vec<String> = vec![];
+    for _ in 0..MAX_NETWORK_WALK {
+        let RdapResponse::Network(network) = &candidate.rdap else {
+            break;
+        };
+        info!(
+            "Candidate network {} - {}",
+            network.start_address().unwrap_or_default(),
+            network.end_address().unwrap_or_default()
+        );
+        if network_match.is_match(query_type, &most_specific, network) {
+            return Ok(candidate);
+        }
+        if !network_match.is_match_up(query_type, network) {
+            break;
+        }
+        let Some(up) = get_up_link(&candidate.rdap) else {
+            debug!("Candidate network has no up link.");
+            break;
+        };
+        if visited.iter().any(|url| url == up) {
+            debug!("Up link {up} has already been followed.");
+            break;
+        }
+        visited.push(up.to_string());
+        debug!("Following up link {up}");
+        let query_type = QueryType::Url(up.to_string());
+        candidate = do_request(base_url, &query_type, processing_params, client).await?;
+    }
+    Err(RdapCliError::NoNetworkMatch)
+}
+
 async fn do_basic_query<'a, W: std::io::Write>(
     query_type: &QueryType,
     processing_params: &ProcessingParams,
@@ -267,8 +413,8 @@ async fn do_basic_query<'a, W: std::io::Write>(
     write: &mut W,
 ) -> Result<(), RdapCliError> {
     let mut transactions = RequestResponses::new();
-    let base_url = get_base_url(&processing_params.bootstrap_type, client, query_type).await?;
-    let response = do_request(&base_url, query_type, processing_params, client).await;
+    let base_url = get_base_url(processing_params, client, query_type).await?;
+    let response = do_paged_request(&base_url, query_type, processing_params, client).await;
     match response {
         Ok(response) => {
             let source_host = response.http_data.host.to_owned();
@@ -286,11 +432,14 @@ async fn do_basic_query<'a, W: std::io::Write>(
                 }
             };
             let replaced_rdap = replace_redacted_items(response.rdap.clone());
-            let replaced_data = ResponseData {
-                rdap: replaced_rdap,
-                // copy other fields from `response`
-                ..response.clone()
-            };
+            let replaced_data = filter_response(
+                processing_params,
+                ResponseData {
+                    rdap: replaced_rdap,
+                    // copy other fields from `response`
+                    ..response.clone()
+                },
+            )?;
             transactions = do_output(
                 processing_params,
                 &req_data,
@@ -305,6 +454,66 @@ async fn do_basic_query<'a, W: std::io::Write>(
     Ok(())
 }
 
+/// Checks and outputs RDAP read from a file, or from stdin if the path is "-",
+/// instead of from the network.
+pub(crate) fn do_input<W: std::io::Write>(
+    input: &Path,
+    processing_params: &ProcessingParams,
+    write: &mut W,
+) -> Result<(), RdapCliError> {
+    let source = input.display().to_string();
+    let json = if source == "-" {
+        let mut json = String::new();
+        std::io::stdin().read_to_string(&mut json)?;
+        json
+    } else {
+        std::fs::read_to_string(input)?
+    };
+    let rdap = RdapResponse::try_from(serde_json::from_str::<Value>(&json)?)
+        .map_err(RdapClientError::from)?;
+    let response = filter_response(
+        processing_params,
+        ResponseData {
+            rdap_type: rdap.to_string(),
+            rdap: replace_redacted_items(rdap),
+            http_data: HttpData::now()
+                .scheme("file")
+                .host(source.clone())
+                .request_uri(source.clone())
+                .build(),
+        },
+    )?;
+    let req_data = RequestData {
+        req_number: 1,
+        source_host: &source,
+        source_type: SourceType::UncategorizedRegistry,
+    };
+    let transactions = do_output(
+        processing_params,
+        &req_data,
+        &response,
+        write,
+        RequestResponses::new(),
+    )?;
+    do_final_output(processing_params, write, transactions)
+}
+
+/// Applies the response filter, if there is one, to a response before it is checked and output.
+fn filter_response(
+    processing_params: &ProcessingParams,
+    response: ResponseData,
+) -> Result<ResponseData, RdapCliError> {
+    let Some(filter) = &processing_params.response_filter else {
+        return Ok(response);
+    };
+    Ok(ResponseData {
+        rdap: filter
+            .apply(&response.rdap)
+            .map_err(RdapClientError::from)?,
+        ..response
+    })
+}
+
 fn do_output<'a, W: std::io::Write>(
     processing_params: &ProcessingParams,
     req_data: &'a RequestData,
@@ -312,83 +521,27 @@ fn do_output<'a, W: std::io::Write>(
     write: &mut W,
     mut transactions: RequestResponses<'a>,
 ) -> Result<RequestResponses<'a>, RdapCliError> {
-    match processing_params.output_type {
-        OutputType::RenderedMarkdown => {
-            let mut skin = MadSkin::default_dark();
-            skin.set_headers_fg(Yellow);
-            skin.headers[1].align = Alignment::Center;
-            skin.headers[2].align = Alignment::Center;
-            skin.headers[3].align = Alignment::Center;
-            skin.headers[4].compound_style.set_fg(DarkGreen);
-            skin.headers[5].compound_style.set_fg(Magenta);
-            skin.headers[6].compound_style.set_fg(Cyan);
-            skin.headers[7].compound_style.set_fg(Red);
-            skin.bold.set_fg(DarkBlue);
-            skin.italic.set_fg(Red);
-            skin.quote_mark.set_fg(DarkBlue);
-            skin.table.set_fg(DarkGreen);
-            skin.table.align = Alignment::Center;
-            skin.inline_code.set_fgbg(Cyan, Reset);
-            skin.write_text_on(
-                write,
-                &response.rdap.to_md(MdParams {
-                    heading_level: 1,
-                    root: &response.rdap,
-                    http_data: &response.http_data,
-                    parent_type: response.rdap.get_type(),
-                    check_types: &processing_params.check_types,
-                    options: &MdOptions::default(),
-                    req_data,
-                }),
-            )?;
-        }
-        OutputType::Markdown => {
-            writeln!(
-                write,
-                "{}",
-                response.rdap.to_md(MdParams {
-                    heading_level: 1,
-                    root: &response.rdap,
-                    http_data: &response.http_data,
-                    parent_type: response.rdap.get_type(),
-                    check_types: &processing_params.check_types,
-                    options: &MdOptions {
-                        text_style_char: '_',
-                        style_in_justify: true,
-                        ..MdOptions::default()
-                    },
-                    req_data,
-                })
-            )?;
-        }
-        OutputType::GtldWhois => {
-            let mut params = GtldParams {
-                root: &response.rdap,
-                parent_type: response.rdap.get_type(),
-                label: "".to_string(),
-            };
-            writeln!(write, "{}", response.rdap.to_gtld_whois(&mut params))?;
-        }
-        _ => {} // do nothing
-    };
-
     let req_res = RequestResponse {
-        checks: do_output_checks(response),
+        checks: do_output_checks(processing_params, response),
         req_data,
         res_data: response,
     };
+    processing_params
+        .output_type
+        .renderer()
+        .render_response(processing_params, &req_res, write)?;
     transactions.push(req_res);
     Ok(transactions)
 }
 
 fn do_no_output<'a>(
-    _processing_params: &ProcessingParams,
+    processing_params: &ProcessingParams,
     req_data: &'a RequestData,
     response: &'a ResponseData,
     mut transactions: RequestResponses<'a>,
 ) -> RequestResponses<'a> {
     let req_res = RequestResponse {
-        checks: do_output_checks(response),
+        checks: do_output_checks(processing_params, response),
         req_data,
         res_data: response,
     };
@@ -396,17 +549,22 @@ fn do_no_output<'a>(
     transactions
 }
 
-fn do_output_checks(response: &ResponseData) -> Checks {
+fn do_output_checks(processing_params: &ProcessingParams, response: &ResponseData) -> Checks {
     let check_params = CheckParams {
         do_subchecks: true,
         root: &response.rdap,
         parent_type: response.rdap.get_type(),
         allow_unreg_ext: false,
+        profiles: processing_params
+            .check_profiles
+            .as_deref()
+            .unwrap_or(ProfileId::declared_by(&response.rdap)),
     };
     let mut checks = response.rdap.get_checks(check_params);
     checks
         .items
         .append(&mut response.http_data.get_checks(check_params).items);
+    processing_params.check_policy.apply(&mut checks);
     checks
 }
 
@@ -415,63 +573,25 @@ fn do_final_output<W: std::io::Write>(
     write: &mut W,
     transactions: RequestResponses<'_>,
 ) -> Result<(), RdapCliError> {
-    match processing_params.output_type {
-        OutputType::Json => {
-            for req_res in &transactions {
-                writeln!(
-                    write,
-                    "{}",
-                    serde_json::to_string(&req_res.res_data.rdap).unwrap()
-                )?;
-            }
-        }
-        OutputType::PrettyJson => {
-            for req_res in &transactions {
-                writeln!(
-                    write,
-                    "{}",
-                    serde_json::to_string_pretty(&req_res.res_data.rdap).unwrap()
-                )?;
-            }
-        }
-        OutputType::JsonExtra => {
-            writeln!(write, "{}", serde_json::to_string(&transactions).unwrap())?
-        }
-        OutputType::GtldWhois => {}
-        OutputType::Url => {
-            for rr in &transactions {
-                if let Some(url) = rr.res_data.http_data.request_uri() {
-                    writeln!(write, "{url}")?;
-                }
-            }
-        }
-        _ => {} // do nothing
-    };
+    processing_params.output_type.renderer().render_final(
+        processing_params,
+        &transactions,
+        write,
+    )?;
 
     let mut checks_found = false;
-    // we don't want to error on informational
-    let error_check_types: Vec<CheckClass> = processing_params
-        .check_types
-        .iter()
-        .filter(|ct| *ct != &CheckClass::Informational)
-        .copied()
-        .collect();
     for req_res in &transactions {
         let found = traverse_checks(
             &req_res.checks,
-            &error_check_types,
+            &processing_params.error_on_check_types,
             None,
-            &mut |struct_tree, check_item| {
-                if processing_params.error_on_checks {
-                    error!("{struct_tree} -> {check_item}")
-                }
-            },
+            &mut |struct_tree, check_item| error!("{struct_tree} -> {check_item}"),
         );
         if found {
             checks_found = true
         }
     }
-    if checks_found && processing_params.error_on_checks {
+    if checks_found {
         return Err(RdapCliError::ErrorOnChecks);
     }
 
@@ -0,0 +1,529 @@
+use std::{fmt::Write as _, fs, io::Write};
+
+use {
+    icann_rdap_client::{
+        gtld::{GtldParams, ToGtldWhois},
+        md::{MdOptions, MdParams, ToMd},
+        rdap::{RequestResponse, RequestResponses},
+        whois::ToWhois,
+    },
+    icann_rdap_common::check::{traverse_checks, CheckClass, Checks},
+    serde_json::{json, Value},
+    strum::EnumMessage,
+    termimad::{crossterm::style::Color::*, Alignment, MadSkin},
+    tracing::info,
+};
+
+use crate::{
+    defang::defang,
+    error::RdapCliError,
+    query::{OutputType, ProcessingParams},
+    summary::Summary,
+    vcf::entity_contacts,
+};
+
+/// Renders the responses of a query as output.
+///
+/// Responses are given to the renderer as they are fetched with [ResponseRenderer::render_response],
+/// and then all together once the query is complete with [ResponseRenderer::render_final].
+/// A renderer implements either or both.
+pub(crate) trait ResponseRenderer {
+    /// Renders a response as soon as it is fetched.
+    fn render_response(
+        &self,
+        _processing_params: &ProcessingParams,
+        _req_res: &RequestResponse<'_>,
+        _write: &mut dyn Write,
+    ) -> Result<(), RdapCliError> {
+        Ok(())
+    }
+
+    /// Renders all the responses of the query once it is complete.
+    fn render_final(
+        &self,
+        _processing_params: &ProcessingParams,
+        _transactions: &RequestResponses<'_>,
+        _write: &mut dyn Write,
+    ) -> Result<(), RdapCliError> {
+        Ok(())
+    }
+}
+
+impl ProcessingParams {
+    /// Gets text for output, defanged if asked for.
+    fn text(&self, text: String) -> String {
+        if self.defang {
+            defang(&text)
+        } else {
+            text
+        }
+    }
+}
+
+impl OutputType {
+    /// Gets the renderer for the output type.
+    pub(crate) fn renderer(&self) -> Box<dyn ResponseRenderer> {
+        match self {
+            Self::RenderedMarkdown => Box::new(RenderedMarkdownRenderer),
+            Self::Markdown => Box::new(MarkdownRenderer),
+            Self::TextWrapped => Box::new(TextWrappedRenderer),
+            Self::Json => Box::new(JsonRenderer { pretty: false }),
+            Self::PrettyJson => Box::new(JsonRenderer { pretty: true }),
+            Self::GtldWhois => Box::new(GtldWhoisRenderer),
+            Self::Whois => Box::new(WhoisRenderer),
+            Self::JsonExtra => Box::new(JsonExtraRenderer),
+            Self::Ndjson => Box::new(NdjsonRenderer),
+            Self::ChecksJson => Box::new(ChecksJsonRenderer),
+            Self::Checks => Box::new(ChecksRenderer),
+            Self::Url => Box::new(UrlRenderer),
+            Self::Vcf => Box::new(VcfRenderer),
+            Self::Summary => Box::new(SummaryRenderer),
+            Self::TraceJson => Box::new(TraceJsonRenderer),
+        }
+    }
+}
+
+/// Renders Markdown in the terminal using ANSI terminal capabilities.
+struct RenderedMarkdownRenderer;
+
+impl ResponseRenderer for RenderedMarkdownRenderer {
+    fn render_response(
+        &self,
+        processing_params: &ProcessingParams,
+        req_res: &RequestResponse<'_>,
+        mut write: &mut dyn Write,
+    ) -> Result<(), RdapCliError> {
+        let mut skin = MadSkin::default_dark();
+        skin.set_headers_fg(Yellow);
+        skin.headers[1].align = Alignment::Center;
+        skin.headers[2].align = Alignment::Center;
+        skin.headers[3].align = Alignment::Center;
+        skin.headers[4].compound_style.set_fg(DarkGreen);
+        skin.headers[5].compound_style.set_fg(Magenta);
+        skin.headers[6].compound_style.set_fg(Cyan);
+        skin.headers[7].compound_style.set_fg(Red);
+        skin.bold.set_fg(DarkBlue);
+        skin.italic.set_fg(Red);
+        skin.quote_mark.set_fg(DarkBlue);
+        skin.table.set_fg(DarkGreen);
+        skin.table.align = Alignment::Center;
+        skin.inline_code.set_fgbg(Cyan, Reset);
+        let response = req_res.res_data;
+        let md = response.rdap.to_md(MdParams {
+            heading_level: processing_params.md_heading_level,
+            root: &response.rdap,
+            http_data: &response.http_data,
+            parent_type: response.rdap.get_type(),
+            check_types: &processing_params.check_types,
+            options: &MdOptions {
+                asn_format: processing_params.asn_format,
+                check_policy: processing_params.check_policy.clone(),
+                table_style: processing_params.md_table_style,
+                link_style: processing_params.md_link_style,
+                hide_notices: processing_params.quiet,
+                profiles: processing_params.check_profiles.clone(),
+                ..MdOptions::default()
+            },
+            req_data: req_res.req_data,
+        });
+        skin.write_text_on(&mut write, &processing_params.text(md))?;
+        Ok(())
+    }
+}
+
+/// Renders Markdown in plain text.
+struct MarkdownRenderer;
+
+impl ResponseRenderer for MarkdownRenderer {
+    fn render_response(
+        &self,
+        processing_params: &ProcessingParams,
+        req_res: &RequestResponse<'_>,
+        write: &mut dyn Write,
+    ) -> Result<(), RdapCliError> {
+        writeln!(write, "{}", plain_md(processing_params, req_res))?;
+        Ok(())
+    }
+}
+
+/// Renders Markdown in plain text with long lines wrapped, as email and
+/// ticketing systems can mangle very long lines.
+struct TextWrappedRenderer;
+
+impl ResponseRenderer for TextWrappedRenderer {
+    fn render_response(
+        &self,
+        processing_params: &ProcessingParams,
+        req_res: &RequestResponse<'_>,
+        write: &mut dyn Write,
+    ) -> Result<(), RdapCliError> {
+        let md = plain_md(processing_params, req_res);
+        writeln!(write, "{}", wrap_lines(&md, processing_params.wrap_width))?;
+        Ok(())
+    }
+}
+
+/// Gets the Markdown of a response for plain text.
+fn plain_md(processing_params: &ProcessingParams, req_res: &RequestResponse<'_>) -> String {
+    let response = req_res.res_data;
+    let md = response.rdap.to_md(MdParams {
+        heading_level: processing_params.md_heading_level,
+        root: &response.rdap,
+        http_data: &response.http_data,
+        parent_type: response.rdap.get_type(),
+        check_types: &processing_params.check_types,
+        options: &MdOptions {
+            text_style_char: '_',
+            style_in_justify: true,
+            asn_format: processing_params.asn_format,
+            check_policy: processing_params.check_policy.clone(),
+            table_style: processing_params.md_table_style,
+            link_style: processing_params.md_link_style,
+            hide_notices: processing_params.quiet,
+            profiles: processing_params.check_profiles.clone(),
+            ..MdOptions::default()
+        },
+        req_data: req_res.req_data,
+    });
+    processing_params.text(md)
+}
+
+/// Wraps the lines of text longer than the width, indenting the continuations of
+/// a line as much as the line. Words longer than the width, such as URLs, are broken.
+fn wrap_lines(text: &str, width: usize) -> String {
+    let mut wrapped = String::with_capacity(text.len());
+    for line in text.lines() {
+        let indent = &line[..line.len() - line.trim_start().len()];
+        let options = textwrap::Options::new(width).subsequent_indent(indent);
+        for wrapped_line in textwrap::wrap(line, options) {
+            wrapped.push_str(wrapped_line.trim_end());
+            wrapped.push('\n');
+        }
+    }
+    wrapped
+}
+
+/// Renders gTLD WHOIS output.
+struct GtldWhoisRenderer;
+
+impl ResponseRenderer for GtldWhoisRenderer {
+    fn render_response(
+        &self,
+        processing_params: &ProcessingParams,
+        req_res: &RequestResponse<'_>,
+        write: &mut dyn Write,
+    ) -> Result<(), RdapCliError> {
+        let response = req_res.res_data;
+        let mut params = GtldParams {
+            root: &response.rdap,
+            parent_type: response.rdap.get_type(),
+            label: "".to_string(),
+        };
+        let whois = response.rdap.to_gtld_whois(&mut params);
+        writeln!(write, "{}", processing_params.text(whois))?;
+        Ok(())
+    }
+}
+
+/// Renders flat key/value WHOIS text.
+struct WhoisRenderer;
+
+impl ResponseRenderer for WhoisRenderer {
+    fn render_response(
+        &self,
+        processing_params: &ProcessingParams,
+        req_res: &RequestResponse<'_>,
+        write: &mut dyn Write,
+    ) -> Result<(), RdapCliError> {
+        let whois = req_res.res_data.rdap.to_whois();
+        if !whois.is_empty() {
+            writeln!(write, "{}", processing_params.text(whois))?;
+        }
+        Ok(())
+    }
+}
+
+/// Renders the RDAP JSON of each response.
+struct JsonRenderer {
+    pretty: bool,
+}
+
+impl ResponseRenderer for JsonRenderer {
+    fn render_final(
+        &self,
+        _processing_params: &ProcessingParams,
+        transactions: &RequestResponses<'_>,
+        write: &mut dyn Write,
+    ) -> Result<(), RdapCliError> {
+        for req_res in transactions {
+            let json = if self.pretty {
+                serde_json::to_string_pretty(&req_res.res_data.rdap)
+            } else {
+                serde_json::to_string(&req_res.res_data.rdap)
+            };
+            writeln!(write, "{}", json.unwrap())?;
+        }
+        Ok(())
+    }
+}
+
+/// Renders all the responses, with their request data and checks, as one JSON array.
+struct JsonExtraRenderer;
+
+impl ResponseRenderer for JsonExtraRenderer {
+    fn render_final(
+        &self,
+        _processing_params: &ProcessingParams,
+        transactions: &RequestResponses<'_>,
+        write: &mut dyn Write,
+    ) -> Result<(), RdapCliError> {
+        writeln!(write, "{}", serde_json::to_string(transactions).unwrap())?;
+        Ok(())
+    }
+}
+
+/// Renders each response, with its request data and checks, as a line of JSON
+/// as soon as it is fetched.
+struct NdjsonRenderer;
+
+impl ResponseRenderer for NdjsonRenderer {
+    fn render_response(
+        &self,
+        _processing_params: &ProcessingParams,
+        req_res: &RequestResponse<'_>,
+        write: &mut dyn Write,
+    ) -> Result<(), RdapCliError> {
+        writeln!(write, "{}", serde_json::to_string(req_res).unwrap())?;
+        write.flush()?;
+        Ok(())
+    }
+}
+
+/// Renders the checks of all the responses as one JSON array, with an
+/// object for each response listing its checks by their RDAP structure path.
+struct ChecksJsonRenderer;
+
+impl ResponseRenderer for ChecksJsonRenderer {
+    fn render_final(
+        &self,
+        processing_params: &ProcessingParams,
+        transactions: &RequestResponses<'_>,
+        write: &mut dyn Write,
+    ) -> Result<(), RdapCliError> {
+        let responses: Vec<Value> = transactions
+            .iter()
+            .map(|req_res| {
+                let mut checks = vec![];
+                traverse_checks(
+                    &req_res.checks,
+                    &processing_params.check_types,
+                    None,
+                    &mut |struct_tree, item| {
+                        checks.push(json!({
+                            "path": struct_tree,
+                            "id": item.check.code(),
+                            "check": item.check,
+                            "class": item.check_class,
+                            "message": item.check.get_message(),
+                            "specification": item.specification,
+                        }))
+                    },
+                );
+                json!({
+                    "url": req_res.res_data.http_data.request_uri(),
+                    "object": req_res.res_data.rdap_type,
+                    "checks": checks,
+                })
+            })
+            .collect();
+        writeln!(write, "{}", serde_json::to_string(&responses).unwrap())?;
+        Ok(())
+    }
+}
+
+/// Renders the checks of each response as a tree of text, indented by RDAP structure.
+///
+/// Only the structures with checks of the check types are given.
+struct ChecksRenderer;
+
+impl ResponseRenderer for ChecksRenderer {
+    fn render_response(
+        &self,
+        processing_params: &ProcessingParams,
+        req_res: &RequestResponse<'_>,
+        write: &mut dyn Write,
+    ) -> Result<(), RdapCliError> {
+        let response = req_res.res_data;
+        let mut text = format!(
+            "{}\n",
+            response
+                .http_data
+                .request_uri()
+                .unwrap_or(&response.rdap_type)
+        );
+        if !checks_tree(
+            &req_res.checks,
+            &processing_params.check_types,
+            1,
+            &mut text,
+        ) {
+            text.push_str("  No issues or errors.\n");
+        }
+        writeln!(write, "{}", processing_params.text(text))?;
+        Ok(())
+    }
+}
+
+/// Writes the checks of the check classes as a tree, returning true if any are found.
+fn checks_tree(checks: &Checks, classes: &[CheckClass], depth: usize, text: &mut String) -> bool {
+    let mut branch = String::new();
+    let mut found = false;
+    for item in checks
+        .items
+        .iter()
+        .filter(|item| classes.contains(&item.check_class))
+    {
+        writeln!(branch, "{:indent$}{item}", "", indent = (depth + 1) * 2).ok();
+        found = true;
+    }
+    for sub_checks in &checks.sub_checks {
+        found |= checks_tree(sub_checks, classes, depth + 1, &mut branch);
+    }
+    if found {
+        writeln!(
+            text,
+            "{:indent$}{}",
+            "",
+            checks.rdap_struct,
+            indent = depth * 2
+        )
+        .ok();
+        text.push_str(&branch);
+    }
+    found
+}
+
+/// Renders the URL of each response.
+struct UrlRenderer;
+
+impl ResponseRenderer for UrlRenderer {
+    fn render_final(
+        &self,
+        processing_params: &ProcessingParams,
+        transactions: &RequestResponses<'_>,
+        write: &mut dyn Write,
+    ) -> Result<(), RdapCliError> {
+        for req_res in transactions {
+            if let Some(url) = req_res.res_data.http_data.request_uri() {
+                writeln!(write, "{}", processing_params.text(url.to_string()))?;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Renders the contacts of the entities of all the responses as vCard text,
+/// either together or as a file for each entity.
+struct VcfRenderer;
+
+impl ResponseRenderer for VcfRenderer {
+    fn render_final(
+        &self,
+        processing_params: &ProcessingParams,
+        transactions: &RequestResponses<'_>,
+        write: &mut dyn Write,
+    ) -> Result<(), RdapCliError> {
+        let contacts = entity_contacts(transactions.iter().map(|req_res| &req_res.res_data.rdap));
+        let Some(dir) = &processing_params.vcf_dir else {
+            for entity_contact in contacts {
+                write!(write, "{}", entity_contact.contact.to_vcard_text())?;
+            }
+            return Ok(());
+        };
+        fs::create_dir_all(dir)?;
+        for (index, entity_contact) in contacts.iter().enumerate() {
+            let path = dir.join(entity_contact.file_name(index));
+            fs::write(&path, entity_contact.contact.to_vcard_text())?;
+            info!("Wrote vCard {}", path.display());
+        }
+        Ok(())
+    }
+}
+
+/// Renders all the responses of the query as a single line.
+struct SummaryRenderer;
+
+impl ResponseRenderer for SummaryRenderer {
+    fn render_final(
+        &self,
+        processing_params: &ProcessingParams,
+        transactions: &RequestResponses<'_>,
+        write: &mut dyn Write,
+    ) -> Result<(), RdapCliError> {
+        let summary = Summary::from_responses(
+            transactions
+                .iter()
+                .map(|req_res| (&req_res.res_data.rdap, &req_res.checks)),
+            &processing_params.check_types,
+            &processing_params.check_policy,
+        );
+        writeln!(write, "{}", processing_params.text(summary.to_line()))?;
+        Ok(())
+    }
+}
+
+/// Renders none of the responses, as only the trace of the query is output,
+/// which is written once the query is done whether or not it succeeded.
+struct TraceJsonRenderer;
+
+impl ResponseRenderer for TraceJsonRenderer {}
+
+#[cfg(test)]
+#[allow(non_snake_case)]
+mod tests {
+    use icann_rdap_common::check::{Check, CheckClass, Checks, RdapStructure};
+
+    use super::{checks_tree, wrap_lines};
+
+    #[test]
+    fn GIVEN_nested_checks_WHEN_checks_tree_THEN_indented_and_pruned_by_class() {
+        // GIVEN
+        let checks = Checks {
+            rdap_struct: RdapStructure::Domain,
+            items: vec![Check::IcannExpirationEventIsAbsent.check_item()],
+            sub_checks: vec![
+                Checks {
+                    rdap_struct: RdapStructure::Events,
+                    items: vec![Check::EventDateIsAbsent.check_item()],
+                    sub_checks: vec![],
+                },
+                Checks {
+                    rdap_struct: RdapStructure::Links,
+                    items: vec![Check::IcannExpirationEventIsAbsent.check_item()],
+                    sub_checks: vec![],
+                },
+            ],
+        };
+        let mut text = String::new();
+
+        // WHEN
+        let found = checks_tree(&checks, &[CheckClass::StdError], 1, &mut text);
+
+        // THEN
+        assert!(found);
+        let item = Check::EventDateIsAbsent.check_item();
+        assert_eq!(text, format!("  domain\n    events\n      {item}\n"));
+    }
+
+    #[test]
+    fn GIVEN_long_indented_line_WHEN_wrap_lines_THEN_wrapped_with_indent() {
+        // GIVEN
+        let text = "short\n  one two three four five six\n";
+
+        // WHEN
+        let actual = wrap_lines(text, 12);
+
+        // THEN
+        assert_eq!(actual, "short\n  one two\n  three four\n  five six\n");
+    }
+}
@@ -1,88 +1,96 @@
-use std::{
-    fs::{self, File},
-    io::{BufRead, BufReader},
-};
-
 use {
+    chrono::Utc,
     icann_rdap_client::{
         http::Client,
-        rdap::{rdap_url_request, QueryType, ResponseData},
+        rdap::{
+            merge_page, rdap_cached_request, rdap_url_request, QueryType, RequestTrace,
+            ResponseCache, ResponseData,
+        },
     },
-    icann_rdap_common::{httpdata::HttpData, response::GetSelfLink},
-    pct_str::{PctString, URIReserved},
     tracing::{debug, info},
 };
 
-use crate::{dirs::rdap_cache_path, error::RdapCliError, query::ProcessingParams};
+use crate::{error::RdapCliError, query::ProcessingParams};
 
-pub(crate) async fn do_request(
+/// Does a request, fetching the subsequent pages of search results
+/// up to the maximum number of pages.
+pub(crate) async fn do_paged_request(
     base_url: &str,
     query_type: &QueryType,
     processing_params: &ProcessingParams,
     client: &Client,
 ) -> Result<ResponseData, RdapCliError> {
-    if processing_params.no_cache {
-        info!("Cache has been disabled.")
+    let mut response = do_request(base_url, query_type, processing_params, client).await?;
+    let mut pages = 1;
+    let mut visited: Vec<String> = vec![];
+    while processing_params.max_pages == 0 || pages < processing_params.max_pages {
+        let Some(next) = response
+            .rdap
+            .get_paging_metadata()
+            .and_then(|paging| paging.next_link())
+        else {
+            break;
+        };
+        if visited.iter().any(|url| url == next) {
+            debug!("Next page {next} has already been fetched.");
+            break;
+        }
+        visited.push(next.to_string());
+        info!("Fetching page {} of search results.", pages + 1);
+        let query_type = QueryType::Url(next.to_string());
+        let page = do_request(base_url, &query_type, processing_params, client).await?;
+        if !merge_page(&mut response.rdap, page.rdap) {
+            break;
+        }
+        pages += 1;
+    }
+    if response
+        .rdap
+        .get_paging_metadata()
+        .and_then(|paging| paging.next_link())
+        .is_some()
+    {
+        info!("More pages of search results are available. Use --max-pages to fetch them.");
     }
+    Ok(response)
+}
+
+pub(crate) async fn do_request(
+    base_url: &str,
+    query_type: &QueryType,
+    processing_params: &ProcessingParams,
+    client: &Client,
+) -> Result<ResponseData, RdapCliError> {
     let query_url = query_type.query_url(base_url)?;
-    if !processing_params.no_cache {
-        let file_name = format!(
-            "{}.cache",
-            PctString::encode(query_url.chars(), URIReserved)
-        );
-        let path = rdap_cache_path().join(&file_name);
-        if path.exists() {
-            let input = File::open(path)?;
-            let buf = BufReader::new(input);
-            let mut lines = vec![];
-            for line in buf.lines() {
-                lines.push(line?)
-            }
-            let cache_data = HttpData::from_lines(&lines)?;
-            if !cache_data
-                .0
-                .is_expired(processing_params.max_cache_age as i64)
-            {
-                debug!("Returning response from cache file {file_name}");
-                let response: ResponseData = serde_json::from_str(&cache_data.1.join(""))?;
-                return Ok(response);
+    let started = Utc::now();
+    let response = match &processing_params.cache {
+        Some(cache) => match cache.get_response(&query_url) {
+            Ok(Some(response)) => {
+                debug!("returning response from cache for {query_url}");
+                Ok(response)
             }
-        }
-    }
-    let response = rdap_url_request(&query_url, client).await?;
-    if !processing_params.no_cache {
-        if response.http_data.should_cache() {
-            let data = serde_json::to_string_pretty(&response)?;
-            let cache_contents = response.http_data.to_lines(&data)?;
-            let query_url = query_type.query_url(base_url)?;
-            let file_name = format!(
-                "{}.cache",
-                PctString::encode(query_url.chars(), URIReserved)
-            );
-            debug!("Saving query response to cache file {file_name}");
-            let path = rdap_cache_path().join(file_name);
-            fs::write(path, &cache_contents)?;
-            if let Some(self_link) = response.rdap.get_self_link() {
-                if let Some(self_link_href) = &self_link.href {
-                    if query_url != *self_link_href {
-                        let file_name = format!(
-                            "{}.cache",
-                            PctString::encode(self_link_href.chars(), URIReserved)
-                        );
-                        debug!("Saving object with self link to cache file {file_name}");
-                        let path = rdap_cache_path().join(file_name);
-                        fs::write(path, &cache_contents)?;
-                    }
-                }
+            _ => {
+                wait_for_server(&query_url, processing_params).await;
+                rdap_cached_request(&query_url, client, cache).await
             }
-        } else {
-            debug!("Not caching data according to server policy.");
-            debug!("Expires header: {:?}", &response.http_data.expires);
-            debug!(
-                "Cache-control header: {:?}",
-                &response.http_data.cache_control
-            );
+        },
+        None => {
+            info!("Cache has been disabled.");
+            wait_for_server(&query_url, processing_params).await;
+            rdap_url_request(&query_url, client).await
         }
+    };
+    processing_params.record_trace(|trace| {
+        trace
+            .requests
+            .push(RequestTrace::new(&query_url, started, &response))
+    });
+    Ok(response?)
+}
+
+/// Waits until a request to the server of a URL is within the rate limit, if there is one.
+async fn wait_for_server(url: &str, processing_params: &ProcessingParams) {
+    if let Some(limiter) = &processing_params.server_rate_limiter {
+        limiter.wait(url).await;
     }
-    Ok(response)
 }
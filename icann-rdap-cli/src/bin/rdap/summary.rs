@@ -0,0 +1,244 @@
+//! Summaries of queries on a single line, for scanning the output of many queries.
+
+use icann_rdap_common::{
+    check::{traverse_checks, CheckClass, CheckPolicy, Checks},
+    response::{Entity, Event, ObjectCommonFields, RdapResponse},
+};
+
+/// The value of a field of a summary that is not known.
+const UNKNOWN: &str = "-";
+
+/// The summary of a query, from all of its responses.
+#[derive(Debug, Default, PartialEq, Eq)]
+pub(crate) struct Summary {
+    /// The class of the object, such as `domain` or `ip network`.
+    pub object_class: Option<String>,
+
+    /// The name, handle or number identifying the object.
+    pub identity: Option<String>,
+
+    /// The name of the registrar, for domains.
+    pub registrar: Option<String>,
+
+    /// The date of the expiration event.
+    pub expiry: Option<String>,
+
+    /// The most severe class of the checks of the responses.
+    pub worst_check: Option<CheckClass>,
+}
+
+impl Summary {
+    /// Summarizes the responses of a query, with their checks.
+    ///
+    /// The object is that of the first response, which is usually that of the registry.
+    /// The registrar and expiry are taken from the first response having them.
+    pub(crate) fn from_responses<'a>(
+        responses: impl IntoIterator<Item = (&'a RdapResponse, &'a Checks)>,
+        check_classes: &[CheckClass],
+        check_policy: &CheckPolicy,
+    ) -> Self {
+        let mut summary = Self::default();
+        for (rdap, checks) in responses {
+            if summary.object_class.is_none() {
+                summary.object_class = Some(object_class(rdap));
+                summary.identity = identity(rdap);
+            }
+            if summary.registrar.is_none() {
+                summary.registrar = registrar(rdap);
+            }
+            if summary.expiry.is_none() {
+                summary.expiry = events(rdap)
+                    .iter()
+                    .find(|event| event.event_action() == Some("expiration"))
+                    .and_then(|event| event.event_date())
+                    .map(str::to_string);
+            }
+            traverse_checks(checks, check_classes, None, &mut |_, item| {
+                if let Some(item) = check_policy.apply_item(item) {
+                    summary.worst_check = summary.worst_check.max(Some(item.check_class));
+                }
+            });
+        }
+        summary
+    }
+
+    /// Gets the summary as a line of tab separated fields, with `-` for those not known.
+    pub(crate) fn to_line(&self) -> String {
+        [
+            self.object_class.clone(),
+            self.identity.clone(),
+            self.registrar.clone(),
+            self.expiry.clone(),
+            self.worst_check.map(|class| class.to_string()),
+        ]
+        .map(|field| field.unwrap_or_else(|| UNKNOWN.to_string()))
+        .join("\t")
+    }
+}
+
+fn object_class(rdap: &RdapResponse) -> String {
+    match rdap {
+        RdapResponse::Entity(entity) => entity.object_class_name().to_string(),
+        RdapResponse::Domain(domain) => domain.object_class_name().to_string(),
+        RdapResponse::Nameserver(nameserver) => nameserver.object_class_name().to_string(),
+        RdapResponse::Autnum(autnum) => autnum.object_class_name().to_string(),
+        RdapResponse::Network(network) => network.object_class_name().to_string(),
+        RdapResponse::DomainSearchResults(_) => "domain search".to_string(),
+        RdapResponse::EntitySearchResults(_) => "entity search".to_string(),
+        RdapResponse::NameserverSearchResults(_) => "nameserver search".to_string(),
+        RdapResponse::IpSearchResults(_) => "ip network search".to_string(),
+        RdapResponse::AutnumSearchResults(_) => "autnum search".to_string(),
+        RdapResponse::ErrorResponse(_) => "error".to_string(),
+        RdapResponse::Help(_) => "help".to_string(),
+    }
+}
+
+/// Gets the identity of the object, which for searches is the number of results
+/// and for errors is the error code.
+fn identity(rdap: &RdapResponse) -> Option<String> {
+    match rdap {
+        RdapResponse::Entity(entity) => entity.handle().map(str::to_string),
+        RdapResponse::Domain(domain) => domain
+            .ldh_name()
+            .or(domain.unicode_name())
+            .or(domain.handle())
+            .map(str::to_string),
+        RdapResponse::Nameserver(nameserver) => nameserver
+            .ldh_name()
+            .or(nameserver.unicode_name())
+            .or(nameserver.handle())
+            .map(str::to_string),
+        RdapResponse::Autnum(autnum) => match (autnum.start_autnum(), autnum.end_autnum()) {
+            (Some(start), Some(end)) if start != end => Some(format!("{start}-{end}")),
+            (Some(start), _) => Some(start.to_string()),
+            _ => autnum.handle().map(str::to_string),
+        },
+        RdapResponse::Network(network) => match (network.start_address(), network.end_address()) {
+            (Some(start), Some(end)) => Some(format!("{start}-{end}")),
+            _ => network.handle().map(str::to_string),
+        },
+        RdapResponse::DomainSearchResults(results) => Some(results.results().len().to_string()),
+        RdapResponse::EntitySearchResults(results) => Some(results.results().len().to_string()),
+        RdapResponse::NameserverSearchResults(results) => Some(results.results().len().to_string()),
+        RdapResponse::IpSearchResults(results) => Some(results.results().len().to_string()),
+        RdapResponse::AutnumSearchResults(results) => Some(results.results().len().to_string()),
+        RdapResponse::ErrorResponse(error) => Some(error.error_code().to_string()),
+        RdapResponse::Help(_) => None,
+    }
+}
+
+/// Gets the name of the registrar entity of a domain, or its handle if it has no name.
+fn registrar(rdap: &RdapResponse) -> Option<String> {
+    let RdapResponse::Domain(domain) = rdap else {
+        return None;
+    };
+    let registrar = domain
+        .entities()
+        .iter()
+        .find(|entity| entity.roles().iter().any(|role| role == "registrar"))?;
+    entity_name(registrar)
+}
+
+fn entity_name(entity: &Entity) -> Option<String> {
+    entity
+        .contact()
+        .and_then(|contact| {
+            contact
+                .full_name()
+                .map(str::to_string)
+                .or_else(|| contact.organizational_names().first().cloned())
+        })
+        .or_else(|| entity.handle().map(str::to_string))
+}
+
+fn events(rdap: &RdapResponse) -> &[Event] {
+    match rdap {
+        RdapResponse::Entity(entity) => entity.events(),
+        RdapResponse::Domain(domain) => domain.events(),
+        RdapResponse::Nameserver(nameserver) => nameserver.events(),
+        RdapResponse::Autnum(autnum) => autnum.events(),
+        RdapResponse::Network(network) => network.events(),
+        _ => &[],
+    }
+}
+
+#[cfg(test)]
+#[allow(non_snake_case)]
+mod tests {
+    use icann_rdap_common::{
+        check::{Check, CheckClass, CheckPolicy, Checks, RdapStructure},
+        contact::Contact,
+        prelude::ToResponse,
+        response::{Domain, Entity, Event, RdapResponse, Rfc9083Error},
+    };
+
+    use super::Summary;
+
+    fn checks(items: Vec<Check>) -> Checks {
+        Checks {
+            rdap_struct: RdapStructure::Domain,
+            items: items.into_iter().map(|check| check.check_item()).collect(),
+            sub_checks: vec![],
+        }
+    }
+
+    #[test]
+    fn GIVEN_registry_and_registrar_domains_WHEN_summary_THEN_one_line_from_both() {
+        // GIVEN
+        let registry = Domain::builder()
+            .ldh_name("foo.example")
+            .entity(
+                Entity::builder()
+                    .handle("REG-1")
+                    .role("registrar")
+                    .contact(Contact::builder().full_name("Example Registrar").build())
+                    .build(),
+            )
+            .build()
+            .to_response();
+        let registrar = Domain::builder()
+            .ldh_name("foo.example")
+            .event(
+                Event::builder()
+                    .event_action("expiration")
+                    .event_date("2030-01-01T00:00:00Z")
+                    .build(),
+            )
+            .build()
+            .to_response();
+        let registry_checks = checks(vec![Check::IcannExpirationEventIsAbsent]);
+        let registrar_checks = checks(vec![]);
+
+        // WHEN
+        let actual = Summary::from_responses(
+            [
+                (&registry, &registry_checks),
+                (&registrar, &registrar_checks),
+            ],
+            &[CheckClass::StdWarning, CheckClass::IcannError],
+            &CheckPolicy::default(),
+        );
+
+        // THEN
+        assert_eq!(
+            actual.to_line(),
+            "domain\tfoo.example\tExample Registrar\t2030-01-01T00:00:00Z\tIcannErr"
+        );
+    }
+
+    #[test]
+    fn GIVEN_error_response_WHEN_summary_THEN_unknown_fields_are_dashes() {
+        // GIVEN
+        let error: RdapResponse = Rfc9083Error::response_obj()
+            .error_code(404)
+            .build()
+            .to_response();
+
+        // WHEN
+        let actual =
+            Summary::from_responses([(&error, &checks(vec![]))], &[], &CheckPolicy::default());
+
+        // THEN
+        assert_eq!(actual.to_line(), "error\t404\t-\t-\t-");
+    }
+}
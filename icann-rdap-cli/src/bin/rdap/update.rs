@@ -0,0 +1,178 @@
+use std::{fs, time::Duration};
+
+use {
+    chrono::{DateTime, Utc},
+    icann_rdap_cli::dirs,
+    icann_rdap_client::RdapClientError,
+    icann_rdap_common::VERSION,
+    serde::{Deserialize, Serialize},
+    serde_json::Value,
+    tracing::debug,
+};
+
+use crate::error::RdapCliError;
+
+/// The crates.io API URL for the crate of this program.
+pub(crate) const CRATES_IO_URL: &str = "https://crates.io/api/v1/crates/icann-rdap-cli";
+
+/// Automatic checks are done no more than once in this many seconds.
+const AUTO_CHECK_INTERVAL_SECS: i64 = 86400;
+
+/// The result of the last update check, kept to rate limit automatic checks.
+///
+/// Failed checks are kept too, with the latest version known before them if any,
+/// so that an unreachable crates.io is not tried on every run.
+#[derive(Debug, Serialize, Deserialize)]
+struct UpdateCheck {
+    checked: DateTime<Utc>,
+    #[serde(default)]
+    latest_version: Option<String>,
+}
+
+/// Checks for a newer release and prints the result.
+pub(crate) async fn check_update() -> Result<(), RdapCliError> {
+    let latest_version = latest_version().await?;
+    save_update_check(Some(&latest_version));
+    match upgrade_hint(&latest_version) {
+        Some(hint) => println!("{hint}"),
+        None => println!("This is the latest version ({}).", current_version()),
+    }
+    Ok(())
+}
+
+/// Checks for a newer release no more than once a day, printing a hint if there is one.
+///
+/// Errors are logged and otherwise ignored.
+pub(crate) async fn auto_check_update() {
+    let last_check = fs::read_to_string(dirs::update_check_path())
+        .ok()
+        .and_then(|json| serde_json::from_str::<UpdateCheck>(&json).ok());
+    let latest_version = match last_check {
+        Some(last_check)
+            if (Utc::now() - last_check.checked).num_seconds() < AUTO_CHECK_INTERVAL_SECS =>
+        {
+            last_check.latest_version
+        }
+        last_check => match latest_version().await {
+            Ok(latest_version) => {
+                save_update_check(Some(&latest_version));
+                Some(latest_version)
+            }
+            Err(e) => {
+                debug!("Unable to check for updates: {e}");
+                let known_version = last_check.and_then(|last_check| last_check.latest_version);
+                save_update_check(known_version.as_deref());
+                known_version
+            }
+        },
+    };
+    if let Some(hint) = latest_version.and_then(|v| upgrade_hint(&v)) {
+        eprintln!("\n{hint}\n");
+    }
+}
+
+async fn latest_version() -> Result<String, RdapCliError> {
+    let client = reqwest::Client::builder()
+        .user_agent(format!("icann-rdap-cli/{}", current_version()))
+        .timeout(Duration::from_secs(5))
+        .build()
+        .map_err(RdapClientError::from)?;
+    let response = client
+        .get(CRATES_IO_URL)
+        .send()
+        .await
+        .and_then(|r| r.error_for_status())
+        .map_err(RdapClientError::from)?;
+    let json: Value = response.json().await.map_err(RdapClientError::from)?;
+    json["crate"]["max_stable_version"]
+        .as_str()
+        .map(|v| v.to_string())
+        .ok_or(RdapCliError::UpdateCheckFailed)
+}
+
+fn save_update_check(latest_version: Option<&str>) {
+    let update_check = UpdateCheck {
+        checked: Utc::now(),
+        latest_version: latest_version.map(str::to_string),
+    };
+    let result = serde_json::to_string(&update_check)
+        .map_err(std::io::Error::from)
+        .and_then(|json| fs::write(dirs::update_check_path(), json));
+    if let Err(e) = result {
+        debug!("Unable to save update check: {e}");
+    }
+}
+
+/// The version of this program without any development build suffix.
+fn current_version() -> &'static str {
+    VERSION.split('_').next().unwrap_or(VERSION)
+}
+
+fn upgrade_hint(latest_version: &str) -> Option<String> {
+    is_newer(latest_version, current_version()).then(|| {
+        format!(
+            "A newer version of rdap is available: {latest_version} (this is {}).\n\
+            Upgrade with 'cargo install icann-rdap-cli' or see https://github.com/icann/icann-rdap/releases",
+            current_version()
+        )
+    })
+}
+
+/// Returns true if version `a` is newer than version `b`, comparing dotted numbers.
+fn is_newer(a: &str, b: &str) -> bool {
+    let parse = |v: &str| {
+        v.split('.')
+            .map(|n| n.parse::<u64>().unwrap_or(0))
+            .collect::<Vec<u64>>()
+    };
+    parse(a) > parse(b)
+}
+
+#[cfg(test)]
+#[allow(non_snake_case)]
+mod tests {
+    use super::{is_newer, UpdateCheck};
+
+    #[test]
+    fn GIVEN_newer_patch_WHEN_is_newer_THEN_true() {
+        // GIVEN
+        let latest = "0.0.24";
+        let current = "0.0.23";
+
+        // WHEN
+        let actual = is_newer(latest, current);
+
+        // THEN
+        assert!(actual);
+    }
+
+    #[test]
+    fn GIVEN_same_or_older_WHEN_is_newer_THEN_false() {
+        // GIVEN
+        let current = "0.1.10";
+
+        // WHEN
+        let same = is_newer("0.1.10", current);
+        let older = is_newer("0.1.9", current);
+
+        // THEN
+        assert!(!same);
+        assert!(!older);
+    }
+
+    #[test]
+    fn GIVEN_failed_check_saved_WHEN_deserialize_THEN_no_latest_version() {
+        // GIVEN
+        let failed = r#"{"checked":"2026-01-01T00:00:00Z","latest_version":null}"#;
+        let succeeded = r#"{"checked":"2026-01-01T00:00:00Z","latest_version":"0.0.24"}"#;
+
+        // WHEN
+        let failed: UpdateCheck = serde_json::from_str(failed).expect("parsing failed check");
+        let succeeded: UpdateCheck =
+            serde_json::from_str(succeeded).expect("parsing successful check");
+
+        // THEN
+        assert!(failed.latest_version.is_none());
+        assert_eq!(succeeded.latest_version.as_deref(), Some("0.0.24"));
+    }
+}
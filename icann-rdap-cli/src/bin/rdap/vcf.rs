@@ -0,0 +1,171 @@
+//! Export of the contacts of entities as vCard (`.vcf`) text.
+
+use std::collections::HashSet;
+
+use icann_rdap_common::{
+    contact::Contact,
+    response::{Entity, ObjectCommonFields, RdapResponse},
+};
+
+/// The contact of an entity, with the handle of the entity if it has one.
+pub(crate) struct EntityContact {
+    pub handle: Option<String>,
+    pub contact: Contact,
+}
+
+impl EntityContact {
+    /// Gets the name of the file of the contact, from the handle of the entity
+    /// or else the given index.
+    pub(crate) fn file_name(&self, index: usize) -> String {
+        let stem = self
+            .handle
+            .as_deref()
+            .map(|handle| {
+                handle
+                    .chars()
+                    .map(|c| {
+                        if c.is_ascii_alphanumeric() || c == '-' || c == '_' || c == '.' {
+                            c
+                        } else {
+                            '_'
+                        }
+                    })
+                    .collect::<String>()
+            })
+            .filter(|stem| !stem.trim_matches('.').is_empty())
+            .unwrap_or_else(|| format!("entity-{index}"));
+        format!("{stem}.vcf")
+    }
+}
+
+/// Gets the contacts of all the entities in responses, including the entities
+/// of the entities. An entity found more than once, by handle or by contact,
+/// is given once.
+pub(crate) fn entity_contacts<'a>(
+    responses: impl IntoIterator<Item = &'a RdapResponse>,
+) -> Vec<EntityContact> {
+    let mut entities = vec![];
+    for rdap in responses {
+        match rdap {
+            RdapResponse::Entity(entity) => collect(entity, &mut entities),
+            RdapResponse::Domain(domain) => {
+                collect_all(domain.entities(), &mut entities);
+                for nameserver in domain.nameservers() {
+                    collect_all(nameserver.entities(), &mut entities);
+                }
+            }
+            RdapResponse::Nameserver(nameserver) => {
+                collect_all(nameserver.entities(), &mut entities)
+            }
+            RdapResponse::Autnum(autnum) => collect_all(autnum.entities(), &mut entities),
+            RdapResponse::Network(network) => collect_all(network.entities(), &mut entities),
+            RdapResponse::DomainSearchResults(results) => {
+                for domain in results.results() {
+                    collect_all(domain.entities(), &mut entities);
+                }
+            }
+            RdapResponse::EntitySearchResults(results) => {
+                collect_all(results.results(), &mut entities)
+            }
+            RdapResponse::NameserverSearchResults(results) => {
+                for nameserver in results.results() {
+                    collect_all(nameserver.entities(), &mut entities);
+                }
+            }
+            RdapResponse::IpSearchResults(results) => {
+                for network in results.results() {
+                    collect_all(network.entities(), &mut entities);
+                }
+            }
+            RdapResponse::AutnumSearchResults(results) => {
+                for autnum in results.results() {
+                    collect_all(autnum.entities(), &mut entities);
+                }
+            }
+            RdapResponse::ErrorResponse(_) | RdapResponse::Help(_) => {}
+        }
+    }
+
+    let mut seen = HashSet::new();
+    entities
+        .into_iter()
+        .filter_map(|entity| {
+            let contact = entity.contact()?;
+            let key = entity
+                .handle()
+                .map(|handle| format!("handle:{handle}"))
+                .unwrap_or_else(|| contact.to_vcard_text());
+            seen.insert(key).then(|| EntityContact {
+                handle: entity.handle().map(str::to_string),
+                contact: contact.clone(),
+            })
+        })
+        .collect()
+}
+
+fn collect_all<'a>(entities: &'a [Entity], found: &mut Vec<&'a Entity>) {
+    for entity in entities {
+        collect(entity, found);
+    }
+}
+
+fn collect<'a>(entity: &'a Entity, found: &mut Vec<&'a Entity>) {
+    found.push(entity);
+    collect_all(entity.entities(), found);
+}
+
+#[cfg(test)]
+#[allow(non_snake_case)]
+mod tests {
+    use icann_rdap_common::{
+        contact::Contact,
+        response::{Domain, Entity, RdapResponse},
+    };
+
+    use super::entity_contacts;
+
+    fn entity(handle: &str, name: &str) -> Entity {
+        Entity::builder()
+            .handle(handle)
+            .contact(Contact::builder().full_name(name).build())
+            .build()
+    }
+
+    #[test]
+    fn GIVEN_domain_with_nested_entities_WHEN_entity_contacts_THEN_all_found_once() {
+        // GIVEN
+        let registrar = Entity::builder()
+            .handle("REG-1")
+            .contact(Contact::builder().full_name("Registrar").build())
+            .entities(vec![entity("ABUSE-1", "Abuse")])
+            .build();
+        let domain = Domain::builder()
+            .ldh_name("foo.example")
+            .entities(vec![registrar, entity("ABUSE-1", "Abuse")])
+            .build();
+        let responses = [RdapResponse::Domain(Box::new(domain))];
+
+        // WHEN
+        let actual = entity_contacts(&responses);
+
+        // THEN
+        let handles = actual
+            .iter()
+            .map(|c| c.handle.as_deref().expect("handle"))
+            .collect::<Vec<_>>();
+        assert_eq!(handles, vec!["REG-1", "ABUSE-1"]);
+    }
+
+    #[test]
+    fn GIVEN_handle_with_path_characters_WHEN_file_name_THEN_sanitized() {
+        // GIVEN
+        let contacts =
+            entity_contacts(&[RdapResponse::Entity(Box::new(entity("../ABC/1", "Joe")))]);
+
+        // WHEN
+        let actual = contacts[0].file_name(0);
+
+        // THEN
+        assert_eq!(actual, ".._ABC_1.vcf");
+    }
+}
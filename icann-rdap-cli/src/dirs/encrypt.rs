@@ -0,0 +1,217 @@
+//! Encryption of cache files at rest.
+
+use std::{
+    fs,
+    io::{Error, ErrorKind},
+    num::NonZeroU32,
+    path::Path,
+};
+
+use {
+    base64::{engine::general_purpose::STANDARD, Engine},
+    icann_rdap_client::rdap::ContentsCipher,
+    ring::{
+        aead::{Aad, LessSafeKey, Nonce, UnboundKey, AES_256_GCM, NONCE_LEN},
+        hmac, pbkdf2,
+        rand::{SecureRandom, SystemRandom},
+    },
+};
+
+use super::lock::{write_atomic, DirLock};
+
+/// The first line of an encrypted cache file.
+pub const ENCRYPTED_MARKER: &str = "#rdap-encrypted-v1";
+
+pub const SALT_FILE_NAME: &str = ".salt";
+
+const SALT_LEN: usize = 16;
+const PBKDF2_ITERATIONS: u32 = 100_000;
+
+/// Encrypts and decrypts cache files with a key derived from a passphrase.
+///
+/// The names of the cache files are HMAC-SHA256 hashes of their URLs, under a
+/// second key derived from the passphrase.
+pub struct CacheCipher {
+    key: LessSafeKey,
+    url_key: hmac::Key,
+    rng: SystemRandom,
+}
+
+impl CacheCipher {
+    /// Derives the key for a cache directory from a passphrase.
+    ///
+    /// The salt for the key is kept in the directory and is created if
+    /// it does not exist.
+    pub fn new(dir: &Path, passphrase: &str) -> Result<Self, Error> {
+        let rng = SystemRandom::new();
+        let salt = {
+            let _lock = DirLock::exclusive(dir)?;
+            let salt_path = dir.join(SALT_FILE_NAME);
+            match fs::read(&salt_path) {
+                Ok(salt) => salt,
+                Err(e) if e.kind() == ErrorKind::NotFound => {
+                    let mut salt = [0; SALT_LEN];
+                    rng.fill(&mut salt).map_err(|_| random_error())?;
+                    write_atomic(&salt_path, salt)?;
+                    salt.to_vec()
+                }
+                Err(e) => return Err(e),
+            }
+        };
+        Ok(Self::with_salt(&salt, passphrase, rng))
+    }
+
+    fn with_salt(salt: &[u8], passphrase: &str, rng: SystemRandom) -> Self {
+        // the first 32 bytes are the encryption key and the last 32 the HMAC key
+        let mut key = [0; 64];
+        pbkdf2::derive(
+            pbkdf2::PBKDF2_HMAC_SHA256,
+            NonZeroU32::new(PBKDF2_ITERATIONS).expect("iterations must be non-zero"),
+            salt,
+            passphrase.as_bytes(),
+            &mut key,
+        );
+        let (key, url_key) = key.split_at(32);
+        let key = LessSafeKey::new(
+            UnboundKey::new(&AES_256_GCM, key).expect("key must be the correct length"),
+        );
+        let url_key = hmac::Key::new(hmac::HMAC_SHA256, url_key);
+        Self { key, url_key, rng }
+    }
+
+    /// Encrypts the contents of a cache file.
+    pub fn encrypt(&self, contents: &str) -> Result<String, Error> {
+        let mut nonce = [0; NONCE_LEN];
+        self.rng.fill(&mut nonce).map_err(|_| random_error())?;
+        let mut in_out = contents.as_bytes().to_vec();
+        self.key
+            .seal_in_place_append_tag(
+                Nonce::assume_unique_for_key(nonce),
+                Aad::empty(),
+                &mut in_out,
+            )
+            .map_err(|_| Error::other("unable to encrypt cache contents"))?;
+        let mut sealed = nonce.to_vec();
+        sealed.append(&mut in_out);
+        Ok(format!("{ENCRYPTED_MARKER}\n{}\n", STANDARD.encode(sealed)))
+    }
+
+    /// Decrypts the lines of an encrypted cache file.
+    pub fn decrypt_lines(&self, lines: &[String]) -> Result<Vec<String>, Error> {
+        let sealed = lines
+            .get(1)
+            .and_then(|line| STANDARD.decode(line.trim()).ok())
+            .filter(|sealed| sealed.len() >= NONCE_LEN)
+            .ok_or_else(decrypt_error)?;
+        let (nonce, ciphertext) = sealed.split_at(NONCE_LEN);
+        let nonce = Nonce::try_assume_unique_for_key(nonce).map_err(|_| decrypt_error())?;
+        let mut in_out = ciphertext.to_vec();
+        let plain = self
+            .key
+            .open_in_place(nonce, Aad::empty(), &mut in_out)
+            .map_err(|_| decrypt_error())?;
+        let plain = std::str::from_utf8(plain).map_err(|_| decrypt_error())?;
+        Ok(plain.lines().map(|line| line.to_string()).collect())
+    }
+
+    /// Returns the hex encoded HMAC of a URL.
+    pub fn url_hash(&self, url: &str) -> String {
+        hmac::sign(&self.url_key, url.as_bytes())
+            .as_ref()
+            .iter()
+            .map(|b| format!("{b:02x}"))
+            .collect()
+    }
+}
+
+impl ContentsCipher for CacheCipher {
+    fn encrypt(&self, contents: &str) -> Result<String, Error> {
+        CacheCipher::encrypt(self, contents)
+    }
+
+    fn decrypt_lines(&self, lines: &[String]) -> Result<Vec<String>, Error> {
+        CacheCipher::decrypt_lines(self, lines)
+    }
+
+    fn is_encrypted(&self, lines: &[String]) -> bool {
+        is_encrypted(lines)
+    }
+
+    fn url_hash(&self, url: &str) -> String {
+        CacheCipher::url_hash(self, url)
+    }
+}
+
+/// Returns true if the lines are of an encrypted cache file.
+pub fn is_encrypted(lines: &[String]) -> bool {
+    lines.first().is_some_and(|line| line == ENCRYPTED_MARKER)
+}
+
+fn random_error() -> Error {
+    Error::other("unable to generate random bytes")
+}
+
+fn decrypt_error() -> Error {
+    Error::new(ErrorKind::InvalidData, "unable to decrypt cache contents")
+}
+
+#[cfg(test)]
+#[allow(non_snake_case)]
+mod test {
+    use ring::rand::SystemRandom;
+
+    use super::{is_encrypted, CacheCipher};
+
+    #[test]
+    fn GIVEN_encrypted_contents_WHEN_decrypt_THEN_original_lines() {
+        // GIVEN
+        let cipher = CacheCipher::with_salt(b"salt", "passphrase", SystemRandom::new());
+        let encrypted = cipher.encrypt("foo\nbar\n").expect("encrypting");
+        let lines = encrypted
+            .lines()
+            .map(|line| line.to_string())
+            .collect::<Vec<String>>();
+
+        // WHEN
+        let actual = cipher.decrypt_lines(&lines).expect("decrypting");
+
+        // THEN
+        assert!(is_encrypted(&lines));
+        assert_eq!(actual, vec!["foo", "bar"]);
+    }
+
+    #[test]
+    fn GIVEN_wrong_passphrase_WHEN_decrypt_THEN_error() {
+        // GIVEN
+        let cipher = CacheCipher::with_salt(b"salt", "passphrase", SystemRandom::new());
+        let encrypted = cipher.encrypt("foo\nbar\n").expect("encrypting");
+        let lines = encrypted
+            .lines()
+            .map(|line| line.to_string())
+            .collect::<Vec<String>>();
+        let wrong = CacheCipher::with_salt(b"salt", "wrong", SystemRandom::new());
+
+        // WHEN
+        let actual = wrong.decrypt_lines(&lines);
+
+        // THEN
+        assert!(actual.is_err());
+    }
+
+    #[test]
+    fn GIVEN_url_WHEN_url_hash_THEN_keyed_by_passphrase() {
+        // GIVEN
+        let url = "https://rdap.example/domain/foo.example";
+        let cipher = CacheCipher::with_salt(b"salt", "passphrase", SystemRandom::new());
+        let other = CacheCipher::with_salt(b"salt", "other", SystemRandom::new());
+
+        // WHEN
+        let actual = cipher.url_hash(url);
+
+        // THEN
+        assert_eq!(actual.len(), 64);
+        assert!(!actual.contains("rdap"));
+        assert_eq!(actual, cipher.url_hash(url));
+        assert_ne!(actual, other.url_hash(url));
+    }
+}
@@ -1,4 +1,6 @@
-pub mod fcbs;
+pub mod encrypt;
 pub mod project;
 
+pub use icann_rdap_client::files::{lock, lru};
+
 pub use project::*;
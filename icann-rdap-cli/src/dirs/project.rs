@@ -1,5 +1,5 @@
 use std::{
-    fs::{create_dir_all, remove_dir_all, write},
+    fs::{create_dir_all, remove_dir_all},
     path::PathBuf,
     sync::LazyLock,
 };
@@ -13,6 +13,11 @@ pub const APPLICATION: &str = "rdap";
 pub const ENV_FILE_NAME: &str = "rdap.env";
 pub const RDAP_CACHE_NAME: &str = "rdap_cache";
 pub const BOOTSTRAP_CACHE_NAME: &str = "bootstrap_cache";
+pub const RDAP_TEST_HISTORY_NAME: &str = "rdap_test_history";
+pub const UPDATE_CHECK_NAME: &str = "update_check.json";
+
+/// The commented template of the configuration file.
+pub const EXAMPLE_CONFIG: &str = include_str!("rdap.env");
 
 pub(crate) static PROJECT_DIRS: LazyLock<ProjectDirs> = LazyLock::new(|| {
     ProjectDirs::from(QUALIFIER, ORGANIZATION, APPLICATION)
@@ -25,12 +30,7 @@ pub fn init() -> Result<(), std::io::Error> {
     create_dir_all(PROJECT_DIRS.cache_dir())?;
     create_dir_all(rdap_cache_path())?;
     create_dir_all(bootstrap_cache_path())?;
-
-    // create default config file
-    if !config_path().exists() {
-        let example_config = include_str!("rdap.env");
-        write(config_path(), example_config)?;
-    }
+    create_dir_all(rdap_test_history_path())?;
     Ok(())
 }
 
@@ -63,3 +63,21 @@ pub fn bootstrap_cache_path() -> PathBuf {
         PROJECT_DIRS.cache_dir().join(BOOTSTRAP_CACHE_NAME)
     }
 }
+
+/// Returns a [PathBuf] to the file recording the last check for a newer release.
+pub fn update_check_path() -> PathBuf {
+    if let Ok(xdg_cache) = std::env::var("XDG_CACHE_HOME") {
+        PathBuf::from(xdg_cache).join(UPDATE_CHECK_NAME)
+    } else {
+        PROJECT_DIRS.cache_dir().join(UPDATE_CHECK_NAME)
+    }
+}
+
+/// Returns a [PathBuf] to the data directory for the history of `rdap-test` runs.
+pub fn rdap_test_history_path() -> PathBuf {
+    if let Ok(xdg_data) = std::env::var("XDG_DATA_HOME") {
+        PathBuf::from(xdg_data).join(RDAP_TEST_HISTORY_NAME)
+    } else {
+        PROJECT_DIRS.data_dir().join(RDAP_TEST_HISTORY_NAME)
+    }
+}
@@ -0,0 +1,133 @@
+//! SVG badges of the conformance of a server.
+
+use std::fmt::Display;
+
+use super::history::RunSummary;
+
+/// The label of a badge.
+const LABEL: &str = "rdap conformance";
+
+/// The conformance shown by a badge.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BadgeStatus {
+    /// All test runs executed without error or warning checks.
+    Pass,
+
+    /// All test runs executed without error checks, but with warning checks.
+    Warn,
+
+    /// A test run could not be executed or there were error checks.
+    Fail,
+}
+
+impl BadgeStatus {
+    /// Gets the status of a summary of the tests.
+    pub fn from_summary(summary: &RunSummary) -> Self {
+        if summary.execution_errors > 0 || summary.error_checks > 0 {
+            Self::Fail
+        } else if summary.warning_checks > 0 {
+            Self::Warn
+        } else {
+            Self::Pass
+        }
+    }
+
+    fn color(&self) -> &'static str {
+        match self {
+            Self::Pass => "#4c1",
+            Self::Warn => "#dfb317",
+            Self::Fail => "#e05d44",
+        }
+    }
+}
+
+impl Display for BadgeStatus {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let message = match self {
+            Self::Pass => "pass",
+            Self::Warn => "warn",
+            Self::Fail => "fail",
+        };
+        write!(f, "{message}")
+    }
+}
+
+/// Renders a badge of the status as SVG, in the flat style common on status pages.
+pub fn badge_svg(status: BadgeStatus) -> String {
+    let message = status.to_string();
+    let label_width = text_width(LABEL);
+    let message_width = text_width(&message);
+    let width = label_width + message_width;
+    let color = status.color();
+    let label_x = label_width * 5;
+    let message_x = (label_width + message_width / 2) * 10;
+    format!(
+        r##"<svg xmlns="http://www.w3.org/2000/svg" width="{width}" height="20" role="img" aria-label="{LABEL}: {message}">
+<title>{LABEL}: {message}</title>
+<linearGradient id="s" x2="0" y2="100%"><stop offset="0" stop-color="#bbb" stop-opacity=".1"/><stop offset="1" stop-opacity=".1"/></linearGradient>
+<clipPath id="r"><rect width="{width}" height="20" rx="3" fill="#fff"/></clipPath>
+<g clip-path="url(#r)"><rect width="{label_width}" height="20" fill="#555"/><rect x="{label_width}" width="{message_width}" height="20" fill="{color}"/><rect width="{width}" height="20" fill="url(#s)"/></g>
+<g fill="#fff" text-anchor="middle" font-family="Verdana,Geneva,DejaVu Sans,sans-serif" font-size="110" transform="scale(.1)"><text x="{label_x}" y="140">{LABEL}</text><text x="{message_x}" y="140">{message}</text></g>
+</svg>
+"##
+    )
+}
+
+/// Approximates the width of text in 11px Verdana, with padding.
+fn text_width(text: &str) -> usize {
+    text.chars().count() * 7 + 10
+}
+
+#[cfg(test)]
+#[allow(non_snake_case)]
+mod tests {
+    use chrono::Utc;
+    use rstest::rstest;
+
+    use super::{badge_svg, BadgeStatus};
+    use crate::rt::history::RunSummary;
+
+    #[rstest]
+    #[case(0, 0, 0, BadgeStatus::Pass)]
+    #[case(0, 0, 2, BadgeStatus::Warn)]
+    #[case(0, 1, 2, BadgeStatus::Fail)]
+    #[case(1, 0, 0, BadgeStatus::Fail)]
+    fn GIVEN_summary_WHEN_from_summary_THEN_status(
+        #[case] execution_errors: usize,
+        #[case] error_checks: usize,
+        #[case] warning_checks: usize,
+        #[case] expected: BadgeStatus,
+    ) {
+        // GIVEN
+        let summary = RunSummary {
+            query_url: "https://example.com/domain/foo.example".to_string(),
+            start_time: Utc::now(),
+            end_time: None,
+            test_runs: 4,
+            tested: 4 - execution_errors,
+            execution_errors,
+            error_checks,
+            warning_checks,
+        };
+
+        // WHEN
+        let actual = BadgeStatus::from_summary(&summary);
+
+        // THEN
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn GIVEN_warn_WHEN_badge_svg_THEN_message_and_color() {
+        // GIVEN
+        let status = BadgeStatus::Warn;
+
+        // WHEN
+        let actual = badge_svg(status);
+
+        // THEN
+        assert!(actual.starts_with("<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"160\""));
+        assert!(actual.contains("aria-label=\"rdap conformance: warn\""));
+        assert!(actual.contains("fill=\"#dfb317\""));
+    }
+}
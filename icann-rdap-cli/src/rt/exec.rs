@@ -6,25 +6,32 @@ use std::{
 };
 
 use {
+    futures::{stream, StreamExt},
     hickory_client::{
         client::{AsyncClient, ClientConnection, ClientHandle},
         rr::{DNSClass, Name, RecordType},
         udp::UdpClientConnection,
     },
     icann_rdap_client::{
-        http::{create_client, create_client_with_addr, ClientConfig},
+        http::{create_client, create_client_with_addr, Client, ClientConfig},
         iana::{qtype_to_bootstrap_url, BootstrapStore},
         rdap::{rdap_url_request, QueryType},
         RdapClientError,
     },
-    icann_rdap_common::response::{get_related_links, ExtensionId},
+    icann_rdap_common::{
+        check::ProfileId,
+        response::{get_related_links, ExtensionId},
+    },
     reqwest::{header::HeaderValue, Url},
     thiserror::Error,
     tracing::{debug, info},
-    url::ParseError,
+    url::{ParseError, Position},
 };
 
-use crate::rt::results::{RunFeature, TestRun};
+use crate::rt::{
+    results::{RunFeature, TestRun},
+    tls::{inspect_tls, TlsInspection},
+};
 
 use super::results::{DnsData, TestResults};
 
@@ -38,8 +45,19 @@ pub struct TestOptions {
     pub expect_extensions: Vec<String>,
     pub expect_groups: Vec<ExtensionGroup>,
     pub allow_unregistered_extensions: bool,
+    /// The profiles to check responses against, or if `None` those declared by the
+    /// responses and the NRO profile if an NRO extension group is expected.
+    pub profiles: Option<Vec<ProfileId>>,
     pub one_addr: bool,
     pub dns_resolver: Option<String>,
+    /// The name sent in the TLS SNI instead of the host of the query URL.
+    pub sni: Option<String>,
+    /// The value of the Host header instead of the host of the query URL.
+    pub host_header: Option<String>,
+    /// Inspects the TLS connections of HTTPS test runs if given.
+    pub tls_inspection: Option<TlsInspection>,
+    /// The number of test runs executed at the same time, where `0` is the same as `1`.
+    pub parallel: usize,
 }
 
 #[derive(Clone)]
@@ -90,6 +108,9 @@ pub async fn execute_tests<BS: BootstrapStore>(
         expect_groups: options.expect_groups.clone(),
         origin_value: options.origin_value.clone(),
         dns_resolver: options.dns_resolver.clone(),
+        profiles: options.profiles.clone(),
+        sni: options.sni.clone(),
+        host_header: options.host_header.clone(),
         ..*options
     };
 
@@ -129,34 +150,71 @@ pub async fn execute_tests<BS: BootstrapStore>(
         .host_str()
         .ok_or(TestExecutionError::NoHostToResolve)?;
 
+    // the requests are sent to the SNI name, which is resolved to the tested addresses,
+    // with the Host header of the query URL unless it is given
+    let sni = options.sni.as_deref().unwrap_or(host);
+    let request_url = if sni == host {
+        query_url.clone()
+    } else {
+        let mut request_url = parsed_url.clone();
+        request_url.set_host(Some(sni))?;
+        request_url.to_string()
+    };
+    let host_header = options
+        .host_header
+        .as_deref()
+        .or((sni != host).then_some(&parsed_url[Position::BeforeHost..Position::AfterPort]));
+    let client_config = &match host_header {
+        Some(host_header) => ClientConfig::from_config(client_config)
+            .host(HeaderValue::from_str(host_header)?)
+            .build(),
+        None => ClientConfig::from_config(client_config).build(),
+    };
+    let mut features = vec![];
+    if options.sni.is_some() {
+        features.push(RunFeature::Sni);
+    }
+    if options.host_header.is_some() {
+        features.push(RunFeature::HostHeader);
+    }
+    let origin_features = [vec![RunFeature::OriginHeader], features.clone()].concat();
+
     info!("Testing {query_url}");
     let dns_data = get_dns_records(host, options).await?;
     let mut test_results = TestResults::new(query_url.clone(), dns_data.clone());
 
+    // plan the test runs, with clients for those not skipped
+    let mut planned_runs: Vec<(TestRun, Option<Client>)> = vec![];
     let mut more_runs = true;
     for v4 in dns_data.v4_addrs {
         // test run without origin
-        let mut test_run = TestRun::new_v4(vec![], v4, port);
-        if !options.skip_v4 && more_runs {
-            let client = create_client_with_addr(client_config, host, test_run.socket_addr)?;
-            info!("Sending request to {}", test_run.socket_addr);
-            let rdap_response = rdap_url_request(&query_url, &client).await;
-            test_run = test_run.end(rdap_response, options);
-        }
-        test_results.add_test_run(test_run);
+        let test_run = TestRun::new_v4(features.clone(), v4, port);
+        let client = if !options.skip_v4 && more_runs {
+            Some(create_client_with_addr(
+                client_config,
+                sni,
+                test_run.socket_addr,
+            )?)
+        } else {
+            None
+        };
+        planned_runs.push((test_run, client));
 
         // test run with origin
-        let mut test_run = TestRun::new_v4(vec![RunFeature::OriginHeader], v4, port);
-        if !options.skip_v4 && !options.skip_origin && more_runs {
+        let test_run = TestRun::new_v4(origin_features.clone(), v4, port);
+        let client = if !options.skip_v4 && !options.skip_origin && more_runs {
             let client_config = ClientConfig::from_config(client_config)
                 .origin(HeaderValue::from_str(&options.origin_value)?)
                 .build();
-            let client = create_client_with_addr(&client_config, host, test_run.socket_addr)?;
-            info!("Sending request to {}", test_run.socket_addr);
-            let rdap_response = rdap_url_request(&query_url, &client).await;
-            test_run = test_run.end(rdap_response, options);
-        }
-        test_results.add_test_run(test_run);
+            Some(create_client_with_addr(
+                &client_config,
+                sni,
+                test_run.socket_addr,
+            )?)
+        } else {
+            None
+        };
+        planned_runs.push((test_run, client));
         if options.one_addr {
             more_runs = false;
         }
@@ -165,32 +223,70 @@ pub async fn execute_tests<BS: BootstrapStore>(
     let mut more_runs = true;
     for v6 in dns_data.v6_addrs {
         // test run without origin
-        let mut test_run = TestRun::new_v6(vec![], v6, port);
-        if !options.skip_v6 && more_runs {
-            let client = create_client_with_addr(client_config, host, test_run.socket_addr)?;
-            info!("Sending request to {}", test_run.socket_addr);
-            let rdap_response = rdap_url_request(&query_url, &client).await;
-            test_run = test_run.end(rdap_response, options);
-        }
-        test_results.add_test_run(test_run);
+        let test_run = TestRun::new_v6(features.clone(), v6, port);
+        let client = if !options.skip_v6 && more_runs {
+            Some(create_client_with_addr(
+                client_config,
+                sni,
+                test_run.socket_addr,
+            )?)
+        } else {
+            None
+        };
+        planned_runs.push((test_run, client));
 
         // test run with origin
-        let mut test_run = TestRun::new_v6(vec![RunFeature::OriginHeader], v6, port);
-        if !options.skip_v6 && !options.skip_origin && more_runs {
+        let test_run = TestRun::new_v6(origin_features.clone(), v6, port);
+        let client = if !options.skip_v6 && !options.skip_origin && more_runs {
             let client_config = ClientConfig::from_config(client_config)
                 .origin(HeaderValue::from_str(&options.origin_value)?)
                 .build();
-            let client = create_client_with_addr(&client_config, host, test_run.socket_addr)?;
-            info!("Sending request to {}", test_run.socket_addr);
-            let rdap_response = rdap_url_request(&query_url, &client).await;
-            test_run = test_run.end(rdap_response, options);
-        }
-        test_results.add_test_run(test_run);
+            Some(create_client_with_addr(
+                &client_config,
+                sni,
+                test_run.socket_addr,
+            )?)
+        } else {
+            None
+        };
+        planned_runs.push((test_run, client));
         if options.one_addr {
             more_runs = false;
         }
     }
 
+    // execute the test runs, keeping the results in the order they were planned
+    let request_url = &request_url;
+    let tls_inspection = options
+        .tls_inspection
+        .filter(|_| parsed_url.scheme() == "https");
+    let test_runs = stream::iter(planned_runs)
+        .map(|(test_run, client)| async move {
+            let Some(client) = client else {
+                return test_run;
+            };
+            let tls = match &tls_inspection {
+                Some(tls_inspection) => {
+                    info!("Inspecting TLS of {}", test_run.socket_addr);
+                    Some(inspect_tls(sni, test_run.socket_addr, tls_inspection).await)
+                }
+                None => None,
+            };
+            info!("Sending request to {}", test_run.socket_addr);
+            let rdap_response = rdap_url_request(request_url, &client).await;
+            let test_run = test_run.end(rdap_response, options);
+            match tls {
+                Some(tls) => test_run.with_tls(tls),
+                None => test_run,
+            }
+        })
+        .buffered(options.parallel.max(1))
+        .collect::<Vec<_>>()
+        .await;
+    for test_run in test_runs {
+        test_results.add_test_run(test_run);
+    }
+
     test_results.end(options);
     info!("Testing complete.");
     Ok(test_results)
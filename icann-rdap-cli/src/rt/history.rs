@@ -0,0 +1,189 @@
+//! History of test executions.
+
+use std::{
+    fs::OpenOptions,
+    io::Write,
+    path::{Path, PathBuf},
+};
+
+use {
+    chrono::{DateTime, Utc},
+    icann_rdap_common::check::{traverse_checks, CheckClass},
+    pct_str::{PctString, URIReserved},
+    serde::{Deserialize, Serialize},
+};
+
+use crate::dirs::lock::{locked_read_lines, DirLock};
+
+use super::results::{RunOutcome, TestResults};
+
+/// A summary of one execution of the tests, suitable for keeping a history.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
+pub struct RunSummary {
+    pub query_url: String,
+    pub start_time: DateTime<Utc>,
+    pub end_time: Option<DateTime<Utc>>,
+    pub test_runs: usize,
+    pub tested: usize,
+    pub execution_errors: usize,
+    pub error_checks: usize,
+    pub warning_checks: usize,
+}
+
+impl RunSummary {
+    /// Summarizes test results, counting only the given check classes.
+    pub fn from_results(test_results: &TestResults, check_classes: &[CheckClass]) -> Self {
+        let error_classes = check_classes
+            .iter()
+            .filter(|c| {
+                matches!(
+                    c,
+                    CheckClass::StdError
+                        | CheckClass::Cidr0Error
+                        | CheckClass::IcannError
+                        | CheckClass::NroError
+                )
+            })
+            .copied()
+            .collect::<Vec<CheckClass>>();
+        let warning_classes = check_classes
+            .iter()
+            .filter(|c| matches!(c, CheckClass::StdWarning))
+            .copied()
+            .collect::<Vec<CheckClass>>();
+        Self {
+            query_url: test_results.query_url.clone(),
+            start_time: test_results.start_time,
+            end_time: test_results.end_time,
+            test_runs: test_results.test_runs.len(),
+            tested: test_results
+                .test_runs
+                .iter()
+                .filter(|r| matches!(r.outcome, RunOutcome::Tested))
+                .count(),
+            execution_errors: test_results
+                .test_runs
+                .iter()
+                .filter(|r| !matches!(r.outcome, RunOutcome::Tested | RunOutcome::Skipped))
+                .count(),
+            error_checks: count_checks(test_results, &error_classes),
+            warning_checks: count_checks(test_results, &warning_classes),
+        }
+    }
+
+    /// Returns true if this summary shows worse conformance than a previous summary.
+    pub fn is_regression_of(&self, previous: &Self) -> bool {
+        self.execution_errors > previous.execution_errors
+            || self.error_checks > previous.error_checks
+            || self.warning_checks > previous.warning_checks
+    }
+}
+
+fn count_checks(test_results: &TestResults, classes: &[CheckClass]) -> usize {
+    let mut count = test_results
+        .service_checks
+        .iter()
+        .filter(|c| classes.contains(&c.check_class))
+        .count();
+    for checks in test_results
+        .test_runs
+        .iter()
+        .filter_map(|r| r.checks.as_ref())
+    {
+        traverse_checks(checks, classes, None, &mut |_, _| count += 1);
+    }
+    count
+}
+
+/// Returns the path of the history file for a query URL in a history directory.
+pub fn history_file(dir: &Path, query_url: &str) -> PathBuf {
+    dir.join(format!(
+        "{}.jsonl",
+        PctString::encode(query_url.chars(), URIReserved)
+    ))
+}
+
+/// Appends a summary to a history file, one JSON object per line.
+pub fn append_history(path: &Path, summary: &RunSummary) -> Result<(), std::io::Error> {
+    let _lock = match path.parent() {
+        Some(dir) => Some(DirLock::exclusive(dir)?),
+        None => None,
+    };
+    let mut file = OpenOptions::new().create(true).append(true).open(path)?;
+    writeln!(file, "{}", serde_json::to_string(summary)?)?;
+    Ok(())
+}
+
+/// Reads the last summary from a history file, if there is one.
+pub fn last_history(path: &Path) -> Result<Option<RunSummary>, std::io::Error> {
+    if !path.exists() {
+        return Ok(None);
+    }
+    let last = locked_read_lines(path)?
+        .into_iter()
+        .rfind(|line| !line.trim().is_empty());
+    match last {
+        Some(line) => Ok(Some(serde_json::from_str(&line)?)),
+        None => Ok(None),
+    }
+}
+
+#[cfg(test)]
+#[allow(non_snake_case)]
+mod tests {
+    use chrono::Utc;
+
+    use super::RunSummary;
+
+    fn summary(execution_errors: usize, error_checks: usize, warning_checks: usize) -> RunSummary {
+        RunSummary {
+            query_url: "https://example.com/domain/foo.example".to_string(),
+            start_time: Utc::now(),
+            end_time: None,
+            test_runs: 4,
+            tested: 4,
+            execution_errors,
+            error_checks,
+            warning_checks,
+        }
+    }
+
+    #[test]
+    fn GIVEN_more_errors_WHEN_compared_THEN_regression() {
+        // GIVEN
+        let previous = summary(0, 1, 0);
+        let current = summary(0, 2, 0);
+
+        // WHEN
+        let actual = current.is_regression_of(&previous);
+
+        // THEN
+        assert!(actual);
+    }
+
+    #[test]
+    fn GIVEN_fewer_errors_WHEN_compared_THEN_no_regression() {
+        // GIVEN
+        let previous = summary(1, 2, 3);
+        let current = summary(0, 2, 1);
+
+        // WHEN
+        let actual = current.is_regression_of(&previous);
+
+        // THEN
+        assert!(!actual);
+    }
+
+    #[test]
+    fn GIVEN_new_execution_errors_WHEN_compared_THEN_regression() {
+        // GIVEN
+        let previous = summary(0, 0, 0);
+        let current = summary(1, 0, 0);
+
+        // WHEN
+        let actual = current.is_regression_of(&previous);
+
+        // THEN
+        assert!(actual);
+    }
+}
@@ -0,0 +1,348 @@
+//! HTML reports of test results.
+
+use std::{collections::BTreeMap, fmt::Write};
+
+use icann_rdap_common::check::{traverse_checks, CheckClass, CheckItem};
+
+use super::results::{RunOutcome, TestResults, TestRun};
+
+/// The style sheet embedded in the report so that the report is a single file.
+const STYLE: &str = r#"
+body { font-family: sans-serif; margin: 2em; color: #222; }
+h1 { font-size: 1.5em; }
+table { border-collapse: collapse; margin: 0.5em 0 1em 0; }
+th, td { border: 1px solid #ccc; padding: 0.3em 0.6em; text-align: left; }
+th { background: #f0f0f0; }
+details { border: 1px solid #ccc; border-radius: 4px; margin: 0.5em 0; padding: 0.5em; }
+summary { cursor: pointer; font-weight: bold; }
+.attr { border-radius: 3px; padding: 0.1em 0.4em; margin-right: 0.3em; color: #fff; }
+.attr-v4 { background: #2e7d32; }
+.attr-v6 { background: #1565c0; }
+.attr-feature { background: #6a1b9a; }
+.outcome-tested { color: #2e7d32; font-weight: bold; }
+.outcome-skipped { color: #777; }
+.outcome-error { color: #c62828; font-style: italic; }
+.class-info, .class-specnote { color: #555; }
+.class-stdwarn { color: #ef6c00; }
+.class-stderr, .class-cidr0err, .class-icannerr { color: #c62828; }
+"#;
+
+impl TestResults {
+    /// Renders the test results as a self-contained HTML report.
+    ///
+    /// Each test run is a collapsible section with its checks grouped by [CheckClass].
+    pub fn to_html(&self, check_classes: &[CheckClass]) -> String {
+        let mut html = String::new();
+        let title = escape_html(&self.query_url);
+        html.push_str("<!DOCTYPE html>\n<html>\n<head>\n<meta charset=\"utf-8\">\n");
+        let _ = writeln!(html, "<title>RDAP Test Report: {title}</title>");
+        let _ = writeln!(html, "<style>{STYLE}</style>\n</head>\n<body>");
+        let _ = writeln!(html, "<h1>{title}</h1>");
+
+        // test results summary
+        let (end_time_s, duration_s) = if let Some(end_time) = self.end_time {
+            (
+                format_date_time(end_time),
+                format!("{} s", (end_time - self.start_time).num_seconds()),
+            )
+        } else {
+            ("FATAL".to_string(), "N/A".to_string())
+        };
+        let tested = self
+            .test_runs
+            .iter()
+            .filter(|r| matches!(r.outcome, RunOutcome::Tested))
+            .count();
+        html.push_str("<table>\n");
+        push_row(
+            &mut html,
+            "th",
+            &["Start Time", "End Time", "Duration", "Tested"],
+        );
+        push_row(
+            &mut html,
+            "td",
+            &[
+                &format_date_time(self.start_time),
+                &end_time_s,
+                &duration_s,
+                &format!("{tested} of {}", self.test_runs.len()),
+            ],
+        );
+        html.push_str("</table>\n");
+
+        // dns data
+        let v4_answer = match self.dns_data.v4_cname {
+            Some(ref cname) => cname.to_owned(),
+            None => format!("{} A records", self.dns_data.v4_addrs.len()),
+        };
+        let v6_answer = match self.dns_data.v6_cname {
+            Some(ref cname) => cname.to_owned(),
+            None => format!("{} AAAA records", self.dns_data.v6_addrs.len()),
+        };
+        html.push_str("<table>\n");
+        push_row(&mut html, "th", &["DNS Query", "DNS Answer"]);
+        push_row(&mut html, "td", &["A (v4)", &v4_answer]);
+        push_row(&mut html, "td", &["AAAA (v6)", &v6_answer]);
+        html.push_str("</table>\n");
+
+        // summary of each run
+        html.push_str("<table>\n");
+        push_row(
+            &mut html,
+            "th",
+            &["Address", "Attributes", "Duration", "Outcome"],
+        );
+        for test_run in &self.test_runs {
+            let _ = writeln!(
+                html,
+                "<tr><td>{}</td><td>{}</td><td>{}</td><td>{}</td></tr>",
+                test_run.socket_addr,
+                attributes_html(test_run),
+                run_duration(test_run),
+                outcome_html(&test_run.outcome)
+            );
+        }
+        html.push_str("</table>\n");
+
+        // checks that are about the service and not a particular test run
+        if !self.service_checks.is_empty() {
+            html.push_str("<details open>\n<summary>Service Checks</summary>\n");
+            push_check_groups(
+                &mut html,
+                self.service_checks
+                    .iter()
+                    .map(|item| (String::new(), item.clone()))
+                    .collect(),
+                false,
+            );
+            html.push_str("</details>\n");
+        }
+
+        // checks across the test runs
+        let check_stats = self.check_stats_of(check_classes).collect::<Vec<_>>();
+        if !check_stats.is_empty() {
+            html.push_str("<details open>\n<summary>Check Statistics</summary>\n<table>\n");
+            push_row(&mut html, "th", &["RDAP Structure", "Message", "Seen On"]);
+            for stat in check_stats {
+                let class = stat.check_item.check_class.to_string().to_ascii_lowercase();
+                let _ = writeln!(
+                    html,
+                    "<tr><td>{}</td><td><span class=\"class-{class}\">{}</span></td><td>{}</td></tr>",
+                    escape_html(&stat.rdap_struct),
+                    escape_html(&stat.check_item.to_string()),
+                    stat.seen_on()
+                );
+            }
+            html.push_str("</table>\n</details>\n");
+        }
+
+        // each run in detail
+        for test_run in &self.test_runs {
+            let _ = writeln!(
+                html,
+                "<details>\n<summary>{} {}</summary>",
+                test_run.socket_addr,
+                attributes_html(test_run)
+            );
+            if let Some(ref tls) = test_run.tls {
+                let _ = writeln!(html, "<p>TLS: {}</p>", escape_html(&tls.summary()));
+            }
+            if matches!(test_run.outcome, RunOutcome::Tested) {
+                let mut items = vec![];
+                if let Some(ref checks) = test_run.checks {
+                    traverse_checks(checks, check_classes, None, &mut |struct_tree, item| {
+                        items.push((struct_tree.to_string(), item.clone()))
+                    });
+                }
+                if items.is_empty() {
+                    html.push_str("<p>No issues or errors.</p>\n");
+                } else {
+                    push_check_groups(&mut html, items, true);
+                }
+            } else {
+                let _ = writeln!(html, "<p>{}</p>", outcome_html(&test_run.outcome));
+                let items = test_run
+                    .tls
+                    .iter()
+                    .flat_map(|tls| tls.check_items())
+                    .filter(|item| check_classes.contains(&item.check_class))
+                    .map(|item| ("TLS".to_string(), item))
+                    .collect::<Vec<_>>();
+                if !items.is_empty() {
+                    push_check_groups(&mut html, items, false);
+                }
+            }
+            html.push_str("</details>\n");
+        }
+
+        html.push_str("</body>\n</html>\n");
+        html
+    }
+}
+
+/// Writes a table for each class of checks.
+fn push_check_groups(html: &mut String, items: Vec<(String, CheckItem)>, with_struct: bool) {
+    let mut groups: BTreeMap<CheckClass, Vec<(String, CheckItem)>> = BTreeMap::new();
+    for (struct_tree, item) in items {
+        groups
+            .entry(item.check_class)
+            .or_default()
+            .push((struct_tree, item));
+    }
+    for (check_class, items) in groups.iter().rev() {
+        let class = check_class.to_string().to_ascii_lowercase();
+        let _ = writeln!(
+            html,
+            "<h3 class=\"class-{class}\">{check_class} ({})</h3>\n<table>",
+            items.len()
+        );
+        if with_struct {
+            push_row(html, "th", &["RDAP Structure", "Message"]);
+        } else {
+            push_row(html, "th", &["Message"]);
+        }
+        for (struct_tree, item) in items {
+            let message = format!(
+                "<span class=\"class-{class}\">{}</span>",
+                escape_html(&item.to_string())
+            );
+            if with_struct {
+                let _ = writeln!(
+                    html,
+                    "<tr><td>{}</td><td>{message}</td></tr>",
+                    escape_html(struct_tree)
+                );
+            } else {
+                let _ = writeln!(html, "<tr><td>{message}</td></tr>");
+            }
+        }
+        html.push_str("</table>\n");
+    }
+}
+
+/// Writes a table row of escaped cells.
+fn push_row(html: &mut String, cell: &str, values: &[&str]) {
+    html.push_str("<tr>");
+    for value in values {
+        let _ = write!(html, "<{cell}>{}</{cell}>", escape_html(value));
+    }
+    html.push_str("</tr>\n");
+}
+
+/// The attributes of a test run, each colored by its kind.
+fn attributes_html(test_run: &TestRun) -> String {
+    let mut attributes = vec![if test_run.socket_addr.is_ipv4() {
+        "<span class=\"attr attr-v4\">v4</span>".to_string()
+    } else {
+        "<span class=\"attr attr-v6\">v6</span>".to_string()
+    }];
+    attributes.extend(
+        test_run
+            .features
+            .iter()
+            .map(|f| format!("<span class=\"attr attr-feature\">{f}</span>")),
+    );
+    attributes.join("")
+}
+
+fn outcome_html(outcome: &RunOutcome) -> String {
+    let class = match outcome {
+        RunOutcome::Tested => "outcome-tested",
+        RunOutcome::Skipped => "outcome-skipped",
+        _ => "outcome-error",
+    };
+    format!("<span class=\"{class}\">{outcome}</span>")
+}
+
+fn run_duration(test_run: &TestRun) -> String {
+    if let Some(end_time) = test_run.end_time {
+        format!("{} ms", (end_time - test_run.start_time).num_milliseconds())
+    } else {
+        "n/a".to_string()
+    }
+}
+
+fn format_date_time(date: chrono::DateTime<chrono::Utc>) -> String {
+    date.format("%a, %v %X %Z").to_string()
+}
+
+/// Escapes text for use in HTML.
+pub(crate) fn escape_html(s: &str) -> String {
+    let mut escaped = String::with_capacity(s.len());
+    for c in s.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&#39;"),
+            _ => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+#[cfg(test)]
+#[allow(non_snake_case)]
+mod tests {
+    use std::net::Ipv4Addr;
+
+    use icann_rdap_common::check::{Check, CheckClass, Checks, RdapStructure};
+
+    use crate::rt::{
+        exec::TestOptions,
+        results::{DnsData, RunFeature, RunOutcome, TestResults, TestRun},
+    };
+
+    use super::escape_html;
+
+    #[test]
+    fn GIVEN_markup_WHEN_escape_html_THEN_escaped() {
+        // GIVEN
+        let s = "<a href=\"x\">&</a>";
+
+        // WHEN
+        let actual = escape_html(s);
+
+        // THEN
+        assert_eq!(actual, "&lt;a href=&quot;x&quot;&gt;&amp;&lt;/a&gt;");
+    }
+
+    #[test]
+    fn GIVEN_test_run_with_checks_WHEN_to_html_THEN_checks_grouped_in_run_section() {
+        // GIVEN
+        let mut results = TestResults::new(
+            "https://example.com/rdap/domain/foo.example".to_string(),
+            DnsData::default(),
+        );
+        let mut test_run = TestRun::new_v4(
+            vec![RunFeature::OriginHeader],
+            Ipv4Addr::new(192, 0, 2, 1),
+            443,
+        );
+        test_run.outcome = RunOutcome::Tested;
+        test_run.checks = Some(Checks {
+            rdap_struct: RdapStructure::Domain,
+            items: vec![
+                Check::LdhNameInvalid.check_item(),
+                Check::UnknownExtention.check_item(),
+            ],
+            sub_checks: vec![],
+        });
+        results.add_test_run(test_run);
+        results.end(&TestOptions::default());
+
+        // WHEN
+        let actual = results.to_html(&[CheckClass::StdError, CheckClass::StdWarning]);
+
+        // THEN
+        assert!(actual.starts_with("<!DOCTYPE html>"));
+        assert!(actual.contains("<details>\n<summary>192.0.2.1:443"));
+        assert!(actual.contains("attr-feature\">origin_header</span>"));
+        assert!(actual.contains("<h3 class=\"class-stderr\">StdErr (1)</h3>"));
+        assert!(actual.contains("<h3 class=\"class-stdwarn\">StdWarn (1)</h3>"));
+        assert!(actual.contains("<summary>Check Statistics</summary>"));
+        assert!(actual.contains("<td>1 of 1 addresses</td>"));
+    }
+}
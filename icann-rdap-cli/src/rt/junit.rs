@@ -0,0 +1,290 @@
+//! JUnit XML reports of test results, for continuous integration servers.
+
+use std::fmt::Write;
+
+use {
+    icann_rdap_common::check::{traverse_checks, CheckClass, CheckItem},
+    strum::EnumMessage,
+};
+
+use super::{
+    html::escape_html,
+    results::{RunOutcome, TestResults, TestRun},
+};
+
+/// A test case of a report.
+struct TestCase {
+    name: String,
+    time_secs: f64,
+    result: CaseResult,
+}
+
+enum CaseResult {
+    /// Passed, with a message for the output of the case.
+    Passed(Option<String>),
+    Failure {
+        kind: String,
+        message: String,
+    },
+    Error {
+        kind: String,
+        message: String,
+    },
+    Skipped,
+}
+
+impl TestResults {
+    /// Renders the test results as a JUnit XML report.
+    ///
+    /// Each test run is a test suite, with a test case for the request, which is an
+    /// error if the request failed, and a test case for each check of the given classes,
+    /// which is a failure unless the check is informational or a specification note.
+    /// The checks of the service are a test suite of their own.
+    pub fn to_junit(&self, check_classes: &[CheckClass]) -> String {
+        let mut suites = vec![];
+
+        let service_cases = self
+            .service_checks
+            .iter()
+            .filter(|item| check_classes.contains(&item.check_class))
+            .map(|item| check_case("[SERVICE]", item))
+            .collect::<Vec<_>>();
+        if !service_cases.is_empty() {
+            suites.push(("service".to_string(), service_cases));
+        }
+
+        for test_run in &self.test_runs {
+            let mut cases = vec![request_case(test_run)];
+            if let Some(ref checks) = test_run.checks {
+                traverse_checks(checks, check_classes, None, &mut |struct_tree, item| {
+                    cases.push(check_case(struct_tree, item))
+                });
+            } else {
+                cases.extend(
+                    test_run
+                        .tls
+                        .iter()
+                        .flat_map(|tls| tls.check_items())
+                        .filter(|item| check_classes.contains(&item.check_class))
+                        .map(|item| check_case("[TLS]", &item)),
+                );
+            }
+            let name = format!("{} - {}", test_run.socket_addr, test_run.attribute_set());
+            suites.push((name, cases));
+        }
+
+        let all_cases = suites.iter().flat_map(|(_, cases)| cases);
+        let mut xml = String::from("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n");
+        let _ = writeln!(
+            xml,
+            "<testsuites name=\"{}\" {} timestamp=\"{}\">",
+            escape_html(&self.query_url),
+            counts(all_cases),
+            self.start_time.format("%Y-%m-%dT%H:%M:%S")
+        );
+        for (name, cases) in &suites {
+            push_suite(&mut xml, name, cases);
+        }
+        xml.push_str("</testsuites>\n");
+        xml
+    }
+}
+
+fn request_case(test_run: &TestRun) -> TestCase {
+    let time_secs = test_run
+        .end_time
+        .map(|end_time| (end_time - test_run.start_time).num_milliseconds() as f64 / 1000.0)
+        .unwrap_or_default();
+    let result = match test_run.outcome {
+        RunOutcome::Tested => CaseResult::Passed(None),
+        RunOutcome::Skipped => CaseResult::Skipped,
+        ref outcome => CaseResult::Error {
+            kind: outcome.to_string(),
+            message: format!("request to {} failed", test_run.socket_addr),
+        },
+    };
+    TestCase {
+        name: "request".to_string(),
+        time_secs,
+        result,
+    }
+}
+
+fn check_case(struct_tree: &str, item: &CheckItem) -> TestCase {
+    let name = format!(
+        "{struct_tree} ({:0>4}) {}",
+        item.check.code(),
+        item.check
+            .get_message()
+            .unwrap_or("[Check has no description]")
+    );
+    let result = if matches!(
+        item.check_class,
+        CheckClass::Informational | CheckClass::SpecificationNote
+    ) {
+        CaseResult::Passed(Some(item.to_string()))
+    } else {
+        CaseResult::Failure {
+            kind: item.check_class.to_string(),
+            message: item.to_string(),
+        }
+    };
+    TestCase {
+        name,
+        time_secs: 0.0,
+        result,
+    }
+}
+
+/// Gets the attributes counting test cases, as on test suites.
+fn counts<'a>(cases: impl Iterator<Item = &'a TestCase>) -> String {
+    let (mut tests, mut failures, mut errors, mut skipped, mut time_secs) = (0, 0, 0, 0, 0.0);
+    for case in cases {
+        tests += 1;
+        time_secs += case.time_secs;
+        match case.result {
+            CaseResult::Passed(_) => {}
+            CaseResult::Failure { .. } => failures += 1,
+            CaseResult::Error { .. } => errors += 1,
+            CaseResult::Skipped => skipped += 1,
+        }
+    }
+    format!(
+        "tests=\"{tests}\" failures=\"{failures}\" errors=\"{errors}\" skipped=\"{skipped}\" time=\"{time_secs:.3}\""
+    )
+}
+
+fn push_suite(xml: &mut String, name: &str, cases: &[TestCase]) {
+    let name = escape_html(name);
+    let _ = writeln!(
+        xml,
+        "  <testsuite name=\"{name}\" {}>",
+        counts(cases.iter())
+    );
+    for case in cases {
+        let _ = write!(
+            xml,
+            "    <testcase classname=\"{name}\" name=\"{}\" time=\"{:.3}\"",
+            escape_html(&case.name),
+            case.time_secs
+        );
+        match &case.result {
+            CaseResult::Passed(None) => xml.push_str("/>\n"),
+            CaseResult::Passed(Some(out)) => {
+                let _ = writeln!(
+                    xml,
+                    ">\n      <system-out>{}</system-out>\n    </testcase>",
+                    escape_html(out)
+                );
+            }
+            CaseResult::Failure { kind, message } => {
+                let _ = writeln!(
+                    xml,
+                    ">\n      <failure type=\"{}\" message=\"{}\"/>\n    </testcase>",
+                    escape_html(kind),
+                    escape_html(message)
+                );
+            }
+            CaseResult::Error { kind, message } => {
+                let _ = writeln!(
+                    xml,
+                    ">\n      <error type=\"{}\" message=\"{}\"/>\n    </testcase>",
+                    escape_html(kind),
+                    escape_html(message)
+                );
+            }
+            CaseResult::Skipped => xml.push_str(">\n      <skipped/>\n    </testcase>\n"),
+        }
+    }
+    xml.push_str("  </testsuite>\n");
+}
+
+#[cfg(test)]
+#[allow(non_snake_case)]
+mod tests {
+    use std::net::Ipv4Addr;
+
+    use icann_rdap_common::check::{Check, CheckClass, Checks, RdapStructure};
+
+    use crate::rt::{
+        exec::TestOptions,
+        results::{DnsData, RunOutcome, TestResults, TestRun},
+    };
+
+    fn test_results() -> TestResults {
+        let mut test_results = TestResults::new(
+            "https://rdap.example.com/domain/foo.example".to_string(),
+            DnsData {
+                v4_addrs: vec![Ipv4Addr::new(192, 0, 2, 1), Ipv4Addr::new(192, 0, 2, 2)],
+                v6_addrs: vec!["2001:db8::1".parse().expect("parsing address")],
+                ..Default::default()
+            },
+        );
+        let mut tested = TestRun::new_v4(vec![], Ipv4Addr::new(192, 0, 2, 1), 443);
+        tested.outcome = RunOutcome::Tested;
+        tested.checks = Some(Checks {
+            rdap_struct: RdapStructure::Domain,
+            items: vec![
+                Check::LdhNameInvalid.check_item(),
+                Check::UnknownExtention.check_item(),
+            ],
+            sub_checks: vec![],
+        });
+        test_results.add_test_run(tested);
+        let mut failed = TestRun::new_v4(vec![], Ipv4Addr::new(192, 0, 2, 2), 443);
+        failed.outcome = RunOutcome::HttpConnectError;
+        test_results.add_test_run(failed);
+        test_results.add_test_run(TestRun::new_v6(
+            vec![],
+            "2001:db8::1".parse().expect("parsing address"),
+            443,
+        ));
+        test_results.end(&TestOptions::default());
+        test_results
+    }
+
+    #[test]
+    fn GIVEN_test_results_WHEN_to_junit_THEN_suite_per_run_and_case_per_check() {
+        // GIVEN
+        let test_results = test_results();
+
+        // WHEN
+        let actual = test_results.to_junit(&[CheckClass::StdError, CheckClass::StdWarning]);
+
+        // THEN
+        assert!(actual.starts_with("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n<testsuites name=\"https://rdap.example.com/domain/foo.example\" tests=\"5\" failures=\"2\" errors=\"1\" skipped=\"1\""));
+        assert!(actual.contains(
+            "<testsuite name=\"192.0.2.1:443 - v4\" tests=\"3\" failures=\"2\" errors=\"0\" skipped=\"0\""
+        ));
+        assert!(actual.contains("<failure type=\"StdErr\""));
+        assert!(actual.contains("<error type=\"HTTP_CONNECT_ERROR\""));
+        assert!(actual.contains(
+            "<testsuite name=\"[2001:db8::1]:443 - v6\" tests=\"1\" failures=\"0\" errors=\"0\" skipped=\"1\""
+        ));
+        assert!(!actual.contains("name=\"service\""));
+    }
+
+    #[test]
+    fn GIVEN_service_checks_WHEN_to_junit_THEN_service_suite() {
+        // GIVEN
+        let mut test_results = TestResults::new(
+            "https://rdap.example.com/domain/foo.example".to_string(),
+            DnsData {
+                v4_addrs: vec![Ipv4Addr::new(192, 0, 2, 1)],
+                ..Default::default()
+            },
+        );
+        test_results.end(&TestOptions::default());
+
+        // WHEN
+        let actual = test_results.to_junit(&[CheckClass::SpecificationNote]);
+
+        // THEN
+        assert!(actual.contains(
+            "<testsuite name=\"service\" tests=\"1\" failures=\"0\" errors=\"0\" skipped=\"0\""
+        ));
+        assert!(
+            actual.contains("<system-out>SpecNote:(2103) No AAAA records. [RFC 3596]</system-out>")
+        );
+    }
+}
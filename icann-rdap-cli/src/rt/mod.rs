@@ -1,2 +1,8 @@
+pub mod badge;
 pub mod exec;
+pub mod history;
+pub mod html;
+pub mod junit;
 pub mod results;
+pub mod scan;
+pub mod tls;
@@ -1,4 +1,8 @@
-use std::net::{IpAddr, Ipv4Addr, Ipv6Addr, SocketAddr};
+use std::{
+    cmp::Reverse,
+    collections::{BTreeMap, BTreeSet},
+    net::{IpAddr, Ipv4Addr, Ipv6Addr, SocketAddr},
+};
 
 /// Contains the results of test execution.
 use chrono::{DateTime, Utc};
@@ -9,15 +13,22 @@ use {
         RdapClientError,
     },
     icann_rdap_common::{
-        check::{traverse_checks, Check, CheckClass, CheckItem, CheckParams, Checks, GetChecks},
+        check::{
+            traverse_checks, Check, CheckClass, CheckItem, CheckParams, Checks, GetChecks,
+            ProfileId,
+        },
         response::{ExtensionId, RdapResponse},
     },
     reqwest::StatusCode,
     serde::Serialize,
+    strum::IntoEnumIterator,
     strum_macros::Display,
 };
 
-use super::exec::TestOptions;
+use super::{
+    exec::{ExtensionGroup, TestOptions},
+    tls::TlsData,
+};
 
 #[derive(Debug, Serialize)]
 pub struct TestResults {
@@ -27,6 +38,28 @@ pub struct TestResults {
     pub end_time: Option<DateTime<Utc>>,
     pub service_checks: Vec<CheckItem>,
     pub test_runs: Vec<TestRun>,
+    /// The checks found across the test runs, with the addresses on which they were found.
+    pub check_stats: Vec<CheckStat>,
+}
+
+/// The number of tested addresses on which a check was found in an RDAP structure,
+/// which distinguishes issues of the service from issues of particular servers.
+#[derive(Debug, Serialize, Clone, PartialEq, Eq)]
+pub struct CheckStat {
+    /// The tree of the RDAP structure, such as `[ROOT]/domain/links`.
+    pub rdap_struct: String,
+    pub check_item: CheckItem,
+    /// The number of tested addresses on which the check was found.
+    pub addrs_found: usize,
+    /// The number of addresses tested.
+    pub addrs_tested: usize,
+}
+
+impl CheckStat {
+    /// Describes the addresses on which the check was found, such as "4 of 6 addresses".
+    pub fn seen_on(&self) -> String {
+        format!("{} of {} addresses", self.addrs_found, self.addrs_tested)
+    }
 }
 
 impl TestResults {
@@ -38,11 +71,13 @@ impl TestResults {
             end_time: None,
             service_checks: vec![],
             test_runs: vec![],
+            check_stats: vec![],
         }
     }
 
     pub fn end(&mut self, options: &TestOptions) {
         self.end_time = Some(Utc::now());
+        self.check_stats = check_stats(&self.test_runs);
 
         //service checks
         if self.dns_data.v4_cname.is_some() && self.dns_data.v4_addrs.is_empty() {
@@ -77,6 +112,16 @@ impl TestResults {
         self.test_runs.push(test_run);
     }
 
+    /// Gets the statistics of the checks of the given classes.
+    pub fn check_stats_of<'a>(
+        &'a self,
+        check_classes: &'a [CheckClass],
+    ) -> impl Iterator<Item = &'a CheckStat> {
+        self.check_stats
+            .iter()
+            .filter(|stat| check_classes.contains(&stat.check_item.check_class))
+    }
+
     pub fn to_md(&self, options: &MdOptions, check_classes: &[CheckClass]) -> String {
         let mut md = String::new();
 
@@ -162,6 +207,28 @@ impl TestResults {
             md.push('\n');
         }
 
+        // checks across the test runs
+        let check_stats = self.check_stats_of(check_classes).collect::<Vec<_>>();
+        if !check_stats.is_empty() {
+            md.push_str(&"Check Statistics".to_string().to_header(1, options));
+            let mut table = MultiPartTable::new();
+
+            table = table.multi_raw(vec![
+                "RDAP Structure".to_inline(options),
+                "Message".to_inline(options),
+                "Seen On".to_inline(options),
+            ]);
+            for stat in check_stats {
+                table = table.multi_raw(vec![
+                    stat.rdap_struct.clone(),
+                    check_item_md(&stat.check_item, options),
+                    stat.seen_on(),
+                ]);
+            }
+            md.push_str(&table.to_md_table(options));
+            md.push('\n');
+        }
+
         // each run in detail
         for run in &self.test_runs {
             md.push_str(&run.to_md(options, check_classes));
@@ -170,6 +237,39 @@ impl TestResults {
     }
 }
 
+/// Aggregates the checks of the tested runs by RDAP structure and check, the most
+/// widespread first.
+fn check_stats(test_runs: &[TestRun]) -> Vec<CheckStat> {
+    let all_classes = CheckClass::iter().collect::<Vec<_>>();
+    let mut tested = BTreeSet::new();
+    let mut found: BTreeMap<(String, CheckItem), BTreeSet<SocketAddr>> = BTreeMap::new();
+    for test_run in test_runs
+        .iter()
+        .filter(|run| matches!(run.outcome, RunOutcome::Tested))
+    {
+        tested.insert(test_run.socket_addr);
+        if let Some(ref checks) = test_run.checks {
+            traverse_checks(checks, &all_classes, None, &mut |struct_tree, item| {
+                found
+                    .entry((struct_tree.to_string(), item.clone()))
+                    .or_default()
+                    .insert(test_run.socket_addr);
+            });
+        }
+    }
+    let mut stats = found
+        .into_iter()
+        .map(|((rdap_struct, check_item), addrs)| CheckStat {
+            rdap_struct,
+            check_item,
+            addrs_found: addrs.len(),
+            addrs_tested: tested.len(),
+        })
+        .collect::<Vec<_>>();
+    stats.sort_by_key(|stat| Reverse(stat.addrs_found));
+    stats
+}
+
 #[derive(Debug, Serialize, Clone, Default)]
 pub struct DnsData {
     pub v4_cname: Option<String>,
@@ -193,16 +293,78 @@ pub enum RunOutcome {
     HttpBadRequestError,
     HttpUnauthorizedError,
     HttpForbiddenError,
+    SpkiPinMismatchError,
     JsonError,
     RdapDataError,
     InternalError,
     Skipped,
 }
 
-#[derive(Debug, Serialize, Display)]
+#[derive(Debug, Serialize, Display, Clone)]
 #[strum(serialize_all = "snake_case")]
 pub enum RunFeature {
     OriginHeader,
+    /// The TLS SNI is a given name instead of the host of the query URL.
+    Sni,
+    /// The Host header is a given value instead of the host of the query URL.
+    HostHeader,
+}
+
+impl From<&RdapClientError> for RunOutcome {
+    fn from(error: &RdapClientError) -> Self {
+        match error {
+            RdapClientError::InvalidQueryValue
+            | RdapClientError::AmbiquousQueryType
+            | RdapClientError::Poison
+            | RdapClientError::SharedRequest(_)
+            | RdapClientError::DomainNameError(_)
+            | RdapClientError::BootstrapUnavailable
+            | RdapClientError::BootstrapError(_)
+            | RdapClientError::IanaResponse(_) => RunOutcome::InternalError,
+            RdapClientError::Response(_)
+            | RdapClientError::InvalidMediaType { .. }
+            | RdapClientError::UnexpectedResponse { .. }
+            | RdapClientError::ResponseTooLarge(_) => RunOutcome::RdapDataError,
+            RdapClientError::Json(_) => RunOutcome::JsonError,
+            RdapClientError::ParsingError(e) => {
+                let status_code = e.http_data.status_code();
+                if status_code > 299 && status_code < 400 {
+                    RunOutcome::HttpRedirectResponse
+                } else {
+                    RunOutcome::JsonError
+                }
+            }
+            RdapClientError::IoError(_) => RunOutcome::NetworkError,
+            RdapClientError::SpkiPinMismatch(_) => RunOutcome::SpkiPinMismatchError,
+            RdapClientError::RetriesExhausted(e) => {
+                if e.http_data.status_code() == StatusCode::TOO_MANY_REQUESTS.as_u16() {
+                    RunOutcome::HttpTooManyRequestsError
+                } else {
+                    RunOutcome::HttpNon200Error
+                }
+            }
+            RdapClientError::Client(e) => {
+                if e.is_redirect() {
+                    RunOutcome::HttpRedirectResponse
+                } else if e.is_connect() {
+                    RunOutcome::HttpConnectError
+                } else if e.is_timeout() {
+                    RunOutcome::HttpTimeoutError
+                } else if e.is_status() {
+                    match e.status().unwrap() {
+                        StatusCode::TOO_MANY_REQUESTS => RunOutcome::HttpTooManyRequestsError,
+                        StatusCode::NOT_FOUND => RunOutcome::HttpNotFoundError,
+                        StatusCode::BAD_REQUEST => RunOutcome::HttpBadRequestError,
+                        StatusCode::UNAUTHORIZED => RunOutcome::HttpUnauthorizedError,
+                        StatusCode::FORBIDDEN => RunOutcome::HttpForbiddenError,
+                        _ => RunOutcome::HttpNon200Error,
+                    }
+                } else {
+                    RunOutcome::HttpProtocolError
+                }
+            }
+        }
+    }
 }
 
 impl RunOutcome {
@@ -224,6 +386,8 @@ pub struct TestRun {
     pub response_data: Option<ResponseData>,
     pub outcome: RunOutcome,
     pub checks: Option<Checks>,
+    /// The TLS connection to the server, if it was inspected.
+    pub tls: Option<TlsData>,
 }
 
 impl TestRun {
@@ -236,6 +400,7 @@ impl TestRun {
             response_data: None,
             outcome: RunOutcome::Skipped,
             checks: None,
+            tls: None,
         }
     }
 
@@ -258,51 +423,21 @@ impl TestRun {
             self.checks = Some(do_checks(&response_data, options));
             self.response_data = Some(response_data);
         } else {
-            self.outcome = match rdap_response.err().unwrap() {
-                RdapClientError::InvalidQueryValue
-                | RdapClientError::AmbiquousQueryType
-                | RdapClientError::Poison
-                | RdapClientError::DomainNameError(_)
-                | RdapClientError::BootstrapUnavailable
-                | RdapClientError::BootstrapError(_)
-                | RdapClientError::IanaResponse(_) => RunOutcome::InternalError,
-                RdapClientError::Response(_) => RunOutcome::RdapDataError,
-                RdapClientError::Json(_) => RunOutcome::JsonError,
-                RdapClientError::ParsingError(e) => {
-                    let status_code = e.http_data.status_code();
-                    if status_code > 299 && status_code < 400 {
-                        RunOutcome::HttpRedirectResponse
-                    } else {
-                        RunOutcome::JsonError
-                    }
-                }
-                RdapClientError::IoError(_) => RunOutcome::NetworkError,
-                RdapClientError::Client(e) => {
-                    if e.is_redirect() {
-                        RunOutcome::HttpRedirectResponse
-                    } else if e.is_connect() {
-                        RunOutcome::HttpConnectError
-                    } else if e.is_timeout() {
-                        RunOutcome::HttpTimeoutError
-                    } else if e.is_status() {
-                        match e.status().unwrap() {
-                            StatusCode::TOO_MANY_REQUESTS => RunOutcome::HttpTooManyRequestsError,
-                            StatusCode::NOT_FOUND => RunOutcome::HttpNotFoundError,
-                            StatusCode::BAD_REQUEST => RunOutcome::HttpBadRequestError,
-                            StatusCode::UNAUTHORIZED => RunOutcome::HttpUnauthorizedError,
-                            StatusCode::FORBIDDEN => RunOutcome::HttpForbiddenError,
-                            _ => RunOutcome::HttpNon200Error,
-                        }
-                    } else {
-                        RunOutcome::HttpProtocolError
-                    }
-                }
-            };
+            self.outcome = RunOutcome::from(&rdap_response.err().unwrap());
             self.end_time = Some(Utc::now());
         };
         self
     }
 
+    /// Adds the inspection of the TLS connection, with its checks if the run was tested.
+    pub fn with_tls(mut self, tls: TlsData) -> Self {
+        if let Some(ref mut checks) = self.checks {
+            checks.items.append(&mut tls.check_items());
+        }
+        self.tls = Some(tls);
+        self
+    }
+
     fn add_summary(&self, mut table: MultiPartTable, options: &MdOptions) -> MultiPartTable {
         let duration_s = if let Some(end_time) = self.end_time {
             format!("{} ms", (end_time - self.start_time).num_milliseconds())
@@ -324,6 +459,9 @@ impl TestRun {
         // h1
         let header_value = format!("{} - {}", self.socket_addr, self.attribute_set());
         md.push_str(&format!("\n{}\n", header_value.to_header(1, options)));
+        if let Some(ref tls) = self.tls {
+            md.push_str(&format!("\nTLS: {}\n\n", tls.summary()));
+        }
 
         // if outcome is tested
         if matches!(self.outcome, RunOutcome::Tested) {
@@ -354,13 +492,21 @@ impl TestRun {
         } else {
             let mut table = MultiPartTable::new();
             table = table.multi_raw(vec![self.outcome.to_md(options)]);
+            for item in self
+                .tls
+                .iter()
+                .flat_map(|tls| tls.check_items())
+                .filter(|item| check_classes.contains(&item.check_class))
+            {
+                table = table.multi_raw(vec![check_item_md(&item, options)]);
+            }
             md.push_str(&table.to_md_table(options));
         }
 
         md
     }
 
-    fn attribute_set(&self) -> String {
+    pub(crate) fn attribute_set(&self) -> String {
         let socket_type = if self.socket_addr.is_ipv4() {
             "v4"
         } else {
@@ -396,11 +542,23 @@ fn format_date_time(date: DateTime<Utc>) -> String {
 }
 
 fn do_checks(response: &ResponseData, options: &TestOptions) -> Checks {
+    let profiles = options.profiles.clone().unwrap_or_else(|| {
+        let mut profiles = ProfileId::declared_by(&response.rdap).to_vec();
+        if options
+            .expect_groups
+            .iter()
+            .any(|group| matches!(group, ExtensionGroup::Nro | ExtensionGroup::NroAsn))
+        {
+            profiles.push(ProfileId::Nro);
+        }
+        profiles
+    });
     let check_params = CheckParams {
         do_subchecks: true,
         root: &response.rdap,
         parent_type: response.rdap.get_type(),
         allow_unreg_ext: options.allow_unregistered_extensions,
+        profiles: &profiles,
     };
     let mut checks = response.rdap.get_checks(check_params);
 
@@ -430,12 +588,97 @@ fn rdap_has_expected_extension(rdap: &RdapResponse, ext: &str) -> bool {
 #[cfg(test)]
 #[allow(non_snake_case)]
 mod tests {
+    use std::net::Ipv4Addr;
+
     use icann_rdap_common::{
+        check::{Check, CheckItem, Checks, RdapStructure},
         prelude::ToResponse,
         response::{Domain, Extension},
     };
 
-    use super::rdap_has_expected_extension;
+    use super::{check_stats, rdap_has_expected_extension, RunFeature, RunOutcome, TestRun};
+    use crate::rt::tls::TlsData;
+
+    fn tested_run(addr: Ipv4Addr, features: Vec<RunFeature>, items: Vec<CheckItem>) -> TestRun {
+        let mut test_run = TestRun::new_v4(features, addr, 443);
+        test_run.outcome = RunOutcome::Tested;
+        test_run.checks = Some(Checks {
+            rdap_struct: RdapStructure::Domain,
+            items,
+            sub_checks: vec![],
+        });
+        test_run
+    }
+
+    #[test]
+    fn GIVEN_runs_on_addresses_WHEN_check_stats_THEN_counted_by_address() {
+        // GIVEN
+        let addr1 = Ipv4Addr::new(192, 0, 2, 1);
+        let addr2 = Ipv4Addr::new(192, 0, 2, 2);
+        let test_runs = vec![
+            tested_run(addr1, vec![], vec![Check::LdhNameInvalid.check_item()]),
+            tested_run(
+                addr1,
+                vec![RunFeature::OriginHeader],
+                vec![Check::LdhNameInvalid.check_item()],
+            ),
+            tested_run(
+                addr2,
+                vec![],
+                vec![
+                    Check::LdhNameInvalid.check_item(),
+                    Check::UnknownExtention.check_item(),
+                ],
+            ),
+            TestRun::new_v4(vec![], Ipv4Addr::new(192, 0, 2, 3), 443),
+        ];
+
+        // WHEN
+        let actual = check_stats(&test_runs);
+
+        // THEN
+        assert_eq!(actual.len(), 2);
+        assert_eq!(actual[0].check_item.check, Check::LdhNameInvalid);
+        assert_eq!(actual[0].rdap_struct, "[ROOT]/domain");
+        assert_eq!(actual[0].seen_on(), "2 of 2 addresses");
+        assert_eq!(actual[1].check_item.check, Check::UnknownExtention);
+        assert_eq!(actual[1].seen_on(), "1 of 2 addresses");
+    }
+
+    #[test]
+    fn GIVEN_tested_run_WHEN_with_tls_THEN_tls_checks_added() {
+        // GIVEN
+        let test_run = tested_run(
+            Ipv4Addr::new(192, 0, 2, 1),
+            vec![],
+            vec![Check::LdhNameInvalid.check_item()],
+        );
+        let tls = TlsData {
+            protocol_version: Some("TLSv1_3".to_string()),
+            certificate_chain_len: 2,
+            certificate_chain_valid: true,
+            certificate_expires_soon: true,
+            certificate_covers_host: true,
+            ..Default::default()
+        };
+
+        // WHEN
+        let actual = test_run.with_tls(tls);
+
+        // THEN
+        let checks = actual
+            .checks
+            .expect("checks")
+            .items
+            .into_iter()
+            .map(|item| item.check)
+            .collect::<Vec<_>>();
+        assert_eq!(
+            checks,
+            vec![Check::LdhNameInvalid, Check::TlsCertificateExpiresSoon]
+        );
+        assert!(actual.tls.is_some());
+    }
 
     #[test]
     fn GIVEN_expected_extension_WHEN_rdap_has_THEN_true() {
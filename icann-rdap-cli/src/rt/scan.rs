@@ -0,0 +1,185 @@
+//! Scans of the health of every service of an IANA RDAP bootstrap registry.
+
+use std::time::Instant;
+
+use {
+    futures::{stream, StreamExt},
+    icann_rdap_client::{
+        http::Client,
+        rdap::{rdap_request, QueryType},
+        RdapClientError,
+    },
+    icann_rdap_common::iana::IanaRegistry,
+    serde::Serialize,
+    tracing::info,
+};
+
+use super::results::RunOutcome;
+
+/// The header of the CSV of service health.
+const CSV_HEADER: &str = "service_url,outcome,status_code,duration_ms,entries,error";
+
+/// Options for scanning a bootstrap registry.
+pub struct ScanOptions {
+    /// The number of services probed at the same time.
+    pub concurrency: usize,
+}
+
+/// The health of a service URL of a bootstrap registry, from probing its help.
+#[derive(Debug, Serialize)]
+pub struct ServiceHealth {
+    pub service_url: String,
+    /// The entries of the registry served by the URL, such as TLDs.
+    pub entries: Vec<String>,
+    pub outcome: RunOutcome,
+    pub status_code: Option<u16>,
+    pub duration_ms: u64,
+    pub error: Option<String>,
+}
+
+impl ServiceHealth {
+    fn to_csv_line(&self) -> String {
+        [
+            csv_field(&self.service_url),
+            self.outcome.to_string(),
+            self.status_code
+                .map(|status_code| status_code.to_string())
+                .unwrap_or_default(),
+            self.duration_ms.to_string(),
+            csv_field(&self.entries.join(" ")),
+            csv_field(self.error.as_deref().unwrap_or_default()),
+        ]
+        .join(",")
+    }
+}
+
+/// Probes the help of every service URL of a bootstrap registry, a bounded number at a time.
+///
+/// The health of the services is in the order of their URLs.
+pub async fn scan_bootstrap(
+    registry: &IanaRegistry,
+    client: &Client,
+    options: &ScanOptions,
+) -> Vec<ServiceHealth> {
+    let service_urls = registry.service_urls();
+    info!("Scanning {} service URLs", service_urls.len());
+    let mut health = stream::iter(service_urls)
+        .map(|(service_url, entries)| probe_service(service_url, entries, client))
+        .buffer_unordered(options.concurrency.max(1))
+        .collect::<Vec<_>>()
+        .await;
+    health.sort_by(|a, b| a.service_url.cmp(&b.service_url));
+    health
+}
+
+async fn probe_service(
+    service_url: String,
+    entries: Vec<String>,
+    client: &Client,
+) -> ServiceHealth {
+    let start = Instant::now();
+    let response = rdap_request(&service_url, &QueryType::Help, client).await;
+    let duration_ms = start.elapsed().as_millis() as u64;
+    let (outcome, status_code, error) = match response {
+        Ok(response) => (
+            RunOutcome::Tested,
+            Some(response.http_data.status_code()),
+            None,
+        ),
+        Err(error) => (
+            RunOutcome::from(&error),
+            error_status_code(&error),
+            Some(error.to_string()),
+        ),
+    };
+    info!("Probed {service_url}: {outcome}");
+    ServiceHealth {
+        service_url,
+        entries,
+        outcome,
+        status_code,
+        duration_ms,
+        error,
+    }
+}
+
+fn error_status_code(error: &RdapClientError) -> Option<u16> {
+    match error {
+        RdapClientError::ParsingError(e) => Some(e.http_data.status_code()),
+        RdapClientError::RetriesExhausted(e) => Some(e.http_data.status_code()),
+        RdapClientError::Client(e) => e.status().map(|status| status.as_u16()),
+        _ => None,
+    }
+}
+
+/// Gets the health of services as CSV, with a header line.
+pub fn to_csv(health: &[ServiceHealth]) -> String {
+    let mut csv = format!("{CSV_HEADER}\n");
+    for service in health {
+        csv.push_str(&service.to_csv_line());
+        csv.push('\n');
+    }
+    csv
+}
+
+/// Quotes a CSV field if it has commas, quotes or line breaks.
+fn csv_field(value: &str) -> String {
+    if value.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
+
+#[cfg(test)]
+#[allow(non_snake_case)]
+mod tests {
+    use super::{csv_field, to_csv, ServiceHealth};
+    use crate::rt::results::RunOutcome;
+
+    #[test]
+    fn GIVEN_field_with_comma_and_quote_WHEN_csv_field_THEN_quoted() {
+        // GIVEN
+        let value = "Error \"parsing\", response";
+
+        // WHEN
+        let actual = csv_field(value);
+
+        // THEN
+        assert_eq!(actual, "\"Error \"\"parsing\"\", response\"");
+    }
+
+    #[test]
+    fn GIVEN_service_health_WHEN_to_csv_THEN_header_and_line_per_service() {
+        // GIVEN
+        let health = vec![
+            ServiceHealth {
+                service_url: "https://rdap.example.com/".to_string(),
+                entries: vec!["com".to_string(), "net".to_string()],
+                outcome: RunOutcome::Tested,
+                status_code: Some(200),
+                duration_ms: 120,
+                error: None,
+            },
+            ServiceHealth {
+                service_url: "https://rdap.example.org/".to_string(),
+                entries: vec!["org".to_string()],
+                outcome: RunOutcome::HttpNotFoundError,
+                status_code: Some(404),
+                duration_ms: 80,
+                error: Some("HTTP status client error (404 Not Found)".to_string()),
+            },
+        ];
+
+        // WHEN
+        let actual = to_csv(&health);
+
+        // THEN
+        assert_eq!(
+            actual,
+            "service_url,outcome,status_code,duration_ms,entries,error\n\
+             https://rdap.example.com/,TESTED,200,120,com net,\n\
+             https://rdap.example.org/,HTTP_NOT_FOUND_ERROR,404,80,org,HTTP status client error (404 Not Found)\n"
+        );
+    }
+}
@@ -0,0 +1,343 @@
+//! Inspection of the TLS connections to servers.
+
+use std::{
+    net::SocketAddr,
+    sync::Arc,
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+
+use {
+    icann_rdap_common::check::{Check, CheckItem},
+    rustls::{
+        client::{
+            danger::{HandshakeSignatureValid, ServerCertVerified, ServerCertVerifier},
+            verify_server_cert_signed_by_trust_anchor, verify_server_name,
+        },
+        crypto::{ring, verify_tls12_signature, verify_tls13_signature, CryptoProvider},
+        pki_types::{CertificateDer, Der, ServerName, TrustAnchor, UnixTime},
+        server::ParsedCertificate,
+        AlertDescription, CertificateError, ClientConfig, DigitallySignedStruct, Error as TlsError,
+        PeerIncompatible, RootCertStore, SignatureScheme,
+    },
+    serde::Serialize,
+    tokio::{net::TcpStream, time::timeout},
+    tokio_rustls::TlsConnector,
+};
+
+/// Options for inspecting TLS connections.
+#[derive(Debug, Clone, Copy)]
+pub struct TlsInspection {
+    /// Certificates expiring within this window are reported as expiring soon.
+    pub expiry_window: Duration,
+    /// The time allowed for connecting and the TLS handshake.
+    pub timeout: Duration,
+}
+
+/// The TLS connection to a server, independently of the RDAP request.
+#[derive(Debug, Serialize, Clone, Default)]
+pub struct TlsData {
+    /// The negotiated TLS version, such as `TLSv1_3`.
+    pub protocol_version: Option<String>,
+    /// The negotiated cipher suite, such as `TLS13_AES_256_GCM_SHA384`.
+    pub cipher_suite: Option<String>,
+    /// The number of certificates sent by the server.
+    pub certificate_chain_len: usize,
+    /// The certificate chain is signed by a root of the web PKI and is not expired.
+    pub certificate_chain_valid: bool,
+    pub certificate_expired: bool,
+    /// The certificate chain is valid now but not at the end of the expiry window.
+    pub certificate_expires_soon: bool,
+    /// The certificate has a subject alternative name covering the host.
+    pub certificate_covers_host: bool,
+    /// The server only supports versions of TLS below 1.2.
+    pub version_below_12: bool,
+    /// The error connecting or in the TLS handshake, if there was one.
+    pub error: Option<String>,
+}
+
+impl TlsData {
+    /// Summarizes the connection, such as "TLSv1_3 with TLS13_AES_256_GCM_SHA384".
+    pub fn summary(&self) -> String {
+        match (&self.protocol_version, &self.cipher_suite, &self.error) {
+            (Some(version), Some(suite), _) => format!("{version} with {suite}"),
+            (Some(version), None, _) => version.clone(),
+            (None, _, Some(error)) => error.clone(),
+            (None, _, None) => "no TLS connection".to_string(),
+        }
+    }
+
+    /// Gets the checks of the TLS connection.
+    ///
+    /// There are none if the connection could not be made for reasons other than the version of TLS.
+    pub fn check_items(&self) -> Vec<CheckItem> {
+        let mut items = vec![];
+        if self.version_below_12 {
+            items.push(Check::TlsVersionIsBelow12.check_item());
+        }
+        if self.protocol_version.is_none() {
+            return items;
+        }
+        if self.certificate_expired {
+            items.push(Check::TlsCertificateIsExpired.check_item());
+        } else if !self.certificate_chain_valid {
+            items.push(Check::TlsCertificateChainIsInvalid.check_item());
+        }
+        if self.certificate_expires_soon {
+            items.push(Check::TlsCertificateExpiresSoon.check_item());
+        }
+        if !self.certificate_covers_host {
+            items.push(Check::TlsCertificateDoesNotCoverHost.check_item());
+        }
+        items
+    }
+}
+
+/// Connects to the server at the address with TLS for the host and inspects the connection.
+///
+/// Certificates are accepted during the handshake so that they can be inspected afterward.
+pub async fn inspect_tls(host: &str, socket_addr: SocketAddr, options: &TlsInspection) -> TlsData {
+    let provider = Arc::new(ring::default_provider());
+    let server_name = match ServerName::try_from(host.to_string()) {
+        Ok(server_name) => server_name,
+        Err(e) => return tls_error(e.to_string()),
+    };
+    let config = ClientConfig::builder_with_provider(provider.clone())
+        .with_safe_default_protocol_versions()
+        .map(|builder| {
+            builder
+                .dangerous()
+                .with_custom_certificate_verifier(Arc::new(AcceptAnyCertificate(provider.clone())))
+                .with_no_client_auth()
+        });
+    let config = match config {
+        Ok(config) => config,
+        Err(e) => return tls_error(e.to_string()),
+    };
+    let connect = async {
+        let tcp = TcpStream::connect(socket_addr).await?;
+        TlsConnector::from(Arc::new(config))
+            .connect(server_name.clone(), tcp)
+            .await
+    };
+    let stream = match timeout(options.timeout, connect).await {
+        Ok(Ok(stream)) => stream,
+        Ok(Err(e)) => {
+            let version_below_12 = e
+                .get_ref()
+                .and_then(|e| e.downcast_ref::<TlsError>())
+                .is_some_and(is_version_below_12);
+            return TlsData {
+                version_below_12,
+                ..tls_error(e.to_string())
+            };
+        }
+        Err(_) => return tls_error("TLS handshake timed out".to_string()),
+    };
+    let (_, connection) = stream.get_ref();
+    let mut tls_data = TlsData {
+        protocol_version: connection
+            .protocol_version()
+            .map(|version| format!("{version:?}")),
+        cipher_suite: connection
+            .negotiated_cipher_suite()
+            .map(|suite| format!("{:?}", suite.suite())),
+        ..Default::default()
+    };
+    let Some((end_entity, intermediates)) = connection
+        .peer_certificates()
+        .and_then(|chain| chain.split_first())
+    else {
+        return tls_data;
+    };
+    tls_data.certificate_chain_len = intermediates.len() + 1;
+    let Ok(certificate) = ParsedCertificate::try_from(end_entity) else {
+        return tls_data;
+    };
+    tls_data.certificate_covers_host = verify_server_name(&certificate, &server_name).is_ok();
+
+    let roots = web_pki_roots();
+    let algorithms = provider.signature_verification_algorithms.all;
+    let verify_at = |time: Duration| {
+        verify_server_cert_signed_by_trust_anchor(
+            &certificate,
+            &roots,
+            intermediates,
+            UnixTime::since_unix_epoch(time),
+            algorithms,
+        )
+    };
+    let now = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default();
+    match verify_at(now) {
+        Ok(()) => {
+            tls_data.certificate_chain_valid = true;
+            tls_data.certificate_expires_soon =
+                verify_at(now + options.expiry_window).is_err_and(|e| is_expired(&e));
+        }
+        Err(e) => tls_data.certificate_expired = is_expired(&e),
+    }
+    tls_data
+}
+
+fn tls_error(error: String) -> TlsData {
+    TlsData {
+        error: Some(error),
+        ..Default::default()
+    }
+}
+
+/// Gets the roots of the web PKI, as trusted by browsers.
+fn web_pki_roots() -> RootCertStore {
+    RootCertStore {
+        roots: webpki_roots::TLS_SERVER_ROOTS
+            .iter()
+            .map(|root| TrustAnchor {
+                subject: Der::from_slice(root.subject),
+                subject_public_key_info: Der::from_slice(root.spki),
+                name_constraints: root.name_constraints.map(Der::from_slice),
+            })
+            .collect(),
+    }
+}
+
+fn is_expired(error: &TlsError) -> bool {
+    matches!(
+        error,
+        TlsError::InvalidCertificate(
+            CertificateError::Expired | CertificateError::ExpiredContext { .. }
+        )
+    )
+}
+
+fn is_version_below_12(error: &TlsError) -> bool {
+    matches!(
+        error,
+        TlsError::PeerIncompatible(
+            PeerIncompatible::ServerDoesNotSupportTls12Or13
+                | PeerIncompatible::ServerTlsVersionIsDisabledByOurConfig
+        ) | TlsError::AlertReceived(AlertDescription::ProtocolVersion)
+    )
+}
+
+/// Accepts the certificates of servers, but not bad handshake signatures.
+#[derive(Debug)]
+struct AcceptAnyCertificate(Arc<CryptoProvider>);
+
+impl ServerCertVerifier for AcceptAnyCertificate {
+    fn verify_server_cert(
+        &self,
+        _end_entity: &CertificateDer<'_>,
+        _intermediates: &[CertificateDer<'_>],
+        _server_name: &ServerName<'_>,
+        _ocsp_response: &[u8],
+        _now: UnixTime,
+    ) -> Result<ServerCertVerified, TlsError> {
+        Ok(ServerCertVerified::assertion())
+    }
+
+    fn verify_tls12_signature(
+        &self,
+        message: &[u8],
+        cert: &CertificateDer<'_>,
+        dss: &DigitallySignedStruct,
+    ) -> Result<HandshakeSignatureValid, TlsError> {
+        verify_tls12_signature(
+            message,
+            cert,
+            dss,
+            &self.0.signature_verification_algorithms,
+        )
+    }
+
+    fn verify_tls13_signature(
+        &self,
+        message: &[u8],
+        cert: &CertificateDer<'_>,
+        dss: &DigitallySignedStruct,
+    ) -> Result<HandshakeSignatureValid, TlsError> {
+        verify_tls13_signature(
+            message,
+            cert,
+            dss,
+            &self.0.signature_verification_algorithms,
+        )
+    }
+
+    fn supported_verify_schemes(&self) -> Vec<SignatureScheme> {
+        self.0.signature_verification_algorithms.supported_schemes()
+    }
+}
+
+#[cfg(test)]
+#[allow(non_snake_case)]
+mod tests {
+    use icann_rdap_common::check::Check;
+
+    use super::TlsData;
+
+    fn checks(tls_data: &TlsData) -> Vec<Check> {
+        tls_data
+            .check_items()
+            .into_iter()
+            .map(|item| item.check)
+            .collect()
+    }
+
+    #[test]
+    fn GIVEN_valid_connection_WHEN_check_items_THEN_none() {
+        // GIVEN
+        let tls_data = TlsData {
+            protocol_version: Some("TLSv1_3".to_string()),
+            certificate_chain_len: 2,
+            certificate_chain_valid: true,
+            certificate_covers_host: true,
+            ..Default::default()
+        };
+
+        // WHEN
+        let actual = checks(&tls_data);
+
+        // THEN
+        assert!(actual.is_empty());
+    }
+
+    #[test]
+    fn GIVEN_expired_certificate_for_other_host_WHEN_check_items_THEN_expired_and_host() {
+        // GIVEN
+        let tls_data = TlsData {
+            protocol_version: Some("TLSv1_2".to_string()),
+            certificate_chain_len: 1,
+            certificate_expired: true,
+            ..Default::default()
+        };
+
+        // WHEN
+        let actual = checks(&tls_data);
+
+        // THEN
+        assert_eq!(
+            actual,
+            vec![
+                Check::TlsCertificateIsExpired,
+                Check::TlsCertificateDoesNotCoverHost
+            ]
+        );
+    }
+
+    #[test]
+    fn GIVEN_old_version_WHEN_check_items_THEN_only_version() {
+        // GIVEN
+        let tls_data = TlsData {
+            version_below_12: true,
+            error: Some("peer is incompatible".to_string()),
+            ..Default::default()
+        };
+
+        // WHEN
+        let actual = checks(&tls_data);
+
+        // THEN
+        assert_eq!(actual, vec![Check::TlsVersionIsBelow12]);
+    }
+}
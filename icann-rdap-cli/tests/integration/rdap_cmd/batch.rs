@@ -0,0 +1,49 @@
+#![allow(non_snake_case)]
+
+use {
+    icann_rdap_common::response::{Autnum, Domain},
+    icann_rdap_srv::storage::StoreOps,
+    serde_json::Value,
+};
+
+use crate::test_jig::TestJig;
+
+#[tokio::test(flavor = "multi_thread")]
+async fn GIVEN_batch_of_queries_WHEN_query_THEN_ndjson_line_for_each_query() {
+    // GIVEN
+    let mut test_jig = TestJig::new_rdap().await;
+    let mut tx = test_jig.mem.new_tx().await.expect("new transaction");
+    tx.add_domain(&Domain::builder().ldh_name("foo.example").build())
+        .await
+        .expect("add domain in tx");
+    tx.add_autnum(&Autnum::builder().autnum_range(700..710).build())
+        .await
+        .expect("add autnum in tx");
+    tx.commit().await.expect("tx commit");
+
+    // WHEN
+    test_jig
+        .cmd
+        .arg("--batch")
+        .arg("-")
+        .arg("-O")
+        .arg("ndjson")
+        .arg("--batch-server-rate")
+        .arg("0")
+        .write_stdin("# queries\nfoo.example\n\nAS705\nbar.example\n");
+
+    // THEN
+    let output = test_jig.cmd.output().expect("executing batch");
+    assert_eq!(output.status.code(), Some(108));
+    let lines: Vec<Value> = String::from_utf8_lossy(&output.stdout)
+        .lines()
+        .map(|line| serde_json::from_str(line).expect("parsing line"))
+        .collect();
+    assert_eq!(lines.len(), 3);
+    assert_eq!(lines[0]["query"], "foo.example");
+    assert_eq!(lines[0]["res_data"]["rdap"]["ldhName"], "foo.example");
+    assert_eq!(lines[1]["query"], "AS705");
+    assert_eq!(lines[1]["res_data"]["rdap"]["startAutnum"], 700);
+    assert_eq!(lines[2]["query"], "bar.example");
+    assert!(lines[2]["error"].is_object());
+}
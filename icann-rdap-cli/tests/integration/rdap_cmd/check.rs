@@ -21,3 +21,128 @@ async fn GIVEN_domain_with_check_WHEN_query_THEN_failure() {
     let assert = test_jig.cmd.assert();
     assert.failure();
 }
+
+#[tokio::test(flavor = "multi_thread")]
+async fn GIVEN_domain_with_info_check_WHEN_query_error_on_info_THEN_failure() {
+    // GIVEN
+    let mut test_jig = TestJig::new_rdap().await;
+    let mut tx = test_jig.mem.new_tx().await.expect("new transaction");
+    tx.add_domain(&Domain::builder().ldh_name("foo.example").build())
+        .await
+        .expect("add domain in tx");
+    tx.commit().await.expect("tx commit");
+
+    // WHEN
+    test_jig
+        .cmd
+        .arg("--error-on")
+        .arg("std_warn,info")
+        .arg("foo.example");
+
+    // THEN
+    let assert = test_jig.cmd.assert();
+    assert.failure().code(201);
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn GIVEN_domain_without_error_checks_WHEN_query_error_on_errors_THEN_success() {
+    // GIVEN
+    let mut test_jig = TestJig::new_rdap().await;
+    let mut tx = test_jig.mem.new_tx().await.expect("new transaction");
+    tx.add_domain(&Domain::response_obj().ldh_name("foo.example").build())
+        .await
+        .expect("add domain in tx");
+    tx.commit().await.expect("tx commit");
+
+    // WHEN
+    test_jig
+        .cmd
+        .arg("--error-on")
+        .arg("std_error,icann_error")
+        .arg("foo.example");
+
+    // THEN
+    let assert = test_jig.cmd.assert();
+    assert.success();
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn GIVEN_domain_with_check_WHEN_query_checks_json_THEN_checks_output() {
+    // GIVEN
+    let mut test_jig = TestJig::new_rdap().await;
+    let mut tx = test_jig.mem.new_tx().await.expect("new transaction");
+    tx.add_domain(&Domain::builder().ldh_name("foo.example").build())
+        .await
+        .expect("add domain in tx");
+    tx.commit().await.expect("tx commit");
+
+    // WHEN
+    test_jig
+        .cmd
+        .env("RDAP_OUTPUT", "checks-json")
+        .arg("foo.example");
+
+    // THEN
+    let output = test_jig.cmd.output().expect("executing domain query");
+    let responses: Vec<serde_json::Value> =
+        serde_json::from_slice(&output.stdout).expect("parsing stdout");
+    let checks = responses[0]["checks"].as_array().expect("checks array");
+    assert!(checks.iter().any(|c| c["check"] == "ldh_name_documentation"
+        && c["path"] == "[ROOT]/domain"
+        && c["class"] == "informational"));
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn GIVEN_valid_rdap_on_stdin_WHEN_input_THEN_success_without_query() {
+    // GIVEN
+    let mut test_jig = TestJig::new_rdap().await;
+    let rdap = r#"{
+        "rdapConformance": ["rdap_level_0"],
+        "objectClassName": "domain",
+        "ldhName": "foo.example"
+    }"#;
+
+    // WHEN
+    test_jig
+        .cmd
+        .arg("--input")
+        .arg("-")
+        .arg("-O")
+        .arg("checks-json")
+        .write_stdin(rdap);
+
+    // THEN
+    let output = test_jig.cmd.output().expect("executing input check");
+    assert!(output.status.success());
+    let responses: Vec<serde_json::Value> =
+        serde_json::from_slice(&output.stdout).expect("parsing stdout");
+    assert_eq!(responses[0]["url"], "-");
+    let checks = responses[0]["checks"].as_array().expect("checks array");
+    assert!(checks
+        .iter()
+        .any(|c| c["check"] == "ldh_name_documentation"));
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn GIVEN_invalid_rdap_on_stdin_WHEN_input_error_on_checks_THEN_failure() {
+    // GIVEN
+    let mut test_jig = TestJig::new_rdap().await;
+    let rdap = r#"{
+        "objectClassName": "domain",
+        "ldhName": "foo.example",
+        "links": [{ "value": "https://foo.example", "rel": "self" }]
+    }"#;
+
+    // WHEN
+    test_jig
+        .cmd
+        .arg("--input")
+        .arg("-")
+        .arg("--error-on")
+        .arg("std_error")
+        .write_stdin(rdap);
+
+    // THEN
+    let assert = test_jig.cmd.assert();
+    assert.failure().code(201);
+}
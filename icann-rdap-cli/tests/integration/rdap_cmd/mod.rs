@@ -1,5 +1,8 @@
+mod batch;
 mod cache;
 mod check;
 mod queries;
+#[cfg(feature = "replay")]
+mod replay;
 mod source;
 mod url;
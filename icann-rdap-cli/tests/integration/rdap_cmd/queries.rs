@@ -68,6 +68,29 @@ async fn GIVEN_entity_WHEN_query_THEN_success() {
     assert.success();
 }
 
+#[rstest]
+#[case("config")]
+#[case("init")]
+#[tokio::test(flavor = "multi_thread")]
+async fn GIVEN_entity_named_for_subcommand_WHEN_query_after_double_dash_THEN_success(
+    #[case] handle: &str,
+) {
+    // GIVEN
+    let mut test_jig = TestJig::new_rdap().await;
+    let mut tx = test_jig.mem.new_tx().await.expect("new transaction");
+    tx.add_entity(&Entity::builder().handle(handle).build())
+        .await
+        .expect("add entity in tx");
+    tx.commit().await.expect("tx commit");
+
+    // WHEN
+    test_jig.cmd.arg("--").arg(handle);
+
+    // THEN
+    let assert = test_jig.cmd.assert();
+    assert.success();
+}
+
 #[tokio::test(flavor = "multi_thread")]
 async fn GIVEN_nameserver_WHEN_query_THEN_success() {
     // GIVEN
@@ -158,6 +181,58 @@ async fn GIVEN_network_cidr_WHEN_query_THEN_success(#[case] db_cidr: &str, #[cas
     assert.success();
 }
 
+#[rstest]
+#[case("--up", "10.0.1.0/24")]
+#[case("--down", "10.0.0.0/16")]
+#[case("--top", "10.0.1.1")]
+#[case("--bottom", "10.0.0.0/16")]
+#[tokio::test(flavor = "multi_thread")]
+async fn GIVEN_networks_WHEN_query_relation_THEN_success(
+    #[case] relation: &str,
+    #[case] q_cidr: &str,
+) {
+    // GIVEN
+    let mut test_jig = TestJig::new_rdap().await;
+    let mut tx = test_jig.mem.new_tx().await.expect("new transaction");
+    for cidr in ["10.0.0.0/16", "10.0.1.0/24"] {
+        tx.add_network(&Network::builder().cidr(cidr).build().expect("cidr parsing"))
+            .await
+            .expect("add network in tx");
+    }
+    tx.commit().await.expect("tx commit");
+
+    // WHEN
+    test_jig.cmd.arg(relation).arg(q_cidr);
+
+    // THEN
+    let assert = test_jig.cmd.assert();
+    assert.success();
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn GIVEN_network_WHEN_search_ip_names_THEN_success() {
+    // GIVEN
+    let mut test_jig = TestJig::new_rdap().await;
+    let mut tx = test_jig.mem.new_tx().await.expect("new transaction");
+    tx.add_network(
+        &Network::builder()
+            .cidr("10.0.0.0/24")
+            .name("EXAMPLE-NET")
+            .build()
+            .expect("cidr parsing"),
+    )
+    .await
+    .expect("add network in tx");
+    tx.commit().await.expect("tx commit");
+
+    // WHEN
+    test_jig.cmd.arg("-t").arg("ip-name").arg("example-*");
+
+    // THEN
+    let assert = test_jig.cmd.assert();
+    assert.success();
+}
+
 #[tokio::test(flavor = "multi_thread")]
 async fn GIVEN_url_WHEN_query_THEN_success() {
     // GIVEN
@@ -212,3 +287,33 @@ async fn GIVEN_domain_WHEN_search_domain_names_THEN_success() {
     let assert = test_jig.cmd.assert();
     assert.success();
 }
+
+#[tokio::test(flavor = "multi_thread")]
+async fn GIVEN_connect_to_WHEN_query_other_host_THEN_success() {
+    // GIVEN
+    let mut test_jig = TestJig::new_rdap().await;
+    let mut tx = test_jig.mem.new_tx().await.expect("new transaction");
+    tx.add_domain(&Domain::builder().ldh_name("foo.example").build())
+        .await
+        .expect("add domain in tx");
+    tx.commit().await.expect("tx commit");
+    let port = test_jig
+        .rdap_base
+        .rsplit_once(':')
+        .and_then(|(_, rest)| rest.split_once('/'))
+        .map(|(port, _)| port.to_string())
+        .expect("port of server");
+
+    // WHEN
+    test_jig
+        .cmd
+        .arg("-B")
+        .arg(format!("http://rdap.test.example:{port}/rdap"))
+        .arg("--connect-to")
+        .arg(format!("rdap.test.example:{port}:127.0.0.1"))
+        .arg("foo.example");
+
+    // THEN
+    let assert = test_jig.cmd.assert();
+    assert.success();
+}
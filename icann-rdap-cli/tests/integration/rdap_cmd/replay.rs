@@ -0,0 +1,52 @@
+#![allow(non_snake_case)]
+
+use {icann_rdap_client::rdap::RequestResponseOwned, icann_rdap_common::response::RdapResponse};
+
+use crate::test_jig::TestJig;
+
+#[tokio::test(flavor = "multi_thread")]
+async fn GIVEN_recorded_domain_WHEN_query_THEN_success() {
+    // GIVEN
+    let mut test_jig = TestJig::new_rdap_replay("domain_lookup.json");
+
+    // WHEN
+    test_jig.cmd.arg("foo.example");
+
+    // THEN
+    let output = test_jig.cmd.output().expect("executing domain query");
+    assert!(output.status.success());
+    let responses: Vec<RequestResponseOwned> =
+        serde_json::from_slice(&output.stdout).expect("parsing stdout");
+    let rdap = &responses.first().expect("response is empty").res_data.rdap;
+    let RdapResponse::Domain(domain) = rdap else {
+        panic!("response type is {rdap}");
+    };
+    assert_eq!(domain.ldh_name(), Some("foo.example"));
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn GIVEN_recorded_not_found_WHEN_query_THEN_not_found() {
+    // GIVEN
+    let mut test_jig = TestJig::new_rdap_replay("domain_not_found.json");
+
+    // WHEN
+    test_jig.cmd.arg("nosuch.example");
+
+    // THEN
+    let assert = test_jig.cmd.assert();
+    assert.code(42);
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn GIVEN_unrecorded_domain_WHEN_query_THEN_io_error() {
+    // GIVEN
+    let mut test_jig = TestJig::new_rdap_replay("domain_lookup.json");
+
+    // WHEN
+    test_jig.cmd.arg("bar.example");
+
+    // THEN
+    let output = test_jig.cmd.output().expect("executing domain query");
+    assert_eq!(output.status.code(), Some(43));
+    assert!(String::from_utf8_lossy(&output.stderr).contains("no recorded response"));
+}
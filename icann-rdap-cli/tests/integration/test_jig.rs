@@ -2,13 +2,14 @@ use {
     assert_cmd::Command,
     icann_rdap_srv::{
         config::ListenConfig,
+        rdap::{compression::Compression, error_body::ErrorBodies},
         server::{AppState, Listener},
         storage::{
             mem::{config::MemConfig, ops::Mem},
             CommonConfig,
         },
     },
-    std::time::Duration,
+    std::{path::PathBuf, time::Duration},
     test_dir::{DirBuilder, FileType, TestDir},
 };
 
@@ -22,6 +23,8 @@ pub struct TestJig {
     pub cmd: Command,
     pub cmd_type: CommandType,
     pub rdap_base: String,
+    /// The cassette replayed by the command instead of querying the server.
+    pub replay: Option<PathBuf>,
     // pass ownership to the test so the directories are dropped when the test is done.
     test_dir: TestDir,
 }
@@ -35,6 +38,7 @@ impl TestJig {
     pub async fn new_rdap_with_dn_search() -> Self {
         let common_config = CommonConfig::builder()
             .domain_search_by_name_enable(true)
+            .entity_search_index_enable(false)
             .build();
         Self::new_common_config(common_config, CommandType::Rdap).await
     }
@@ -49,6 +53,15 @@ impl TestJig {
         let app_state = AppState {
             storage: mem.clone(),
             bootstrap: false,
+            search_page_size: None,
+            error_bodies: ErrorBodies::default(),
+            response_filter: None,
+            compression: Compression::default(),
+            deny_invalid_rdap: false,
+            reloader: None,
+            reload_endpoint: false,
+            rate_limiter: None,
+            proxy: None,
         };
         let _ = tracing_subscriber::fmt().try_init();
         let listener = Listener::listen(&ListenConfig::default())
@@ -63,13 +76,46 @@ impl TestJig {
         });
         let test_dir = TestDir::temp()
             .create("cache", FileType::Dir)
-            .create("config", FileType::Dir);
+            .create("config", FileType::Dir)
+            .create("data", FileType::Dir);
         let cmd = Command::new("sh"); //throw away
         Self {
             mem,
             cmd,
             cmd_type,
             rdap_base,
+            replay: None,
+            test_dir,
+        }
+        .new_cmd()
+    }
+
+    /// Creates a jig for the rdap command replaying a cassette in the `cassettes` directory
+    /// instead of querying a server, so no server is started.
+    ///
+    /// The cassettes are recorded against `https://rdap.example.com/rdap`.
+    #[cfg(feature = "replay")]
+    pub fn new_rdap_replay(cassette: &str) -> Self {
+        let mem = Mem::new(
+            MemConfig::builder()
+                .common_config(CommonConfig::default())
+                .build(),
+        );
+        let _ = tracing_subscriber::fmt().try_init();
+        let test_dir = TestDir::temp()
+            .create("cache", FileType::Dir)
+            .create("config", FileType::Dir)
+            .create("data", FileType::Dir);
+        let replay = PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+            .join("tests/integration/cassettes")
+            .join(cassette);
+        let cmd = Command::new("sh"); //throw away
+        Self {
+            mem,
+            cmd,
+            cmd_type: CommandType::Rdap,
+            rdap_base: "https://rdap.example.com/rdap".to_string(),
+            replay: Some(replay),
             test_dir,
         }
         .new_cmd()
@@ -90,7 +136,11 @@ impl TestJig {
                     .env("RDAP_LOG", "debug")
                     .env("RDAP_ALLOW_HTTP", "true")
                     .env("XDG_CACHE_HOME", self.test_dir.path("cache"))
-                    .env("XDG_CONFIG_HOME", self.test_dir.path("config"));
+                    .env("XDG_CONFIG_HOME", self.test_dir.path("config"))
+                    .env("XDG_DATA_HOME", self.test_dir.path("data"));
+                if let Some(replay) = &self.replay {
+                    cmd.env("RDAP_REPLAY", replay);
+                }
                 cmd
             }
             CommandType::RdapTest => {
@@ -100,7 +150,8 @@ impl TestJig {
                     .env("RDAP_TEST_LOG", "debug")
                     .env("RDAP_TEST_ALLOW_HTTP", "true")
                     .env("XDG_CACHE_HOME", self.test_dir.path("cache"))
-                    .env("XDG_CONFIG_HOME", self.test_dir.path("config"));
+                    .env("XDG_CONFIG_HOME", self.test_dir.path("config"))
+                    .env("XDG_DATA_HOME", self.test_dir.path("data"));
                 cmd
             }
         };
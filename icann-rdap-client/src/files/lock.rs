@@ -0,0 +1,133 @@
+//! Locking of the project directories so that concurrent processes may share them.
+
+use std::{
+    fs::{self, File, OpenOptions},
+    io::{BufRead, BufReader},
+    path::Path,
+    process,
+};
+
+pub const LOCK_FILE_NAME: &str = ".lock";
+
+/// A lock on a directory, held until dropped.
+///
+/// The lock is advisory and is taken on a lock file in the directory.
+/// Many processes may hold a shared lock at the same time, but only one
+/// process may hold an exclusive lock.
+pub struct DirLock(File);
+
+impl DirLock {
+    /// Blocks until a shared lock on the directory is acquired.
+    pub fn shared(dir: &Path) -> Result<Self, std::io::Error> {
+        let file = lock_file(dir)?;
+        file.lock_shared()?;
+        Ok(Self(file))
+    }
+
+    /// Blocks until an exclusive lock on the directory is acquired.
+    pub fn exclusive(dir: &Path) -> Result<Self, std::io::Error> {
+        let file = lock_file(dir)?;
+        file.lock()?;
+        Ok(Self(file))
+    }
+}
+
+impl Drop for DirLock {
+    fn drop(&mut self) {
+        let _ = self.0.unlock();
+    }
+}
+
+fn lock_file(dir: &Path) -> Result<File, std::io::Error> {
+    OpenOptions::new()
+        .create(true)
+        .truncate(false)
+        .write(true)
+        .open(dir.join(LOCK_FILE_NAME))
+}
+
+/// Writes a file while holding an exclusive lock on its directory.
+///
+/// The contents are written to a temporary file which is then renamed,
+/// so readers never see a partially written file.
+pub fn locked_write(path: &Path, contents: impl AsRef<[u8]>) -> Result<(), std::io::Error> {
+    let Some(dir) = path.parent() else {
+        return fs::write(path, contents);
+    };
+    let _lock = DirLock::exclusive(dir)?;
+    write_atomic(path, contents)
+}
+
+/// Writes a file by renaming a temporary file, without taking a lock.
+///
+/// Callers should already hold an exclusive lock on the directory.
+pub fn write_atomic(path: &Path, contents: impl AsRef<[u8]>) -> Result<(), std::io::Error> {
+    let Some(dir) = path.parent() else {
+        return fs::write(path, contents);
+    };
+    let file_name = path
+        .file_name()
+        .map(|f| f.to_string_lossy())
+        .unwrap_or_default();
+    let tmp_path = dir.join(format!(".{file_name}.{}.tmp", process::id()));
+    fs::write(&tmp_path, contents)?;
+    fs::rename(tmp_path, path)
+}
+
+/// Reads the lines of a file while holding a shared lock on its directory.
+pub fn locked_read_lines(path: &Path) -> Result<Vec<String>, std::io::Error> {
+    let _lock = match path.parent() {
+        Some(dir) => Some(DirLock::shared(dir)?),
+        None => None,
+    };
+    let buf = BufReader::new(File::open(path)?);
+    let mut lines = vec![];
+    for line in buf.lines() {
+        lines.push(line?);
+    }
+    Ok(lines)
+}
+
+#[cfg(test)]
+#[allow(non_snake_case)]
+mod test {
+    use std::{fs, path::PathBuf, process};
+
+    use super::{locked_read_lines, locked_write, DirLock, LOCK_FILE_NAME};
+
+    fn temp_dir(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("icann-rdap-{name}-{}", process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).expect("creating temp dir");
+        dir
+    }
+
+    #[test]
+    fn GIVEN_locked_write_WHEN_locked_read_lines_THEN_same_lines() {
+        // GIVEN
+        let dir = temp_dir("locked-write");
+        let path = dir.join("foo.cache");
+        locked_write(&path, "foo\nbar\n").expect("writing file");
+
+        // WHEN
+        let actual = locked_read_lines(&path).expect("reading file");
+
+        // THEN
+        assert_eq!(actual, vec!["foo", "bar"]);
+        assert!(dir.join(LOCK_FILE_NAME).exists());
+    }
+
+    #[test]
+    fn GIVEN_shared_lock_WHEN_try_exclusive_lock_THEN_would_block() {
+        // GIVEN
+        let dir = temp_dir("shared-lock");
+        let _shared = DirLock::shared(&dir).expect("shared lock");
+
+        // WHEN
+        let file = super::lock_file(&dir).expect("lock file");
+        let actual = file.try_lock();
+
+        // THEN
+        assert!(actual.is_err());
+    }
+}
@@ -0,0 +1,205 @@
+//! Size limits for cache directories with least recently used eviction.
+
+use std::{collections::HashMap, fs, io::ErrorKind, path::Path};
+
+use {
+    chrono::{DateTime, Utc},
+    serde::{Deserialize, Serialize},
+    tracing::debug,
+};
+
+use super::lock::{write_atomic, DirLock};
+
+pub const CACHE_INDEX_FILE_NAME: &str = ".index.json";
+
+/// Reads of an entry within this many seconds of its last recorded access are not
+/// recorded, so that reading the cache does not rewrite the index every time.
+const READ_ACCESS_RESOLUTION_SECS: i64 = 600;
+
+/// An index of the entries in a cache directory.
+#[derive(Debug, Default, Serialize, Deserialize, Clone, PartialEq, Eq)]
+pub struct CacheIndex {
+    pub entries: HashMap<String, IndexEntry>,
+}
+
+/// The size and last access time of an entry in a cache directory.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
+pub struct IndexEntry {
+    pub size: u64,
+    pub last_access: DateTime<Utc>,
+}
+
+impl CacheIndex {
+    /// Loads the index of a cache directory.
+    ///
+    /// A missing or unreadable index results in an empty index.
+    pub fn load(dir: &Path) -> Result<Self, std::io::Error> {
+        match fs::read_to_string(dir.join(CACHE_INDEX_FILE_NAME)) {
+            Ok(json) => Ok(serde_json::from_str(&json).unwrap_or_default()),
+            Err(e) if e.kind() == ErrorKind::NotFound => Ok(Self::default()),
+            Err(e) => Err(e),
+        }
+    }
+
+    /// Saves the index of a cache directory.
+    pub fn save(&self, dir: &Path) -> Result<(), std::io::Error> {
+        write_atomic(
+            &dir.join(CACHE_INDEX_FILE_NAME),
+            serde_json::to_string(self)?,
+        )
+    }
+
+    /// Records an access to an entry.
+    pub fn touch(&mut self, file_name: &str, size: u64, now: DateTime<Utc>) {
+        self.entries.insert(
+            file_name.to_string(),
+            IndexEntry {
+                size,
+                last_access: now,
+            },
+        );
+    }
+
+    /// Returns true if the last recorded access to an entry is recent enough that
+    /// a read of it need not be recorded.
+    pub fn is_recently_accessed(&self, file_name: &str, now: DateTime<Utc>) -> bool {
+        self.entries.get(file_name).is_some_and(|entry| {
+            (now - entry.last_access).num_seconds() < READ_ACCESS_RESOLUTION_SECS
+        })
+    }
+
+    /// The sum of the sizes of all the entries.
+    pub fn total_size(&self) -> u64 {
+        self.entries.values().map(|e| e.size).sum()
+    }
+
+    /// Removes the least recently used entries from the index until the total size
+    /// is no more than the given maximum, returning the names of the removed entries.
+    pub fn evict(&mut self, max_size: u64) -> Vec<String> {
+        let mut lru = self
+            .entries
+            .iter()
+            .map(|(name, entry)| (entry.last_access, name.clone()))
+            .collect::<Vec<_>>();
+        lru.sort();
+        let mut total = self.total_size();
+        let mut evicted = vec![];
+        for (_, name) in lru {
+            if total <= max_size {
+                break;
+            }
+            if let Some(entry) = self.entries.remove(&name) {
+                total = total.saturating_sub(entry.size);
+                evicted.push(name);
+            }
+        }
+        evicted
+    }
+}
+
+/// Records a read of a file in a cache directory.
+///
+/// The index is only rewritten if the file has not been accessed recently,
+/// as given by [CacheIndex::is_recently_accessed].
+pub fn record_cache_read(dir: &Path, file_name: &str, max_size: u64) -> Result<(), std::io::Error> {
+    let index = {
+        let _lock = DirLock::shared(dir)?;
+        CacheIndex::load(dir)?
+    };
+    if index.is_recently_accessed(file_name, Utc::now()) {
+        return Ok(());
+    }
+    record_cache_access(dir, file_name, max_size)
+}
+
+/// Records an access to a file in a cache directory, such as writing it, and evicts
+/// the least recently used files if the directory has grown beyond the maximum size.
+///
+/// A maximum size of 0 means the size of the directory is not limited.
+pub fn record_cache_access(
+    dir: &Path,
+    file_name: &str,
+    max_size: u64,
+) -> Result<(), std::io::Error> {
+    let _lock = DirLock::exclusive(dir)?;
+    let mut index = CacheIndex::load(dir)?;
+    let size = match fs::metadata(dir.join(file_name)) {
+        Ok(metadata) => metadata.len(),
+        Err(e) if e.kind() == ErrorKind::NotFound => 0,
+        Err(e) => return Err(e),
+    };
+    index.touch(file_name, size, Utc::now());
+    if max_size != 0 {
+        for evicted in index.evict(max_size) {
+            debug!("Evicting {evicted} from cache");
+            match fs::remove_file(dir.join(evicted)) {
+                Err(e) if e.kind() != ErrorKind::NotFound => return Err(e),
+                _ => {}
+            }
+        }
+    }
+    index.save(dir)
+}
+
+#[cfg(test)]
+#[allow(non_snake_case)]
+mod test {
+    use chrono::{Duration, Utc};
+
+    use super::{CacheIndex, READ_ACCESS_RESOLUTION_SECS};
+
+    #[test]
+    fn GIVEN_index_over_max_WHEN_evict_THEN_least_recent_evicted() {
+        // GIVEN
+        let now = Utc::now();
+        let mut index = CacheIndex::default();
+        index.touch("old", 100, now - Duration::hours(2));
+        index.touch("new", 100, now);
+        index.touch("middle", 100, now - Duration::hours(1));
+
+        // WHEN
+        let actual = index.evict(150);
+
+        // THEN
+        assert_eq!(actual, vec!["old", "middle"]);
+        assert_eq!(index.total_size(), 100);
+        assert!(index.entries.contains_key("new"));
+    }
+
+    #[test]
+    fn GIVEN_index_under_max_WHEN_evict_THEN_nothing_evicted() {
+        // GIVEN
+        let mut index = CacheIndex::default();
+        index.touch("foo", 100, Utc::now());
+
+        // WHEN
+        let actual = index.evict(1000);
+
+        // THEN
+        assert!(actual.is_empty());
+        assert_eq!(index.total_size(), 100);
+    }
+
+    #[test]
+    fn GIVEN_entries_WHEN_is_recently_accessed_THEN_only_recent_entries() {
+        // GIVEN
+        let now = Utc::now();
+        let mut index = CacheIndex::default();
+        index.touch("recent", 100, now - Duration::seconds(1));
+        index.touch(
+            "stale",
+            100,
+            now - Duration::seconds(READ_ACCESS_RESOLUTION_SECS),
+        );
+
+        // WHEN
+        let recent = index.is_recently_accessed("recent", now);
+        let stale = index.is_recently_accessed("stale", now);
+        let missing = index.is_recently_accessed("missing", now);
+
+        // THEN
+        assert!(recent);
+        assert!(!stale);
+        assert!(!missing);
+    }
+}
@@ -0,0 +1,4 @@
+//! Files and directories shared by concurrent processes.
+
+pub mod lock;
+pub mod lru;
@@ -1,9 +1,6 @@
 use {
     super::{GtldParams, RoleInfo, ToGtldWhois},
-    icann_rdap_common::{
-        contact::{Contact, PostalAddress},
-        response::Entity,
-    },
+    icann_rdap_common::{contact::PostalAddress, response::Entity},
 };
 
 impl ToGtldWhois for Option<Vec<Entity>> {
@@ -29,7 +26,7 @@ impl ToGtldWhois for Option<Vec<Entity>> {
                     params.label = label.to_string();
 
                     if let Some(vcard_array) = &entity.vcard_array {
-                        let role_info = extract_role_info(vcard_array, params);
+                        let role_info = extract_role_info(entity, vcard_array, params);
                         // Now use role_info to append to formatted_data
                         if !role_info.name.is_empty() {
                             if ["registrar", "reseller", "sponsor", "proxy"]
@@ -137,15 +134,19 @@ fn format_address_with_label(
     postal_address.to_gtld_whois(params).to_string()
 }
 
-fn extract_role_info(vcard_array: &[serde_json::Value], params: &mut GtldParams) -> RoleInfo {
-    let contact = match Contact::from_vcard(vcard_array) {
-        Some(contact) => contact,
-        None => return RoleInfo::default(),
+fn extract_role_info(
+    entity: &Entity,
+    vcard_array: &[serde_json::Value],
+    params: &mut GtldParams,
+) -> RoleInfo {
+    let Some(contact) = entity.contact() else {
+        return RoleInfo::default();
     };
     let mut adr = String::new();
-    let name = contact.full_name.unwrap_or_default();
+    let name = contact.full_name.clone().unwrap_or_default();
     let org = contact
         .organization_names
+        .as_ref()
         .and_then(|orgs| orgs.first().cloned())
         .unwrap_or_default();
 
@@ -166,6 +167,7 @@ fn extract_role_info(vcard_array: &[serde_json::Value], params: &mut GtldParams)
 
     let email = contact
         .emails
+        .as_ref()
         .and_then(|emails| emails.first().map(|email| email.email.clone()))
         .unwrap_or_default();
     let phone = contact
@@ -214,30 +216,30 @@ fn append_abuse_contact_info(entity: &Entity, formatted_data: &mut String) {
         for entity in entities {
             for role in entity.roles() {
                 if role.as_str() == "abuse" {
-                    if let Some(vcard_array) = &entity.vcard_array {
-                        if let Some(contact) = Contact::from_vcard(vcard_array) {
-                            // Emails
-                            if let Some(emails) = &contact.emails {
-                                for email in emails {
-                                    let abuse_contact_email = &email.email;
-                                    if !abuse_contact_email.is_empty() {
-                                        formatted_data.push_str(&format!(
-                                            "Registrar Abuse Contact Email: {}\n",
-                                            abuse_contact_email
-                                        ));
-                                    }
+                    if let Some(contact) =
+                        entity.vcard_array.as_ref().and_then(|_| entity.contact())
+                    {
+                        // Emails
+                        if let Some(emails) = &contact.emails {
+                            for email in emails {
+                                let abuse_contact_email = &email.email;
+                                if !abuse_contact_email.is_empty() {
+                                    formatted_data.push_str(&format!(
+                                        "Registrar Abuse Contact Email: {}\n",
+                                        abuse_contact_email
+                                    ));
                                 }
                             }
-                            // Phones
-                            if let Some(phones) = &contact.phones {
-                                for phone in phones {
-                                    let abuse_contact_phone = &phone.phone;
-                                    if !abuse_contact_phone.is_empty() {
-                                        formatted_data.push_str(&format!(
-                                            "Registrar Abuse Contact Phone: {}\n",
-                                            abuse_contact_phone
-                                        ));
-                                    }
+                        }
+                        // Phones
+                        if let Some(phones) = &contact.phones {
+                            for phone in phones {
+                                let abuse_contact_phone = &phone.phone;
+                                if !abuse_contact_phone.is_empty() {
+                                    formatted_data.push_str(&format!(
+                                        "Registrar Abuse Contact Phone: {}\n",
+                                        abuse_contact_phone
+                                    ));
                                 }
                             }
                         }
@@ -0,0 +1,205 @@
+//! Decoding of compressed responses.
+
+#[cfg(not(target_arch = "wasm32"))]
+use std::io::{Error as IoError, ErrorKind, Read};
+
+use {reqwest::Response, thiserror::Error};
+
+#[cfg(not(target_arch = "wasm32"))]
+use flate2::read::{GzDecoder, ZlibDecoder};
+
+use super::WrappedError;
+
+/// The value of the Accept-Encoding header, which are the content-encodings the client decodes.
+#[cfg(not(target_arch = "wasm32"))]
+pub(crate) const ACCEPT_ENCODING_VALUE: &str = "gzip, deflate, br";
+
+/// The default of the most bytes of a response body after it is decompressed.
+pub const DEFAULT_MAX_RESPONSE_BYTES: u64 = 16 * 1024 * 1024;
+
+/// The error of a response with a body larger than the client allows once decompressed,
+/// such as from a server sending a decompression bomb.
+#[derive(Debug, Error)]
+#[error("response from {url} is larger than {max_bytes} bytes")]
+pub struct ResponseTooLargeError {
+    /// The URL of the response.
+    pub url: String,
+
+    /// The most bytes allowed.
+    pub max_bytes: u64,
+}
+
+/// Reads the body of a response as text, decompressing it according to its
+/// content-encoding, and failing if it is larger than `max_bytes` before or after.
+#[cfg(not(target_arch = "wasm32"))]
+pub(crate) async fn read_body(
+    mut response: Response,
+    content_encoding: Option<&str>,
+    max_bytes: u64,
+) -> Result<String, WrappedError> {
+    let too_large = |response: &Response| ResponseTooLargeError {
+        url: response.url().to_string(),
+        max_bytes,
+    };
+    let mut body = vec![];
+    while let Some(chunk) = response.chunk().await? {
+        if (body.len() + chunk.len()) as u64 > max_bytes {
+            return Err(too_large(&response).into());
+        }
+        body.extend_from_slice(&chunk);
+    }
+    let body = decode_body(content_encoding, body, max_bytes)?;
+    if body.len() as u64 > max_bytes {
+        return Err(too_large(&response).into());
+    }
+    Ok(String::from_utf8(body)
+        .unwrap_or_else(|e| String::from_utf8_lossy(e.as_bytes()).into_owned()))
+}
+
+/// Reads the body of a response as text, which the browser has already decompressed.
+#[cfg(target_arch = "wasm32")]
+pub(crate) async fn read_body(
+    response: Response,
+    _content_encoding: Option<&str>,
+    max_bytes: u64,
+) -> Result<String, WrappedError> {
+    let url = response.url().to_string();
+    let text = response.text().await?;
+    if text.len() as u64 > max_bytes {
+        return Err(ResponseTooLargeError { url, max_bytes }.into());
+    }
+    Ok(text)
+}
+
+/// Decodes a body with a content-encoding of gzip, deflate or br (brotli).
+///
+/// No more than one byte beyond `max_bytes` is decoded, so that a body too
+/// large once decoded is found without decoding all of it.
+#[cfg(not(target_arch = "wasm32"))]
+pub(crate) fn decode_body(
+    content_encoding: Option<&str>,
+    body: Vec<u8>,
+    max_bytes: u64,
+) -> Result<Vec<u8>, IoError> {
+    let content_encoding = content_encoding.map(|encoding| encoding.trim().to_ascii_lowercase());
+    let decoder: Box<dyn Read + '_> = match content_encoding.as_deref() {
+        None | Some("identity") => return Ok(body),
+        Some("gzip" | "x-gzip") => Box::new(GzDecoder::new(body.as_slice())),
+        Some("deflate") => Box::new(ZlibDecoder::new(body.as_slice())),
+        Some("br") => Box::new(brotli::Decompressor::new(body.as_slice(), 4096)),
+        Some(encoding) => {
+            return Err(IoError::new(
+                ErrorKind::InvalidData,
+                format!("content-encoding '{encoding}' is not supported"),
+            ))
+        }
+    };
+    let mut decoded = vec![];
+    decoder
+        .take(max_bytes.saturating_add(1))
+        .read_to_end(&mut decoded)?;
+    Ok(decoded)
+}
+
+/// Encodes a body with a content-encoding, for replaying a recorded response.
+#[cfg(feature = "replay")]
+pub(crate) fn encode_body(content_encoding: &str, body: &[u8]) -> Result<Vec<u8>, IoError> {
+    use {
+        flate2::{
+            write::{GzEncoder, ZlibEncoder},
+            Compression,
+        },
+        std::io::Write,
+    };
+
+    match content_encoding.trim().to_ascii_lowercase().as_str() {
+        "gzip" | "x-gzip" => {
+            let mut encoder = GzEncoder::new(vec![], Compression::default());
+            encoder.write_all(body)?;
+            encoder.finish()
+        }
+        "deflate" => {
+            let mut encoder = ZlibEncoder::new(vec![], Compression::default());
+            encoder.write_all(body)?;
+            encoder.finish()
+        }
+        "br" => {
+            let mut encoded = vec![];
+            brotli::CompressorWriter::new(&mut encoded, 4096, 5, 22).write_all(body)?;
+            Ok(encoded)
+        }
+        _ => Ok(body.to_vec()),
+    }
+}
+
+#[cfg(all(test, not(target_arch = "wasm32")))]
+#[allow(non_snake_case)]
+mod tests {
+    use std::io::Write;
+
+    use {
+        flate2::{write::GzEncoder, Compression},
+        rstest::rstest,
+    };
+
+    use super::decode_body;
+
+    fn gzip(body: &[u8]) -> Vec<u8> {
+        let mut encoder = GzEncoder::new(vec![], Compression::default());
+        encoder.write_all(body).expect("compressing");
+        encoder.finish().expect("compressing")
+    }
+
+    fn brotli(body: &[u8]) -> Vec<u8> {
+        let mut encoded = vec![];
+        brotli::CompressorWriter::new(&mut encoded, 4096, 5, 22)
+            .write_all(body)
+            .expect("compressing");
+        encoded
+    }
+
+    #[rstest]
+    #[case(None)]
+    #[case(Some("gzip"))]
+    #[case(Some("GZIP"))]
+    #[case(Some("br"))]
+    fn GIVEN_encoded_body_WHEN_decode_body_THEN_decoded(#[case] content_encoding: Option<&str>) {
+        // GIVEN
+        let text = br#"{"objectClassName":"domain","ldhName":"foo.example"}"#;
+        let body = match content_encoding {
+            Some("br") => brotli(text),
+            Some(_) => gzip(text),
+            None => text.to_vec(),
+        };
+
+        // WHEN
+        let actual = decode_body(content_encoding, body, 1024).expect("decoding");
+
+        // THEN
+        assert_eq!(actual, text);
+    }
+
+    #[test]
+    fn GIVEN_decompression_bomb_WHEN_decode_body_THEN_decoding_stops_past_max() {
+        // GIVEN
+        let body = gzip(&vec![b' '; 1024 * 1024]);
+
+        // WHEN
+        let actual = decode_body(Some("gzip"), body, 1000).expect("decoding");
+
+        // THEN
+        assert_eq!(actual.len(), 1001);
+    }
+
+    #[test]
+    fn GIVEN_unknown_encoding_WHEN_decode_body_THEN_error() {
+        // GIVEN
+        let body = b"foo".to_vec();
+
+        // WHEN
+        let actual = decode_body(Some("compress"), body, 1024);
+
+        // THEN
+        assert!(actual.is_err());
+    }
+}
@@ -1,9 +1,23 @@
 //! The HTTP layer of RDAP.
 
+#[doc(inline)]
+pub use encoding::*;
+#[doc(inline)]
+pub use pin::*;
+#[doc(inline)]
+pub use rate_limit::*;
+#[cfg(feature = "replay")]
+#[doc(inline)]
+pub use replay::*;
 #[doc(inline)]
 pub use reqwest::*;
 #[doc(inline)]
 pub use wrapped::*;
 
+pub(crate) mod encoding;
+pub(crate) mod pin;
+pub(crate) mod rate_limit;
+#[cfg(feature = "replay")]
+pub(crate) mod replay;
 pub(crate) mod reqwest;
 pub(crate) mod wrapped;
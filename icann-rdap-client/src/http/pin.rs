@@ -0,0 +1,296 @@
+//! Pinning of the public keys of servers.
+
+use std::str::FromStr;
+
+use {
+    base64::{engine::general_purpose::STANDARD, Engine},
+    sha2::{Digest, Sha256},
+    thiserror::Error,
+};
+
+/// Pins the public key of the servers at a base URL, so that responses from
+/// URLs starting with the base URL are refused unless the certificate of the
+/// server has the public key.
+///
+/// This is given as `BASE_URL=PIN`, where the pin is the base64 of the SHA-256
+/// hash of the DER of the SubjectPublicKeyInfo of the certificate, as with the
+/// `--pinnedpubkey` option of curl.
+///
+/// ```rust
+/// use icann_rdap_client::http::SpkiPin;
+///
+/// let pin: SpkiPin = "https://rdap.example.com/=47DEQpj8HBSa+/TImW+5JCeuQeRkm5NMpJWZG3hSuFU="
+///     .parse()
+///     .unwrap();
+/// assert_eq!(pin.base_url, "https://rdap.example.com/");
+/// assert_eq!(pin.pin, "47DEQpj8HBSa+/TImW+5JCeuQeRkm5NMpJWZG3hSuFU=");
+/// assert!(pin.applies_to("https://rdap.example.com/domain/example.com"));
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SpkiPin {
+    pub base_url: String,
+    pub pin: String,
+}
+
+impl SpkiPin {
+    /// Returns true if the pin applies to the URL.
+    pub fn applies_to(&self, url: &str) -> bool {
+        url.starts_with(&self.base_url)
+    }
+}
+
+/// Error returned when a [SpkiPin] cannot be parsed.
+#[derive(Debug, Error)]
+#[error("'{0}' is not BASE_URL=PIN with a base64 SHA-256 PIN")]
+pub struct SpkiPinError(String);
+
+impl FromStr for SpkiPin {
+    type Err = SpkiPinError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let error = || SpkiPinError(s.to_string());
+        let (base_url, pin) = s.split_once('=').ok_or_else(error)?;
+        if base_url.is_empty() {
+            return Err(error());
+        }
+        let hash = STANDARD.decode(pin).map_err(|_| error())?;
+        if hash.len() != Sha256::output_size() {
+            return Err(error());
+        }
+        Ok(Self {
+            base_url: base_url.to_string(),
+            pin: pin.to_string(),
+        })
+    }
+}
+
+/// The error of a response from a server without a pinned public key.
+#[derive(Debug, Error)]
+#[error("public key of {url} does not match the pins for it")]
+pub struct SpkiPinMismatchError {
+    /// The URL of the response.
+    pub url: String,
+
+    /// The pin of the public key of the server, if the response was over TLS.
+    pub actual: Option<String>,
+}
+
+/// Verifies the certificate of the server of the response to a URL against the pins
+/// that apply to the URL, failing if none of them match.
+///
+/// The certificate is the DER of the certificate of the server, which is not present
+/// if the response was not over TLS.
+pub(crate) fn verify_spki_pins(
+    pins: &[SpkiPin],
+    url: &str,
+    certificate: Option<&[u8]>,
+) -> Result<(), SpkiPinMismatchError> {
+    let mut pins = pins.iter().filter(|pin| pin.applies_to(url)).peekable();
+    if pins.peek().is_none() {
+        return Ok(());
+    }
+    let actual = certificate.and_then(spki_pin);
+    if let Some(actual) = &actual {
+        if pins.any(|pin| pin.pin == *actual) {
+            return Ok(());
+        }
+    }
+    Err(SpkiPinMismatchError {
+        url: url.to_string(),
+        actual,
+    })
+}
+
+/// Gets the pin of the public key of a DER certificate.
+pub fn spki_pin(certificate: &[u8]) -> Option<String> {
+    let spki = subject_public_key_info(certificate)?;
+    Some(STANDARD.encode(Sha256::digest(spki)))
+}
+
+/// Gets the DER of the SubjectPublicKeyInfo of a DER certificate.
+///
+/// The SubjectPublicKeyInfo is the seventh element of the TBSCertificate when
+/// the optional version is present, otherwise the sixth (RFC 5280 section 4.1).
+fn subject_public_key_info(certificate: &[u8]) -> Option<&[u8]> {
+    let (_, certificate, _) = der_element(certificate)?;
+    let (_, tbs_certificate, _) = der_element(certificate)?;
+    let (mut element, _, mut rest) = der_element(tbs_certificate)?;
+    // version [0] EXPLICIT
+    if element[0] == 0xa0 {
+        (element, _, rest) = der_element(rest)?;
+    }
+    // serialNumber, signature, issuer, validity, subject
+    for _ in 0..5 {
+        (element, _, rest) = der_element(rest)?;
+    }
+    (element[0] == 0x30).then_some(element)
+}
+
+/// Splits the first DER element off the input, giving the whole element,
+/// its content, and the rest of the input.
+fn der_element(input: &[u8]) -> Option<(&[u8], &[u8], &[u8])> {
+    let first = *input.get(1)?;
+    let (length, header) = if first < 0x80 {
+        (usize::from(first), 2)
+    } else {
+        let octets = usize::from(first & 0x7f);
+        if octets == 0 || octets > 4 {
+            return None;
+        }
+        let length = input
+            .get(2..2 + octets)?
+            .iter()
+            .fold(0usize, |length, octet| (length << 8) | usize::from(*octet));
+        (length, 2 + octets)
+    };
+    let end = header.checked_add(length)?;
+    let element = input.get(..end)?;
+    Some((element, &element[header..], &input[end..]))
+}
+
+#[cfg(test)]
+#[allow(non_snake_case)]
+mod tests {
+    use rstest::rstest;
+
+    use super::{der_element, spki_pin, verify_spki_pins, SpkiPin};
+
+    /// A self-signed certificate for rdap.example.com.
+    const CERTIFICATE: &str = "MIIBszCCAVmgAwIBAgIUTNL/QuBFTc+jpkM3qUVUhkRh5sgwCgYIKoZIzj0EAwIwGzEZMBcGA1UEAwwQcmRhcC5leGFtcGxlLmNvbTAeFw0yNjEwMTgwMTA2MzdaFw0zNjEwMTUwMTA2MzdaMBsxGTAXBgNVBAMMEHJkYXAuZXhhbXBsZS5jb20wWTATBgcqhkjOPQIBBggqhkjOPQMBBwNCAAR6eM17nuPslIGxcEamZaE7haGdx5VVdo9oSn2btndzukJBB+HtEBbadjCqbTL9Xrm1PiA6eXdyYPkxj3TQFNFPo3sweTAdBgNVHQ4EFgQUNJV+lR29jSD0mB3TsHgbfNDfAQUwHwYDVR0jBBgwFoAUNJV+lR29jSD0mB3TsHgbfNDfAQUwDwYDVR0TAQH/BAUwAwEB/zAmBgNVHREEHzAdghByZGFwLmV4YW1wbGUuY29tgglsb2NhbGhvc3QwCgYIKoZIzj0EAwIDSAAwRQIhAJXkvkK5Mgf8y/+o3lR1IOM8Bsq6ekWlhwS7FFfPbwi3AiA2QwlilS5RbBkMFB2OQIcroWPQmZ2LzVxeO8aFHi83WQ==";
+
+    fn certificate() -> Vec<u8> {
+        use base64::{engine::general_purpose::STANDARD, Engine};
+        STANDARD.decode(CERTIFICATE).expect("decoding certificate")
+    }
+
+    #[test]
+    fn GIVEN_long_form_length_WHEN_der_element_THEN_split() {
+        // GIVEN
+        let mut input = vec![0x04, 0x81, 0x80];
+        input.extend([0u8; 0x80]);
+        input.push(0x05);
+
+        // WHEN
+        let (element, content, rest) = der_element(&input).expect("parsing element");
+
+        // THEN
+        assert_eq!(element.len(), 0x83);
+        assert_eq!(content.len(), 0x80);
+        assert_eq!(rest, [0x05]);
+    }
+
+    #[test]
+    fn GIVEN_truncated_input_WHEN_der_element_THEN_none() {
+        // GIVEN
+        let input = [0x30, 0x05, 0x01];
+
+        // WHEN
+        let actual = der_element(&input);
+
+        // THEN
+        assert!(actual.is_none());
+    }
+
+    #[test]
+    fn GIVEN_certificate_WHEN_spki_pin_THEN_pin_of_public_key() {
+        // GIVEN
+        let certificate = certificate();
+
+        // WHEN
+        let actual = spki_pin(&certificate);
+
+        // THEN
+        assert_eq!(
+            actual.as_deref(),
+            Some("m8VQhx4xGkZWPEG4lKdFHmhDcHbj7VV86F6pYUIHfcw=")
+        );
+    }
+
+    #[rstest]
+    #[case("https://rdap.example.com/")]
+    #[case("https://rdap.example.com=47DEQpj8HBSa+/TImW+5JCeuQeRkm5NMpJWZG3hSuFU")]
+    #[case("https://rdap.example.com=AAAA")]
+    #[case("=47DEQpj8HBSa+/TImW+5JCeuQeRkm5NMpJWZG3hSuFU=")]
+    fn GIVEN_invalid_spki_pin_WHEN_parse_THEN_error(#[case] pin: &str) {
+        // GIVEN pin
+
+        // WHEN
+        let actual = pin.parse::<SpkiPin>();
+
+        // THEN
+        assert!(actual.is_err());
+    }
+
+    #[test]
+    fn GIVEN_no_pins_for_url_WHEN_verify_THEN_ok() {
+        // GIVEN
+        let pins = vec![
+            "https://rdap.example.net/=47DEQpj8HBSa+/TImW+5JCeuQeRkm5NMpJWZG3hSuFU="
+                .parse::<SpkiPin>()
+                .expect("parsing pin"),
+        ];
+
+        // WHEN
+        let actual = verify_spki_pins(&pins, "https://rdap.example.com/help", None);
+
+        // THEN
+        assert!(actual.is_ok());
+    }
+
+    #[test]
+    fn GIVEN_matching_pin_WHEN_verify_THEN_ok() {
+        // GIVEN
+        let certificate = certificate();
+        let pin = spki_pin(&certificate).expect("pin of certificate");
+        let pins = vec![
+            "https://rdap.example.com/=47DEQpj8HBSa+/TImW+5JCeuQeRkm5NMpJWZG3hSuFU="
+                .parse::<SpkiPin>()
+                .expect("parsing pin"),
+            format!("https://rdap.example.com/={pin}")
+                .parse::<SpkiPin>()
+                .expect("parsing pin"),
+        ];
+
+        // WHEN
+        let actual = verify_spki_pins(&pins, "https://rdap.example.com/help", Some(&certificate));
+
+        // THEN
+        assert!(actual.is_ok());
+    }
+
+    #[test]
+    fn GIVEN_mismatched_pin_WHEN_verify_THEN_error() {
+        // GIVEN
+        let certificate = certificate();
+        let pins = vec![
+            "https://rdap.example.com/=47DEQpj8HBSa+/TImW+5JCeuQeRkm5NMpJWZG3hSuFU="
+                .parse::<SpkiPin>()
+                .expect("parsing pin"),
+        ];
+
+        // WHEN
+        let actual = verify_spki_pins(&pins, "https://rdap.example.com/help", Some(&certificate));
+
+        // THEN
+        let error = actual.expect_err("pins do not match");
+        assert_eq!(error.url, "https://rdap.example.com/help");
+        assert_eq!(error.actual, spki_pin(&certificate));
+    }
+
+    #[test]
+    fn GIVEN_pins_for_url_and_no_tls_WHEN_verify_THEN_error() {
+        // GIVEN
+        let pins = vec![
+            "http://rdap.example.com/=47DEQpj8HBSa+/TImW+5JCeuQeRkm5NMpJWZG3hSuFU="
+                .parse::<SpkiPin>()
+                .expect("parsing pin"),
+        ];
+
+        // WHEN
+        let actual = verify_spki_pins(&pins, "http://rdap.example.com/help", None);
+
+        // THEN
+        let error = actual.expect_err("no certificate to match");
+        assert!(error.actual.is_none());
+    }
+}
@@ -0,0 +1,103 @@
+//! Rate limits learned from servers.
+
+use std::{
+    collections::HashMap,
+    sync::{Arc, Mutex},
+};
+
+use chrono::{DateTime, Duration, Utc};
+
+/// The times before which servers have asked not to be sent requests, by host.
+///
+/// Servers ask for this with HTTP 429 responses and their Retry-After headers.
+/// Clones share the same state, so all the requests of a client honor the
+/// limits learned by any one of them.
+#[derive(Debug, Clone, Default)]
+pub struct RateLimits {
+    hosts: Arc<Mutex<HashMap<String, DateTime<Utc>>>>,
+}
+
+impl RateLimits {
+    /// Records that a host has asked not to be sent requests for a number of seconds.
+    pub fn hold_off(&self, host: &str, seconds: u64) {
+        let until = Utc::now() + Duration::seconds(seconds as i64);
+        let mut hosts = self.hosts.lock().unwrap_or_else(|e| e.into_inner());
+        let held = hosts.entry(host.to_string()).or_insert(until);
+        if *held < until {
+            *held = until;
+        }
+    }
+
+    /// Gets the number of seconds until a host may be sent requests, if any.
+    pub fn wait_for(&self, host: &str) -> Option<u64> {
+        let mut hosts = self.hosts.lock().unwrap_or_else(|e| e.into_inner());
+        let until = *hosts.get(host)?;
+        let wait = (until - Utc::now()).num_seconds();
+        if wait > 0 {
+            Some(wait as u64)
+        } else {
+            hosts.remove(host);
+            None
+        }
+    }
+}
+
+#[cfg(test)]
+#[allow(non_snake_case)]
+mod tests {
+    use super::RateLimits;
+
+    #[test]
+    fn GIVEN_no_hold_off_WHEN_wait_for_THEN_none() {
+        // GIVEN
+        let limits = RateLimits::default();
+
+        // WHEN
+        let actual = limits.wait_for("rdap.example.com");
+
+        // THEN
+        assert_eq!(actual, None);
+    }
+
+    #[test]
+    fn GIVEN_hold_off_WHEN_wait_for_THEN_only_that_host_waits() {
+        // GIVEN
+        let limits = RateLimits::default();
+        limits.hold_off("rdap.example.com", 60);
+
+        // WHEN
+        let held = limits.clone().wait_for("rdap.example.com");
+        let other = limits.wait_for("rdap.example.net");
+
+        // THEN
+        assert!(held.is_some_and(|seconds| seconds > 50 && seconds <= 60));
+        assert_eq!(other, None);
+    }
+
+    #[test]
+    fn GIVEN_shorter_hold_off_WHEN_wait_for_THEN_longer_kept() {
+        // GIVEN
+        let limits = RateLimits::default();
+        limits.hold_off("rdap.example.com", 60);
+        limits.hold_off("rdap.example.com", 5);
+
+        // WHEN
+        let actual = limits.wait_for("rdap.example.com");
+
+        // THEN
+        assert!(actual.is_some_and(|seconds| seconds > 50));
+    }
+
+    #[test]
+    fn GIVEN_expired_hold_off_WHEN_wait_for_THEN_none() {
+        // GIVEN
+        let limits = RateLimits::default();
+        limits.hold_off("rdap.example.com", 0);
+
+        // WHEN
+        let actual = limits.wait_for("rdap.example.com");
+
+        // THEN
+        assert_eq!(actual, None);
+    }
+}
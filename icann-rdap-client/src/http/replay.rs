@@ -0,0 +1,351 @@
+//! Recording and replaying of HTTP interactions.
+//!
+//! A [Cassette] either records the HTTP interactions of a client to a file or
+//! replays them from a file, so that the same responses can be given without
+//! the servers, such as in tests and offline demonstrations.
+
+use std::{
+    collections::HashMap,
+    fs,
+    io::{Error as IoError, ErrorKind},
+    path::{Path, PathBuf},
+    sync::Mutex,
+};
+
+use {
+    reqwest::{header::CONTENT_ENCODING, Request, Response, ResponseBuilderExt, Url},
+    serde::{Deserialize, Serialize},
+};
+
+use super::{
+    encoding::{decode_body, encode_body},
+    ReqwestClient, WrappedError,
+};
+
+/// A recorded HTTP request and the response to it.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Interaction {
+    pub request: RecordedRequest,
+    pub response: RecordedResponse,
+}
+
+/// A recorded HTTP request.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RecordedRequest {
+    pub method: String,
+    pub url: String,
+}
+
+/// A recorded HTTP response.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RecordedResponse {
+    /// The URL of the response.
+    pub url: String,
+    pub status_code: u16,
+    pub headers: Vec<(String, String)>,
+
+    /// The body, which is recorded decompressed and compressed again with the
+    /// content-encoding of the headers when replayed.
+    pub body: String,
+}
+
+/// Whether a [Cassette] records or replays.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CassetteMode {
+    /// Requests are sent to the servers and written to the file with their responses.
+    Record,
+
+    /// Requests are answered with the responses in the file, without sending them.
+    Replay,
+}
+
+/// Records HTTP interactions to a file or replays them from it.
+///
+/// The file is a JSON array of [Interaction]s. When replaying, a request is answered
+/// with the recorded responses to requests of the same method and URL in the order
+/// they were recorded, with the last one answering any further requests.
+#[derive(Debug)]
+pub struct Cassette {
+    mode: CassetteMode,
+    path: PathBuf,
+    interactions: Mutex<Vec<Interaction>>,
+    replayed: Mutex<HashMap<(String, String), usize>>,
+}
+
+impl Cassette {
+    /// Creates a cassette which records to the file, replacing any earlier recording.
+    pub fn record(path: impl AsRef<Path>) -> Self {
+        Self {
+            mode: CassetteMode::Record,
+            path: path.as_ref().to_path_buf(),
+            interactions: Mutex::new(vec![]),
+            replayed: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Creates a cassette which replays the recording in the file.
+    pub fn replay(path: impl AsRef<Path>) -> Result<Self, IoError> {
+        let interactions = serde_json::from_str(&fs::read_to_string(path.as_ref())?)?;
+        Ok(Self {
+            mode: CassetteMode::Replay,
+            path: path.as_ref().to_path_buf(),
+            interactions: Mutex::new(interactions),
+            replayed: Mutex::new(HashMap::new()),
+        })
+    }
+
+    /// Gets whether the cassette records or replays.
+    pub fn mode(&self) -> CassetteMode {
+        self.mode
+    }
+
+    /// Gets the recorded interactions.
+    pub fn interactions(&self) -> Vec<Interaction> {
+        self.interactions
+            .lock()
+            .map(|interactions| interactions.clone())
+            .unwrap_or_default()
+    }
+
+    /// Executes a request, recording it or replaying the response to it.
+    pub(crate) async fn execute(
+        &self,
+        request: Request,
+        client: &ReqwestClient,
+    ) -> Result<Response, WrappedError> {
+        let recorded_request = RecordedRequest {
+            method: request.method().to_string(),
+            url: request.url().to_string(),
+        };
+        let recorded_response = match self.mode {
+            CassetteMode::Replay => self.replayed_response(&recorded_request)?,
+            CassetteMode::Record => {
+                let response = client.execute(request).await?;
+                let content_encoding = response
+                    .headers()
+                    .get(CONTENT_ENCODING)
+                    .and_then(|value| value.to_str().ok())
+                    .map(str::to_string);
+                let recorded_response = RecordedResponse {
+                    url: response.url().to_string(),
+                    status_code: response.status().as_u16(),
+                    headers: response
+                        .headers()
+                        .iter()
+                        .map(|(name, value)| {
+                            (
+                                name.to_string(),
+                                String::from_utf8_lossy(value.as_bytes()).to_string(),
+                            )
+                        })
+                        .collect(),
+                    body: String::from_utf8_lossy(&decode_body(
+                        content_encoding.as_deref(),
+                        response.bytes().await?.to_vec(),
+                        u64::MAX,
+                    )?)
+                    .into_owned(),
+                };
+                self.add(Interaction {
+                    request: recorded_request,
+                    response: recorded_response.clone(),
+                })?;
+                recorded_response
+            }
+        };
+        to_response(recorded_response)
+    }
+
+    /// Gets the next recorded response to the request.
+    fn replayed_response(&self, request: &RecordedRequest) -> Result<RecordedResponse, IoError> {
+        let interactions = self.interactions.lock().map_err(poisoned)?;
+        let responses = interactions
+            .iter()
+            .filter(|interaction| {
+                interaction.request.method == request.method
+                    && interaction.request.url == request.url
+            })
+            .map(|interaction| &interaction.response)
+            .collect::<Vec<_>>();
+        let mut replayed = self.replayed.lock().map_err(poisoned)?;
+        let count = replayed
+            .entry((request.method.clone(), request.url.clone()))
+            .or_default();
+        let response = responses.get(*count).or(responses.last()).ok_or_else(|| {
+            IoError::new(
+                ErrorKind::NotFound,
+                format!(
+                    "no recorded response to {} {} in {}",
+                    request.method,
+                    request.url,
+                    self.path.display()
+                ),
+            )
+        })?;
+        *count += 1;
+        Ok((*response).clone())
+    }
+
+    /// Adds an interaction and writes the recording to the file.
+    fn add(&self, interaction: Interaction) -> Result<(), IoError> {
+        let mut interactions = self.interactions.lock().map_err(poisoned)?;
+        interactions.push(interaction);
+        fs::write(&self.path, serde_json::to_string_pretty(&*interactions)?)
+    }
+}
+
+/// Creates a Reqwest response from a recorded response.
+fn to_response(recorded: RecordedResponse) -> Result<Response, WrappedError> {
+    let url = Url::parse(&recorded.url).map_err(|e| IoError::new(ErrorKind::InvalidData, e))?;
+    let mut builder = http::Response::builder()
+        .status(recorded.status_code)
+        .url(url);
+    let mut body = recorded.body.into_bytes();
+    for (name, value) in &recorded.headers {
+        if CONTENT_ENCODING == name.as_str() {
+            body = encode_body(value, &body)?;
+        }
+        builder = builder.header(name, value);
+    }
+    let response = builder
+        .body(body)
+        .map_err(|e| IoError::new(ErrorKind::InvalidData, e))?;
+    Ok(Response::from(response))
+}
+
+fn poisoned<T>(_: T) -> IoError {
+    IoError::other("cassette lock poisoned")
+}
+
+#[cfg(test)]
+#[allow(non_snake_case)]
+mod tests {
+    use std::{
+        io::Write,
+        sync::atomic::{AtomicUsize, Ordering},
+    };
+
+    use super::{Cassette, RecordedRequest};
+
+    static CASSETTES: AtomicUsize = AtomicUsize::new(0);
+
+    const CASSETTE: &str = r#"[
+      {
+        "request": { "method": "GET", "url": "https://rdap.example.com/domain/foo.example" },
+        "response": {
+          "url": "https://rdap.example.com/domain/foo.example",
+          "status_code": 503,
+          "headers": [],
+          "body": ""
+        }
+      },
+      {
+        "request": { "method": "GET", "url": "https://rdap.example.com/domain/foo.example" },
+        "response": {
+          "url": "https://rdap.example.com/domain/foo.example",
+          "status_code": 200,
+          "headers": [["content-type", "application/rdap+json"]],
+          "body": "{\"objectClassName\":\"domain\",\"ldhName\":\"foo.example\"}"
+        }
+      }
+    ]"#;
+
+    fn cassette() -> Cassette {
+        let path = std::env::temp_dir().join(format!(
+            "cassette-{}-{}.json",
+            std::process::id(),
+            CASSETTES.fetch_add(1, Ordering::SeqCst)
+        ));
+        let mut file = std::fs::File::create(&path).expect("creating cassette");
+        file.write_all(CASSETTE.as_bytes())
+            .expect("writing cassette");
+        let cassette = Cassette::replay(&path).expect("reading cassette");
+        std::fs::remove_file(&path).expect("removing cassette");
+        cassette
+    }
+
+    fn request(url: &str) -> RecordedRequest {
+        RecordedRequest {
+            method: "GET".to_string(),
+            url: url.to_string(),
+        }
+    }
+
+    #[test]
+    fn GIVEN_recorded_responses_WHEN_replayed_THEN_in_order_and_last_repeats() {
+        // GIVEN
+        let cassette = cassette();
+        let request = request("https://rdap.example.com/domain/foo.example");
+
+        // WHEN
+        let actual = (0..3)
+            .map(|_| {
+                cassette
+                    .replayed_response(&request)
+                    .expect("replaying response")
+                    .status_code
+            })
+            .collect::<Vec<_>>();
+
+        // THEN
+        assert_eq!(actual, vec![503, 200, 200]);
+    }
+
+    #[test]
+    fn GIVEN_unrecorded_request_WHEN_replayed_THEN_error() {
+        // GIVEN
+        let cassette = cassette();
+
+        // WHEN
+        let actual =
+            cassette.replayed_response(&request("https://rdap.example.com/domain/bar.example"));
+
+        // THEN
+        assert!(actual.is_err());
+    }
+
+    #[tokio::test]
+    async fn GIVEN_recorded_response_WHEN_to_response_THEN_status_headers_and_body() {
+        // GIVEN
+        let recorded = cassette()
+            .interactions()
+            .pop()
+            .expect("recorded interaction")
+            .response;
+
+        // WHEN
+        let actual = super::to_response(recorded).expect("creating response");
+
+        // THEN
+        assert_eq!(actual.status().as_u16(), 200);
+        assert_eq!(
+            actual.url().as_str(),
+            "https://rdap.example.com/domain/foo.example"
+        );
+        assert_eq!(actual.headers()["content-type"], "application/rdap+json");
+        assert_eq!(
+            actual.text().await.expect("reading body"),
+            r#"{"objectClassName":"domain","ldhName":"foo.example"}"#
+        );
+    }
+
+    #[tokio::test]
+    async fn GIVEN_recorded_gzip_response_WHEN_to_response_and_read_THEN_body_decoded() {
+        // GIVEN
+        let recorded = super::RecordedResponse {
+            url: "https://rdap.example.com/domain/foo.example".to_string(),
+            status_code: 200,
+            headers: vec![("content-encoding".to_string(), "gzip".to_string())],
+            body: r#"{"objectClassName":"domain"}"#.to_string(),
+        };
+
+        // WHEN
+        let response = super::to_response(recorded).expect("creating response");
+        let actual = crate::http::encoding::read_body(response, Some("gzip"), 1024)
+            .await
+            .expect("reading body");
+
+        // THEN
+        assert_eq!(actual, r#"{"objectClassName":"domain"}"#);
+    }
+}
@@ -5,13 +5,68 @@ pub use reqwest::{
     Client as ReqwestClient, Error as ReqwestError,
 };
 
-use icann_rdap_common::media_types::{JSON_MEDIA_TYPE, RDAP_MEDIA_TYPE};
+use {
+    icann_rdap_common::media_types::{rdap_x_media_type, JSON_MEDIA_TYPE, RDAP_MEDIA_TYPE},
+    std::{net::IpAddr, str::FromStr},
+    thiserror::Error,
+};
 
 #[cfg(not(target_arch = "wasm32"))]
 use {icann_rdap_common::VERSION, std::net::SocketAddr, std::time::Duration};
 
 const ACCEPT_HEADER_VALUES: &str = const_format::formatcp!("{RDAP_MEDIA_TYPE}, {JSON_MEDIA_TYPE}");
 
+/// Directs connections to a host to an address instead of the addresses from DNS,
+/// similar to the `--resolve` option of curl.
+///
+/// This is given as `HOST:PORT:ADDR`, where IPv6 addresses may be in brackets.
+///
+/// ```rust
+/// use icann_rdap_client::http::ConnectTo;
+///
+/// let connect_to: ConnectTo = "rdap.example.com:443:[2001:db8::1]".parse().unwrap();
+/// assert_eq!(connect_to.host, "rdap.example.com");
+/// assert_eq!(connect_to.port, 443);
+/// assert_eq!(connect_to.addr.to_string(), "2001:db8::1");
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ConnectTo {
+    pub host: String,
+
+    /// The port of the connections. As with curl, the port used is the one
+    /// of the URL being queried.
+    pub port: u16,
+    pub addr: IpAddr,
+}
+
+/// Error returned when a [ConnectTo] cannot be parsed.
+#[derive(Debug, Error)]
+#[error("'{0}' is not HOST:PORT:ADDR")]
+pub struct ConnectToError(String);
+
+impl FromStr for ConnectTo {
+    type Err = ConnectToError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let error = || ConnectToError(s.to_string());
+        let mut parts = s.splitn(3, ':');
+        let (Some(host), Some(port), Some(addr)) = (parts.next(), parts.next(), parts.next())
+        else {
+            return Err(error());
+        };
+        if host.is_empty() {
+            return Err(error());
+        }
+        let addr = addr.strip_prefix('[').unwrap_or(addr);
+        let addr = addr.strip_suffix(']').unwrap_or(addr);
+        Ok(Self {
+            host: host.to_string(),
+            port: port.parse().map_err(|_| error())?,
+            addr: addr.parse().map_err(|_| error())?,
+        })
+    }
+}
+
 /// Configures the HTTP client.
 pub struct ReqwestClientConfig {
     /// This string is appended to the user agent.
@@ -55,6 +110,28 @@ pub struct ReqwestClientConfig {
     ///
     /// This is ignored on wasm32.
     pub timeout_secs: u64,
+
+    /// Connections to these hosts are made to the given addresses instead of
+    /// the addresses from DNS.
+    ///
+    /// This is ignored on wasm32.
+    pub connect_to: Vec<ConnectTo>,
+
+    /// If specified, connections are made to the unix domain socket at this path
+    /// instead of over TCP.
+    ///
+    /// This is ignored on wasm32 and platforms other than unix.
+    pub unix_socket: Option<String>,
+
+    /// If specified, the RDAP-X media type is preferred in the Accept header with
+    /// these extension identifiers, asking the server for only these extensions.
+    pub rdap_x_extensions: Option<Vec<String>>,
+
+    /// If true, responses carry the TLS information of the connection,
+    /// such as the certificate of the server.
+    ///
+    /// This is ignored on wasm32.
+    pub tls_info: bool,
 }
 
 impl Default for ReqwestClientConfig {
@@ -68,6 +145,10 @@ impl Default for ReqwestClientConfig {
             host: None,
             origin: None,
             timeout_secs: 60,
+            connect_to: vec![],
+            unix_socket: None,
+            rdap_x_extensions: None,
+            tls_info: false,
         }
     }
 }
@@ -84,6 +165,10 @@ impl ReqwestClientConfig {
         host: Option<HeaderValue>,
         origin: Option<HeaderValue>,
         timeout_secs: Option<u64>,
+        connect_to: Option<Vec<ConnectTo>>,
+        unix_socket: Option<String>,
+        rdap_x_extensions: Option<Vec<String>>,
+        tls_info: Option<bool>,
     ) -> Self {
         let default = Self::default();
         Self {
@@ -97,6 +182,10 @@ impl ReqwestClientConfig {
             host,
             origin,
             timeout_secs: timeout_secs.unwrap_or(default.timeout_secs),
+            connect_to: connect_to.unwrap_or(default.connect_to),
+            unix_socket,
+            rdap_x_extensions,
+            tls_info: tls_info.unwrap_or(default.tls_info),
         }
     }
 
@@ -111,6 +200,10 @@ impl ReqwestClientConfig {
         host: Option<HeaderValue>,
         origin: Option<HeaderValue>,
         timeout_secs: Option<u64>,
+        connect_to: Option<Vec<ConnectTo>>,
+        unix_socket: Option<String>,
+        rdap_x_extensions: Option<Vec<String>>,
+        tls_info: Option<bool>,
     ) -> Self {
         Self {
             user_agent_suffix: user_agent_suffix.unwrap_or(self.user_agent_suffix.clone()),
@@ -123,6 +216,10 @@ impl ReqwestClientConfig {
             host: host.map_or(self.host.clone(), Some),
             origin: origin.map_or(self.origin.clone(), Some),
             timeout_secs: timeout_secs.unwrap_or(self.timeout_secs),
+            connect_to: connect_to.unwrap_or(self.connect_to.clone()),
+            unix_socket: unix_socket.map_or(self.unix_socket.clone(), Some),
+            rdap_x_extensions: rdap_x_extensions.map_or(self.rdap_x_extensions.clone(), Some),
+            tls_info: tls_info.unwrap_or(self.tls_info),
         }
     }
 }
@@ -133,27 +230,7 @@ impl ReqwestClientConfig {
 /// necessary.
 #[cfg(not(target_arch = "wasm32"))]
 pub fn create_reqwest_client(config: &ReqwestClientConfig) -> Result<ReqwestClient, ReqwestError> {
-    let default_headers = default_headers(config);
-
-    let mut client = reqwest::Client::builder();
-
-    let redirects = if config.follow_redirects {
-        reqwest::redirect::Policy::default()
-    } else {
-        reqwest::redirect::Policy::none()
-    };
-    client = client
-        .timeout(Duration::from_secs(config.timeout_secs))
-        .user_agent(format!(
-            "icann_rdap client {VERSION} {}",
-            config.user_agent_suffix
-        ))
-        .redirect(redirects)
-        .https_only(config.https_only)
-        .danger_accept_invalid_hostnames(config.accept_invalid_host_names)
-        .danger_accept_invalid_certs(config.accept_invalid_certificates);
-
-    let client = client.default_headers(default_headers).build()?;
+    let client = client_builder(config).build()?;
     Ok(client)
 }
 
@@ -167,16 +244,19 @@ pub fn create_reqwest_client_with_addr(
     domain: &str,
     addr: SocketAddr,
 ) -> Result<ReqwestClient, ReqwestError> {
-    let default_headers = default_headers(config);
-
-    let mut client = reqwest::Client::builder();
+    let client = client_builder(config).resolve(domain, addr).build()?;
+    Ok(client)
+}
 
+/// Creates a Reqwest client builder from the configuration.
+#[cfg(not(target_arch = "wasm32"))]
+fn client_builder(config: &ReqwestClientConfig) -> reqwest::ClientBuilder {
     let redirects = if config.follow_redirects {
         reqwest::redirect::Policy::default()
     } else {
         reqwest::redirect::Policy::none()
     };
-    client = client
+    let mut client = reqwest::Client::builder()
         .timeout(Duration::from_secs(config.timeout_secs))
         .user_agent(format!(
             "icann_rdap client {VERSION} {}",
@@ -186,10 +266,18 @@ pub fn create_reqwest_client_with_addr(
         .https_only(config.https_only)
         .danger_accept_invalid_hostnames(config.accept_invalid_host_names)
         .danger_accept_invalid_certs(config.accept_invalid_certificates)
-        .resolve(domain, addr);
-
-    let client = client.default_headers(default_headers).build()?;
-    Ok(client)
+        .tls_info(config.tls_info);
+    for connect_to in &config.connect_to {
+        client = client.resolve(
+            &connect_to.host,
+            SocketAddr::new(connect_to.addr, connect_to.port),
+        );
+    }
+    #[cfg(unix)]
+    if let Some(unix_socket) = &config.unix_socket {
+        client = client.unix_socket(unix_socket.as_str());
+    }
+    client.default_headers(default_headers(config))
 }
 
 /// Creates an HTTP client using Reqwest. The Reqwest
@@ -208,11 +296,27 @@ pub fn create_reqwest_client(config: &ReqwestClientConfig) -> Result<ReqwestClie
     Ok(client)
 }
 
+/// Gets the value of the Accept header, which prefers the RDAP-X media type
+/// if extensions are to be negotiated.
+fn accept_header_value(config: &ReqwestClientConfig) -> HeaderValue {
+    let Some(extensions) = &config.rdap_x_extensions else {
+        return HeaderValue::from_static(ACCEPT_HEADER_VALUES);
+    };
+    let accept = format!(
+        "{}, {RDAP_MEDIA_TYPE};q=0.9, {JSON_MEDIA_TYPE};q=0.8",
+        rdap_x_media_type(extensions)
+    );
+    HeaderValue::from_str(&accept).unwrap_or(HeaderValue::from_static(ACCEPT_HEADER_VALUES))
+}
+
 fn default_headers(config: &ReqwestClientConfig) -> header::HeaderMap {
     let mut default_headers = header::HeaderMap::new();
+    default_headers.insert(header::ACCEPT, accept_header_value(config));
+    // the browser negotiates the compression of WASM requests
+    #[cfg(not(target_arch = "wasm32"))]
     default_headers.insert(
-        header::ACCEPT,
-        HeaderValue::from_static(ACCEPT_HEADER_VALUES),
+        header::ACCEPT_ENCODING,
+        HeaderValue::from_static(super::encoding::ACCEPT_ENCODING_VALUE),
     );
     if let Some(host) = &config.host {
         default_headers.insert(header::HOST, host.into());
@@ -222,3 +326,87 @@ fn default_headers(config: &ReqwestClientConfig) -> header::HeaderMap {
     }
     default_headers
 }
+
+#[cfg(test)]
+#[allow(non_snake_case)]
+mod tests {
+    use rstest::rstest;
+
+    use super::{accept_header_value, ConnectTo, ReqwestClientConfig};
+
+    #[rstest]
+    #[case("rdap.example.com:443:192.0.2.1", "rdap.example.com", 443, "192.0.2.1")]
+    #[case(
+        "rdap.example.com:80:2001:db8::1",
+        "rdap.example.com",
+        80,
+        "2001:db8::1"
+    )]
+    #[case(
+        "rdap.example.com:80:[2001:db8::1]",
+        "rdap.example.com",
+        80,
+        "2001:db8::1"
+    )]
+    fn GIVEN_connect_to_WHEN_parse_THEN_parts(
+        #[case] connect_to: &str,
+        #[case] host: &str,
+        #[case] port: u16,
+        #[case] addr: &str,
+    ) {
+        // GIVEN connect_to
+
+        // WHEN
+        let actual: ConnectTo = connect_to.parse().expect("parsing connect to");
+
+        // THEN
+        assert_eq!(actual.host, host);
+        assert_eq!(actual.port, port);
+        assert_eq!(actual.addr.to_string(), addr);
+    }
+
+    #[rstest]
+    #[case("rdap.example.com")]
+    #[case("rdap.example.com:443")]
+    #[case(":443:192.0.2.1")]
+    #[case("rdap.example.com:https:192.0.2.1")]
+    #[case("rdap.example.com:443:rdap.example.net")]
+    fn GIVEN_invalid_connect_to_WHEN_parse_THEN_error(#[case] connect_to: &str) {
+        // GIVEN connect_to
+
+        // WHEN
+        let actual = connect_to.parse::<ConnectTo>();
+
+        // THEN
+        assert!(actual.is_err());
+    }
+
+    #[test]
+    fn GIVEN_default_config_WHEN_accept_header_value_THEN_rdap_and_json() {
+        // GIVEN
+        let config = ReqwestClientConfig::default();
+
+        // WHEN
+        let actual = accept_header_value(&config);
+
+        // THEN
+        assert_eq!(actual, "application/rdap+json, application/json");
+    }
+
+    #[test]
+    fn GIVEN_rdap_x_extensions_WHEN_accept_header_value_THEN_rdap_x_preferred() {
+        // GIVEN
+        let config = ReqwestClientConfig::builder()
+            .rdap_x_extensions(vec!["rdap_level_0".to_string(), "redacted".to_string()])
+            .build();
+
+        // WHEN
+        let actual = accept_header_value(&config);
+
+        // THEN
+        assert_eq!(
+            actual,
+            r#"application/rdap-x+json;extensions="rdap_level_0 redacted", application/rdap+json;q=0.9, application/json;q=0.8"#
+        );
+    }
+}
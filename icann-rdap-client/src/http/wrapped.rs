@@ -2,38 +2,179 @@
 
 pub use reqwest::{header::HeaderValue, Client as ReqwestClient, Error as ReqwestError};
 use {
-    icann_rdap_common::httpdata::HttpData,
-    reqwest::header::{
-        ACCESS_CONTROL_ALLOW_ORIGIN, CACHE_CONTROL, CONTENT_TYPE, EXPIRES, LOCATION, RETRY_AFTER,
-        STRICT_TRANSPORT_SECURITY,
+    chrono::{DateTime, Utc},
+    icann_rdap_common::httpdata::{HttpData, Redirect},
+    reqwest::{
+        header::{
+            ACCESS_CONTROL_ALLOW_ORIGIN, CACHE_CONTROL, CONTENT_ENCODING, CONTENT_TYPE, ETAG,
+            EXPIRES, IF_MODIFIED_SINCE, IF_NONE_MATCH, LAST_MODIFIED, LOCATION, RETRY_AFTER,
+            STRICT_TRANSPORT_SECURITY,
+        },
+        RequestBuilder, Response,
     },
+    thiserror::Error,
 };
 
+#[cfg(feature = "replay")]
+use {super::Cassette, std::sync::Arc};
 use {
-    super::{create_reqwest_client, ReqwestClientConfig},
+    super::{
+        create_reqwest_client, read_body, verify_spki_pins, ConnectTo, RateLimits,
+        ReqwestClientConfig, ResponseTooLargeError, SpkiPin, SpkiPinMismatchError,
+        DEFAULT_MAX_RESPONSE_BYTES,
+    },
     crate::RdapClientError,
 };
 
 #[cfg(not(target_arch = "wasm32"))]
 use {
-    super::create_reqwest_client_with_addr, chrono::DateTime, chrono::Utc, reqwest::StatusCode,
-    std::net::SocketAddr, tracing::debug, tracing::info,
+    super::create_reqwest_client_with_addr, reqwest::StatusCode, std::net::SocketAddr,
+    tracing::debug, tracing::info,
 };
 
 /// Used by the request functions.
 #[derive(Clone, Copy)]
 pub struct RequestOptions {
-    pub(crate) max_retry_secs: u32,
-    pub(crate) def_retry_secs: u32,
-    pub(crate) max_retries: u16,
+    pub(crate) retry_policy: RetryPolicy,
+    pub(crate) strict_media_types: bool,
+
+    /// If true, redirects are followed by the request functions, which record
+    /// each redirect in the HTTP data, instead of by the Reqwest client.
+    pub(crate) follow_redirects: bool,
+
+    /// The most bytes of a response body, both before and after it is decompressed.
+    pub(crate) max_response_bytes: u64,
 }
 
 impl Default for RequestOptions {
     fn default() -> Self {
         Self {
-            max_retry_secs: 120,
-            def_retry_secs: 60,
+            retry_policy: RetryPolicy::default(),
+            strict_media_types: false,
+            follow_redirects: false,
+            max_response_bytes: DEFAULT_MAX_RESPONSE_BYTES,
+        }
+    }
+}
+
+/// The most redirects followed for a request, which is the default of Reqwest.
+const MAX_REDIRECTS: usize = 10;
+
+/// How requests are retried when servers respond with HTTP 429 (too many requests)
+/// or HTTP 503 (service unavailable).
+///
+/// A server may say how long to wait with the Retry-After header, which is honored
+/// up to `max_retry_secs`. Otherwise the wait backs off exponentially from
+/// `def_retry_secs`, doubling with each retry.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RetryPolicy {
+    /// The number of times a request is retried.
+    pub max_retries: u16,
+
+    /// The seconds waited before the first retry when a server does not give a
+    /// usable Retry-After.
+    pub def_retry_secs: u32,
+
+    /// The most seconds waited, whatever a server asks for.
+    pub max_retry_secs: u32,
+
+    /// If true, the waits of the backoff are randomly shortened by up to half,
+    /// so that many clients refused at once do not all retry at once.
+    pub jitter: bool,
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        Self {
             max_retries: 1,
+            def_retry_secs: 60,
+            max_retry_secs: 120,
+            jitter: true,
+        }
+    }
+}
+
+impl RetryPolicy {
+    /// Returns true if a response with the status code should be retried.
+    pub fn is_retry_status(status_code: u16) -> bool {
+        status_code == 429 || status_code == 503
+    }
+
+    /// Gets the seconds to wait before a retry, given the number of retries already
+    /// made and the Retry-After header of the response.
+    ///
+    /// The jitter is a fraction between 0 and 1 by which the backoff is shortened,
+    /// up to half of it.
+    pub fn wait_secs(
+        &self,
+        retries: u16,
+        retry_after: Option<&str>,
+        now: DateTime<Utc>,
+        jitter: f64,
+    ) -> u64 {
+        let max_retry_secs = u64::from(self.max_retry_secs);
+        let retry_after_secs = retry_after.and_then(|retry_after| {
+            if let Ok(date) = DateTime::parse_from_rfc2822(retry_after) {
+                u64::try_from((date.with_timezone(&Utc) - now).num_seconds()).ok()
+            } else {
+                retry_after.trim().parse::<u64>().ok()
+            }
+        });
+        match retry_after_secs {
+            Some(secs) if secs > 0 => secs.min(max_retry_secs),
+            _ => {
+                let backoff = u64::from(self.def_retry_secs)
+                    .saturating_mul(1u64.checked_shl(retries.into()).unwrap_or(u64::MAX))
+                    .min(max_retry_secs);
+                if self.jitter {
+                    backoff - (backoff as f64 * jitter.clamp(0.0, 1.0) / 2.0) as u64
+                } else {
+                    backoff
+                }
+            }
+        }
+    }
+}
+
+/// The error of a request to which the server was still responding with HTTP 429
+/// or HTTP 503 when the retries ran out.
+#[derive(Debug, Error)]
+#[error("{source} after {} retries", .http_data.retries.unwrap_or_default())]
+pub struct RetriesExhaustedError {
+    /// The HTTP data of the last response, with the number of retries.
+    pub http_data: Box<HttpData>,
+
+    #[source]
+    pub source: ReqwestError,
+}
+
+/// The errors of the wrapped requests.
+#[derive(Debug, Error)]
+pub(crate) enum WrappedError {
+    #[error(transparent)]
+    Reqwest(#[from] ReqwestError),
+
+    #[error(transparent)]
+    RetriesExhausted(#[from] RetriesExhaustedError),
+
+    #[error(transparent)]
+    SpkiPinMismatch(#[from] SpkiPinMismatchError),
+
+    #[error(transparent)]
+    ResponseTooLarge(#[from] ResponseTooLargeError),
+
+    #[error(transparent)]
+    Io(#[from] std::io::Error),
+}
+
+impl From<WrappedError> for RdapClientError {
+    fn from(e: WrappedError) -> Self {
+        match e {
+            WrappedError::Reqwest(e) => Self::Client(e),
+            WrappedError::RetriesExhausted(e) => Self::RetriesExhausted(e),
+            WrappedError::SpkiPinMismatch(e) => Self::SpkiPinMismatch(e),
+            WrappedError::ResponseTooLarge(e) => Self::ResponseTooLarge(e),
+            WrappedError::Io(e) => Self::IoError(e),
         }
     }
 }
@@ -46,6 +187,13 @@ pub struct ClientConfig {
 
     /// Request options.
     request_options: RequestOptions,
+
+    /// Pins of the public keys of servers.
+    spki_pins: Vec<SpkiPin>,
+
+    /// Records or replays the HTTP interactions of the clients.
+    #[cfg(feature = "replay")]
+    cassette: Option<Arc<Cassette>>,
 }
 
 #[buildstructor::buildstructor]
@@ -63,6 +211,13 @@ impl ClientConfig {
         max_retry_secs: Option<u32>,
         def_retry_secs: Option<u32>,
         max_retries: Option<u16>,
+        retry_policy: Option<RetryPolicy>,
+        strict_media_types: Option<bool>,
+        max_response_bytes: Option<u64>,
+        connect_to: Option<Vec<ConnectTo>>,
+        unix_socket: Option<String>,
+        rdap_x_extensions: Option<Vec<String>>,
+        spki_pins: Option<Vec<SpkiPin>>,
     ) -> Self {
         let default_cc = ReqwestClientConfig::default();
         let default_ro = RequestOptions::default();
@@ -78,12 +233,25 @@ impl ClientConfig {
                 host,
                 origin,
                 timeout_secs: timeout_secs.unwrap_or(default_cc.timeout_secs),
+                connect_to: connect_to.unwrap_or(default_cc.connect_to),
+                unix_socket,
+                rdap_x_extensions,
+                tls_info: default_cc.tls_info,
             },
             request_options: RequestOptions {
-                max_retry_secs: max_retry_secs.unwrap_or(default_ro.max_retry_secs),
-                def_retry_secs: def_retry_secs.unwrap_or(default_ro.def_retry_secs),
-                max_retries: max_retries.unwrap_or(default_ro.max_retries),
+                retry_policy: retry_policy_from(
+                    retry_policy.unwrap_or(default_ro.retry_policy),
+                    max_retry_secs,
+                    def_retry_secs,
+                    max_retries,
+                ),
+                strict_media_types: strict_media_types.unwrap_or(default_ro.strict_media_types),
+                max_response_bytes: max_response_bytes.unwrap_or(default_ro.max_response_bytes),
+                ..default_ro
             },
+            spki_pins: spki_pins.unwrap_or_default(),
+            #[cfg(feature = "replay")]
+            cassette: None,
         }
     }
 
@@ -101,6 +269,13 @@ impl ClientConfig {
         max_retry_secs: Option<u32>,
         def_retry_secs: Option<u32>,
         max_retries: Option<u16>,
+        retry_policy: Option<RetryPolicy>,
+        strict_media_types: Option<bool>,
+        max_response_bytes: Option<u64>,
+        connect_to: Option<Vec<ConnectTo>>,
+        unix_socket: Option<String>,
+        rdap_x_extensions: Option<Vec<String>>,
+        spki_pins: Option<Vec<SpkiPin>>,
     ) -> Self {
         Self {
             client_config: ReqwestClientConfig {
@@ -115,16 +290,83 @@ impl ClientConfig {
                 host: host.map_or(self.client_config.host.clone(), Some),
                 origin: origin.map_or(self.client_config.origin.clone(), Some),
                 timeout_secs: timeout_secs.unwrap_or(self.client_config.timeout_secs),
+                connect_to: connect_to.unwrap_or(self.client_config.connect_to.clone()),
+                unix_socket: unix_socket.map_or(self.client_config.unix_socket.clone(), Some),
+                rdap_x_extensions: rdap_x_extensions
+                    .map_or(self.client_config.rdap_x_extensions.clone(), Some),
+                tls_info: self.client_config.tls_info,
             },
             request_options: RequestOptions {
-                max_retry_secs: max_retry_secs.unwrap_or(self.request_options.max_retry_secs),
-                def_retry_secs: def_retry_secs.unwrap_or(self.request_options.def_retry_secs),
-                max_retries: max_retries.unwrap_or(self.request_options.max_retries),
+                retry_policy: retry_policy_from(
+                    retry_policy.unwrap_or(self.request_options.retry_policy),
+                    max_retry_secs,
+                    def_retry_secs,
+                    max_retries,
+                ),
+                strict_media_types: strict_media_types
+                    .unwrap_or(self.request_options.strict_media_types),
+                max_response_bytes: max_response_bytes
+                    .unwrap_or(self.request_options.max_response_bytes),
+                ..self.request_options
             },
+            spki_pins: spki_pins.unwrap_or(self.spki_pins.clone()),
+            #[cfg(feature = "replay")]
+            cassette: self.cassette.clone(),
         }
     }
 }
 
+impl ClientConfig {
+    /// Gets the config of the Reqwest client, which does not follow redirects
+    /// so that the request functions can follow and record them.
+    fn reqwest_config(&self) -> ReqwestClientConfig {
+        ReqwestClientConfig::from_config(&self.client_config)
+            .follow_redirects(false)
+            .tls_info(!self.spki_pins.is_empty())
+            .build()
+    }
+
+    /// Gets the request options of the wrapped client.
+    fn client_request_options(&self) -> RequestOptions {
+        RequestOptions {
+            follow_redirects: self.client_config.follow_redirects,
+            ..self.request_options
+        }
+    }
+
+    /// Wraps a Reqwest client made with the config.
+    fn wrap(&self, reqwest_client: ReqwestClient) -> Client {
+        let mut client = Client::new(reqwest_client, self.client_request_options());
+        client.set_spki_pins(self.spki_pins.clone());
+        #[cfg(feature = "replay")]
+        if let Some(cassette) = &self.cassette {
+            client.set_cassette(cassette.clone());
+        }
+        client
+    }
+
+    /// Records or replays the HTTP interactions of the clients made with the config.
+    #[cfg(feature = "replay")]
+    pub fn set_cassette(&mut self, cassette: Cassette) {
+        self.cassette = Some(Arc::new(cassette));
+    }
+}
+
+/// Gets a retry policy with the individually given settings in place of those of the policy.
+fn retry_policy_from(
+    policy: RetryPolicy,
+    max_retry_secs: Option<u32>,
+    def_retry_secs: Option<u32>,
+    max_retries: Option<u16>,
+) -> RetryPolicy {
+    RetryPolicy {
+        max_retry_secs: max_retry_secs.unwrap_or(policy.max_retry_secs),
+        def_retry_secs: def_retry_secs.unwrap_or(policy.def_retry_secs),
+        max_retries: max_retries.unwrap_or(policy.max_retries),
+        ..policy
+    }
+}
+
 /// A wrapper around Reqwest client to give additional features when used with the request functions.
 pub struct Client {
     /// The reqwest client.
@@ -132,6 +374,16 @@ pub struct Client {
 
     /// Request options.
     pub(crate) request_options: RequestOptions,
+
+    /// Rate limits learned from servers.
+    pub(crate) rate_limits: RateLimits,
+
+    /// Pins of the public keys of servers, against which responses are verified.
+    pub(crate) spki_pins: Vec<SpkiPin>,
+
+    /// Records or replays the HTTP interactions of the client.
+    #[cfg(feature = "replay")]
+    pub(crate) cassette: Option<Arc<Cassette>>,
 }
 
 impl Client {
@@ -139,8 +391,41 @@ impl Client {
         Self {
             reqwest_client,
             request_options,
+            rate_limits: RateLimits::default(),
+            spki_pins: vec![],
+            #[cfg(feature = "replay")]
+            cassette: None,
         }
     }
+
+    /// Changes the pins of the public keys of servers.
+    ///
+    /// The Reqwest client must give the TLS information of responses for
+    /// the pins to match.
+    pub fn set_spki_pins(&mut self, spki_pins: Vec<SpkiPin>) {
+        self.spki_pins = spki_pins;
+    }
+
+    /// Records or replays the HTTP interactions of the client.
+    #[cfg(feature = "replay")]
+    pub fn set_cassette(&mut self, cassette: Arc<Cassette>) {
+        self.cassette = Some(cassette);
+    }
+
+    /// Changes how requests are retried.
+    pub fn set_retry_policy(&mut self, policy: RetryPolicy) {
+        self.request_options.retry_policy = policy;
+    }
+
+    /// Gets how requests are retried.
+    pub fn retry_policy(&self) -> &RetryPolicy {
+        &self.request_options.retry_policy
+    }
+
+    /// Gets the rate limits learned from servers.
+    pub fn rate_limits(&self) -> &RateLimits {
+        &self.rate_limits
+    }
 }
 
 /// Creates a wrapped HTTP client. The wrapped
@@ -148,8 +433,8 @@ impl Client {
 /// uses cases creating only one client per process is
 /// necessary.
 pub fn create_client(config: &ClientConfig) -> Result<Client, RdapClientError> {
-    let client = create_reqwest_client(&config.client_config)?;
-    Ok(Client::new(client, config.request_options))
+    let client = create_reqwest_client(&config.reqwest_config())?;
+    Ok(config.wrap(client))
 }
 
 /// Creates a wrapped HTTP client.
@@ -160,8 +445,8 @@ pub fn create_client_with_addr(
     domain: &str,
     addr: SocketAddr,
 ) -> Result<Client, RdapClientError> {
-    let client = create_reqwest_client_with_addr(&config.client_config, domain, addr)?;
-    Ok(Client::new(client, config.request_options))
+    let client = create_reqwest_client_with_addr(&config.reqwest_config(), domain, addr)?;
+    Ok(config.wrap(client))
 }
 
 pub(crate) struct WrappedResponse {
@@ -172,71 +457,95 @@ pub(crate) struct WrappedResponse {
 pub(crate) async fn wrapped_request(
     request_uri: &str,
     client: &Client,
-) -> Result<WrappedResponse, ReqwestError> {
+) -> Result<WrappedResponse, WrappedError> {
+    wrapped_conditional_request(request_uri, client, None).await
+}
+
+/// Does a request that, if given the HTTP data of an earlier response, is conditional
+/// on the resource having changed since, as told by the validators of the data.
+///
+/// If the resource has not changed, the HTTP status code of the response is 304 and
+/// the text is empty.
+#[tracing::instrument(
+    level = "info",
+    name = "http_request",
+    skip(client, validators),
+    fields(status)
+)]
+pub(crate) async fn wrapped_conditional_request(
+    request_uri: &str,
+    client: &Client,
+    validators: Option<&HttpData>,
+) -> Result<WrappedResponse, WrappedError> {
+    // honor what the server has asked of earlier requests
+    #[cfg(not(target_arch = "wasm32"))]
+    let host = reqwest::Url::parse(request_uri)
+        .ok()
+        .and_then(|url| url.host_str().map(str::to_string));
+    #[cfg(not(target_arch = "wasm32"))]
+    if let Some(wait_time_seconds) = host
+        .as_deref()
+        .and_then(|host| client.rate_limits.wait_for(host))
+    {
+        let wait_time_seconds =
+            wait_time_seconds.min(client.request_options.retry_policy.max_retry_secs as u64);
+        info!("Waiting {wait_time_seconds} seconds for the rate limit of the server.");
+        tokio::time::sleep(tokio::time::Duration::from_secs(wait_time_seconds)).await;
+    }
+
     // send request and loop for possible retries
+    let mut redirects = vec![];
     #[allow(unused_mut)] //because of wasm32 exclusion below
-    let mut response = client.reqwest_client.get(request_uri).send().await?;
+    let mut response = send(request_uri, client, validators, &mut redirects).await?;
+
+    #[allow(unused_mut)]
+    let mut retries: u16 = 0;
 
     // this doesn't work on wasm32 because tokio doesn't work on wasm
     #[cfg(not(target_arch = "wasm32"))]
     {
-        let mut tries: u16 = 0;
+        let policy = client.request_options.retry_policy;
         loop {
             debug!("HTTP version: {:?}", response.version());
-            // don't repeat the request
-            if !matches!(response.status(), StatusCode::TOO_MANY_REQUESTS) {
+            // only repeat the request if HTTP 429 or 503
+            if !RetryPolicy::is_retry_status(response.status().as_u16()) {
+                break;
+            }
+            if retries >= policy.max_retries {
+                info!("Max query retries reached.");
                 break;
             }
-            // loop if HTTP 429
-            let retry_after_header = response
+            let retry_after = response
                 .headers()
                 .get(RETRY_AFTER)
-                .map(|value| value.to_str().unwrap().to_string());
-            let retry_after = if let Some(rt) = retry_after_header {
-                info!("Server says too many requests and to retry-after '{rt}'.");
-                rt
-            } else {
-                info!("Server says too many requests but does not offer 'retry-after' value.");
-                client.request_options.def_retry_secs.to_string()
-            };
-            let mut wait_time_seconds = if let Ok(date) = DateTime::parse_from_rfc2822(&retry_after)
-            {
-                (date.with_timezone(&Utc) - Utc::now()).num_seconds() as u64
-            } else if let Ok(seconds) = retry_after.parse::<u64>() {
-                seconds
-            } else {
-                info!(
-                    "Unable to parse retry-after header value. Using {}",
-                    client.request_options.def_retry_secs
-                );
-                client.request_options.def_retry_secs.into()
-            };
-            if wait_time_seconds == 0 {
-                info!("Given {wait_time_seconds} for retry-after. Does not make sense.");
-                wait_time_seconds = client.request_options.def_retry_secs as u64;
+                .and_then(|value| value.to_str().ok());
+            match retry_after {
+                Some(retry_after) => info!(
+                    "Server responded with {} and to retry-after '{retry_after}'.",
+                    response.status()
+                ),
+                None => info!(
+                    "Server responded with {} but does not offer 'retry-after' value.",
+                    response.status()
+                ),
             }
-            if wait_time_seconds > client.request_options.max_retry_secs as u64 {
-                info!(
-                    "Server is asking to wait longer than configured max of {}.",
-                    client.request_options.max_retry_secs
-                );
-                wait_time_seconds = client.request_options.max_retry_secs as u64;
+            let wait_time_seconds =
+                policy.wait_secs(retries, retry_after, Utc::now(), rand::random::<f64>());
+            if response.status() == StatusCode::TOO_MANY_REQUESTS {
+                if let Some(host) = &host {
+                    client.rate_limits.hold_off(host, wait_time_seconds);
+                }
             }
             info!("Waiting {wait_time_seconds} seconds to retry.");
             tokio::time::sleep(tokio::time::Duration::from_secs(wait_time_seconds + 1)).await;
-            tries += 1;
-            if tries > client.request_options.max_retries {
-                info!("Max query retries reached.");
-                break;
-            } else {
-                // send the query again
-                response = client.reqwest_client.get(request_uri).send().await?;
-            }
+            retries += 1;
+            // send the query again
+            response = send(request_uri, client, validators, &mut redirects).await?;
         }
     }
 
     // throw an error if not 200 OK
-    let response = response.error_for_status()?;
+    let status_error = response.error_for_status_ref().err();
 
     // get the response
     let content_type = response
@@ -267,16 +576,32 @@ pub(crate) async fn wrapped_request(
         .headers()
         .get(RETRY_AFTER)
         .map(|value| value.to_str().unwrap().to_string());
+    let etag = response
+        .headers()
+        .get(ETAG)
+        .and_then(|value| value.to_str().ok())
+        .map(str::to_string);
+    let last_modified = response
+        .headers()
+        .get(LAST_MODIFIED)
+        .and_then(|value| value.to_str().ok())
+        .map(str::to_string);
+    let content_encoding = response
+        .headers()
+        .get(CONTENT_ENCODING)
+        .and_then(|value| value.to_str().ok())
+        .map(str::to_string);
     let content_length = response.content_length();
     let status_code = response.status().as_u16();
+    tracing::Span::current().record("status", status_code);
     let url = response.url().to_owned();
-    let text = response.text().await?;
 
     let http_data = HttpData::now()
         .status_code(status_code)
         .and_location(location)
         .and_content_length(content_length)
         .and_content_type(content_type)
+        .and_content_encoding(content_encoding)
         .scheme(url.scheme())
         .host(
             url.host_str()
@@ -289,7 +614,260 @@ pub(crate) async fn wrapped_request(
         .and_strict_transport_security(strict_transport_security)
         .and_retry_after(retry_after)
         .request_uri(request_uri)
+        .and_etag(etag)
+        .and_last_modified(last_modified)
+        .and_retries((retries > 0).then_some(retries))
+        .redirects(redirects)
         .build();
 
+    if let Some(source) = status_error {
+        return if retries > 0 && RetryPolicy::is_retry_status(status_code) {
+            Err(RetriesExhaustedError {
+                http_data: Box::new(http_data),
+                source,
+            }
+            .into())
+        } else {
+            Err(source.into())
+        };
+    }
+    let text = read_body(
+        response,
+        http_data.content_encoding(),
+        client.request_options.max_response_bytes,
+    )
+    .await?;
+
     Ok(WrappedResponse { http_data, text })
 }
+
+/// Sends a request, following redirects if the client is to and recording each redirect.
+///
+/// Redirects are not followed beyond [MAX_REDIRECTS], or back to a URL already requested,
+/// in which case the response is the last redirect.
+async fn send(
+    request_uri: &str,
+    client: &Client,
+    validators: Option<&HttpData>,
+    redirects: &mut Vec<Redirect>,
+) -> Result<Response, WrappedError> {
+    redirects.clear();
+    let mut response = get(request_uri, client, validators).await?;
+    verify_response_pins(&response, client)?;
+    while client.request_options.follow_redirects && is_redirect(response.status().as_u16()) {
+        let location = response
+            .headers()
+            .get(LOCATION)
+            .and_then(|value| value.to_str().ok())
+            .and_then(|location| response.url().join(location).ok());
+        let Some(location) = location else {
+            break;
+        };
+        let headers = response
+            .headers()
+            .iter()
+            .map(|(name, value)| {
+                (
+                    name.to_string(),
+                    String::from_utf8_lossy(value.as_bytes()).to_string(),
+                )
+            })
+            .collect();
+        let looped = location.as_str() == request_uri
+            || redirects
+                .iter()
+                .any(|redirect| redirect.url == location.as_str());
+        redirects.push(Redirect {
+            url: response.url().to_string(),
+            status_code: response.status().as_u16(),
+            location: Some(location.to_string()),
+            headers,
+        });
+        if looped || redirects.len() >= MAX_REDIRECTS {
+            tracing::warn!("Not following redirect to {location}.");
+            break;
+        }
+        tracing::debug!("Following redirect to {location}.");
+        response = get(location.as_str(), client, validators).await?;
+        verify_response_pins(&response, client)?;
+    }
+    Ok(response)
+}
+
+/// Verifies the certificate of the server of a response against the pins of the client.
+fn verify_response_pins(response: &Response, client: &Client) -> Result<(), SpkiPinMismatchError> {
+    if client.spki_pins.is_empty() {
+        return Ok(());
+    }
+    #[cfg(not(target_arch = "wasm32"))]
+    let certificate = response
+        .extensions()
+        .get::<reqwest::tls::TlsInfo>()
+        .and_then(|tls_info| tls_info.peer_certificate());
+    #[cfg(target_arch = "wasm32")]
+    let certificate = None;
+    verify_spki_pins(&client.spki_pins, response.url().as_str(), certificate)
+}
+
+/// Returns true if the status code is of a redirect to the location header,
+/// which 300 (multiple choices) and 304 (not modified) are not.
+fn is_redirect(status_code: u16) -> bool {
+    matches!(status_code, 301 | 302 | 303 | 307 | 308)
+}
+
+/// Sends a conditional GET, or records or replays it if the client has a cassette.
+async fn get(
+    request_uri: &str,
+    client: &Client,
+    validators: Option<&HttpData>,
+) -> Result<Response, WrappedError> {
+    let request = conditional_get(request_uri, client, validators);
+    #[cfg(feature = "replay")]
+    if let Some(cassette) = &client.cassette {
+        return cassette
+            .execute(request.build()?, &client.reqwest_client)
+            .await;
+    }
+    Ok(request.send().await?)
+}
+
+fn conditional_get(
+    request_uri: &str,
+    client: &Client,
+    validators: Option<&HttpData>,
+) -> RequestBuilder {
+    let mut request = client.reqwest_client.get(request_uri);
+    if let Some(validators) = validators {
+        if let Some(etag) = &validators.etag {
+            request = request.header(IF_NONE_MATCH, etag);
+        }
+        if let Some(last_modified) = &validators.last_modified {
+            request = request.header(IF_MODIFIED_SINCE, last_modified);
+        }
+    }
+    #[cfg(all(feature = "otel", not(target_arch = "wasm32")))]
+    {
+        let mut headers = reqwest::header::HeaderMap::new();
+        crate::otel::inject_trace_context(&mut headers);
+        request = request.headers(headers);
+    }
+    request
+}
+
+#[cfg(test)]
+#[allow(non_snake_case)]
+mod tests {
+    use {
+        chrono::{DateTime, Duration, Utc},
+        rstest::rstest,
+    };
+
+    use super::{ClientConfig, RetryPolicy};
+
+    fn policy(jitter: bool) -> RetryPolicy {
+        RetryPolicy {
+            max_retries: 5,
+            def_retry_secs: 10,
+            max_retry_secs: 60,
+            jitter,
+        }
+    }
+
+    #[rstest]
+    #[case(0, Some("7"), 7)]
+    #[case(3, Some("7"), 7)]
+    #[case(0, Some("600"), 60)]
+    #[case(0, Some("0"), 10)]
+    #[case(0, Some("soon"), 10)]
+    #[case(0, None, 10)]
+    #[case(1, None, 20)]
+    #[case(2, None, 40)]
+    #[case(3, None, 60)]
+    #[case(u16::MAX, None, 60)]
+    fn GIVEN_retries_and_retry_after_WHEN_wait_secs_THEN_retry_after_or_backoff(
+        #[case] retries: u16,
+        #[case] retry_after: Option<&str>,
+        #[case] expected: u64,
+    ) {
+        // GIVEN
+        let policy = policy(false);
+
+        // WHEN
+        let actual = policy.wait_secs(retries, retry_after, Utc::now(), 1.0);
+
+        // THEN
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn GIVEN_retry_after_date_WHEN_wait_secs_THEN_seconds_until_date() {
+        // GIVEN
+        let now = DateTime::parse_from_rfc2822("Wed, 21 Oct 2015 07:28:00 GMT")
+            .unwrap()
+            .with_timezone(&Utc);
+        let retry_after = (now + Duration::seconds(30)).to_rfc2822();
+
+        // WHEN
+        let actual = policy(true).wait_secs(0, Some(&retry_after), now, 1.0);
+
+        // THEN
+        assert_eq!(actual, 30);
+    }
+
+    #[rstest]
+    #[case(0.0, 40)]
+    #[case(0.5, 30)]
+    #[case(1.0, 20)]
+    fn GIVEN_jitter_WHEN_wait_secs_THEN_backoff_shortened_up_to_half(
+        #[case] jitter: f64,
+        #[case] expected: u64,
+    ) {
+        // GIVEN
+        let policy = policy(true);
+
+        // WHEN
+        let actual = policy.wait_secs(2, None, Utc::now(), jitter);
+
+        // THEN
+        assert_eq!(actual, expected);
+    }
+
+    #[rstest]
+    #[case(429, true)]
+    #[case(503, true)]
+    #[case(500, false)]
+    #[case(404, false)]
+    fn GIVEN_status_code_WHEN_is_retry_status_THEN_only_429_and_503(
+        #[case] status_code: u16,
+        #[case] expected: bool,
+    ) {
+        // GIVEN status code
+
+        // WHEN
+        let actual = RetryPolicy::is_retry_status(status_code);
+
+        // THEN
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn GIVEN_retry_policy_and_max_retries_WHEN_build_config_THEN_max_retries_overrides() {
+        // GIVEN
+        let retry_policy = policy(false);
+
+        // WHEN
+        let config = ClientConfig::builder()
+            .retry_policy(retry_policy)
+            .max_retries(2)
+            .build();
+
+        // THEN
+        assert_eq!(
+            config.request_options.retry_policy,
+            RetryPolicy {
+                max_retries: 2,
+                ..retry_policy
+            }
+        );
+    }
+}
@@ -0,0 +1,230 @@
+//! Bogon address space.
+//!
+//! Bogons are addresses that are reserved, special-purpose, or otherwise not
+//! allocated to any network. They are not found in the RDAP bootstrap registries,
+//! so there is no RDAP server to query about them.
+
+use std::{fmt::Display, net::IpAddr, str::FromStr};
+
+use {cidr::IpCidr, icann_rdap_common::httpdata::HttpData};
+
+use crate::{
+    http::{wrapped_request, Client},
+    rdap::QueryType,
+    RdapClientError,
+};
+
+/// The bogon prefixes built into this library, taken from the IANA IPv4 and IPv6
+/// Special-Purpose Address registries.
+const BUILTIN_BOGONS: &[(&str, &str)] = &[
+    ("0.0.0.0/8", "\"This network\" (RFC 791)"),
+    ("10.0.0.0/8", "Private-Use (RFC 1918)"),
+    ("100.64.0.0/10", "Shared Address Space (RFC 6598)"),
+    ("127.0.0.0/8", "Loopback (RFC 1122)"),
+    ("169.254.0.0/16", "Link Local (RFC 3927)"),
+    ("172.16.0.0/12", "Private-Use (RFC 1918)"),
+    ("192.0.0.0/24", "IETF Protocol Assignments (RFC 6890)"),
+    ("192.0.2.0/24", "Documentation (TEST-NET-1) (RFC 5737)"),
+    ("192.168.0.0/16", "Private-Use (RFC 1918)"),
+    ("198.18.0.0/15", "Benchmarking (RFC 2544)"),
+    ("198.51.100.0/24", "Documentation (TEST-NET-2) (RFC 5737)"),
+    ("203.0.113.0/24", "Documentation (TEST-NET-3) (RFC 5737)"),
+    ("224.0.0.0/4", "Multicast (RFC 5771)"),
+    ("240.0.0.0/4", "Reserved (RFC 1112)"),
+    ("::/128", "Unspecified Address (RFC 4291)"),
+    ("::1/128", "Loopback Address (RFC 4291)"),
+    ("::ffff:0:0/96", "IPv4-mapped Address (RFC 4291)"),
+    ("100::/64", "Discard-Only Address Block (RFC 6666)"),
+    ("2001:db8::/32", "Documentation (RFC 3849)"),
+    ("3fff::/20", "Documentation (RFC 9637)"),
+    ("fc00::/7", "Unique-Local (RFC 4193)"),
+    ("fe80::/10", "Link-Local Unicast (RFC 4291)"),
+    ("fec0::/10", "Site-Local Unicast (RFC 3879)"),
+    ("ff00::/8", "Multicast (RFC 4291)"),
+];
+
+/// A prefix of bogon address space.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Bogon {
+    prefix: IpCidr,
+    description: String,
+}
+
+impl Bogon {
+    /// Get the prefix.
+    pub fn prefix(&self) -> &IpCidr {
+        &self.prefix
+    }
+
+    /// Get the description of why the prefix is bogon space.
+    pub fn description(&self) -> &str {
+        &self.description
+    }
+
+    fn contains(&self, first: &IpAddr, last: &IpAddr) -> bool {
+        self.prefix.contains(first) && self.prefix.contains(last)
+    }
+}
+
+impl Display for Bogon {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} {}", self.prefix, self.description)
+    }
+}
+
+/// A table of bogon prefixes.
+///
+/// The default table has the built-in bogon prefixes.
+///
+/// ```rust
+/// use icann_rdap_client::{iana::BogonTable, rdap::QueryType};
+/// use std::str::FromStr;
+///
+/// let bogons = BogonTable::default();
+/// let query = QueryType::from_str("192.168.1.1").unwrap();
+/// assert!(bogons.find(&query).is_some());
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BogonTable {
+    bogons: Vec<Bogon>,
+}
+
+impl Default for BogonTable {
+    fn default() -> Self {
+        Self::builtin()
+    }
+}
+
+impl BogonTable {
+    /// Creates a table of the built-in bogon prefixes.
+    pub fn builtin() -> Self {
+        let bogons = BUILTIN_BOGONS
+            .iter()
+            .map(|(prefix, description)| Bogon {
+                prefix: IpCidr::from_str(prefix).expect("invalid builtin bogon"),
+                description: description.to_string(),
+            })
+            .collect();
+        Self { bogons }
+    }
+
+    /// Adds the prefixes of a list with one prefix per line, such as the
+    /// bogon lists published by Team Cymru.
+    ///
+    /// Blank lines, comments starting with `#`, and lines that are not prefixes
+    /// are ignored. Each prefix is given the description. Returns the number of
+    /// prefixes added.
+    pub fn add_list(&mut self, list: &str, description: &str) -> usize {
+        let before = self.bogons.len();
+        self.bogons.extend(
+            list.lines()
+                .map(|line| line.split('#').next().unwrap_or_default().trim())
+                .filter_map(|line| {
+                    cidr::parsers::parse_cidr_ignore_hostbits::<IpCidr, _>(line, IpAddr::from_str)
+                        .ok()
+                })
+                .map(|prefix| Bogon {
+                    prefix,
+                    description: description.to_string(),
+                }),
+        );
+        self.bogons.len() - before
+    }
+
+    /// Finds the bogon prefix containing the addresses of an IP query.
+    ///
+    /// Returns None if the query is not an IP address or CIDR query, or
+    /// is not bogon space.
+    pub fn find(&self, query_type: &QueryType) -> Option<&Bogon> {
+        let (first, last) = match query_type {
+            QueryType::IpV4Addr(addr) => (IpAddr::V4(*addr), IpAddr::V4(*addr)),
+            QueryType::IpV6Addr(addr) => (IpAddr::V6(*addr), IpAddr::V6(*addr)),
+            QueryType::IpV4Cidr(cidr) => (
+                IpAddr::V4(cidr.first_address()),
+                IpAddr::V4(cidr.last_address()),
+            ),
+            QueryType::IpV6Cidr(cidr) => (
+                IpAddr::V6(cidr.first_address()),
+                IpAddr::V6(cidr.last_address()),
+            ),
+            _ => return None,
+        };
+        self.bogons.iter().find(|b| b.contains(&first, &last))
+    }
+}
+
+/// Response from getting a bogon list.
+#[derive(Debug, Clone)]
+pub struct BogonListResponse {
+    pub list: String,
+    pub http_data: HttpData,
+}
+
+/// Issues the HTTP request to get a bogon list, such as those published by Team Cymru.
+///
+/// The list is added to a [BogonTable] with [BogonTable::add_list].
+pub async fn bogon_list_request(
+    url: &str,
+    client: &Client,
+) -> Result<BogonListResponse, RdapClientError> {
+    let wrapped_response = wrapped_request(url, client).await?;
+    Ok(BogonListResponse {
+        list: wrapped_response.text,
+        http_data: wrapped_response.http_data,
+    })
+}
+
+#[cfg(test)]
+#[allow(non_snake_case)]
+mod tests {
+    use std::str::FromStr;
+
+    use rstest::rstest;
+
+    use crate::rdap::QueryType;
+
+    use super::BogonTable;
+
+    #[rstest]
+    #[case("10.1.1.1", Some("10.0.0.0/8"))]
+    #[case("192.168.0.0/24", Some("192.168.0.0/16"))]
+    #[case("2001:db8::1", Some("2001:db8::/32"))]
+    #[case("fe80::/64", Some("fe80::/10"))]
+    #[case("199.43.0.1", None)]
+    #[case("2620:0:2d0::/48", None)]
+    #[case("192.168.0.0/15", None)]
+    #[case("example.com", None)]
+    fn GIVEN_builtin_bogons_WHEN_find_THEN_bogon_found_if_bogon_space(
+        #[case] query: &str,
+        #[case] expected: Option<&str>,
+    ) {
+        // GIVEN
+        let bogons = BogonTable::builtin();
+        let query_type = QueryType::from_str(query).expect("query type");
+
+        // WHEN
+        let actual = bogons.find(&query_type);
+
+        // THEN
+        assert_eq!(
+            actual.map(|b| b.prefix().to_string()),
+            expected.map(|s| s.to_string())
+        );
+    }
+
+    #[test]
+    fn GIVEN_list_WHEN_add_list_THEN_prefixes_added() {
+        // GIVEN
+        let mut bogons = BogonTable::builtin();
+        let list = "# last updated 1700000000\n\n41.62.0.0/16\n2001:4200::/23 # unallocated\nnot a prefix\n";
+
+        // WHEN
+        let added = bogons.add_list(list, "Unallocated");
+
+        // THEN
+        assert_eq!(added, 2);
+        let query_type = QueryType::from_str("41.62.1.1").expect("query type");
+        let bogon = bogons.find(&query_type).expect("bogon not found");
+        assert_eq!(bogon.description(), "Unallocated");
+    }
+}
@@ -2,15 +2,25 @@
 
 use std::sync::{Arc, RwLock};
 
-use icann_rdap_common::{
-    httpdata::HttpData,
-    iana::{
-        get_preferred_url, BootstrapRegistry, BootstrapRegistryError, IanaRegistry,
-        IanaRegistryType,
+use {
+    chrono::{Duration, Utc},
+    cidr::IpCidr,
+    icann_rdap_common::{
+        httpdata::HttpData,
+        iana::{
+            get_preferred_url, BootstrapRegistry, BootstrapRegistryError, IanaRegistry,
+            IanaRegistryType, ParsedBootstrapRegistry,
+        },
     },
+    tracing::{debug, warn},
 };
 
-use crate::{http::Client, iana::iana_request::iana_request, rdap::QueryType, RdapClientError};
+use crate::{
+    http::Client,
+    iana::iana_request::{iana_request, iana_revalidation_request, IanaRevalidation},
+    rdap::{BootstrapTrace, QueryType},
+    RdapClientError,
+};
 
 const SECONDS_IN_WEEK: i64 = 604800;
 
@@ -31,6 +41,38 @@ pub trait BootstrapStore: Send + Sync {
         http_data: HttpData,
     ) -> Result<(), RdapClientError>;
 
+    /// Gets the HTTP data of a registry in the store, whether out-of-date or not.
+    ///
+    /// A registry with HTTP data having validators is revalidated with IANA when
+    /// out-of-date instead of being downloaded again, and, if it cannot be refreshed,
+    /// is used for [BootstrapStore::max_stale] seconds past its expiration.
+    /// The default method returns `Ok(None)`, so registries are always downloaded again.
+    fn get_bootstrap_http_data(
+        &self,
+        _reg_type: &IanaRegistryType,
+    ) -> Result<Option<HttpData>, RdapClientError> {
+        Ok(None)
+    }
+
+    /// Keeps the registry in the store with new HTTP data, after IANA has said the
+    /// registry has not changed.
+    ///
+    /// This is only called for stores implementing [BootstrapStore::get_bootstrap_http_data].
+    fn renew_bootstrap_registry(
+        &self,
+        _reg_type: &IanaRegistryType,
+        _http_data: HttpData,
+    ) -> Result<(), RdapClientError> {
+        Ok(())
+    }
+
+    /// The seconds past its expiration that a registry is used if it cannot be refreshed.
+    ///
+    /// The default method returns 0, so registries are never used past their expiration.
+    fn max_stale(&self) -> i64 {
+        0
+    }
+
     /// Get the urls for a domain or nameserver (which are domain names) query type.
     ///
     /// The default method should be good enough for most trait implementations.
@@ -66,6 +108,7 @@ pub trait BootstrapStore: Send + Sync {
         let ip = match query_type {
             QueryType::IpV4Addr(addr) => format!("{addr}/32"),
             QueryType::IpV4Cidr(cidr) => cidr.to_string(),
+            QueryType::IpRelationSearch(_, IpCidr::V4(cidr)) => cidr.to_string(),
             _ => panic!("non ip query for ip bootstrap"),
         };
         self.get_ipv4_urls(&ip)
@@ -78,6 +121,7 @@ pub trait BootstrapStore: Send + Sync {
         let ip = match query_type {
             QueryType::IpV6Addr(addr) => format!("{addr}/128"),
             QueryType::IpV6Cidr(cidr) => cidr.to_string(),
+            QueryType::IpRelationSearch(_, IpCidr::V6(cidr)) => cidr.to_string(),
             _ => panic!("non ip query for ip bootstrap"),
         };
         self.get_ipv6_urls(&ip)
@@ -90,7 +134,7 @@ pub trait BootstrapStore: Send + Sync {
         &self,
         query_type: &QueryType,
     ) -> Result<Vec<String>, RdapClientError> {
-        let QueryType::Entity(handle) = query_type else {
+        let (QueryType::Entity(handle) | QueryType::TaggedEntity(handle)) = query_type else {
             panic!("non entity handle for bootstrap")
         };
         let handle_split = handle
@@ -154,13 +198,17 @@ impl PreferredUrl for Vec<String> {
 /// no registries in memory. They are added and maintained over time by calls to [MemoryBootstrapStore::put_bootstrap_registry()] by the
 /// machinery of [crate::rdap::request::rdap_bootstrapped_request()] and [crate::iana::bootstrap::qtype_to_bootstrap_url()].
 ///
+/// The registries are kept in their parsed form, [ParsedBootstrapRegistry], so that they are
+/// parsed once when put into the store and not on every lookup.
+///
 /// Ideally, this should be kept in the same scope as [reqwest::Client].
 pub struct MemoryBootstrapStore {
-    ipv4: Arc<RwLock<Option<(IanaRegistry, HttpData)>>>,
-    ipv6: Arc<RwLock<Option<(IanaRegistry, HttpData)>>>,
-    autnum: Arc<RwLock<Option<(IanaRegistry, HttpData)>>>,
-    dns: Arc<RwLock<Option<(IanaRegistry, HttpData)>>>,
-    tag: Arc<RwLock<Option<(IanaRegistry, HttpData)>>>,
+    ipv4: Arc<RwLock<Option<(ParsedBootstrapRegistry, HttpData)>>>,
+    ipv6: Arc<RwLock<Option<(ParsedBootstrapRegistry, HttpData)>>>,
+    autnum: Arc<RwLock<Option<(ParsedBootstrapRegistry, HttpData)>>>,
+    dns: Arc<RwLock<Option<(ParsedBootstrapRegistry, HttpData)>>>,
+    tag: Arc<RwLock<Option<(ParsedBootstrapRegistry, HttpData)>>>,
+    max_stale: i64,
 }
 
 unsafe impl Send for MemoryBootstrapStore {}
@@ -180,6 +228,26 @@ impl MemoryBootstrapStore {
             autnum: <_>::default(),
             dns: <_>::default(),
             tag: <_>::default(),
+            max_stale: 0,
+        }
+    }
+
+    /// Sets the seconds past its expiration that a registry is used if it cannot be refreshed.
+    pub fn with_max_stale(mut self, max_stale: i64) -> Self {
+        self.max_stale = max_stale;
+        self
+    }
+
+    fn registry(
+        &self,
+        reg_type: &IanaRegistryType,
+    ) -> &RwLock<Option<(ParsedBootstrapRegistry, HttpData)>> {
+        match reg_type {
+            IanaRegistryType::RdapBootstrapDns => &self.dns,
+            IanaRegistryType::RdapBootstrapAsn => &self.autnum,
+            IanaRegistryType::RdapBootstrapIpv4 => &self.ipv4,
+            IanaRegistryType::RdapBootstrapIpv6 => &self.ipv6,
+            IanaRegistryType::RdapObjectTags => &self.tag,
         }
     }
 }
@@ -201,6 +269,7 @@ impl BootstrapStore for MemoryBootstrapStore {
         registry: IanaRegistry,
         http_data: HttpData,
     ) -> Result<(), RdapClientError> {
+        let registry = ParsedBootstrapRegistry::parse(reg_type, &registry)?;
         match reg_type {
             IanaRegistryType::RdapBootstrapDns => {
                 let mut g = self.dns.write()?;
@@ -226,6 +295,32 @@ impl BootstrapStore for MemoryBootstrapStore {
         Ok(())
     }
 
+    fn get_bootstrap_http_data(
+        &self,
+        reg_type: &IanaRegistryType,
+    ) -> Result<Option<HttpData>, RdapClientError> {
+        Ok(self
+            .registry(reg_type)
+            .read()?
+            .as_ref()
+            .map(|(_registry, http_data)| http_data.clone()))
+    }
+
+    fn renew_bootstrap_registry(
+        &self,
+        reg_type: &IanaRegistryType,
+        http_data: HttpData,
+    ) -> Result<(), RdapClientError> {
+        if let Some((_registry, held)) = self.registry(reg_type).write()?.as_mut() {
+            *held = http_data;
+        }
+        Ok(())
+    }
+
+    fn max_stale(&self) -> i64 {
+        self.max_stale
+    }
+
     fn get_dns_urls(&self, ldh: &str) -> Result<Vec<String>, RdapClientError> {
         if let Some((iana, _http_data)) = self.dns.read()?.as_ref() {
             Ok(iana.get_dns_bootstrap_urls(ldh)?)
@@ -282,6 +377,16 @@ impl RegistryHasNotExpired for Option<(IanaRegistry, HttpData)> {
     }
 }
 
+impl RegistryHasNotExpired for Option<(ParsedBootstrapRegistry, HttpData)> {
+    fn registry_has_not_expired(&self) -> bool {
+        if let Some((_parsed, http_data)) = self {
+            !http_data.is_expired(SECONDS_IN_WEEK)
+        } else {
+            false
+        }
+    }
+}
+
 /// Given a [QueryType], it will get the bootstrap URL.
 pub async fn qtype_to_bootstrap_url<F>(
     client: &Client,
@@ -292,50 +397,70 @@ pub async fn qtype_to_bootstrap_url<F>(
 where
     F: FnOnce(&IanaRegistryType),
 {
-    match query_type {
-        QueryType::IpV4Addr(_) | QueryType::IpV4Cidr(_) => {
-            fetch_bootstrap(
-                &IanaRegistryType::RdapBootstrapIpv4,
-                client,
-                store,
-                callback,
-            )
-            .await?;
-            Ok(store.get_ipv4_query_urls(query_type)?.preferred_url()?)
-        }
-        QueryType::IpV6Addr(_) | QueryType::IpV6Cidr(_) => {
-            fetch_bootstrap(
-                &IanaRegistryType::RdapBootstrapIpv6,
-                client,
-                store,
-                callback,
-            )
-            .await?;
-            Ok(store.get_ipv6_query_urls(query_type)?.preferred_url()?)
-        }
-        QueryType::AsNumber(_) => {
-            fetch_bootstrap(&IanaRegistryType::RdapBootstrapAsn, client, store, callback).await?;
-            Ok(store.get_autnum_query_urls(query_type)?.preferred_url()?)
-        }
-        QueryType::Domain(_) => {
-            fetch_bootstrap(&IanaRegistryType::RdapBootstrapDns, client, store, callback).await?;
-            Ok(store.get_domain_query_urls(query_type)?.preferred_url()?)
-        }
-        QueryType::Entity(_) => {
-            fetch_bootstrap(&IanaRegistryType::RdapObjectTags, client, store, callback).await?;
-            Ok(store
-                .get_entity_handle_query_urls(query_type)?
-                .preferred_url()?)
-        }
-        QueryType::Nameserver(_) => {
-            fetch_bootstrap(&IanaRegistryType::RdapBootstrapDns, client, store, callback).await?;
-            Ok(store.get_domain_query_urls(query_type)?.preferred_url()?)
-        }
-        _ => Err(RdapClientError::BootstrapUnavailable),
-    }
+    Ok(
+        qtype_to_bootstrap_trace(client, store, query_type, callback)
+            .await?
+            .chosen_url,
+    )
 }
 
-/// Fetches a bootstrap registry for a [BootstrapStore].
+/// Given a [QueryType], it will get the bootstrap URL with a trace of how it was chosen,
+/// which has the registry consulted and the URLs it gives for the query.
+#[tracing::instrument(level = "info", name = "bootstrap", skip_all, fields(query_type = ?query_type))]
+pub async fn qtype_to_bootstrap_trace<F>(
+    client: &Client,
+    store: &dyn BootstrapStore,
+    query_type: &QueryType,
+    callback: F,
+) -> Result<BootstrapTrace, RdapClientError>
+where
+    F: FnOnce(&IanaRegistryType),
+{
+    let started = Utc::now();
+    let registry = match query_type {
+        QueryType::IpV4Addr(_)
+        | QueryType::IpV4Cidr(_)
+        | QueryType::IpRelationSearch(_, IpCidr::V4(_)) => IanaRegistryType::RdapBootstrapIpv4,
+        QueryType::IpV6Addr(_)
+        | QueryType::IpV6Cidr(_)
+        | QueryType::IpRelationSearch(_, IpCidr::V6(_)) => IanaRegistryType::RdapBootstrapIpv6,
+        QueryType::AsNumber(_) => IanaRegistryType::RdapBootstrapAsn,
+        QueryType::Domain(_) | QueryType::Nameserver(_) => IanaRegistryType::RdapBootstrapDns,
+        QueryType::Entity(_) | QueryType::TaggedEntity(_) => IanaRegistryType::RdapObjectTags,
+        _ => return Err(RdapClientError::BootstrapUnavailable),
+    };
+    let mut fetched = false;
+    fetch_bootstrap(&registry, client, store, |reg| {
+        fetched = true;
+        callback(reg)
+    })
+    .await?;
+    let candidate_urls = match registry {
+        IanaRegistryType::RdapBootstrapIpv4 => store.get_ipv4_query_urls(query_type)?,
+        IanaRegistryType::RdapBootstrapIpv6 => store.get_ipv6_query_urls(query_type)?,
+        IanaRegistryType::RdapBootstrapAsn => store.get_autnum_query_urls(query_type)?,
+        IanaRegistryType::RdapBootstrapDns => store.get_domain_query_urls(query_type)?,
+        IanaRegistryType::RdapObjectTags => store.get_entity_handle_query_urls(query_type)?,
+    };
+    let chosen_url = candidate_urls.clone().preferred_url()?;
+    Ok(BootstrapTrace {
+        registry_url: registry.url().to_string(),
+        registry,
+        fetched,
+        candidate_urls,
+        chosen_url,
+        started,
+        duration_ms: (Utc::now() - started).num_milliseconds(),
+    })
+}
+
+/// Fetches a bootstrap registry for a [BootstrapStore], if the store does not have
+/// it or it is out-of-date.
+///
+/// An out-of-date registry with validators is revalidated with IANA, being downloaded
+/// again only if changed, and is used for [BootstrapStore::max_stale] seconds past its
+/// expiration if it cannot be refreshed.
+#[tracing::instrument(level = "info", skip_all, fields(registry = ?reg_type))]
 pub async fn fetch_bootstrap<F>(
     reg_type: &IanaRegistryType,
     client: &Client,
@@ -345,12 +470,55 @@ pub async fn fetch_bootstrap<F>(
 where
     F: FnOnce(&IanaRegistryType),
 {
-    if !store.has_bootstrap_registry(reg_type)? {
-        callback(reg_type);
-        let iana_resp = iana_request(reg_type.clone(), client).await?;
-        store.put_bootstrap_registry(reg_type, iana_resp.registry, iana_resp.http_data)?;
+    if store.has_bootstrap_registry(reg_type)? {
+        return Ok(());
+    }
+    callback(reg_type);
+    let held = store.get_bootstrap_http_data(reg_type)?;
+    let result = match &held {
+        Some(held) if held.has_validators() => {
+            revalidate_bootstrap(reg_type, client, store, held).await
+        }
+        _ => refresh_bootstrap(reg_type, client, store).await,
+    };
+    match (result, held) {
+        (Err(e), Some(held))
+            if Utc::now()
+                < held.expires_at(SECONDS_IN_WEEK) + Duration::seconds(store.max_stale()) =>
+        {
+            warn!("Using out-of-date {reg_type:?} registry, which cannot be refreshed: {e}");
+            Ok(())
+        }
+        (result, _) => result,
+    }
+}
+
+/// Fetches a bootstrap registry for a [BootstrapStore], whether or not the store
+/// has the registry and it is up-to-date.
+pub async fn refresh_bootstrap(
+    reg_type: &IanaRegistryType,
+    client: &Client,
+    store: &dyn BootstrapStore,
+) -> Result<(), RdapClientError> {
+    let iana_resp = iana_request(reg_type.clone(), client).await?;
+    store.put_bootstrap_registry(reg_type, iana_resp.registry, iana_resp.http_data)
+}
+
+async fn revalidate_bootstrap(
+    reg_type: &IanaRegistryType,
+    client: &Client,
+    store: &dyn BootstrapStore,
+    held: &HttpData,
+) -> Result<(), RdapClientError> {
+    match iana_revalidation_request(reg_type.clone(), client, held).await? {
+        IanaRevalidation::NotModified(http_data) => {
+            debug!("{reg_type:?} registry has not changed");
+            store.renew_bootstrap_registry(reg_type, http_data)
+        }
+        IanaRevalidation::Modified(iana_resp) => {
+            store.put_bootstrap_registry(reg_type, iana_resp.registry, iana_resp.http_data)
+        }
     }
-    Ok(())
 }
 
 #[cfg(test)]
@@ -620,4 +788,43 @@ mod test {
         // THEN
         assert_eq!(actual, "https://example.com/rdap/");
     }
+
+    #[test]
+    fn GIVEN_membootstrap_with_expired_registry_WHEN_renewed_THEN_has_registry() {
+        // GIVEN
+        let mem = MemoryBootstrapStore::new();
+        let iana = serde_json::from_str::<IanaRegistry>(
+            r#"{"version": "1.0", "publication": "2024-01-07T10:11:12Z", "services": []}"#,
+        )
+        .expect("cannot parse bootstrap");
+        mem.put_bootstrap_registry(
+            &IanaRegistryType::RdapBootstrapDns,
+            iana,
+            HttpData::example()
+                .cache_control("max-age=0")
+                .etag("\"abc\"")
+                .build(),
+        )
+        .expect("put iana registry");
+        assert!(!mem
+            .has_bootstrap_registry(&IanaRegistryType::RdapBootstrapDns)
+            .expect("has registry"));
+
+        // WHEN
+        mem.renew_bootstrap_registry(
+            &IanaRegistryType::RdapBootstrapDns,
+            HttpData::example().etag("\"abc\"").build(),
+        )
+        .expect("renew registry");
+
+        // THEN
+        assert!(mem
+            .has_bootstrap_registry(&IanaRegistryType::RdapBootstrapDns)
+            .expect("has registry"));
+        let http_data = mem
+            .get_bootstrap_http_data(&IanaRegistryType::RdapBootstrapDns)
+            .expect("get http data")
+            .expect("http data");
+        assert!(http_data.has_validators());
+    }
 }
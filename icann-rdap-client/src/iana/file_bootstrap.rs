@@ -1,11 +1,8 @@
-use std::{
-    fs::{self, File},
-    io::{BufRead, BufReader},
-    path::PathBuf,
-};
+//! A bootstrap registry store backed by files.
+
+use std::path::{Path, PathBuf};
 
 use {
-    icann_rdap_client::iana::{BootstrapStore, RegistryHasNotExpired},
     icann_rdap_common::{
         httpdata::HttpData,
         iana::{BootstrapRegistry, IanaRegistry, IanaRegistryType},
@@ -13,18 +10,70 @@ use {
     tracing::debug,
 };
 
-use super::bootstrap_cache_path;
+use crate::{
+    files::lock::{locked_read_lines, locked_write},
+    RdapClientError,
+};
+
+use super::bootstrap::{BootstrapStore, RegistryHasNotExpired};
+
+/// A bootstrap registry store backed by files in a directory.
+///
+/// Each registry is kept in a file named for the registry, holding the HTTP data of
+/// the registry followed by the registry, so that the registries outlive the process
+/// and may be shared by many processes. The HTTP data is used to revalidate
+/// registries with IANA once they are out-of-date.
+pub struct FileBootstrapStore {
+    dir: PathBuf,
+    max_stale: i64,
+}
+
+impl FileBootstrapStore {
+    /// Creates a store of the files in a directory, which must exist.
+    pub fn new(dir: impl Into<PathBuf>) -> Self {
+        Self {
+            dir: dir.into(),
+            max_stale: 0,
+        }
+    }
+
+    /// Sets the seconds past its expiration that a registry is used if it cannot be refreshed.
+    pub fn with_max_stale(mut self, max_stale: i64) -> Self {
+        self.max_stale = max_stale;
+        self
+    }
+
+    fn path(&self, reg_type: &IanaRegistryType) -> PathBuf {
+        self.dir.join(reg_type.file_name())
+    }
 
-pub struct FileCacheBootstrapStore;
+    fn read(
+        &self,
+        reg_type: &IanaRegistryType,
+    ) -> Result<(IanaRegistry, HttpData), RdapClientError> {
+        Ok(fetch_file_cache_bootstrap(&self.path(reg_type), |s| {
+            debug!("Reading {s}")
+        })?)
+    }
 
-impl BootstrapStore for FileCacheBootstrapStore {
-    fn has_bootstrap_registry(
+    fn write(
         &self,
         reg_type: &IanaRegistryType,
-    ) -> Result<bool, icann_rdap_client::RdapClientError> {
-        let path = bootstrap_cache_path().join(reg_type.file_name());
+        registry: &IanaRegistry,
+        http_data: &HttpData,
+    ) -> Result<(), RdapClientError> {
+        let data = serde_json::to_string_pretty(registry)?;
+        let cache_contents = http_data.to_lines(&data)?;
+        locked_write(&self.path(reg_type), cache_contents)?;
+        Ok(())
+    }
+}
+
+impl BootstrapStore for FileBootstrapStore {
+    fn has_bootstrap_registry(&self, reg_type: &IanaRegistryType) -> Result<bool, RdapClientError> {
+        let path = self.path(reg_type);
         if path.exists() {
-            let fc_reg = fetch_file_cache_bootstrap(path, |s| debug!("Checking for {s}"))?;
+            let fc_reg = fetch_file_cache_bootstrap(&path, |s| debug!("Checking for {s}"))?;
             return Ok(Some(fc_reg).registry_has_not_expired());
         }
         Ok(false)
@@ -35,58 +84,69 @@ impl BootstrapStore for FileCacheBootstrapStore {
         reg_type: &IanaRegistryType,
         registry: IanaRegistry,
         http_data: HttpData,
-    ) -> Result<(), icann_rdap_client::RdapClientError> {
-        let path = bootstrap_cache_path().join(reg_type.file_name());
-        let data = serde_json::to_string_pretty(&registry)?;
-        let cache_contents = http_data.to_lines(&data)?;
-        fs::write(path, cache_contents)?;
-        Ok(())
+    ) -> Result<(), RdapClientError> {
+        self.write(reg_type, &registry, &http_data)
+    }
+
+    fn get_bootstrap_http_data(
+        &self,
+        reg_type: &IanaRegistryType,
+    ) -> Result<Option<HttpData>, RdapClientError> {
+        if !self.path(reg_type).exists() {
+            return Ok(None);
+        }
+        // a registry that cannot be read is downloaded again.
+        Ok(self.read(reg_type).ok().map(|(_iana, http_data)| http_data))
+    }
+
+    fn renew_bootstrap_registry(
+        &self,
+        reg_type: &IanaRegistryType,
+        http_data: HttpData,
+    ) -> Result<(), RdapClientError> {
+        let (iana, _http_data) = self.read(reg_type)?;
+        self.write(reg_type, &iana, &http_data)
+    }
+
+    fn max_stale(&self) -> i64 {
+        self.max_stale
     }
 
-    fn get_dns_urls(&self, ldh: &str) -> Result<Vec<String>, icann_rdap_client::RdapClientError> {
-        let path = bootstrap_cache_path().join(IanaRegistryType::RdapBootstrapDns.file_name());
-        let (iana, _http_data) = fetch_file_cache_bootstrap(path, |s| debug!("Reading {s}"))?;
+    fn get_dns_urls(&self, ldh: &str) -> Result<Vec<String>, RdapClientError> {
+        let (iana, _http_data) = self.read(&IanaRegistryType::RdapBootstrapDns)?;
         Ok(iana.get_dns_bootstrap_urls(ldh)?)
     }
 
-    fn get_asn_urls(&self, asn: &str) -> Result<Vec<String>, icann_rdap_client::RdapClientError> {
-        let path = bootstrap_cache_path().join(IanaRegistryType::RdapBootstrapAsn.file_name());
-        let (iana, _http_data) = fetch_file_cache_bootstrap(path, |s| debug!("Reading {s}"))?;
+    fn get_asn_urls(&self, asn: &str) -> Result<Vec<String>, RdapClientError> {
+        let (iana, _http_data) = self.read(&IanaRegistryType::RdapBootstrapAsn)?;
         Ok(iana.get_asn_bootstrap_urls(asn)?)
     }
 
-    fn get_ipv4_urls(&self, ipv4: &str) -> Result<Vec<String>, icann_rdap_client::RdapClientError> {
-        let path = bootstrap_cache_path().join(IanaRegistryType::RdapBootstrapIpv4.file_name());
-        let (iana, _http_data) = fetch_file_cache_bootstrap(path, |s| debug!("Reading {s}"))?;
+    fn get_ipv4_urls(&self, ipv4: &str) -> Result<Vec<String>, RdapClientError> {
+        let (iana, _http_data) = self.read(&IanaRegistryType::RdapBootstrapIpv4)?;
         Ok(iana.get_ipv4_bootstrap_urls(ipv4)?)
     }
 
-    fn get_ipv6_urls(&self, ipv6: &str) -> Result<Vec<String>, icann_rdap_client::RdapClientError> {
-        let path = bootstrap_cache_path().join(IanaRegistryType::RdapBootstrapIpv6.file_name());
-        let (iana, _http_data) = fetch_file_cache_bootstrap(path, |s| debug!("Reading {s}"))?;
+    fn get_ipv6_urls(&self, ipv6: &str) -> Result<Vec<String>, RdapClientError> {
+        let (iana, _http_data) = self.read(&IanaRegistryType::RdapBootstrapIpv6)?;
         Ok(iana.get_ipv6_bootstrap_urls(ipv6)?)
     }
 
-    fn get_tag_urls(&self, tag: &str) -> Result<Vec<String>, icann_rdap_client::RdapClientError> {
-        let path = bootstrap_cache_path().join(IanaRegistryType::RdapObjectTags.file_name());
-        let (iana, _http_data) = fetch_file_cache_bootstrap(path, |s| debug!("Reading {s}"))?;
+    fn get_tag_urls(&self, tag: &str) -> Result<Vec<String>, RdapClientError> {
+        let (iana, _http_data) = self.read(&IanaRegistryType::RdapObjectTags)?;
         Ok(iana.get_tag_bootstrap_urls(tag)?)
     }
 }
 
+/// Reads a registry and its HTTP data from a file.
 pub fn fetch_file_cache_bootstrap<F>(
-    path: PathBuf,
+    path: &Path,
     callback: F,
 ) -> Result<(IanaRegistry, HttpData), std::io::Error>
 where
     F: FnOnce(String),
 {
-    let input = File::open(&path)?;
-    let buf = BufReader::new(input);
-    let mut lines = vec![];
-    for line in buf.lines() {
-        lines.push(line?);
-    }
+    let lines = locked_read_lines(path)?;
     let cache_data = HttpData::from_lines(&lines)?;
     callback(path.display().to_string());
     let iana: IanaRegistry = serde_json::from_str(&cache_data.1.join(""))?;
@@ -96,37 +156,31 @@ where
 #[cfg(test)]
 #[allow(non_snake_case)]
 mod test {
-    use {
-        icann_rdap_client::{
-            iana::{BootstrapStore, PreferredUrl},
-            rdap::QueryType,
-        },
-        icann_rdap_common::{
-            httpdata::HttpData,
-            iana::{IanaRegistry, IanaRegistryType},
-        },
-        serial_test::serial,
-        test_dir::{DirBuilder, FileType, TestDir},
+    use std::{fs, process};
+
+    use icann_rdap_common::{
+        httpdata::HttpData,
+        iana::{IanaRegistry, IanaRegistryType},
+    };
+
+    use crate::{
+        iana::{BootstrapStore, PreferredUrl},
+        rdap::QueryType,
     };
 
-    use crate::dirs::{self, fcbs::FileCacheBootstrapStore};
+    use super::FileBootstrapStore;
 
-    fn test_dir() -> TestDir {
-        let test_dir = TestDir::temp()
-            .create("cache", FileType::Dir)
-            .create("config", FileType::Dir);
-        std::env::set_var("XDG_CACHE_HOME", test_dir.path("cache"));
-        std::env::set_var("XDG_CONFIG_HOME", test_dir.path("config"));
-        dirs::init().expect("unable to init directories");
-        test_dir
+    fn store(name: &str) -> FileBootstrapStore {
+        let dir = std::env::temp_dir().join(format!("icann-rdap-{name}-{}", process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).expect("creating bootstrap dir");
+        FileBootstrapStore::new(dir)
     }
 
     #[test]
-    #[serial]
-    fn GIVEN_fcbootstrap_with_dns_WHEN_get_domain_query_url_THEN_correct_url() {
+    fn GIVEN_filebootstrap_with_dns_WHEN_get_domain_query_url_THEN_correct_url() {
         // GIVEN
-        let _test_dir = test_dir();
-        let bs = FileCacheBootstrapStore;
+        let bs = store("fbs-dns");
         let bootstrap = r#"
             {
                 "version": "1.0",
@@ -169,11 +223,9 @@ mod test {
     }
 
     #[test]
-    #[serial]
-    fn GIVEN_fcbootstrap_with_autnum_WHEN_get_autnum_query_url_THEN_correct_url() {
+    fn GIVEN_filebootstrap_with_autnum_WHEN_get_autnum_query_url_THEN_correct_url() {
         // GIVEN
-        let _test_dir = test_dir();
-        let bs = FileCacheBootstrapStore;
+        let bs = store("fbs-autnum");
         let bootstrap = r#"
             {
                 "version": "1.0",
@@ -223,11 +275,9 @@ mod test {
     }
 
     #[test]
-    #[serial]
-    fn GIVEN_fcbootstrap_with_ipv4_THEN_get_ipv4_query_urls_THEN_correct_url() {
+    fn GIVEN_filebootstrap_with_ipv4_THEN_get_ipv4_query_urls_THEN_correct_url() {
         // GIVEN
-        let _test_dir = test_dir();
-        let bs = FileCacheBootstrapStore;
+        let bs = store("fbs-ipv4");
         let bootstrap = r#"
             {
                 "version": "1.0",
@@ -277,11 +327,9 @@ mod test {
     }
 
     #[test]
-    #[serial]
-    fn GIVEN_fcbootstrap_with_ipv6_THEN_get_ipv6_query_urls_THEN_correct_url() {
+    fn GIVEN_filebootstrap_with_ipv6_THEN_get_ipv6_query_urls_THEN_correct_url() {
         // GIVEN
-        let _test_dir = test_dir();
-        let bs = FileCacheBootstrapStore;
+        let bs = store("fbs-ipv6");
         let bootstrap = r#"
             {
                 "version": "1.0",
@@ -331,11 +379,9 @@ mod test {
     }
 
     #[test]
-    #[serial]
-    fn GIVEN_fcbootstrap_with_tag_THEN_get_entity_handle_query_urls_THEN_correct_url() {
+    fn GIVEN_filebootstrap_with_tag_THEN_get_entity_handle_query_urls_THEN_correct_url() {
         // GIVEN
-        let _test_dir = test_dir();
-        let bs = FileCacheBootstrapStore;
+        let bs = store("fbs-tag");
         let bootstrap = r#"
             {
               "version": "1.0",
@@ -9,7 +9,10 @@ use {
     thiserror::Error,
 };
 
-use crate::http::{wrapped_request, Client};
+use crate::http::{
+    wrapped_conditional_request, wrapped_request, Client, ResponseTooLargeError,
+    RetriesExhaustedError, SpkiPinMismatchError, WrappedError,
+};
 
 /// Response from getting an IANA registry.
 #[derive(Debug, Serialize, Deserialize, Clone)]
@@ -19,13 +22,43 @@ pub struct IanaResponse {
     pub http_data: HttpData,
 }
 
+/// Response from revalidating an IANA registry.
+#[derive(Debug, Clone)]
+pub enum IanaRevalidation {
+    /// The registry has not changed, and is good for as long as the HTTP data says.
+    NotModified(HttpData),
+
+    /// The registry has changed.
+    Modified(IanaResponse),
+}
+
 /// Errors from issuing a request to get an IANA registry.
 #[derive(Debug, Error)]
 pub enum IanaResponseError {
     #[error(transparent)]
     Reqwest(#[from] reqwest::Error),
     #[error(transparent)]
+    RetriesExhausted(#[from] RetriesExhaustedError),
+    #[error(transparent)]
+    SpkiPinMismatch(#[from] SpkiPinMismatchError),
+    #[error(transparent)]
+    ResponseTooLarge(#[from] ResponseTooLargeError),
+    #[error(transparent)]
     SerdeJson(#[from] serde_json::Error),
+    #[error(transparent)]
+    Io(#[from] std::io::Error),
+}
+
+impl From<WrappedError> for IanaResponseError {
+    fn from(e: WrappedError) -> Self {
+        match e {
+            WrappedError::Reqwest(e) => Self::Reqwest(e),
+            WrappedError::RetriesExhausted(e) => Self::RetriesExhausted(e),
+            WrappedError::SpkiPinMismatch(e) => Self::SpkiPinMismatch(e),
+            WrappedError::ResponseTooLarge(e) => Self::ResponseTooLarge(e),
+            WrappedError::Io(e) => Self::Io(e),
+        }
+    }
 }
 
 /// Issues the HTTP request to get an IANA registry.
@@ -46,3 +79,39 @@ pub async fn iana_request(
         http_data,
     })
 }
+
+/// Issues the HTTP request to revalidate an IANA registry, given the HTTP data of
+/// the registry held.
+///
+/// The request is conditional on the registry having changed since, as told by the
+/// `ETag` and `Last-Modified` headers of the HTTP data, so the registry is only
+/// downloaded again if it has changed.
+pub async fn iana_revalidation_request(
+    registry_type: IanaRegistryType,
+    client: &Client,
+    http_data: &HttpData,
+) -> Result<IanaRevalidation, IanaResponseError> {
+    let url = registry_type.url();
+
+    let wrapped_response = wrapped_conditional_request(url, client, Some(http_data)).await?;
+    if wrapped_response.http_data.status_code == 304 {
+        // servers need not repeat the validators in a 304.
+        let mut revalidated = wrapped_response.http_data;
+        if revalidated.etag.is_none() {
+            revalidated.etag.clone_from(&http_data.etag);
+        }
+        if revalidated.last_modified.is_none() {
+            revalidated
+                .last_modified
+                .clone_from(&http_data.last_modified);
+        }
+        return Ok(IanaRevalidation::NotModified(revalidated));
+    }
+
+    let json: RdapBootstrapRegistry = serde_json::from_str(&wrapped_response.text)?;
+    Ok(IanaRevalidation::Modified(IanaResponse {
+        registry: IanaRegistry::RdapBootstrapRegistry(json),
+        registry_type,
+        http_data: wrapped_response.http_data,
+    }))
+}
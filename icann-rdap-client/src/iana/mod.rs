@@ -1,9 +1,17 @@
 //! IANA and RDAP Bootstrapping
 
+#[doc(inline)]
+pub use bogons::*;
 #[doc(inline)]
 pub use bootstrap::*;
+#[cfg(not(target_arch = "wasm32"))]
+#[doc(inline)]
+pub use file_bootstrap::*;
 #[doc(inline)]
 pub use iana_request::*;
 
+pub(crate) mod bogons;
 pub(crate) mod bootstrap;
+#[cfg(not(target_arch = "wasm32"))]
+pub(crate) mod file_bootstrap;
 pub(crate) mod iana_request;
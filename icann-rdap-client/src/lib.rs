@@ -1,41 +1,86 @@
 #![allow(dead_code)] // TODO remove this at some point
 #![allow(rustdoc::bare_urls)]
 #![doc = include_str!("../README.md")]
-use std::{fmt::Display, sync::PoisonError};
+use std::{
+    fmt::Display,
+    sync::{Arc, PoisonError},
+};
 
 use {
+    http::{ResponseTooLargeError, RetriesExhaustedError, SpkiPinMismatchError},
     iana::iana_request::IanaResponseError,
     icann_rdap_common::{
         dns_types::DomainNameError, httpdata::HttpData, iana::BootstrapRegistryError,
         response::RdapResponseError,
     },
+    reqwest::StatusCode,
+    strum_macros::Display,
     thiserror::Error,
 };
 
+#[cfg(not(target_arch = "wasm32"))]
+pub mod files;
 pub mod gtld;
 pub mod http;
 pub mod iana;
 pub mod md;
+#[cfg(all(feature = "otel", not(target_arch = "wasm32")))]
+pub mod otel;
 pub mod rdap;
+pub mod whois;
 
 /// Basics necesasry for a simple clients.
+///
+/// The items documented here are the stable API of this crate, centered on
+/// [rdap::RdapClient], which changes only with a major release. The lower level
+/// request functions are also here for existing callers, but they may change
+/// between minor releases.
 pub mod prelude {
-    #[doc(inline)]
-    pub use crate::http::create_client;
     #[doc(inline)]
     pub use crate::http::ClientConfig;
     #[doc(inline)]
+    pub use crate::http::RetryPolicy;
+    #[doc(inline)]
     pub use crate::iana::MemoryBootstrapStore;
+    #[cfg(not(target_arch = "wasm32"))]
     #[doc(inline)]
-    pub use crate::rdap::rdap_bootstrapped_request;
+    pub use crate::rdap::BatchPolicy;
+    #[cfg(not(target_arch = "wasm32"))]
     #[doc(inline)]
-    pub use crate::rdap::rdap_request;
+    pub use crate::rdap::FileResponseCache;
     #[doc(inline)]
-    pub use crate::rdap::rdap_url_request;
+    pub use crate::rdap::Lookup;
+    #[doc(inline)]
+    pub use crate::rdap::MemoryResponseCache;
     #[doc(inline)]
     pub use crate::rdap::QueryType;
     #[doc(inline)]
+    pub use crate::rdap::RdapClient;
+    #[doc(inline)]
     pub use crate::RdapClientError;
+    #[doc(inline)]
+    pub use icann_rdap_common::check::Checks;
+    #[doc(inline)]
+    pub use icann_rdap_common::contact::Contact;
+    #[doc(inline)]
+    pub use icann_rdap_common::response::RdapResponse;
+
+    #[doc(hidden)]
+    pub use crate::http::create_client;
+    #[doc(hidden)]
+    pub use crate::rdap::rdap_bootstrapped_request;
+    #[doc(hidden)]
+    pub use crate::rdap::rdap_cached_request;
+    #[doc(hidden)]
+    pub use crate::rdap::rdap_request;
+    #[doc(hidden)]
+    pub use crate::rdap::rdap_traced_bootstrapped_request;
+    #[doc(hidden)]
+    pub use crate::rdap::rdap_url_paged_request;
+    #[doc(hidden)]
+    pub use crate::rdap::rdap_url_request;
+    #[doc(hidden)]
+    pub use crate::rdap::QueryTrace;
 }
 
 /// Error returned by RDAP client functions and methods.
@@ -53,12 +98,33 @@ pub enum RdapClientError {
     #[error(transparent)]
     Client(#[from] reqwest::Error),
 
+    /// The server still refused the request after it was retried.
+    #[error(transparent)]
+    RetriesExhausted(#[from] RetriesExhaustedError),
+
+    /// The public key of the server does not match the pins for it.
+    #[error(transparent)]
+    SpkiPinMismatch(#[from] SpkiPinMismatchError),
+
+    /// The response is larger than the client allows once decompressed.
+    #[error(transparent)]
+    ResponseTooLarge(#[from] ResponseTooLargeError),
+
     #[error("Error parsing response")]
     ParsingError(Box<ParsingErrorInfo>),
 
     #[error(transparent)]
     Json(#[from] serde_json::Error),
 
+    #[error("Expected {expected} but the response is {actual}")]
+    UnexpectedResponse { expected: String, actual: String },
+
+    #[error("Response media type '{}' is not application/rdap+json", .media_type.as_deref().unwrap_or("none"))]
+    InvalidMediaType {
+        media_type: Option<String>,
+        url: Option<String>,
+    },
+
     #[error("RwLock Poison Error")]
     Poison,
 
@@ -76,6 +142,132 @@ pub enum RdapClientError {
 
     #[error(transparent)]
     DomainNameError(#[from] DomainNameError),
+
+    /// The error of a request made once for several concurrent callers.
+    #[error(transparent)]
+    SharedRequest(Arc<RdapClientError>),
+}
+
+impl RdapClientError {
+    /// A stable numeric code for the error.
+    ///
+    /// These codes do not change between releases and are used
+    /// as the exit codes of the command line programs.
+    pub fn code(&self) -> u8 {
+        match self {
+            // I/O Errors
+            Self::Client(_) | Self::RetriesExhausted(_) => 42,
+            Self::IoError(_) => 43,
+            Self::SpkiPinMismatch(_) => 45,
+
+            // RDAP Server Errors
+            Self::Response(_) => 60,
+            Self::InvalidMediaType { .. } => 61,
+            Self::ParsingError(_) => 62,
+            Self::Json(_) => 63,
+            Self::UnexpectedResponse { .. } => 64,
+            Self::ResponseTooLarge(_) => 65,
+
+            // Bootstrap Errors
+            Self::BootstrapUnavailable => 70,
+            Self::BootstrapError(_) => 71,
+            Self::IanaResponse(_) => 72,
+
+            // User Errors
+            Self::InvalidQueryValue => 202,
+            Self::AmbiquousQueryType => 203,
+            Self::DomainNameError(_) => 204,
+
+            // Internal Errors
+            Self::Poison => 250,
+
+            Self::SharedRequest(e) => e.code(),
+        }
+    }
+
+    /// The phase of the RDAP request in which the error occurred.
+    pub fn phase(&self) -> ErrorPhase {
+        match self {
+            Self::InvalidQueryValue | Self::AmbiquousQueryType | Self::DomainNameError(_) => {
+                ErrorPhase::Query
+            }
+            Self::BootstrapUnavailable | Self::BootstrapError(_) | Self::IanaResponse(_) => {
+                ErrorPhase::Bootstrap
+            }
+            Self::Client(_)
+            | Self::RetriesExhausted(_)
+            | Self::SpkiPinMismatch(_)
+            | Self::IoError(_) => ErrorPhase::Request,
+            Self::Response(_)
+            | Self::InvalidMediaType { .. }
+            | Self::ParsingError(_)
+            | Self::Json(_)
+            | Self::UnexpectedResponse { .. }
+            | Self::ResponseTooLarge(_) => ErrorPhase::Response,
+            Self::Poison => ErrorPhase::Internal,
+            Self::SharedRequest(e) => e.phase(),
+        }
+    }
+
+    /// Returns true if the same request may succeed if tried again later.
+    ///
+    /// Timeouts, connection failures, HTTP 429 and HTTP 5xx responses are retryable.
+    pub fn is_retryable(&self) -> bool {
+        match self {
+            Self::Client(e) => {
+                e.is_timeout()
+                    || e.is_connect()
+                    || e.status().is_some_and(|status| {
+                        status == StatusCode::TOO_MANY_REQUESTS || status.is_server_error()
+                    })
+            }
+            Self::RetriesExhausted(_) => true,
+            Self::IanaResponse(IanaResponseError::Reqwest(e)) => e.is_timeout() || e.is_connect(),
+            Self::IanaResponse(IanaResponseError::RetriesExhausted(_)) => true,
+            Self::SharedRequest(e) => e.is_retryable(),
+            _ => false,
+        }
+    }
+
+    /// The URL of the request that caused the error, if known.
+    pub fn url(&self) -> Option<&str> {
+        match self {
+            Self::Client(e) => e.url().map(|url| url.as_str()),
+            Self::IanaResponse(IanaResponseError::Reqwest(e)) => e.url().map(|url| url.as_str()),
+            Self::RetriesExhausted(e) => e.http_data.request_uri.as_deref(),
+            Self::IanaResponse(IanaResponseError::RetriesExhausted(e)) => {
+                e.http_data.request_uri.as_deref()
+            }
+            Self::ParsingError(e) => e.http_data.request_uri.as_deref(),
+            Self::SpkiPinMismatch(e) => Some(&e.url),
+            Self::IanaResponse(IanaResponseError::SpkiPinMismatch(e)) => Some(&e.url),
+            Self::ResponseTooLarge(e) => Some(&e.url),
+            Self::IanaResponse(IanaResponseError::ResponseTooLarge(e)) => Some(&e.url),
+            Self::InvalidMediaType { url, .. } => url.as_deref(),
+            Self::SharedRequest(e) => e.url(),
+            _ => None,
+        }
+    }
+}
+
+/// The phase of an RDAP request in which an error occurred.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Display)]
+#[strum(serialize_all = "snake_case")]
+pub enum ErrorPhase {
+    /// Determining the query from the input.
+    Query,
+
+    /// Finding the RDAP server with the IANA bootstrap registries.
+    Bootstrap,
+
+    /// Sending the request and receiving the response.
+    Request,
+
+    /// Parsing the response.
+    Response,
+
+    /// An error internal to the client.
+    Internal,
 }
 
 impl<T> From<PoisonError<T>> for RdapClientError {
@@ -110,3 +302,47 @@ impl Display for ParsingErrorInfo {
         )
     }
 }
+
+#[cfg(test)]
+#[allow(non_snake_case)]
+mod tests {
+    use icann_rdap_common::httpdata::HttpData;
+
+    use super::{ErrorPhase, ParsingErrorInfo, RdapClientError};
+
+    #[test]
+    fn GIVEN_invalid_query_WHEN_classified_THEN_query_phase_and_not_retryable() {
+        // GIVEN
+        let error = RdapClientError::InvalidQueryValue;
+
+        // WHEN
+        let (code, phase, retryable) = (error.code(), error.phase(), error.is_retryable());
+
+        // THEN
+        assert_eq!(code, 202);
+        assert_eq!(phase, ErrorPhase::Query);
+        assert!(!retryable);
+        assert!(error.url().is_none());
+    }
+
+    #[test]
+    fn GIVEN_parsing_error_WHEN_url_THEN_request_uri() {
+        // GIVEN
+        let error = RdapClientError::ParsingError(Box::new(ParsingErrorInfo {
+            text: "not json".to_string(),
+            http_data: HttpData::example()
+                .request_uri("https://rdap.example/domain/foo.example")
+                .build(),
+            error: serde_json::from_str::<serde_json::Value>("not json").unwrap_err(),
+        }));
+
+        // WHEN
+        let url = error.url();
+
+        // THEN
+        assert_eq!(url, Some("https://rdap.example/domain/foo.example"));
+        assert_eq!(error.code(), 62);
+        assert_eq!(error.phase(), ErrorPhase::Response);
+        assert_eq!(error.phase().to_string(), "response");
+    }
+}
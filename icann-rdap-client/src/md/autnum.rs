@@ -2,14 +2,14 @@ use std::any::TypeId;
 
 use icann_rdap_common::{
     check::{CheckParams, GetChecks, GetSubChecks},
-    response::Autnum,
+    response::{Autnum, Numberish},
 };
 
 use super::{
     string::StringUtil,
     table::{MultiPartTable, ToMpTable},
     types::checks_to_table,
-    FromMd, MdHeaderText, MdParams, MdUtil, ToMd, HR,
+    FromMd, MdHeaderText, MdOptions, MdParams, MdUtil, ToMd, HR,
 };
 
 impl ToMd for Autnum {
@@ -36,11 +36,17 @@ impl ToMd for Autnum {
             .header_ref(&"Identifiers")
             .and_nv_ref(
                 &"Start AS Number",
-                &self.start_autnum.as_ref().map(|n| n.to_string()),
+                &self
+                    .start_autnum
+                    .as_ref()
+                    .map(|n| asn_text(n, params.options)),
             )
             .and_nv_ref(
                 &"End AS Number",
-                &self.end_autnum.as_ref().map(|n| n.to_string()),
+                &self
+                    .end_autnum
+                    .as_ref()
+                    .map(|n| asn_text(n, params.options)),
             )
             .and_nv_ref(&"Handle", &self.object_common.handle)
             .and_nv_ref(&"Autnum Type", &self.autnum_type)
@@ -83,6 +89,12 @@ impl ToMd for Autnum {
     }
 }
 
+/// Gets the text of an AS number in the notation of the options, or as given if not a 32-bit number.
+fn asn_text(asn: &Numberish<u32>, options: &MdOptions) -> String {
+    asn.as_u32()
+        .map_or_else(|| asn.to_string(), |n| options.asn_format.format(n))
+}
+
 impl MdUtil for Autnum {
     fn get_header_text(&self) -> MdHeaderText {
         let header_text = if self.start_autnum.is_some() && self.end_autnum.is_some() {
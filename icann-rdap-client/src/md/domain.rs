@@ -2,7 +2,7 @@ use std::any::TypeId;
 
 use icann_rdap_common::{
     dns_types::{DnsAlgorithmType, DnsDigestType},
-    response::{Domain, SecureDns, Variant},
+    response::{Domain, SecureDns, Variant, VariantName},
 };
 
 use icann_rdap_common::check::{CheckParams, GetChecks, GetSubChecks};
@@ -11,7 +11,7 @@ use crate::rdap::registered_redactions::{self, text_or_registered_redaction};
 
 use super::{
     redacted::REDACTED_TEXT,
-    string::{StringListUtil, StringUtil},
+    string::StringUtil,
     table::{MultiPartTable, ToMpTable},
     types::{checks_to_table, events_to_table, links_to_table, public_ids_to_table},
     FromMd, MdHeaderText, MdParams, MdUtil, ToMd, HR,
@@ -114,33 +114,45 @@ impl ToMd for Domain {
 
 fn do_variants(variants: &[Variant], params: MdParams) -> String {
     let mut md = String::new();
-    md.push_str(&format!(
-        "|:-:|\n|{}|\n",
-        "Domain Variants".to_right_bold(8, params.options)
-    ));
-    md.push_str("|:-:|:-:|:-:|\n|Relations|IDN Table|Variant Names|\n");
-    variants.iter().for_each(|v| {
-        md.push_str(&format!(
-            "|{}|{}|{}|",
-            v.relations().make_title_case_list(),
-            v.idn_table.as_deref().unwrap_or_default(),
-            v.variant_names
-                .as_deref()
-                .unwrap_or_default()
-                .iter()
-                .map(|dv| format!(
-                    "ldh: '{}' utf:'{}'",
-                    dv.ldh_name.as_deref().unwrap_or_default(),
-                    dv.unicode_name.as_deref().unwrap_or_default()
-                ))
-                .collect::<Vec<String>>()
-                .join(", "),
-        ))
-    });
-    md.push('\n');
+    // multipart data
+    let mut table = MultiPartTable::new();
+
+    for (i, variant) in variants.iter().enumerate() {
+        let header = format!("Domain Variant ({i})").replace_md_chars();
+        let relations = variant
+            .relations()
+            .iter()
+            .map(|r| r.to_owned().to_title_case())
+            .collect::<Vec<String>>();
+        let names = variant
+            .variant_names()
+            .iter()
+            .map(variant_name_text)
+            .collect::<Vec<String>>();
+        table = table
+            .header_ref(&header)
+            .nv_ul(&"Relations", relations)
+            .and_nv_ref(&"IDN Table", &variant.idn_table)
+            .nv_ul(&"Variant Names", names);
+    }
+
+    // render table
+    md.push_str(&table.to_md(params));
     md
 }
 
+/// Gets the text of a variant name, showing the Unicode form if different from the LDH form.
+fn variant_name_text(variant_name: &VariantName) -> String {
+    match (variant_name.ldh_name(), variant_name.unicode_name()) {
+        (Some(ldh), Some(unicode)) if !ldh.eq_ignore_ascii_case(unicode) => {
+            format!("{ldh} ({unicode})")
+        }
+        (Some(ldh), _) => ldh.to_string(),
+        (None, Some(unicode)) => unicode.to_string(),
+        (None, None) => String::default(),
+    }
+}
+
 fn do_secure_dns(secure_dns: &SecureDns, params: MdParams) -> String {
     let mut md = String::new();
     // multipart data
@@ -182,7 +194,7 @@ fn do_secure_dns(secure_dns: &SecureDns, params: MdParams) -> String {
             }
             if let Some(links) = &ds.links {
                 let ds_header = format!("DS ({i}) Links");
-                table = links_to_table(links, table, &ds_header);
+                table = links_to_table(links, table, &ds_header, params);
             }
         }
     }
@@ -205,7 +217,7 @@ fn do_secure_dns(secure_dns: &SecureDns, params: MdParams) -> String {
             }
             if let Some(links) = &key.links {
                 let key_header = format!("Key ({i}) Links");
-                table = links_to_table(links, table, &key_header);
+                table = links_to_table(links, table, &key_header, params);
             }
         }
     }
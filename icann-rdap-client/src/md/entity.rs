@@ -74,7 +74,7 @@ impl ToMd for Entity {
             table = public_ids_to_table(public_ids, table);
         }
 
-        if let Some(contact) = self.contact() {
+        if let Some(contact) = self.contact().cloned() {
             // nutty RFC 9537 redaction stuff
 
             // check if registrant or tech name are redacted
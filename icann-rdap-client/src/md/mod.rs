@@ -3,12 +3,16 @@
 use {
     crate::rdap::rr::RequestData,
     buildstructor::Builder,
-    icann_rdap_common::{check::CheckParams, httpdata::HttpData, response::RdapResponse},
+    icann_rdap_common::{
+        check::{CheckParams, ProfileId},
+        httpdata::HttpData,
+        response::RdapResponse,
+    },
     std::{any::TypeId, char},
     strum::EnumMessage,
 };
 
-use icann_rdap_common::check::{CheckClass, Checks, CHECK_CLASS_LEN};
+use icann_rdap_common::check::{CheckClass, CheckItem, CheckPolicy, Checks, CHECK_CLASS_LEN};
 
 use self::string::StringUtil;
 
@@ -42,6 +46,78 @@ pub struct MdOptions {
 
     /// If true, the text_style_char will appear in a justified text.
     pub style_in_justify: bool,
+
+    /// The notation used for autonomous system numbers.
+    pub asn_format: AsnFormat,
+
+    /// The policy applied to checks before they are shown.
+    pub check_policy: CheckPolicy,
+
+    /// The style of tables.
+    pub table_style: MdTableStyle,
+
+    /// How links are rendered.
+    pub link_style: MdLinkStyle,
+
+    /// If true, the notices of servers are not rendered.
+    pub hide_notices: bool,
+
+    /// The profiles to check against, or those declared by the response if none are given.
+    pub profiles: Option<Vec<ProfileId>>,
+}
+
+/// The styles of tables.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum MdTableStyle {
+    /// Tables for rendering in a terminal, with rows of headers between the rows of data.
+    #[default]
+    Terminal,
+
+    /// GitHub-flavored markdown tables, one for each header, which render in wikis and documents.
+    Gfm,
+
+    /// Aligned text without table markup.
+    PlainText,
+}
+
+/// How links are rendered.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum MdLinkStyle {
+    /// The URL of a link as text.
+    #[default]
+    Plain,
+
+    /// Markdown inline links, such as `[title](https://example.com)`.
+    Inline,
+
+    /// Links are not rendered.
+    Omit,
+}
+
+/// The notations for autonomous system numbers, as described in RFC 5396.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum AsnFormat {
+    /// A single decimal number, such as "65546".
+    #[default]
+    AsPlain,
+
+    /// Numbers greater than 65535 as two dot separated decimal numbers, such as "1.10".
+    AsDot,
+
+    /// All numbers as two dot separated decimal numbers, such as "0.100".
+    AsDotPlus,
+}
+
+impl AsnFormat {
+    /// Formats an autonomous system number in this notation.
+    pub fn format(&self, asn: u32) -> String {
+        let (high, low) = (asn >> 16, asn & 0xFFFF);
+        match self {
+            Self::AsPlain => asn.to_string(),
+            Self::AsDot if high == 0 => asn.to_string(),
+            Self::AsDot | Self::AsDotPlus => format!("{high}.{low}"),
+        }
+    }
 }
 
 impl Default for MdOptions {
@@ -51,6 +127,12 @@ impl Default for MdOptions {
             text_style_char: '*',
             hash_headers: true,
             style_in_justify: false,
+            asn_format: AsnFormat::AsPlain,
+            check_policy: CheckPolicy::default(),
+            table_style: MdTableStyle::Terminal,
+            link_style: MdLinkStyle::Plain,
+            hide_notices: false,
+            profiles: None,
         }
     }
 }
@@ -63,6 +145,12 @@ impl MdOptions {
             text_style_char: '_',
             hash_headers: false,
             style_in_justify: true,
+            asn_format: AsnFormat::AsPlain,
+            check_policy: CheckPolicy::default(),
+            table_style: MdTableStyle::Terminal,
+            link_style: MdLinkStyle::Plain,
+            hide_notices: false,
+            profiles: None,
         }
     }
 }
@@ -78,7 +166,7 @@ pub struct MdParams<'a> {
     pub req_data: &'a RequestData<'a>,
 }
 
-impl MdParams<'_> {
+impl<'a> MdParams<'a> {
     pub fn from_parent(&self, parent_type: TypeId) -> Self {
         Self {
             parent_type,
@@ -91,6 +179,14 @@ impl MdParams<'_> {
         }
     }
 
+    /// Gets the profiles to check against.
+    pub fn profiles(&self) -> &'a [ProfileId] {
+        self.options
+            .profiles
+            .as_deref()
+            .unwrap_or(ProfileId::declared_by(self.root))
+    }
+
     pub fn next_level(&self) -> Self {
         Self {
             heading_level: self.heading_level + 1,
@@ -116,6 +212,8 @@ impl ToMd for RdapResponse {
             Self::DomainSearchResults(results) => results.to_md(params),
             Self::EntitySearchResults(results) => results.to_md(params),
             Self::NameserverSearchResults(results) => results.to_md(params),
+            Self::IpSearchResults(results) => results.to_md(params),
+            Self::AutnumSearchResults(results) => results.to_md(params),
             Self::ErrorResponse(error) => error.to_md(params),
             Self::Help(help) => help.to_md(params),
         };
@@ -152,17 +250,32 @@ impl MdUtil for RdapResponse {
             Self::DomainSearchResults(results) => results.get_header_text(),
             Self::EntitySearchResults(results) => results.get_header_text(),
             Self::NameserverSearchResults(results) => results.get_header_text(),
+            Self::IpSearchResults(results) => results.get_header_text(),
+            Self::AutnumSearchResults(results) => results.get_header_text(),
             Self::ErrorResponse(error) => error.get_header_text(),
             Self::Help(help) => help.get_header_text(),
         }
     }
 }
 
+/// Gets the message of a check item, followed by the specification from which it
+/// comes unless the message already names it.
+pub(crate) fn check_message(item: &CheckItem) -> String {
+    let message = item.check.get_message().unwrap_or_default();
+    match &item.specification {
+        Some(specification) if !message.contains(specification.as_str()) => {
+            format!("{message} ({specification})")
+        }
+        _ => message.to_string(),
+    }
+}
+
 pub(crate) fn checks_ul(checks: &Checks, params: MdParams) -> String {
     let mut md = String::new();
     checks
         .items
         .iter()
+        .filter_map(|item| params.options.check_policy.apply_item(item))
         .filter(|item| params.check_types.contains(&item.check_class))
         .for_each(|item| {
             md.push_str(&format!(
@@ -171,9 +284,7 @@ pub(crate) fn checks_ul(checks: &Checks, params: MdParams) -> String {
                     .check_class
                     .to_string()
                     .to_right_em(*CHECK_CLASS_LEN, params.options),
-                item.check
-                    .get_message()
-                    .expect("Check has no message. Coding error.")
+                check_message(&item)
             ))
         });
     md
@@ -191,6 +302,7 @@ impl<'a> FromMd<'a> for CheckParams<'a> {
             root: md_params.root,
             parent_type,
             allow_unreg_ext: false,
+            profiles: md_params.profiles(),
         }
     }
 
@@ -200,6 +312,7 @@ impl<'a> FromMd<'a> for CheckParams<'a> {
             root: md_params.root,
             parent_type: md_params.parent_type,
             allow_unreg_ext: false,
+            profiles: md_params.profiles(),
         }
     }
 }
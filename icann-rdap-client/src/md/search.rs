@@ -1,7 +1,8 @@
 use std::any::TypeId;
 
 use icann_rdap_common::response::{
-    DomainSearchResults, EntitySearchResults, NameserverSearchResults,
+    AutnumSearchResults, DomainSearchResults, EntitySearchResults, IpSearchResults,
+    NameserverSearchResults,
 };
 
 use super::{MdHeaderText, MdParams, MdUtil, ToMd};
@@ -57,6 +58,40 @@ impl ToMd for EntitySearchResults {
     }
 }
 
+impl ToMd for IpSearchResults {
+    fn to_md(&self, params: MdParams) -> String {
+        let typeid = TypeId::of::<Self>();
+        let mut md = String::new();
+        md.push_str(&self.common.to_md(params.from_parent(typeid)));
+        self.results.iter().for_each(|result| {
+            md.push_str(&result.to_md(MdParams {
+                heading_level: params.heading_level + 1,
+                parent_type: typeid,
+                ..params
+            }))
+        });
+        md.push('\n');
+        md
+    }
+}
+
+impl ToMd for AutnumSearchResults {
+    fn to_md(&self, params: MdParams) -> String {
+        let typeid = TypeId::of::<Self>();
+        let mut md = String::new();
+        md.push_str(&self.common.to_md(params.from_parent(typeid)));
+        self.results.iter().for_each(|result| {
+            md.push_str(&result.to_md(MdParams {
+                heading_level: params.heading_level + 1,
+                parent_type: typeid,
+                ..params
+            }))
+        });
+        md.push('\n');
+        md
+    }
+}
+
 impl MdUtil for DomainSearchResults {
     fn get_header_text(&self) -> MdHeaderText {
         MdHeaderText::builder()
@@ -80,3 +115,19 @@ impl MdUtil for NameserverSearchResults {
             .build()
     }
 }
+
+impl MdUtil for IpSearchResults {
+    fn get_header_text(&self) -> MdHeaderText {
+        MdHeaderText::builder()
+            .header_text("IP Network Search Results")
+            .build()
+    }
+}
+
+impl MdUtil for AutnumSearchResults {
+    fn get_header_text(&self) -> MdHeaderText {
+        MdHeaderText::builder()
+            .header_text("Autnum Search Results")
+            .build()
+    }
+}
@@ -63,7 +63,8 @@ impl<T: ToString> StringUtil for T {
     fn to_header(self, level: usize, options: &MdOptions) -> String {
         let s = self.to_string();
         if options.hash_headers {
-            format!("{} {s}\n\n", "#".repeat(level))
+            // markdown has no headings deeper than 6.
+            format!("{} {s}\n\n", "#".repeat(level.min(6)))
         } else {
             let line = if level == 1 {
                 "=".repeat(s.len())
@@ -1,6 +1,6 @@
 use std::cmp::max;
 
-use super::{string::StringUtil, MdHeaderText, MdOptions, MdParams, ToMd};
+use super::{string::StringUtil, MdHeaderText, MdOptions, MdParams, MdTableStyle, ToMd};
 
 pub(crate) trait ToMpTable {
     fn add_to_mptable(&self, table: MultiPartTable, params: MdParams) -> MultiPartTable;
@@ -85,6 +85,19 @@ impl MultiPartTable {
         self
     }
 
+    /// Add a name/value row with unordered list without processing whitespace or markdown characters.
+    pub fn nv_ul_raw(mut self, name: &impl ToString, value: Vec<String>) -> Self {
+        value.into_iter().enumerate().for_each(|(i, v)| {
+            let name = if i == 0 {
+                name.to_string()
+            } else {
+                String::default()
+            };
+            self.rows.push(Row::NameValue((name, format!("* {v}"))));
+        });
+        self
+    }
+
     /// Add a name/value row with unordered list.
     pub fn nv_ul(mut self, name: &impl ToString, value: Vec<impl ToString>) -> Self {
         value.iter().enumerate().for_each(|(i, v)| {
@@ -200,6 +213,86 @@ impl MultiPartTable {
     }
 
     pub fn to_md_table(&self, options: &MdOptions) -> String {
+        match options.table_style {
+            MdTableStyle::Terminal => self.to_terminal_table(options),
+            MdTableStyle::Gfm => self.to_gfm_tables(options),
+            MdTableStyle::PlainText => self.to_plain_text(),
+        }
+    }
+
+    /// Renders GitHub-flavored markdown tables, which must begin with a header row,
+    /// so each header starts a new table.
+    fn to_gfm_tables(&self, options: &MdOptions) -> String {
+        let mut md = String::new();
+        let mut in_table = false;
+        for row in &self.rows {
+            match row {
+                Row::Header(name) => {
+                    if in_table {
+                        md.push('\n');
+                    }
+                    md.push_str(&format!("| {} | |\n|-:|:-|\n", name.to_bold(options)));
+                    in_table = true;
+                }
+                Row::NameValue((name, value)) => {
+                    if !in_table {
+                        md.push_str("| | |\n|-:|:-|\n");
+                        in_table = true;
+                    }
+                    md.push_str(&format!("| {name} | {value} |\n"));
+                }
+                Row::MultiValue(values) => {
+                    if in_table {
+                        md.push('\n');
+                    }
+                    md.push_str(&format!("| {} |\n", values.join(" | ")));
+                    md.push_str(&format!("|{}\n", ":-:|".repeat(values.len())));
+                    in_table = true;
+                }
+            }
+        }
+        md.push('\n');
+        md
+    }
+
+    /// Renders the rows as aligned text, without markdown.
+    fn to_plain_text(&self) -> String {
+        let name_width = self
+            .rows
+            .iter()
+            .map(|row| match row {
+                Row::NameValue((name, _)) => name.chars().count(),
+                _ => 0,
+            })
+            .max()
+            .unwrap_or_default();
+        let mut text = String::new();
+        for row in &self.rows {
+            match row {
+                Row::Header(name) => {
+                    if !text.is_empty() {
+                        text.push('\n');
+                    }
+                    text.push_str(&format!("{}\n", unescape(name)));
+                }
+                Row::NameValue((name, value)) => {
+                    let separator = if name.is_empty() { " " } else { ":" };
+                    text.push_str(
+                        format!("  {name:>name_width$}{separator} {}", unescape(value)).trim_end(),
+                    );
+                    text.push('\n');
+                }
+                Row::MultiValue(values) => {
+                    let values = values.iter().map(|v| unescape(v)).collect::<Vec<_>>();
+                    text.push_str(&format!("  {}\n", values.join("  ")));
+                }
+            }
+        }
+        text.push('\n');
+        text
+    }
+
+    fn to_terminal_table(&self, options: &MdOptions) -> String {
         let mut md = String::new();
 
         let col_type_width = max(
@@ -264,6 +357,14 @@ impl MultiPartTable {
     }
 }
 
+/// Removes the escapes of the characters that would otherwise be markdown.
+fn unescape(s: &str) -> String {
+    s.replace("\\*", "*")
+        .replace("\\_", "_")
+        .replace("\\|", "|")
+        .replace("\\#", "#")
+}
+
 impl ToMd for MultiPartTable {
     fn to_md(&self, params: super::MdParams) -> String {
         self.to_md_table(params.options)
@@ -276,7 +377,7 @@ mod tests {
     use icann_rdap_common::{httpdata::HttpData, prelude::ToResponse, response::Rfc9083Error};
 
     use crate::{
-        md::ToMd,
+        md::{MdOptions, MdTableStyle, ToMd},
         rdap::rr::{RequestData, SourceType},
     };
 
@@ -474,4 +575,53 @@ mod tests {
             "|:-:|\n|__foo__|\n|-:|:-|\n|bizz|buzz|\n| bar|baz|\n|:-:|\n|__foo__|\n|-:|:-|\n|bizz|buzz|\n| bar|baz|\n|\n\n"
         )
     }
+
+    #[test]
+    fn GIVEN_headers_and_data_WHEN_to_md_table_gfm_THEN_table_for_each_header() {
+        // GIVEN
+        let table = MultiPartTable::new()
+            .header_ref(&"foo")
+            .nv_ref(&"bizz", &"buzz")
+            .nv_ul(&"bar", vec!["baz", "qux"])
+            .header_ref(&"fizz")
+            .nv_ref(&"a", &"c_d");
+        let options = MdOptions {
+            table_style: MdTableStyle::Gfm,
+            ..MdOptions::default()
+        };
+
+        // WHEN
+        let actual = table.to_md_table(&options);
+
+        // THEN
+        assert_eq!(
+            actual,
+            "| **foo** | |\n|-:|:-|\n| bizz | buzz |\n| bar | * baz |\n|  | * qux |\n\n\
+             | **fizz** | |\n|-:|:-|\n| a | c\\_d |\n\n"
+        )
+    }
+
+    #[test]
+    fn GIVEN_headers_and_data_WHEN_to_md_table_plain_text_THEN_aligned_text() {
+        // GIVEN
+        let table = MultiPartTable::new()
+            .header_ref(&"foo")
+            .nv_ref(&"bizz", &"buzz")
+            .nv_ul(&"bar", vec!["baz", "qux"])
+            .header_ref(&"fizz")
+            .nv_ref(&"a", &"c_d");
+        let options = MdOptions {
+            table_style: MdTableStyle::PlainText,
+            ..MdOptions::default()
+        };
+
+        // WHEN
+        let actual = table.to_md_table(&options);
+
+        // THEN
+        assert_eq!(
+            actual,
+            "foo\n  bizz: buzz\n   bar: * baz\n        * qux\n\nfizz\n     a: c_d\n\n"
+        )
+    }
 }
@@ -13,10 +13,9 @@ use {
         },
     },
     reqwest::header::{
-        ACCESS_CONTROL_ALLOW_ORIGIN, CACHE_CONTROL, CONTENT_LENGTH, EXPIRES, HOST,
-        STRICT_TRANSPORT_SECURITY,
+        ACCESS_CONTROL_ALLOW_ORIGIN, CACHE_CONTROL, CONTENT_ENCODING, CONTENT_LENGTH, EXPIRES,
+        HOST, STRICT_TRANSPORT_SECURITY,
     },
-    strum::EnumMessage,
 };
 
 use icann_rdap_common::check::{
@@ -24,10 +23,10 @@ use icann_rdap_common::check::{
 };
 
 use super::{
-    checks_ul,
+    check_message, checks_ul,
     string::{StringListUtil, StringUtil},
     table::{MultiPartTable, ToMpTable},
-    FromMd, MdParams, ToMd, HR,
+    FromMd, MdLinkStyle, MdParams, ToMd, HR,
 };
 
 impl ToMd for RdapConformance {
@@ -51,14 +50,13 @@ impl ToMd for RdapConformance {
         self.get_checks(CheckParams::from_md_no_parent(params))
             .items
             .iter()
+            .filter_map(|item| params.options.check_policy.apply_item(item))
             .filter(|item| params.check_types.contains(&item.check_class))
             .for_each(|item| {
                 md.push_str(&format!(
                     "* {}: {}\n",
                     item.check_class.to_string().to_em(params.options),
-                    item.check
-                        .get_message()
-                        .expect("Check has no message. Coding error.")
+                    check_message(&item)
                 ))
             });
         md.push('\n');
@@ -78,16 +76,20 @@ impl ToMd for Links {
 impl ToMd for Link {
     fn to_md(&self, params: MdParams) -> String {
         let mut md = String::new();
+        if params.options.link_style == MdLinkStyle::Omit {
+            return md;
+        }
         if let Some(title) = &self.title {
             md.push_str(&format!("* {}:\n", title.replace_md_chars()));
         } else {
             md.push_str("* Link:\n")
         };
         if let Some(href) = &self.href {
-            md.push_str(&format!(
-                "* {}\n",
-                href.to_owned().to_inline(params.options)
-            ));
+            let href = match params.options.link_style {
+                MdLinkStyle::Inline => inline_link(href),
+                _ => href.to_owned().to_inline(params.options),
+            };
+            md.push_str(&format!("* {href}\n"));
         };
         if let Some(rel) = &self.rel {
             md.push_str(&format!("* Relation:  {}\n", rel.replace_md_chars()));
@@ -168,14 +170,13 @@ impl ToMd for NoticeOrRemark {
         self.get_checks(CheckParams::from_md(params, TypeId::of::<Self>()))
             .items
             .iter()
+            .filter_map(|item| params.options.check_policy.apply_item(item))
             .filter(|item| params.check_types.contains(&item.check_class))
             .for_each(|item| {
                 md.push_str(&format!(
                     "* {}: {}\n",
                     &item.check_class.to_string().to_em(params.options),
-                    item.check
-                        .get_message()
-                        .expect("Check has no message. Coding error.")
+                    check_message(&item)
                 ))
             });
         if let Some(links) = &self.links {
@@ -191,7 +192,11 @@ impl ToMd for NoticeOrRemark {
 impl ToMd for Common {
     fn to_md(&self, params: MdParams) -> String {
         let mut md = String::new();
-        let not_empty = self.rdap_conformance.is_some() || self.notices.is_some();
+        let notices = self
+            .notices
+            .as_ref()
+            .filter(|_| !params.options.hide_notices);
+        let not_empty = self.rdap_conformance.is_some() || notices.is_some();
         if not_empty {
             md.push('\n');
             md.push_str(HR);
@@ -205,7 +210,7 @@ impl ToMd for Common {
         if let Some(rdap_conformance) = &self.rdap_conformance {
             md.push_str(&rdap_conformance.to_md(params));
         };
-        if let Some(notices) = &self.notices {
+        if let Some(notices) = notices {
             md.push_str(&"Server Notices".to_header(5, params.options));
             md.push_str(&notices.to_md(params));
         }
@@ -218,6 +223,8 @@ impl ToMd for Common {
 
 const RECEIVED: &str = "Received";
 const REQUEST_URI: &str = "Request URI";
+const RETRIES: &str = "Retries";
+const REDIRECT: &str = "Redirect";
 
 pub static NAMES: LazyLock<[String; 7]> = LazyLock::new(|| {
     [
@@ -250,6 +257,12 @@ impl ToMd for HttpData {
                 CONTENT_LENGTH, content_length
             ));
         }
+        if let Some(content_encoding) = &self.content_encoding {
+            md.push_str(&format!(
+                " * {:<NAME_LEN$}: {}\n",
+                CONTENT_ENCODING, content_encoding
+            ));
+        }
         if let Some(expires) = &self.expires {
             md.push_str(&format!(" * {:<NAME_LEN$}: {}\n", EXPIRES, expires));
         }
@@ -271,18 +284,28 @@ impl ToMd for HttpData {
                 ACCESS_CONTROL_ALLOW_ORIGIN, access_control_allow_origin
             ));
         }
+        if let Some(retries) = &self.retries {
+            md.push_str(&format!(" * {RETRIES:<NAME_LEN$}: {retries}\n"));
+        }
+        for redirect in &self.redirects {
+            md.push_str(&format!(
+                " * {REDIRECT:<NAME_LEN$}: {} {} -> {}\n",
+                redirect.status_code,
+                redirect.url,
+                redirect.location.as_deref().unwrap_or("?")
+            ));
+        }
         md.push_str(&format!(" * {RECEIVED:<NAME_LEN$}: {}\n", &self.received));
         self.get_checks(CheckParams::from_md(params, TypeId::of::<NoticeOrRemark>()))
             .items
             .iter()
+            .filter_map(|item| params.options.check_policy.apply_item(item))
             .filter(|item| params.check_types.contains(&item.check_class))
             .for_each(|item| {
                 md.push_str(&format!(
                     "* {}: {}\n",
                     &item.check_class.to_string().to_em(params.options),
-                    item.check
-                        .get_message()
-                        .expect("Check has no message. Coding error.")
+                    check_message(&item)
                 ))
             });
         md
@@ -311,7 +334,7 @@ impl ToMpTable for ObjectCommon {
 
         // Links
         if let Some(links) = &self.links {
-            table = links_to_table(links, table, "Links");
+            table = links_to_table(links, table, "Links", params);
         }
 
         // TODO Checks
@@ -368,11 +391,29 @@ pub(crate) fn events_to_table(
     table
 }
 
+/// Renders a markdown inline link to a URL.
+pub(crate) fn inline_link(href: &str) -> String {
+    let text = href
+        .replace_md_chars()
+        .replace('[', "\\[")
+        .replace(']', "\\]");
+    let destination = href
+        .replace(' ', "%20")
+        .replace('(', "%28")
+        .replace(')', "%29")
+        .replace('|', "%7C");
+    format!("[{text}]({destination})")
+}
+
 pub(crate) fn links_to_table(
     links: &[Link],
     mut table: MultiPartTable,
     header_name: &str,
+    params: MdParams,
 ) -> MultiPartTable {
+    if params.options.link_style == MdLinkStyle::Omit {
+        return table;
+    }
     table = table.header_ref(&header_name.replace_md_chars());
     for link in links {
         if let Some(title) = &link.title {
@@ -383,28 +424,30 @@ pub(crate) fn links_to_table(
             .as_ref()
             .unwrap_or(&"Link".to_string())
             .to_title_case();
-        let mut ul: Vec<&String> = vec![];
+        let mut ul: Vec<String> = vec![];
         if let Some(href) = &link.href {
-            ul.push(href)
+            ul.push(match params.options.link_style {
+                MdLinkStyle::Inline => inline_link(href),
+                _ => href.replace_md_chars(),
+            })
         }
         if let Some(media_type) = &link.media_type {
-            ul.push(media_type)
+            ul.push(media_type.replace_md_chars())
         };
         if let Some(media) = &link.media {
-            ul.push(media)
+            ul.push(media.replace_md_chars())
         };
         if let Some(value) = &link.value {
-            ul.push(value)
+            ul.push(value.replace_md_chars())
         };
-        let hreflang_s;
         if let Some(hreflang) = &link.hreflang {
-            hreflang_s = match hreflang {
+            let hreflang = match hreflang {
                 icann_rdap_common::response::HrefLang::Lang(lang) => lang.to_owned(),
                 icann_rdap_common::response::HrefLang::Langs(langs) => langs.join(", "),
             };
-            ul.push(&hreflang_s)
+            ul.push(hreflang.replace_md_chars())
         };
-        table = table.nv_ul_ref(&rel, ul);
+        table = table.nv_ul_raw(&rel, ul);
     }
     table
 }
@@ -417,6 +460,7 @@ pub(crate) fn checks_to_table(
     let mut filtered_checks: Vec<CheckItem> = checks
         .into_iter()
         .flat_map(|checks| checks.items)
+        .filter_map(|item| params.options.check_policy.apply_item(&item))
         .filter(|item| params.check_types.contains(&item.check_class))
         .collect();
 
@@ -430,7 +474,7 @@ pub(crate) fn checks_to_table(
         let ul: Vec<String> = filtered_checks
             .iter()
             .filter(|item| item.check_class == class)
-            .map(|item| item.check.get_message().unwrap_or_default().to_owned())
+            .map(check_message)
             .collect();
         table = table.nv_ul_ref(
             &&class
@@ -444,7 +488,7 @@ pub(crate) fn checks_to_table(
         let ul: Vec<String> = filtered_checks
             .iter()
             .filter(|item| item.check_class == class)
-            .map(|item| item.check.get_message().unwrap_or_default().to_owned())
+            .map(check_message)
             .collect();
         table = table.nv_ul_ref(
             &class
@@ -458,7 +502,7 @@ pub(crate) fn checks_to_table(
         let ul: Vec<String> = filtered_checks
             .iter()
             .filter(|item| item.check_class == class)
-            .map(|item| item.check.get_message().unwrap_or_default().to_owned())
+            .map(check_message)
             .collect();
         table = table.nv_ul_ref(
             &&class
@@ -470,3 +514,124 @@ pub(crate) fn checks_to_table(
 
     table
 }
+
+#[cfg(test)]
+#[allow(non_snake_case)]
+mod tests {
+    use {
+        icann_rdap_common::{
+            httpdata::HttpData,
+            prelude::ToResponse,
+            response::{Common, Link, Notice, NoticeOrRemark},
+        },
+        rstest::rstest,
+    };
+
+    use crate::{
+        md::{table::MultiPartTable, MdLinkStyle, MdOptions, MdParams, ToMd},
+        rdap::rr::{RequestData, SourceType},
+    };
+
+    use super::links_to_table;
+
+    #[rstest]
+    #[case(MdLinkStyle::Plain, Some("| Self|* https://example.com/a\\_(b)|\n"))]
+    #[case(
+        MdLinkStyle::Inline,
+        Some("| Self|* [https://example.com/a\\_(b)](https://example.com/a_%28b%29)|\n")
+    )]
+    #[case(MdLinkStyle::Omit, None)]
+    fn GIVEN_link_WHEN_links_to_table_THEN_link_style_applied(
+        #[case] link_style: MdLinkStyle,
+        #[case] expected_row: Option<&str>,
+    ) {
+        // GIVEN
+        let links = vec![Link::builder()
+            .href("https://example.com/a_(b)")
+            .rel("self")
+            .value("https://example.com")
+            .build()];
+        let options = MdOptions {
+            link_style,
+            no_unicode_chars: true,
+            ..MdOptions::default()
+        };
+        let req_data = RequestData {
+            req_number: 0,
+            source_host: "",
+            source_type: SourceType::UncategorizedRegistry,
+        };
+        let rdap_response = icann_rdap_common::response::Rfc9083Error::response_obj()
+            .error_code(500)
+            .build()
+            .to_response();
+        let params = MdParams {
+            heading_level: 1,
+            root: &rdap_response,
+            http_data: &HttpData::example().build(),
+            parent_type: std::any::TypeId::of::<MdParams>(),
+            check_types: &[],
+            options: &options,
+            req_data: &req_data,
+        };
+
+        // WHEN
+        let actual =
+            links_to_table(&links, MultiPartTable::new(), "Links", params).to_md_table(&options);
+
+        // THEN
+        if let Some(expected_row) = expected_row {
+            assert!(actual.contains(expected_row));
+        } else {
+            assert_eq!(actual, "|\n\n");
+        }
+    }
+
+    #[rstest]
+    #[case(false, true)]
+    #[case(true, false)]
+    fn GIVEN_notices_WHEN_to_md_THEN_notices_hidden_if_asked(
+        #[case] hide_notices: bool,
+        #[case] expected: bool,
+    ) {
+        // GIVEN
+        let common = Common {
+            rdap_conformance: None,
+            notices: Some(vec![Notice(
+                NoticeOrRemark::builder()
+                    .title("Terms of Use")
+                    .description_entry("Be nice.")
+                    .build(),
+            )]),
+        };
+        let options = MdOptions {
+            hide_notices,
+            ..MdOptions::default()
+        };
+        let req_data = RequestData {
+            req_number: 0,
+            source_host: "",
+            source_type: SourceType::UncategorizedRegistry,
+        };
+        let rdap_response = icann_rdap_common::response::Rfc9083Error::response_obj()
+            .error_code(500)
+            .build()
+            .to_response();
+        let params = MdParams {
+            heading_level: 1,
+            root: &rdap_response,
+            http_data: &HttpData::example().build(),
+            parent_type: std::any::TypeId::of::<MdParams>(),
+            check_types: &[],
+            options: &options,
+            req_data: &req_data,
+        };
+
+        // WHEN
+        let actual = common.to_md(params);
+
+        // THEN
+        assert_eq!(actual.contains("Terms of Use"), expected);
+        assert_eq!(actual.contains("Server Notices"), expected);
+    }
+}
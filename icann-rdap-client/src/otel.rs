@@ -0,0 +1,130 @@
+//! Export of traces with the OpenTelemetry protocol (OTLP).
+//!
+//! The request functions of this library make [tracing] spans for the phases of
+//! a request, such as bootstrapping and each HTTP request. With the `otel` feature,
+//! the layer of [otlp_layer] exports these spans, and the HTTP requests carry the
+//! W3C trace context of the current span so that the spans of servers are joined
+//! to them.
+//!
+//! ```rust,no_run
+//! use icann_rdap_client::otel::otlp_layer;
+//! use tracing_subscriber::{layer::SubscriberExt, util::SubscriberInitExt};
+//!
+//! let (layer, _provider) = otlp_layer("my-rdap-client").expect("exporter");
+//! tracing_subscriber::registry().with(layer).init();
+//! ```
+
+use {
+    opentelemetry::{global, trace::TracerProvider},
+    opentelemetry_http::{HeaderExtractor, HeaderInjector},
+    opentelemetry_otlp::{ExporterBuildError, SpanExporter},
+    opentelemetry_sdk::{propagation::TraceContextPropagator, trace::SdkTracer, Resource},
+    reqwest::header::HeaderMap,
+    tracing::{debug, Span},
+    tracing_opentelemetry::{OpenTelemetryLayer, OpenTelemetrySpanExt},
+    tracing_subscriber::registry::LookupSpan,
+};
+
+#[doc(inline)]
+pub use opentelemetry_sdk::trace::SdkTracerProvider;
+
+/// Creates a layer exporting spans with OTLP over HTTP, and sets the global
+/// propagator to the W3C trace context.
+///
+/// The exporter is configured by the standard `OTEL_EXPORTER_OTLP_*` environment
+/// variables, such as `OTEL_EXPORTER_OTLP_ENDPOINT`.
+///
+/// The returned provider should be kept until the program exits, and then shut
+/// down so that the last spans are exported.
+pub fn otlp_layer<S>(
+    service_name: &'static str,
+) -> Result<(OpenTelemetryLayer<S, SdkTracer>, SdkTracerProvider), ExporterBuildError>
+where
+    S: tracing::Subscriber + for<'span> LookupSpan<'span>,
+{
+    let exporter = SpanExporter::builder().with_http().build()?;
+    let provider = SdkTracerProvider::builder()
+        .with_batch_exporter(exporter)
+        .with_resource(Resource::builder().with_service_name(service_name).build())
+        .build();
+    global::set_text_map_propagator(TraceContextPropagator::new());
+    global::set_tracer_provider(provider.clone());
+    let layer = tracing_opentelemetry::layer().with_tracer(provider.tracer(service_name));
+    Ok((layer, provider))
+}
+
+/// Adds the trace context of the current span to the headers of a request.
+pub fn inject_trace_context(headers: &mut HeaderMap) {
+    let context = Span::current().context();
+    global::get_text_map_propagator(|propagator| {
+        propagator.inject_context(&context, &mut HeaderInjector(headers))
+    });
+}
+
+/// Makes the trace context in the headers of a request, if any, the parent of a span.
+pub fn set_parent_from_headers(span: &Span, headers: &HeaderMap) {
+    let context =
+        global::get_text_map_propagator(|propagator| propagator.extract(&HeaderExtractor(headers)));
+    if let Err(e) = span.set_parent(context) {
+        debug!("Unable to set the parent of a span: {e}");
+    }
+}
+
+#[cfg(test)]
+#[allow(non_snake_case)]
+mod tests {
+    use {
+        opentelemetry::global,
+        opentelemetry_sdk::propagation::TraceContextPropagator,
+        reqwest::header::{HeaderMap, HeaderValue},
+        tracing::info_span,
+        tracing_subscriber::layer::SubscriberExt,
+    };
+
+    use super::{inject_trace_context, set_parent_from_headers};
+
+    const TRACE_ID: &str = "4bf92f3577b34da6a3ce929d0e0e4736";
+
+    #[test]
+    fn GIVEN_no_span_WHEN_inject_trace_context_THEN_no_traceparent() {
+        // GIVEN
+        global::set_text_map_propagator(TraceContextPropagator::new());
+        let mut headers = HeaderMap::new();
+
+        // WHEN
+        inject_trace_context(&mut headers);
+
+        // THEN
+        assert!(headers.get("traceparent").is_none());
+    }
+
+    #[test]
+    fn GIVEN_inbound_traceparent_WHEN_outbound_request_THEN_same_trace() {
+        // GIVEN
+        global::set_text_map_propagator(TraceContextPropagator::new());
+        let subscriber = tracing_subscriber::registry().with(tracing_opentelemetry::layer());
+        let mut inbound = HeaderMap::new();
+        inbound.insert(
+            "traceparent",
+            HeaderValue::from_static("00-4bf92f3577b34da6a3ce929d0e0e4736-00f067aa0ba902b7-01"),
+        );
+
+        // WHEN
+        let outbound = tracing::subscriber::with_default(subscriber, || {
+            let span = info_span!("inbound");
+            set_parent_from_headers(&span, &inbound);
+            let _entered = span.enter();
+            let mut outbound = HeaderMap::new();
+            inject_trace_context(&mut outbound);
+            outbound
+        });
+
+        // THEN
+        let traceparent = outbound
+            .get("traceparent")
+            .expect("traceparent")
+            .to_str()
+            .expect("traceparent string");
+        assert!(traceparent.contains(TRACE_ID));
+    }
+}
@@ -0,0 +1,254 @@
+//! Concurrent lookups of many queries.
+
+use std::{
+    collections::{HashMap, VecDeque},
+    sync::Arc,
+};
+
+use {
+    futures::{
+        stream::{self, BoxStream},
+        StreamExt,
+    },
+    tokio::sync::Semaphore,
+    tracing::debug,
+};
+
+use crate::RdapClientError;
+
+use super::{lookup::RdapClient, qtype::QueryType, request::ResponseData};
+
+/// How the queries of a batch are sent.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BatchPolicy {
+    /// The most requests sent to a server at the same time.
+    pub max_per_host: usize,
+
+    /// The number of times a query failing with a retryable error, such as a
+    /// timeout, is sent again.
+    ///
+    /// This is in addition to the retries of the client when servers respond
+    /// with HTTP 429.
+    pub max_retries: u16,
+}
+
+impl Default for BatchPolicy {
+    fn default() -> Self {
+        Self {
+            max_per_host: 2,
+            max_retries: 1,
+        }
+    }
+}
+
+/// A query of a batch with its URL, found by bootstrapping.
+struct Planned {
+    query: QueryType,
+    url: Result<String, RdapClientError>,
+    host: Arc<Semaphore>,
+}
+
+impl RdapClient {
+    /// Sends many queries, returning a stream of their responses in the order they arrive.
+    ///
+    /// All the queries are bootstrapped before any is sent, so that each bootstrap
+    /// registry is fetched once. Then no more than `concurrency` queries are sent at a
+    /// time, and no more than [BatchPolicy::max_per_host] of them to the same server.
+    /// The queries of different servers are interleaved, so that a server with many
+    /// queries does not hold up the others, and the rate limits servers ask for
+    /// are honored by the client.
+    ///
+    /// Dropping the stream cancels the queries not yet done.
+    ///
+    /// ```no_run
+    /// use futures::StreamExt;
+    /// use icann_rdap_client::prelude::*;
+    ///
+    /// #[tokio::main]
+    /// async fn main() -> Result<(), RdapClientError> {
+    ///     let client = RdapClient::builder().build()?;
+    ///     let queries = vec![
+    ///         QueryType::domain("icann.org")?,
+    ///         QueryType::ipv4("192.0.2.1")?,
+    ///         QueryType::AsNumber(64496),
+    ///     ];
+    ///
+    ///     let mut responses = client.rdap_batch(queries, 8, BatchPolicy::default());
+    ///     while let Some((query, response)) = responses.next().await {
+    ///         match response {
+    ///             Ok(response) => println!("{query:?}: {}", response.rdap_type),
+    ///             Err(e) => println!("{query:?}: {e}"),
+    ///         }
+    ///     }
+    ///     Ok(())
+    /// }
+    /// ```
+    pub fn rdap_batch(
+        &self,
+        queries: Vec<QueryType>,
+        concurrency: usize,
+        policy: BatchPolicy,
+    ) -> BoxStream<'_, (QueryType, Result<ResponseData, RdapClientError>)> {
+        stream::once(self.plan_batch(queries, policy))
+            .flat_map(stream::iter)
+            .map(move |planned| self.batch_request(planned, policy))
+            .buffer_unordered(concurrency.max(1))
+            .boxed()
+    }
+
+    async fn plan_batch(&self, queries: Vec<QueryType>, policy: BatchPolicy) -> Vec<Planned> {
+        let mut hosts: HashMap<String, Arc<Semaphore>> = HashMap::new();
+        let mut planned = Vec::with_capacity(queries.len());
+        for query in queries {
+            let url = self.query_url(&query).await;
+            let host_name = url.as_deref().ok().and_then(url_host).unwrap_or_default();
+            let host = hosts
+                .entry(host_name.clone())
+                .or_insert_with(|| Arc::new(Semaphore::new(policy.max_per_host.max(1))))
+                .clone();
+            planned.push((host_name, Planned { query, url, host }));
+        }
+        debug!(
+            "batch of {} queries to {} hosts",
+            planned.len(),
+            hosts.len()
+        );
+        interleave_by_host(planned)
+    }
+
+    async fn batch_request(
+        &self,
+        planned: Planned,
+        policy: BatchPolicy,
+    ) -> (QueryType, Result<ResponseData, RdapClientError>) {
+        let Planned { query, url, host } = planned;
+        let url = match url {
+            Ok(url) => url,
+            Err(e) => return (query, Err(e)),
+        };
+        let mut retries = 0;
+        loop {
+            let result = {
+                let _permit = host
+                    .acquire()
+                    .await
+                    .expect("batch host semaphores are never closed");
+                self.url_request(&url).await
+            };
+            match result {
+                Err(e) if e.is_retryable() && retries < policy.max_retries => {
+                    retries += 1;
+                    debug!("retrying {url} after error: {e}");
+                }
+                result => return (query, result),
+            }
+        }
+    }
+}
+
+fn url_host(url: &str) -> Option<String> {
+    reqwest::Url::parse(url)
+        .ok()
+        .and_then(|url| url.host_str().map(str::to_string))
+}
+
+/// Orders items by taking one of each host in turn, keeping the order of the
+/// items of a host.
+fn interleave_by_host<T>(items: Vec<(String, T)>) -> Vec<T> {
+    let mut hosts: Vec<String> = vec![];
+    let mut queues: HashMap<String, VecDeque<T>> = HashMap::new();
+    for (host, item) in items {
+        if !queues.contains_key(&host) {
+            hosts.push(host.clone());
+        }
+        queues.entry(host).or_default().push_back(item);
+    }
+    let mut interleaved = vec![];
+    while !queues.is_empty() {
+        for host in &hosts {
+            if let Some(queue) = queues.get_mut(host) {
+                if let Some(item) = queue.pop_front() {
+                    interleaved.push(item);
+                }
+                if queue.is_empty() {
+                    queues.remove(host);
+                }
+            }
+        }
+    }
+    interleaved
+}
+
+#[cfg(test)]
+#[allow(non_snake_case)]
+mod tests {
+    use futures::StreamExt;
+
+    use crate::{
+        http::{ClientConfig, RetryPolicy},
+        rdap::{QueryType, RdapClient},
+    };
+
+    use super::{interleave_by_host, BatchPolicy};
+
+    #[test]
+    fn GIVEN_items_of_hosts_WHEN_interleave_by_host_THEN_one_of_each_host_in_turn() {
+        // GIVEN
+        let items = vec![
+            ("a".to_string(), 1),
+            ("a".to_string(), 2),
+            ("a".to_string(), 3),
+            ("b".to_string(), 4),
+            ("c".to_string(), 5),
+            ("b".to_string(), 6),
+        ];
+
+        // WHEN
+        let actual = interleave_by_host(items);
+
+        // THEN
+        assert_eq!(actual, vec![1, 4, 5, 2, 6, 3]);
+    }
+
+    #[tokio::test]
+    async fn GIVEN_unreachable_server_WHEN_rdap_batch_THEN_result_for_each_query() {
+        // GIVEN
+        let client = RdapClient::builder()
+            .config(ClientConfig::builder().https_only(false).build())
+            .retry_policy(RetryPolicy {
+                max_retries: 0,
+                ..Default::default()
+            })
+            .base_url("http://127.0.0.1:1/rdap")
+            .build()
+            .expect("client");
+        let queries = vec![
+            QueryType::domain("foo.example").expect("domain"),
+            QueryType::domain("bar.example").expect("domain"),
+            QueryType::AsNumber(64496),
+        ];
+
+        // WHEN
+        let actual = client
+            .rdap_batch(queries, 2, BatchPolicy::default())
+            .collect::<Vec<_>>()
+            .await;
+
+        // THEN
+        assert_eq!(actual.len(), 3);
+        assert!(actual.iter().all(|(_, result)| result.is_err()));
+        let mut queries = actual
+            .iter()
+            .map(|(query, _)| query.to_string())
+            .collect::<Vec<_>>();
+        queries.sort();
+        assert_eq!(
+            queries,
+            vec![
+                "Autonomous System Number Lookup".to_string(),
+                "Domain Lookup".to_string(),
+                "Domain Lookup".to_string()
+            ]
+        );
+    }
+}
@@ -0,0 +1,287 @@
+//! Caching of RDAP responses.
+
+use std::{
+    collections::HashMap,
+    sync::{Arc, RwLock},
+};
+
+use icann_rdap_common::response::GetSelfLink;
+
+use crate::RdapClientError;
+
+use super::ResponseData;
+
+/// The default number of seconds responses are cached, unless the server says otherwise.
+pub const DEFAULT_MAX_CACHE_AGE: i64 = 86400;
+
+/// Defines a trait for things that cache RDAP responses by their URLs.
+pub trait ResponseCache: Send + Sync {
+    /// Gets the response of a URL, if it is cached and has not expired.
+    fn get_response(&self, url: &str) -> Result<Option<ResponseData>, RdapClientError>;
+
+    /// Puts the response of a URL into the cache.
+    ///
+    /// Responses the server has asked not to be cached are not put in the cache.
+    fn put_response(&self, url: &str, response: &ResponseData) -> Result<(), RdapClientError>;
+}
+
+/// A [ResponseCache] held in memory.
+///
+/// Responses are also cached by the URL of their self link, so that a lookup
+/// of an object by its self link is answered from the cache.
+///
+/// Responses expire after the maximum age given to the cache, or sooner if the
+/// server says so with the `Cache-Control` or `Expires` headers.
+#[derive(Debug, Clone)]
+pub struct MemoryResponseCache {
+    max_age: i64,
+    max_entries: usize,
+    responses: Arc<RwLock<MemoryEntries>>,
+}
+
+/// The responses of a [MemoryResponseCache] with the order they were last used.
+#[derive(Debug, Default)]
+struct MemoryEntries {
+    entries: HashMap<String, MemoryEntry>,
+    uses: u64,
+}
+
+#[derive(Debug)]
+struct MemoryEntry {
+    response: ResponseData,
+    last_use: u64,
+}
+
+impl MemoryEntries {
+    fn next_use(&mut self) -> u64 {
+        self.uses += 1;
+        self.uses
+    }
+
+    fn insert(&mut self, url: &str, response: &ResponseData) {
+        let last_use = self.next_use();
+        self.entries.insert(
+            url.to_string(),
+            MemoryEntry {
+                response: response.clone(),
+                last_use,
+            },
+        );
+    }
+
+    /// Removes the least recently used entries until there are no more than `max_entries`.
+    fn evict(&mut self, max_entries: usize) {
+        if self.entries.len() <= max_entries {
+            return;
+        }
+        let mut lru = self
+            .entries
+            .iter()
+            .map(|(url, entry)| (entry.last_use, url.clone()))
+            .collect::<Vec<_>>();
+        lru.sort();
+        let excess = self.entries.len() - max_entries;
+        for (_, url) in lru.into_iter().take(excess) {
+            self.entries.remove(&url);
+        }
+    }
+}
+
+impl Default for MemoryResponseCache {
+    fn default() -> Self {
+        Self::new(DEFAULT_MAX_CACHE_AGE)
+    }
+}
+
+impl MemoryResponseCache {
+    /// Creates a cache holding responses at most `max_age` seconds.
+    pub fn new(max_age: i64) -> Self {
+        Self::bounded(max_age, 0)
+    }
+
+    /// Creates a cache holding responses at most `max_age` seconds, and holding
+    /// no more than `max_entries` responses by evicting the least recently used.
+    ///
+    /// A `max_entries` of 0 means the number of responses is not limited.
+    pub fn bounded(max_age: i64, max_entries: usize) -> Self {
+        Self {
+            max_age,
+            max_entries,
+            responses: <_>::default(),
+        }
+    }
+}
+
+impl ResponseCache for MemoryResponseCache {
+    fn get_response(&self, url: &str) -> Result<Option<ResponseData>, RdapClientError> {
+        let mut responses = self.responses.write()?;
+        let last_use = responses.next_use();
+        Ok(responses
+            .entries
+            .get_mut(url)
+            .filter(|entry| !entry.response.http_data.is_expired(self.max_age))
+            .map(|entry| {
+                entry.last_use = last_use;
+                entry.response.clone()
+            }))
+    }
+
+    fn put_response(&self, url: &str, response: &ResponseData) -> Result<(), RdapClientError> {
+        if !response.http_data.should_cache() {
+            return Ok(());
+        }
+        let mut responses = self.responses.write()?;
+        responses
+            .entries
+            .retain(|_, entry| !entry.response.http_data.is_expired(self.max_age));
+        if let Some(self_link) = response
+            .rdap
+            .get_self_link()
+            .and_then(|link| link.href.as_deref())
+        {
+            if self_link != url {
+                responses.insert(self_link, response);
+            }
+        }
+        responses.insert(url, response);
+        if self.max_entries != 0 {
+            responses.evict(self.max_entries);
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+#[allow(non_snake_case)]
+mod tests {
+    use icann_rdap_common::{
+        httpdata::HttpData,
+        prelude::ToResponse,
+        response::{Domain, Link, RdapResponse},
+    };
+
+    use crate::rdap::ResponseData;
+
+    use super::{MemoryResponseCache, ResponseCache, DEFAULT_MAX_CACHE_AGE};
+
+    fn response_data(rdap: RdapResponse, cache_control: Option<&str>) -> ResponseData {
+        ResponseData {
+            rdap_type: rdap.to_string(),
+            rdap,
+            http_data: HttpData::example()
+                .and_cache_control(cache_control.map(str::to_string))
+                .build(),
+        }
+    }
+
+    fn domain() -> RdapResponse {
+        Domain::builder()
+            .ldh_name("foo.example")
+            .link(
+                Link::builder()
+                    .value("https://rdap.example/domain/foo.example")
+                    .href("https://rdap.example/domain/foo.example")
+                    .rel("self")
+                    .build(),
+            )
+            .build()
+            .to_response()
+    }
+
+    #[test]
+    fn GIVEN_response_put_WHEN_get_response_THEN_cached_by_url_and_self_link() {
+        // GIVEN
+        let cache = MemoryResponseCache::default();
+        cache
+            .put_response(
+                "https://rdap.example/domain/FOO.EXAMPLE",
+                &response_data(domain(), None),
+            )
+            .expect("put response");
+
+        // WHEN
+        let by_url = cache
+            .get_response("https://rdap.example/domain/FOO.EXAMPLE")
+            .expect("get response");
+        let by_self_link = cache
+            .get_response("https://rdap.example/domain/foo.example")
+            .expect("get response");
+
+        // THEN
+        assert!(by_url.is_some());
+        assert!(by_self_link.is_some());
+    }
+
+    #[test]
+    fn GIVEN_no_store_response_WHEN_put_response_THEN_not_cached() {
+        // GIVEN
+        let cache = MemoryResponseCache::default();
+
+        // WHEN
+        cache
+            .put_response(
+                "https://rdap.example/domain/foo.example",
+                &response_data(domain(), Some("no-store")),
+            )
+            .expect("put response");
+
+        // THEN
+        let actual = cache
+            .get_response("https://rdap.example/domain/foo.example")
+            .expect("get response");
+        assert!(actual.is_none());
+    }
+
+    #[test]
+    fn GIVEN_bounded_cache_WHEN_put_beyond_bound_THEN_least_recently_used_evicted() {
+        // GIVEN
+        let cache = MemoryResponseCache::bounded(DEFAULT_MAX_CACHE_AGE, 2);
+        let response = response_data(
+            Domain::builder()
+                .ldh_name("foo.example")
+                .build()
+                .to_response(),
+            None,
+        );
+        cache
+            .put_response("https://rdap.example/domain/a.example", &response)
+            .expect("put response");
+        cache
+            .put_response("https://rdap.example/domain/b.example", &response)
+            .expect("put response");
+        cache
+            .get_response("https://rdap.example/domain/a.example")
+            .expect("get response");
+
+        // WHEN
+        cache
+            .put_response("https://rdap.example/domain/c.example", &response)
+            .expect("put response");
+
+        // THEN
+        let cached = |url: &str| cache.get_response(url).expect("get response").is_some();
+        assert!(cached("https://rdap.example/domain/a.example"));
+        assert!(!cached("https://rdap.example/domain/b.example"));
+        assert!(cached("https://rdap.example/domain/c.example"));
+    }
+
+    #[test]
+    fn GIVEN_expired_response_WHEN_get_response_THEN_none() {
+        // GIVEN
+        let cache = MemoryResponseCache::new(0);
+        cache
+            .put_response(
+                "https://rdap.example/domain/foo.example",
+                &response_data(domain(), None),
+            )
+            .expect("put response");
+
+        // WHEN
+        let actual = cache
+            .get_response("https://rdap.example/domain/foo.example")
+            .expect("get response");
+
+        // THEN
+        assert!(actual.is_none());
+    }
+}
@@ -0,0 +1,236 @@
+//! Checks run off the async runtime, within a time budget.
+
+use std::{
+    any::TypeId,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc,
+    },
+    time::Duration,
+};
+
+use {
+    icann_rdap_common::{
+        check::{CheckParams, Checks, GetChecks, GetSubChecks},
+        response::RdapResponse,
+    },
+    tokio::{
+        sync::mpsc::{unbounded_channel, UnboundedSender},
+        time::{timeout_at, Instant},
+    },
+    tracing::warn,
+};
+
+/// The checks of RDAP found within a time budget.
+#[derive(Debug, Clone)]
+pub struct BudgetedChecks {
+    /// The checks found, which are partial if the time budget ran out, or
+    /// `None` if it ran out before any were found.
+    pub checks: Option<Checks>,
+
+    /// True if the time budget ran out before all checks were found.
+    pub timed_out: bool,
+}
+
+/// A part of the checks of RDAP, in the order they are found.
+enum Stage {
+    /// The checks of the RDAP itself, without sub checks.
+    Root(Checks),
+    /// Sub checks of the RDAP.
+    Sub(Checks),
+}
+
+/// Finds the checks of RDAP on a blocking thread, waiting no longer than the budget.
+///
+/// Checks of large responses, such as search results with many objects, can take long
+/// enough to stall an async runtime if found on it. Here they are found on the blocking
+/// thread pool and returned as they were when the budget ran out, with a marker of
+/// the time out. The checks of the RDAP itself are found first, then those of its
+/// parts, such as each result of search results, and the finding of checks stops
+/// at the next part once the budget has run out.
+///
+/// ```no_run
+/// use std::{sync::Arc, time::Duration};
+///
+/// use icann_rdap_client::rdap::checks_within_budget;
+/// use icann_rdap_common::prelude::*;
+///
+/// #[tokio::main]
+/// async fn main() {
+///     let rdap = Domain::builder().ldh_name("foo.example").build().to_response();
+///
+///     let found = checks_within_budget(Arc::new(rdap), Duration::from_secs(1)).await;
+///     if found.timed_out {
+///         println!("checks are partial");
+///     }
+/// }
+/// ```
+pub async fn checks_within_budget(rdap: Arc<RdapResponse>, budget: Duration) -> BudgetedChecks {
+    let deadline = Instant::now() + budget;
+    let (sender, mut receiver) = unbounded_channel();
+    let stop = Arc::new(AtomicBool::new(false));
+    let task_stop = stop.clone();
+    tokio::task::spawn_blocking(move || staged_checks(&rdap, &task_stop, &sender));
+
+    let mut checks: Option<Checks> = None;
+    loop {
+        match timeout_at(deadline, receiver.recv()).await {
+            Ok(Some(Stage::Root(root))) => checks = Some(root),
+            Ok(Some(Stage::Sub(sub))) => {
+                if let Some(checks) = checks.as_mut() {
+                    checks.sub_checks.push(sub);
+                }
+            }
+            Ok(None) => {
+                return BudgetedChecks {
+                    checks,
+                    timed_out: false,
+                }
+            }
+            Err(_) => {
+                warn!("checks did not finish within {} ms", budget.as_millis());
+                stop.store(true, Ordering::Relaxed);
+                return BudgetedChecks {
+                    checks,
+                    timed_out: true,
+                };
+            }
+        }
+    }
+}
+
+/// Finds the checks of RDAP in stages, sending each stage until told to stop.
+fn staged_checks(rdap: &RdapResponse, stop: &AtomicBool, sender: &UnboundedSender<Stage>) {
+    let params = CheckParams::for_rdap(rdap);
+    let root = rdap.get_checks(CheckParams {
+        do_subchecks: false,
+        ..params
+    });
+    if sender.send(Stage::Root(root)).is_err() {
+        return;
+    }
+    let sub_checks = match rdap {
+        RdapResponse::DomainSearchResults(r) => {
+            search_sub_checks(&r.common, &r.results, params, type_of(r.as_ref()))
+        }
+        RdapResponse::EntitySearchResults(r) => {
+            search_sub_checks(&r.common, &r.results, params, type_of(r.as_ref()))
+        }
+        RdapResponse::NameserverSearchResults(r) => {
+            search_sub_checks(&r.common, &r.results, params, type_of(r.as_ref()))
+        }
+        RdapResponse::IpSearchResults(r) => {
+            search_sub_checks(&r.common, &r.results, params, type_of(r.as_ref()))
+        }
+        RdapResponse::AutnumSearchResults(r) => {
+            search_sub_checks(&r.common, &r.results, params, type_of(r.as_ref()))
+        }
+        _ => Box::new(rdap.get_checks(params).sub_checks.into_iter()),
+    };
+    for sub in sub_checks {
+        if stop.load(Ordering::Relaxed) || sender.send(Stage::Sub(sub)).is_err() {
+            return;
+        }
+    }
+}
+
+/// Gets the sub checks of search results lazily, one result at a time.
+fn search_sub_checks<'a, C: GetSubChecks, T: GetChecks>(
+    common: &'a C,
+    results: &'a [T],
+    params: CheckParams<'a>,
+    parent_type: TypeId,
+) -> Box<dyn Iterator<Item = Checks> + 'a> {
+    let params = params.from_parent(parent_type);
+    Box::new(
+        common
+            .get_sub_checks(params)
+            .into_iter()
+            .chain(results.iter().map(move |result| result.get_checks(params))),
+    )
+}
+
+fn type_of<T: 'static>(_: &T) -> TypeId {
+    TypeId::of::<T>()
+}
+
+#[cfg(test)]
+#[allow(non_snake_case)]
+mod tests {
+    use std::{sync::Arc, time::Duration};
+
+    use icann_rdap_common::{
+        check::{CheckParams, GetChecks},
+        prelude::ToResponse,
+        response::{Domain, DomainSearchResults, Link},
+    };
+
+    use super::checks_within_budget;
+
+    fn search_results(count: usize) -> DomainSearchResults {
+        DomainSearchResults::response_obj()
+            .results(
+                (0..count)
+                    .map(|i| {
+                        Domain::builder()
+                            .ldh_name(format!("foo{i}.example"))
+                            .link(
+                                Link::builder()
+                                    .value("https://rdap.example/domains")
+                                    .href(format!("https://rdap.example/domain/foo{i}.example"))
+                                    .rel("self")
+                                    .build(),
+                            )
+                            .build()
+                    })
+                    .collect(),
+            )
+            .build()
+    }
+
+    #[tokio::test]
+    async fn GIVEN_search_results_WHEN_checks_within_budget_THEN_same_as_get_checks() {
+        // GIVEN
+        let rdap = search_results(5).to_response();
+        let expected = rdap.get_checks(CheckParams::for_rdap(&rdap));
+
+        // WHEN
+        let actual = checks_within_budget(Arc::new(rdap), Duration::from_secs(30)).await;
+
+        // THEN
+        assert!(!actual.timed_out);
+        assert_eq!(actual.checks, Some(expected));
+    }
+
+    #[tokio::test]
+    async fn GIVEN_domain_WHEN_checks_within_budget_THEN_same_as_get_checks() {
+        // GIVEN
+        let rdap = Domain::builder()
+            .ldh_name("foo.example")
+            .build()
+            .to_response();
+        let expected = rdap.get_checks(CheckParams::for_rdap(&rdap));
+
+        // WHEN
+        let actual = checks_within_budget(Arc::new(rdap), Duration::from_secs(30)).await;
+
+        // THEN
+        assert!(!actual.timed_out);
+        assert_eq!(actual.checks, Some(expected));
+    }
+
+    #[tokio::test]
+    async fn GIVEN_no_budget_WHEN_checks_within_budget_THEN_timed_out() {
+        // GIVEN
+        let rdap = search_results(10_000).to_response();
+
+        // WHEN
+        let actual = checks_within_budget(Arc::new(rdap), Duration::ZERO).await;
+
+        // THEN
+        assert!(actual.timed_out);
+        assert!(actual
+            .checks
+            .is_none_or(|checks| checks.sub_checks.len() < 10_000));
+    }
+}
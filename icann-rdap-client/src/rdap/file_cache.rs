@@ -0,0 +1,383 @@
+//! Caching of RDAP responses in files.
+
+use std::{path::PathBuf, sync::Arc};
+
+use {
+    icann_rdap_common::{httpdata::HttpData, response::GetSelfLink},
+    pct_str::{PctString, URIReserved},
+    tracing::debug,
+};
+
+use crate::{
+    files::{
+        lock::{locked_read_lines, locked_write},
+        lru::{record_cache_access, record_cache_read},
+    },
+    RdapClientError,
+};
+
+use super::{
+    cache::{ResponseCache, DEFAULT_MAX_CACHE_AGE},
+    ResponseData,
+};
+
+/// Defines a trait for things that encrypt the files of a [FileResponseCache].
+pub trait ContentsCipher: Send + Sync {
+    /// Encrypts the contents of a cache file.
+    fn encrypt(&self, contents: &str) -> Result<String, std::io::Error>;
+
+    /// Decrypts the lines of an encrypted cache file.
+    fn decrypt_lines(&self, lines: &[String]) -> Result<Vec<String>, std::io::Error>;
+
+    /// Returns true if the lines are of an encrypted cache file.
+    fn is_encrypted(&self, lines: &[String]) -> bool;
+
+    /// Returns a keyed hash of a URL, such as an HMAC, naming the cache file of the URL
+    /// so that the URLs queried are not disclosed by the names of the files.
+    fn url_hash(&self, url: &str) -> String;
+}
+
+/// A [ResponseCache] of files in a directory, which may be shared by many processes.
+///
+/// Each response is kept in a file named for its percent-encoded URL, holding the
+/// HTTP data of the response followed by the response. As with the
+/// [super::MemoryResponseCache], responses are also cached by the URL of their self
+/// link, and expire after the maximum age given to the cache or sooner if the
+/// server says so.
+///
+/// If the cache has a [ContentsCipher], files are encrypted and named for the keyed
+/// hash of their URL given by the cipher, so URLs are only kept in the encrypted
+/// contents. Files that cannot be read, such as encrypted files when the cache has
+/// no cipher, are not used.
+#[derive(Clone)]
+pub struct FileResponseCache {
+    dir: PathBuf,
+    max_age: i64,
+    max_size: u64,
+    cipher: Option<Arc<dyn ContentsCipher>>,
+}
+
+#[buildstructor::buildstructor]
+impl FileResponseCache {
+    /// Creates a new cache.
+    ///
+    /// * dir - the directory of the cache files, which must exist.
+    /// * max_age - the most seconds responses are cached, which is [DEFAULT_MAX_CACHE_AGE] if not given.
+    /// * max_size - the most bytes of the cache files, beyond which the least recently used are
+    ///   removed. This is 0, meaning the size is not limited, if not given.
+    /// * cipher - if given, encrypts the cache files.
+    #[builder]
+    pub fn new(
+        dir: PathBuf,
+        max_age: Option<i64>,
+        max_size: Option<u64>,
+        cipher: Option<Arc<dyn ContentsCipher>>,
+    ) -> Self {
+        Self {
+            dir,
+            max_age: max_age.unwrap_or(DEFAULT_MAX_CACHE_AGE),
+            max_size: max_size.unwrap_or_default(),
+            cipher,
+        }
+    }
+
+    /// Reads the lines of a cache file, decrypting them if need be.
+    fn read_lines(&self, file_name: &str) -> Result<Option<Vec<String>>, RdapClientError> {
+        let path = self.dir.join(file_name);
+        if !path.exists() {
+            return Ok(None);
+        }
+        let lines = locked_read_lines(&path)?;
+        let Some(cipher) = &self.cipher else {
+            return Ok(Some(lines));
+        };
+        if !cipher.is_encrypted(&lines) {
+            return Ok(Some(lines));
+        }
+        Ok(cipher
+            .decrypt_lines(&lines)
+            .inspect_err(|e| debug!("Ignoring cache file {file_name}: {e}"))
+            .ok())
+    }
+
+    /// Returns the name of the cache file of a URL.
+    fn file_name(&self, url: &str) -> String {
+        match &self.cipher {
+            Some(cipher) => format!("{}.cache", cipher.url_hash(url)),
+            None => cache_file_name(url),
+        }
+    }
+
+    fn write(&self, file_name: &str, contents: &str) -> Result<(), RdapClientError> {
+        locked_write(&self.dir.join(file_name), contents)?;
+        record_cache_access(&self.dir, file_name, self.max_size)?;
+        Ok(())
+    }
+}
+
+impl ResponseCache for FileResponseCache {
+    fn get_response(&self, url: &str) -> Result<Option<ResponseData>, RdapClientError> {
+        let file_name = self.file_name(url);
+        let Some(lines) = self.read_lines(&file_name)? else {
+            return Ok(None);
+        };
+        let Ok((http_data, data)) = HttpData::from_lines(&lines) else {
+            debug!("Ignoring unreadable cache file {file_name}");
+            return Ok(None);
+        };
+        if http_data.is_expired(self.max_age) {
+            return Ok(None);
+        }
+        debug!("Returning response from cache file {file_name}");
+        let response: ResponseData = serde_json::from_str(&data.join(""))?;
+        record_cache_read(&self.dir, &file_name, self.max_size)?;
+        Ok(Some(response))
+    }
+
+    fn put_response(&self, url: &str, response: &ResponseData) -> Result<(), RdapClientError> {
+        if !response.http_data.should_cache() {
+            debug!("Not caching data according to server policy.");
+            debug!("Expires header: {:?}", &response.http_data.expires);
+            debug!(
+                "Cache-control header: {:?}",
+                &response.http_data.cache_control
+            );
+            return Ok(());
+        }
+        let data = serde_json::to_string_pretty(response)?;
+        let mut contents = response.http_data.to_lines(&data)?;
+        if let Some(cipher) = &self.cipher {
+            contents = cipher.encrypt(&contents)?;
+        }
+        let file_name = self.file_name(url);
+        debug!("Saving query response to cache file {file_name}");
+        self.write(&file_name, &contents)?;
+        if let Some(self_link) = response
+            .rdap
+            .get_self_link()
+            .and_then(|link| link.href.as_deref())
+        {
+            if self_link != url {
+                let file_name = self.file_name(self_link);
+                debug!("Saving object with self link to cache file {file_name}");
+                self.write(&file_name, &contents)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+fn cache_file_name(url: &str) -> String {
+    format!("{}.cache", PctString::encode(url.chars(), URIReserved))
+}
+
+#[cfg(test)]
+#[allow(non_snake_case)]
+mod tests {
+    use std::{
+        fs,
+        hash::{DefaultHasher, Hash, Hasher},
+        process,
+        sync::Arc,
+    };
+
+    use icann_rdap_common::{
+        httpdata::HttpData,
+        prelude::ToResponse,
+        response::{Domain, Link},
+    };
+
+    use crate::{
+        files::lru::CACHE_INDEX_FILE_NAME,
+        rdap::{ResponseCache, ResponseData},
+    };
+
+    use super::{cache_file_name, ContentsCipher, FileResponseCache};
+
+    /// A cipher that reverses the contents, for testing.
+    struct ReverseCipher;
+
+    impl ContentsCipher for ReverseCipher {
+        fn encrypt(&self, contents: &str) -> Result<String, std::io::Error> {
+            Ok(format!(
+                "#reversed\n{}",
+                contents.chars().rev().collect::<String>()
+            ))
+        }
+
+        fn decrypt_lines(&self, lines: &[String]) -> Result<Vec<String>, std::io::Error> {
+            let contents = lines[1..].join("\n").chars().rev().collect::<String>();
+            Ok(contents.lines().map(str::to_string).collect())
+        }
+
+        fn is_encrypted(&self, lines: &[String]) -> bool {
+            lines.first().is_some_and(|line| line == "#reversed")
+        }
+
+        fn url_hash(&self, url: &str) -> String {
+            let mut hasher = DefaultHasher::new();
+            url.hash(&mut hasher);
+            format!("{:016x}", hasher.finish())
+        }
+    }
+
+    fn cache_dir(name: &str) -> std::path::PathBuf {
+        let dir = std::env::temp_dir().join(format!("icann-rdap-{name}-{}", process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).expect("creating cache dir");
+        dir
+    }
+
+    fn response_data(cache_control: Option<&str>) -> ResponseData {
+        let rdap = Domain::builder()
+            .ldh_name("foo.example")
+            .link(
+                Link::builder()
+                    .value("https://rdap.example/domain/foo.example")
+                    .href("https://rdap.example/domain/foo.example")
+                    .rel("self")
+                    .build(),
+            )
+            .build()
+            .to_response();
+        ResponseData {
+            rdap_type: rdap.to_string(),
+            rdap,
+            http_data: HttpData::example()
+                .and_cache_control(cache_control.map(str::to_string))
+                .build(),
+        }
+    }
+
+    #[test]
+    fn GIVEN_response_put_WHEN_get_response_THEN_cached_by_url_and_self_link() {
+        // GIVEN
+        let dir = cache_dir("file-cache");
+        let cache = FileResponseCache::builder().dir(&dir).build();
+        cache
+            .put_response(
+                "https://rdap.example/domain/FOO.EXAMPLE",
+                &response_data(None),
+            )
+            .expect("put response");
+
+        // WHEN
+        let by_url = cache
+            .get_response("https://rdap.example/domain/FOO.EXAMPLE")
+            .expect("get response");
+        let by_self_link = cache
+            .get_response("https://rdap.example/domain/foo.example")
+            .expect("get response");
+
+        // THEN
+        assert_eq!(
+            by_url.map(|response| response.rdap),
+            Some(response_data(None).rdap)
+        );
+        assert!(by_self_link.is_some());
+    }
+
+    #[test]
+    fn GIVEN_response_just_put_WHEN_get_response_THEN_index_not_rewritten() {
+        // GIVEN
+        let dir = cache_dir("file-cache-read-index");
+        let cache = FileResponseCache::builder().dir(&dir).build();
+        cache
+            .put_response(
+                "https://rdap.example/domain/foo.example",
+                &response_data(None),
+            )
+            .expect("put response");
+        let index = fs::read_to_string(dir.join(CACHE_INDEX_FILE_NAME)).expect("reading index");
+
+        // WHEN
+        cache
+            .get_response("https://rdap.example/domain/foo.example")
+            .expect("get response")
+            .expect("cached response");
+
+        // THEN
+        let actual = fs::read_to_string(dir.join(CACHE_INDEX_FILE_NAME)).expect("reading index");
+        assert_eq!(actual, index);
+    }
+
+    #[test]
+    fn GIVEN_no_store_response_WHEN_put_response_THEN_not_cached() {
+        // GIVEN
+        let dir = cache_dir("file-cache-no-store");
+        let cache = FileResponseCache::builder().dir(&dir).build();
+
+        // WHEN
+        cache
+            .put_response(
+                "https://rdap.example/domain/foo.example",
+                &response_data(Some("no-store")),
+            )
+            .expect("put response");
+
+        // THEN
+        assert!(!dir
+            .join(cache_file_name("https://rdap.example/domain/foo.example"))
+            .exists());
+    }
+
+    #[test]
+    fn GIVEN_encrypted_cache_WHEN_get_response_without_cipher_THEN_none() {
+        // GIVEN
+        let dir = cache_dir("file-cache-encrypted");
+        let encrypted = FileResponseCache::builder()
+            .dir(&dir)
+            .cipher(Arc::new(ReverseCipher))
+            .build();
+        encrypted
+            .put_response(
+                "https://rdap.example/domain/foo.example",
+                &response_data(None),
+            )
+            .expect("put response");
+        let plain = FileResponseCache::builder().dir(&dir).build();
+
+        // WHEN
+        let with_cipher = encrypted
+            .get_response("https://rdap.example/domain/foo.example")
+            .expect("get response");
+        let without_cipher = plain
+            .get_response("https://rdap.example/domain/foo.example")
+            .expect("get response");
+
+        // THEN
+        assert!(with_cipher.is_some());
+        assert!(without_cipher.is_none());
+    }
+
+    #[test]
+    fn GIVEN_encrypted_cache_WHEN_put_response_THEN_files_not_named_for_url() {
+        // GIVEN
+        let dir = cache_dir("file-cache-hashed-names");
+        let cache = FileResponseCache::builder()
+            .dir(&dir)
+            .cipher(Arc::new(ReverseCipher))
+            .build();
+
+        // WHEN
+        cache
+            .put_response(
+                "https://rdap.example/domain/FOO.EXAMPLE",
+                &response_data(None),
+            )
+            .expect("put response");
+
+        // THEN
+        let names = fs::read_dir(&dir)
+            .expect("reading cache dir")
+            .map(|entry| entry.expect("dir entry").file_name())
+            .collect::<Vec<_>>();
+        assert!(names.iter().any(|name| name.to_string_lossy()
+            == ReverseCipher.url_hash("https://rdap.example/domain/FOO.EXAMPLE") + ".cache"));
+        assert!(!names
+            .iter()
+            .any(|name| name.to_string_lossy().contains("rdap.example")));
+        let index = fs::read_to_string(dir.join(CACHE_INDEX_FILE_NAME)).unwrap_or_default();
+        assert!(!index.contains("rdap.example"));
+    }
+}
@@ -0,0 +1,451 @@
+//! A high-level client for typed RDAP lookups.
+
+use {
+    icann_rdap_common::{
+        check::{CheckParams, Checks, GetChecks},
+        httpdata::HttpData,
+        iana::IanaRegistryType,
+        response::{Autnum, Domain, Entity, Nameserver, Network, RdapResponse},
+    },
+    tracing::debug,
+};
+
+use crate::{
+    http::{create_client, Client, ClientConfig, RateLimits, RetryPolicy},
+    iana::{qtype_to_bootstrap_url, refresh_bootstrap, BootstrapStore, MemoryBootstrapStore},
+    RdapClientError,
+};
+
+use super::{
+    cache::{MemoryResponseCache, ResponseCache},
+    qtype::QueryType,
+    request::{rdap_url_request, ResponseData},
+    single_flight::InFlight,
+};
+
+/// The result of a typed lookup.
+#[derive(Debug, Clone)]
+pub struct Lookup<T> {
+    /// The object found.
+    pub object: T,
+
+    /// The checks of the object and of the HTTP response.
+    pub checks: Checks,
+
+    /// Data about the HTTP response.
+    pub http_data: HttpData,
+}
+
+/// A client that does typed RDAP lookups, bundling bootstrapping, the request,
+/// parsing of the response, and checks into a single call.
+///
+/// The client owns the state shared by its lookups: the HTTP client and its
+/// connection pools, the bootstrap registries, the cache of responses, how
+/// requests are retried, and the rate limits servers have asked for. A client
+/// should therefore be reused for many lookups. Concurrent lookups of the same
+/// URL are sent to the server once, with the response shared among them. The
+/// lower level request functions, such as [super::rdap_request], remain for
+/// callers managing this state themselves.
+///
+/// ```no_run
+/// use icann_rdap_client::prelude::*;
+///
+/// #[tokio::main]
+/// async fn main() -> Result<(), RdapClientError> {
+///     let client = RdapClient::builder().build()?;
+///
+///     let domain = client.lookup_domain("icann.org").await?;
+///     println!("{:?}", domain.object.ldh_name);
+///
+///     let network = client.lookup_ip("192.0.2.1").await?;
+///     let autnum = client.lookup_autnum(64496).await?;
+///
+///     Ok(())
+/// }
+/// ```
+///
+/// To query a specific server instead of bootstrapping, give the base URL:
+///
+/// ```no_run
+/// use icann_rdap_client::prelude::*;
+///
+/// # fn main() -> Result<(), RdapClientError> {
+/// let client = RdapClient::builder()
+///     .base_url("https://rdap.example.com/rdap")
+///     .build()?;
+/// # Ok(())
+/// # }
+/// ```
+pub struct RdapClient {
+    client: Client,
+    store: Box<dyn BootstrapStore>,
+    cache: Option<Box<dyn ResponseCache>>,
+    in_flight: InFlight,
+    base_url: Option<String>,
+}
+
+#[buildstructor::buildstructor]
+impl RdapClient {
+    /// Creates a new client.
+    ///
+    /// * config - the configuration of the HTTP client, which is [ClientConfig::default] if not given.
+    /// * store - the store of bootstrap registries, which is a [MemoryBootstrapStore] if not given.
+    /// * cache - the cache of responses, which is a [MemoryResponseCache] if not given.
+    /// * no_cache - if true, responses are not cached.
+    /// * retry_policy - how requests are retried, overriding the retries of the config.
+    /// * base_url - if given, lookups are sent to this server and bootstrapping is not done,
+    ///   except for entity handles with an object tag, which are sent to the server of the
+    ///   tag in the IANA object tags registry and to this server only if the tag is not there.
+    #[builder]
+    pub fn new(
+        config: Option<ClientConfig>,
+        store: Option<Box<dyn BootstrapStore>>,
+        cache: Option<Box<dyn ResponseCache>>,
+        no_cache: Option<bool>,
+        retry_policy: Option<RetryPolicy>,
+        base_url: Option<String>,
+    ) -> Result<Self, RdapClientError> {
+        let mut client = create_client(&config.unwrap_or_default())?;
+        if let Some(retry_policy) = retry_policy {
+            client.set_retry_policy(retry_policy);
+        }
+        let cache = if no_cache.unwrap_or(false) {
+            None
+        } else {
+            Some(cache.unwrap_or_else(|| Box::new(MemoryResponseCache::default())))
+        };
+        Ok(Self {
+            client,
+            store: store.unwrap_or_else(|| Box::new(MemoryBootstrapStore::new())),
+            cache,
+            in_flight: InFlight::default(),
+            base_url,
+        })
+    }
+
+    /// Gets the HTTP client, for use with the lower level request functions.
+    pub fn client(&self) -> &Client {
+        &self.client
+    }
+
+    /// Gets the store of bootstrap registries.
+    pub fn store(&self) -> &dyn BootstrapStore {
+        self.store.as_ref()
+    }
+
+    /// Fetches a bootstrap registry into the store of the client, whether or not
+    /// the store has the registry and it is up-to-date.
+    pub async fn refresh_bootstrap(
+        &self,
+        reg_type: &IanaRegistryType,
+    ) -> Result<(), RdapClientError> {
+        refresh_bootstrap(reg_type, &self.client, self.store.as_ref()).await
+    }
+
+    /// Gets the rate limits servers have asked for.
+    pub fn rate_limits(&self) -> &RateLimits {
+        self.client.rate_limits()
+    }
+
+    /// Looks up a domain by its name.
+    pub async fn lookup_domain(&self, name: &str) -> Result<Lookup<Domain>, RdapClientError> {
+        self.lookup(&QueryType::domain(name)?, "domain", |rdap| match rdap {
+            RdapResponse::Domain(domain) => Some(*domain),
+            _ => None,
+        })
+        .await
+    }
+
+    /// Looks up the network of an IP address or CIDR, such as "192.0.2.1" or "2001:db8::/32".
+    pub async fn lookup_ip(&self, ip: &str) -> Result<Lookup<Network>, RdapClientError> {
+        self.lookup(&ip_query(ip)?, "network", |rdap| match rdap {
+            RdapResponse::Network(network) => Some(*network),
+            _ => None,
+        })
+        .await
+    }
+
+    /// Looks up an autonomous system number.
+    pub async fn lookup_autnum(&self, autnum: u32) -> Result<Lookup<Autnum>, RdapClientError> {
+        self.lookup(&QueryType::AsNumber(autnum), "autnum", |rdap| match rdap {
+            RdapResponse::Autnum(autnum) => Some(*autnum),
+            _ => None,
+        })
+        .await
+    }
+
+    /// Looks up a nameserver by its name.
+    pub async fn lookup_nameserver(
+        &self,
+        name: &str,
+    ) -> Result<Lookup<Nameserver>, RdapClientError> {
+        self.lookup(&QueryType::ns(name)?, "nameserver", |rdap| match rdap {
+            RdapResponse::Nameserver(nameserver) => Some(*nameserver),
+            _ => None,
+        })
+        .await
+    }
+
+    /// Looks up an entity by its handle.
+    ///
+    /// When bootstrapping, the handle must have a tag registered with IANA, such as "FOO-ARIN".
+    pub async fn lookup_entity(&self, handle: &str) -> Result<Lookup<Entity>, RdapClientError> {
+        self.lookup(&QueryType::entity(handle), "entity", |rdap| match rdap {
+            RdapResponse::Entity(entity) => Some(*entity),
+            _ => None,
+        })
+        .await
+    }
+
+    /// Sends any query, returning the response without checks.
+    ///
+    /// The response is taken from the cache if there, and otherwise put into it.
+    pub async fn request(&self, query: &QueryType) -> Result<ResponseData, RdapClientError> {
+        let url = self.query_url(query).await?;
+        self.url_request(&url).await
+    }
+
+    /// Gets the URL of a query, bootstrapping unless the client has a base URL.
+    ///
+    /// Tagged entity handles are bootstrapped even with a base URL, which is used
+    /// when the tag cannot be bootstrapped.
+    pub(crate) async fn query_url(&self, query: &QueryType) -> Result<String, RdapClientError> {
+        let bootstrap = || {
+            qtype_to_bootstrap_url(&self.client, self.store.as_ref(), query, |reg| {
+                debug!("fetching IANA registry {reg:?}")
+            })
+        };
+        let base_url = match (&self.base_url, query) {
+            (Some(base_url), QueryType::TaggedEntity(_)) => bootstrap().await.unwrap_or_else(|e| {
+                debug!("using base URL as object tag cannot be bootstrapped: {e}");
+                base_url.clone()
+            }),
+            (Some(base_url), _) => base_url.clone(),
+            (None, _) => bootstrap().await?,
+        };
+        query.query_url(&base_url)
+    }
+
+    /// Sends the request for a URL, using the cache and sharing concurrent requests.
+    pub(crate) async fn url_request(&self, url: &str) -> Result<ResponseData, RdapClientError> {
+        if let Some(cache) = &self.cache {
+            if let Some(response) = cache.get_response(url)? {
+                debug!("returning response from cache for {url}");
+                return Ok(response);
+            }
+        }
+        let response = self
+            .in_flight
+            .request(url, || rdap_url_request(url, &self.client))
+            .await?;
+        if let Some(cache) = &self.cache {
+            cache.put_response(url, &response)?;
+        }
+        Ok(response)
+    }
+
+    async fn lookup<T>(
+        &self,
+        query: &QueryType,
+        expected: &str,
+        object: impl FnOnce(RdapResponse) -> Option<T>,
+    ) -> Result<Lookup<T>, RdapClientError> {
+        let response = self.request(query).await?;
+        typed_lookup(response, expected, object)
+    }
+}
+
+/// Makes the lookup of the object, with its checks, from a response.
+fn typed_lookup<T>(
+    response: ResponseData,
+    expected: &str,
+    object: impl FnOnce(RdapResponse) -> Option<T>,
+) -> Result<Lookup<T>, RdapClientError> {
+    let params = CheckParams::for_rdap(&response.rdap);
+    let mut checks = response.rdap.get_checks(params);
+    checks
+        .items
+        .append(&mut response.http_data.get_checks(params).items);
+    let ResponseData {
+        rdap,
+        rdap_type,
+        http_data,
+    } = response;
+    let Some(object) = object(rdap) else {
+        return Err(RdapClientError::UnexpectedResponse {
+            expected: expected.to_string(),
+            actual: rdap_type,
+        });
+    };
+    Ok(Lookup {
+        object,
+        checks,
+        http_data,
+    })
+}
+
+/// Creates an IP address or CIDR query.
+fn ip_query(ip: &str) -> Result<QueryType, RdapClientError> {
+    match (ip.contains('/'), ip.contains(':')) {
+        (true, false) => QueryType::ipv4cidr(ip),
+        (true, true) => QueryType::ipv6cidr(ip),
+        (false, false) => QueryType::ipv4(ip),
+        (false, true) => QueryType::ipv6(ip),
+    }
+}
+
+#[cfg(test)]
+#[allow(non_snake_case)]
+mod tests {
+    use {
+        icann_rdap_common::{
+            httpdata::HttpData,
+            iana::{IanaRegistry, IanaRegistryType},
+            prelude::ToResponse,
+            response::{Domain, RdapResponse},
+        },
+        rstest::rstest,
+    };
+
+    use crate::{
+        iana::{BootstrapStore, MemoryBootstrapStore},
+        rdap::{QueryType, ResponseData},
+        RdapClientError,
+    };
+
+    use super::{ip_query, typed_lookup, RdapClient};
+
+    fn response_data(rdap: RdapResponse) -> ResponseData {
+        ResponseData {
+            rdap_type: rdap.to_string(),
+            rdap,
+            http_data: HttpData::example().build(),
+        }
+    }
+
+    fn object_tags_store() -> MemoryBootstrapStore {
+        let store = MemoryBootstrapStore::new();
+        let registry = serde_json::from_str::<IanaRegistry>(
+            r#"
+            {
+              "version": "1.0",
+              "publication": "2024-01-07T10:11:12Z",
+              "services": [
+                [
+                  ["contact@example.com"],
+                  ["ARIN"],
+                  ["https://rdap.arin.example/registry/"]
+                ]
+              ]
+            }
+            "#,
+        )
+        .expect("parsing object tags registry");
+        store
+            .put_bootstrap_registry(
+                &IanaRegistryType::RdapObjectTags,
+                registry,
+                HttpData::example().build(),
+            )
+            .expect("putting registry");
+        store
+    }
+
+    #[rstest]
+    #[case("FOO-ARIN", "https://rdap.arin.example/registry/entity/FOO-ARIN")]
+    #[case("FOO-NOPE", "https://rdap.example.com/rdap/entity/FOO-NOPE")]
+    #[case("FOO", "https://rdap.example.com/rdap/entity/FOO")]
+    #[tokio::test]
+    async fn GIVEN_base_url_WHEN_query_url_of_entity_THEN_tag_routed_or_base_url(
+        #[case] handle: &str,
+        #[case] expected: &str,
+    ) {
+        // GIVEN
+        let client = RdapClient::builder()
+            .store(Box::new(object_tags_store()))
+            .base_url("https://rdap.example.com/rdap")
+            .build()
+            .expect("client");
+
+        // WHEN
+        let actual = client
+            .query_url(&QueryType::entity(handle))
+            .await
+            .expect("query url");
+
+        // THEN
+        assert_eq!(actual, expected);
+    }
+
+    #[rstest]
+    #[case("192.0.2.1", "IpV4 Address Lookup")]
+    #[case("2001:db8::1", "IpV6 Address Lookup")]
+    #[case("192.0.2.0/24", "IpV4 CIDR Lookup")]
+    #[case("2001:db8::/32", "IpV6 CIDR Lookup")]
+    fn GIVEN_ip_WHEN_ip_query_THEN_query_type(#[case] ip: &str, #[case] expected: &str) {
+        // GIVEN ip
+
+        // WHEN
+        let actual = ip_query(ip).expect("ip query");
+
+        // THEN
+        assert_eq!(actual.to_string(), expected);
+    }
+
+    #[test]
+    fn GIVEN_not_ip_WHEN_ip_query_THEN_error() {
+        // GIVEN
+        let ip = "foo.example";
+
+        // WHEN
+        let actual = ip_query(ip);
+
+        // THEN
+        assert!(matches!(actual, Err(RdapClientError::InvalidQueryValue)));
+    }
+
+    #[test]
+    fn GIVEN_domain_response_WHEN_typed_lookup_of_domain_THEN_domain_and_checks() {
+        // GIVEN
+        let response = response_data(
+            Domain::builder()
+                .ldh_name("foo.example")
+                .build()
+                .to_response(),
+        );
+
+        // WHEN
+        let actual = typed_lookup(response, "domain", |rdap| match rdap {
+            RdapResponse::Domain(domain) => Some(*domain),
+            _ => None,
+        })
+        .expect("typed lookup");
+
+        // THEN
+        assert_eq!(actual.object.ldh_name.as_deref(), Some("foo.example"));
+        assert!(!actual.checks.items.is_empty());
+    }
+
+    #[test]
+    fn GIVEN_domain_response_WHEN_typed_lookup_of_network_THEN_unexpected_response() {
+        // GIVEN
+        let response = response_data(
+            Domain::builder()
+                .ldh_name("foo.example")
+                .build()
+                .to_response(),
+        );
+
+        // WHEN
+        let actual = typed_lookup(response, "network", |rdap| match rdap {
+            RdapResponse::Network(network) => Some(*network),
+            _ => None,
+        });
+
+        // THEN
+        let Err(RdapClientError::UnexpectedResponse { expected, .. }) = actual else {
+            panic!("expected unexpected response error");
+        };
+        assert_eq!(expected, "network");
+    }
+}
@@ -1,5 +1,22 @@
 //! Code for managing RDAP queries.
 
+#[cfg(not(target_arch = "wasm32"))]
+#[doc(inline)]
+pub use batch::*;
+#[doc(inline)]
+pub use cache::*;
+#[cfg(not(target_arch = "wasm32"))]
+#[doc(inline)]
+pub use check_task::*;
+#[cfg(not(target_arch = "wasm32"))]
+#[doc(inline)]
+pub use file_cache::*;
+#[doc(inline)]
+pub use lookup::*;
+#[doc(inline)]
+pub use network_match::*;
+#[doc(inline)]
+pub use paging::*;
 #[doc(inline)]
 pub use qtype::*;
 #[doc(inline)]
@@ -8,8 +25,22 @@ pub use registered_redactions::*;
 pub use request::*;
 #[doc(inline)]
 pub use rr::*;
+#[doc(inline)]
+pub use trace::*;
 
+#[cfg(not(target_arch = "wasm32"))]
+pub(crate) mod batch;
+pub(crate) mod cache;
+#[cfg(not(target_arch = "wasm32"))]
+pub(crate) mod check_task;
+#[cfg(not(target_arch = "wasm32"))]
+pub(crate) mod file_cache;
+pub(crate) mod lookup;
+pub(crate) mod network_match;
+pub(crate) mod paging;
 pub(crate) mod qtype;
 pub(crate) mod registered_redactions;
 pub(crate) mod request;
 pub(crate) mod rr;
+pub(crate) mod single_flight;
+pub(crate) mod trace;
@@ -0,0 +1,204 @@
+//! Selection of IP networks for IP queries.
+
+use std::net::IpAddr;
+
+use icann_rdap_common::response::Network;
+
+use super::qtype::QueryType;
+
+/// The semantics used to select the network answering an IP query.
+///
+/// RDAP servers answer IP queries with the most specific network containing
+/// the query. Other networks are found by walking the `up` links of the
+/// networks starting with the most specific.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum NetworkMatch {
+    /// The network with exactly the range of addresses in the query.
+    Exact,
+
+    /// The most specific network containing the query.
+    #[default]
+    MostSpecific,
+
+    /// The network immediately less specific than the most specific network.
+    LessSpecific,
+}
+
+impl NetworkMatch {
+    /// Determines if a candidate network satisfies the match semantics.
+    ///
+    /// * query_type - the IP query.
+    /// * most_specific - the network returned by the server for the query.
+    /// * candidate - the network being considered.
+    pub fn is_match(
+        &self,
+        query_type: &QueryType,
+        most_specific: &Network,
+        candidate: &Network,
+    ) -> bool {
+        let Some(candidate_range) = network_range(candidate) else {
+            return false;
+        };
+        match self {
+            Self::MostSpecific => network_range(most_specific) == Some(candidate_range),
+            Self::Exact => query_range(query_type) == Some(candidate_range),
+            Self::LessSpecific => network_range(most_specific).is_some_and(|most_specific| {
+                most_specific != candidate_range && contains(&candidate_range, &most_specific)
+            }),
+        }
+    }
+
+    /// Determines if a network less specific than the candidate could satisfy
+    /// the match semantics, and therefore if the `up` link of the candidate should
+    /// be followed.
+    pub fn is_match_up(&self, query_type: &QueryType, candidate: &Network) -> bool {
+        match self {
+            Self::MostSpecific => false,
+            // less specific networks only get bigger.
+            Self::Exact => match (query_range(query_type), network_range(candidate)) {
+                (Some(query), Some(candidate)) => contains(&query, &candidate),
+                _ => false,
+            },
+            Self::LessSpecific => true,
+        }
+    }
+}
+
+/// Gets the first and last addresses of an IP query.
+fn query_range(query_type: &QueryType) -> Option<(IpAddr, IpAddr)> {
+    match query_type {
+        QueryType::IpV4Addr(addr) => Some((IpAddr::V4(*addr), IpAddr::V4(*addr))),
+        QueryType::IpV6Addr(addr) => Some((IpAddr::V6(*addr), IpAddr::V6(*addr))),
+        QueryType::IpV4Cidr(cidr) => Some((
+            IpAddr::V4(cidr.first_address()),
+            IpAddr::V4(cidr.last_address()),
+        )),
+        QueryType::IpV6Cidr(cidr) => Some((
+            IpAddr::V6(cidr.first_address()),
+            IpAddr::V6(cidr.last_address()),
+        )),
+        _ => None,
+    }
+}
+
+/// Gets the first and last addresses of a network.
+fn network_range(network: &Network) -> Option<(IpAddr, IpAddr)> {
+    let start = network.start_address()?.parse::<IpAddr>().ok()?;
+    let end = network.end_address()?.parse::<IpAddr>().ok()?;
+    Some((start, end))
+}
+
+/// Determines if the outer range contains the inner range.
+fn contains(outer: &(IpAddr, IpAddr), inner: &(IpAddr, IpAddr)) -> bool {
+    outer.0.is_ipv4() == inner.0.is_ipv4() && outer.0 <= inner.0 && outer.1 >= inner.1
+}
+
+#[cfg(test)]
+#[allow(non_snake_case)]
+mod tests {
+    use std::str::FromStr;
+
+    use {icann_rdap_common::response::Network, rstest::rstest};
+
+    use crate::rdap::QueryType;
+
+    use super::NetworkMatch;
+
+    fn network(cidr: &str) -> Network {
+        Network::builder().cidr(cidr).build().expect("network")
+    }
+
+    #[rstest]
+    #[case(
+        NetworkMatch::MostSpecific,
+        "10.0.0.1",
+        "10.0.0.0/24",
+        "10.0.0.0/24",
+        true
+    )]
+    #[case(
+        NetworkMatch::MostSpecific,
+        "10.0.0.1",
+        "10.0.0.0/24",
+        "10.0.0.0/8",
+        false
+    )]
+    #[case(NetworkMatch::Exact, "10.0.0.0/16", "10.0.0.0/16", "10.0.0.0/16", true)]
+    #[case(
+        NetworkMatch::Exact,
+        "10.0.0.0/16",
+        "10.0.0.0/24",
+        "10.0.0.0/24",
+        false
+    )]
+    #[case(NetworkMatch::Exact, "10.0.0.0/16", "10.0.0.0/24", "10.0.0.0/16", true)]
+    #[case(NetworkMatch::Exact, "10.0.0.0/16", "10.0.0.0/24", "10.0.0.0/8", false)]
+    #[case(
+        NetworkMatch::LessSpecific,
+        "10.0.0.1",
+        "10.0.0.0/24",
+        "10.0.0.0/24",
+        false
+    )]
+    #[case(
+        NetworkMatch::LessSpecific,
+        "10.0.0.1",
+        "10.0.0.0/24",
+        "10.0.0.0/16",
+        true
+    )]
+    #[case(
+        NetworkMatch::LessSpecific,
+        "2001:db8::1",
+        "2001:db8::/48",
+        "2001:db8::/32",
+        true
+    )]
+    #[case(
+        NetworkMatch::LessSpecific,
+        "2001:db8::1",
+        "2001:db8::/48",
+        "10.0.0.0/8",
+        false
+    )]
+    fn GIVEN_networks_WHEN_is_match_THEN_correct(
+        #[case] network_match: NetworkMatch,
+        #[case] query: &str,
+        #[case] most_specific: &str,
+        #[case] candidate: &str,
+        #[case] expected: bool,
+    ) {
+        // GIVEN
+        let query_type = QueryType::from_str(query).expect("query type");
+        let most_specific = network(most_specific);
+        let candidate = network(candidate);
+
+        // WHEN
+        let actual = network_match.is_match(&query_type, &most_specific, &candidate);
+
+        // THEN
+        assert_eq!(actual, expected);
+    }
+
+    #[rstest]
+    #[case(NetworkMatch::MostSpecific, "10.0.0.1", "10.0.0.0/24", false)]
+    #[case(NetworkMatch::Exact, "10.0.0.0/16", "10.0.0.0/24", true)]
+    #[case(NetworkMatch::Exact, "10.0.0.0/16", "10.0.0.0/8", false)]
+    #[case(NetworkMatch::LessSpecific, "10.0.0.1", "10.0.0.0/8", true)]
+    fn GIVEN_candidate_WHEN_is_match_up_THEN_correct(
+        #[case] network_match: NetworkMatch,
+        #[case] query: &str,
+        #[case] candidate: &str,
+        #[case] expected: bool,
+    ) {
+        // GIVEN
+        let query_type = QueryType::from_str(query).expect("query type");
+        let candidate = network(candidate);
+
+        // WHEN
+        let actual = network_match.is_match_up(&query_type, &candidate);
+
+        // THEN
+        assert_eq!(actual, expected);
+    }
+}
@@ -0,0 +1,176 @@
+//! Paged search results (RFC 8977).
+
+use icann_rdap_common::response::RdapResponse;
+
+use crate::{http::Client, RdapClientError};
+
+use super::request::{rdap_url_request, ResponseData};
+
+/// Appends the results of a page of search results to search results.
+///
+/// The paging metadata of the page replaces that of the search results, so that
+/// the `next` link refers to the page that follows. If the page is not search results
+/// of the same kind, the search results are left as is and false is returned.
+pub fn merge_page(results: &mut RdapResponse, page: RdapResponse) -> bool {
+    match (results, page) {
+        (RdapResponse::DomainSearchResults(results), RdapResponse::DomainSearchResults(page)) => {
+            results.results.extend(page.results);
+            results.paging_metadata = page.paging_metadata;
+        }
+        (
+            RdapResponse::NameserverSearchResults(results),
+            RdapResponse::NameserverSearchResults(page),
+        ) => {
+            results.results.extend(page.results);
+            results.paging_metadata = page.paging_metadata;
+        }
+        (RdapResponse::EntitySearchResults(results), RdapResponse::EntitySearchResults(page)) => {
+            results.results.extend(page.results);
+            results.paging_metadata = page.paging_metadata;
+        }
+        (RdapResponse::IpSearchResults(results), RdapResponse::IpSearchResults(page)) => {
+            results.results.extend(page.results);
+            results.paging_metadata = page.paging_metadata;
+        }
+        (RdapResponse::AutnumSearchResults(results), RdapResponse::AutnumSearchResults(page)) => {
+            results.results.extend(page.results);
+            results.paging_metadata = page.paging_metadata;
+        }
+        _ => return false,
+    };
+    true
+}
+
+/// Makes an RDAP request with a full RDAP URL, fetching subsequent pages of search results.
+///
+/// The pages are fetched by following the `next` links of the paging metadata and
+/// are merged with [merge_page]. No more than `max_pages` pages are fetched, including
+/// the first, and a `max_pages` of 0 fetches all pages.
+///
+/// ```no_run
+/// use icann_rdap_client::prelude::*;
+/// use tokio::main;
+///
+/// #[tokio::main]
+/// async fn main() -> Result<(), RdapClientError> {
+///
+///     // create a client (from icann-rdap-common)
+///     let config = ClientConfig::default();
+///     let client = create_client(&config)?;
+///
+///     // issue the RDAP query, fetching no more than 5 pages
+///     let response =
+///         rdap_url_paged_request(
+///             "https://rdap.example.com/rdap/domains?name=foo*.example",
+///             &client,
+///             5,
+///     ).await?;
+///
+///     Ok(())
+/// }
+/// ```
+pub async fn rdap_url_paged_request(
+    url: &str,
+    client: &Client,
+    max_pages: usize,
+) -> Result<ResponseData, RdapClientError> {
+    let mut response = rdap_url_request(url, client).await?;
+    let mut fetched = vec![url.to_string()];
+    while max_pages == 0 || fetched.len() < max_pages {
+        let Some(next) = response
+            .rdap
+            .get_paging_metadata()
+            .and_then(|paging| paging.next_link())
+        else {
+            break;
+        };
+        if fetched.iter().any(|url| url == next) {
+            break;
+        }
+        let next = next.to_string();
+        let page = rdap_url_request(&next, client).await?;
+        if !merge_page(&mut response.rdap, page.rdap) {
+            break;
+        }
+        fetched.push(next);
+    }
+    Ok(response)
+}
+
+#[cfg(test)]
+#[allow(non_snake_case)]
+mod tests {
+    use icann_rdap_common::{
+        prelude::ToResponse,
+        response::{Domain, DomainSearchResults, Link, PagingMetadata, RdapResponse},
+    };
+
+    use super::merge_page;
+
+    fn page(names: &[&str], next: Option<&str>) -> RdapResponse {
+        let links = next
+            .map(|next| {
+                vec![Link::builder()
+                    .value("https://example.com/rdap/domains?name=*.example")
+                    .rel("next")
+                    .href(next)
+                    .build()]
+            })
+            .unwrap_or_default();
+        DomainSearchResults::response_obj()
+            .results(
+                names
+                    .iter()
+                    .map(|name| Domain::builder().ldh_name(*name).build())
+                    .collect::<Vec<Domain>>(),
+            )
+            .paging_metadata(PagingMetadata::builder().links(links).build())
+            .build()
+            .to_response()
+    }
+
+    #[test]
+    fn GIVEN_domain_search_results_WHEN_merge_page_THEN_results_appended() {
+        // GIVEN
+        let mut results = page(&["a.example", "b.example"], Some("https://example.com/2"));
+        let next = page(&["c.example"], None);
+
+        // WHEN
+        let merged = merge_page(&mut results, next);
+
+        // THEN
+        assert!(merged);
+        let RdapResponse::DomainSearchResults(results) = results else {
+            panic!("not domain search results")
+        };
+        assert_eq!(results.results().len(), 3);
+        assert_eq!(results.results()[2].ldh_name.as_deref(), Some("c.example"));
+        assert!(results
+            .paging_metadata()
+            .expect("no paging metadata")
+            .next_link()
+            .is_none());
+    }
+
+    #[test]
+    fn GIVEN_different_responses_WHEN_merge_page_THEN_not_merged() {
+        // GIVEN
+        let mut results = page(&["a.example"], Some("https://example.com/2"));
+        let next = Domain::builder()
+            .ldh_name("c.example")
+            .build()
+            .to_response();
+
+        // WHEN
+        let merged = merge_page(&mut results, next);
+
+        // THEN
+        assert!(!merged);
+        assert_eq!(
+            results
+                .get_paging_metadata()
+                .and_then(|paging| paging.next_link()),
+            Some("https://example.com/2")
+        );
+    }
+}
@@ -7,7 +7,11 @@ use std::{
 
 use {
     cidr::{IpCidr, Ipv4Cidr, Ipv6Cidr},
-    icann_rdap_common::{check::StringCheck, dns_types::DomainName},
+    icann_rdap_common::{
+        check::StringCheck,
+        dns_types::DomainName,
+        response::{ReverseSearchProperty, RirSearchRelation},
+    },
     pct_str::{PctString, URIReserved},
     regex::Regex,
     strum_macros::Display,
@@ -42,6 +46,11 @@ pub enum QueryType {
     #[strum(serialize = "Entity Lookup")]
     Entity(String),
 
+    /// An entity handle ending with an RFC 8521 object tag, such as "FOO-ARIN",
+    /// which is bootstrapped with the IANA object tags registry.
+    #[strum(serialize = "Tagged Entity Lookup")]
+    TaggedEntity(String),
+
     #[strum(serialize = "Nameserver Lookup")]
     Nameserver(DomainName),
 
@@ -66,6 +75,36 @@ pub enum QueryType {
     #[strum(serialize = "Nameserver IP Address Search")]
     NameserverIpSearch(IpAddr),
 
+    #[strum(serialize = "Domain Reverse Search")]
+    DomainReverseSearch(ReverseSearchProperty, String),
+
+    #[strum(serialize = "Nameserver Reverse Search")]
+    NameserverReverseSearch(ReverseSearchProperty, String),
+
+    #[strum(serialize = "Entity Reverse Search")]
+    EntityReverseSearch(ReverseSearchProperty, String),
+
+    #[strum(serialize = "IP Network Reverse Search")]
+    IpReverseSearch(ReverseSearchProperty, String),
+
+    #[strum(serialize = "Autonomous System Number Reverse Search")]
+    AutnumReverseSearch(ReverseSearchProperty, String),
+
+    #[strum(serialize = "IP Network Name Search")]
+    IpNameSearch(String),
+
+    #[strum(serialize = "IP Network Handle Search")]
+    IpHandleSearch(String),
+
+    #[strum(serialize = "Autonomous System Number Name Search")]
+    AutnumNameSearch(String),
+
+    #[strum(serialize = "Autonomous System Number Handle Search")]
+    AutnumHandleSearch(String),
+
+    #[strum(serialize = "IP Network Relation Search")]
+    IpRelationSearch(RirSearchRelation, IpCidr),
+
     #[strum(serialize = "Server Help Lookup")]
     Help,
 
@@ -107,7 +146,7 @@ impl QueryType {
                 "{base_url}/domain/{}",
                 PctString::encode(value.to_ascii().chars(), URIReserved),
             )),
-            Self::Entity(value) => Ok(format!(
+            Self::Entity(value) | Self::TaggedEntity(value) => Ok(format!(
                 "{base_url}/entity/{}",
                 PctString::encode(value.chars(), URIReserved)
             )),
@@ -126,6 +165,30 @@ impl QueryType {
             Self::NameserverIpSearch(value) => {
                 search_query(&value.to_string(), "nameservers?ip", base_url)
             }
+            Self::DomainReverseSearch(property, value) => {
+                reverse_search_query(value, "domains", property, base_url)
+            }
+            Self::NameserverReverseSearch(property, value) => {
+                reverse_search_query(value, "nameservers", property, base_url)
+            }
+            Self::EntityReverseSearch(property, value) => {
+                reverse_search_query(value, "entities", property, base_url)
+            }
+            Self::IpReverseSearch(property, value) => {
+                reverse_search_query(value, "ips", property, base_url)
+            }
+            Self::AutnumReverseSearch(property, value) => {
+                reverse_search_query(value, "autnums", property, base_url)
+            }
+            Self::IpNameSearch(value) => search_query(value, "ips?name", base_url),
+            Self::IpHandleSearch(value) => search_query(value, "ips?handle", base_url),
+            Self::AutnumNameSearch(value) => search_query(value, "autnums?name", base_url),
+            Self::AutnumHandleSearch(value) => search_query(value, "autnums?handle", base_url),
+            Self::IpRelationSearch(relation, value) => Ok(format!(
+                "{base_url}/ips/rirSearch1/{relation}/{}/{}",
+                PctString::encode(value.first_address().to_string().chars(), URIReserved),
+                PctString::encode(value.network_length().to_string().chars(), URIReserved)
+            )),
             Self::Help => Ok(format!("{base_url}/help")),
             Self::Url(url) => Ok(url.to_owned()),
         }
@@ -139,15 +202,35 @@ impl QueryType {
         Ok(Self::ALabel(DomainName::from_str(alabel)?))
     }
 
+    /// Creates an entity lookup, which is a [QueryType::TaggedEntity] if the handle
+    /// ends with an object tag.
+    pub fn entity(handle: &str) -> Self {
+        if object_tag(handle).is_some() {
+            Self::TaggedEntity(handle.to_owned())
+        } else {
+            Self::Entity(handle.to_owned())
+        }
+    }
+
+    /// Gets the RFC 8521 object tag of an entity lookup, such as "ARIN" of "FOO-ARIN".
+    pub fn object_tag(&self) -> Option<&str> {
+        match self {
+            Self::Entity(handle) | Self::TaggedEntity(handle) => object_tag(handle),
+            _ => None,
+        }
+    }
+
     pub fn ns(nameserver: &str) -> Result<Self, RdapClientError> {
         Ok(Self::Nameserver(DomainName::from_str(nameserver)?))
     }
 
+    /// Creates an autnum query from an AS number in either asplain (e.g. "AS65546")
+    /// or asdot (e.g. "AS1.10") notation.
     pub fn autnum(autnum: &str) -> Result<Self, RdapClientError> {
-        let value = autnum
-            .trim_start_matches(|c| -> bool { matches!(c, 'a' | 'A' | 's' | 'S') })
-            .parse::<u32>()
-            .map_err(|_e| RdapClientError::InvalidQueryValue)?;
+        let value = parse_asn(
+            autnum.trim_start_matches(|c| -> bool { matches!(c, 'a' | 'A' | 's' | 'S') }),
+        )
+        .ok_or(RdapClientError::InvalidQueryValue)?;
         Ok(Self::AsNumber(value))
     }
 
@@ -196,6 +279,18 @@ impl QueryType {
         let value = IpAddr::from_str(ip).map_err(|_e| RdapClientError::InvalidQueryValue)?;
         Ok(Self::NameserverIpSearch(value))
     }
+
+    /// Creates an RIR search (RFC 9910) for the networks related to an IP address or CIDR.
+    pub fn ip_relation_search(
+        relation: RirSearchRelation,
+        ip: &str,
+    ) -> Result<Self, RdapClientError> {
+        let value = match IpAddr::from_str(ip) {
+            Ok(addr) => IpCidr::new_host(addr),
+            Err(_) => parse_cidr(ip)?,
+        };
+        Ok(Self::IpRelationSearch(relation, value))
+    }
 }
 
 fn search_query(value: &str, path_query: &str, base_url: &str) -> Result<String, RdapClientError> {
@@ -205,6 +300,20 @@ fn search_query(value: &str, path_query: &str, base_url: &str) -> Result<String,
     ))
 }
 
+/// Creates an RFC 9536 reverse search URL, where the related resource type is always "entity".
+fn reverse_search_query(
+    value: &str,
+    searchable_type: &str,
+    property: &ReverseSearchProperty,
+    base_url: &str,
+) -> Result<String, RdapClientError> {
+    search_query(
+        value,
+        &format!("{searchable_type}/reverse_search/entity?{property}"),
+        base_url,
+    )
+}
+
 impl FromStr for QueryType {
     type Err = RdapClientError;
 
@@ -220,6 +329,15 @@ impl FromStr for QueryType {
             return Self::autnum(s);
         }
 
+        // if looks like an asdot autnum, which must have the "AS" prefix so as not to be a domain name
+        let asdot = s
+            .get(..2)
+            .filter(|prefix| prefix.eq_ignore_ascii_case("as"))
+            .and_then(|_| s.get(2..));
+        if asdot.and_then(parse_asn).is_some() {
+            return Self::autnum(s);
+        }
+
         // If it's an IP address
         if let Ok(ip_addr) = IpAddr::from_str(s) {
             if ip_addr.is_ipv4() {
@@ -248,7 +366,7 @@ impl FromStr for QueryType {
 
         // if it is just one word
         if !s.contains(|c: char| c.is_whitespace() || matches!(c, '.' | ',' | '"')) {
-            return Ok(Self::Entity(s.to_owned()));
+            return Ok(Self::entity(s));
         }
 
         // The query type cannot be deteremined.
@@ -256,6 +374,18 @@ impl FromStr for QueryType {
     }
 }
 
+/// Parses an AS number in asplain or asdot notation (RFC 5396) into asplain.
+fn parse_asn(s: &str) -> Option<u32> {
+    match s.split_once('.') {
+        Some((high, low)) => {
+            let high = high.parse::<u16>().ok()?;
+            let low = low.parse::<u16>().ok()?;
+            Some((u32::from(high) << 16) | u32::from(low))
+        }
+        None => s.parse::<u32>().ok(),
+    }
+}
+
 fn parse_cidr(s: &str) -> Result<IpCidr, RdapClientError> {
     let Some((prefix, suffix)) = s.split_once('/') else {
         return Err(RdapClientError::InvalidQueryValue);
@@ -276,6 +406,17 @@ fn parse_cidr(s: &str) -> Result<IpCidr, RdapClientError> {
     }
 }
 
+/// Gets the object tag of an entity handle, which follows the last hyphen and
+/// is alphanumeric (RFC 8521).
+fn object_tag(handle: &str) -> Option<&str> {
+    handle
+        .rsplit_once('-')
+        .filter(|(id, tag)| {
+            !id.is_empty() && !tag.is_empty() && tag.chars().all(|c| c.is_ascii_alphanumeric())
+        })
+        .map(|(_, tag)| tag)
+}
+
 fn is_ldh_domain(text: &str) -> bool {
     static LDH_DOMAIN_RE: LazyLock<Regex> =
         LazyLock::new(|| Regex::new(r"^(?i)(\.?[a-zA-Z0-9-]+)*\.[a-zA-Z0-9-]+\.?$").unwrap());
@@ -416,6 +557,36 @@ mod tests {
         assert!(matches!(q, QueryType::Entity(_)))
     }
 
+    #[rstest]
+    #[case("FOO-ARIN", Some("ARIN"))]
+    #[case("foo-bar-RIPE", Some("RIPE"))]
+    #[case("foo", None)]
+    #[case("foo-", None)]
+    #[case("-ARIN", None)]
+    #[case("foo-A_B", None)]
+    fn test_tagged_entity_query_type_from_str(#[case] input: &str, #[case] expected: Option<&str>) {
+        // GIVEN case input
+
+        // WHEN
+        let q = QueryType::from_str(input).unwrap();
+
+        // THEN
+        assert_eq!(q.object_tag(), expected);
+        assert_eq!(matches!(q, QueryType::TaggedEntity(_)), expected.is_some());
+    }
+
+    #[test]
+    fn test_tagged_entity_query_url() {
+        // GIVEN
+        let q = QueryType::entity("FOO-ARIN");
+
+        // WHEN
+        let actual = q.query_url("https://example.com").unwrap();
+
+        // THEN
+        assert_eq!(actual, "https://example.com/entity/FOO-ARIN");
+    }
+
     #[rstest]
     #[case("https://example.com")]
     #[case("http://foo.example.com")]
@@ -523,6 +694,45 @@ mod tests {
         assert_eq!(actual, "https://example.com/autnum/16509")
     }
 
+    #[rstest]
+    #[case("AS1.10", 65546)]
+    #[case("as0.100", 100)]
+    #[case("AS65535.65535", u32::MAX)]
+    #[case("AS65546", 65546)]
+    fn test_asdot_autnum_query_url(#[case] input: &str, #[case] expected: u32) {
+        // GIVEN
+        let q = QueryType::from_str(input).expect("query type");
+
+        // WHEN
+        let actual = q.query_url("https://example.com").expect("query url");
+
+        // THEN
+        assert_eq!(actual, format!("https://example.com/autnum/{expected}"))
+    }
+
+    #[rstest]
+    #[case("AS1.65536")]
+    #[case("AS1.")]
+    #[case("AS65536.1")]
+    fn test_invalid_asdot_autnum(#[case] input: &str) {
+        // GIVEN
+        // WHEN
+        let actual = QueryType::autnum(input);
+
+        // THEN
+        assert!(actual.is_err());
+    }
+
+    #[test]
+    fn test_asdot_without_prefix_is_not_autnum() {
+        // GIVEN
+        // WHEN
+        let q = QueryType::from_str("1.10").expect("query type");
+
+        // THEN
+        assert!(!matches!(q, QueryType::AsNumber(_)));
+    }
+
     #[test]
     fn test_domain_query_url() {
         // GIVEN
@@ -642,4 +852,93 @@ mod tests {
         // THEN
         assert_eq!(actual, "https://example.com/nameservers?ip=1.1.1.1")
     }
+
+    #[test]
+    fn test_domain_reverse_search_query_url() {
+        // GIVEN
+        let q = QueryType::DomainReverseSearch(ReverseSearchProperty::Fn, "Bobby*".to_string());
+
+        // WHEN
+        let actual = q.query_url("https://example.com").expect("query url");
+
+        // THEN
+        assert_eq!(
+            actual,
+            "https://example.com/domains/reverse_search/entity?fn=Bobby%2A"
+        )
+    }
+
+    #[test]
+    fn test_ip_reverse_search_query_url() {
+        // GIVEN
+        let q = QueryType::IpReverseSearch(ReverseSearchProperty::Handle, "XXXX".to_string());
+
+        // WHEN
+        let actual = q.query_url("https://example.com").expect("query url");
+
+        // THEN
+        assert_eq!(
+            actual,
+            "https://example.com/ips/reverse_search/entity?handle=XXXX"
+        )
+    }
+
+    #[test]
+    fn test_ip_name_search_query_url() {
+        // GIVEN
+        let q = QueryType::IpNameSearch("NET-EXAMPLE*".to_string());
+
+        // WHEN
+        let actual = q.query_url("https://example.com").expect("query url");
+
+        // THEN
+        assert_eq!(actual, "https://example.com/ips?name=NET-EXAMPLE%2A")
+    }
+
+    #[test]
+    fn test_autnum_handle_search_query_url() {
+        // GIVEN
+        let q = QueryType::AutnumHandleSearch("AS64496".to_string());
+
+        // WHEN
+        let actual = q.query_url("https://example.com").expect("query url");
+
+        // THEN
+        assert_eq!(actual, "https://example.com/autnums?handle=AS64496")
+    }
+
+    #[rstest]
+    #[case(RirSearchRelation::Up, "10.0.0.1", "up/10.0.0.1/32")]
+    #[case(RirSearchRelation::Down, "10.0.0.0/8", "down/10.0.0.0/8")]
+    #[case(RirSearchRelation::Top, "2001:db8::/32", "top/2001%3Adb8%3A%3A/32")]
+    #[case(RirSearchRelation::Bottom, "10/8", "bottom/10.0.0.0/8")]
+    fn test_ip_relation_search_query_url(
+        #[case] relation: RirSearchRelation,
+        #[case] ip: &str,
+        #[case] expected: &str,
+    ) {
+        // GIVEN
+        let q = QueryType::ip_relation_search(relation, ip).expect("relation search");
+
+        // WHEN
+        let actual = q.query_url("https://example.com").expect("query url");
+
+        // THEN
+        assert_eq!(
+            actual,
+            format!("https://example.com/ips/rirSearch1/{expected}")
+        )
+    }
+
+    #[test]
+    fn test_ip_relation_search_of_non_ip() {
+        // GIVEN
+        let ip = "example.com";
+
+        // WHEN
+        let q = QueryType::ip_relation_search(RirSearchRelation::Up, ip);
+
+        // THEN
+        assert!(q.is_err())
+    }
 }
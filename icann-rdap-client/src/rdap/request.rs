@@ -1,18 +1,29 @@
 //! Functions to make RDAP requests.
 
 use {
-    icann_rdap_common::{httpdata::HttpData, iana::IanaRegistryType, response::RdapResponse},
+    chrono::Utc,
+    icann_rdap_common::{
+        httpdata::HttpData,
+        iana::IanaRegistryType,
+        media_types::{is_rdap_media_type, is_rdap_x_media_type},
+        response::RdapResponse,
+    },
     serde::{Deserialize, Serialize},
     serde_json::Value,
+    tracing::debug,
 };
 
 use crate::{
     http::{wrapped_request, Client},
-    iana::bootstrap::{qtype_to_bootstrap_url, BootstrapStore},
+    iana::bootstrap::{qtype_to_bootstrap_trace, BootstrapStore},
     RdapClientError,
 };
 
-use super::qtype::QueryType;
+use super::{
+    cache::ResponseCache,
+    qtype::QueryType,
+    trace::{QueryTrace, RequestTrace},
+};
 
 /// Makes an RDAP request with a full RDAP URL.
 ///
@@ -42,12 +53,25 @@ use super::qtype::QueryType;
 ///     Ok(())
 /// }
 /// ```
+#[tracing::instrument(level = "info", name = "rdap_request", skip(client))]
 pub async fn rdap_url_request(url: &str, client: &Client) -> Result<ResponseData, RdapClientError> {
     let wrapped_response = wrapped_request(url, client).await?;
     // for convenience purposes
     let text = wrapped_response.text;
     let http_data = wrapped_response.http_data;
 
+    // application/json is otherwise accepted, and is noted by the checks of the HTTP data
+    if client.request_options.strict_media_types
+        && !http_data
+            .content_type()
+            .is_some_and(|ct| is_rdap_media_type(ct) || is_rdap_x_media_type(ct))
+    {
+        return Err(RdapClientError::InvalidMediaType {
+            media_type: http_data.content_type,
+            url: http_data.request_uri,
+        });
+    }
+
     let json: Result<Value, serde_json::Error> = serde_json::from_str(&text);
     if let Ok(rdap_json) = json {
         let rdap = RdapResponse::try_from(rdap_json)?;
@@ -67,6 +91,49 @@ pub async fn rdap_url_request(url: &str, client: &Client) -> Result<ResponseData
     }
 }
 
+/// Makes an RDAP request with a full RDAP URL, answering it from a cache if able.
+///
+/// This function takes the following parameters:
+/// * url - a string reference of the URL
+/// * client - a reference to a [reqwest::Client].
+/// * cache - the [ResponseCache] the response is taken from, or put into if not there.
+///
+/// ```no_run
+/// use icann_rdap_client::prelude::*;
+/// use tokio::main;
+///
+/// #[tokio::main]
+/// async fn main() -> Result<(), RdapClientError> {
+///
+///     // create a client and a cache to be kept for many requests
+///     let client = create_client(&ClientConfig::default())?;
+///     let cache = MemoryResponseCache::bounded(3600, 1000);
+///
+///     // issue the RDAP query
+///     let response =
+///         rdap_cached_request(
+///             "https://rdap-bootstrap.arin.net/bootstrap/ip/192.168.0.1",
+///             &client,
+///             &cache,
+///     ).await?;
+///
+///     Ok(())
+/// }
+/// ```
+pub async fn rdap_cached_request(
+    url: &str,
+    client: &Client,
+    cache: &dyn ResponseCache,
+) -> Result<ResponseData, RdapClientError> {
+    if let Some(response) = cache.get_response(url)? {
+        debug!("returning response from cache for {url}");
+        return Ok(response);
+    }
+    let response = rdap_url_request(url, client).await?;
+    cache.put_response(url, &response)?;
+    Ok(response)
+}
+
 /// Makes an RDAP request with a base URL.
 ///
 /// This function takes the following parameters:
@@ -164,8 +231,69 @@ pub async fn rdap_bootstrapped_request<F>(
 where
     F: FnOnce(&IanaRegistryType),
 {
-    let base_url = qtype_to_bootstrap_url(client, store, query_type, callback).await?;
-    rdap_request(&base_url, query_type, client).await
+    rdap_traced_bootstrapped_request(
+        query_type,
+        client,
+        store,
+        callback,
+        &mut QueryTrace::default(),
+    )
+    .await
+}
+
+/// Makes an RDAP request using bootstrapping, accumulating a [QueryTrace] of how it was made.
+///
+/// This is [rdap_bootstrapped_request()], with the trace recording the bootstrap registry
+/// consulted, the base URLs it gives for the query and the one chosen, and the request made
+/// with its redirects, retries and timing. The trace is accumulated even if the request fails.
+///
+/// ```no_run
+/// use icann_rdap_client::prelude::*;
+/// use std::str::FromStr;
+/// use tokio::main;
+///
+/// #[tokio::main]
+/// async fn main() -> Result<(), RdapClientError> {
+///
+///     let query = QueryType::from_str("icann.org")?;
+///     let client = create_client(&ClientConfig::default())?;
+///     let store = MemoryBootstrapStore::new();
+///
+///     // issue the RDAP query
+///     let mut trace = QueryTrace::default();
+///     let response =
+///         rdap_traced_bootstrapped_request(
+///             &query,
+///             &client,
+///             &store,
+///             |reg| eprintln!("fetching {reg:?}"),
+///             &mut trace,
+///     ).await;
+///     eprintln!("{}", serde_json::to_string_pretty(&trace).unwrap());
+///
+///     Ok(())
+/// }
+/// ```
+pub async fn rdap_traced_bootstrapped_request<F>(
+    query_type: &QueryType,
+    client: &Client,
+    store: &dyn BootstrapStore,
+    callback: F,
+    trace: &mut QueryTrace,
+) -> Result<ResponseData, RdapClientError>
+where
+    F: FnOnce(&IanaRegistryType),
+{
+    trace.query_type = Some(query_type.to_string());
+    let bootstrap = qtype_to_bootstrap_trace(client, store, query_type, callback).await?;
+    let url = query_type.query_url(&bootstrap.chosen_url)?;
+    trace.bootstrap = Some(bootstrap);
+    let started = Utc::now();
+    let response = rdap_url_request(&url, client).await;
+    trace
+        .requests
+        .push(RequestTrace::new(&url, started, &response));
+    response
 }
 
 /// The data returned from an rdap request.
@@ -0,0 +1,252 @@
+//! Coalescing of concurrent requests for the same URL.
+
+use std::{
+    collections::HashMap,
+    future::Future,
+    sync::{Arc, Mutex},
+};
+
+use {
+    futures::channel::oneshot::{channel, Sender},
+    tracing::debug,
+};
+
+use crate::RdapClientError;
+
+use super::ResponseData;
+
+type SharedResult = Result<ResponseData, Arc<RdapClientError>>;
+
+/// The requests in flight, by URL, with the callers waiting on each of them.
+///
+/// The first caller for a URL makes the request, and callers for the same URL
+/// arriving before it is done wait for its result instead of making their own.
+#[derive(Debug, Default)]
+pub(crate) struct InFlight {
+    requests: Mutex<HashMap<String, Vec<Sender<SharedResult>>>>,
+}
+
+impl InFlight {
+    /// Makes the request for the URL, unless the same request is already in flight,
+    /// in which case its result is shared.
+    ///
+    /// Errors of a request shared by several callers are [RdapClientError::SharedRequest].
+    pub(crate) async fn request<F, Fut>(
+        &self,
+        url: &str,
+        request: F,
+    ) -> Result<ResponseData, RdapClientError>
+    where
+        F: FnOnce() -> Fut,
+        Fut: Future<Output = Result<ResponseData, RdapClientError>>,
+    {
+        let waiting = {
+            let mut requests = self.requests.lock()?;
+            if let Some(waiters) = requests.get_mut(url) {
+                let (sender, receiver) = channel();
+                waiters.push(sender);
+                Some(receiver)
+            } else {
+                requests.insert(url.to_string(), vec![]);
+                None
+            }
+        };
+        if let Some(receiver) = waiting {
+            debug!("waiting on request in flight for {url}");
+            match receiver.await {
+                Ok(result) => return result.map_err(RdapClientError::SharedRequest),
+                // the request in flight was cancelled, so this caller makes its own.
+                Err(_) => return request().await,
+            }
+        }
+
+        let leader = Leader {
+            in_flight: self,
+            url,
+            finished: false,
+        };
+        let result = request().await;
+        let waiters = leader.finish();
+        if waiters.is_empty() {
+            return result;
+        }
+        debug!("sharing response for {url} with {} waiters", waiters.len());
+        match result {
+            Ok(response) => {
+                for waiter in waiters {
+                    let _ = waiter.send(Ok(response.clone()));
+                }
+                Ok(response)
+            }
+            Err(e) => {
+                let e = Arc::new(e);
+                for waiter in waiters {
+                    let _ = waiter.send(Err(e.clone()));
+                }
+                Err(RdapClientError::SharedRequest(e))
+            }
+        }
+    }
+
+    fn remove(&self, url: &str) -> Vec<Sender<SharedResult>> {
+        self.requests
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .remove(url)
+            .unwrap_or_default()
+    }
+}
+
+/// The caller making a request, which takes the request out of flight when done
+/// or when cancelled.
+struct Leader<'a> {
+    in_flight: &'a InFlight,
+    url: &'a str,
+    finished: bool,
+}
+
+impl Leader<'_> {
+    fn finish(mut self) -> Vec<Sender<SharedResult>> {
+        self.finished = true;
+        self.in_flight.remove(self.url)
+    }
+}
+
+impl Drop for Leader<'_> {
+    fn drop(&mut self) {
+        if !self.finished {
+            // dropping the senders lets the waiters know to make their own requests.
+            self.in_flight.remove(self.url);
+        }
+    }
+}
+
+#[cfg(test)]
+#[allow(non_snake_case)]
+mod tests {
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    use {
+        icann_rdap_common::{httpdata::HttpData, prelude::ToResponse, response::Domain},
+        tokio::sync::Notify,
+    };
+
+    use crate::{rdap::ResponseData, RdapClientError};
+
+    use super::InFlight;
+
+    fn response_data() -> ResponseData {
+        let rdap = Domain::builder()
+            .ldh_name("foo.example")
+            .build()
+            .to_response();
+        ResponseData {
+            rdap_type: rdap.to_string(),
+            rdap,
+            http_data: HttpData::example().build(),
+        }
+    }
+
+    #[tokio::test]
+    async fn GIVEN_concurrent_requests_for_same_url_WHEN_request_THEN_one_request_made() {
+        // GIVEN
+        let in_flight = InFlight::default();
+        let requests = AtomicUsize::new(0);
+        let release = Notify::new();
+        let request = || async {
+            requests.fetch_add(1, Ordering::SeqCst);
+            release.notified().await;
+            Ok(response_data())
+        };
+
+        // WHEN
+        let (first, second, _) = tokio::join!(
+            in_flight.request("https://rdap.example/domain/foo.example", request),
+            in_flight.request("https://rdap.example/domain/foo.example", request),
+            async {
+                tokio::task::yield_now().await;
+                release.notify_one();
+            }
+        );
+
+        // THEN
+        assert!(first.is_ok());
+        assert!(second.is_ok());
+        assert_eq!(requests.load(Ordering::SeqCst), 1);
+    }
+
+    #[tokio::test]
+    async fn GIVEN_concurrent_requests_for_different_urls_WHEN_request_THEN_each_made() {
+        // GIVEN
+        let in_flight = InFlight::default();
+        let requests = AtomicUsize::new(0);
+        let request = || async {
+            requests.fetch_add(1, Ordering::SeqCst);
+            tokio::task::yield_now().await;
+            Ok(response_data())
+        };
+
+        // WHEN
+        let (first, second) = tokio::join!(
+            in_flight.request("https://rdap.example/domain/foo.example", request),
+            in_flight.request("https://rdap.example/domain/bar.example", request),
+        );
+
+        // THEN
+        assert!(first.is_ok());
+        assert!(second.is_ok());
+        assert_eq!(requests.load(Ordering::SeqCst), 2);
+    }
+
+    #[tokio::test]
+    async fn GIVEN_shared_request_fails_WHEN_request_THEN_error_shared() {
+        // GIVEN
+        let in_flight = InFlight::default();
+        let release = Notify::new();
+        let request = || async {
+            release.notified().await;
+            Err(RdapClientError::BootstrapUnavailable)
+        };
+
+        // WHEN
+        let (first, second, _) = tokio::join!(
+            in_flight.request("https://rdap.example/domain/foo.example", request),
+            in_flight.request("https://rdap.example/domain/foo.example", request),
+            async {
+                tokio::task::yield_now().await;
+                release.notify_one();
+            }
+        );
+
+        // THEN
+        let (Err(first), Err(second)) = (first, second) else {
+            panic!("expected errors");
+        };
+        assert_eq!(first.code(), RdapClientError::BootstrapUnavailable.code());
+        assert_eq!(second.code(), RdapClientError::BootstrapUnavailable.code());
+    }
+
+    #[tokio::test]
+    async fn GIVEN_request_done_WHEN_request_again_THEN_new_request_made() {
+        // GIVEN
+        let in_flight = InFlight::default();
+        let requests = AtomicUsize::new(0);
+        let request = || async {
+            requests.fetch_add(1, Ordering::SeqCst);
+            Ok(response_data())
+        };
+        in_flight
+            .request("https://rdap.example/domain/foo.example", request)
+            .await
+            .expect("first request");
+
+        // WHEN
+        in_flight
+            .request("https://rdap.example/domain/foo.example", request)
+            .await
+            .expect("second request");
+
+        // THEN
+        assert_eq!(requests.load(Ordering::SeqCst), 2);
+    }
+}
@@ -0,0 +1,180 @@
+//! Traces of how RDAP queries are made.
+
+use {
+    chrono::{DateTime, Utc},
+    icann_rdap_common::{httpdata::Redirect, iana::IanaRegistryType},
+    serde::{Deserialize, Serialize},
+};
+
+use crate::RdapClientError;
+
+use super::request::ResponseData;
+
+/// A trace of how a query was made, for finding out why a query went to a server.
+///
+/// The trace is accumulated as the query is made, such as by
+/// [crate::rdap::rdap_traced_bootstrapped_request()].
+#[derive(Serialize, Deserialize, Clone, Debug, Default)]
+pub struct QueryTrace {
+    /// The type of the query, such as "Domain Lookup".
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub query_type: Option<String>,
+
+    /// The bootstrapping of the query, if it was bootstrapped.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub bootstrap: Option<BootstrapTrace>,
+
+    /// The requests made, in the order made.
+    pub requests: Vec<RequestTrace>,
+}
+
+/// The bootstrapping of a query to the base URL of a server.
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct BootstrapTrace {
+    /// The IANA bootstrap registry consulted.
+    pub registry: IanaRegistryType,
+
+    /// The URL of the IANA bootstrap registry.
+    pub registry_url: String,
+
+    /// True if the registry was fetched or revalidated from IANA, rather than taken from the store.
+    pub fetched: bool,
+
+    /// The base URLs of the servers given by the registry for the query.
+    pub candidate_urls: Vec<String>,
+
+    /// The base URL chosen from the candidates, which prefers HTTPS.
+    pub chosen_url: String,
+
+    /// When bootstrapping started.
+    pub started: DateTime<Utc>,
+
+    /// The milliseconds taken to bootstrap, including fetching the registry.
+    pub duration_ms: i64,
+}
+
+/// A request made for a query.
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct RequestTrace {
+    /// The URL requested.
+    pub url: String,
+
+    /// The HTTP status code of the final response, if there was one.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub status_code: Option<u16>,
+
+    /// The redirects followed, in the order followed.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub redirects: Vec<Redirect>,
+
+    /// The number of times the request was retried.
+    pub retries: u16,
+
+    /// The error of the request, if it failed.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+
+    /// When the request started.
+    pub started: DateTime<Utc>,
+
+    /// The milliseconds taken by the request, including redirects and retries.
+    pub duration_ms: i64,
+}
+
+impl RequestTrace {
+    /// Creates the trace of a request to the URL that started at the given time and
+    /// has just completed with the result.
+    pub fn new(
+        url: &str,
+        started: DateTime<Utc>,
+        result: &Result<ResponseData, RdapClientError>,
+    ) -> Self {
+        let http_data = match result {
+            Ok(response) => Some(&response.http_data),
+            Err(RdapClientError::RetriesExhausted(e)) => Some(e.http_data.as_ref()),
+            Err(RdapClientError::ParsingError(e)) => Some(&e.http_data),
+            Err(_) => None,
+        };
+        Self {
+            url: url.to_string(),
+            status_code: match result {
+                Err(RdapClientError::Client(e)) => e.status().map(|status| status.as_u16()),
+                _ => http_data.map(|http_data| http_data.status_code()),
+            },
+            redirects: http_data
+                .map(|http_data| http_data.redirects().to_vec())
+                .unwrap_or_default(),
+            retries: http_data
+                .and_then(|http_data| http_data.retries())
+                .unwrap_or_default(),
+            error: result.as_ref().err().map(|e| e.to_string()),
+            started,
+            duration_ms: (Utc::now() - started).num_milliseconds(),
+        }
+    }
+}
+
+#[cfg(test)]
+#[allow(non_snake_case)]
+mod tests {
+    use {
+        chrono::Utc,
+        icann_rdap_common::{httpdata::HttpData, prelude::Domain, response::RdapResponse},
+    };
+
+    use crate::{rdap::ResponseData, RdapClientError};
+
+    use super::{QueryTrace, RequestTrace};
+
+    #[test]
+    fn GIVEN_response_WHEN_new_request_trace_THEN_status_and_retries() {
+        // GIVEN
+        let http_data = HttpData::example().status_code(200).retries(2).build();
+        let response = ResponseData {
+            rdap: RdapResponse::Domain(Box::new(Domain::builder().ldh_name("foo.example").build())),
+            rdap_type: "Domain".to_string(),
+            http_data,
+        };
+
+        // WHEN
+        let actual = RequestTrace::new(
+            "https://rdap.example.com/rdap/domain/foo.example",
+            Utc::now(),
+            &Ok(response),
+        );
+
+        // THEN
+        assert_eq!(actual.status_code, Some(200));
+        assert_eq!(actual.retries, 2);
+        assert!(actual.error.is_none());
+    }
+
+    #[test]
+    fn GIVEN_error_WHEN_new_request_trace_THEN_error() {
+        // GIVEN
+        let result = Err(RdapClientError::BootstrapUnavailable);
+
+        // WHEN
+        let actual = RequestTrace::new(
+            "https://rdap.example.com/rdap/domain/foo.example",
+            Utc::now(),
+            &result,
+        );
+
+        // THEN
+        assert!(actual.status_code.is_none());
+        assert!(actual.error.is_some());
+    }
+
+    #[test]
+    fn GIVEN_empty_trace_WHEN_serialize_THEN_only_requests() {
+        // GIVEN
+        let trace = QueryTrace::default();
+
+        // WHEN
+        let actual = serde_json::to_string(&trace).expect("serializing trace");
+
+        // THEN
+        assert_eq!(actual, r#"{"requests":[]}"#);
+    }
+}
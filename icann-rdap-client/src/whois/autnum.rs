@@ -0,0 +1,23 @@
+use icann_rdap_common::response::{Autnum, ObjectCommonFields};
+
+use super::{common_lines, entities_lines, line, opt_line, ToWhois};
+
+impl ToWhois for Autnum {
+    fn to_whois(&self) -> String {
+        let mut whois = String::new();
+        match (self.start_autnum(), self.end_autnum()) {
+            (Some(start), Some(end)) if start != end => {
+                line(&mut whois, "ASNumber", &format!("{start} - {end}"))
+            }
+            (Some(start), _) => line(&mut whois, "ASNumber", &start.to_string()),
+            _ => {}
+        }
+        opt_line(&mut whois, "ASName", self.name());
+        opt_line(&mut whois, "ASHandle", self.handle());
+        opt_line(&mut whois, "ASType", self.autnum_type());
+        opt_line(&mut whois, "Country", self.country());
+        common_lines(&mut whois, self);
+        entities_lines(&mut whois, self.entities());
+        whois
+    }
+}
@@ -0,0 +1,123 @@
+use icann_rdap_common::response::{Domain, Entity, ObjectCommonFields};
+
+use super::{contact_lines, event_date, is_fax, line, opt_line, role_label, ToWhois};
+
+/// The roles of the contacts of a domain, in the order of gTLD WHOIS.
+const CONTACT_ROLES: [&str; 4] = ["registrant", "administrative", "technical", "billing"];
+
+impl ToWhois for Domain {
+    fn to_whois(&self) -> String {
+        let mut whois = String::new();
+        opt_line(
+            &mut whois,
+            "Domain Name",
+            self.ldh_name().or(self.unicode_name()),
+        );
+        opt_line(&mut whois, "Registry Domain ID", self.handle());
+        opt_line(
+            &mut whois,
+            "Registrar WHOIS Server",
+            self.port_43().map(String::as_str),
+        );
+        let events = self.events();
+        opt_line(
+            &mut whois,
+            "Updated Date",
+            event_date(events, "last changed"),
+        );
+        opt_line(
+            &mut whois,
+            "Creation Date",
+            event_date(events, "registration"),
+        );
+        opt_line(
+            &mut whois,
+            "Registry Expiry Date",
+            event_date(events, "expiration"),
+        );
+
+        if let Some(registrar) = with_role(self.entities(), "registrar") {
+            registrar_lines(&mut whois, registrar);
+        }
+        for status in self.status() {
+            line(&mut whois, "Domain Status", status);
+        }
+        for role in CONTACT_ROLES {
+            if let (Some(entity), Some(label)) =
+                (with_role(self.entities(), role), role_label(role))
+            {
+                opt_line(&mut whois, &format!("Registry {label} ID"), entity.handle());
+                contact_lines(&mut whois, label, entity);
+            }
+        }
+        for nameserver in self.nameservers() {
+            opt_line(
+                &mut whois,
+                "Name Server",
+                nameserver.ldh_name().or(nameserver.unicode_name()),
+            );
+        }
+        let signed = self.secure_dns().is_some_and(|dns| dns.delegation_signed());
+        line(
+            &mut whois,
+            "DNSSEC",
+            if signed {
+                "signedDelegation"
+            } else {
+                "unsigned"
+            },
+        );
+        whois
+    }
+}
+
+/// Gets the first entity with a role.
+fn with_role<'a>(entities: &'a [Entity], role: &str) -> Option<&'a Entity> {
+    entities
+        .iter()
+        .find(|entity| entity.roles().iter().any(|r| r == role))
+}
+
+/// Adds the registrar, its IANA ID, and the email and phone of its abuse contact.
+fn registrar_lines(whois: &mut String, registrar: &Entity) {
+    let name = registrar.contact().and_then(|contact| {
+        contact
+            .full_name()
+            .or(contact.organizational_names().first().map(String::as_str))
+    });
+    opt_line(whois, "Registrar", name);
+    let iana_id = registrar
+        .public_ids()
+        .iter()
+        .find(|id| id.id_type() == Some("IANA Registrar ID"))
+        .and_then(|id| id.identifier());
+    opt_line(whois, "Registrar IANA ID", iana_id);
+    if let Some(abuse) = with_role(registrar.entities(), "abuse").and_then(Entity::contact) {
+        for email in abuse.emails() {
+            line(whois, "Registrar Abuse Contact Email", email.email());
+        }
+        for phone in abuse.phones().iter().filter(|phone| !is_fax(phone)) {
+            line(whois, "Registrar Abuse Contact Phone", phone.phone());
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use icann_rdap_common::response::Domain;
+
+    use crate::whois::ToWhois;
+
+    #[test]
+    fn test_ms_click_response() {
+        let expected_output =
+            std::fs::read_to_string("src/test_files/microsoft.click-expected.whois").unwrap();
+        let json = std::fs::read_to_string("src/test_files/microsoft.click.json").unwrap();
+        let domain = serde_json::from_str::<Domain>(&json).unwrap();
+
+        let actual = domain.to_whois();
+        eprintln!("--- ACTUAL ---{actual}--- ---");
+
+        assert_eq!(actual, expected_output);
+    }
+}
@@ -0,0 +1,17 @@
+use icann_rdap_common::response::{Entity, ObjectCommonFields};
+
+use super::{common_lines, contact_lines, entities_lines, line, opt_line, ToWhois};
+
+impl ToWhois for Entity {
+    fn to_whois(&self) -> String {
+        let mut whois = String::new();
+        opt_line(&mut whois, "Handle", self.handle());
+        contact_lines(&mut whois, "", self);
+        for role in self.roles() {
+            line(&mut whois, "Role", role);
+        }
+        common_lines(&mut whois, self);
+        entities_lines(&mut whois, self.entities());
+        whois
+    }
+}
@@ -0,0 +1,268 @@
+//! Converts RDAP structures to the flat key/value text of WHOIS.
+//!
+//! Domains are laid out as the WHOIS of gTLD registries, and the other objects
+//! as the WHOIS of Regional Internet Registries. Contacts are those of the
+//! entities of the objects, labeled by their roles, such as `Registrant Name`.
+
+use std::fmt::Write;
+
+use icann_rdap_common::{
+    contact::Phone,
+    response::{Entity, Event, ObjectCommonFields, RdapResponse},
+};
+
+pub mod autnum;
+pub mod domain;
+pub mod entity;
+pub mod nameserver;
+pub mod network;
+
+/// Converts an RDAP structure to WHOIS text.
+pub trait ToWhois {
+    fn to_whois(&self) -> String;
+}
+
+impl ToWhois for RdapResponse {
+    fn to_whois(&self) -> String {
+        match self {
+            Self::Entity(entity) => entity.to_whois(),
+            Self::Domain(domain) => domain.to_whois(),
+            Self::Nameserver(nameserver) => nameserver.to_whois(),
+            Self::Autnum(autnum) => autnum.to_whois(),
+            Self::Network(network) => network.to_whois(),
+            Self::DomainSearchResults(results) => join(results.results()),
+            Self::EntitySearchResults(results) => join(results.results()),
+            Self::NameserverSearchResults(results) => join(results.results()),
+            Self::AutnumSearchResults(results) => join(results.results()),
+            Self::IpSearchResults(results) => join(results.results()),
+            Self::ErrorResponse(_) | Self::Help(_) => String::new(),
+        }
+    }
+}
+
+/// Joins the WHOIS text of search results with blank lines.
+fn join(results: &[impl ToWhois]) -> String {
+    results
+        .iter()
+        .map(ToWhois::to_whois)
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// Gets the WHOIS label of a role, or `None` if the contacts of the role are not shown.
+pub(crate) fn role_label(role: &str) -> Option<&'static str> {
+    let label = match role {
+        "registrant" => "Registrant",
+        "technical" => "Tech",
+        "administrative" => "Admin",
+        "abuse" => "Abuse",
+        "billing" => "Billing",
+        "registrar" => "Registrar",
+        "reseller" => "Reseller",
+        "sponsor" => "Sponsor",
+        "proxy" => "Proxy",
+        "notifications" => "Notifications",
+        "noc" => "NOC",
+        _ => return None,
+    };
+    Some(label)
+}
+
+/// Adds a line of a key and value.
+pub(crate) fn line(whois: &mut String, key: &str, value: &str) {
+    let _ = writeln!(whois, "{key}: {value}");
+}
+
+/// Adds a line of a key and value, if there is a value.
+pub(crate) fn opt_line(whois: &mut String, key: &str, value: Option<&str>) {
+    if let Some(value) = value {
+        line(whois, key, value);
+    }
+}
+
+/// Gets the date of the first event with an action.
+pub(crate) fn event_date<'a>(events: &'a [Event], action: &str) -> Option<&'a str> {
+    events
+        .iter()
+        .find(|event| event.event_action() == Some(action))
+        .and_then(Event::event_date)
+}
+
+/// Adds the status and the registration, last changed and expiration dates of an object.
+pub(crate) fn common_lines(whois: &mut String, object: &impl ObjectCommonFields) {
+    for status in object.status() {
+        line(whois, "Status", status);
+    }
+    let events = object.events();
+    opt_line(
+        whois,
+        "Registration Date",
+        event_date(events, "registration"),
+    );
+    opt_line(whois, "Updated Date", event_date(events, "last changed"));
+    opt_line(whois, "Expiration Date", event_date(events, "expiration"));
+}
+
+/// Adds the contact of an entity, derived from its vCard or JSContact, with keys
+/// starting with a label, such as `Registrant`, or without one if it is empty.
+pub(crate) fn contact_lines(whois: &mut String, label: &str, entity: &Entity) {
+    let key = |field: &str| {
+        if label.is_empty() {
+            field.to_string()
+        } else {
+            format!("{label} {field}")
+        }
+    };
+    let Some(contact) = entity.contact() else {
+        return;
+    };
+    opt_line(whois, &key("Name"), contact.full_name());
+    for organization in contact.organizational_names() {
+        line(whois, &key("Organization"), organization);
+    }
+    for address in contact.postal_addresses() {
+        for street in address.street_parts() {
+            line(whois, &key("Street"), street);
+        }
+        opt_line(whois, &key("City"), address.locality());
+        opt_line(
+            whois,
+            &key("State/Province"),
+            address.region_name().or(address.region_code()),
+        );
+        opt_line(whois, &key("Postal Code"), address.postal_code());
+        opt_line(
+            whois,
+            &key("Country"),
+            address.country_code().or(address.country_name()),
+        );
+        if address.street_parts().is_empty() && address.locality().is_none() {
+            opt_line(whois, &key("Address"), address.full_address());
+        }
+    }
+    for phone in contact.phones() {
+        if is_fax(phone) {
+            line(whois, &key("Fax"), phone.phone());
+        } else {
+            line(whois, &key("Phone"), phone.phone());
+        }
+    }
+    for email in contact.emails() {
+        line(whois, &key("Email"), email.email());
+    }
+}
+
+/// Whether a phone is a facsimile, as typed in vCard or JSContact.
+pub(crate) fn is_fax(phone: &Phone) -> bool {
+    phone
+        .features()
+        .iter()
+        .chain(phone.contexts())
+        .any(|kind| kind == "fax")
+}
+
+/// Adds the contacts of the entities of an object having roles, labeled by role,
+/// as with the contacts of the networks of Regional Internet Registries.
+pub(crate) fn entities_lines(whois: &mut String, entities: &[Entity]) {
+    for entity in entities {
+        for label in entity.roles().iter().filter_map(|role| role_label(role)) {
+            opt_line(whois, &format!("{label} Handle"), entity.handle());
+            contact_lines(whois, label, entity);
+        }
+    }
+}
+
+#[cfg(test)]
+#[allow(non_snake_case)]
+mod tests {
+    use icann_rdap_common::{
+        contact::{Contact, Phone, PostalAddress},
+        prelude::ToResponse,
+        response::{Entity, IpSearchResults, Network},
+    };
+
+    use super::{contact_lines, ToWhois};
+
+    #[test]
+    fn GIVEN_entity_with_contact_WHEN_contact_lines_THEN_labeled_lines() {
+        // GIVEN
+        let contact = Contact::builder()
+            .full_name("Alice Example")
+            .organization_name("Example Inc.")
+            .postal_address(
+                PostalAddress::builder()
+                    .street_part("123 Main St")
+                    .locality("Springfield")
+                    .region_code("VA")
+                    .postal_code("22150")
+                    .country_code("US")
+                    .build(),
+            )
+            .phone(
+                Phone::builder()
+                    .phone("+1.5555550100")
+                    .feature("voice")
+                    .build(),
+            )
+            .phone(
+                Phone::builder()
+                    .phone("+1.5555550101")
+                    .feature("fax")
+                    .build(),
+            )
+            .build()
+            .set_emails(&["alice@example.com"]);
+        let entity = Entity::builder()
+            .handle("ALICE")
+            .contact(contact)
+            .role("registrant")
+            .build();
+        let mut actual = String::new();
+
+        // WHEN
+        contact_lines(&mut actual, "Registrant", &entity);
+
+        // THEN
+        assert_eq!(
+            actual,
+            "Registrant Name: Alice Example\n\
+             Registrant Organization: Example Inc.\n\
+             Registrant Street: 123 Main St\n\
+             Registrant City: Springfield\n\
+             Registrant State/Province: VA\n\
+             Registrant Postal Code: 22150\n\
+             Registrant Country: US\n\
+             Registrant Phone: +1.5555550100\n\
+             Registrant Fax: +1.5555550101\n\
+             Registrant Email: alice@example.com\n"
+        );
+    }
+
+    #[test]
+    fn GIVEN_network_search_WHEN_to_whois_THEN_networks_separated_by_blank_line() {
+        // GIVEN
+        let results = IpSearchResults::response_obj()
+            .results(vec![
+                Network::builder()
+                    .cidr("192.0.2.0/24")
+                    .build()
+                    .expect("building network"),
+                Network::builder()
+                    .cidr("198.51.100.0/24")
+                    .build()
+                    .expect("building network"),
+            ])
+            .build()
+            .to_response();
+
+        // WHEN
+        let actual = results.to_whois();
+
+        // THEN
+        assert_eq!(
+            actual,
+            "NetRange: 192.0.2.0 - 192.0.2.255\nCIDR: 192.0.2.0/24\n\n\
+             NetRange: 198.51.100.0 - 198.51.100.255\nCIDR: 198.51.100.0/24\n"
+        );
+    }
+}
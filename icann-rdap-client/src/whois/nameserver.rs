@@ -0,0 +1,21 @@
+use icann_rdap_common::response::Nameserver;
+
+use super::{common_lines, line, opt_line, ToWhois};
+
+impl ToWhois for Nameserver {
+    fn to_whois(&self) -> String {
+        let mut whois = String::new();
+        opt_line(
+            &mut whois,
+            "Name Server",
+            self.ldh_name().or(self.unicode_name()),
+        );
+        if let Some(addresses) = self.ip_addresses() {
+            for address in addresses.v4s().iter().chain(addresses.v6s()) {
+                line(&mut whois, "IP Address", address);
+            }
+        }
+        common_lines(&mut whois, self);
+        whois
+    }
+}
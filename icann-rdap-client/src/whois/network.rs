@@ -0,0 +1,23 @@
+use icann_rdap_common::response::{Network, ObjectCommonFields};
+
+use super::{common_lines, entities_lines, line, opt_line, ToWhois};
+
+impl ToWhois for Network {
+    fn to_whois(&self) -> String {
+        let mut whois = String::new();
+        if let (Some(start), Some(end)) = (self.start_address(), self.end_address()) {
+            line(&mut whois, "NetRange", &format!("{start} - {end}"));
+        }
+        for cidr in self.cidr0_cidrs() {
+            line(&mut whois, "CIDR", &cidr.to_string());
+        }
+        opt_line(&mut whois, "NetName", self.name());
+        opt_line(&mut whois, "NetHandle", self.handle());
+        opt_line(&mut whois, "Parent", self.parent_handle());
+        opt_line(&mut whois, "NetType", self.network_type());
+        opt_line(&mut whois, "Country", self.country());
+        common_lines(&mut whois, self);
+        entities_lines(&mut whois, self.entities());
+        whois
+    }
+}
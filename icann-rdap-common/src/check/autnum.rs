@@ -3,7 +3,8 @@ use std::any::TypeId;
 use crate::response::autnum::Autnum;
 
 use super::{
-    string::StringCheck, Check, CheckParams, Checks, GetChecks, GetSubChecks, RdapStructure,
+    nro_profile::autnum_profile_checks, string::StringCheck, Check, CheckParams, Checks, GetChecks,
+    GetSubChecks, ProfileId, RdapStructure,
 };
 
 impl GetChecks for Autnum {
@@ -28,6 +29,15 @@ impl GetChecks for Autnum {
             items.push(Check::AutnumMissing.check_item())
         }
 
+        // AS numbers are 32-bit (RFC 6793), but may be deserialized from larger numbers.
+        let exceeds_32_bits = [&self.start_autnum, &self.end_autnum]
+            .into_iter()
+            .flatten()
+            .any(|n| n.as_u64().is_some_and(|n| n > u64::from(u32::MAX)));
+        if exceeds_32_bits {
+            items.push(Check::AutnumExceeds32Bits.check_item())
+        }
+
         if let Some(start_num) = &self.start_autnum.as_ref().and_then(|n| n.as_u32()) {
             if let Some(end_num) = &self.end_autnum.as_ref().and_then(|n| n.as_u32()) {
                 if start_num > end_num {
@@ -69,6 +79,11 @@ impl GetChecks for Autnum {
             }
         }
 
+        // check the NRO profile of lookups
+        if params.has_profile(ProfileId::Nro) && params.parent_type == params.root.get_type() {
+            items.append(&mut autnum_profile_checks(self, params.root));
+        }
+
         Checks {
             rdap_struct: RdapStructure::Autnum,
             items,
@@ -151,6 +166,29 @@ mod tests {
             .any(|c| c.check == Check::NetworkOrAutnumNameIsNotString));
     }
 
+    #[test]
+    fn check_autnum_exceeding_32_bits() {
+        // GIVEN
+        let json = r#"
+            {
+              "objectClassName" : "autnum",
+              "handle" : "XXXX-RIR",
+              "startAutnum" : 4294967295,
+              "endAutnum" : 4294967296
+            }
+        "#;
+        let rdap = serde_json::from_str::<RdapResponse>(json).expect("parsing JSON");
+
+        // WHEN
+        let checks = rdap.get_checks(CheckParams::for_rdap(&rdap));
+
+        // THEN
+        assert!(checks
+            .items
+            .iter()
+            .any(|c| c.check == Check::AutnumExceeds32Bits));
+    }
+
     #[test]
     fn check_autnum_with_empty_type() {
         // GIVEN
@@ -0,0 +1,319 @@
+//! Checks of DNSSEC data for consistency.
+
+use {
+    base64::{engine::general_purpose::STANDARD, Engine},
+    sha1::Sha1,
+    sha2::{Digest, Sha256, Sha384},
+};
+
+use crate::response::domain::{DsDatum, KeyDatum, SecureDns};
+
+use super::{Check, CheckItem};
+
+/// Gets the number of hexadecimal characters in digests of a DS digest type.
+fn digest_hex_len(digest_type: u8) -> Option<usize> {
+    match digest_type {
+        // SHA-1
+        1 => Some(40),
+        // SHA-256, GOST R 34.11-94, GOST R 34.11-2012, SM3
+        2 | 3 | 5 | 6 => Some(64),
+        // SHA-384
+        4 => Some(96),
+        _ => None,
+    }
+}
+
+/// Checks the digest of a dsData against its digest type.
+pub(crate) fn ds_datum_checks(ds_datum: &DsDatum) -> Vec<CheckItem> {
+    let mut items = vec![];
+    let Some(digest) = ds_datum.digest.as_deref().map(strip_whitespace) else {
+        return items;
+    };
+    if !digest.chars().all(|c| c.is_ascii_hexdigit()) {
+        items.push(Check::DsDatumDigestIsNotHex.check_item());
+    } else if let Some(expected) = ds_datum
+        .digest_type
+        .as_ref()
+        .and_then(|t| t.as_u8())
+        .and_then(digest_hex_len)
+    {
+        if digest.len() != expected {
+            items.push(Check::DsDatumDigestLengthMismatch.check_item());
+        }
+    }
+    items
+}
+
+/// Checks that the public key of a keyData is base64.
+pub(crate) fn key_datum_checks(key_datum: &KeyDatum) -> Vec<CheckItem> {
+    let mut items = vec![];
+    if let Some(public_key) = &key_datum.public_key {
+        if decode_public_key(public_key).is_none() {
+            items.push(Check::KeyDatumPublicKeyIsNotBase64.check_item());
+        }
+    }
+    items
+}
+
+/// Checks the dsData of a domain against the DS records computed from its keyData.
+///
+/// A dsData is only checked if there is keyData with its key tag and algorithm,
+/// as keyData need not be given for every key.
+pub(crate) fn ds_key_data_checks(secure_dns: &SecureDns, owner: &str) -> Vec<CheckItem> {
+    let mut items = vec![];
+    let (Some(ds_data), Some(key_data)) = (&secure_dns.ds_data, &secure_dns.key_data) else {
+        return items;
+    };
+    let Some(owner) = owner_wire_format(owner) else {
+        return items;
+    };
+    let keys = key_data
+        .iter()
+        .filter_map(dnskey_rdata)
+        .collect::<Vec<(u8, Vec<u8>)>>();
+    for ds_datum in ds_data {
+        let (Some(key_tag), Some(algorithm), Some(digest_type), Some(digest)) = (
+            ds_datum.key_tag.as_ref().and_then(|n| n.as_u32()),
+            ds_datum.algorithm.as_ref().and_then(|n| n.as_u8()),
+            ds_datum.digest_type.as_ref().and_then(|n| n.as_u8()),
+            ds_datum.digest.as_deref().map(strip_whitespace),
+        ) else {
+            continue;
+        };
+        let mut matching_keys = keys
+            .iter()
+            .filter(|(key_algorithm, rdata)| {
+                *key_algorithm == algorithm && u32::from(dnskey_tag(rdata)) == key_tag
+            })
+            .peekable();
+        if matching_keys.peek().is_none() {
+            continue;
+        }
+        let computed = matching_keys
+            .filter_map(|(_, rdata)| ds_digest(digest_type, &owner, rdata))
+            .collect::<Vec<String>>();
+        // digest types that cannot be computed here are not checked
+        if !computed.is_empty() && !computed.iter().any(|c| c.eq_ignore_ascii_case(&digest)) {
+            items.push(Check::DsDatumDoesNotMatchKeyData.check_item());
+        }
+    }
+    items
+}
+
+fn strip_whitespace(s: &str) -> String {
+    s.chars().filter(|c| !c.is_ascii_whitespace()).collect()
+}
+
+fn decode_public_key(public_key: &str) -> Option<Vec<u8>> {
+    STANDARD.decode(strip_whitespace(public_key)).ok()
+}
+
+/// Gets the algorithm and the DNSKEY RDATA of a keyData (RFC 4034 section 2.1).
+fn dnskey_rdata(key_datum: &KeyDatum) -> Option<(u8, Vec<u8>)> {
+    let flags = key_datum.flags.as_ref()?.as_u16()?;
+    let protocol = key_datum.protocol.as_ref()?.as_u8()?;
+    let algorithm = key_datum.algorithm.as_ref()?.as_u8()?;
+    let public_key = decode_public_key(key_datum.public_key.as_deref()?)?;
+    let mut rdata = Vec::with_capacity(4 + public_key.len());
+    rdata.extend_from_slice(&flags.to_be_bytes());
+    rdata.push(protocol);
+    rdata.push(algorithm);
+    rdata.extend_from_slice(&public_key);
+    Some((algorithm, rdata))
+}
+
+/// Computes the key tag of DNSKEY RDATA (RFC 4034 appendix B).
+fn dnskey_tag(rdata: &[u8]) -> u16 {
+    let mut ac: u32 = 0;
+    for (i, byte) in rdata.iter().enumerate() {
+        ac += if i & 1 == 1 {
+            u32::from(*byte)
+        } else {
+            u32::from(*byte) << 8
+        };
+    }
+    ac += (ac >> 16) & 0xFFFF;
+    (ac & 0xFFFF) as u16
+}
+
+/// Gets the canonical wire format of a domain name (RFC 4034 section 6.2).
+fn owner_wire_format(name: &str) -> Option<Vec<u8>> {
+    let mut wire = vec![];
+    for label in name.trim_end_matches('.').split('.') {
+        if label.is_empty() || label.len() > 63 {
+            return None;
+        }
+        wire.push(label.len() as u8);
+        wire.extend(label.to_ascii_lowercase().bytes());
+    }
+    wire.push(0);
+    Some(wire)
+}
+
+/// Computes the hexadecimal digest of a DS record (RFC 4034 section 5.1.4), if
+/// the digest type is one computed here.
+fn ds_digest(digest_type: u8, owner: &[u8], rdata: &[u8]) -> Option<String> {
+    let digest = match digest_type {
+        1 => hash::<Sha1>(owner, rdata),
+        2 => hash::<Sha256>(owner, rdata),
+        4 => hash::<Sha384>(owner, rdata),
+        _ => return None,
+    };
+    Some(digest.iter().map(|b| format!("{b:02X}")).collect())
+}
+
+fn hash<D: Digest>(owner: &[u8], rdata: &[u8]) -> Vec<u8> {
+    D::new()
+        .chain_update(owner)
+        .chain_update(rdata)
+        .finalize()
+        .to_vec()
+}
+
+#[cfg(test)]
+#[allow(non_snake_case)]
+mod tests {
+    use {
+        crate::{
+            check::{is_checked, Check, CheckParams, GetChecks},
+            prelude::ToResponse,
+            response::domain::{Domain, SecureDns},
+        },
+        rstest::rstest,
+    };
+
+    use super::{ds_datum_checks, ds_key_data_checks, key_datum_checks};
+
+    /// The DNSKEY of the examples of RFC 4034 section 5.4 and RFC 4509 section 2.3.
+    const KEY: &str = "AQOeiiR0GOMYkDshWoSKz9XzfwJr1AYtsmx3TGkJaNXVbfi/2pHm822aJ5iI9BMzNXxeYCmZDRD99WYwYqUSdjMmmAphXdvxegXd/M5+X7OrzKBaMbCVdFLUUh6DhweJBjEVv5f2wwjM9XzcnOf+EPbtG9DMBmADjFDc2w/rljwvFw==";
+
+    fn secure_dns(digest_type: u8, digest: &str) -> SecureDns {
+        serde_json::from_str(&format!(
+            r#"{{
+                "dsData": [{{
+                    "keyTag": 60485,
+                    "algorithm": 5,
+                    "digestType": {digest_type},
+                    "digest": "{digest}"
+                }}],
+                "keyData": [{{
+                    "flags": 256,
+                    "protocol": 3,
+                    "algorithm": 5,
+                    "publicKey": "{KEY}"
+                }}]
+            }}"#
+        ))
+        .expect("parsing secure dns")
+    }
+
+    #[rstest]
+    #[case(1, "2BB183AF5F22588179A53B0A98631FAD1A292118")]
+    #[case(1, "2bb183af 5f225881 79a53b0a 98631fad 1a292118")]
+    #[case(2, "D4B7D520E7BB5F0F67674A0CCEB1E3E0614B93C4F9E99B8383F6A1E4469DA50A")]
+    fn GIVEN_ds_of_key_WHEN_ds_key_data_checks_THEN_no_checks(
+        #[case] digest_type: u8,
+        #[case] digest: &str,
+    ) {
+        // GIVEN
+        let secure_dns = secure_dns(digest_type, digest);
+
+        // WHEN
+        let actual = ds_key_data_checks(&secure_dns, "dskey.example.com");
+
+        // THEN
+        assert!(actual.is_empty());
+    }
+
+    #[test]
+    fn GIVEN_ds_of_other_owner_WHEN_ds_key_data_checks_THEN_mismatch() {
+        // GIVEN
+        let secure_dns = secure_dns(1, "2BB183AF5F22588179A53B0A98631FAD1A292118");
+
+        // WHEN
+        let actual = ds_key_data_checks(&secure_dns, "other.example.com");
+
+        // THEN
+        assert_eq!(actual.len(), 1);
+        assert_eq!(actual[0].check, Check::DsDatumDoesNotMatchKeyData);
+    }
+
+    #[rstest]
+    #[case(1, "2BB183AF5F22588179A53B0A98631FAD1A292118", None)]
+    #[case(
+        2,
+        "2BB183AF5F22588179A53B0A98631FAD1A292118",
+        Some(Check::DsDatumDigestLengthMismatch)
+    )]
+    #[case(
+        4,
+        "D4B7D520E7BB5F0F67674A0CCEB1E3E0614B93C4F9E99B8383F6A1E4469DA50A",
+        Some(Check::DsDatumDigestLengthMismatch)
+    )]
+    #[case(
+        1,
+        "2BB183AF5F22588179A53B0A98631FAD1A29211Z",
+        Some(Check::DsDatumDigestIsNotHex)
+    )]
+    #[case(99, "ABCD", None)]
+    fn GIVEN_ds_digest_WHEN_ds_datum_checks_THEN_length_checked(
+        #[case] digest_type: u8,
+        #[case] digest: &str,
+        #[case] expected: Option<Check>,
+    ) {
+        // GIVEN
+        let secure_dns = secure_dns(digest_type, digest);
+        let ds_datum = &secure_dns.ds_data.as_ref().unwrap()[0];
+
+        // WHEN
+        let actual = ds_datum_checks(ds_datum);
+
+        // THEN
+        assert_eq!(actual.first().map(|item| item.check), expected);
+    }
+
+    #[test]
+    fn GIVEN_public_key_not_base64_WHEN_key_datum_checks_THEN_not_base64() {
+        // GIVEN
+        let secure_dns = serde_json::from_str::<SecureDns>(
+            r#"{
+                "keyData": [{
+                    "flags": 256,
+                    "protocol": 3,
+                    "algorithm": 5,
+                    "publicKey": "not base64!"
+                }]
+            }"#,
+        )
+        .unwrap();
+
+        // WHEN
+        let actual = key_datum_checks(&secure_dns.key_data.as_ref().unwrap()[0]);
+
+        // THEN
+        assert_eq!(actual.len(), 1);
+        assert_eq!(actual[0].check, Check::KeyDatumPublicKeyIsNotBase64);
+    }
+
+    #[test]
+    fn GIVEN_domain_with_mismatched_ds_WHEN_checked_THEN_mismatch_found() {
+        // GIVEN
+        let rdap = Domain::builder()
+            .ldh_name("other.example.com")
+            .secure_dns(secure_dns(
+                2,
+                "D4B7D520E7BB5F0F67674A0CCEB1E3E0614B93C4F9E99B8383F6A1E4469DA50A",
+            ))
+            .build()
+            .to_response();
+
+        // WHEN
+        let checks = rdap.get_checks(CheckParams::for_rdap(&rdap));
+
+        // THEN
+        assert!(is_checked(
+            Check::DsDatumDoesNotMatchKeyData,
+            &checks.sub_checks
+        ));
+    }
+}
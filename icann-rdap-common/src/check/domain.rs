@@ -1,8 +1,14 @@
-use std::any::TypeId;
+use std::{any::TypeId, str::FromStr};
 
-use crate::response::domain::{Domain, SecureDns};
+use crate::response::domain::{Domain, SecureDns, Variant, VariantName, VariantRelation};
 
-use super::{string::StringCheck, Check, CheckParams, Checks, GetChecks, GetSubChecks};
+use super::{
+    dnssec::{ds_datum_checks, ds_key_data_checks, key_datum_checks},
+    entity::entities_checks,
+    icann_profile::domain_profile_checks,
+    string::StringCheck,
+    Check, CheckItem, CheckParams, Checks, GetChecks, GetSubChecks,
+};
 
 impl GetChecks for Domain {
     fn get_checks(&self, params: CheckParams) -> super::Checks {
@@ -18,8 +24,34 @@ impl GetChecks for Domain {
             if let Some(public_ids) = &self.public_ids {
                 sub_checks.append(&mut public_ids.get_sub_checks(params));
             }
+            if let Some(nameservers) = &self.nameservers {
+                nameservers.iter().for_each(|nameserver| {
+                    sub_checks.push(nameserver.get_checks(params.from_parent(TypeId::of::<Self>())))
+                });
+            }
             if let Some(secure_dns) = &self.secure_dns {
                 sub_checks.append(&mut secure_dns.get_sub_checks(params));
+                // the DS computed from keyData depends on the name of the domain
+                if let Some(ldh_name) = &self.ldh_name {
+                    let items = ds_key_data_checks(secure_dns, ldh_name);
+                    if !items.is_empty() {
+                        sub_checks.push(Checks {
+                            rdap_struct: super::RdapStructure::SecureDns,
+                            items,
+                            sub_checks: vec![],
+                        });
+                    }
+                }
+            }
+            if let Some(variants) = &self.variants {
+                let items = variants_checks(variants, self.ldh_name.as_deref());
+                if !items.is_empty() {
+                    sub_checks.push(Checks {
+                        rdap_struct: super::RdapStructure::Variants,
+                        items,
+                        sub_checks: vec![],
+                    });
+                }
             }
             sub_checks
         } else {
@@ -28,19 +60,6 @@ impl GetChecks for Domain {
 
         let mut items = vec![];
 
-        // check variants
-        if let Some(variants) = &self.variants {
-            let empty_count = variants
-                .iter()
-                .filter(|v| {
-                    v.relations.is_none() && v.idn_table.is_none() && v.variant_names.is_none()
-                })
-                .count();
-            if empty_count != 0 {
-                items.push(Check::VariantEmptyDomain.check_item());
-            };
-        };
-
         // check ldh
         if let Some(ldh) = &self.ldh_name {
             if !ldh.is_ldh_domain_name() {
@@ -81,6 +100,16 @@ impl GetChecks for Domain {
             }
         }
 
+        // check the entities together
+        items.append(&mut entities_checks(
+            self.object_common.entities.as_deref().unwrap_or_default(),
+        ));
+
+        // check the gTLD profile of lookups
+        if params.parent_type == params.root.get_type() && params.has_gtld_profile() {
+            items.append(&mut domain_profile_checks(self));
+        }
+
         Checks {
             rdap_struct: super::RdapStructure::Domain,
             items,
@@ -89,6 +118,70 @@ impl GetChecks for Domain {
     }
 }
 
+/// Checks the variants of a domain, which are not to include the domain itself.
+fn variants_checks(variants: &[Variant], domain_ldh: Option<&str>) -> Vec<CheckItem> {
+    let mut items = vec![];
+    for variant in variants {
+        if variant.relations.is_none()
+            && variant.idn_table.is_none()
+            && variant.variant_names.is_none()
+        {
+            items.push(Check::VariantEmptyDomain.check_item());
+            continue;
+        }
+        for relation in variant.relations() {
+            if VariantRelation::from_str(relation).is_err() {
+                items.push(Check::VariantRelationNotRegistered.check_item());
+            }
+        }
+        if variant
+            .idn_table()
+            .is_none_or(|t| t.is_whitespace_or_empty())
+        {
+            items.push(Check::VariantIdnTableIsAbsent.check_item());
+        }
+        for variant_name in variant.variant_names() {
+            items.append(&mut variant_name_checks(variant_name));
+            let is_domain = variant_name
+                .ldh_name()
+                .zip(domain_ldh)
+                .is_some_and(|(ldh, domain)| {
+                    ldh.trim_end_matches('.')
+                        .eq_ignore_ascii_case(domain.trim_end_matches('.'))
+                });
+            if is_domain {
+                items.push(Check::VariantNameIsDomainName.check_item());
+            }
+        }
+    }
+    items
+}
+
+/// Checks that a variant name is a valid IDN.
+fn variant_name_checks(variant_name: &VariantName) -> Vec<CheckItem> {
+    let mut items = vec![];
+    if let Some(ldh) = variant_name.ldh_name() {
+        if !ldh.is_ldh_domain_name() {
+            items.push(Check::VariantNameLdhNameInvalid.check_item());
+        } else if idna::domain_to_unicode(ldh).1.is_err() {
+            items.push(Check::VariantNameALabelInvalid.check_item());
+        }
+    }
+    if let Some(unicode_name) = variant_name.unicode_name() {
+        match idna::domain_to_ascii(unicode_name) {
+            Ok(expected) if unicode_name.is_unicode_domain_name() => {
+                if let Some(ldh) = variant_name.ldh_name() {
+                    if !expected.eq_ignore_ascii_case(ldh) {
+                        items.push(Check::VariantNameLdhDoesNotMatchUnicode.check_item());
+                    }
+                }
+            }
+            _ => items.push(Check::VariantNameUnicodeNameInvalid.check_item()),
+        }
+    }
+    items
+}
+
 impl GetSubChecks for SecureDns {
     fn get_sub_checks(&self, _params: CheckParams) -> Vec<Checks> {
         let mut sub_checks = Vec::new();
@@ -154,6 +247,14 @@ impl GetSubChecks for SecureDns {
                         });
                     }
                 }
+                let items = key_datum_checks(key_datum);
+                if !items.is_empty() {
+                    sub_checks.push(Checks {
+                        rdap_struct: super::RdapStructure::SecureDns,
+                        items,
+                        sub_checks: vec![],
+                    });
+                }
                 if let Some(protocol) = &key_datum.protocol {
                     if protocol.is_string() {
                         sub_checks.push(Checks {
@@ -191,6 +292,14 @@ impl GetSubChecks for SecureDns {
                         });
                     }
                 }
+                let items = ds_datum_checks(ds_datum);
+                if !items.is_empty() {
+                    sub_checks.push(Checks {
+                        rdap_struct: super::RdapStructure::SecureDns,
+                        items,
+                        sub_checks: vec![],
+                    });
+                }
                 if let Some(key_tag) = &ds_datum.key_tag {
                     if key_tag.is_string() {
                         sub_checks.push(Checks {
@@ -236,9 +345,12 @@ mod tests {
 
     use {
         crate::{
-            check::{is_checked, is_checked_item, GetSubChecks},
+            check::{is_checked, is_checked_item, GetSubChecks, RdapStructure},
             prelude::ToResponse,
-            response::domain::{Domain, SecureDns},
+            response::{
+                domain::{Domain, SecureDns, Variant, VariantName},
+                entity::Entity,
+            },
         },
         rstest::rstest,
     };
@@ -295,6 +407,100 @@ mod tests {
         assert!(is_checked_item(Check::LdhNameDoesNotMatchUnicode, &checks));
     }
 
+    #[test]
+    fn test_check_for_valid_variants() {
+        // GIVEN
+        let domain = Domain::builder()
+            .ldh_name("xn--fo-5ja.example")
+            .variant(
+                Variant::builder()
+                    .relation("registered")
+                    .relation("conjoined")
+                    .idn_table(".EXAMPLE Swedish")
+                    .variant_name(
+                        VariantName::builder()
+                            .ldh_name("xn--fo-8ja.example")
+                            .unicode_name("fôo.example")
+                            .build(),
+                    )
+                    .build(),
+            )
+            .build();
+        let rdap = domain.to_response();
+
+        // WHEN
+        let checks = rdap.get_checks(CheckParams::for_rdap(&rdap));
+
+        // THEN
+        dbg!(&checks);
+        assert!(checks
+            .sub(RdapStructure::Variants)
+            .is_none_or(|variants| variants.items.is_empty()));
+    }
+
+    #[rstest]
+    #[case(Variant::builder().relation("cousin").idn_table("t").build(), Check::VariantRelationNotRegistered)]
+    #[case(Variant::builder().relation("registered").build(), Check::VariantIdnTableIsAbsent)]
+    #[case(
+        Variant::builder()
+            .idn_table("t")
+            .variant_name(VariantName::builder().ldh_name("foo_bar.example").build())
+            .build(),
+        Check::VariantNameLdhNameInvalid
+    )]
+    #[case(
+        Variant::builder()
+            .idn_table("t")
+            .variant_name(VariantName::builder().unicode_name("fôo .example").build())
+            .build(),
+        Check::VariantNameUnicodeNameInvalid
+    )]
+    #[case(
+        Variant::builder()
+            .idn_table("t")
+            .variant_name(
+                VariantName::builder()
+                    .ldh_name("xn--fo-5ja.example")
+                    .unicode_name("fôo.example")
+                    .build()
+            )
+            .build(),
+        Check::VariantNameLdhDoesNotMatchUnicode
+    )]
+    #[case(
+        Variant::builder()
+            .idn_table("t")
+            .variant_name(VariantName::builder().ldh_name("xn--zz.example").build())
+            .build(),
+        Check::VariantNameALabelInvalid
+    )]
+    #[case(
+        Variant::builder()
+            .idn_table("t")
+            .variant_name(VariantName::builder().ldh_name("XN--FO-5JA.example.").build())
+            .build(),
+        Check::VariantNameIsDomainName
+    )]
+    fn test_check_for_bad_variant(#[case] variant: Variant, #[case] expected: Check) {
+        // GIVEN
+        let domain = Domain::builder()
+            .ldh_name("xn--fo-5ja.example")
+            .variant(variant)
+            .build();
+        let rdap = domain.to_response();
+
+        // WHEN
+        let checks = rdap.get_checks(CheckParams::for_rdap(&rdap));
+
+        // THEN
+        dbg!(&checks);
+        let variants = checks
+            .sub(RdapStructure::Variants)
+            .expect("no variants checks");
+        assert!(is_checked_item(expected, variants));
+        assert!(!is_checked_item(expected, &checks));
+    }
+
     #[test]
     fn test_delegation_signed_as_string() {
         // GIVEN
@@ -314,6 +520,7 @@ mod tests {
                 .to_response(),
             parent_type: TypeId::of::<SecureDns>(),
             allow_unreg_ext: false,
+            profiles: &[],
         });
 
         // THEN
@@ -340,6 +547,7 @@ mod tests {
                 .to_response(),
             parent_type: TypeId::of::<SecureDns>(),
             allow_unreg_ext: false,
+            profiles: &[],
         });
 
         // THEN
@@ -365,6 +573,7 @@ mod tests {
                 .to_response(),
             parent_type: TypeId::of::<SecureDns>(),
             allow_unreg_ext: false,
+            profiles: &[],
         });
 
         // THEN
@@ -391,6 +600,7 @@ mod tests {
                 .to_response(),
             parent_type: TypeId::of::<SecureDns>(),
             allow_unreg_ext: false,
+            profiles: &[],
         });
 
         // THEN
@@ -416,6 +626,7 @@ mod tests {
                 .to_response(),
             parent_type: TypeId::of::<SecureDns>(),
             allow_unreg_ext: false,
+            profiles: &[],
         });
 
         // THEN
@@ -442,6 +653,7 @@ mod tests {
                 .to_response(),
             parent_type: TypeId::of::<SecureDns>(),
             allow_unreg_ext: false,
+            profiles: &[],
         });
 
         // THEN
@@ -473,6 +685,7 @@ mod tests {
                 .to_response(),
             parent_type: TypeId::of::<SecureDns>(),
             allow_unreg_ext: false,
+            profiles: &[],
         });
 
         // THEN
@@ -507,6 +720,7 @@ mod tests {
                 .to_response(),
             parent_type: TypeId::of::<SecureDns>(),
             allow_unreg_ext: false,
+            profiles: &[],
         });
 
         // THEN
@@ -538,6 +752,7 @@ mod tests {
                 .to_response(),
             parent_type: TypeId::of::<SecureDns>(),
             allow_unreg_ext: false,
+            profiles: &[],
         });
 
         // THEN
@@ -572,6 +787,7 @@ mod tests {
                 .to_response(),
             parent_type: TypeId::of::<SecureDns>(),
             allow_unreg_ext: false,
+            profiles: &[],
         });
 
         // THEN
@@ -606,6 +822,7 @@ mod tests {
                 .to_response(),
             parent_type: TypeId::of::<SecureDns>(),
             allow_unreg_ext: false,
+            profiles: &[],
         });
 
         // THEN
@@ -637,6 +854,7 @@ mod tests {
                 .to_response(),
             parent_type: TypeId::of::<SecureDns>(),
             allow_unreg_ext: false,
+            profiles: &[],
         });
 
         // THEN
@@ -645,4 +863,29 @@ mod tests {
         assert!(is_checked(Check::DsDatumKeyTagIsOutOfRange, &checks));
         assert!(is_checked(Check::DsDatumDigestTypeIsOutOfRange, &checks));
     }
+
+    #[rstest]
+    #[case("FOO", "technical", true)]
+    #[case("FOO", "administrative", false)]
+    #[case("BAR", "technical", false)]
+    fn test_check_for_duplicated_entity(
+        #[case] handle: &str,
+        #[case] role: &str,
+        #[case] expected: bool,
+    ) {
+        // GIVEN
+        let domain = Domain::builder()
+            .ldh_name("example.com")
+            .entity(Entity::builder().handle("FOO").role("technical").build())
+            .entity(Entity::builder().handle(handle).role(role).build())
+            .build();
+        let rdap = domain.to_response();
+
+        // WHEN
+        let checks = rdap.get_checks(CheckParams::for_rdap(&rdap));
+
+        // THEN
+        dbg!(&checks);
+        assert_eq!(is_checked_item(Check::RoleIsDuplicated, &checks), expected);
+    }
 }
@@ -1,13 +1,10 @@
 use std::{any::TypeId, str::FromStr};
 
-use crate::{
-    contact::Contact,
-    response::entity::{Entity, EntityRole},
-};
+use crate::response::entity::{Entity, EntityRole};
 
 use super::{
     string::{StringCheck, StringListCheck},
-    Check, CheckParams, Checks, GetChecks, GetSubChecks, RdapStructure,
+    Check, CheckItem, CheckParams, Checks, GetChecks, GetSubChecks, RdapStructure,
 };
 
 impl GetChecks for Entity {
@@ -48,9 +45,9 @@ impl GetChecks for Entity {
             }
         }
 
-        if let Some(vcard) = &self.vcard_array {
-            if let Some(contact) = Contact::from_vcard(vcard) {
-                if let Some(full_name) = contact.full_name {
+        if self.vcard_array.is_some() {
+            if let Some(contact) = self.contact() {
+                if let Some(full_name) = &contact.full_name {
                     if full_name.is_whitespace_or_empty() {
                         items.push(Check::VcardFnIsEmpty.check_item())
                     }
@@ -69,3 +66,28 @@ impl GetChecks for Entity {
         }
     }
 }
+
+/// Checks the entities of an object together, finding entities given more than
+/// once with the same handle and role.
+pub(crate) fn entities_checks(entities: &[Entity]) -> Vec<CheckItem> {
+    let mut seen: Vec<(&str, &str)> = vec![];
+    let mut duplicated = false;
+    for entity in entities {
+        let Some(handle) = entity.object_common.handle.as_deref() else {
+            continue;
+        };
+        for role in entity.roles() {
+            let key = (handle, role.as_str());
+            if seen.contains(&key) {
+                duplicated = true;
+            } else {
+                seen.push(key);
+            }
+        }
+    }
+    if duplicated {
+        vec![Check::RoleIsDuplicated.check_item()]
+    } else {
+        vec![]
+    }
+}
@@ -1,11 +1,30 @@
-use crate::{httpdata::HttpData, media_types::RDAP_MEDIA_TYPE, response::types::ExtensionId};
+use crate::{
+    httpdata::HttpData,
+    media_types::{
+        is_json_media_type, is_rdap_media_type, is_rdap_x_media_type, media_type_charset,
+    },
+    response::types::ExtensionId,
+};
 
 use super::{Check, Checks, GetChecks};
 
+/// The length of a response below which it is not worth compressing, as many servers
+/// do not compress short responses.
+const MIN_COMPRESSIBLE_LENGTH: u64 = 1024;
+
 impl GetChecks for HttpData {
     fn get_checks(&self, params: crate::check::CheckParams) -> crate::check::Checks {
         let mut items = vec![];
 
+        // RDAP not received with HTTP, such as RDAP read from a file, has no HTTP to check.
+        if self.scheme() == Some("file") {
+            return Checks {
+                rdap_struct: super::RdapStructure::HttpData,
+                items,
+                sub_checks: vec![],
+            };
+        }
+
         // RFC checks
         if let Some(allow_origin) = &self.access_control_allow_origin {
             if !allow_origin.eq("*") {
@@ -18,12 +37,34 @@ impl GetChecks for HttpData {
             items.push(Check::CorsAllowCredentialsNotRecommended.check_item())
         }
         if let Some(content_type) = &self.content_type {
-            if !content_type.starts_with(RDAP_MEDIA_TYPE) {
+            if is_json_media_type(content_type) {
+                items.push(Check::ContentTypeIsJson.check_item());
+            } else if !is_rdap_media_type(content_type) && !is_rdap_x_media_type(content_type) {
                 items.push(Check::ContentTypeIsNotRdap.check_item());
             }
+            if media_type_charset(content_type).is_some_and(|c| !c.eq_ignore_ascii_case("utf-8")) {
+                items.push(Check::ContentTypeCharsetIsNotUtf8.check_item());
+            }
         } else {
             items.push(Check::ContentTypeIsAbsent.check_item());
         }
+        if self
+            .redirects
+            .iter()
+            .any(|redirect| redirect.is_scheme_downgrade())
+        {
+            items.push(Check::RedirectDowngradesToHttp.check_item());
+        }
+        if self.has_redirect_loop() {
+            items.push(Check::RedirectLoop.check_item());
+        }
+        if self.content_encoding.is_none()
+            && self
+                .content_length
+                .is_some_and(|length| length >= MIN_COMPRESSIBLE_LENGTH)
+        {
+            items.push(Check::ResponseIsNotCompressed.check_item());
+        }
 
         // checks for ICANN profile
         if params
@@ -59,10 +100,12 @@ impl GetChecks for HttpData {
 
 #[cfg(test)]
 mod tests {
+    use rstest::rstest;
+
     use crate::{
         check::{Check, CheckParams, GetChecks},
-        httpdata::HttpData,
-        media_types::{JSON_MEDIA_TYPE, RDAP_MEDIA_TYPE},
+        httpdata::{HttpData, Redirect},
+        media_types::{rdap_x_media_type, JSON_MEDIA_TYPE, RDAP_MEDIA_TYPE},
         prelude::{Common, ObjectCommon, ToResponse},
         response::{domain::Domain, types::ExtensionId},
     };
@@ -86,7 +129,7 @@ mod tests {
         let rdap = domain.to_response();
 
         // and GIVEN httpdata with content type that is not RDAP media type
-        let http_data = HttpData::example().content_type(JSON_MEDIA_TYPE).build();
+        let http_data = HttpData::example().content_type("text/html").build();
 
         // WHEN checks are run
         let checks = http_data.get_checks(CheckParams::for_rdap(&rdap));
@@ -98,6 +141,57 @@ mod tests {
             .any(|c| c.check == Check::ContentTypeIsNotRdap));
     }
 
+    #[test]
+    fn check_json_media() {
+        // GIVEN an rdap response
+        let rdap = Domain::builder()
+            .ldh_name("foo.example")
+            .build()
+            .to_response();
+
+        // and GIVEN httpdata with the JSON media type
+        let http_data = HttpData::example().content_type(JSON_MEDIA_TYPE).build();
+
+        // WHEN checks are run
+        let checks = http_data.get_checks(CheckParams::for_rdap(&rdap));
+
+        // THEN the JSON media type check is found instead of the incorrect media type check
+        assert!(checks
+            .items
+            .iter()
+            .any(|c| c.check == Check::ContentTypeIsJson));
+        assert!(!checks
+            .items
+            .iter()
+            .any(|c| c.check == Check::ContentTypeIsNotRdap));
+    }
+
+    #[test]
+    fn check_rdap_media_with_other_charset() {
+        // GIVEN an rdap response
+        let rdap = Domain::builder()
+            .ldh_name("foo.example")
+            .build()
+            .to_response();
+
+        // and GIVEN httpdata with the RDAP media type and a charset that is not UTF-8
+        let mt = format!("{RDAP_MEDIA_TYPE}; charset=ISO-8859-1");
+        let http_data = HttpData::example().content_type(mt).build();
+
+        // WHEN checks are run
+        let checks = http_data.get_checks(CheckParams::for_rdap(&rdap));
+
+        // THEN the charset check is found
+        assert!(checks
+            .items
+            .iter()
+            .any(|c| c.check == Check::ContentTypeCharsetIsNotUtf8));
+        assert!(!checks
+            .items
+            .iter()
+            .any(|c| c.check == Check::ContentTypeIsNotRdap));
+    }
+
     #[test]
     fn check_exactly_rdap_media() {
         // GIVEN an rdap response
@@ -161,6 +255,47 @@ mod tests {
             .any(|c| c.check == Check::ContentTypeIsNotRdap));
     }
 
+    #[test]
+    fn check_rdap_x_media() {
+        // GIVEN an rdap response
+        let rdap = Domain::builder()
+            .ldh_name("foo.example")
+            .build()
+            .to_response();
+
+        // and GIVEN httpdata with the RDAP-X media type
+        let http_data = HttpData::example()
+            .content_type(rdap_x_media_type(&["rdap_level_0"]))
+            .build();
+
+        // WHEN checks are run
+        let checks = http_data.get_checks(CheckParams::for_rdap(&rdap));
+
+        // THEN incorrect media type check is not found
+        assert!(!checks
+            .items
+            .iter()
+            .any(|c| c.check == Check::ContentTypeIsNotRdap));
+    }
+
+    #[test]
+    fn check_file_scheme() {
+        // GIVEN an rdap response
+        let rdap = Domain::builder()
+            .ldh_name("foo.example")
+            .build()
+            .to_response();
+
+        // and GIVEN httpdata of RDAP read from a file
+        let http_data = HttpData::now().scheme("file").host("foo.json").build();
+
+        // WHEN checks are run
+        let checks = http_data.get_checks(CheckParams::for_rdap(&rdap));
+
+        // THEN there are no checks
+        assert!(checks.items.is_empty());
+    }
+
     #[test]
     fn check_media_type_absent() {
         // GIVEN an rdap response
@@ -343,4 +478,108 @@ mod tests {
         // THEN check for must use https is found
         assert!(checks.items.iter().any(|c| c.check == Check::MustUseHttps));
     }
+
+    fn redirect(url: &str, location: &str) -> Redirect {
+        Redirect {
+            url: url.to_string(),
+            status_code: 301,
+            location: Some(location.to_string()),
+            headers: vec![],
+        }
+    }
+
+    #[test]
+    fn check_redirect_downgrade_to_http() {
+        // GIVEN an rdap response
+        let rdap = Domain::builder()
+            .ldh_name("foo.example")
+            .build()
+            .to_response();
+
+        // and GIVEN httpdata redirected from https to http
+        let http_data = HttpData::example()
+            .request_uri("https://rdap.example/domain/foo.example")
+            .redirect(redirect(
+                "https://rdap.example/domain/foo.example",
+                "http://rdap.example/domain/foo.example",
+            ))
+            .build();
+
+        // WHEN checks are run
+        let checks = http_data.get_checks(CheckParams::for_rdap(&rdap));
+
+        // THEN the downgrade check is found but not the loop check
+        assert!(checks
+            .items
+            .iter()
+            .any(|c| c.check == Check::RedirectDowngradesToHttp));
+        assert!(!checks.items.iter().any(|c| c.check == Check::RedirectLoop));
+    }
+
+    #[test]
+    fn check_redirect_loop() {
+        // GIVEN an rdap response
+        let rdap = Domain::builder()
+            .ldh_name("foo.example")
+            .build()
+            .to_response();
+
+        // and GIVEN httpdata with redirects back to the first URL
+        let http_data = HttpData::example()
+            .request_uri("https://a.example/domain/foo.example")
+            .redirect(redirect(
+                "https://a.example/domain/foo.example",
+                "https://b.example/domain/foo.example",
+            ))
+            .redirect(redirect(
+                "https://b.example/domain/foo.example",
+                "https://a.example/domain/foo.example",
+            ))
+            .build();
+
+        // WHEN checks are run
+        let checks = http_data.get_checks(CheckParams::for_rdap(&rdap));
+
+        // THEN the loop check is found but not the downgrade check
+        assert!(checks.items.iter().any(|c| c.check == Check::RedirectLoop));
+        assert!(!checks
+            .items
+            .iter()
+            .any(|c| c.check == Check::RedirectDowngradesToHttp));
+    }
+
+    #[rstest]
+    #[case(Some(4096), None, true)]
+    #[case(Some(4096), Some("gzip"), false)]
+    #[case(Some(100), None, false)]
+    #[case(None, None, false)]
+    fn check_response_not_compressed(
+        #[case] content_length: Option<u64>,
+        #[case] content_encoding: Option<&str>,
+        #[case] expected: bool,
+    ) {
+        // GIVEN an rdap response
+        let rdap = Domain::builder()
+            .ldh_name("foo.example")
+            .build()
+            .to_response();
+
+        // and GIVEN httpdata with the content length and encoding
+        let http_data = HttpData::example()
+            .and_content_length(content_length)
+            .and_content_encoding(content_encoding.map(str::to_string))
+            .build();
+
+        // WHEN checks are run
+        let checks = http_data.get_checks(CheckParams::for_rdap(&rdap));
+
+        // THEN the not compressed check is found only for long uncompressed responses
+        assert_eq!(
+            checks
+                .items
+                .iter()
+                .any(|c| c.check == Check::ResponseIsNotCompressed),
+            expected
+        );
+    }
 }
@@ -0,0 +1,514 @@
+//! Checks of the ICANN gTLD RDAP Response Profile.
+
+use crate::response::{
+    domain::Domain, entity::Entity, nameserver::Nameserver, CommonFields, ExtensionId, Link,
+    Notice, ObjectCommonFields, RdapResponse,
+};
+
+use super::{Check, CheckItem};
+
+/// The event required on all lookups.
+const RDAP_DATABASE_UPDATE_EVENT: &str = "last update of RDAP database";
+
+/// The publicId type of the IANA identifier of a registrar.
+const IANA_REGISTRAR_ID: &str = "IANA Registrar ID";
+
+/// The titles of the notices which the profile requires to have links.
+const LINKED_NOTICE_TITLES: [&str; 2] = ["Status Codes", "RDDS Inaccuracy Complaint Form"];
+
+/// Returns true if the response declares conformance to the gTLD RDAP Response Profile.
+pub(crate) fn has_gtld_profile(root: &RdapResponse) -> bool {
+    root.has_extension_id(ExtensionId::IcannRdapResponseProfile0)
+        || root.has_extension_id(ExtensionId::IcannRdapResponseProfile1)
+}
+
+/// Checks a domain that is the root of a lookup against the profile.
+pub(crate) fn domain_profile_checks(domain: &Domain) -> Vec<CheckItem> {
+    let mut items = vec![];
+
+    // notices
+    let notice_links = domain
+        .notices()
+        .iter()
+        .flat_map(|notice| notice.0.links())
+        .collect::<Vec<&Link>>();
+    if !notice_links
+        .iter()
+        .any(|link| links_to(link, "icann.org/epp"))
+    {
+        items.push(Check::IcannStatusCodesNoticeIsAbsent.check_item());
+    }
+    if !notice_links
+        .iter()
+        .any(|link| links_to(link, "icann.org/wicf"))
+    {
+        items.push(Check::IcannInaccuracyComplaintNoticeIsAbsent.check_item());
+    }
+    items.append(&mut notice_checks(domain.notices()));
+
+    // registrar
+    let registrar = domain
+        .entities()
+        .iter()
+        .find(|entity| has_role(entity, "registrar"));
+    match registrar {
+        None => items.push(Check::IcannRegistrarEntityIsAbsent.check_item()),
+        Some(registrar) => {
+            if !registrar
+                .public_ids()
+                .iter()
+                .any(|id| id.id_type() == Some(IANA_REGISTRAR_ID))
+            {
+                items.push(Check::IcannRegistrarIanaIdIsAbsent.check_item());
+            }
+            items.append(&mut abuse_contact_checks(registrar));
+        }
+    }
+
+    // registrant
+    if !domain
+        .entities()
+        .iter()
+        .any(|entity| has_role(entity, "registrant"))
+    {
+        items.push(Check::IcannRegistrantEntityIsAbsent.check_item());
+    }
+
+    // events
+    let has_event = |action: &str| {
+        domain
+            .events()
+            .iter()
+            .any(|event| event.event_action() == Some(action))
+    };
+    if !has_event("registration") {
+        items.push(Check::IcannRegistrationEventIsAbsent.check_item());
+    }
+    if !has_event("expiration") {
+        items.push(Check::IcannExpirationEventIsAbsent.check_item());
+    }
+    if !has_event(RDAP_DATABASE_UPDATE_EVENT) {
+        items.push(Check::IcannRdapDatabaseUpdateEventIsAbsent.check_item());
+    }
+
+    // nameservers
+    if domain
+        .nameservers()
+        .iter()
+        .any(|nameserver| nameserver.ldh_name().is_none())
+    {
+        items.push(Check::IcannNameserverLdhNameIsAbsent.check_item());
+    }
+
+    items
+}
+
+/// Checks that a registrar has an abuse contact reachable by voice phone and email.
+fn abuse_contact_checks(registrar: &Entity) -> Vec<CheckItem> {
+    let mut items = vec![];
+    let Some(abuse) = registrar
+        .entities()
+        .iter()
+        .find(|entity| has_role(entity, "abuse"))
+    else {
+        items.push(Check::IcannAbuseEntityIsAbsent.check_item());
+        return items;
+    };
+    let contact = abuse.contact();
+    // a phone with no features is a voice phone, as that is the default of vCard
+    if !contact.is_some_and(|contact| {
+        contact.phones().iter().any(|phone| {
+            phone.features().is_empty()
+                || phone
+                    .features()
+                    .iter()
+                    .any(|feature| feature.eq_ignore_ascii_case("voice"))
+        })
+    }) {
+        items.push(Check::IcannAbusePhoneIsAbsent.check_item());
+    }
+    if contact.is_none_or(|contact| contact.emails().is_empty()) {
+        items.push(Check::IcannAbuseEmailIsAbsent.check_item());
+    }
+    items
+}
+
+fn has_role(entity: &Entity, role: &str) -> bool {
+    entity
+        .roles()
+        .iter()
+        .any(|entity_role| entity_role.eq_ignore_ascii_case(role))
+}
+
+/// Checks a nameserver that is the root of a lookup against the profile.
+pub(crate) fn nameserver_profile_checks(nameserver: &Nameserver) -> Vec<CheckItem> {
+    let mut items = notice_checks(nameserver.notices());
+    if nameserver.ldh_name().is_none() {
+        items.push(Check::IcannNameserverLdhNameIsAbsent.check_item());
+    }
+    if !nameserver
+        .events()
+        .iter()
+        .any(|event| event.event_action() == Some(RDAP_DATABASE_UPDATE_EVENT))
+    {
+        items.push(Check::IcannRdapDatabaseUpdateEventIsAbsent.check_item());
+    }
+    items
+}
+
+/// Checks that the terms of service are in the notices and that the notices
+/// required to have links have them.
+///
+/// The terms of service are titled "Terms of Service" or "Terms of Use" in practice.
+fn notice_checks(notices: &[Notice]) -> Vec<CheckItem> {
+    let mut items = vec![];
+    let is_terms_of_service = |notice: &&Notice| {
+        notice
+            .0
+            .title()
+            .is_some_and(|title| title.to_ascii_lowercase().starts_with("terms of"))
+    };
+    let terms_of_service = notices.iter().find(is_terms_of_service);
+    if terms_of_service.is_none() {
+        items.push(Check::IcannTermsOfServiceNoticeIsAbsent.check_item());
+    }
+    let requires_link = |notice: &&Notice| {
+        is_terms_of_service(notice)
+            || notice.0.title().is_some_and(|title| {
+                LINKED_NOTICE_TITLES
+                    .iter()
+                    .any(|linked| title.eq_ignore_ascii_case(linked))
+            })
+    };
+    if notices
+        .iter()
+        .filter(requires_link)
+        .any(|notice| notice.0.links().is_empty())
+    {
+        items.push(Check::IcannNoticeLinkIsAbsent.check_item());
+    }
+    items
+}
+
+/// Returns true if the link is to the ICANN page, ignoring the scheme, "www." and a
+/// trailing slash, all of which vary in practice.
+fn links_to(link: &Link, page: &str) -> bool {
+    link.href().is_some_and(|href| {
+        let href = href
+            .trim_start_matches("https://")
+            .trim_start_matches("http://")
+            .trim_start_matches("www.")
+            .trim_end_matches('/');
+        href.eq_ignore_ascii_case(page)
+    })
+}
+
+#[cfg(test)]
+#[allow(non_snake_case)]
+mod tests {
+    use crate::{
+        check::{Check, CheckParams, GetChecks, ProfileId},
+        prelude::*,
+    };
+
+    fn root_checks(rdap: &RdapResponse) -> Vec<Check> {
+        rdap.get_checks(CheckParams::for_rdap(rdap))
+            .items
+            .iter()
+            .map(|item| item.check)
+            .collect()
+    }
+
+    fn is_profile_check(check: &Check) -> bool {
+        matches!(
+            check,
+            Check::IcannStatusCodesNoticeIsAbsent
+                | Check::IcannInaccuracyComplaintNoticeIsAbsent
+                | Check::IcannRegistrarEntityIsAbsent
+                | Check::IcannRegistrarIanaIdIsAbsent
+                | Check::IcannRegistrationEventIsAbsent
+                | Check::IcannExpirationEventIsAbsent
+                | Check::IcannRdapDatabaseUpdateEventIsAbsent
+                | Check::IcannNameserverLdhNameIsAbsent
+                | Check::IcannTermsOfServiceNoticeIsAbsent
+                | Check::IcannNoticeLinkIsAbsent
+                | Check::IcannRegistrantEntityIsAbsent
+                | Check::IcannAbuseEntityIsAbsent
+                | Check::IcannAbusePhoneIsAbsent
+                | Check::IcannAbuseEmailIsAbsent
+        )
+    }
+
+    #[test]
+    fn GIVEN_gtld_sample_with_profile_WHEN_checked_THEN_no_profile_checks() {
+        // GIVEN
+        let rdap: RdapResponse = serde_json::from_str(include_str!(
+            "../response/test_files/domain_home_moscow.json"
+        ))
+        .expect("parsing sample");
+
+        // WHEN
+        let actual = root_checks(&rdap);
+
+        // THEN
+        assert!(!actual.iter().any(is_profile_check));
+    }
+
+    #[test]
+    fn GIVEN_gtld_sample_without_profile_WHEN_checked_THEN_no_profile_checks() {
+        // GIVEN
+        let rdap: RdapResponse = serde_json::from_str(include_str!(
+            "../response/test_files/domain_microsoft_click.json"
+        ))
+        .expect("parsing sample");
+
+        // WHEN
+        let actual = root_checks(&rdap);
+
+        // THEN
+        assert!(!actual.iter().any(is_profile_check));
+    }
+
+    #[test]
+    fn GIVEN_gtld_sample_without_profile_WHEN_checked_for_registrar_THEN_profile_checks() {
+        // GIVEN
+        let rdap: RdapResponse = serde_json::from_str(include_str!(
+            "../response/test_files/domain_microsoft_click.json"
+        ))
+        .expect("parsing sample");
+
+        // WHEN
+        let params = CheckParams {
+            profiles: &[ProfileId::GtldRegistrar],
+            ..CheckParams::for_rdap(&rdap)
+        };
+        let actual = rdap.get_checks(params);
+
+        // THEN
+        assert!(actual
+            .items
+            .iter()
+            .any(|item| item.check == Check::IcannRegistrarIanaIdIsAbsent));
+    }
+
+    #[test]
+    fn GIVEN_bare_domain_with_profile_WHEN_checked_for_no_profiles_THEN_no_profile_checks() {
+        // GIVEN
+        let mut domain = Domain::builder().ldh_name("foo.example").build();
+        domain.common.rdap_conformance =
+            Some(vec![ExtensionId::IcannRdapResponseProfile1.to_extension()]);
+        let rdap = domain.to_response();
+
+        // WHEN
+        let params = CheckParams {
+            profiles: &[],
+            ..CheckParams::for_rdap(&rdap)
+        };
+        let actual = rdap.get_checks(params);
+
+        // THEN
+        assert!(!actual
+            .items
+            .iter()
+            .any(|item| is_profile_check(&item.check)));
+    }
+
+    #[test]
+    fn GIVEN_gtld_sample_with_profile_and_no_registrar_iana_id_WHEN_checked_THEN_iana_id_absent() {
+        // GIVEN
+        let mut rdap: RdapResponse = serde_json::from_str(include_str!(
+            "../response/test_files/domain_microsoft_click.json"
+        ))
+        .expect("parsing sample");
+        let RdapResponse::Domain(domain) = &mut rdap else {
+            panic!("sample is not a domain");
+        };
+        domain
+            .common
+            .rdap_conformance
+            .get_or_insert_with(Vec::new)
+            .push(ExtensionId::IcannRdapResponseProfile1.to_extension());
+
+        // WHEN
+        let actual = root_checks(&rdap);
+
+        // THEN
+        assert_eq!(
+            actual
+                .into_iter()
+                .filter(is_profile_check)
+                .collect::<Vec<_>>(),
+            vec![Check::IcannRegistrarIanaIdIsAbsent]
+        );
+    }
+
+    #[test]
+    fn GIVEN_bare_domain_with_profile_WHEN_checked_THEN_all_domain_profile_checks() {
+        // GIVEN
+        let mut domain = Domain::builder()
+            .ldh_name("foo.example")
+            .nameserver(
+                Nameserver::builder()
+                    .ldh_name("ns.foo.example")
+                    .build()
+                    .unwrap(),
+            )
+            .build();
+        domain.common.rdap_conformance = Some(vec![
+            ExtensionId::RdapLevel0.to_extension(),
+            ExtensionId::IcannRdapResponseProfile1.to_extension(),
+        ]);
+        let rdap = domain.to_response();
+
+        // WHEN
+        let actual = root_checks(&rdap);
+
+        // THEN
+        let actual = actual
+            .into_iter()
+            .filter(is_profile_check)
+            .collect::<Vec<_>>();
+        assert_eq!(
+            actual,
+            vec![
+                Check::IcannStatusCodesNoticeIsAbsent,
+                Check::IcannInaccuracyComplaintNoticeIsAbsent,
+                Check::IcannTermsOfServiceNoticeIsAbsent,
+                Check::IcannRegistrarEntityIsAbsent,
+                Check::IcannRegistrantEntityIsAbsent,
+                Check::IcannRegistrationEventIsAbsent,
+                Check::IcannExpirationEventIsAbsent,
+                Check::IcannRdapDatabaseUpdateEventIsAbsent,
+            ]
+        );
+    }
+
+    #[test]
+    fn GIVEN_nameserver_with_profile_and_no_events_WHEN_checked_THEN_database_update_absent() {
+        // GIVEN
+        let mut nameserver = Nameserver::builder()
+            .ldh_name("ns.foo.example")
+            .build()
+            .unwrap();
+        nameserver.common.rdap_conformance = Some(vec![
+            ExtensionId::RdapLevel0.to_extension(),
+            ExtensionId::IcannRdapResponseProfile1.to_extension(),
+        ]);
+        let rdap = nameserver.to_response();
+
+        // WHEN
+        let actual = root_checks(&rdap);
+
+        // THEN
+        assert!(actual.contains(&Check::IcannRdapDatabaseUpdateEventIsAbsent));
+        assert!(!actual.contains(&Check::IcannNameserverLdhNameIsAbsent));
+        assert!(actual.contains(&Check::IcannTermsOfServiceNoticeIsAbsent));
+    }
+
+    #[test]
+    fn GIVEN_gtld_sample_with_profile_and_unlinked_notice_WHEN_checked_THEN_notice_link_absent() {
+        // GIVEN
+        let mut rdap: RdapResponse = serde_json::from_str(include_str!(
+            "../response/test_files/domain_home_moscow.json"
+        ))
+        .expect("parsing sample");
+        let RdapResponse::Domain(domain) = &mut rdap else {
+            panic!("sample is not a domain");
+        };
+        domain
+            .common
+            .notices
+            .as_mut()
+            .expect("sample has notices")
+            .iter_mut()
+            .filter(|notice| notice.0.title() == Some("Terms of Use"))
+            .for_each(|notice| notice.0.links = None);
+
+        // WHEN
+        let actual = root_checks(&rdap);
+
+        // THEN
+        assert_eq!(
+            actual
+                .into_iter()
+                .filter(is_profile_check)
+                .collect::<Vec<_>>(),
+            vec![Check::IcannNoticeLinkIsAbsent]
+        );
+    }
+
+    #[test]
+    fn GIVEN_gtld_sample_with_profile_and_abuse_without_phone_WHEN_checked_THEN_abuse_phone_absent()
+    {
+        // GIVEN
+        let mut rdap: RdapResponse = serde_json::from_str(include_str!(
+            "../response/test_files/domain_home_moscow.json"
+        ))
+        .expect("parsing sample");
+        let RdapResponse::Domain(domain) = &mut rdap else {
+            panic!("sample is not a domain");
+        };
+        let abuse = Entity::builder()
+            .handle("ABUSE")
+            .role("abuse")
+            .contact(
+                Contact::builder()
+                    .full_name("Abuse")
+                    .email(Email::builder().email("abuse@example.com").build())
+                    .build(),
+            )
+            .build();
+        domain
+            .object_common
+            .entities
+            .as_mut()
+            .expect("sample has entities")
+            .iter_mut()
+            .filter(|entity| entity.roles().iter().any(|role| role == "registrar"))
+            .for_each(|registrar| registrar.object_common.entities = Some(vec![abuse.clone()]));
+
+        // WHEN
+        let actual = root_checks(&rdap);
+
+        // THEN
+        assert_eq!(
+            actual
+                .into_iter()
+                .filter(is_profile_check)
+                .collect::<Vec<_>>(),
+            vec![Check::IcannAbusePhoneIsAbsent]
+        );
+    }
+
+    #[test]
+    fn GIVEN_gtld_sample_with_profile_and_registrar_without_abuse_WHEN_checked_THEN_abuse_absent() {
+        // GIVEN
+        let mut rdap: RdapResponse = serde_json::from_str(include_str!(
+            "../response/test_files/domain_home_moscow.json"
+        ))
+        .expect("parsing sample");
+        let RdapResponse::Domain(domain) = &mut rdap else {
+            panic!("sample is not a domain");
+        };
+        domain
+            .object_common
+            .entities
+            .as_mut()
+            .expect("sample has entities")
+            .iter_mut()
+            .filter(|entity| entity.roles().iter().any(|role| role == "registrar"))
+            .for_each(|registrar| registrar.object_common.entities = None);
+
+        // WHEN
+        let actual = root_checks(&rdap);
+
+        // THEN
+        assert_eq!(
+            actual
+                .into_iter()
+                .filter(is_profile_check)
+                .collect::<Vec<_>>(),
+            vec![Check::IcannAbuseEntityIsAbsent]
+        );
+    }
+}
@@ -9,17 +9,27 @@ use {
     strum_macros::{Display, EnumIter, EnumMessage, EnumString, FromRepr},
 };
 
+#[doc(inline)]
+pub use policy::*;
+#[doc(inline)]
+pub use registry::*;
 #[doc(inline)]
 pub use string::*;
 
 mod autnum;
+mod dnssec;
 mod domain;
 mod entity;
 mod error;
 mod help;
 mod httpdata;
+mod icann_profile;
 mod nameserver;
 mod network;
+mod nro_profile;
+mod policy;
+mod redacted;
+mod registry;
 mod search;
 mod string;
 mod types;
@@ -86,6 +96,12 @@ pub enum CheckClass {
     /// This class represents errors with respect to the gTLD RDAP profile.
     #[strum(serialize = "IcannErr")]
     IcannError,
+
+    /// NRO Profile Errors
+    ///
+    /// This class represents errors with respect to the NRO RDAP profile.
+    #[strum(serialize = "NroErr")]
+    NroError,
 }
 
 /// Represents the name of an RDAP structure for which a check appears.
@@ -100,6 +116,7 @@ pub enum CheckClass {
 #[serde(rename_all = "snake_case")]
 pub enum RdapStructure {
     Autnum,
+    AutnumSearchResults,
     Cidr0,
     Domain,
     DomainSearchResults,
@@ -111,6 +128,7 @@ pub enum RdapStructure {
     Handle,
     HttpData,
     IpNetwork,
+    IpSearchResults,
     Link,
     Links,
     Nameserver,
@@ -124,6 +142,45 @@ pub enum RdapStructure {
     Remarks,
     SecureDns,
     Status,
+    Variants,
+}
+
+/// Identifies a profile of RDAP against which checks are made.
+///
+/// Checks specific to a profile are only found when the profile is given
+/// in [CheckParams].
+#[derive(
+    Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Hash, Display, EnumString, EnumIter,
+)]
+#[strum(serialize_all = "kebab-case")]
+#[serde(rename_all = "kebab-case")]
+pub enum ProfileId {
+    /// The ICANN gTLD RDAP Response Profile, as it applies to registries.
+    GtldRegistry,
+
+    /// The ICANN gTLD RDAP Response Profile, as it applies to registrars.
+    GtldRegistrar,
+
+    /// The NRO RDAP Profile, which only applies to RIRs.
+    Nro,
+}
+
+/// The profiles of a response declaring conformance to the gTLD RDAP Response Profile,
+/// which does not say whether it is from a registry or a registrar.
+const GTLD_PROFILES: &[ProfileId] = &[ProfileId::GtldRegistry, ProfileId::GtldRegistrar];
+
+impl ProfileId {
+    /// Gets the profiles declared by the `rdapConformance` of a response.
+    ///
+    /// The NRO profile is never declared, as RIRs declare its extensions
+    /// regardless of conformance to it.
+    pub fn declared_by(rdap: &RdapResponse) -> &'static [ProfileId] {
+        if icann_profile::has_gtld_profile(rdap) {
+            GTLD_PROFILES
+        } else {
+            &[]
+        }
+    }
 }
 
 /// Contains many [CheckItem] structures and sub checks.
@@ -149,18 +206,29 @@ impl Checks {
 pub struct CheckItem {
     pub check_class: CheckClass,
     pub check: Check,
+    /// The specification, or section of it, from which the check comes,
+    /// such as "RFC 9083 Section 4.2".
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub specification: Option<String>,
 }
 
 impl std::fmt::Display for CheckItem {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let message = self
+            .check
+            .get_message()
+            .unwrap_or("[Check has no description]");
         f.write_fmt(format_args!(
-            "{}:({:0>4}) {}",
+            "{}:({:0>4}) {message}",
             self.check_class,
-            self.check as usize,
-            self.check
-                .get_message()
-                .unwrap_or("[Check has no description]"),
-        ))
+            self.check.code(),
+        ))?;
+        match &self.specification {
+            Some(specification) if !message.contains(specification.as_str()) => {
+                f.write_fmt(format_args!(" [{specification}]"))
+            }
+            _ => Ok(()),
+        }
     }
 }
 
@@ -175,7 +243,10 @@ pub struct CheckParams<'a> {
     pub do_subchecks: bool,
     pub root: &'a RdapResponse,
     pub parent_type: TypeId,
+    /// Allows extensions, and values such as event actions, not registered with IANA.
     pub allow_unreg_ext: bool,
+    /// The profiles to check against, which may be none.
+    pub profiles: &'a [ProfileId],
 }
 
 impl CheckParams<'_> {
@@ -185,17 +256,30 @@ impl CheckParams<'_> {
             root: self.root,
             parent_type,
             allow_unreg_ext: self.allow_unreg_ext,
+            profiles: self.profiles,
         }
     }
 
+    /// Gets the parameters for checking RDAP against the profiles it declares.
     pub fn for_rdap(rdap: &RdapResponse) -> CheckParams<'_> {
         CheckParams {
             do_subchecks: true,
             root: rdap,
             parent_type: rdap.get_type(),
             allow_unreg_ext: false,
+            profiles: ProfileId::declared_by(rdap),
         }
     }
+
+    /// Returns true if checks are made against the profile.
+    pub fn has_profile(&self, profile: ProfileId) -> bool {
+        self.profiles.contains(&profile)
+    }
+
+    /// Returns true if checks are made against the gTLD RDAP Response Profile.
+    pub fn has_gtld_profile(&self) -> bool {
+        self.has_profile(ProfileId::GtldRegistry) || self.has_profile(ProfileId::GtldRegistrar)
+    }
 }
 
 impl GetChecks for RdapResponse {
@@ -209,6 +293,8 @@ impl GetChecks for RdapResponse {
             Self::DomainSearchResults(r) => r.get_checks(params),
             Self::EntitySearchResults(r) => r.get_checks(params),
             Self::NameserverSearchResults(r) => r.get_checks(params),
+            Self::IpSearchResults(r) => r.get_checks(params),
+            Self::AutnumSearchResults(r) => r.get_checks(params),
             Self::ErrorResponse(e) => e.get_checks(params),
             Self::Help(h) => h.get_checks(params),
         }
@@ -261,8 +347,15 @@ pub fn is_checked_item(check: Check, checks: &Checks) -> bool {
 }
 
 /// The variant check types.
+///
+/// Each check has a numeric code, its discriminant, which is stable: the code of
+/// a check does not change between releases, and the code of a removed check is
+/// not given to another. Codes are grouped in ranges of 100 by what is checked,
+/// as commented below. A check is found by its code with [Check::from_code] and
+/// described with [Check::describe].
 #[derive(
     Debug,
+    EnumIter,
     EnumMessage,
     EnumString,
     Display,
@@ -306,10 +399,26 @@ pub enum Check {
     LinkMissingHrefProperty = 207,
     #[strum(message = "ambiguous follow because the 'href' may not contain an RDAP URL")]
     LinkRelatedNotToRdap = 208,
+    #[strum(message = "nameserver without IP addresses has no self link")]
+    LinkNameserverWithoutGlueHasNoSelf = 209,
 
     // Domain Variant 300 - 399
     #[strum(message = "empty domain variant is ambiguous")]
     VariantEmptyDomain = 300,
+    #[strum(message = "domain variant relation may not be registered")]
+    VariantRelationNotRegistered = 301,
+    #[strum(message = "domain variant has no 'idnTable'")]
+    VariantIdnTableIsAbsent = 302,
+    #[strum(message = "variant ldhName does not appear to be an LDH name")]
+    VariantNameLdhNameInvalid = 303,
+    #[strum(message = "variant unicodeName does not appear to be a valid IDN")]
+    VariantNameUnicodeNameInvalid = 304,
+    #[strum(message = "variant unicodeName does not match ldhName")]
+    VariantNameLdhDoesNotMatchUnicode = 305,
+    #[strum(message = "variant ldhName has an A-label that is not valid punycode")]
+    VariantNameALabelInvalid = 306,
+    #[strum(message = "variant name is the name of the domain")]
+    VariantNameIsDomainName = 307,
 
     // Event 400 - 499
     #[strum(message = "event date is absent")]
@@ -318,12 +427,16 @@ pub enum Check {
     EventDateIsNotRfc3339 = 401,
     #[strum(message = "event action is absent")]
     EventActionIsAbsent = 402,
+    #[strum(message = "event action is not registered with IANA")]
+    UnknownEventAction = 403,
 
     // Notice Or Remark 500 - 599
     #[strum(message = "RFC 9083 requires a description in a notice or remark")]
     NoticeOrRemarkDescriptionIsAbsent = 500,
     #[strum(message = "RFC 9083 requires a description to be an array of strings")]
     NoticeOrRemarkDescriptionIsString = 501,
+    #[strum(message = "notice or remark type is not registered with IANA")]
+    UnknownNoticeOrRemarkType = 502,
 
     // Handle 600 - 699
     #[strum(message = "handle appears to be empty or only whitespace")]
@@ -336,6 +449,10 @@ pub enum Check {
     // Status 700 - 799
     #[strum(message = "status appears to be empty or only whitespace")]
     StatusIsEmpty = 700,
+    #[strum(message = "status is not registered with IANA")]
+    UnknownStatusValue = 701,
+    #[strum(message = "status is an EPP status instead of its RDAP status")]
+    StatusIsEppStatus = 702,
 
     // Role 800 - 899
     #[strum(message = "role appears to be empty or only whitespace")]
@@ -344,6 +461,8 @@ pub enum Check {
     UnknownRole = 801,
     #[strum(message = "role is a string, not array of strings")]
     RoleIsString = 802,
+    #[strum(message = "entity with the same handle and role appears more than once")]
+    RoleIsDuplicated = 803,
 
     // LDH Name 900 - 999
     #[strum(message = "ldhName does not appear to be an LDH name")]
@@ -404,6 +523,8 @@ pub enum Check {
     IpAddressArrayIsString = 1314,
     #[strum(message = "IP version is not a string")]
     IpVersionIsNotString = 1315,
+    #[strum(message = "IP address is not within the networks linked by the nameserver")]
+    IpAddressNotInLinkedNetwork = 1316,
 
     // Autnum 1400 - 1499
     #[strum(message = "start or end autnum is missing")]
@@ -416,6 +537,8 @@ pub enum Check {
     AutnumDocumentation = 1403,
     #[strum(message = "Reserved AS number. See RFC 6996")]
     AutnumReserved = 1404,
+    #[strum(message = "start or end AS number exceeds 32-bit bounds")]
+    AutnumExceeds32Bits = 1405,
 
     // Vcard 1500 - 1599
     #[strum(message = "vCard array does not contain a vCard")]
@@ -450,6 +573,16 @@ pub enum Check {
     ContentTypeIsAbsent = 1803,
     #[strum(message = "Content-type is not application/rdap+json.")]
     ContentTypeIsNotRdap = 1804,
+    #[strum(message = "Content-type is application/json instead of application/rdap+json.")]
+    ContentTypeIsJson = 1805,
+    #[strum(message = "Content-type charset is not UTF-8.")]
+    ContentTypeCharsetIsNotUtf8 = 1806,
+    #[strum(message = "A redirect is from HTTPS to HTTP.")]
+    RedirectDowngradesToHttp = 1807,
+    #[strum(message = "Redirects loop back to a URL already requested.")]
+    RedirectLoop = 1808,
+    #[strum(message = "Response is not compressed.")]
+    ResponseIsNotCompressed = 1809,
 
     // Cidr0 1900 - 1999
     #[strum(message = "Cidr0 v4 prefix is absent")]
@@ -466,6 +599,36 @@ pub enum Check {
     MustUseHttps = 2000,
     #[strum(message = "access-control-allow-origin is not asterisk")]
     AllowOriginNotStar = 2001,
+    #[strum(message = "notice linking to the status codes at https://icann.org/epp is absent")]
+    IcannStatusCodesNoticeIsAbsent = 2002,
+    #[strum(
+        message = "notice linking to the inaccuracy complaint form at https://icann.org/wicf is absent"
+    )]
+    IcannInaccuracyComplaintNoticeIsAbsent = 2003,
+    #[strum(message = "entity with the registrar role is absent")]
+    IcannRegistrarEntityIsAbsent = 2004,
+    #[strum(message = "registrar has no 'IANA Registrar ID' publicId")]
+    IcannRegistrarIanaIdIsAbsent = 2005,
+    #[strum(message = "registration event is absent")]
+    IcannRegistrationEventIsAbsent = 2006,
+    #[strum(message = "expiration event is absent")]
+    IcannExpirationEventIsAbsent = 2007,
+    #[strum(message = "'last update of RDAP database' event is absent")]
+    IcannRdapDatabaseUpdateEventIsAbsent = 2008,
+    #[strum(message = "nameserver ldhName is absent")]
+    IcannNameserverLdhNameIsAbsent = 2009,
+    #[strum(message = "notice with the terms of service is absent")]
+    IcannTermsOfServiceNoticeIsAbsent = 2010,
+    #[strum(message = "notice required by the gTLD profile has no link")]
+    IcannNoticeLinkIsAbsent = 2011,
+    #[strum(message = "entity with the registrant role is absent")]
+    IcannRegistrantEntityIsAbsent = 2012,
+    #[strum(message = "registrar has no entity with the abuse role")]
+    IcannAbuseEntityIsAbsent = 2013,
+    #[strum(message = "abuse contact has no voice phone")]
+    IcannAbusePhoneIsAbsent = 2014,
+    #[strum(message = "abuse contact has no email")]
+    IcannAbuseEmailIsAbsent = 2015,
 
     // Explicit Testing Errors 2100 - 2199
     #[strum(message = "CNAME without A records.")]
@@ -480,6 +643,16 @@ pub enum Check {
     ExpectedExtensionNotFound = 2104,
     #[strum(message = "IPv6 Support Required.")]
     Ipv6SupportRequiredByIcann = 2105,
+    #[strum(message = "TLS version is below 1.2.")]
+    TlsVersionIsBelow12 = 2106,
+    #[strum(message = "TLS certificate chain is not valid.")]
+    TlsCertificateChainIsInvalid = 2107,
+    #[strum(message = "TLS certificate does not cover the host name.")]
+    TlsCertificateDoesNotCoverHost = 2108,
+    #[strum(message = "TLS certificate is expired.")]
+    TlsCertificateIsExpired = 2109,
+    #[strum(message = "TLS certificate expires soon.")]
+    TlsCertificateExpiresSoon = 2110,
 
     // Secure DNS 2200 - 2299
     #[strum(message = "delegationSigned is a string not a bool.")]
@@ -501,6 +674,8 @@ pub enum Check {
     KeyDatumProtocolIsString = 2207,
     #[strum(message = "keyData protocol is out of range.")]
     KeyDatumProtocolIsOutOfRange = 2208,
+    #[strum(message = "keyData publicKey is not base64.")]
+    KeyDatumPublicKeyIsNotBase64 = 2209,
     // ds data
     #[strum(message = "dsData algorithm is a string not a number.")]
     DsDatumAlgorithmIsString = 2213,
@@ -514,10 +689,56 @@ pub enum Check {
     DsDatumDigestTypeIsString = 2217,
     #[strum(message = "dsData digestType is out of range.")]
     DsDatumDigestTypeIsOutOfRange = 2218,
+    #[strum(message = "dsData digest is not hexadecimal.")]
+    DsDatumDigestIsNotHex = 2219,
+    #[strum(message = "dsData digest length does not match the digestType.")]
+    DsDatumDigestLengthMismatch = 2220,
+    #[strum(message = "dsData does not match the DS computed from keyData.")]
+    DsDatumDoesNotMatchKeyData = 2221,
 
     // Network or Autnum Country 2300 - 2399
     #[strum(message = "country is not a string")]
     NetworkOrAutnumCountryIsNotString = 2300,
+
+    // Redacted 2400 - 2499
+    #[strum(message = "redaction name has neither a type nor a description")]
+    RedactedNameIsEmpty = 2400,
+    #[strum(message = "redaction name type may not be registered")]
+    RedactedNameNotRegistered = 2401,
+    #[strum(message = "redaction method is not defined by RFC 9537")]
+    RedactedMethodUnrecognized = 2402,
+    #[strum(message = "prePath is not a valid JSONPath expression")]
+    RedactedPrePathInvalid = 2403,
+    #[strum(message = "postPath is not a valid JSONPath expression")]
+    RedactedPostPathInvalid = 2404,
+    #[strum(message = "replacementPath is not a valid JSONPath expression")]
+    RedactedReplacementPathInvalid = 2405,
+    #[strum(message = "postPath is required by the redaction method")]
+    RedactedPostPathIsAbsent = 2406,
+    #[strum(message = "prePath is only used with the removal method")]
+    RedactedPrePathNotRemoval = 2407,
+    #[strum(message = "postPath does not reference anything in the response")]
+    RedactedPostPathNotFound = 2408,
+    #[strum(message = "emptyValue redaction references a value that is not empty")]
+    RedactedEmptyValueIsNotEmpty = 2409,
+    #[strum(message = "partialValue redaction references a value that is empty")]
+    RedactedPartialValueIsEmpty = 2410,
+    #[strum(message = "redaction references a value that is not a string")]
+    RedactedValueIsNotString = 2411,
+
+    // NRO Profile 2500 - 2599
+    #[strum(message = "cidr0_cidrs is absent")]
+    NroCidr0IsAbsent = 2500,
+    #[strum(message = "cidr0_cidrs do not match the start and end addresses")]
+    NroCidr0DoesNotMatchAddresses = 2501,
+    #[strum(message = "status is absent")]
+    NroStatusIsAbsent = 2502,
+    #[strum(message = "entity with the registrant role is absent")]
+    NroRegistrantEntityIsAbsent = 2503,
+    #[strum(message = "registrant handle is absent")]
+    NroRegistrantHandleIsAbsent = 2504,
+    #[strum(message = "both the flat and hierarchical ASN profiles are declared")]
+    NroAsnFlatAndHierarchical = 2505,
 }
 
 impl Check {
@@ -535,9 +756,20 @@ impl Check {
             | Self::LinkSelfIsNotRdap => CheckClass::StdWarning,
             Self::LinkObjectClassHasNoSelf => CheckClass::SpecificationNote,
             Self::LinkMissingHrefProperty => CheckClass::StdError,
-            Self::LinkRelatedNotToRdap => CheckClass::StdWarning,
+            Self::LinkRelatedNotToRdap | Self::LinkNameserverWithoutGlueHasNoSelf => {
+                CheckClass::StdWarning
+            }
 
-            Self::VariantEmptyDomain => CheckClass::StdWarning,
+            Self::VariantEmptyDomain
+            | Self::VariantRelationNotRegistered
+            | Self::VariantIdnTableIsAbsent => CheckClass::StdWarning,
+            Self::VariantNameLdhNameInvalid | Self::VariantNameUnicodeNameInvalid => {
+                CheckClass::StdError
+            }
+            Self::VariantNameLdhDoesNotMatchUnicode | Self::VariantNameIsDomainName => {
+                CheckClass::StdWarning
+            }
+            Self::VariantNameALabelInvalid => CheckClass::StdError,
 
             Self::EventDateIsAbsent
             | Self::EventDateIsNotRfc3339
@@ -545,12 +777,15 @@ impl Check {
             | Self::NoticeOrRemarkDescriptionIsAbsent
             | Self::NoticeOrRemarkDescriptionIsString => CheckClass::StdError,
 
+            Self::UnknownEventAction | Self::UnknownNoticeOrRemarkType => CheckClass::StdWarning,
+
             Self::HandleIsEmpty => CheckClass::StdWarning,
             Self::HandleIsNotString => CheckClass::StdError,
             Self::ParentHandleIsNotString => CheckClass::StdError,
 
             Self::StatusIsEmpty | Self::RoleIsEmpty => CheckClass::StdError,
-            Self::UnknownRole => CheckClass::StdWarning,
+            Self::UnknownStatusValue | Self::StatusIsEppStatus => CheckClass::StdWarning,
+            Self::UnknownRole | Self::RoleIsDuplicated => CheckClass::StdWarning,
             Self::RoleIsString | Self::LdhNameInvalid => CheckClass::StdError,
             Self::LdhNameDocumentation => CheckClass::Informational,
             Self::LdhNameDoesNotMatchUnicode => CheckClass::StdWarning,
@@ -579,11 +814,13 @@ impl Check {
             | Self::IpAddressReservedNet => CheckClass::Informational,
             Self::IpAddressArrayIsString => CheckClass::StdError,
             Self::IpVersionIsNotString => CheckClass::StdError,
+            Self::IpAddressNotInLinkedNetwork => CheckClass::StdWarning,
 
             Self::AutnumMissing | Self::AutnumEndBeforeStart => CheckClass::StdWarning,
             Self::AutnumPrivateUse | Self::AutnumDocumentation | Self::AutnumReserved => {
                 CheckClass::Informational
             }
+            Self::AutnumExceeds32Bits => CheckClass::StdError,
 
             Self::VcardArrayIsEmpty | Self::VcardHasNoFn => CheckClass::StdError,
             Self::VcardFnIsEmpty => CheckClass::SpecificationNote,
@@ -597,19 +834,45 @@ impl Check {
             Self::CorsAllowOriginRecommended
             | Self::CorsAllowOriginStarRecommended
             | Self::CorsAllowCredentialsNotRecommended => CheckClass::StdWarning,
-            Self::ContentTypeIsAbsent | Self::ContentTypeIsNotRdap => CheckClass::StdError,
+            Self::ContentTypeIsAbsent
+            | Self::ContentTypeIsNotRdap
+            | Self::ContentTypeCharsetIsNotUtf8
+            | Self::RedirectDowngradesToHttp
+            | Self::RedirectLoop => CheckClass::StdError,
+            Self::ContentTypeIsJson => CheckClass::StdWarning,
+            Self::ResponseIsNotCompressed => CheckClass::Informational,
 
             Self::Cidr0V4PrefixIsAbsent
             | Self::Cidr0V4LengthIsAbsent
             | Self::Cidr0V6PrefixIsAbsent
             | Self::Cidr0V6LengthIsAbsent => CheckClass::Cidr0Error,
 
-            Self::MustUseHttps | Self::AllowOriginNotStar => CheckClass::IcannError,
+            Self::MustUseHttps
+            | Self::AllowOriginNotStar
+            | Self::IcannStatusCodesNoticeIsAbsent
+            | Self::IcannInaccuracyComplaintNoticeIsAbsent
+            | Self::IcannRegistrarEntityIsAbsent
+            | Self::IcannRegistrarIanaIdIsAbsent
+            | Self::IcannRegistrationEventIsAbsent
+            | Self::IcannExpirationEventIsAbsent
+            | Self::IcannRdapDatabaseUpdateEventIsAbsent
+            | Self::IcannNameserverLdhNameIsAbsent
+            | Self::IcannTermsOfServiceNoticeIsAbsent
+            | Self::IcannNoticeLinkIsAbsent
+            | Self::IcannRegistrantEntityIsAbsent
+            | Self::IcannAbuseEntityIsAbsent
+            | Self::IcannAbusePhoneIsAbsent
+            | Self::IcannAbuseEmailIsAbsent => CheckClass::IcannError,
 
             Self::CnameWithoutARecords | Self::CnameWithoutAAAARecords => CheckClass::StdError,
             Self::NoARecords | Self::NoAAAARecords => CheckClass::SpecificationNote,
             Self::ExpectedExtensionNotFound => CheckClass::StdError,
             Self::Ipv6SupportRequiredByIcann => CheckClass::IcannError,
+            Self::TlsVersionIsBelow12
+            | Self::TlsCertificateChainIsInvalid
+            | Self::TlsCertificateDoesNotCoverHost
+            | Self::TlsCertificateIsExpired => CheckClass::StdError,
+            Self::TlsCertificateExpiresSoon => CheckClass::StdWarning,
 
             Self::DelegationSignedIsString
             | Self::ZoneSignedIsString
@@ -625,13 +888,37 @@ impl Check {
             | Self::DsDatumKeyTagIsString
             | Self::DsDatumKeyTagIsOutOfRange
             | Self::DsDatumDigestTypeIsString
-            | Self::DsDatumDigestTypeIsOutOfRange => CheckClass::StdError,
+            | Self::DsDatumDigestTypeIsOutOfRange
+            | Self::KeyDatumPublicKeyIsNotBase64
+            | Self::DsDatumDigestIsNotHex
+            | Self::DsDatumDigestLengthMismatch => CheckClass::StdError,
+            Self::DsDatumDoesNotMatchKeyData => CheckClass::StdWarning,
 
             Self::NetworkOrAutnumCountryIsNotString => CheckClass::StdError,
+
+            Self::RedactedNameIsEmpty | Self::RedactedMethodUnrecognized => CheckClass::StdError,
+            Self::RedactedNameNotRegistered => CheckClass::StdWarning,
+            Self::RedactedPrePathInvalid
+            | Self::RedactedPostPathInvalid
+            | Self::RedactedReplacementPathInvalid
+            | Self::RedactedPostPathIsAbsent => CheckClass::StdError,
+            Self::RedactedPrePathNotRemoval
+            | Self::RedactedPostPathNotFound
+            | Self::RedactedEmptyValueIsNotEmpty
+            | Self::RedactedPartialValueIsEmpty
+            | Self::RedactedValueIsNotString => CheckClass::StdWarning,
+
+            Self::NroCidr0IsAbsent
+            | Self::NroCidr0DoesNotMatchAddresses
+            | Self::NroStatusIsAbsent
+            | Self::NroRegistrantEntityIsAbsent
+            | Self::NroRegistrantHandleIsAbsent
+            | Self::NroAsnFlatAndHierarchical => CheckClass::NroError,
         };
         CheckItem {
             check_class,
             check: self,
+            specification: Some(self.citation().to_string()),
         }
     }
 }
@@ -651,6 +938,7 @@ mod tests {
             items: vec![CheckItem {
                 check_class: CheckClass::Informational,
                 check: Check::VariantEmptyDomain,
+                specification: None,
             }],
             sub_checks: vec![],
         };
@@ -675,6 +963,7 @@ mod tests {
             items: vec![CheckItem {
                 check_class: CheckClass::StdWarning,
                 check: Check::VariantEmptyDomain,
+                specification: None,
             }],
             sub_checks: vec![],
         };
@@ -702,6 +991,7 @@ mod tests {
                 items: vec![CheckItem {
                     check_class: CheckClass::Informational,
                     check: Check::VariantEmptyDomain,
+                    specification: None,
                 }],
                 sub_checks: vec![],
             }],
@@ -730,6 +1020,7 @@ mod tests {
                 items: vec![CheckItem {
                     check_class: CheckClass::StdWarning,
                     check: Check::VariantEmptyDomain,
+                    specification: None,
                 }],
                 sub_checks: vec![],
             }],
@@ -755,12 +1046,14 @@ mod tests {
             items: vec![CheckItem {
                 check_class: CheckClass::Informational,
                 check: Check::RdapConformanceInvalidParent,
+                specification: None,
             }],
             sub_checks: vec![Checks {
                 rdap_struct: RdapStructure::Autnum,
                 items: vec![CheckItem {
                     check_class: CheckClass::Informational,
                     check: Check::VariantEmptyDomain,
+                    specification: None,
                 }],
                 sub_checks: vec![],
             }],
@@ -781,4 +1074,51 @@ mod tests {
         assert!(structs.contains(&"[ROOT]/entity".to_string()));
         assert!(structs.contains(&"[ROOT]/entity/autnum".to_string()));
     }
+
+    #[test]
+    fn GIVEN_check_WHEN_check_item_THEN_specification_cited() {
+        // GIVEN
+        let check = Check::IpAddressEndBeforeStart;
+
+        // WHEN
+        let item = check.check_item();
+
+        // THEN
+        assert_eq!(item.specification.as_deref(), Some("RFC 9083 Section 5.4"));
+        assert_eq!(
+            item.to_string(),
+            "StdWarn:(1302) end IP address comes before start IP address [RFC 9083 Section 5.4]"
+        );
+    }
+
+    #[test]
+    fn GIVEN_message_naming_specification_WHEN_displayed_THEN_not_repeated() {
+        // GIVEN
+        let item = Check::LdhNameDocumentation.check_item();
+
+        // WHEN
+        let actual = item.to_string();
+
+        // THEN
+        assert_eq!(
+            actual,
+            "Info:(0901) Documentation domain name. See RFC 6761"
+        );
+    }
+
+    #[test]
+    fn GIVEN_item_json_without_specification_WHEN_deserialized_THEN_no_specification() {
+        // GIVEN
+        let json = r#"{"check_class":"std_warning","check":"ip_address_end_before_start"}"#;
+
+        // WHEN
+        let item: CheckItem = serde_json::from_str(json).expect("deserializing check item");
+
+        // THEN
+        assert!(item.specification.is_none());
+        assert_eq!(
+            item.to_string(),
+            "StdWarn:(1302) end IP address comes before start IP address"
+        );
+    }
 }
@@ -1,8 +1,11 @@
 use std::{any::TypeId, net::IpAddr, str::FromStr};
 
-use crate::response::nameserver::Nameserver;
+use ipnet::IpNet;
+
+use crate::response::{domain::Domain, nameserver::Nameserver, Link};
 
 use super::{
+    icann_profile::nameserver_profile_checks,
     string::{StringCheck, StringListCheck},
     Check, CheckParams, Checks, GetChecks, GetSubChecks,
 };
@@ -32,7 +35,12 @@ impl GetChecks for Nameserver {
             }
         }
 
-        if let Some(ip_addresses) = &self.ip_addresses {
+        // addresses validated by the builder need no checking
+        if let Some(ip_addresses) = self
+            .ip_addresses
+            .as_ref()
+            .filter(|ip_addresses| !ip_addresses.is_known_valid())
+        {
             if let Some(v6_addrs) = &ip_addresses.v6 {
                 if v6_addrs.is_string() {
                     items.push(Check::IpAddressArrayIsString.check_item())
@@ -40,13 +48,17 @@ impl GetChecks for Nameserver {
                 if v6_addrs.is_empty_or_any_empty_or_whitespace() {
                     items.push(Check::IpAddressListIsEmpty.check_item())
                 }
-                if v6_addrs
+                let ips = v6_addrs
                     .vec()
                     .iter()
-                    .any(|ip| IpAddr::from_str(ip).is_err())
-                {
+                    .map(|ip| IpAddr::from_str(ip))
+                    .collect::<Vec<_>>();
+                if ips.iter().any(Result::is_err) {
                     items.push(Check::IpAddressMalformed.check_item())
                 }
+                if ips.iter().flatten().any(IpAddr::is_ipv4) {
+                    items.push(Check::IpAddressVersionMismatch.check_item())
+                }
             }
             if let Some(v4_addrs) = &ip_addresses.v4 {
                 if v4_addrs.is_string() {
@@ -55,16 +67,51 @@ impl GetChecks for Nameserver {
                 if v4_addrs.is_empty_or_any_empty_or_whitespace() {
                     items.push(Check::IpAddressListIsEmpty.check_item())
                 }
-                if v4_addrs
+                let ips = v4_addrs
                     .vec()
                     .iter()
-                    .any(|ip| IpAddr::from_str(ip).is_err())
-                {
+                    .map(|ip| IpAddr::from_str(ip))
+                    .collect::<Vec<_>>();
+                if ips.iter().any(Result::is_err) {
                     items.push(Check::IpAddressMalformed.check_item())
                 }
+                if ips.iter().flatten().any(IpAddr::is_ipv6) {
+                    items.push(Check::IpAddressVersionMismatch.check_item())
+                }
+            }
+        }
+
+        // addresses should be within the networks the nameserver links to
+        if let Some(ip_addresses) = &self.ip_addresses {
+            let networks = linked_networks(self.object_common.links.as_deref().unwrap_or_default());
+            if !networks.is_empty()
+                && ip_addresses
+                    .v4s()
+                    .iter()
+                    .chain(ip_addresses.v6s())
+                    .filter_map(|ip| IpAddr::from_str(ip).ok())
+                    .any(|ip| !networks.iter().any(|network| network.contains(&ip)))
+            {
+                items.push(Check::IpAddressNotInLinkedNetwork.check_item())
             }
         }
 
+        // without glue, a nameserver of a domain can only be followed with its self link
+        let has_glue = self.ip_addresses.as_ref().is_some_and(|ip_addresses| {
+            !ip_addresses.v4s().is_empty() || !ip_addresses.v6s().is_empty()
+        });
+        if params.parent_type == TypeId::of::<Domain>()
+            && !has_glue
+            && self.object_common.get_self_link().is_none()
+        {
+            items.push(Check::LinkNameserverWithoutGlueHasNoSelf.check_item())
+        }
+
+        // check the gTLD profile of lookups
+        if params.parent_type == params.root.get_type() && params.has_gtld_profile() {
+            items.append(&mut nameserver_profile_checks(self));
+        }
+
         Checks {
             rdap_struct: super::RdapStructure::Nameserver,
             items,
@@ -73,6 +120,26 @@ impl GetChecks for Nameserver {
     }
 }
 
+/// Gets the networks of the links to RDAP IP network lookups,
+/// such as `https://rdap.example/ip/192.0.2.0/24`.
+fn linked_networks(links: &[Link]) -> Vec<IpNet> {
+    links
+        .iter()
+        .filter_map(Link::href)
+        .filter_map(|href| href.rsplit_once("/ip/"))
+        .filter_map(|(_, network)| {
+            let network = network
+                .split(['?', '#'])
+                .next()
+                .unwrap_or_default()
+                .trim_end_matches('/');
+            IpNet::from_str(network)
+                .ok()
+                .or_else(|| IpAddr::from_str(network).ok().map(IpNet::from))
+        })
+        .collect()
+}
+
 #[cfg(test)]
 mod tests {
     use {crate::prelude::*, rstest::rstest};
@@ -181,4 +248,140 @@ mod tests {
             .iter()
             .any(|c| c.check == Check::IpAddressMalformed));
     }
+
+    #[test]
+    fn check_nameserver_with_v6_in_v4s() {
+        // GIVEN
+        let ns = Nameserver::illegal()
+            .ldh_name("ns1.example.com")
+            .ip_addresses(
+                IpAddresses::illegal()
+                    .v4(vec!["2001:db8::1".to_string()])
+                    .build(),
+            )
+            .build()
+            .to_response();
+
+        // WHEN
+        let checks = ns.get_checks(CheckParams::for_rdap(&ns));
+
+        // THEN
+        dbg!(&checks);
+        assert!(checks
+            .items
+            .iter()
+            .any(|c| c.check == Check::IpAddressVersionMismatch));
+    }
+
+    #[test]
+    fn check_nameserver_with_built_addresses() {
+        // GIVEN
+        let ns = Nameserver::builder()
+            .ldh_name("ns1.example.com")
+            .address("192.0.2.1")
+            .address("2001:db8::1")
+            .build()
+            .unwrap()
+            .to_response();
+
+        // WHEN
+        let checks = ns.get_checks(CheckParams::for_rdap(&ns));
+
+        // THEN
+        dbg!(&checks);
+        assert!(!checks
+            .items
+            .iter()
+            .any(|c| c.check == Check::IpAddressMalformed
+                || c.check == Check::IpAddressVersionMismatch));
+    }
+
+    #[rstest]
+    #[case("192.0.2.1", "https://rdap.example/ip/192.0.2.0/24", false)]
+    #[case("2001:db8::1", "https://rdap.example/ip/2001:db8::/32", false)]
+    #[case("192.0.2.1", "https://rdap.example/ip/192.0.2.1", false)]
+    #[case("198.51.100.1", "https://rdap.example/ip/192.0.2.0/24", true)]
+    #[case("2001:db9::1", "https://rdap.example/ip/2001:db8::/32", true)]
+    #[case("198.51.100.1", "https://rdap.example/domain/example.com", false)]
+    fn check_nameserver_addresses_within_linked_networks(
+        #[case] address: &str,
+        #[case] href: &str,
+        #[case] expected: bool,
+    ) {
+        // GIVEN
+        let ns = Nameserver::builder()
+            .ldh_name("ns1.example.com")
+            .address(address)
+            .link(
+                Link::builder()
+                    .value("https://rdap.example/nameserver/ns1.example.com")
+                    .href(href)
+                    .rel("related")
+                    .media_type("application/rdap+json")
+                    .build(),
+            )
+            .build()
+            .unwrap()
+            .to_response();
+
+        // WHEN
+        let checks = ns.get_checks(CheckParams::for_rdap(&ns));
+
+        // THEN
+        dbg!(&checks);
+        assert_eq!(
+            checks
+                .items
+                .iter()
+                .any(|c| c.check == Check::IpAddressNotInLinkedNetwork),
+            expected
+        );
+    }
+
+    #[rstest]
+    #[case(None, false, true)]
+    #[case(Some("192.0.2.1"), false, false)]
+    #[case(None, true, false)]
+    fn check_domain_nameserver_without_glue_has_self_link(
+        #[case] address: Option<&str>,
+        #[case] self_link: bool,
+        #[case] expected: bool,
+    ) {
+        // GIVEN
+        let mut ns = Nameserver::builder().ldh_name("ns1.example.com");
+        if let Some(address) = address {
+            ns = ns.address(address);
+        }
+        if self_link {
+            ns = ns.link(
+                Link::builder()
+                    .value("https://rdap.example/domain/example.com")
+                    .href("https://rdap.example/nameserver/ns1.example.com")
+                    .rel("self")
+                    .media_type("application/rdap+json")
+                    .build(),
+            );
+        }
+        let rdap = Domain::builder()
+            .ldh_name("example.com")
+            .nameserver(ns.build().unwrap())
+            .build()
+            .to_response();
+
+        // WHEN
+        let checks = rdap.get_checks(CheckParams::for_rdap(&rdap));
+
+        // THEN
+        dbg!(&checks);
+        let ns_checks = checks
+            .sub(crate::check::RdapStructure::Nameserver)
+            .expect("nameserver checks");
+        assert_eq!(
+            ns_checks
+                .items
+                .iter()
+                .any(|c| c.check == Check::LinkNameserverWithoutGlueHasNoSelf),
+            expected
+        );
+    }
 }
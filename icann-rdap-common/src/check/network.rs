@@ -4,7 +4,10 @@ use cidr::IpCidr;
 
 use crate::response::network::{Cidr0Cidr, Network};
 
-use super::{string::StringCheck, Check, CheckParams, Checks, GetChecks, GetSubChecks};
+use super::{
+    nro_profile::network_profile_checks, string::StringCheck, Check, CheckParams, Checks,
+    GetChecks, GetSubChecks, ProfileId,
+};
 
 impl GetChecks for Network {
     fn get_checks(&self, params: CheckParams) -> super::Checks {
@@ -189,6 +192,11 @@ impl GetChecks for Network {
             }
         }
 
+        // check the NRO profile of lookups
+        if params.has_profile(ProfileId::Nro) && params.parent_type == params.root.get_type() {
+            items.append(&mut network_profile_checks(self));
+        }
+
         Checks {
             rdap_struct: super::RdapStructure::IpNetwork,
             items,
@@ -0,0 +1,323 @@
+//! Checks of the NRO RDAP Profile.
+
+use std::{net::IpAddr, str::FromStr};
+
+use cidr::IpCidr;
+
+use crate::response::{
+    autnum::Autnum,
+    network::{Cidr0Cidr, Network},
+    ExtensionId, ObjectCommonFields, RdapResponse,
+};
+
+use super::{Check, CheckItem};
+
+/// Checks a network that is the root of a lookup against the profile.
+pub(crate) fn network_profile_checks(network: &Network) -> Vec<CheckItem> {
+    let mut items = vec![];
+    if network.cidr0_cidrs().is_empty() {
+        items.push(Check::NroCidr0IsAbsent.check_item());
+    } else if !cidr0_matches_addresses(network) {
+        items.push(Check::NroCidr0DoesNotMatchAddresses.check_item());
+    }
+    items.append(&mut registration_checks(network));
+    items
+}
+
+/// Checks an autnum that is the root of a lookup against the profile.
+pub(crate) fn autnum_profile_checks(autnum: &Autnum, root: &RdapResponse) -> Vec<CheckItem> {
+    let mut items = registration_checks(autnum);
+    if root.has_extension_id(ExtensionId::NroRdapProfileAsnFlat0)
+        && root.has_extension_id(ExtensionId::NroRdapProfileAsnHierarchical0)
+    {
+        items.push(Check::NroAsnFlatAndHierarchical.check_item());
+    }
+    items
+}
+
+/// Checks required of both networks and autnums.
+fn registration_checks<T: ObjectCommonFields>(object: &T) -> Vec<CheckItem> {
+    let mut items = vec![];
+    if object.status().is_empty() {
+        items.push(Check::NroStatusIsAbsent.check_item());
+    }
+    let registrant = object
+        .entities()
+        .iter()
+        .find(|entity| entity.roles().iter().any(|role| role == "registrant"));
+    match registrant {
+        None => items.push(Check::NroRegistrantEntityIsAbsent.check_item()),
+        Some(registrant) => {
+            if registrant.handle().is_none() {
+                items.push(Check::NroRegistrantHandleIsAbsent.check_item());
+            }
+        }
+    }
+    items
+}
+
+/// Returns true if the CIDRs, taken together, cover exactly the start to the end
+/// address of the network.
+///
+/// CIDRs without a prefix or length are checked as cidr0 errors, so they are
+/// not taken as a mismatch here.
+fn cidr0_matches_addresses(network: &Network) -> bool {
+    let (Some(start), Some(end)) = (
+        network
+            .start_address()
+            .and_then(|a| IpAddr::from_str(a).ok()),
+        network.end_address().and_then(|a| IpAddr::from_str(a).ok()),
+    ) else {
+        return true;
+    };
+    let mut ranges = vec![];
+    for cidr in network.cidr0_cidrs() {
+        let (prefix, length) = match cidr {
+            Cidr0Cidr::V4Cidr(v4) => (v4.v4prefix(), v4.length()),
+            Cidr0Cidr::V6Cidr(v6) => (v6.v6prefix(), v6.length()),
+        };
+        let (Some(prefix), Some(length)) = (prefix, length) else {
+            return true;
+        };
+        let Some(cidr) = IpAddr::from_str(prefix)
+            .ok()
+            .and_then(|prefix| IpCidr::new(prefix, length).ok())
+        else {
+            return false;
+        };
+        if cidr.is_ipv4() != start.is_ipv4() {
+            return false;
+        }
+        ranges.push((
+            addr_value(cidr.first_address()),
+            addr_value(cidr.last_address()),
+        ));
+    }
+    ranges.sort();
+    let mut next = addr_value(start);
+    for (first, last) in ranges {
+        if first != next {
+            return false;
+        }
+        next = last.wrapping_add(1);
+    }
+    next == addr_value(end).wrapping_add(1)
+}
+
+fn addr_value(addr: IpAddr) -> u128 {
+    match addr {
+        IpAddr::V4(v4) => u128::from(u32::from(v4)),
+        IpAddr::V6(v6) => u128::from(v6),
+    }
+}
+
+#[cfg(test)]
+#[allow(non_snake_case)]
+mod tests {
+    use crate::{
+        check::{Check, CheckParams, GetChecks, ProfileId},
+        prelude::*,
+        response::network::{Cidr0Cidr, V4Cidr},
+    };
+
+    fn nro_checks(rdap: &RdapResponse) -> Vec<Check> {
+        let params = CheckParams {
+            profiles: &[ProfileId::Nro],
+            ..CheckParams::for_rdap(rdap)
+        };
+        rdap.get_checks(params)
+            .items
+            .iter()
+            .map(|item| item.check)
+            .collect()
+    }
+
+    fn is_nro_check(check: &Check) -> bool {
+        matches!(
+            check,
+            Check::NroCidr0IsAbsent
+                | Check::NroCidr0DoesNotMatchAddresses
+                | Check::NroStatusIsAbsent
+                | Check::NroRegistrantEntityIsAbsent
+                | Check::NroRegistrantHandleIsAbsent
+                | Check::NroAsnFlatAndHierarchical
+        )
+    }
+
+    fn registrant() -> Entity {
+        Entity::builder()
+            .handle("REG-1")
+            .role("registrant".to_string())
+            .build()
+    }
+
+    #[test]
+    fn GIVEN_complete_network_WHEN_checked_THEN_no_nro_checks() {
+        // GIVEN
+        let rdap = Network::builder()
+            .cidr("10.0.0.0/16")
+            .status("active")
+            .entity(registrant())
+            .build()
+            .unwrap()
+            .to_response();
+
+        // WHEN
+        let actual = nro_checks(&rdap);
+
+        // THEN
+        assert!(!actual.iter().any(is_nro_check));
+    }
+
+    #[test]
+    fn GIVEN_nro_profile_not_enabled_WHEN_checked_THEN_no_nro_checks() {
+        // GIVEN
+        let rdap = Network::builder()
+            .cidr("10.0.0.0/16")
+            .build()
+            .unwrap()
+            .to_response();
+
+        // WHEN
+        let actual = rdap
+            .get_checks(CheckParams::for_rdap(&rdap))
+            .items
+            .iter()
+            .map(|item| item.check)
+            .collect::<Vec<_>>();
+
+        // THEN
+        assert!(!actual.iter().any(is_nro_check));
+    }
+
+    #[test]
+    fn GIVEN_network_without_status_or_registrant_WHEN_checked_THEN_absent() {
+        // GIVEN
+        let rdap = Network::builder()
+            .cidr("10.0.0.0/16")
+            .build()
+            .unwrap()
+            .to_response();
+
+        // WHEN
+        let actual = nro_checks(&rdap);
+
+        // THEN
+        assert!(actual.contains(&Check::NroStatusIsAbsent));
+        assert!(actual.contains(&Check::NroRegistrantEntityIsAbsent));
+    }
+
+    #[test]
+    fn GIVEN_registrant_without_handle_WHEN_checked_THEN_handle_absent() {
+        // GIVEN
+        let rdap = Network::builder()
+            .cidr("10.0.0.0/16")
+            .status("active")
+            .entity(
+                Entity::builder::<String>()
+                    .role("registrant".to_string())
+                    .build(),
+            )
+            .build()
+            .unwrap()
+            .to_response();
+
+        // WHEN
+        let actual = nro_checks(&rdap);
+
+        // THEN
+        assert!(actual.contains(&Check::NroRegistrantHandleIsAbsent));
+    }
+
+    #[test]
+    fn GIVEN_network_without_cidr0_WHEN_checked_THEN_cidr0_absent() {
+        // GIVEN
+        let mut network = Network::builder()
+            .cidr("10.0.0.0/16")
+            .status("active")
+            .entity(registrant())
+            .build()
+            .unwrap();
+        network.cidr0_cidrs = None;
+        let rdap = network.to_response();
+
+        // WHEN
+        let actual = nro_checks(&rdap);
+
+        // THEN
+        assert_eq!(
+            actual.into_iter().filter(is_nro_check).collect::<Vec<_>>(),
+            vec![Check::NroCidr0IsAbsent]
+        );
+    }
+
+    #[test]
+    fn GIVEN_cidr0_covering_range_in_pieces_WHEN_checked_THEN_matches() {
+        // GIVEN
+        let mut network = Network::builder()
+            .cidr("10.0.0.0/16")
+            .status("active")
+            .entity(registrant())
+            .build()
+            .unwrap();
+        network.end_address = Some("10.1.255.255".to_string());
+        network.cidr0_cidrs = Some(vec![
+            Cidr0Cidr::V4Cidr(V4Cidr {
+                v4prefix: Some("10.1.0.0".to_string()),
+                length: Some(16.into()),
+            }),
+            Cidr0Cidr::V4Cidr(V4Cidr {
+                v4prefix: Some("10.0.0.0".to_string()),
+                length: Some(16.into()),
+            }),
+        ]);
+        let rdap = network.to_response();
+
+        // WHEN
+        let actual = nro_checks(&rdap);
+
+        // THEN
+        assert!(!actual.contains(&Check::NroCidr0DoesNotMatchAddresses));
+    }
+
+    #[test]
+    fn GIVEN_cidr0_not_covering_range_WHEN_checked_THEN_mismatch() {
+        // GIVEN
+        let mut network = Network::builder()
+            .cidr("10.0.0.0/16")
+            .status("active")
+            .entity(registrant())
+            .build()
+            .unwrap();
+        network.end_address = Some("10.1.255.255".to_string());
+        let rdap = network.to_response();
+
+        // WHEN
+        let actual = nro_checks(&rdap);
+
+        // THEN
+        assert!(actual.contains(&Check::NroCidr0DoesNotMatchAddresses));
+    }
+
+    #[test]
+    fn GIVEN_autnum_with_flat_and_hierarchical_WHEN_checked_THEN_mixed() {
+        // GIVEN
+        let rdap = Autnum::response_obj()
+            .autnum_range(700..710)
+            .status("active")
+            .entity(registrant())
+            .extension(ExtensionId::NroRdapProfileAsnFlat0.as_ref())
+            .extension(ExtensionId::NroRdapProfileAsnHierarchical0.as_ref())
+            .build()
+            .to_response();
+
+        // WHEN
+        let actual = nro_checks(&rdap);
+
+        // THEN
+        assert_eq!(
+            actual.into_iter().filter(is_nro_check).collect::<Vec<_>>(),
+            vec![Check::NroAsnFlatAndHierarchical]
+        );
+    }
+}
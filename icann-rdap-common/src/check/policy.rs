@@ -0,0 +1,124 @@
+use std::collections::BTreeMap;
+
+use serde::{Deserialize, Serialize};
+
+use super::{Check, CheckClass, CheckItem, Checks};
+
+/// A policy for reclassifying or silencing checks.
+///
+/// Policies allow users to decide how severe a check is for their purposes,
+/// such as treating an unregistered extension as an error or ignoring checks
+/// about documentation names. A policy is usually read from a JSON file.
+///
+/// ```rust
+/// use icann_rdap_common::check::{Check, CheckClass, CheckPolicy};
+///
+/// let policy = CheckPolicy::from_json(r#"
+///   {
+///     "reclassify": { "unknown_extention": "std_error" },
+///     "silence": [ "ldh_name_documentation" ]
+///   }
+/// "#).unwrap();
+///
+/// let item = policy.apply_item(&Check::UnknownExtention.check_item()).unwrap();
+/// assert_eq!(item.check_class, CheckClass::StdError);
+/// assert!(policy.apply_item(&Check::LdhNameDocumentation.check_item()).is_none());
+/// ```
+#[derive(Debug, Serialize, Deserialize, Clone, Default, PartialEq, Eq)]
+#[serde(deny_unknown_fields)]
+pub struct CheckPolicy {
+    /// Checks given a class other than their own.
+    #[serde(default)]
+    pub reclassify: BTreeMap<Check, CheckClass>,
+
+    /// Checks that are removed.
+    #[serde(default)]
+    pub silence: Vec<Check>,
+}
+
+impl CheckPolicy {
+    /// Parses a policy from JSON.
+    pub fn from_json(json: &str) -> Result<Self, serde_json::Error> {
+        serde_json::from_str(json)
+    }
+
+    /// Applies the policy to a check item, returning None if the check is silenced.
+    pub fn apply_item(&self, item: &CheckItem) -> Option<CheckItem> {
+        if self.silence.contains(&item.check) {
+            return None;
+        }
+        Some(CheckItem {
+            check_class: self
+                .reclassify
+                .get(&item.check)
+                .copied()
+                .unwrap_or(item.check_class),
+            check: item.check,
+            specification: item.specification.clone(),
+        })
+    }
+
+    /// Applies the policy to checks and all their sub checks.
+    pub fn apply(&self, checks: &mut Checks) {
+        checks.items = checks
+            .items
+            .iter()
+            .filter_map(|item| self.apply_item(item))
+            .collect();
+        for sub_checks in &mut checks.sub_checks {
+            self.apply(sub_checks);
+        }
+    }
+}
+
+#[cfg(test)]
+#[allow(non_snake_case)]
+mod tests {
+    use crate::check::{Check, CheckClass, CheckPolicy, Checks, RdapStructure};
+
+    #[test]
+    fn GIVEN_policy_WHEN_apply_THEN_sub_checks_reclassified_and_silenced() {
+        // GIVEN
+        let policy = CheckPolicy::from_json(
+            r#"{"reclassify": {"link_self_has_no_type": "std_error"}, "silence": ["unknown_extention"]}"#,
+        )
+        .expect("parsing policy");
+        let mut checks = Checks {
+            rdap_struct: RdapStructure::Domain,
+            items: vec![Check::UnknownExtention.check_item()],
+            sub_checks: vec![Checks {
+                rdap_struct: RdapStructure::Link,
+                items: vec![
+                    Check::LinkSelfHasNoType.check_item(),
+                    Check::LinkMissingValueProperty.check_item(),
+                ],
+                sub_checks: vec![],
+            }],
+        };
+
+        // WHEN
+        policy.apply(&mut checks);
+
+        // THEN
+        assert!(checks.items.is_empty());
+        let links = &checks.sub_checks[0].items;
+        assert_eq!(links.len(), 2);
+        assert_eq!(links[0].check_class, CheckClass::StdError);
+        assert_eq!(
+            links[1].check_class,
+            Check::LinkMissingValueProperty.check_item().check_class
+        );
+    }
+
+    #[test]
+    fn GIVEN_policy_with_unknown_check_WHEN_from_json_THEN_error() {
+        // GIVEN
+        let json = r#"{"silence": ["no_such_check"]}"#;
+
+        // WHEN
+        let actual = CheckPolicy::from_json(json);
+
+        // THEN
+        assert!(actual.is_err());
+    }
+}
@@ -0,0 +1,256 @@
+use std::str::FromStr;
+
+use {
+    jsonpath_rust::{path::config::JsonPathConfig, JsonPathInst},
+    serde_json::Value,
+};
+
+use crate::response::redacted::{Method, Redacted, RedactedName};
+
+use super::{Check, CheckItem, CheckParams, Checks, GetChecks, RdapStructure};
+
+impl GetChecks for Redacted {
+    fn get_checks(&self, params: CheckParams) -> Checks {
+        let mut items: Vec<CheckItem> = vec![];
+
+        // name
+        match (self.name.type_field(), self.name.description()) {
+            (Some(name_type), _) => {
+                if RedactedName::from_str(name_type).is_err() {
+                    items.push(Check::RedactedNameNotRegistered.check_item())
+                }
+            }
+            (None, Some(description)) if !description.trim().is_empty() => {}
+            _ => items.push(Check::RedactedNameIsEmpty.check_item()),
+        }
+
+        // method
+        let method = self.method.clone().unwrap_or_default();
+        if method == Method::Unrecognized {
+            items.push(Check::RedactedMethodUnrecognized.check_item())
+        }
+        if self.pre_path.is_some() && method != Method::Removal {
+            items.push(Check::RedactedPrePathNotRemoval.check_item())
+        }
+        let needs_post_path = matches!(
+            method,
+            Method::EmptyValue | Method::PartialValue | Method::ReplacementValue
+        );
+        if needs_post_path && self.post_path.is_none() {
+            items.push(Check::RedactedPostPathIsAbsent.check_item())
+        }
+
+        // paths are only checked when they are JSONPath, which is the default
+        let is_json_path = self
+            .path_lang()
+            .is_none_or(|lang| lang.eq_ignore_ascii_case("jsonpath"));
+        if is_json_path {
+            if let Some(pre_path) = self.pre_path() {
+                if JsonPathInst::from_str(pre_path).is_err() {
+                    items.push(Check::RedactedPrePathInvalid.check_item())
+                }
+            }
+            if let Some(replacement_path) = self.replacement_path() {
+                if JsonPathInst::from_str(replacement_path).is_err() {
+                    items.push(Check::RedactedReplacementPathInvalid.check_item())
+                }
+            }
+            if let Some(post_path) = self.post_path() {
+                match JsonPathInst::from_str(post_path) {
+                    Ok(post_path) => {
+                        if needs_post_path {
+                            items.append(&mut referenced_value_checks(&post_path, &method, params))
+                        }
+                    }
+                    Err(_) => items.push(Check::RedactedPostPathInvalid.check_item()),
+                }
+            }
+        }
+
+        Checks {
+            rdap_struct: RdapStructure::Redacted,
+            items,
+            sub_checks: vec![],
+        }
+    }
+}
+
+/// Checks that the values referenced by a postPath are consistent with the redaction method.
+fn referenced_value_checks(
+    post_path: &JsonPathInst,
+    method: &Method,
+    params: CheckParams,
+) -> Vec<CheckItem> {
+    let mut items: Vec<CheckItem> = vec![];
+    let Ok(root) = serde_json::to_value(params.root) else {
+        return items;
+    };
+    let values = post_path.find_slice(&root, JsonPathConfig::default());
+    if values.is_empty() {
+        items.push(Check::RedactedPostPathNotFound.check_item())
+    }
+    for value in values.iter() {
+        match &**value {
+            Value::String(s) => {
+                if *method == Method::EmptyValue && !s.is_empty() {
+                    items.push(Check::RedactedEmptyValueIsNotEmpty.check_item())
+                } else if *method == Method::PartialValue && s.is_empty() {
+                    items.push(Check::RedactedPartialValueIsEmpty.check_item())
+                }
+            }
+            _ => items.push(Check::RedactedValueIsNotString.check_item()),
+        }
+    }
+    items
+}
+
+#[cfg(test)]
+#[allow(non_snake_case)]
+mod tests {
+    use rstest::rstest;
+
+    use crate::{
+        check::{is_checked, Check, CheckParams, GetChecks},
+        response::RdapResponse,
+    };
+
+    fn domain_with_redaction(redaction: &str) -> RdapResponse {
+        let json = format!(
+            r#"
+            {{
+              "objectClassName": "domain",
+              "ldhName": "example.com",
+              "handle": "",
+              "entities": [
+                {{
+                  "objectClassName": "entity",
+                  "roles": ["registrant"],
+                  "vcardArray": [
+                    "vcard",
+                    [
+                      ["version", {{}}, "text", "4.0"],
+                      ["fn", {{}}, "text", ""],
+                      ["email", {{}}, "text", "jane@example.com"],
+                      ["org", {{}}, "text", "Example Inc"]
+                    ]
+                  ]
+                }}
+              ],
+              "redacted": [{redaction}]
+            }}
+        "#
+        );
+        serde_json::from_str::<RdapResponse>(&json).expect("parsing JSON")
+    }
+
+    #[test]
+    fn GIVEN_consistent_redactions_WHEN_checked_THEN_no_redacted_checks() {
+        // GIVEN
+        let rdap = domain_with_redaction(
+            r#"
+            {
+              "name": {"type": "Registry Domain ID"},
+              "prePath": "$.handle",
+              "method": "removal"
+            },
+            {
+              "name": {"type": "Registrant Name"},
+              "postPath": "$.entities[?(@.roles[0]=='registrant')].vcardArray[1][?(@[0]=='fn')][3]",
+              "pathLang": "jsonpath",
+              "method": "emptyValue"
+            }
+            "#,
+        );
+
+        // WHEN
+        let checks = rdap.get_checks(CheckParams::for_rdap(&rdap));
+
+        // THEN
+        dbg!(&checks);
+        let redacted = checks
+            .sub_checks
+            .iter()
+            .filter(|c| c.rdap_struct == crate::check::RdapStructure::Redacted)
+            .collect::<Vec<_>>();
+        assert_eq!(redacted.len(), 2);
+        assert!(redacted.iter().all(|c| c.items.is_empty()));
+    }
+
+    #[rstest]
+    #[case(r#"{"name": {}, "method": "removal"}"#, Check::RedactedNameIsEmpty)]
+    #[case(
+        r#"{"name": {"type": "Favorite Color"}, "method": "removal"}"#,
+        Check::RedactedNameNotRegistered
+    )]
+    #[case(
+        r#"{"name": {"type": "Tech Email"}, "method": "scramble"}"#,
+        Check::RedactedMethodUnrecognized
+    )]
+    #[case(
+        r#"{"name": {"type": "Tech Email"}, "prePath": "$.entities[?(@.roles[0]=='technical'"}"#,
+        Check::RedactedPrePathInvalid
+    )]
+    #[case(
+        r#"{"name": {"type": "Tech Email"}, "postPath": "$.entities[0", "method": "emptyValue"}"#,
+        Check::RedactedPostPathInvalid
+    )]
+    #[case(
+        r#"{"name": {"type": "Tech Email"}, "replacementPath": "$.[", "method": "replacementValue", "postPath": "$.handle"}"#,
+        Check::RedactedReplacementPathInvalid
+    )]
+    #[case(
+        r#"{"name": {"type": "Tech Email"}, "method": "partialValue"}"#,
+        Check::RedactedPostPathIsAbsent
+    )]
+    #[case(
+        r#"{"name": {"type": "Tech Email"}, "prePath": "$.handle", "postPath": "$.handle", "method": "emptyValue"}"#,
+        Check::RedactedPrePathNotRemoval
+    )]
+    #[case(
+        r#"{"name": {"type": "Tech Email"}, "postPath": "$.port43", "method": "emptyValue"}"#,
+        Check::RedactedPostPathNotFound
+    )]
+    #[case(
+        r#"{"name": {"type": "Registrant Email"}, "postPath": "$.entities[0].vcardArray[1][2][3]", "method": "emptyValue"}"#,
+        Check::RedactedEmptyValueIsNotEmpty
+    )]
+    #[case(
+        r#"{"name": {"type": "Registrant Name"}, "postPath": "$.entities[0].vcardArray[1][1][3]", "method": "partialValue"}"#,
+        Check::RedactedPartialValueIsEmpty
+    )]
+    #[case(
+        r#"{"name": {"type": "Registrant Name"}, "postPath": "$.entities[0].roles", "method": "emptyValue"}"#,
+        Check::RedactedValueIsNotString
+    )]
+    fn GIVEN_bad_redaction_WHEN_checked_THEN_check_found(
+        #[case] redaction: &str,
+        #[case] expected: Check,
+    ) {
+        // GIVEN
+        let rdap = domain_with_redaction(redaction);
+
+        // WHEN
+        let checks = rdap.get_checks(CheckParams::for_rdap(&rdap));
+
+        // THEN
+        dbg!(&checks);
+        assert!(is_checked(expected, &checks.sub_checks));
+    }
+
+    #[test]
+    fn GIVEN_non_jsonpath_path_lang_WHEN_checked_THEN_paths_not_checked() {
+        // GIVEN
+        let rdap = domain_with_redaction(
+            r#"{"name": {"type": "Tech Email"}, "prePath": "/handle", "pathLang": "jsonpointer"}"#,
+        );
+
+        // WHEN
+        let checks = rdap.get_checks(CheckParams::for_rdap(&rdap));
+
+        // THEN
+        assert!(!is_checked(
+            Check::RedactedPrePathInvalid,
+            &checks.sub_checks
+        ));
+    }
+}
@@ -0,0 +1,309 @@
+use serde::Serialize;
+use strum::EnumMessage;
+
+use super::{Check, CheckClass};
+
+/// The description of a check, as given by [Check::describe].
+///
+/// ```rust
+/// use icann_rdap_common::check::{Check, CheckClass};
+///
+/// let check = Check::from_code(1302).unwrap();
+/// let description = check.describe();
+/// assert_eq!(description.name, "ip_address_end_before_start");
+/// assert_eq!(description.class, CheckClass::StdWarning);
+/// assert_eq!(description.citation, "RFC 9083 Section 5.4");
+/// ```
+#[derive(Debug, Serialize, Clone, PartialEq, Eq)]
+pub struct CheckDescription {
+    /// The stable numeric code of the check.
+    pub code: u32,
+
+    /// The name of the check, as used in check policies.
+    pub name: String,
+
+    /// The message of the check.
+    pub message: &'static str,
+
+    /// The class of the check before any check policy is applied.
+    pub class: CheckClass,
+
+    /// The specification, or section of it, on which the check is based.
+    pub citation: &'static str,
+}
+
+impl Check {
+    /// Gets the stable numeric code of the check.
+    pub fn code(self) -> u32 {
+        self as u32
+    }
+
+    /// Gets the check with a numeric code, or `None` if there is no such check.
+    pub fn from_code(code: u32) -> Option<Self> {
+        Self::from_repr(code as usize)
+    }
+
+    /// Describes the check with its code, name, message, class and citation.
+    pub fn describe(self) -> CheckDescription {
+        CheckDescription {
+            code: self.code(),
+            name: self.to_string(),
+            message: self.get_message().unwrap_or("[Check has no description]"),
+            class: self.check_item().check_class,
+            citation: self.citation(),
+        }
+    }
+
+    /// Gets the specification, or section of it, on which the check is based.
+    pub fn citation(self) -> &'static str {
+        match self {
+            Self::RdapConformanceMissing
+            | Self::RdapConformanceInvalidParent
+            | Self::UnknownExtention => "RFC 9083 Section 4.1",
+
+            Self::LinkMissingValueProperty
+            | Self::LinkMissingRelProperty
+            | Self::LinkRelatedHasNoType
+            | Self::LinkRelatedIsNotRdap
+            | Self::LinkSelfHasNoType
+            | Self::LinkSelfIsNotRdap
+            | Self::LinkObjectClassHasNoSelf
+            | Self::LinkMissingHrefProperty
+            | Self::LinkRelatedNotToRdap
+            | Self::LinkNameserverWithoutGlueHasNoSelf => "RFC 9083 Section 4.2",
+
+            Self::VariantEmptyDomain
+            | Self::VariantRelationNotRegistered
+            | Self::VariantIdnTableIsAbsent
+            | Self::VariantNameLdhNameInvalid
+            | Self::VariantNameUnicodeNameInvalid
+            | Self::VariantNameLdhDoesNotMatchUnicode
+            | Self::VariantNameALabelInvalid
+            | Self::VariantNameIsDomainName => "RFC 9083 Section 5.3",
+
+            Self::EventDateIsAbsent | Self::EventDateIsNotRfc3339 | Self::EventActionIsAbsent => {
+                "RFC 9083 Section 4.5"
+            }
+            Self::UnknownEventAction => "RFC 9083 Section 10.2.3",
+
+            Self::NoticeOrRemarkDescriptionIsAbsent | Self::NoticeOrRemarkDescriptionIsString => {
+                "RFC 9083 Section 4.3"
+            }
+            Self::UnknownNoticeOrRemarkType => "RFC 9083 Section 10.2.1",
+
+            Self::HandleIsEmpty | Self::HandleIsNotString => "RFC 9083 Section 5",
+            Self::ParentHandleIsNotString => "RFC 9083 Section 5.4",
+
+            Self::StatusIsEmpty => "RFC 9083 Section 4.6",
+            Self::UnknownStatusValue => "RFC 9083 Section 10.2.2",
+            Self::StatusIsEppStatus => "RFC 8056",
+
+            Self::RoleIsEmpty | Self::RoleIsString | Self::RoleIsDuplicated => {
+                "RFC 9083 Section 5.1"
+            }
+            Self::UnknownRole => "RFC 9083 Section 10.2.4",
+
+            Self::LdhNameInvalid | Self::LdhNameDoesNotMatchUnicode => "RFC 9083 Section 3",
+            Self::LdhNameDocumentation => "RFC 6761",
+
+            Self::UnicodeNameInvalidDomain | Self::UnicodeNameInvalidUnicode => {
+                "RFC 9083 Section 3"
+            }
+
+            Self::NetworkOrAutnumNameIsEmpty
+            | Self::NetworkOrAutnumNameIsNotString
+            | Self::NetworkOrAutnumTypeIsEmpty
+            | Self::NetworkOrAutnumTypeIsNotString
+            | Self::NetworkOrAutnumCountryIsNotString => "RFC 9083 Sections 5.4 and 5.5",
+
+            Self::IpAddressMissing
+            | Self::IpAddressMalformed
+            | Self::IpAddressEndBeforeStart
+            | Self::IpAddressVersionMismatch
+            | Self::IpAddressMalformedVersion
+            | Self::IpVersionIsNotString => "RFC 9083 Section 5.4",
+            Self::IpAddressListIsEmpty
+            | Self::IpAddressArrayIsString
+            | Self::IpAddressNotInLinkedNetwork => "RFC 9083 Section 5.2",
+            Self::IpAddressThisNetwork => "RFC 791",
+            Self::IpAddressPrivateUse => "RFC 1918",
+            Self::IpAddressSharedNat => "RFC 6598",
+            Self::IpAddressLoopback => "RFC 1122",
+            Self::IpAddressLinkLocal => "RFC 3927",
+            Self::IpAddressUniqueLocal => "RFC 8190",
+            Self::IpAddressDocumentationNet => "RFC 5737",
+            Self::IpAddressReservedNet => "RFC 1112",
+
+            Self::AutnumMissing | Self::AutnumEndBeforeStart => "RFC 9083 Section 5.5",
+            Self::AutnumPrivateUse | Self::AutnumReserved => "RFC 6996",
+            Self::AutnumDocumentation => "RFC 5398",
+            Self::AutnumExceeds32Bits => "RFC 6793",
+
+            Self::VcardArrayIsEmpty => "RFC 9083 Section 5.1",
+            Self::VcardHasNoFn | Self::VcardFnIsEmpty => "RFC 6350 Section 6.2.1",
+
+            Self::Port43IsEmpty => "RFC 9083 Section 4.7",
+
+            Self::PublicIdTypeIsAbsent
+            | Self::PublicIdIdentifierIsAbsent
+            | Self::PublicIdTypeIsNotString
+            | Self::PublicIdIdentifierIsNotString => "RFC 9083 Section 4.8",
+
+            Self::CorsAllowOriginRecommended
+            | Self::CorsAllowOriginStarRecommended
+            | Self::CorsAllowCredentialsNotRecommended => "RFC 7480 Section 5.6",
+            Self::ContentTypeIsAbsent | Self::ContentTypeIsNotRdap | Self::ContentTypeIsJson => {
+                "RFC 7480 Section 4.2"
+            }
+            Self::ContentTypeCharsetIsNotUtf8 => "RFC 8259 Section 8.1",
+            Self::RedirectDowngradesToHttp => "RFC 7481 Section 3.4",
+            Self::RedirectLoop => "RFC 9110 Section 15.4",
+            Self::ResponseIsNotCompressed => "RFC 9110 Section 8.4",
+
+            Self::Cidr0V4PrefixIsAbsent
+            | Self::Cidr0V4LengthIsAbsent
+            | Self::Cidr0V6PrefixIsAbsent
+            | Self::Cidr0V6LengthIsAbsent => "NRO RDAP CIDR0 Extension",
+
+            Self::MustUseHttps | Self::AllowOriginNotStar | Self::Ipv6SupportRequiredByIcann => {
+                "ICANN gTLD RDAP Technical Implementation Guide"
+            }
+            Self::IcannStatusCodesNoticeIsAbsent
+            | Self::IcannInaccuracyComplaintNoticeIsAbsent
+            | Self::IcannRegistrarEntityIsAbsent
+            | Self::IcannRegistrarIanaIdIsAbsent
+            | Self::IcannRegistrationEventIsAbsent
+            | Self::IcannExpirationEventIsAbsent
+            | Self::IcannRdapDatabaseUpdateEventIsAbsent
+            | Self::IcannNameserverLdhNameIsAbsent
+            | Self::IcannTermsOfServiceNoticeIsAbsent
+            | Self::IcannNoticeLinkIsAbsent
+            | Self::IcannRegistrantEntityIsAbsent
+            | Self::IcannAbuseEntityIsAbsent
+            | Self::IcannAbusePhoneIsAbsent
+            | Self::IcannAbuseEmailIsAbsent => "ICANN gTLD RDAP Response Profile",
+
+            Self::CnameWithoutARecords | Self::CnameWithoutAAAARecords => "RFC 1034 Section 3.6.2",
+            Self::NoARecords => "RFC 1035",
+            Self::NoAAAARecords => "RFC 3596",
+            Self::ExpectedExtensionNotFound => "RFC 9083 Section 4.1",
+            Self::TlsVersionIsBelow12 => "RFC 9325",
+            Self::TlsCertificateChainIsInvalid
+            | Self::TlsCertificateIsExpired
+            | Self::TlsCertificateExpiresSoon => "RFC 5280",
+            Self::TlsCertificateDoesNotCoverHost => "RFC 6125",
+
+            Self::DelegationSignedIsString
+            | Self::ZoneSignedIsString
+            | Self::MaxSigLifeIsString => "RFC 9083 Section 5.3",
+            Self::KeyDatumAlgorithmIsString
+            | Self::KeyDatumAlgorithmIsOutOfRange
+            | Self::KeyDatumFlagsIsString
+            | Self::KeyDatumFlagsIsOutOfRange
+            | Self::KeyDatumProtocolIsString
+            | Self::KeyDatumProtocolIsOutOfRange
+            | Self::KeyDatumPublicKeyIsNotBase64 => "RFC 4034 Section 2",
+            Self::DsDatumAlgorithmIsString
+            | Self::DsDatumAlgorithmIsOutOfRange
+            | Self::DsDatumKeyTagIsString
+            | Self::DsDatumKeyTagIsOutOfRange
+            | Self::DsDatumDigestTypeIsString
+            | Self::DsDatumDigestTypeIsOutOfRange
+            | Self::DsDatumDigestIsNotHex
+            | Self::DsDatumDigestLengthMismatch
+            | Self::DsDatumDoesNotMatchKeyData => "RFC 4034 Section 5",
+
+            Self::RedactedNameIsEmpty
+            | Self::RedactedNameNotRegistered
+            | Self::RedactedPrePathInvalid
+            | Self::RedactedPostPathInvalid
+            | Self::RedactedReplacementPathInvalid
+            | Self::RedactedPostPathIsAbsent
+            | Self::RedactedPrePathNotRemoval
+            | Self::RedactedPostPathNotFound => "RFC 9537 Section 4.2",
+            Self::RedactedMethodUnrecognized
+            | Self::RedactedEmptyValueIsNotEmpty
+            | Self::RedactedPartialValueIsEmpty
+            | Self::RedactedValueIsNotString => "RFC 9537 Section 3",
+
+            Self::NroCidr0IsAbsent
+            | Self::NroCidr0DoesNotMatchAddresses
+            | Self::NroStatusIsAbsent
+            | Self::NroRegistrantEntityIsAbsent
+            | Self::NroRegistrantHandleIsAbsent
+            | Self::NroAsnFlatAndHierarchical => "NRO RDAP Profile",
+        }
+    }
+}
+
+#[cfg(test)]
+#[allow(non_snake_case)]
+mod tests {
+    use strum::IntoEnumIterator;
+
+    use crate::check::{Check, CheckClass};
+
+    #[test]
+    fn GIVEN_code_WHEN_from_code_THEN_check() {
+        // GIVEN
+        let code = 1302;
+
+        // WHEN
+        let actual = Check::from_code(code);
+
+        // THEN
+        assert_eq!(actual, Some(Check::IpAddressEndBeforeStart));
+    }
+
+    #[test]
+    fn GIVEN_unused_code_WHEN_from_code_THEN_none() {
+        // GIVEN
+        let code = 2210;
+
+        // WHEN
+        let actual = Check::from_code(code);
+
+        // THEN
+        assert!(actual.is_none());
+    }
+
+    #[test]
+    fn GIVEN_every_check_WHEN_code_THEN_from_code_is_same_check() {
+        // GIVEN
+        let checks = Check::iter();
+
+        // WHEN / THEN
+        for check in checks {
+            assert_eq!(Check::from_code(check.code()), Some(check));
+        }
+    }
+
+    #[test]
+    fn GIVEN_every_check_WHEN_describe_THEN_has_message_and_citation() {
+        // GIVEN
+        let checks = Check::iter();
+
+        // WHEN / THEN
+        for check in checks {
+            let description = check.describe();
+            assert_ne!(description.message, "[Check has no description]");
+            assert!(!description.citation.is_empty());
+        }
+    }
+
+    #[test]
+    fn GIVEN_check_WHEN_describe_as_json_THEN_structured() {
+        // GIVEN
+        let check = Check::UnknownExtention;
+
+        // WHEN
+        let actual = serde_json::to_value(check.describe()).expect("serializing description");
+
+        // THEN
+        assert_eq!(actual["code"], 102);
+        assert_eq!(actual["name"], "unknown_extention");
+        assert_eq!(actual["class"], "std_warning");
+        assert_eq!(actual["citation"], "RFC 9083 Section 4.1");
+        assert_eq!(CheckClass::StdWarning, check.describe().class);
+    }
+}
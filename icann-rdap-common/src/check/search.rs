@@ -1,6 +1,9 @@
 use std::any::TypeId;
 
-use crate::response::search::{DomainSearchResults, EntitySearchResults, NameserverSearchResults};
+use crate::response::search::{
+    AutnumSearchResults, DomainSearchResults, EntitySearchResults, IpSearchResults,
+    NameserverSearchResults,
+};
 
 use super::{CheckParams, Checks, GetChecks, GetSubChecks};
 
@@ -66,3 +69,45 @@ impl GetChecks for EntitySearchResults {
         }
     }
 }
+
+impl GetChecks for IpSearchResults {
+    fn get_checks(&self, params: CheckParams) -> super::Checks {
+        let sub_checks: Vec<Checks> = if params.do_subchecks {
+            let mut sub_checks: Vec<Checks> = self
+                .common
+                .get_sub_checks(params.from_parent(TypeId::of::<Self>()));
+            self.results.iter().for_each(|result| {
+                sub_checks.push(result.get_checks(params.from_parent(TypeId::of::<Self>())))
+            });
+            sub_checks
+        } else {
+            vec![]
+        };
+        Checks {
+            rdap_struct: super::RdapStructure::IpSearchResults,
+            items: vec![],
+            sub_checks,
+        }
+    }
+}
+
+impl GetChecks for AutnumSearchResults {
+    fn get_checks(&self, params: CheckParams) -> super::Checks {
+        let sub_checks: Vec<Checks> = if params.do_subchecks {
+            let mut sub_checks: Vec<Checks> = self
+                .common
+                .get_sub_checks(params.from_parent(TypeId::of::<Self>()));
+            self.results.iter().for_each(|result| {
+                sub_checks.push(result.get_checks(params.from_parent(TypeId::of::<Self>())))
+            });
+            sub_checks
+        } else {
+            vec![]
+        };
+        Checks {
+            rdap_struct: super::RdapStructure::AutnumSearchResults,
+            items: vec![],
+            sub_checks,
+        }
+    }
+}
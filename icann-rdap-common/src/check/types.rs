@@ -7,7 +7,7 @@ use {
 
 use {
     crate::{
-        media_types::RDAP_MEDIA_TYPE,
+        media_types::is_rdap_media_type,
         prelude::Common,
         response::{
             autnum::Autnum,
@@ -16,8 +16,8 @@ use {
             nameserver::Nameserver,
             network::Network,
             types::{
-                ExtensionId, Link, Links, NoticeOrRemark, Notices, PublicIds, RdapConformance,
-                Remarks,
+                EventAction, ExtensionId, Link, Links, NoticeOrRemark, NoticeOrRemarkType, Notices,
+                PublicIds, RdapConformance, Remarks, StatusValue,
             },
         },
     },
@@ -87,11 +87,11 @@ impl GetChecks for Link {
         if let Some(rel) = &self.rel {
             if rel.eq("related") {
                 if let Some(media_type) = &self.media_type {
-                    if !media_type.eq(RDAP_MEDIA_TYPE)
+                    if !is_rdap_media_type(media_type)
                         && RELATED_AND_SELF_LINK_PARENTS.contains(&params.parent_type)
                     {
                         items.push(Check::LinkRelatedIsNotRdap.check_item())
-                    } else if media_type.eq(RDAP_MEDIA_TYPE) {
+                    } else if is_rdap_media_type(media_type) {
                         if let Some(ref href) = self.href {
                             if !has_rdap_path(href) {
                                 items.push(Check::LinkRelatedNotToRdap.check_item())
@@ -103,7 +103,7 @@ impl GetChecks for Link {
                 }
             } else if rel.eq("self") {
                 if let Some(media_type) = &self.media_type {
-                    if !media_type.eq(RDAP_MEDIA_TYPE) {
+                    if !is_rdap_media_type(media_type) {
                         items.push(Check::LinkSelfIsNotRdap.check_item())
                     }
                 } else {
@@ -179,7 +179,11 @@ impl GetChecks for NoticeOrRemark {
                 });
             };
         };
-        // TODO checks on 'type'
+        if let Some(nr_type) = &self.nr_type {
+            if !params.allow_unreg_ext && NoticeOrRemarkType::from_str(nr_type).is_err() {
+                items.push(Check::UnknownNoticeOrRemarkType.check_item())
+            }
+        }
         Checks {
             rdap_struct: super::RdapStructure::NoticeOrRemark,
             items,
@@ -285,6 +289,13 @@ impl GetSubChecks for ObjectCommon {
             sub_checks.push(remarks.get_checks(params))
         };
 
+        // redacted
+        if let Some(redacted) = &self.redacted {
+            redacted
+                .iter()
+                .for_each(|r| sub_checks.push(r.get_checks(params)))
+        };
+
         // events
         if let Some(events) = &self.events {
             events.iter().for_each(|e| {
@@ -304,12 +315,22 @@ impl GetSubChecks for ObjectCommon {
                         sub_checks: vec![],
                     })
                 }
-                if e.event_action.is_none() {
-                    sub_checks.push(Checks {
+                match &e.event_action {
+                    None => sub_checks.push(Checks {
                         rdap_struct: super::RdapStructure::Events,
                         items: vec![Check::EventActionIsAbsent.check_item()],
                         sub_checks: vec![],
-                    })
+                    }),
+                    Some(action)
+                        if !params.allow_unreg_ext && EventAction::from_str(action).is_err() =>
+                    {
+                        sub_checks.push(Checks {
+                            rdap_struct: super::RdapStructure::Events,
+                            items: vec![Check::UnknownEventAction.check_item()],
+                            sub_checks: vec![],
+                        })
+                    }
+                    Some(_) => {}
                 }
             });
         }
@@ -343,6 +364,26 @@ impl GetSubChecks for ObjectCommon {
                     sub_checks: vec![],
                 })
             }
+            for value in status
+                .iter()
+                .filter(|value| !value.is_whitespace_or_empty())
+            {
+                if StatusValue::from_str(value).is_ok() {
+                    continue;
+                }
+                let check = if StatusValue::from_epp(value).is_some() {
+                    Check::StatusIsEppStatus
+                } else if !params.allow_unreg_ext {
+                    Check::UnknownStatusValue
+                } else {
+                    continue;
+                };
+                sub_checks.push(Checks {
+                    rdap_struct: super::RdapStructure::Status,
+                    items: vec![check.check_item()],
+                    sub_checks: vec![],
+                })
+            }
         }
 
         // Port 43
@@ -822,6 +863,82 @@ mod tests {
             .expect("event missing check");
     }
 
+    #[rstest]
+    #[case("registration")]
+    #[case("last changed")]
+    #[case("last update of RDAP database")]
+    #[case("enum validation expiration")]
+    fn test_event_with_registered_action(#[case] action: &str) {
+        // GIVEN
+        let rdap = Domain::builder()
+            .ldh_name("example.com")
+            .event(
+                Event::builder()
+                    .event_action(action)
+                    .event_date("1990-12-31T23:59:59Z")
+                    .build(),
+            )
+            .build()
+            .to_response();
+
+        // WHEN
+        let checks = rdap.get_checks(CheckParams::for_rdap(&rdap));
+
+        // THEN
+        assert!(checks.sub(crate::check::RdapStructure::Events).is_none());
+    }
+
+    #[test]
+    fn test_event_with_unknown_action() {
+        // GIVEN
+        let rdap = Domain::builder()
+            .ldh_name("example.com")
+            .event(
+                Event::builder()
+                    .event_action("foo")
+                    .event_date("1990-12-31T23:59:59Z")
+                    .build(),
+            )
+            .build()
+            .to_response();
+
+        // WHEN
+        let checks = rdap.get_checks(CheckParams::for_rdap(&rdap));
+
+        // THEN
+        checks
+            .sub(crate::check::RdapStructure::Events)
+            .expect("Events not found")
+            .items
+            .iter()
+            .find(|c| c.check == Check::UnknownEventAction)
+            .expect("event missing check");
+    }
+
+    #[test]
+    fn test_event_with_unknown_action_and_unreg_allowed() {
+        // GIVEN
+        let rdap = Domain::builder()
+            .ldh_name("example.com")
+            .event(
+                Event::builder()
+                    .event_action("foo")
+                    .event_date("1990-12-31T23:59:59Z")
+                    .build(),
+            )
+            .build()
+            .to_response();
+
+        // WHEN
+        let checks = rdap.get_checks(CheckParams {
+            allow_unreg_ext: true,
+            ..CheckParams::for_rdap(&rdap)
+        });
+
+        // THEN
+        assert!(checks.sub(crate::check::RdapStructure::Events).is_none());
+    }
+
     #[test]
     fn test_event_with_bad_date() {
         // GIVEN
@@ -983,6 +1100,69 @@ mod tests {
             .expect("description missing check");
     }
 
+    #[rstest]
+    #[case("result set truncated due to authorization")]
+    #[case("object redacted due to authorization")]
+    fn test_notice_with_registered_type(#[case] nr_type: &str) {
+        // GIVEN
+        let rdap = Domain::response_obj()
+            .ldh_name("example.com")
+            .notice(Notice(
+                NoticeOrRemark::builder()
+                    .description_entry("a notice")
+                    .nr_type(nr_type)
+                    .build(),
+            ))
+            .build()
+            .to_response();
+
+        // WHEN
+        let checks = rdap.get_checks(CheckParams::for_rdap(&rdap));
+
+        // THEN
+        assert!(checks
+            .sub(crate::check::RdapStructure::Notices)
+            .expect("Notices not found")
+            .sub(crate::check::RdapStructure::NoticeOrRemark)
+            .expect("Notice/Remark not found")
+            .items
+            .is_empty());
+    }
+
+    #[rstest]
+    #[case(false, true)]
+    #[case(true, false)]
+    fn test_notice_with_unknown_type(#[case] allow_unreg_ext: bool, #[case] expected: bool) {
+        // GIVEN
+        let rdap = Domain::response_obj()
+            .ldh_name("example.com")
+            .notice(Notice(
+                NoticeOrRemark::builder()
+                    .description_entry("a notice")
+                    .nr_type("Result Set Truncated")
+                    .build(),
+            ))
+            .build()
+            .to_response();
+
+        // WHEN
+        let checks = rdap.get_checks(CheckParams {
+            allow_unreg_ext,
+            ..CheckParams::for_rdap(&rdap)
+        });
+
+        // THEN
+        let actual = checks
+            .sub(crate::check::RdapStructure::Notices)
+            .expect("Notices not found")
+            .sub(crate::check::RdapStructure::NoticeOrRemark)
+            .expect("Notice/Remark not found")
+            .items
+            .iter()
+            .any(|c| c.check == Check::UnknownNoticeOrRemarkType);
+        assert_eq!(actual, expected);
+    }
+
     #[test]
     fn test_nameserver_with_no_links() {
         // GIVEN
@@ -1056,6 +1236,99 @@ mod tests {
             .any(|c| c.check == Check::StatusIsEmpty));
     }
 
+    #[rstest]
+    #[case("active")]
+    #[case("client delete prohibited")]
+    #[case("redemption period")]
+    #[case("administrative")]
+    fn test_domain_with_registered_status(#[case] status: &str) {
+        // GIVEN
+        let rdap = Domain::builder()
+            .ldh_name("example.com")
+            .status(status)
+            .build()
+            .to_response();
+
+        // WHEN
+        let checks = rdap.get_checks(CheckParams::for_rdap(&rdap));
+
+        // THEN
+        assert!(checks.sub(crate::check::RdapStructure::Status).is_none());
+    }
+
+    #[rstest]
+    #[case("clientDeleteProhibited")]
+    #[case("serverHold")]
+    #[case("ok")]
+    #[case("linked")]
+    fn test_domain_with_epp_status(#[case] status: &str) {
+        // GIVEN
+        let rdap = Domain::builder()
+            .ldh_name("example.com")
+            .status(status)
+            .build()
+            .to_response();
+
+        // WHEN
+        let checks = rdap.get_checks(CheckParams {
+            allow_unreg_ext: true,
+            ..CheckParams::for_rdap(&rdap)
+        });
+
+        // THEN
+        let status_checks = checks
+            .sub(crate::check::RdapStructure::Status)
+            .expect("status not found");
+        assert!(status_checks
+            .items
+            .iter()
+            .any(|c| c.check == Check::StatusIsEppStatus));
+        assert!(!status_checks
+            .items
+            .iter()
+            .any(|c| c.check == Check::UnknownStatusValue));
+    }
+
+    #[test]
+    fn test_domain_with_unknown_status() {
+        // GIVEN
+        let rdap = Domain::builder()
+            .ldh_name("example.com")
+            .status("active")
+            .status("foo")
+            .build()
+            .to_response();
+
+        // WHEN
+        let checks = rdap.get_checks(CheckParams::for_rdap(&rdap));
+
+        // THEN
+        let status_checks = checks
+            .sub(crate::check::RdapStructure::Status)
+            .expect("status not found");
+        assert_eq!(status_checks.items.len(), 1);
+        assert_eq!(status_checks.items[0].check, Check::UnknownStatusValue);
+    }
+
+    #[test]
+    fn test_domain_with_unknown_status_and_unreg_allowed() {
+        // GIVEN
+        let rdap = Domain::builder()
+            .ldh_name("example.com")
+            .status("foo")
+            .build()
+            .to_response();
+
+        // WHEN
+        let checks = rdap.get_checks(CheckParams {
+            allow_unreg_ext: true,
+            ..CheckParams::for_rdap(&rdap)
+        });
+
+        // THEN
+        assert!(checks.sub(crate::check::RdapStructure::Status).is_none());
+    }
+
     #[rstest]
     #[case("")]
     #[case("  ")]
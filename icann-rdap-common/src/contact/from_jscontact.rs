@@ -0,0 +1,562 @@
+//! Convert JSContact to Contact.
+use std::collections::BTreeMap;
+
+use serde_json::{Map, Value};
+
+use super::{Contact, Email, Lang, NameParts, Phone, PostalAddress};
+
+/// Address component kinds that are part of the street address.
+const STREET_KINDS: &[&str] = &[
+    "room",
+    "apartment",
+    "floor",
+    "building",
+    "number",
+    "name",
+    "block",
+    "subdistrict",
+    "district",
+    "direction",
+    "landmark",
+    "postOfficeBox",
+];
+
+impl Contact {
+    /// Creates a Contact from a JSContact Card ([RFC 9553](https://datatracker.ietf.org/doc/html/rfc9553)).
+    ///
+    /// Localizations in the card are converted to localized contacts.
+    ///
+    /// ```rust
+    /// use icann_rdap_common::contact::Contact;
+    /// use serde::Deserialize;
+    /// use serde_json::Value;
+    ///
+    /// let json = r#"
+    /// {
+    ///   "@type": "Card",
+    ///   "version": "1.0",
+    ///   "kind": "individual",
+    ///   "name": {
+    ///     "full": "Joe User"
+    ///   },
+    ///   "emails": {
+    ///     "e1": {
+    ///       "contexts": { "work": true },
+    ///       "address": "joe.user@example.com"
+    ///     }
+    ///   }
+    /// }"#;
+    ///
+    /// let data: Value = serde_json::from_str(json).unwrap();
+    /// let contact = Contact::from_jscontact(&data);
+    /// ```
+    pub fn from_jscontact(card: &Value) -> Option<Contact> {
+        let card = card.as_object()?;
+        if let Some(card_type) = card.get("@type") {
+            if card_type.as_str() != Some("Card") {
+                return None;
+            }
+        }
+
+        let mut contact = contact_from_card(card);
+
+        if let Some(localizations) = card.get("localizations").and_then(Value::as_object) {
+            let mut localized_contacts = BTreeMap::new();
+            for (lang, patches) in localizations {
+                let Some(patches) = patches.as_object() else {
+                    continue;
+                };
+                let mut localized = card.clone();
+                localized.remove("localizations");
+                for (path, value) in patches {
+                    apply_patch(&mut localized, path, value);
+                }
+                localized_contacts.insert(lang.clone(), contact_from_card(&localized));
+            }
+            contact.localizations = (!localized_contacts.is_empty()).then_some(localized_contacts);
+        }
+
+        contact.is_non_empty().then_some(contact)
+    }
+}
+
+fn contact_from_card(card: &Map<String, Value>) -> Contact {
+    let name = card.get("name").and_then(Value::as_object);
+    let titles = objects(card, "titles");
+    let links = objects(card, "links");
+    Contact::builder()
+        .and_kind(get_str(card, "kind"))
+        .and_full_name(name.and_then(|n| get_str(n, "full")))
+        .and_name_parts(name.and_then(name_parts))
+        .langs(
+            objects(card, "preferredLanguages")
+                .into_iter()
+                .filter_map(|l| {
+                    Some(
+                        Lang::builder()
+                            .tag(get_str(l, "language")?)
+                            .and_preference(get_pref(l))
+                            .build(),
+                    )
+                })
+                .collect(),
+        )
+        .nick_names(names(objects(card, "nicknames")))
+        .organization_names(names(objects(card, "organizations")))
+        .titles(names(
+            titles
+                .iter()
+                .filter(|t| get_str(t, "kind").as_deref() != Some("role"))
+                .copied()
+                .collect(),
+        ))
+        .roles(names(
+            titles
+                .iter()
+                .filter(|t| get_str(t, "kind").as_deref() == Some("role"))
+                .copied()
+                .collect(),
+        ))
+        .emails(
+            objects(card, "emails")
+                .into_iter()
+                .filter_map(|e| {
+                    Some(
+                        Email::builder()
+                            .email(get_str(e, "address")?)
+                            .contexts(get_contexts(e))
+                            .and_preference(get_pref(e))
+                            .build(),
+                    )
+                })
+                .collect(),
+        )
+        .phones(
+            objects(card, "phones")
+                .into_iter()
+                .filter_map(|p| {
+                    Some(
+                        Phone::builder()
+                            .phone(get_str(p, "number")?)
+                            .contexts(get_contexts(p))
+                            .features(
+                                get_flags(p, "features")
+                                    .into_iter()
+                                    .map(|f| {
+                                        if f.eq_ignore_ascii_case("mobile") {
+                                            "cell".to_string()
+                                        } else {
+                                            f
+                                        }
+                                    })
+                                    .collect(),
+                            )
+                            .and_preference(get_pref(p))
+                            .build(),
+                    )
+                })
+                .collect(),
+        )
+        .postal_addresses(
+            objects(card, "addresses")
+                .into_iter()
+                .map(postal_address)
+                .collect(),
+        )
+        .contact_uris(
+            links
+                .iter()
+                .filter(|l| get_str(l, "kind").as_deref() == Some("contact"))
+                .filter_map(|l| get_str(l, "uri"))
+                .collect(),
+        )
+        .urls(
+            links
+                .iter()
+                .filter(|l| get_str(l, "kind").as_deref() != Some("contact"))
+                .filter_map(|l| get_str(l, "uri"))
+                .collect(),
+        )
+        .build()
+}
+
+fn name_parts(name: &Map<String, Value>) -> Option<NameParts> {
+    let components = name.get("components")?.as_array()?;
+    let values = |kinds: &[&str]| {
+        components
+            .iter()
+            .filter_map(Value::as_object)
+            .filter(|c| {
+                get_str(c, "kind")
+                    .as_deref()
+                    .is_some_and(|k| kinds.contains(&k))
+            })
+            .filter_map(|c| get_str(c, "value"))
+            .collect::<Vec<String>>()
+    };
+    let name_parts = NameParts::builder()
+        .prefixes(values(&["title"]))
+        .given_names(values(&["given"]))
+        .middle_names(values(&["given2"]))
+        .surnames(values(&["surname", "surname2"]))
+        .suffixes(values(&["credential", "generation"]))
+        .build();
+    (name_parts != NameParts::builder().build()).then_some(name_parts)
+}
+
+fn postal_address(addr: &Map<String, Value>) -> PostalAddress {
+    let mut street_parts = vec![];
+    let mut line = String::new();
+    let mut separator: Option<String> = None;
+    let mut locality = None;
+    let mut region = None;
+    let mut postal_code = None;
+    let mut country_name = None;
+    let components = addr
+        .get("components")
+        .and_then(Value::as_array)
+        .map(|c| c.iter().filter_map(Value::as_object).collect::<Vec<_>>())
+        .unwrap_or_default();
+    for component in components {
+        let kind = get_str(component, "kind").unwrap_or_default();
+        let value = get_str(component, "value").unwrap_or_default();
+        if kind == "separator" {
+            if value.contains('\n') {
+                finish_line(&mut street_parts, &mut line);
+                separator = None;
+            } else {
+                separator = Some(value);
+            }
+            continue;
+        }
+        if STREET_KINDS.contains(&kind.as_str()) {
+            if !line.is_empty() {
+                line.push_str(separator.as_deref().unwrap_or(" "));
+            }
+            line.push_str(&value);
+        } else {
+            finish_line(&mut street_parts, &mut line);
+            match kind.as_str() {
+                "locality" => locality = Some(value),
+                "region" => region = Some(value),
+                "postcode" => postal_code = Some(value),
+                "country" => country_name = Some(value),
+                _ => {}
+            }
+        }
+        separator = None;
+    }
+    finish_line(&mut street_parts, &mut line);
+
+    // a region may be either a code or a name.
+    let (region_code, region_name) = match region {
+        Some(r) if r.len() == 2 && r.to_uppercase() == r => (Some(r), None),
+        r => (None, r),
+    };
+    PostalAddress::builder()
+        .and_full_address(get_str(addr, "full"))
+        .street_parts(street_parts)
+        .and_locality(locality)
+        .and_region_code(region_code)
+        .and_region_name(region_name)
+        .and_postal_code(postal_code)
+        .and_country_name(country_name)
+        .and_country_code(get_str(addr, "countryCode"))
+        .contexts(get_contexts(addr))
+        .and_preference(get_pref(addr))
+        .build()
+}
+
+fn finish_line(street_parts: &mut Vec<String>, line: &mut String) {
+    if !line.is_empty() {
+        street_parts.push(std::mem::take(line));
+    }
+}
+
+/// Gets the objects of a JSContact map property, ordered by ID.
+fn objects<'a>(card: &'a Map<String, Value>, name: &str) -> Vec<&'a Map<String, Value>> {
+    let Some(map) = card.get(name).and_then(Value::as_object) else {
+        return vec![];
+    };
+    let mut entries = map
+        .iter()
+        .filter_map(|(id, v)| Some((id, v.as_object()?)))
+        .collect::<Vec<_>>();
+    // IDs such as "e2" should be before "e10".
+    entries.sort_by(|(a, _), (b, _)| a.len().cmp(&b.len()).then(a.cmp(b)));
+    entries.into_iter().map(|(_, v)| v).collect()
+}
+
+fn names(objects: Vec<&Map<String, Value>>) -> Vec<String> {
+    objects
+        .into_iter()
+        .filter_map(|o| get_str(o, "name"))
+        .collect()
+}
+
+fn get_str(object: &Map<String, Value>, name: &str) -> Option<String> {
+    object.get(name)?.as_str().map(|s| s.to_string())
+}
+
+fn get_pref(object: &Map<String, Value>) -> Option<u64> {
+    object.get("pref")?.as_u64()
+}
+
+fn get_flags(object: &Map<String, Value>, name: &str) -> Vec<String> {
+    let Some(flags) = object.get(name).and_then(Value::as_object) else {
+        return vec![];
+    };
+    flags
+        .iter()
+        .filter(|(_, v)| v.as_bool().unwrap_or(false))
+        .map(|(k, _)| k.clone())
+        .collect()
+}
+
+/// Gets JSContact contexts as vCard types, where "private" is "home".
+fn get_contexts(object: &Map<String, Value>) -> Vec<String> {
+    get_flags(object, "contexts")
+        .into_iter()
+        .map(|c| {
+            if c.eq_ignore_ascii_case("private") {
+                "home".to_string()
+            } else {
+                c
+            }
+        })
+        .collect()
+}
+
+/// Applies a JSContact patch, where the path is separated by "/".
+fn apply_patch(card: &mut Map<String, Value>, path: &str, value: &Value) {
+    let mut parts = path.split('/').collect::<Vec<&str>>();
+    let Some(last) = parts.pop() else {
+        return;
+    };
+    let mut object = card;
+    for part in parts {
+        let Some(next) = object.get_mut(part).and_then(Value::as_object_mut) else {
+            return;
+        };
+        object = next;
+    }
+    if value.is_null() {
+        object.remove(last);
+    } else {
+        object.insert(last.to_string(), value.clone());
+    }
+}
+
+#[cfg(test)]
+#[allow(non_snake_case)]
+mod tests {
+    use std::collections::BTreeMap;
+
+    use serde_json::Value;
+
+    use crate::contact::{Contact, Email, Lang, NameParts, Phone, PostalAddress};
+
+    #[test]
+    fn GIVEN_contact_WHEN_jscontact_round_trip_THEN_same_contact() {
+        // GIVEN
+        let contact = Contact::builder()
+            .full_name("Joe User")
+            .name_parts(
+                NameParts::builder()
+                    .surnames(vec!["User".to_string()])
+                    .given_names(vec!["Joe".to_string()])
+                    .suffixes(vec!["ing. jr".to_string(), "M.Sc.".to_string()])
+                    .build(),
+            )
+            .kind("individual")
+            .langs(vec![
+                Lang::builder().preference(1).tag("fr").build(),
+                Lang::builder().preference(2).tag("en").build(),
+            ])
+            .organization_names(vec!["Example".to_string()])
+            .titles(vec!["Research Scientist".to_string()])
+            .roles(vec!["Project Lead".to_string()])
+            .contact_uris(vec!["https://example.com/contact-form".to_string()])
+            .postal_addresses(vec![PostalAddress::builder()
+                .context("work")
+                .country_code("CA")
+                .country_name("Canada")
+                .postal_code("G1V 2M2")
+                .region_code("QC")
+                .locality("Quebec")
+                .street_parts(vec![
+                    "Suite 1234".to_string(),
+                    "4321 Rue Somewhere".to_string(),
+                ])
+                .build()])
+            .phones(vec![
+                Phone::builder()
+                    .preference(1)
+                    .contexts(vec!["work".to_string()])
+                    .features(vec!["voice".to_string()])
+                    .phone("tel:+1-555-555-1234;ext=102")
+                    .build(),
+                Phone::builder()
+                    .contexts(vec!["home".to_string()])
+                    .features(vec!["cell".to_string(), "text".to_string()])
+                    .phone("tel:+1-555-555-4321")
+                    .build(),
+            ])
+            .emails(vec![Email::builder()
+                .contexts(vec!["work".to_string()])
+                .email("joe.user@example.com")
+                .build()])
+            .urls(vec!["https://example.com/some-url".to_string()])
+            .localizations(BTreeMap::from([(
+                "fr".to_string(),
+                Contact::builder()
+                    .full_name("Joe User")
+                    .kind("individual")
+                    .titles(vec!["Scientifique".to_string()])
+                    .build(),
+            )]))
+            .build();
+
+        // WHEN
+        let actual = Contact::from_jscontact(&contact.to_jscontact()).expect("from jscontact");
+
+        // THEN
+        assert_eq!(contact.full_name, actual.full_name);
+        assert_eq!(contact.name_parts, actual.name_parts);
+        assert_eq!(contact.kind, actual.kind);
+        assert_eq!(contact.langs, actual.langs);
+        assert_eq!(contact.organization_names, actual.organization_names);
+        assert_eq!(contact.titles, actual.titles);
+        assert_eq!(contact.roles, actual.roles);
+        assert_eq!(contact.postal_addresses, actual.postal_addresses);
+        assert_eq!(contact.phones, actual.phones);
+        assert_eq!(contact.emails, actual.emails);
+        assert_eq!(contact.contact_uris, actual.contact_uris);
+        assert_eq!(contact.urls, actual.urls);
+        assert_eq!(contact.localizations, actual.localizations);
+        assert_eq!(contact, actual);
+    }
+
+    #[test]
+    fn GIVEN_rfc9553_card_WHEN_from_jscontact_THEN_contact() {
+        // GIVEN
+        // adapted from the examples of RFC 9553
+        let json = r#"
+        {
+          "@type": "Card",
+          "version": "1.0",
+          "uid": "22B2C7DF-9120-4969-8460-05956FE6B065",
+          "kind": "individual",
+          "name": {
+            "components": [
+              { "kind": "title", "value": "Mr." },
+              { "kind": "given", "value": "John" },
+              { "kind": "given2", "value": "Quinlan" },
+              { "kind": "surname", "value": "Public" },
+              { "kind": "generation", "value": "Esq." }
+            ],
+            "full": "Mr. John Q. Public, Esq."
+          },
+          "nicknames": {
+            "k391": { "name": "Johnny" }
+          },
+          "organizations": {
+            "o1": { "name": "ABC, Inc." }
+          },
+          "titles": {
+            "le9": { "kind": "title", "name": "Research Scientist" },
+            "k2": { "kind": "role", "name": "Project Leader" }
+          },
+          "emails": {
+            "e1": {
+              "contexts": { "work": true },
+              "address": "jqpublic@xyz.example.com"
+            },
+            "e2": {
+              "address": "jane_doe@example.com",
+              "pref": 1
+            }
+          },
+          "phones": {
+            "tel0": {
+              "contexts": { "private": true },
+              "number": "tel:+1-555-555-5555;ext=5555",
+              "pref": 1
+            },
+            "tel3": {
+              "contexts": { "work": true },
+              "features": { "mobile": true, "voice": true },
+              "number": "tel:+1-201-555-0123"
+            }
+          },
+          "addresses": {
+            "k23": {
+              "contexts": { "work": true },
+              "components": [
+                { "kind": "number", "value": "54321" },
+                { "kind": "separator", "value": " " },
+                { "kind": "name", "value": "Oak St" },
+                { "kind": "locality", "value": "Reston" },
+                { "kind": "region", "value": "VA" },
+                { "kind": "postcode", "value": "20190" },
+                { "kind": "country", "value": "USA" }
+              ],
+              "countryCode": "US",
+              "defaultSeparator": ", ",
+              "isOrdered": true
+            }
+          },
+          "preferredLanguages": {
+            "l1": { "language": "en", "pref": 1 },
+            "l2": { "language": "fr", "pref": 2 }
+          },
+          "links": {
+            "contact": { "kind": "contact", "uri": "mailto:contact@example.com" },
+            "lnk1": { "uri": "https://example.com" }
+          },
+          "localizations": {
+            "fr": {
+              "titles/le9/name": "Scientifique",
+              "addresses/k23/countryCode": null
+            }
+          }
+        }
+        "#;
+        let card: Value = serde_json::from_str(json).expect("parsing card");
+
+        // WHEN
+        let actual = Contact::from_jscontact(&card).expect("from jscontact");
+
+        // THEN
+        assert_eq!(actual.full_name(), Some("Mr. John Q. Public, Esq."));
+        let name_parts = actual.name_parts().expect("name parts");
+        assert_eq!(name_parts.prefixes(), ["Mr."]);
+        assert_eq!(name_parts.given_names(), ["John"]);
+        assert_eq!(name_parts.middle_names(), ["Quinlan"]);
+        assert_eq!(name_parts.surnames(), ["Public"]);
+        assert_eq!(name_parts.suffixes(), ["Esq."]);
+        assert_eq!(actual.nick_names(), ["Johnny"]);
+        assert_eq!(actual.organizational_names(), ["ABC, Inc."]);
+        assert_eq!(actual.titles(), ["Research Scientist"]);
+        assert_eq!(actual.roles(), ["Project Leader"]);
+        assert_eq!(actual.emails().len(), 2);
+        assert_eq!(actual.emails()[0].contexts(), ["work"]);
+        assert_eq!(actual.emails()[1].preference(), Some(1));
+        assert_eq!(actual.phones()[0].contexts(), ["home"]);
+        assert_eq!(actual.phones()[1].features(), ["cell", "voice"]);
+        let addr = actual.postal_addresses().first().expect("address");
+        assert_eq!(addr.street_parts(), ["54321 Oak St"]);
+        assert_eq!(addr.locality(), Some("Reston"));
+        assert_eq!(addr.region_code(), Some("VA"));
+        assert_eq!(addr.postal_code(), Some("20190"));
+        assert_eq!(addr.country_name(), Some("USA"));
+        assert_eq!(addr.country_code(), Some("US"));
+        assert_eq!(actual.langs()[1].tag(), "fr");
+        assert_eq!(actual.contact_uris(), ["mailto:contact@example.com"]);
+        assert_eq!(actual.urls(), ["https://example.com"]);
+        let fr = actual.localization("fr").expect("fr localization");
+        assert_eq!(fr.titles(), ["Scientifique"]);
+        assert_eq!(fr.postal_addresses()[0].country_code(), None);
+        assert_eq!(fr.full_name(), actual.full_name());
+    }
+}
@@ -2,7 +2,8 @@
 //!
 //! This module converts contact information to and from vCard/jCard, which is hard to
 //! work with directly. It is also intended as a way of bridging the between vCard/jCard
-//! and any new contact model.
+//! and JSContact ([RFC 9553](https://datatracker.ietf.org/doc/html/rfc9553)), which is
+//! converted with `to_jscontact` and `from_jscontact`.
 //!
 //! This struct can be built using the builder.
 //!
@@ -83,10 +84,12 @@
 //! let email_addr = contact.emails().first().unwrap().email();
 //! ```
 
+mod from_jscontact;
 mod from_vcard;
+mod to_jscontact;
 mod to_vcard;
 
-use std::fmt::Display;
+use std::{collections::BTreeMap, fmt::Display};
 
 use buildstructor::Builder;
 
@@ -146,6 +149,9 @@ pub struct Contact {
 
     /// URLs
     pub urls: Option<Vec<String>>,
+
+    /// Localized versions of this contact, keyed by language tag.
+    pub localizations: Option<BTreeMap<String, Contact>>,
 }
 
 #[buildstructor::buildstructor]
@@ -165,6 +171,7 @@ impl Contact {
         phones: Vec<Phone>,
         contact_uris: Vec<String>,
         urls: Vec<String>,
+        localizations: BTreeMap<String, Contact>,
     ) -> Self {
         Self {
             langs: to_opt_vec(langs),
@@ -180,6 +187,7 @@ impl Contact {
             phones: to_opt_vec(phones),
             contact_uris: to_opt_vec(contact_uris),
             urls: to_opt_vec(urls),
+            localizations: (!localizations.is_empty()).then_some(localizations),
         }
     }
 
@@ -198,6 +206,7 @@ impl Contact {
             || self.phones.is_some()
             || self.contact_uris.is_some()
             || self.urls.is_some()
+            || self.localizations.is_some()
     }
 
     /// Set the set of emails.
@@ -318,6 +327,11 @@ impl Contact {
     pub fn urls(&self) -> &[String] {
         self.urls.as_deref().unwrap_or_default()
     }
+
+    /// Get the localization for a language tag.
+    pub fn localization(&self, lang: &str) -> Option<&Contact> {
+        self.localizations.as_ref()?.get(lang)
+    }
 }
 
 /// The language preference of the contact.
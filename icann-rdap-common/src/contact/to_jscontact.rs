@@ -0,0 +1,247 @@
+//! Convert a Contact to JSContact.
+use serde_json::{json, Map, Value};
+
+use super::{Contact, NameParts, PostalAddress};
+
+impl Contact {
+    /// Output the Contact data as a JSContact Card ([RFC 9553](https://datatracker.ietf.org/doc/html/rfc9553)).
+    ///
+    /// ```rust
+    /// use icann_rdap_common::contact::Contact;
+    /// use serde::Serialize;
+    /// use serde_json::Value;
+    ///
+    /// let contact = Contact::builder()
+    ///   .kind("individual")
+    ///   .full_name("Bob Smurd")
+    ///   .build();
+    ///
+    /// let v = contact.to_jscontact();
+    /// let json = serde_json::to_string(&v);
+    /// ```
+    pub fn to_jscontact(&self) -> Value {
+        let mut card = self.to_card();
+
+        if let Some(localizations) = &self.localizations {
+            let mut patches = Map::new();
+            for (lang, localization) in localizations {
+                let localized = localization.to_card();
+                let mut patch = Map::new();
+                for (key, value) in &localized {
+                    if card.get(key) != Some(value) {
+                        patch.insert(key.clone(), value.clone());
+                    }
+                }
+                for key in card.keys() {
+                    if !localized.contains_key(key) {
+                        patch.insert(key.clone(), Value::Null);
+                    }
+                }
+                patches.insert(lang.clone(), Value::from(patch));
+            }
+            card.insert("localizations".to_string(), Value::from(patches));
+        }
+
+        Value::from(card)
+    }
+
+    fn to_card(&self) -> Map<String, Value> {
+        let mut card = Map::new();
+        card.insert("@type".to_string(), json!("Card"));
+        card.insert("version".to_string(), json!("1.0"));
+
+        if let Some(kind) = &self.kind {
+            card.insert("kind".to_string(), json!(kind));
+        }
+
+        if self.full_name.is_some() || self.name_parts.is_some() {
+            let mut name = Map::new();
+            if let Some(name_parts) = &self.name_parts {
+                name.insert("components".to_string(), name_components(name_parts));
+            }
+            if let Some(full_name) = &self.full_name {
+                name.insert("full".to_string(), json!(full_name));
+            }
+            card.insert("name".to_string(), Value::from(name));
+        }
+
+        if let Some(langs) = &self.langs {
+            let langs = langs.iter().map(|lang| {
+                let mut pref = Map::new();
+                pref.insert("language".to_string(), json!(lang.tag));
+                if let Some(preference) = lang.preference {
+                    pref.insert("pref".to_string(), json!(preference));
+                }
+                Value::from(pref)
+            });
+            card.insert("preferredLanguages".to_string(), id_map("l", langs));
+        }
+
+        if let Some(nick_names) = &self.nick_names {
+            let nick_names = nick_names.iter().map(|n| json!({"name": n}));
+            card.insert("nicknames".to_string(), id_map("k", nick_names));
+        }
+
+        if let Some(org_names) = &self.organization_names {
+            let org_names = org_names.iter().map(|o| json!({"name": o}));
+            card.insert("organizations".to_string(), id_map("o", org_names));
+        }
+
+        if self.titles.is_some() || self.roles.is_some() {
+            let titles = self
+                .titles()
+                .iter()
+                .map(|t| json!({"kind": "title", "name": t}))
+                .chain(
+                    self.roles()
+                        .iter()
+                        .map(|r| json!({"kind": "role", "name": r})),
+                );
+            card.insert("titles".to_string(), id_map("t", titles));
+        }
+
+        if let Some(emails) = &self.emails {
+            let emails = emails.iter().map(|email| {
+                let mut e = Map::new();
+                e.insert("address".to_string(), json!(email.email));
+                if let Some(contexts) = &email.contexts {
+                    e.insert("contexts".to_string(), contexts_to_value(contexts));
+                }
+                if let Some(pref) = email.preference {
+                    e.insert("pref".to_string(), json!(pref));
+                }
+                Value::from(e)
+            });
+            card.insert("emails".to_string(), id_map("e", emails));
+        }
+
+        if let Some(phones) = &self.phones {
+            let phones = phones.iter().map(|phone| {
+                let mut p = Map::new();
+                p.insert("number".to_string(), json!(phone.phone));
+                if let Some(contexts) = &phone.contexts {
+                    p.insert("contexts".to_string(), contexts_to_value(contexts));
+                }
+                if let Some(features) = &phone.features {
+                    let features = features
+                        .iter()
+                        .map(|f| (feature_to_jscontact(f), Value::Bool(true)))
+                        .collect::<Map<String, Value>>();
+                    p.insert("features".to_string(), Value::from(features));
+                }
+                if let Some(pref) = phone.preference {
+                    p.insert("pref".to_string(), json!(pref));
+                }
+                Value::from(p)
+            });
+            card.insert("phones".to_string(), id_map("p", phones));
+        }
+
+        if let Some(addrs) = &self.postal_addresses {
+            let addrs = addrs.iter().map(address_to_value);
+            card.insert("addresses".to_string(), id_map("a", addrs));
+        }
+
+        if self.contact_uris.is_some() || self.urls.is_some() {
+            let links = self
+                .contact_uris()
+                .iter()
+                .map(|uri| json!({"kind": "contact", "uri": uri}))
+                .chain(self.urls().iter().map(|url| json!({"uri": url})));
+            card.insert("links".to_string(), id_map("u", links));
+        }
+
+        card
+    }
+}
+
+/// Creates a JSContact map of objects with generated IDs.
+fn id_map(prefix: &str, values: impl Iterator<Item = Value>) -> Value {
+    let map = values
+        .enumerate()
+        .map(|(i, v)| (format!("{prefix}{}", i + 1), v))
+        .collect::<Map<String, Value>>();
+    Value::from(map)
+}
+
+fn name_components(name_parts: &NameParts) -> Value {
+    let components = [
+        ("title", name_parts.prefixes()),
+        ("given", name_parts.given_names()),
+        ("given2", name_parts.middle_names()),
+        ("surname", name_parts.surnames()),
+        ("credential", name_parts.suffixes()),
+    ]
+    .into_iter()
+    .flat_map(|(kind, values)| {
+        values
+            .iter()
+            .map(move |value| json!({"kind": kind, "value": value}))
+    })
+    .collect::<Vec<Value>>();
+    Value::from(components)
+}
+
+fn address_to_value(addr: &PostalAddress) -> Value {
+    let mut a = Map::new();
+    let mut components = vec![];
+    for (i, street_part) in addr.street_parts().iter().enumerate() {
+        if i != 0 {
+            components.push(json!({"kind": "separator", "value": "\n"}));
+        }
+        components.push(json!({"kind": "name", "value": street_part}));
+    }
+    if let Some(locality) = &addr.locality {
+        components.push(json!({"kind": "locality", "value": locality}));
+    }
+    if let Some(region) = addr.region_name.as_ref().or(addr.region_code.as_ref()) {
+        components.push(json!({"kind": "region", "value": region}));
+    }
+    if let Some(postal_code) = &addr.postal_code {
+        components.push(json!({"kind": "postcode", "value": postal_code}));
+    }
+    if let Some(country_name) = &addr.country_name {
+        components.push(json!({"kind": "country", "value": country_name}));
+    }
+    if !components.is_empty() {
+        a.insert("components".to_string(), Value::from(components));
+    }
+    if let Some(country_code) = &addr.country_code {
+        a.insert("countryCode".to_string(), json!(country_code));
+    }
+    if let Some(full_address) = &addr.full_address {
+        a.insert("full".to_string(), json!(full_address));
+    }
+    if let Some(contexts) = &addr.contexts {
+        a.insert("contexts".to_string(), contexts_to_value(contexts));
+    }
+    if let Some(pref) = addr.preference {
+        a.insert("pref".to_string(), json!(pref));
+    }
+    Value::from(a)
+}
+
+/// Converts vCard types to JSContact contexts, where "home" is "private".
+fn contexts_to_value(contexts: &[String]) -> Value {
+    let contexts = contexts
+        .iter()
+        .map(|c| {
+            let c = if c.eq_ignore_ascii_case("home") {
+                "private".to_string()
+            } else {
+                c.to_owned()
+            };
+            (c, Value::Bool(true))
+        })
+        .collect::<Map<String, Value>>();
+    Value::from(contexts)
+}
+
+/// Converts vCard phone types to JSContact phone features, where "cell" is "mobile".
+fn feature_to_jscontact(feature: &str) -> String {
+    if feature.eq_ignore_ascii_case("cell") {
+        "mobile".to_string()
+    } else {
+        feature.to_owned()
+    }
+}
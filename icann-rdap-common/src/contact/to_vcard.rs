@@ -1,5 +1,5 @@
 //! Convert a Contact to jCard/vCard.
-use std::str::FromStr;
+use std::fmt::Write;
 
 use serde_json::{json, Map, Value};
 
@@ -112,8 +112,15 @@ impl Contact {
                 if let Some(features) = &phone.features {
                     types.append(&mut features.clone());
                 }
-                params.insert("type".to_string(), vec_string_to_param(&types));
-                vcard.push(json!(["tel", Value::from(params), "text", phone.phone]))
+                if !types.is_empty() {
+                    params.insert("type".to_string(), vec_string_to_param(&types));
+                }
+                let value_type = if phone.phone.starts_with("tel:") {
+                    "uri"
+                } else {
+                    "text"
+                };
+                vcard.push(json!(["tel", Value::from(params), value_type, phone.phone]))
             }
         }
 
@@ -127,43 +134,20 @@ impl Contact {
                     params.insert("type".to_string(), vec_string_to_param(contexts));
                 }
                 if let Some(full_address) = &addr.full_address {
-                    params.insert(
-                        "label".to_string(),
-                        Value::from_str(full_address).expect("serializing full address"),
-                    );
-                }
-                let mut lines: Vec<String> = vec![];
-                if let Some(street_parts) = &addr.street_parts {
-                    lines.push(street_parts.first().cloned().unwrap_or("".to_string()));
-                    lines.push(street_parts.get(1).cloned().unwrap_or("".to_string()));
-                    lines.push(street_parts.get(2).cloned().unwrap_or("".to_string()));
-                } else {
-                    lines.push("".to_string());
-                    lines.push("".to_string());
-                    lines.push("".to_string());
-                }
-                if let Some(locality) = &addr.locality {
-                    lines.push(locality.to_owned());
-                } else {
-                    lines.push("".to_string());
-                }
-                if let Some(region_name) = &addr.region_name {
-                    lines.push(region_name.to_owned());
-                } else if let Some(region_code) = &addr.region_code {
-                    lines.push(region_code.to_owned());
-                } else {
-                    lines.push("".to_string());
-                }
-                if let Some(postal_code) = &addr.postal_code {
-                    lines.push(postal_code.to_owned());
-                } else {
-                    lines.push("".to_string());
-                }
-                if let Some(country_name) = &addr.country_name {
-                    lines.push(country_name.to_owned());
-                } else {
-                    lines.push("".to_string());
+                    params.insert("label".to_string(), Value::String(full_address.to_owned()));
                 }
+                let region = addr.region_name.as_ref().or(addr.region_code.as_ref());
+                // the post office box and extended address are not recommended for use,
+                // so all the street parts are in the street address.
+                let lines = json!([
+                    "",
+                    "",
+                    vec_string_to_value(&addr.street_parts),
+                    addr.locality.as_deref().unwrap_or_default(),
+                    region.map(String::as_str).unwrap_or_default(),
+                    addr.postal_code.as_deref().unwrap_or_default(),
+                    addr.country_name.as_deref().unwrap_or_default(),
+                ]);
                 if let Some(country_code) = &addr.country_code {
                     params.insert("cc".to_string(), Value::String(country_code.to_string()));
                 }
@@ -188,6 +172,130 @@ impl Contact {
     }
 }
 
+impl Contact {
+    /// Output the Contact data as vCard text ([RFC 6350](https://datatracker.ietf.org/doc/html/rfc6350)),
+    /// such as for a `.vcf` file.
+    ///
+    /// ```rust
+    /// use icann_rdap_common::contact::Contact;
+    ///
+    /// let contact = Contact::builder()
+    ///   .kind("individual")
+    ///   .full_name("Bob Smurd")
+    ///   .build();
+    ///
+    /// let text = contact.to_vcard_text();
+    /// assert!(text.starts_with("BEGIN:VCARD\r\nVERSION:4.0\r\n"));
+    /// ```
+    pub fn to_vcard_text(&self) -> String {
+        let vcard = self.to_vcard();
+        let properties = vcard
+            .get(1)
+            .and_then(Value::as_array)
+            .map(Vec::as_slice)
+            .unwrap_or_default();
+        let mut text = String::new();
+        push_folded_line(&mut text, "BEGIN:VCARD");
+        for property in properties.iter().filter_map(Value::as_array) {
+            if let Some(line) = property_to_line(property) {
+                push_folded_line(&mut text, &line);
+            }
+        }
+        push_folded_line(&mut text, "END:VCARD");
+        text
+    }
+}
+
+/// Converts a jCard property, `[name, params, type, value...]`, to a vCard content line.
+fn property_to_line(property: &[Value]) -> Option<String> {
+    let name = property.first()?.as_str()?;
+    let value_type = property.get(2)?.as_str()?;
+    let mut line = name.to_uppercase();
+    if let Some(params) = property.get(1).and_then(Value::as_object) {
+        // sorted, as the order of a map depends on the features of serde_json.
+        let mut params = params.iter().collect::<Vec<_>>();
+        params.sort_by_key(|(key, _)| key.as_str());
+        for (key, value) in params {
+            let values = match value {
+                Value::Array(values) => values.iter().map(param_value).collect::<Vec<_>>(),
+                value => vec![param_value(value)],
+            };
+            write!(line, ";{}={}", key.to_uppercase(), values.join(",")).ok()?;
+        }
+    }
+    let values = property
+        .iter()
+        .skip(3)
+        .map(|value| match value {
+            Value::Array(components) => components
+                .iter()
+                .map(|component| match component {
+                    Value::Array(list) => list
+                        .iter()
+                        .map(|v| text_value(v, value_type))
+                        .collect::<Vec<_>>()
+                        .join(","),
+                    component => text_value(component, value_type),
+                })
+                .collect::<Vec<_>>()
+                .join(";"),
+            value => text_value(value, value_type),
+        })
+        .collect::<Vec<_>>();
+    write!(line, ":{}", values.join(",")).ok()?;
+    Some(line)
+}
+
+/// Gets a parameter value, quoted if needed and with the caret encoding of RFC 6868.
+fn param_value(value: &Value) -> String {
+    let value = match value {
+        Value::String(s) => s.clone(),
+        value => value.to_string(),
+    };
+    let value = value
+        .replace('^', "^^")
+        .replace('"', "^'")
+        .replace("\r\n", "^n")
+        .replace('\n', "^n");
+    if value.contains([',', ';', ':']) {
+        format!("\"{value}\"")
+    } else {
+        value
+    }
+}
+
+/// Gets a property value, escaped as text unless it is a URI.
+fn text_value(value: &Value, value_type: &str) -> String {
+    let value = match value {
+        Value::String(s) => s.clone(),
+        value => value.to_string(),
+    };
+    if value_type == "uri" {
+        return value;
+    }
+    value
+        .replace('\\', "\\\\")
+        .replace(',', "\\,")
+        .replace(';', "\\;")
+        .replace("\r\n", "\\n")
+        .replace('\n', "\\n")
+}
+
+/// Adds a content line, folded at 75 octets without splitting characters.
+fn push_folded_line(text: &mut String, line: &str) {
+    let mut octets = 0;
+    for c in line.chars() {
+        let len = c.len_utf8();
+        if octets + len > 75 {
+            text.push_str("\r\n ");
+            octets = 1;
+        }
+        text.push(c);
+        octets += len;
+    }
+    text.push_str("\r\n");
+}
+
 fn vec_string_to_value(strings: &Option<Vec<String>>) -> Value {
     let Some(strings) = strings else {
         return Value::String("".to_string());
@@ -225,8 +333,11 @@ fn vec_string_to_param(strings: &[String]) -> Value {
 }
 
 #[cfg(test)]
+#[allow(non_snake_case)]
 mod tests {
 
+    use {rstest::rstest, serde_json::json};
+
     use crate::contact::{Contact, Email, Lang, NameParts, Phone, PostalAddress};
 
     #[test]
@@ -307,4 +418,170 @@ mod tests {
         //   but just in case we forgot soemthing
         assert_eq!(contact, actual);
     }
+
+    #[rstest]
+    #[case::full_name(Contact::builder().full_name("Joe User").build())]
+    #[case::kind(Contact::builder().kind("org").build())]
+    #[case::name_parts(Contact::builder()
+        .name_parts(
+            NameParts::builder()
+                .prefixes(vec!["Dr.".to_string()])
+                .given_names(vec!["Joe".to_string()])
+                .middle_names(vec!["Q".to_string(), "Public".to_string()])
+                .surnames(vec!["User".to_string()])
+                .build(),
+        )
+        .build())]
+    #[case::nick_names(Contact::builder()
+        .nick_names(vec!["Joey".to_string(), "JU".to_string()])
+        .build())]
+    #[case::langs_without_pref(Contact::builder()
+        .langs(vec![Lang::builder().tag("de").build()])
+        .build())]
+    #[case::org_names(Contact::builder()
+        .organization_names(vec!["Example".to_string(), "Example Holdings".to_string()])
+        .build())]
+    #[case::email_with_pref_and_contexts(Contact::builder()
+        .emails(vec![Email::builder()
+            .preference(2)
+            .contexts(vec!["work".to_string(), "home".to_string()])
+            .email("joe@example.com")
+            .build()])
+        .build())]
+    #[case::phone_without_types(Contact::builder()
+        .phones(vec![Phone::builder().phone("+1.5555551234").build()])
+        .build())]
+    #[case::address_with_many_street_parts(Contact::builder()
+        .postal_addresses(vec![PostalAddress::builder()
+            .street_parts(vec![
+                "Building 3".to_string(),
+                "Suite 1234".to_string(),
+                "4321 Rue Somewhere".to_string(),
+                "Dock 7".to_string(),
+            ])
+            .locality("Quebec")
+            .region_name("Quebec Province")
+            .postal_code("G1V 2M2")
+            .country_name("Canada")
+            .country_code("CA")
+            .build()])
+        .build())]
+    #[case::address_with_label(Contact::builder()
+        .postal_addresses(vec![PostalAddress::builder()
+            .preference(1)
+            .context("home")
+            .full_address("123 Maple Ave\nSuite 3000\nQuebec, QC G1V 2M2\nCanada")
+            .street_parts(vec!["123 Maple Ave".to_string()])
+            .region_code("QC")
+            .country_code("CA")
+            .build()])
+        .build())]
+    #[case::uris(Contact::builder()
+        .contact_uris(vec!["mailto:contact@example.com".to_string()])
+        .urls(vec!["https://example.com".to_string(), "https://example.net".to_string()])
+        .build())]
+    fn GIVEN_contact_WHEN_to_vcard_and_from_vcard_THEN_contact_round_trips(
+        #[case] contact: Contact,
+    ) {
+        // GIVEN in case
+
+        // WHEN
+        let actual = Contact::from_vcard(&contact.to_vcard()).expect("from vcard");
+
+        // THEN
+        assert_eq!(contact, actual);
+    }
+
+    #[test]
+    fn GIVEN_address_and_phone_WHEN_to_vcard_THEN_params_and_components_emitted() {
+        // GIVEN
+        let contact = Contact::builder()
+            .postal_addresses(vec![PostalAddress::builder()
+                .context("work")
+                .full_address("123 Maple Ave, Quebec")
+                .street_parts(vec!["Suite 3000".to_string(), "123 Maple Ave".to_string()])
+                .locality("Quebec")
+                .region_code("QC")
+                .country_code("CA")
+                .build()])
+            .phones(vec![Phone::builder()
+                .preference(1)
+                .contexts(vec!["work".to_string()])
+                .features(vec!["voice".to_string()])
+                .phone("tel:+1-555-555-1234")
+                .build()])
+            .build();
+
+        // WHEN
+        let actual = contact.to_vcard();
+
+        // THEN
+        let properties = actual[1].as_array().expect("vcard properties");
+        assert!(properties.contains(&json!([
+            "adr",
+            {"type": "work", "label": "123 Maple Ave, Quebec", "cc": "CA"},
+            "text",
+            ["", "", ["Suite 3000", "123 Maple Ave"], "Quebec", "QC", "", ""]
+        ])));
+        assert!(properties.contains(&json!([
+            "tel",
+            {"type": ["work", "voice"], "pref": "1"},
+            "uri",
+            "tel:+1-555-555-1234"
+        ])));
+    }
+
+    #[test]
+    fn GIVEN_contact_WHEN_to_vcard_text_THEN_rfc6350_text() {
+        // GIVEN
+        let contact = Contact::builder()
+            .full_name("Joe User; Jr, Esq")
+            .kind("individual")
+            .postal_addresses(vec![PostalAddress::builder()
+                .context("work")
+                .full_address("123 Maple Ave\nQuebec")
+                .street_parts(vec!["Suite 3000".to_string(), "123 Maple Ave".to_string()])
+                .locality("Quebec")
+                .region_code("QC")
+                .country_code("CA")
+                .build()])
+            .phones(vec![Phone::builder()
+                .contexts(vec!["work".to_string()])
+                .features(vec!["voice".to_string()])
+                .phone("tel:+1-555-555-1234")
+                .build()])
+            .build();
+
+        // WHEN
+        let actual = contact.to_vcard_text();
+
+        // THEN
+        assert_eq!(
+            actual,
+            "BEGIN:VCARD\r\n\
+             VERSION:4.0\r\n\
+             FN:Joe User\\; Jr\\, Esq\r\n\
+             KIND:individual\r\n\
+             TEL;TYPE=work,voice:tel:+1-555-555-1234\r\n\
+             ADR;CC=CA;LABEL=123 Maple Ave^nQuebec;TYPE=work:;;Suite 3000,123 Maple Ave;\r\n \
+             Quebec;QC;;\r\n\
+             END:VCARD\r\n"
+        );
+    }
+
+    #[test]
+    fn GIVEN_long_multibyte_name_WHEN_to_vcard_text_THEN_folded_on_char_boundaries() {
+        // GIVEN
+        let contact = Contact::builder().full_name("é".repeat(100)).build();
+
+        // WHEN
+        let actual = contact.to_vcard_text();
+
+        // THEN
+        for line in actual.split("\r\n") {
+            assert!(line.len() <= 75);
+        }
+        let unfolded = actual.replace("\r\n ", "");
+        assert!(unfolded.contains(&format!("FN:{}\r\n", "é".repeat(100))));
+    }
 }
@@ -5,11 +5,17 @@ use {
     serde::{Deserialize, Serialize},
 };
 
+use crate::media_types::{is_rdap_x_media_type, media_type_extensions};
+
 /// Represents the data from HTTP responses.
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
 pub struct HttpData {
     pub content_length: Option<u64>,
     pub content_type: Option<String>,
+
+    /// The content-encoding with which the response was compressed, such as "gzip".
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub content_encoding: Option<String>,
     pub scheme: Option<String>,
     pub host: String,
     pub expires: Option<String>,
@@ -22,6 +28,50 @@ pub struct HttpData {
     pub strict_transport_security: Option<String>,
     pub retry_after: Option<String>,
     pub request_uri: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub etag: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub last_modified: Option<String>,
+
+    /// The number of times the request was retried before this response.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub retries: Option<u16>,
+
+    /// The redirects followed to get this response, in the order followed.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub redirects: Vec<Redirect>,
+}
+
+/// A response redirecting a request, which is one hop of a chain of redirects.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
+pub struct Redirect {
+    /// The URL requested.
+    pub url: String,
+
+    /// The HTTP status code of the response.
+    pub status_code: u16,
+
+    /// The URL redirected to, which is the location header resolved against the URL requested.
+    pub location: Option<String>,
+
+    /// The headers of the response.
+    #[serde(default)]
+    pub headers: Vec<(String, String)>,
+}
+
+impl Redirect {
+    /// Returns true if the redirect is from HTTPS to HTTP.
+    pub fn is_scheme_downgrade(&self) -> bool {
+        let has_scheme = |url: &str, scheme: &str| {
+            url.get(..scheme.len())
+                .is_some_and(|s| s.eq_ignore_ascii_case(scheme))
+        };
+        has_scheme(&self.url, "https:")
+            && self
+                .location
+                .as_deref()
+                .is_some_and(|location| has_scheme(location, "http:"))
+    }
 }
 
 #[buildstructor::buildstructor]
@@ -30,6 +80,7 @@ impl HttpData {
     fn new(
         content_length: Option<u64>,
         content_type: Option<String>,
+        content_encoding: Option<String>,
         scheme: Option<String>,
         host: String,
         expires: Option<String>,
@@ -42,10 +93,15 @@ impl HttpData {
         retry_after: Option<String>,
         received: DateTime<Utc>,
         request_uri: Option<String>,
+        etag: Option<String>,
+        last_modified: Option<String>,
+        retries: Option<u16>,
+        redirects: Vec<Redirect>,
     ) -> Self {
         Self {
             content_length,
             content_type,
+            content_encoding,
             scheme,
             host,
             expires,
@@ -58,6 +114,10 @@ impl HttpData {
             strict_transport_security,
             retry_after,
             request_uri,
+            etag,
+            last_modified,
+            retries,
+            redirects,
         }
     }
 
@@ -65,6 +125,7 @@ impl HttpData {
     fn new_now(
         content_length: Option<u64>,
         content_type: Option<String>,
+        content_encoding: Option<String>,
         scheme: String,
         host: String,
         expires: Option<String>,
@@ -76,10 +137,15 @@ impl HttpData {
         strict_transport_security: Option<String>,
         retry_after: Option<String>,
         request_uri: Option<String>,
+        etag: Option<String>,
+        last_modified: Option<String>,
+        retries: Option<u16>,
+        redirects: Vec<Redirect>,
     ) -> Self {
         Self {
             content_length,
             content_type,
+            content_encoding,
             scheme: Some(scheme),
             host,
             expires,
@@ -92,6 +158,10 @@ impl HttpData {
             strict_transport_security,
             retry_after,
             request_uri,
+            etag,
+            last_modified,
+            retries,
+            redirects,
         }
     }
 
@@ -99,6 +169,7 @@ impl HttpData {
     fn new_example(
         content_length: Option<u64>,
         content_type: Option<String>,
+        content_encoding: Option<String>,
         expires: Option<String>,
         cache_control: Option<String>,
         status_code: Option<u16>,
@@ -108,10 +179,15 @@ impl HttpData {
         strict_transport_security: Option<String>,
         retry_after: Option<String>,
         request_uri: Option<String>,
+        etag: Option<String>,
+        last_modified: Option<String>,
+        retries: Option<u16>,
+        redirects: Vec<Redirect>,
     ) -> Self {
         Self {
             content_length,
             content_type,
+            content_encoding,
             scheme: Some("http".to_string()),
             host: "example.com".to_string(),
             expires,
@@ -124,14 +200,21 @@ impl HttpData {
             strict_transport_security,
             retry_after,
             request_uri,
+            etag,
+            last_modified,
+            retries,
+            redirects,
         }
     }
 
     pub fn is_expired(&self, max_age: i64) -> bool {
-        let now = Utc::now();
-        if now >= self.received + Duration::seconds(max_age) {
-            return true;
-        }
+        Utc::now() >= self.expires_at(max_age)
+    }
+
+    /// Gets the time the data expires, which is `max_age` seconds after it was
+    /// received or sooner if the `Cache-Control` or `Expires` headers say so.
+    pub fn expires_at(&self, max_age: i64) -> DateTime<Utc> {
+        let expires_at = self.received + Duration::seconds(max_age);
         if let Some(cache_control) = &self.cache_control {
             let cc_max_age = cache_control
                 .split(',')
@@ -140,19 +223,22 @@ impl HttpData {
             if let Some(cc_max_age) = cc_max_age {
                 let cc_max_age = cc_max_age.trim_start_matches("max-age=").parse::<i64>();
                 if let Ok(cc_max_age) = cc_max_age {
-                    return now >= self.received + Duration::seconds(cc_max_age);
+                    return expires_at.min(self.received + Duration::seconds(cc_max_age));
                 }
             }
         }
         if let Some(expires) = &self.expires {
-            let expire_time = DateTime::parse_from_rfc2822(expires);
-            return if let Ok(expire_time) = expire_time {
-                now >= expire_time
-            } else {
-                false
-            };
+            if let Ok(expire_time) = DateTime::parse_from_rfc2822(expires) {
+                return expires_at.min(expire_time.with_timezone(&Utc));
+            }
         }
-        false
+        expires_at
+    }
+
+    /// Returns true if the data has validators, which are the `ETag` and `Last-Modified`
+    /// headers, with which a request can be made conditional on a change.
+    pub fn has_validators(&self) -> bool {
+        self.etag.is_some() || self.last_modified.is_some()
     }
 
     pub fn should_cache(&self) -> bool {
@@ -192,6 +278,18 @@ impl HttpData {
         self.content_type.as_deref()
     }
 
+    pub fn content_encoding(&self) -> Option<&str> {
+        self.content_encoding.as_deref()
+    }
+
+    /// Gets the RDAP extensions negotiated with the server, which are those of the
+    /// content type if it is the RDAP-X media type.
+    pub fn rdap_x_extensions(&self) -> Option<Vec<&str>> {
+        self.content_type()
+            .filter(|content_type| is_rdap_x_media_type(content_type))
+            .map(|content_type| media_type_extensions(content_type).unwrap_or_default())
+    }
+
     pub fn scheme(&self) -> Option<&str> {
         self.scheme.as_deref()
     }
@@ -239,6 +337,34 @@ impl HttpData {
     pub fn request_uri(&self) -> Option<&str> {
         self.request_uri.as_deref()
     }
+
+    pub fn retries(&self) -> Option<u16> {
+        self.retries
+    }
+
+    pub fn redirects(&self) -> &[Redirect] {
+        &self.redirects
+    }
+
+    /// Returns true if the redirects lead back to a URL already requested.
+    pub fn has_redirect_loop(&self) -> bool {
+        let mut requested = self
+            .request_uri
+            .iter()
+            .map(String::as_str)
+            .collect::<Vec<_>>();
+        for redirect in &self.redirects {
+            requested.push(&redirect.url);
+            if redirect
+                .location
+                .as_deref()
+                .is_some_and(|location| requested.contains(&location))
+            {
+                return true;
+            }
+        }
+        false
+    }
 }
 
 #[cfg(test)]
@@ -274,6 +400,55 @@ mod tests {
         assert_eq!(actual, expected);
     }
 
+    #[test]
+    fn test_expires_at_is_expiration_of_max_age_and_headers() {
+        // GIVEN
+        let http_data = HttpData::example().cache_control("max-age=100").build();
+
+        // WHEN
+        let actual = http_data.expires_at(200);
+
+        // THEN
+        assert_eq!(actual, http_data.received + Duration::seconds(100));
+    }
+
+    #[test]
+    fn test_from_lines_without_validators() {
+        // GIVEN
+        let lines = vec![
+            r#"{"content_length":null,"content_type":null,"scheme":"http","host":"example.com","expires":null,"cache_control":null,"received":"2024-01-07T10:11:12Z","status_code":200,"location":null,"access_control_allow_origin":null,"access_control_allow_credentials":null,"strict_transport_security":null,"retry_after":null,"request_uri":null}"#.to_string(),
+            "---".to_string(),
+            "foo".to_string(),
+        ];
+
+        // WHEN
+        let actual = HttpData::from_lines(&lines).expect("parsing lines");
+
+        // THEN
+        assert!(!actual.0.has_validators());
+    }
+
+    #[rstest]
+    #[case(HttpData::example().build(), None)]
+    #[case(HttpData::example().content_type("application/rdap+json").build(), None)]
+    #[case(HttpData::example().content_type("application/rdap-x+json").build(), Some(vec![]))]
+    #[case(
+        HttpData::example().content_type(r#"application/rdap-x+json;extensions="rdap_level_0 redacted""#).build(),
+        Some(vec!["rdap_level_0", "redacted"])
+    )]
+    fn test_rdap_x_extensions_from_content_type(
+        #[case] http_data: HttpData,
+        #[case] expected: Option<Vec<&str>>,
+    ) {
+        // GIVEN in parameters
+
+        // WHEN
+        let actual = http_data.rdap_x_extensions();
+
+        // THEN
+        assert_eq!(actual, expected);
+    }
+
     #[rstest]
     #[case(HttpData::example().cache_control("no-cache").build(), false)]
     #[case(HttpData::example().cache_control("no-store").build(), false)]
@@ -1,5 +1,10 @@
 //! The IANA RDAP Bootstrap Registries.
 
+use std::{
+    collections::{BTreeMap, HashMap},
+    fmt::Display,
+};
+
 use {
     ipnet::{Ipv4Net, Ipv6Net},
     prefix_trie::PrefixMap,
@@ -206,6 +211,376 @@ impl BootstrapRegistry for IanaRegistry {
     }
 }
 
+/// An IANA RDAP bootstrap registry parsed for lookups.
+///
+/// Looking up URLs in an [IanaRegistry] parses the services of the registry on every lookup.
+/// This form is parsed once with [ParsedBootstrapRegistry::parse], after which lookups do not
+/// parse the registry: ASN ranges are binary searched, CIDRs are found in prefix tries,
+/// and TLDs and tags are found in hash maps.
+///
+/// Only the lookups of the type of registry parsed find URLs.
+///
+/// ```rust
+/// use icann_rdap_common::iana::{
+///     BootstrapRegistry, IanaRegistry, IanaRegistryType, ParsedBootstrapRegistry,
+/// };
+///
+/// let registry = serde_json::from_str::<IanaRegistry>(r#"{
+///     "version": "1.0", "publication": "2024-01-07T10:11:12Z",
+///     "services": [[["64496-64511", "65536-65551"], ["https://rdap.example.net/"]]]
+/// }"#).unwrap();
+/// let parsed = ParsedBootstrapRegistry::parse(&IanaRegistryType::RdapBootstrapAsn, &registry).unwrap();
+///
+/// let urls = parsed.get_asn_bootstrap_urls("as65540").unwrap();
+/// assert_eq!(urls, vec!["https://rdap.example.net/"]);
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct ParsedBootstrapRegistry {
+    dns: HashMap<String, Vec<String>>,
+
+    /// Start, end, and URLs of ASN ranges sorted by the start.
+    asns: Vec<(u32, u32, Vec<String>)>,
+
+    ipv4: PrefixMap<Ipv4Net, Vec<String>>,
+    ipv6: PrefixMap<Ipv6Net, Vec<String>>,
+    tags: HashMap<String, Vec<String>>,
+}
+
+impl ParsedBootstrapRegistry {
+    /// Parses a bootstrap registry of the given type.
+    pub fn parse(
+        reg_type: &IanaRegistryType,
+        registry: &IanaRegistry,
+    ) -> Result<Self, BootstrapRegistryError> {
+        let IanaRegistry::RdapBootstrapRegistry(bootstrap) = registry;
+        let mut parsed = Self::default();
+        for service in &bootstrap.services {
+            let urls = service.last().ok_or(BootstrapRegistryError::EmptyService)?;
+            let entries = match reg_type {
+                // object tag services start with contacts, and the tags are second
+                IanaRegistryType::RdapObjectTags => service.get(1),
+                _ => service.first(),
+            }
+            .ok_or(BootstrapRegistryError::InvalidBootstrapService)?;
+            for entry in entries {
+                match reg_type {
+                    IanaRegistryType::RdapBootstrapDns => {
+                        parsed.dns.insert(entry.to_ascii_lowercase(), urls.clone());
+                    }
+                    IanaRegistryType::RdapBootstrapAsn => {
+                        let (start, end) = entry.split_once('-').unwrap_or((entry, entry));
+                        let parse_asn = |asn: &str| {
+                            asn.trim()
+                                .parse::<u32>()
+                                .map_err(|_| BootstrapRegistryError::InvalidBootstrapService)
+                        };
+                        parsed
+                            .asns
+                            .push((parse_asn(start)?, parse_asn(end)?, urls.clone()));
+                    }
+                    IanaRegistryType::RdapBootstrapIpv4 => {
+                        parsed.ipv4.insert(
+                            entry
+                                .parse()
+                                .map_err(|_| BootstrapRegistryError::InvalidBootstrapService)?,
+                            urls.clone(),
+                        );
+                    }
+                    IanaRegistryType::RdapBootstrapIpv6 => {
+                        parsed.ipv6.insert(
+                            entry
+                                .parse()
+                                .map_err(|_| BootstrapRegistryError::InvalidBootstrapService)?,
+                            urls.clone(),
+                        );
+                    }
+                    IanaRegistryType::RdapObjectTags => {
+                        parsed.tags.insert(entry.to_ascii_lowercase(), urls.clone());
+                    }
+                }
+            }
+        }
+        parsed.asns.sort_by_key(|(start, _, _)| *start);
+        Ok(parsed)
+    }
+}
+
+impl BootstrapRegistry for ParsedBootstrapRegistry {
+    /// Get the URLs of the longest matching TLD, or the root if no TLD matches.
+    fn get_dns_bootstrap_urls(&self, ldh: &str) -> Result<Vec<String>, BootstrapRegistryError> {
+        let ldh = ldh.trim_end_matches('.').to_ascii_lowercase();
+        let mut suffix = ldh.as_str();
+        loop {
+            if let Some(urls) = self.dns.get(suffix) {
+                return Ok(urls.to_owned());
+            }
+            match suffix.split_once('.') {
+                Some((_, rest)) => suffix = rest,
+                None if !suffix.is_empty() => suffix = "",
+                None => return Err(BootstrapRegistryError::NoBootstrapUrls),
+            }
+        }
+    }
+
+    /// Get the URLs of the ASN range containing the ASN.
+    ///
+    /// The ranges of the IANA registry do not overlap, so this is the range
+    /// with the greatest start not greater than the ASN.
+    fn get_asn_bootstrap_urls(&self, asn: &str) -> Result<Vec<String>, BootstrapRegistryError> {
+        let autnum = asn
+            .trim_start_matches(|c| -> bool { matches!(c, 'a' | 'A' | 's' | 'S') })
+            .parse::<u32>()
+            .map_err(|_| BootstrapRegistryError::InvalidBootstrapInput)?;
+        let i = self.asns.partition_point(|(start, _, _)| *start <= autnum);
+        match i.checked_sub(1).and_then(|i| self.asns.get(i)) {
+            Some((_, end, urls)) if *end >= autnum => Ok(urls.to_owned()),
+            _ => Err(BootstrapRegistryError::NoBootstrapUrls),
+        }
+    }
+
+    /// Get the URLs of the longest matching IPv4 CIDR.
+    fn get_ipv4_bootstrap_urls(&self, ipv4: &str) -> Result<Vec<String>, BootstrapRegistryError> {
+        let net = ipv4
+            .parse::<Ipv4Net>()
+            .map_err(|_| BootstrapRegistryError::InvalidBootstrapInput)?;
+        let (_, urls) = self
+            .ipv4
+            .get_lpm(&net)
+            .ok_or(BootstrapRegistryError::NoBootstrapUrls)?;
+        Ok(urls.to_owned())
+    }
+
+    /// Get the URLs of the longest matching IPv6 CIDR.
+    fn get_ipv6_bootstrap_urls(&self, ipv6: &str) -> Result<Vec<String>, BootstrapRegistryError> {
+        let net = ipv6
+            .parse::<Ipv6Net>()
+            .map_err(|_| BootstrapRegistryError::InvalidBootstrapInput)?;
+        let (_, urls) = self
+            .ipv6
+            .get_lpm(&net)
+            .ok_or(BootstrapRegistryError::NoBootstrapUrls)?;
+        Ok(urls.to_owned())
+    }
+
+    /// Get the URLs of the object tag, which is case insensitive.
+    fn get_tag_bootstrap_urls(&self, tag: &str) -> Result<Vec<String>, BootstrapRegistryError> {
+        self.tags
+            .get(&tag.to_ascii_lowercase())
+            .cloned()
+            .ok_or(BootstrapRegistryError::NoBootstrapUrls)
+    }
+}
+
+impl IanaRegistry {
+    /// Compares this registry with another, such as a newer copy of the same registry.
+    ///
+    /// The services are compared by their entries, which are the TLDs, ASN ranges,
+    /// CIDRs, or object tags of the registry. An entry is changed if its set of URLs
+    /// is different. Services that are malformed are ignored.
+    ///
+    /// ```rust
+    /// use icann_rdap_common::iana::IanaRegistry;
+    ///
+    /// let old = serde_json::from_str::<IanaRegistry>(r#"{
+    ///     "version": "1.0", "publication": "2024-01-07T10:11:12Z",
+    ///     "services": [[["com"], ["https://rdap.example.com/"]]]
+    /// }"#).unwrap();
+    /// let new = serde_json::from_str::<IanaRegistry>(r#"{
+    ///     "version": "1.0", "publication": "2024-02-07T10:11:12Z",
+    ///     "services": [[["com", "net"], ["https://rdap.example.com/"]]]
+    /// }"#).unwrap();
+    ///
+    /// let diff = old.diff(&new);
+    /// assert_eq!(diff.added[0].entry, "net");
+    /// assert!(diff.removed.is_empty());
+    /// assert!(diff.changed.is_empty());
+    /// assert_eq!(diff.new_publication, "2024-02-07T10:11:12Z");
+    /// ```
+    pub fn diff(&self, other: &IanaRegistry) -> RegistryDiff {
+        let (
+            Self::RdapBootstrapRegistry(old_bootstrap),
+            Self::RdapBootstrapRegistry(new_bootstrap),
+        ) = (self, other);
+        let old_services = self.service_urls();
+        let new_services = other.service_urls();
+        let old = self.entries();
+        let mut new = other.entries();
+        let mut diff = RegistryDiff {
+            old_publication: old_bootstrap.publication.clone(),
+            new_publication: new_bootstrap.publication.clone(),
+            added_services: new_services
+                .keys()
+                .filter(|url| !old_services.contains_key(*url))
+                .cloned()
+                .collect(),
+            removed_services: old_services
+                .keys()
+                .filter(|url| !new_services.contains_key(*url))
+                .cloned()
+                .collect(),
+            ..Default::default()
+        };
+        for (entry, old_urls) in old {
+            match new.remove(&entry) {
+                None => diff.removed.push(RegistryEntry {
+                    entry,
+                    urls: old_urls,
+                }),
+                Some(new_urls) if new_urls != old_urls => diff.changed.push(ChangedRegistryEntry {
+                    entry,
+                    old_urls,
+                    new_urls,
+                }),
+                _ => {}
+            }
+        }
+        diff.added = new
+            .into_iter()
+            .map(|(entry, urls)| RegistryEntry { entry, urls })
+            .collect();
+        diff
+    }
+
+    /// Gets the entries served by each service URL of the registry.
+    ///
+    /// The entries of a URL are in the order of the registry.
+    ///
+    /// ```rust
+    /// use icann_rdap_common::iana::IanaRegistry;
+    ///
+    /// let registry = serde_json::from_str::<IanaRegistry>(r#"{
+    ///     "version": "1.0", "publication": "2024-01-07T10:11:12Z",
+    ///     "services": [
+    ///         [["com", "net"], ["https://rdap.example.com/"]],
+    ///         [["org"], ["https://rdap.example.com/", "http://rdap.example.org/"]]
+    ///     ]
+    /// }"#).unwrap();
+    ///
+    /// let service_urls = registry.service_urls();
+    /// assert_eq!(service_urls["https://rdap.example.com/"], ["com", "net", "org"]);
+    /// assert_eq!(service_urls["http://rdap.example.org/"], ["org"]);
+    /// ```
+    pub fn service_urls(&self) -> BTreeMap<String, Vec<String>> {
+        let Self::RdapBootstrapRegistry(bootstrap) = self;
+        let mut service_urls: BTreeMap<String, Vec<String>> = BTreeMap::new();
+        for service in &bootstrap.services {
+            if service.len() < 2 {
+                continue;
+            }
+            for url in &service[service.len() - 1] {
+                service_urls
+                    .entry(url.to_owned())
+                    .or_default()
+                    .extend(service[service.len() - 2].iter().cloned());
+            }
+        }
+        service_urls
+    }
+
+    /// Gets the URLs of each entry, with the URLs sorted so they compare as a set.
+    ///
+    /// The entries are the second to last array of a service. In object tag registries
+    /// the first array is contacts and the entries are the tags.
+    fn entries(&self) -> BTreeMap<String, Vec<String>> {
+        let Self::RdapBootstrapRegistry(bootstrap) = self;
+        let mut entries = BTreeMap::new();
+        for service in &bootstrap.services {
+            if service.len() < 2 {
+                continue;
+            }
+            let mut urls = service[service.len() - 1].clone();
+            urls.sort();
+            urls.dedup();
+            for entry in &service[service.len() - 2] {
+                entries.insert(entry.to_ascii_lowercase(), urls.clone());
+            }
+        }
+        entries
+    }
+}
+
+/// The differences between two IANA registries.
+#[derive(Debug, Serialize, Deserialize, Clone, Default, PartialEq, Eq)]
+pub struct RegistryDiff {
+    /// The publication time of this registry.
+    #[serde(default)]
+    pub old_publication: String,
+
+    /// The publication time of the other registry.
+    #[serde(default)]
+    pub new_publication: String,
+
+    /// Service URLs only in the other registry.
+    #[serde(default)]
+    pub added_services: Vec<String>,
+
+    /// Service URLs only in this registry.
+    #[serde(default)]
+    pub removed_services: Vec<String>,
+
+    /// Entries only in the other registry.
+    pub added: Vec<RegistryEntry>,
+
+    /// Entries only in this registry.
+    pub removed: Vec<RegistryEntry>,
+
+    /// Entries in both registries with different URLs.
+    pub changed: Vec<ChangedRegistryEntry>,
+}
+
+impl RegistryDiff {
+    /// True if the registries have the same entries and URLs.
+    pub fn is_empty(&self) -> bool {
+        self.added_services.is_empty()
+            && self.removed_services.is_empty()
+            && self.added.is_empty()
+            && self.removed.is_empty()
+            && self.changed.is_empty()
+    }
+}
+
+impl Display for RegistryDiff {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for added in &self.added_services {
+            writeln!(f, "+ service {added}")?;
+        }
+        for removed in &self.removed_services {
+            writeln!(f, "- service {removed}")?;
+        }
+        for added in &self.added {
+            writeln!(f, "+ {} {}", added.entry, added.urls.join(" "))?;
+        }
+        for removed in &self.removed {
+            writeln!(f, "- {} {}", removed.entry, removed.urls.join(" "))?;
+        }
+        for changed in &self.changed {
+            writeln!(
+                f,
+                "~ {} {} -> {}",
+                changed.entry,
+                changed.old_urls.join(" "),
+                changed.new_urls.join(" ")
+            )?;
+        }
+        Ok(())
+    }
+}
+
+/// An entry of an IANA registry, which is a TLD, ASN range, CIDR, or object tag, and its URLs.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
+pub struct RegistryEntry {
+    pub entry: String,
+    pub urls: Vec<String>,
+}
+
+/// An entry of an IANA registry with URLs that have changed.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
+pub struct ChangedRegistryEntry {
+    pub entry: String,
+    pub old_urls: Vec<String>,
+    pub new_urls: Vec<String>,
+}
+
 /// Prefer HTTPS urls.
 pub fn get_preferred_url(urls: Vec<String>) -> Result<String, BootstrapRegistryError> {
     if urls.is_empty() {
@@ -226,7 +601,10 @@ mod tests {
 
     use crate::iana::{get_preferred_url, BootstrapRegistry};
 
-    use super::{IanaRegistry, IanaRegistryType};
+    use super::{
+        ChangedRegistryEntry, IanaRegistry, IanaRegistryType, ParsedBootstrapRegistry,
+        RegistryDiff, RegistryEntry,
+    };
 
     #[rstest]
     #[case(IanaRegistryType::RdapBootstrapDns, "dns.json")]
@@ -743,4 +1121,266 @@ mod tests {
             "https://example.com/rdap/"
         );
     }
+
+    #[test]
+    fn GIVEN_two_dns_bootstraps_WHEN_diff_THEN_added_removed_and_changed_found() {
+        // GIVEN
+        let old = r#"
+            {
+                "version": "1.0",
+                "publication": "2024-01-07T10:11:12Z",
+                "services": [
+                  [["net", "com"], ["https://registry.example.com/myrdap/"]],
+                  [["org"], ["https://example.org/"]],
+                  [["mytld"], ["https://example.org/", "http://example.org/"]]
+                ]
+            }
+        "#;
+        let new = r#"
+            {
+                "version": "1.0",
+                "publication": "2024-02-07T10:11:12Z",
+                "services": [
+                  [["com"], ["https://registry.example.com/myrdap/"]],
+                  [["org"], ["https://rdap.example.org/"]],
+                  [["mytld"], ["http://example.org/", "https://example.org/"]],
+                  [["xn--zckzah"], ["https://example.net/rdap/xn--zckzah/"]]
+                ]
+            }
+        "#;
+        let old = serde_json::from_str::<IanaRegistry>(old).expect("cannot parse old");
+        let new = serde_json::from_str::<IanaRegistry>(new).expect("cannot parse new");
+
+        // WHEN
+        let actual = old.diff(&new);
+
+        // THEN
+        assert_eq!(
+            actual.added,
+            vec![RegistryEntry {
+                entry: "xn--zckzah".to_string(),
+                urls: vec!["https://example.net/rdap/xn--zckzah/".to_string()]
+            }]
+        );
+        assert_eq!(actual.removed.len(), 1);
+        assert_eq!(actual.removed[0].entry, "net");
+        assert_eq!(
+            actual.changed,
+            vec![ChangedRegistryEntry {
+                entry: "org".to_string(),
+                old_urls: vec!["https://example.org/".to_string()],
+                new_urls: vec!["https://rdap.example.org/".to_string()]
+            }]
+        );
+        assert_eq!(
+            actual.added_services,
+            vec![
+                "https://example.net/rdap/xn--zckzah/".to_string(),
+                "https://rdap.example.org/".to_string()
+            ]
+        );
+        assert!(actual.removed_services.is_empty());
+        assert_eq!(actual.old_publication, "2024-01-07T10:11:12Z");
+        assert_eq!(actual.new_publication, "2024-02-07T10:11:12Z");
+    }
+
+    #[test]
+    fn GIVEN_diff_with_services_WHEN_display_THEN_services_then_entries() {
+        // GIVEN
+        let diff = RegistryDiff {
+            added_services: vec!["https://rdap.example.net/".to_string()],
+            removed_services: vec!["https://rdap.example.org/".to_string()],
+            changed: vec![ChangedRegistryEntry {
+                entry: "org".to_string(),
+                old_urls: vec!["https://rdap.example.org/".to_string()],
+                new_urls: vec!["https://rdap.example.net/".to_string()],
+            }],
+            ..Default::default()
+        };
+
+        // WHEN
+        let actual = diff.to_string();
+
+        // THEN
+        assert_eq!(
+            actual,
+            "+ service https://rdap.example.net/\n\
+             - service https://rdap.example.org/\n\
+             ~ org https://rdap.example.org/ -> https://rdap.example.net/\n"
+        );
+    }
+
+    #[test]
+    fn GIVEN_same_tag_bootstraps_WHEN_diff_THEN_empty() {
+        // GIVEN
+        let bootstrap = r#"
+            {
+                "version": "1.0",
+                "publication": "2024-01-07T10:11:12Z",
+                "services": [
+                  [["contact@example.com"], ["ARIN"], ["https://rdap.arin.net/registry/"]]
+                ]
+            }
+        "#;
+        let registry = serde_json::from_str::<IanaRegistry>(bootstrap).expect("cannot parse");
+
+        // WHEN
+        let actual = registry.diff(&registry);
+
+        // THEN
+        assert!(actual.is_empty());
+    }
+
+    #[rstest]
+    #[case(
+        IanaRegistryType::RdapBootstrapDns,
+        "foo.example.com",
+        Some("https://registry.example.com/myrdap/")
+    )]
+    #[case(
+        IanaRegistryType::RdapBootstrapDns,
+        "foo.mytld.",
+        Some("https://example.org/")
+    )]
+    #[case(
+        IanaRegistryType::RdapBootstrapDns,
+        "FOO.XN--ZCKZAH",
+        Some("https://example.net/rdap/xn--zckzah/")
+    )]
+    #[case(IanaRegistryType::RdapBootstrapDns, "foo.invalid", None)]
+    #[case(
+        IanaRegistryType::RdapBootstrapAsn,
+        "as64496",
+        Some("https://rir1.example.com/myrdap/")
+    )]
+    #[case(
+        IanaRegistryType::RdapBootstrapAsn,
+        "64511",
+        Some("https://rir1.example.com/myrdap/")
+    )]
+    #[case(
+        IanaRegistryType::RdapBootstrapAsn,
+        "4200000000",
+        Some("https://example.org/")
+    )]
+    #[case(IanaRegistryType::RdapBootstrapAsn, "64512", None)]
+    #[case(IanaRegistryType::RdapBootstrapAsn, "1", None)]
+    #[case(
+        IanaRegistryType::RdapBootstrapIpv4,
+        "198.51.100.1/32",
+        Some("https://rir1.example.com/myrdap/")
+    )]
+    #[case(
+        IanaRegistryType::RdapBootstrapIpv4,
+        "198.51.100.0/24",
+        Some("https://rir1.example.com/myrdap/")
+    )]
+    #[case(
+        IanaRegistryType::RdapBootstrapIpv4,
+        "203.0.113.0/24",
+        Some("https://example.org/")
+    )]
+    #[case(IanaRegistryType::RdapBootstrapIpv4, "10.0.0.0/8", None)]
+    #[case(
+        IanaRegistryType::RdapBootstrapIpv6,
+        "2001:db8::1/128",
+        Some("https://rir2.example.com/myrdap/")
+    )]
+    #[case(
+        IanaRegistryType::RdapBootstrapIpv6,
+        "2001:db8:4000::/36",
+        Some("https://rir3.example.com/myrdap/")
+    )]
+    #[case(IanaRegistryType::RdapBootstrapIpv6, "3fff::/20", None)]
+    #[case(
+        IanaRegistryType::RdapObjectTags,
+        "arin",
+        Some("https://rdap.arin.net/registry/")
+    )]
+    #[case(
+        IanaRegistryType::RdapObjectTags,
+        "FRNIC",
+        Some("https://rdap.nic.fr/")
+    )]
+    #[case(IanaRegistryType::RdapObjectTags, "NOPE", None)]
+    fn GIVEN_parsed_registry_WHEN_get_bootstrap_urls_THEN_same_as_registry(
+        #[case] reg_type: IanaRegistryType,
+        #[case] query: &str,
+        #[case] expected: Option<&str>,
+    ) {
+        // GIVEN
+        let bootstrap = match reg_type {
+            IanaRegistryType::RdapBootstrapDns => {
+                r#"{"version": "1.0", "publication": "2024-01-07T10:11:12Z", "services": [
+                    [["net", "com"], ["https://registry.example.com/myrdap/"]],
+                    [["org", "mytld"], ["https://example.org/"]],
+                    [["xn--zckzah"], ["https://example.net/rdap/xn--zckzah/"]]
+                ]}"#
+            }
+            IanaRegistryType::RdapBootstrapAsn => {
+                r#"{"version": "1.0", "publication": "2024-01-07T10:11:12Z", "services": [
+                    [["4200000000-4294967294"], ["https://example.org/"]],
+                    [["64496-64511", "65536-65551"], ["https://rir1.example.com/myrdap/"]]
+                ]}"#
+            }
+            IanaRegistryType::RdapBootstrapIpv4 => {
+                r#"{"version": "1.0", "publication": "2024-01-07T10:11:12Z", "services": [
+                    [["198.51.100.0/24", "192.0.0.0/8"], ["https://rir1.example.com/myrdap/"]],
+                    [["203.0.113.0/24"], ["https://example.org/"]]
+                ]}"#
+            }
+            IanaRegistryType::RdapBootstrapIpv6 => {
+                r#"{"version": "1.0", "publication": "2024-01-07T10:11:12Z", "services": [
+                    [["2001:db8::/34"], ["https://rir2.example.com/myrdap/"]],
+                    [["2001:db8:4000::/36", "2001:db8:ffff::/48"], ["https://rir3.example.com/myrdap/"]]
+                ]}"#
+            }
+            IanaRegistryType::RdapObjectTags => {
+                r#"{"version": "1.0", "publication": "2024-01-07T10:11:12Z", "services": [
+                    [["andy@example.com"], ["ARIN"], ["https://rdap.arin.net/registry/"]],
+                    [["carol@example.fr"], ["FRNIC"], ["https://rdap.nic.fr/"]]
+                ]}"#
+            }
+        };
+        let registry = serde_json::from_str::<IanaRegistry>(bootstrap).expect("cannot parse");
+
+        // WHEN
+        let parsed = ParsedBootstrapRegistry::parse(&reg_type, &registry).expect("parse");
+
+        // THEN
+        let lookup = |r: &dyn BootstrapRegistry| match reg_type {
+            IanaRegistryType::RdapBootstrapDns => r.get_dns_bootstrap_urls(query),
+            IanaRegistryType::RdapBootstrapAsn => r.get_asn_bootstrap_urls(query),
+            IanaRegistryType::RdapBootstrapIpv4 => r.get_ipv4_bootstrap_urls(query),
+            IanaRegistryType::RdapBootstrapIpv6 => r.get_ipv6_bootstrap_urls(query),
+            IanaRegistryType::RdapObjectTags => r.get_tag_bootstrap_urls(query),
+        };
+        let actual = lookup(&parsed).ok();
+        assert_eq!(
+            actual
+                .as_ref()
+                .and_then(|urls| urls.first())
+                .map(String::as_str),
+            expected
+        );
+        // the registry does not ignore case or trailing dots in names.
+        if query == query.to_ascii_lowercase() && !query.ends_with('.') {
+            assert_eq!(actual, lookup(&registry).ok());
+        }
+    }
+
+    #[test]
+    fn GIVEN_dns_registry_parsed_as_asn_WHEN_parse_THEN_error() {
+        // GIVEN
+        let bootstrap = r#"{"version": "1.0", "publication": "2024-01-07T10:11:12Z", "services": [
+            [["net", "com"], ["https://registry.example.com/myrdap/"]]
+        ]}"#;
+        let registry = serde_json::from_str::<IanaRegistry>(bootstrap).expect("cannot parse");
+
+        // WHEN
+        let actual = ParsedBootstrapRegistry::parse(&IanaRegistryType::RdapBootstrapAsn, &registry);
+
+        // THEN
+        assert!(actual.is_err());
+    }
 }
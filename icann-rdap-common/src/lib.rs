@@ -9,10 +9,25 @@ pub mod media_types;
 pub mod response;
 
 /// Basics RDAP structures.
+///
+/// The items documented here are the stable API of this crate, which changes only
+/// with a major release. The rest of the RDAP structures are also here so that a
+/// glob import brings them in, but they may change between minor releases.
 pub mod prelude {
     #[doc(inline)]
-    pub use crate::contact::*;
+    pub use crate::check::{CheckClass, CheckItem, CheckParams, Checks, GetChecks};
+    #[doc(inline)]
+    pub use crate::contact::{Contact, Email, Lang, NameParts, Phone, PostalAddress};
     #[doc(inline)]
+    pub use crate::response::{
+        Autnum, AutnumSearchResults, Domain, DomainSearchResults, Entity, EntityRole,
+        EntitySearchResults, Help, IpSearchResults, Nameserver, NameserverSearchResults, Network,
+        RdapResponse, RdapResponseError, Rfc9083Error, ToResponse,
+    };
+
+    #[doc(hidden)]
+    pub use crate::contact::*;
+    #[doc(hidden)]
     pub use crate::response::*;
 }
 
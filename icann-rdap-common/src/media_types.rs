@@ -5,3 +5,176 @@ pub const JSON_MEDIA_TYPE: &str = "application/json";
 
 /// The "application/rdap+json" media type value.
 pub const RDAP_MEDIA_TYPE: &str = "application/rdap+json";
+
+/// The "application/rdap-x+json" media type value, which is the RDAP-X media type
+/// used to negotiate RDAP extensions with its "extensions" parameter.
+pub const RDAP_X_MEDIA_TYPE: &str = "application/rdap-x+json";
+
+/// Gets the essence of a media type, which is its type and subtype without
+/// any parameters, in lowercase.
+///
+/// ```rust
+/// use icann_rdap_common::media_types::media_type_essence;
+///
+/// assert_eq!(media_type_essence("Application/RDAP+JSON; charset=utf-8"), "application/rdap+json");
+/// ```
+pub fn media_type_essence(media_type: &str) -> String {
+    media_type
+        .split(';')
+        .next()
+        .unwrap_or_default()
+        .trim()
+        .to_ascii_lowercase()
+}
+
+/// Gets the value of the charset parameter of a media type, if it has one.
+pub fn media_type_charset(media_type: &str) -> Option<&str> {
+    media_type.split(';').skip(1).find_map(|param| {
+        let (name, value) = param.split_once('=')?;
+        name.trim()
+            .eq_ignore_ascii_case("charset")
+            .then(|| value.trim().trim_matches('"'))
+    })
+}
+
+/// Gets the extension identifiers of the "extensions" parameter of a media type, if it has one.
+///
+/// ```rust
+/// use icann_rdap_common::media_types::media_type_extensions;
+///
+/// let media_type = r#"application/rdap-x+json; extensions="rdap_level_0 redacted""#;
+/// assert_eq!(media_type_extensions(media_type), Some(vec!["rdap_level_0", "redacted"]));
+/// ```
+pub fn media_type_extensions(media_type: &str) -> Option<Vec<&str>> {
+    media_type.split(';').skip(1).find_map(|param| {
+        let (name, value) = param.split_once('=')?;
+        name.trim()
+            .eq_ignore_ascii_case("extensions")
+            .then(|| value.trim().trim_matches('"').split_whitespace().collect())
+    })
+}
+
+/// Makes an RDAP-X media type with the extension identifiers in its "extensions" parameter.
+///
+/// ```rust
+/// use icann_rdap_common::media_types::rdap_x_media_type;
+///
+/// let media_type = rdap_x_media_type(&["rdap_level_0", "redacted"]);
+/// assert_eq!(media_type, r#"application/rdap-x+json;extensions="rdap_level_0 redacted""#);
+/// ```
+pub fn rdap_x_media_type(extensions: &[impl AsRef<str>]) -> String {
+    let extensions = extensions
+        .iter()
+        .map(AsRef::as_ref)
+        .collect::<Vec<_>>()
+        .join(" ");
+    format!("{RDAP_X_MEDIA_TYPE};extensions=\"{extensions}\"")
+}
+
+/// Returns true if the media type is the RDAP-X media type, regardless of case or parameters.
+pub fn is_rdap_x_media_type(media_type: &str) -> bool {
+    media_type_essence(media_type) == RDAP_X_MEDIA_TYPE
+}
+
+/// Returns true if the media type is the RDAP media type, regardless of case or parameters.
+pub fn is_rdap_media_type(media_type: &str) -> bool {
+    media_type_essence(media_type) == RDAP_MEDIA_TYPE
+}
+
+/// Returns true if the media type is the JSON media type, regardless of case or parameters.
+pub fn is_json_media_type(media_type: &str) -> bool {
+    media_type_essence(media_type) == JSON_MEDIA_TYPE
+}
+
+#[cfg(test)]
+#[allow(non_snake_case)]
+mod tests {
+    use rstest::rstest;
+
+    use super::{
+        is_json_media_type, is_rdap_media_type, is_rdap_x_media_type, media_type_charset,
+        media_type_extensions,
+    };
+
+    #[rstest]
+    #[case("application/rdap+json", true, false)]
+    #[case("application/rdap+json;charset=UTF-8", true, false)]
+    #[case("APPLICATION/RDAP+JSON ; charset=utf-8", true, false)]
+    #[case("application/rdap+jsonx", false, false)]
+    #[case("application/rdap", false, false)]
+    #[case("application/json; charset=utf-8", false, true)]
+    #[case("text/html", false, false)]
+    fn GIVEN_media_type_WHEN_is_rdap_or_json_THEN_correct(
+        #[case] media_type: &str,
+        #[case] rdap: bool,
+        #[case] json: bool,
+    ) {
+        // GIVEN media_type
+
+        // WHEN
+        let actual_rdap = is_rdap_media_type(media_type);
+        let actual_json = is_json_media_type(media_type);
+
+        // THEN
+        assert_eq!(actual_rdap, rdap);
+        assert_eq!(actual_json, json);
+    }
+
+    #[rstest]
+    #[case("application/rdap+json", None)]
+    #[case("application/rdap+json;charset=UTF-8", Some("UTF-8"))]
+    #[case(
+        "application/rdap+json; foo=bar; Charset=\"iso-8859-1\"",
+        Some("iso-8859-1")
+    )]
+    fn GIVEN_media_type_WHEN_charset_THEN_parameter_value(
+        #[case] media_type: &str,
+        #[case] expected: Option<&str>,
+    ) {
+        // GIVEN media_type
+
+        // WHEN
+        let actual = media_type_charset(media_type);
+
+        // THEN
+        assert_eq!(actual, expected);
+    }
+
+    #[rstest]
+    #[case("application/rdap-x+json", true)]
+    #[case("Application/RDAP-X+JSON; extensions=\"redacted\"", true)]
+    #[case("application/rdap+json", false)]
+    fn GIVEN_media_type_WHEN_is_rdap_x_THEN_correct(
+        #[case] media_type: &str,
+        #[case] expected: bool,
+    ) {
+        // GIVEN media_type
+
+        // WHEN
+        let actual = is_rdap_x_media_type(media_type);
+
+        // THEN
+        assert_eq!(actual, expected);
+    }
+
+    #[rstest]
+    #[case("application/rdap-x+json", None)]
+    #[case("application/rdap-x+json;extensions=\"\"", Some(vec![]))]
+    #[case("application/rdap-x+json;extensions=redacted", Some(vec!["redacted"]))]
+    #[case(
+        "application/rdap-x+json; charset=utf-8; Extensions=\" rdap_level_0   cidr0 \"",
+        Some(vec!["rdap_level_0", "cidr0"])
+    )]
+    fn GIVEN_media_type_WHEN_extensions_THEN_parameter_values(
+        #[case] media_type: &str,
+        #[case] expected: Option<Vec<&str>>,
+    ) {
+        // GIVEN media_type
+
+        // WHEN
+        let actual = media_type_extensions(media_type);
+
+        // THEN
+        assert_eq!(actual, expected);
+    }
+}
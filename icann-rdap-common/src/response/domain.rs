@@ -3,6 +3,7 @@ use {
     crate::prelude::{Common, Extension, ObjectCommon},
     buildstructor::Builder,
     serde::{Deserialize, Serialize},
+    strum_macros::{Display, EnumString},
 };
 
 use super::{
@@ -89,6 +90,19 @@ impl Variant {
     }
 }
 
+/// IANA registered domain variant relations.
+#[derive(PartialEq, Eq, Debug, EnumString, Display)]
+#[strum(serialize_all = "lowercase")]
+pub enum VariantRelation {
+    Registered,
+    Unregistered,
+    #[strum(serialize = "registration restricted")]
+    RegistrationRestricted,
+    #[strum(serialize = "open registration")]
+    OpenRegistration,
+    Conjoined,
+}
+
 /// Represents `dsData`.
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
 pub struct DsDatum {
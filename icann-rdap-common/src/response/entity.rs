@@ -1,4 +1,6 @@
 //! Entity object class.
+use std::sync::OnceLock;
+
 use {
     crate::{
         contact::Contact,
@@ -108,6 +110,10 @@ pub struct Entity {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub vcard_array: Option<Vec<Value>>,
 
+    #[serde(rename = "jscontact_card")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub jscontact_card: Option<Value>,
+
     #[serde(skip_serializing_if = "Option::is_none")]
     pub roles: Option<VectorStringish>,
 
@@ -124,8 +130,32 @@ pub struct Entity {
 
     #[serde(skip_serializing_if = "Option::is_none")]
     pub networks: Option<Vec<Network>>,
+
+    #[serde(skip)]
+    contact_cache: ContactCache,
 }
 
+/// The [Contact] of an entity, parsed when first needed.
+///
+/// The cache is ignored when comparing entities and is emptied when cloning them,
+/// so that a cloned entity may be given a different vCard or JSContact.
+#[derive(Debug, Default)]
+struct ContactCache(OnceLock<Option<Contact>>);
+
+impl Clone for ContactCache {
+    fn clone(&self) -> Self {
+        Self::default()
+    }
+}
+
+impl PartialEq for ContactCache {
+    fn eq(&self, _other: &Self) -> bool {
+        true
+    }
+}
+
+impl Eq for ContactCache {}
+
 #[buildstructor::buildstructor]
 impl Entity {
     /// Builds a basic entity object for with embedding into other objects.
@@ -186,11 +216,13 @@ impl Entity {
                 .and_redacted(redacted)
                 .build(),
             vcard_array: contact.map(|c| c.to_vcard()),
+            jscontact_card: None,
             roles: to_opt_vectorstringish(roles),
             public_ids: to_opt_vec(public_ids),
             as_event_actor: to_opt_vec(as_event_actors),
             autnums: to_opt_vec(autnums),
             networks: to_opt_vec(networks),
+            contact_cache: ContactCache::default(),
         }
     }
 
@@ -268,10 +300,21 @@ impl Entity {
         entity
     }
 
-    /// Get a [Contact] from the impentrable vCard.
-    pub fn contact(&self) -> Option<Contact> {
-        let vcard = self.vcard_array.as_ref()?;
-        Contact::from_vcard(vcard)
+    /// Get a [Contact] from the impentrable vCard, or from JSContact if there is no vCard.
+    ///
+    /// The contact is parsed once, on the first call, so changes to the vCard or JSContact
+    /// made afterward are not reflected. Clone the entity before changing them.
+    pub fn contact(&self) -> Option<&Contact> {
+        self.contact_cache
+            .0
+            .get_or_init(|| {
+                if let Some(vcard) = self.vcard_array.as_ref() {
+                    return Contact::from_vcard(vcard);
+                }
+                let jscontact = self.jscontact_card.as_ref()?;
+                Contact::from_jscontact(jscontact)
+            })
+            .as_ref()
     }
 
     /// Get the roles.
@@ -363,6 +406,8 @@ pub enum EntityRole {
 
 #[cfg(test)]
 mod tests {
+    use crate::contact::Contact;
+
     use super::Entity;
 
     #[test]
@@ -501,4 +546,35 @@ mod tests {
         assert!(actual.object_common.events.is_some());
         assert!(actual.as_event_actor.is_some());
     }
+
+    #[test]
+    fn test_entity_contact_is_cached_until_cloned() {
+        // GIVEN
+        let entity = Entity::builder()
+            .handle("foo_example_com-1")
+            .contact(Contact::builder().full_name("Bob Smurd").build())
+            .build();
+        let full_name = entity.contact().and_then(|c| c.full_name());
+
+        // WHEN
+        let mut cloned = entity.clone();
+        cloned.vcard_array = Some(
+            Contact::builder()
+                .full_name("Alice Smurd")
+                .build()
+                .to_vcard(),
+        );
+
+        // THEN
+        assert_eq!(full_name, Some("Bob Smurd"));
+        assert_eq!(
+            entity.contact().and_then(|c| c.full_name()),
+            Some("Bob Smurd")
+        );
+        assert_eq!(
+            cloned.contact().and_then(|c| c.full_name()),
+            Some("Alice Smurd")
+        );
+        assert_eq!(entity, entity.clone());
+    }
 }
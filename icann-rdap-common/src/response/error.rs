@@ -79,8 +79,21 @@ impl Rfc9083Error {
     }
 
     /// Creates an RFC 9083 error for an HTTP redirect.
+    ///
+    /// The error code is 307 (Temporary Redirect) unless another is given, such as 301
+    /// (Moved Permanently) for resources that are permanently served elsewhere.
+    ///
+    /// ```rust
+    /// use icann_rdap_common::prelude::*;
+    ///
+    /// let e = Rfc9083Error::redirect()
+    ///   .url("https://rdap.other-rir.example/ip/192.0.2.0/24")
+    ///   .error_code(301)
+    ///   .build();
+    /// assert!(e.is_redirect());
+    /// ```
     #[builder(entry = "redirect", visibility = "pub")]
-    fn new_redirect(url: String, extensions: Vec<Extension>) -> Self {
+    fn new_redirect(url: String, error_code: Option<u16>, extensions: Vec<Extension>) -> Self {
         let links = vec![Link::builder()
             .href(&url)
             .value(&url)
@@ -93,7 +106,7 @@ impl Rfc9083Error {
                 .extensions(extensions)
                 .notices(notices)
                 .build(),
-            error_code: 307,
+            error_code: error_code.unwrap_or(307),
             title: None,
             description: None,
         }
@@ -135,6 +148,8 @@ impl ToResponse for Rfc9083Error {
 #[cfg(test)]
 #[allow(non_snake_case)]
 mod tests {
+    use crate::response::CommonFields;
+
     use super::Rfc9083Error;
 
     #[test]
@@ -149,6 +164,36 @@ mod tests {
         assert!(actual);
     }
 
+    #[test]
+    fn GIVEN_redirect_without_error_code_WHEN_build_THEN_error_code_is_307() {
+        // GIVEN
+        let builder = Rfc9083Error::redirect().url("https://foo.example");
+
+        // WHEN
+        let actual = builder.build();
+
+        // THEN
+        assert_eq!(actual.error_code(), 307);
+    }
+
+    #[test]
+    fn GIVEN_redirect_with_error_code_301_WHEN_build_THEN_error_code_is_301() {
+        // GIVEN
+        let builder = Rfc9083Error::redirect()
+            .url("https://foo.example")
+            .error_code(301);
+
+        // WHEN
+        let actual = builder.build();
+
+        // THEN
+        assert_eq!(actual.error_code(), 301);
+        assert_eq!(
+            actual.notices()[0].links()[0].href(),
+            Some("https://foo.example")
+        );
+    }
+
     #[test]
     fn GIVEN_error_code_404_WHEN_is_redirect_THEN_false() {
         // GIVEN
@@ -0,0 +1,295 @@
+//! Filtering of the members of RDAP objects, such as for privacy policies.
+
+use {
+    serde::{Deserialize, Serialize},
+    serde_json::{Map, Value},
+};
+
+use super::{RdapResponse, RdapResponseError};
+
+/// The text that masked values are replaced with by default.
+pub const DEFAULT_MASK: &str = "REDACTED";
+
+/// The members that identify an object, which are kept though not listed in
+/// [ResponseFilter::keep].
+const IDENTIFYING_MEMBERS: [&str; 10] = [
+    "objectClassName",
+    "rdapConformance",
+    "handle",
+    "ldhName",
+    "unicodeName",
+    "startAddress",
+    "endAddress",
+    "ipVersion",
+    "startAutnum",
+    "endAutnum",
+];
+
+/// The member that is never filtered, as it is needed to know what an object is.
+const OBJECT_CLASS_NAME: &str = "objectClassName";
+
+/// A filter that removes or masks the members of RDAP objects.
+///
+/// Filters apply to every object in a response, whether at the top or embedded
+/// in another object or in search results. Members are named as they are in the
+/// JSON of RDAP, and `objectClassName` is never filtered. A filter is usually
+/// read from a JSON file.
+///
+/// ```rust
+/// use icann_rdap_common::{prelude::*, response::filter::ResponseFilter};
+///
+/// let filter = ResponseFilter::from_json(r#"
+///   {
+///     "keep": [ "status", "events", "entities", "vcardArray" ],
+///     "remove_vcard": [ "adr" ],
+///     "mask_vcard": [ "email", "tel" ]
+///   }
+/// "#).unwrap();
+///
+/// let domain = Domain::builder()
+///   .ldh_name("foo.example.com")
+///   .status("active")
+///   .port_43("whois.example.com".to_string())
+///   .build()
+///   .to_response();
+/// let filtered = filter.apply(&domain).unwrap();
+/// let RdapResponse::Domain(filtered) = filtered else { unreachable!() };
+/// assert_eq!(filtered.ldh_name(), Some("foo.example.com"));
+/// assert!(filtered.port_43().is_none());
+/// ```
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
+#[serde(deny_unknown_fields)]
+pub struct ResponseFilter {
+    /// If not empty, the only members kept in objects, besides those identifying them
+    /// such as `handle` and `ldhName`.
+    #[serde(default)]
+    pub keep: Vec<String>,
+
+    /// Members removed from objects.
+    #[serde(default)]
+    pub remove: Vec<String>,
+
+    /// Members with their text replaced by [ResponseFilter::mask_with].
+    #[serde(default)]
+    pub mask: Vec<String>,
+
+    /// vCard properties removed from the `vcardArray` of entities, such as "adr".
+    #[serde(default)]
+    pub remove_vcard: Vec<String>,
+
+    /// vCard properties with their values replaced by [ResponseFilter::mask_with],
+    /// such as "email" and "tel".
+    #[serde(default)]
+    pub mask_vcard: Vec<String>,
+
+    /// The text that masked values are replaced with.
+    #[serde(default = "default_mask")]
+    pub mask_with: String,
+}
+
+fn default_mask() -> String {
+    DEFAULT_MASK.to_string()
+}
+
+impl Default for ResponseFilter {
+    fn default() -> Self {
+        Self {
+            keep: vec![],
+            remove: vec![],
+            mask: vec![],
+            remove_vcard: vec![],
+            mask_vcard: vec![],
+            mask_with: default_mask(),
+        }
+    }
+}
+
+impl ResponseFilter {
+    /// Parses a filter from JSON.
+    pub fn from_json(json: &str) -> Result<Self, serde_json::Error> {
+        serde_json::from_str(json)
+    }
+
+    /// Returns true if the filter changes nothing.
+    pub fn is_empty(&self) -> bool {
+        self.keep.is_empty()
+            && self.remove.is_empty()
+            && self.mask.is_empty()
+            && self.remove_vcard.is_empty()
+            && self.mask_vcard.is_empty()
+    }
+
+    /// Applies the filter to a response, giving the filtered response.
+    pub fn apply(&self, rdap: &RdapResponse) -> Result<RdapResponse, RdapResponseError> {
+        let mut value = serde_json::to_value(rdap)?;
+        self.apply_value(&mut value);
+        RdapResponse::try_from(value)
+    }
+
+    /// Applies the filter to the JSON of a response.
+    pub fn apply_value(&self, value: &mut Value) {
+        match value {
+            Value::Object(object) => {
+                if object.contains_key(OBJECT_CLASS_NAME) {
+                    self.filter_object(object);
+                }
+                object
+                    .values_mut()
+                    .for_each(|value| self.apply_value(value));
+            }
+            Value::Array(values) => values.iter_mut().for_each(|value| self.apply_value(value)),
+            _ => {}
+        }
+    }
+
+    fn filter_object(&self, object: &mut Map<String, Value>) {
+        if !self.keep.is_empty() {
+            object.retain(|name, _| {
+                IDENTIFYING_MEMBERS.contains(&name.as_str()) || self.keep.contains(name)
+            });
+        }
+        object.retain(|name, _| name == OBJECT_CLASS_NAME || !self.remove.contains(name));
+        for name in self.mask.iter().filter(|name| *name != OBJECT_CLASS_NAME) {
+            if let Some(value) = object.get_mut(name) {
+                mask(value, &self.mask_with);
+            }
+        }
+
+        // a jCard is ["vcard", [[name, parameters, type, value, ...], ...]]
+        let Some(Value::Array(properties)) = object
+            .get_mut("vcardArray")
+            .and_then(|vcard| vcard.get_mut(1))
+        else {
+            return;
+        };
+        let is_listed = |list: &[String], property: &Value| {
+            property
+                .get(0)
+                .and_then(Value::as_str)
+                .is_some_and(|name| list.iter().any(|listed| listed.eq_ignore_ascii_case(name)))
+        };
+        properties.retain(|property| !is_listed(&self.remove_vcard, property));
+        properties
+            .iter_mut()
+            .filter(|property| is_listed(&self.mask_vcard, property))
+            .filter_map(Value::as_array_mut)
+            .flat_map(|property| property.iter_mut().skip(3))
+            .for_each(|value| mask(value, &self.mask_with));
+    }
+}
+
+/// Replaces all the text in a value that is not empty.
+fn mask(value: &mut Value, mask_with: &str) {
+    match value {
+        Value::String(text) if !text.is_empty() => *text = mask_with.to_string(),
+        Value::Array(values) => values.iter_mut().for_each(|value| mask(value, mask_with)),
+        Value::Object(object) => object.values_mut().for_each(|value| mask(value, mask_with)),
+        _ => {}
+    }
+}
+
+#[cfg(test)]
+#[allow(non_snake_case)]
+mod tests {
+    use serde_json::json;
+
+    use super::ResponseFilter;
+
+    fn domain() -> serde_json::Value {
+        json!({
+            "objectClassName": "domain",
+            "ldhName": "foo.example.com",
+            "status": ["active"],
+            "port43": "whois.example.com",
+            "entities": [{
+                "objectClassName": "entity",
+                "handle": "FOO",
+                "roles": ["registrant"],
+                "vcardArray": ["vcard", [
+                    ["version", {}, "text", "4.0"],
+                    ["fn", {}, "text", "Joe User"],
+                    ["email", {}, "text", "joe@example.com"],
+                    ["adr", {}, "text", ["", "", "123 Main St", "Any Town", "", "", ""]]
+                ]]
+            }]
+        })
+    }
+
+    #[test]
+    fn GIVEN_keep_WHEN_apply_value_THEN_only_kept_and_identifying_members() {
+        // GIVEN
+        let filter = ResponseFilter::from_json(r#"{"keep": ["status"]}"#).expect("parsing filter");
+        let mut value = domain();
+
+        // WHEN
+        filter.apply_value(&mut value);
+
+        // THEN
+        assert_eq!(
+            value,
+            json!({
+                "objectClassName": "domain",
+                "ldhName": "foo.example.com",
+                "status": ["active"]
+            })
+        );
+    }
+
+    #[test]
+    fn GIVEN_remove_and_mask_WHEN_apply_value_THEN_embedded_objects_filtered() {
+        // GIVEN
+        let filter = ResponseFilter::from_json(
+            r#"{"remove": ["vcardArray", "objectClassName"], "mask": ["roles", "port43"], "mask_with": "X"}"#,
+        )
+        .expect("parsing filter");
+        let mut value = domain();
+
+        // WHEN
+        filter.apply_value(&mut value);
+
+        // THEN
+        assert_eq!(value["port43"], "X");
+        assert_eq!(value["entities"][0]["objectClassName"], "entity");
+        assert_eq!(value["entities"][0]["roles"], json!(["X"]));
+        assert!(value["entities"][0].get("vcardArray").is_none());
+    }
+
+    #[test]
+    fn GIVEN_vcard_filter_WHEN_apply_value_THEN_properties_removed_and_masked() {
+        // GIVEN
+        let filter =
+            ResponseFilter::from_json(r#"{"remove_vcard": ["EMAIL"], "mask_vcard": ["adr"]}"#)
+                .expect("parsing filter");
+        let mut value = domain();
+
+        // WHEN
+        filter.apply_value(&mut value);
+
+        // THEN
+        assert_eq!(
+            value["entities"][0]["vcardArray"][1],
+            json!([
+                ["version", {}, "text", "4.0"],
+                ["fn", {}, "text", "Joe User"],
+                [
+                    "adr",
+                    {},
+                    "text",
+                    ["", "", "REDACTED", "REDACTED", "", "", ""]
+                ]
+            ])
+        );
+    }
+
+    #[test]
+    fn GIVEN_filter_with_unknown_field_WHEN_from_json_THEN_error() {
+        // GIVEN
+        let json = r#"{"strip": ["vcardArray"]}"#;
+
+        // WHEN
+        let actual = ResponseFilter::from_json(json);
+
+        // THEN
+        assert!(actual.is_err());
+    }
+}
@@ -6,6 +6,64 @@ use {
 
 use super::{to_opt_vec, Common, CommonFields, ToResponse};
 
+/// A reverse search property mapping (RFC 9536).
+///
+/// These appear in help responses to describe the JSONPath to each property
+/// that may be used in a reverse search.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
+pub struct ReverseSearchMapping {
+    #[serde(rename = "searchableResourceType")]
+    pub searchable_resource_type: Option<String>,
+
+    #[serde(rename = "relatedResourceType")]
+    pub related_resource_type: Option<String>,
+
+    #[serde(rename = "property")]
+    pub property: Option<String>,
+
+    #[serde(rename = "propertyPath")]
+    pub property_path: Option<String>,
+}
+
+#[buildstructor::buildstructor]
+impl ReverseSearchMapping {
+    /// Builds a reverse search property mapping.
+    #[builder(visibility = "pub")]
+    fn new(
+        searchable_resource_type: String,
+        related_resource_type: String,
+        property: String,
+        property_path: String,
+    ) -> Self {
+        Self {
+            searchable_resource_type: Some(searchable_resource_type),
+            related_resource_type: Some(related_resource_type),
+            property: Some(property),
+            property_path: Some(property_path),
+        }
+    }
+
+    /// Get the searchable resource type, such as "domains".
+    pub fn searchable_resource_type(&self) -> Option<&str> {
+        self.searchable_resource_type.as_deref()
+    }
+
+    /// Get the related resource type, such as "entity".
+    pub fn related_resource_type(&self) -> Option<&str> {
+        self.related_resource_type.as_deref()
+    }
+
+    /// Get the property, such as "fn".
+    pub fn property(&self) -> Option<&str> {
+        self.property.as_deref()
+    }
+
+    /// Get the JSONPath of the property.
+    pub fn property_path(&self) -> Option<&str> {
+        self.property_path.as_deref()
+    }
+}
+
 /// Represents an RDAP help response.
 ///
 /// Use the builders to create one:
@@ -22,20 +80,36 @@ use super::{to_opt_vec, Common, CommonFields, ToResponse};
 pub struct Help {
     #[serde(flatten)]
     pub common: Common,
+
+    #[serde(rename = "reverse_search_properties")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub reverse_search_properties: Option<Vec<ReverseSearchMapping>>,
 }
 
 #[buildstructor::buildstructor]
 impl Help {
     /// Builds a basic help response.
     #[builder(entry = "response_obj", visibility = "pub")]
-    fn new_response_obj(notices: Vec<Notice>, extensions: Vec<Extension>) -> Self {
+    fn new_response_obj(
+        notices: Vec<Notice>,
+        extensions: Vec<Extension>,
+        reverse_search_properties: Vec<ReverseSearchMapping>,
+    ) -> Self {
         Self {
             common: Common::level0()
                 .extensions(extensions)
                 .and_notices(to_opt_vec(notices))
                 .build(),
+            reverse_search_properties: to_opt_vec(reverse_search_properties),
         }
     }
+
+    /// Get the reverse search property mappings.
+    pub fn reverse_search_properties(&self) -> &[ReverseSearchMapping] {
+        self.reverse_search_properties
+            .as_deref()
+            .unwrap_or_default()
+    }
 }
 
 impl CommonFields for Help {
@@ -49,3 +123,42 @@ impl ToResponse for Help {
         super::RdapResponse::Help(Box::new(self))
     }
 }
+
+#[cfg(test)]
+#[allow(non_snake_case)]
+mod tests {
+    use super::Help;
+
+    #[test]
+    fn GIVEN_help_with_reverse_search_properties_WHEN_deserialize_THEN_mappings_found() {
+        // GIVEN
+        let json = r#"
+            {
+              "rdapConformance": ["rdap_level_0", "reverse_search"],
+              "notices": [{"description": ["help"]}],
+              "reverse_search_properties": [
+                {
+                  "searchableResourceType": "domains",
+                  "relatedResourceType": "entity",
+                  "property": "fn",
+                  "propertyPath": "$.entities[*].vcardArray[1][?(@[0]=='fn')][3]"
+                }
+              ]
+            }
+        "#;
+
+        // WHEN
+        let actual = serde_json::from_str::<Help>(json).expect("parsing help");
+
+        // THEN
+        let mappings = actual.reverse_search_properties();
+        assert_eq!(mappings.len(), 1);
+        assert_eq!(mappings[0].searchable_resource_type(), Some("domains"));
+        assert_eq!(mappings[0].related_resource_type(), Some("entity"));
+        assert_eq!(mappings[0].property(), Some("fn"));
+        assert_eq!(
+            mappings[0].property_path(),
+            Some("$.entities[*].vcardArray[1][?(@[0]=='fn')][3]")
+        );
+    }
+}
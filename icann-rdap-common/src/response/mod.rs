@@ -41,6 +41,7 @@ pub(crate) mod common;
 pub(crate) mod domain;
 pub(crate) mod entity;
 pub(crate) mod error;
+pub mod filter;
 pub(crate) mod help;
 pub(crate) mod lenient;
 pub(crate) mod nameserver;
@@ -74,6 +75,10 @@ pub enum RdapResponseError {
     #[error(transparent)]
     AddrParse(#[from] std::net::AddrParseError),
 
+    /// Text given as an IP address is not one.
+    #[error("Malformed IP address: {0}")]
+    MalformedIpAddress(String),
+
     /// An error caused with parsing a CIDR address.
     #[error(transparent)]
     CidrParse(#[from] cidr::errors::NetworkParseError),
@@ -130,6 +135,8 @@ pub enum RdapResponse {
     DomainSearchResults(Box<DomainSearchResults>),
     EntitySearchResults(Box<EntitySearchResults>),
     NameserverSearchResults(Box<NameserverSearchResults>),
+    IpSearchResults(Box<IpSearchResults>),
+    AutnumSearchResults(Box<AutnumSearchResults>),
 
     // Error
     ErrorResponse(Box<Rfc9083Error>),
@@ -202,6 +209,26 @@ impl TryFrom<Value> for RdapResponse {
                 ));
             }
         }
+        // else if it is an IP network search result
+        if let Some(result) = response.get("ipSearchResults") {
+            if result.is_array() {
+                return Ok(serde_json::from_value::<IpSearchResults>(value)?.to_response());
+            } else {
+                return Err(RdapResponseError::WrongJsonType(
+                    "'ipSearchResults' is not an array".to_string(),
+                ));
+            }
+        }
+        // else if it is an autnum search result
+        if let Some(result) = response.get("autnumSearchResults") {
+            if result.is_array() {
+                return Ok(serde_json::from_value::<AutnumSearchResults>(value)?.to_response());
+            } else {
+                return Err(RdapResponseError::WrongJsonType(
+                    "'autnumSearchResults' is not an array".to_string(),
+                ));
+            }
+        }
 
         // else if it has an errorCode
         if let Some(result) = response.get("errorCode") {
@@ -239,6 +266,8 @@ impl RdapResponse {
             Self::DomainSearchResults(_) => TypeId::of::<DomainSearchResults>(),
             Self::EntitySearchResults(_) => TypeId::of::<EntitySearchResults>(),
             Self::NameserverSearchResults(_) => TypeId::of::<NameserverSearchResults>(),
+            Self::IpSearchResults(_) => TypeId::of::<IpSearchResults>(),
+            Self::AutnumSearchResults(_) => TypeId::of::<AutnumSearchResults>(),
             Self::ErrorResponse(_) => TypeId::of::<crate::response::Rfc9083Error>(),
             Self::Help(_) => TypeId::of::<Help>(),
         }
@@ -254,6 +283,25 @@ impl RdapResponse {
             Self::DomainSearchResults(_)
             | Self::EntitySearchResults(_)
             | Self::NameserverSearchResults(_)
+            | Self::IpSearchResults(_)
+            | Self::AutnumSearchResults(_)
+            | Self::ErrorResponse(_)
+            | Self::Help(_) => None,
+        }
+    }
+
+    pub fn get_paging_metadata(&self) -> Option<&PagingMetadata> {
+        match self {
+            Self::DomainSearchResults(s) => s.paging_metadata.as_ref(),
+            Self::EntitySearchResults(s) => s.paging_metadata.as_ref(),
+            Self::NameserverSearchResults(s) => s.paging_metadata.as_ref(),
+            Self::IpSearchResults(s) => s.paging_metadata.as_ref(),
+            Self::AutnumSearchResults(s) => s.paging_metadata.as_ref(),
+            Self::Entity(_)
+            | Self::Domain(_)
+            | Self::Nameserver(_)
+            | Self::Autnum(_)
+            | Self::Network(_)
             | Self::ErrorResponse(_)
             | Self::Help(_) => None,
         }
@@ -269,6 +317,8 @@ impl RdapResponse {
             Self::DomainSearchResults(s) => s.common.rdap_conformance.as_ref(),
             Self::EntitySearchResults(s) => s.common.rdap_conformance.as_ref(),
             Self::NameserverSearchResults(s) => s.common.rdap_conformance.as_ref(),
+            Self::IpSearchResults(s) => s.common.rdap_conformance.as_ref(),
+            Self::AutnumSearchResults(s) => s.common.rdap_conformance.as_ref(),
             Self::ErrorResponse(e) => e.common.rdap_conformance.as_ref(),
             Self::Help(h) => h.common.rdap_conformance.as_ref(),
         }
@@ -358,6 +408,29 @@ pub fn get_related_links(rdap_response: &RdapResponse) -> Vec<&str> {
     urls
 }
 
+/// Gets the `href` of the first link with a `rel` of "up".
+///
+/// Networks use this link to refer to the next less specific network.
+pub fn get_up_link(rdap_response: &RdapResponse) -> Option<&str> {
+    get_relation_link(rdap_response, RirSearchRelation::Up)
+}
+
+/// Gets the `href` of the first link with a `rel` of an RIR search relation (RFC 9910).
+pub fn get_relation_link(
+    rdap_response: &RdapResponse,
+    relation: RirSearchRelation,
+) -> Option<&str> {
+    let relation = relation.to_string();
+    rdap_response
+        .get_links()?
+        .iter()
+        .find(|l| {
+            l.rel()
+                .is_some_and(|rel| rel.eq_ignore_ascii_case(&relation))
+        })
+        .and_then(|l| l.href())
+}
+
 /// Returns true if the URL contains an RDAP path as defined by RFC 9082.
 pub fn has_rdap_path(url: &str) -> bool {
     if url.contains("/domain/")
@@ -394,7 +467,10 @@ mod tests {
 
     use crate::media_types::RDAP_MEDIA_TYPE;
 
-    use super::{get_related_links, Domain, Link, RdapResponse, ToResponse};
+    use super::{
+        get_related_links, get_relation_link, get_up_link, Domain, ExtensionId, Link, Network,
+        RdapResponse, RirSearchRelation, ToResponse,
+    };
 
     #[test]
     fn test_redaction_response_gets_object() {
@@ -515,6 +591,27 @@ mod tests {
         assert!(matches!(actual, RdapResponse::DomainSearchResults(_)));
     }
 
+    #[test]
+    fn test_response_domain_search_has_paging_metadata() {
+        // GIVEN
+        let expected: Value =
+            serde_json::from_str(include_str!("test_files/domains_with_paging.json")).unwrap();
+
+        // WHEN
+        let actual = RdapResponse::try_from(expected).unwrap();
+
+        // THEN
+        assert!(actual.has_extension_id(ExtensionId::Paging));
+        let paging = actual.get_paging_metadata().expect("no paging metadata");
+        assert_eq!(paging.total_count(), Some(43));
+        assert_eq!(paging.page_size(), Some(2));
+        assert_eq!(paging.page_number(), Some(1));
+        assert_eq!(
+            paging.next_link(),
+            Some("https://example.com/rdap/domains?name=*nr.com&cursor=wJlCDLIl6KTWypN7T6vc6nWEmEYe99Hjf1XY1xmqV-M=")
+        );
+    }
+
     #[test]
     fn test_response_is_entity_search_results() {
         // GIVEN
@@ -528,6 +625,53 @@ mod tests {
         assert!(matches!(actual, RdapResponse::EntitySearchResults(_)));
     }
 
+    #[test]
+    fn test_response_is_ip_search_results() {
+        // GIVEN
+        let expected: Value = serde_json::json!({
+            "rdapConformance": ["rdap_level_0", "rirSearch1", "ips"],
+            "ipSearchResults": [
+                {
+                    "objectClassName": "ip network",
+                    "startAddress": "10.0.0.0",
+                    "endAddress": "10.0.0.255",
+                    "ipVersion": "v4"
+                }
+            ]
+        });
+
+        // WHEN
+        let actual = RdapResponse::try_from(expected).unwrap();
+
+        // THEN
+        assert!(actual.has_extension_id(ExtensionId::RirSearch1));
+        let RdapResponse::IpSearchResults(results) = actual else {
+            panic!("not IP search results");
+        };
+        assert_eq!(results.results().len(), 1);
+    }
+
+    #[test]
+    fn test_response_is_autnum_search_results() {
+        // GIVEN
+        let expected: Value = serde_json::json!({
+            "rdapConformance": ["rdap_level_0", "rirSearch1", "autnums"],
+            "autnumSearchResults": [
+                {
+                    "objectClassName": "autnum",
+                    "startAutnum": 64496,
+                    "endAutnum": 64496
+                }
+            ]
+        });
+
+        // WHEN
+        let actual = RdapResponse::try_from(expected).unwrap();
+
+        // THEN
+        assert!(matches!(actual, RdapResponse::AutnumSearchResults(_)));
+    }
+
     #[test]
     fn test_response_is_help() {
         // GIVEN
@@ -655,4 +799,77 @@ mod tests {
         assert!(!links.is_empty());
         assert_eq!(links.first().expect("empty links"), &link.href().unwrap());
     }
+
+    #[test]
+    fn test_get_up_link() {
+        // GIVEN
+        let rdap = Network::builder()
+            .cidr("10.0.0.0/24")
+            .link(
+                Link::builder()
+                    .rel("self")
+                    .href("http://example.com/ip/10.0.0.0/24")
+                    .value("http://example.com/ip/10.0.0.0/24")
+                    .build(),
+            )
+            .link(
+                Link::builder()
+                    .rel("up")
+                    .href("http://example.com/ip/10.0.0.0/8")
+                    .value("http://example.com/ip/10.0.0.0/24")
+                    .build(),
+            )
+            .build()
+            .expect("network")
+            .to_response();
+
+        // WHEN
+        let link = get_up_link(&rdap);
+
+        // THEN
+        assert_eq!(link, Some("http://example.com/ip/10.0.0.0/8"));
+    }
+
+    #[test]
+    fn test_get_up_link_when_none() {
+        // GIVEN
+        let rdap = Domain::builder()
+            .ldh_name("example.com")
+            .build()
+            .to_response();
+
+        // WHEN
+        let link = get_up_link(&rdap);
+
+        // THEN
+        assert!(link.is_none());
+    }
+
+    #[test]
+    fn test_get_relation_link() {
+        // GIVEN
+        let rdap = Network::builder()
+            .cidr("10.0.0.0/8")
+            .link(
+                Link::builder()
+                    .rel("down")
+                    .href("http://example.com/ips/rirSearch1/down/10.0.0.0/8")
+                    .value("http://example.com/ip/10.0.0.0/8")
+                    .build(),
+            )
+            .build()
+            .expect("network")
+            .to_response();
+
+        // WHEN
+        let down = get_relation_link(&rdap, RirSearchRelation::Down);
+        let top = get_relation_link(&rdap, RirSearchRelation::Top);
+
+        // THEN
+        assert_eq!(
+            down,
+            Some("http://example.com/ips/rirSearch1/down/10.0.0.0/8")
+        );
+        assert!(top.is_none());
+    }
 }
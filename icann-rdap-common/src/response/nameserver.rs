@@ -20,25 +20,70 @@ pub struct IpAddresses {
 
     #[serde(skip_serializing_if = "Option::is_none")]
     pub v4: Option<VectorStringish>,
+
+    #[serde(skip)]
+    known_valid: KnownValid,
 }
 
+/// Whether IP addresses were validated when built, so that checks of them may be skipped.
+///
+/// This is ignored when comparing IP addresses, as it is not part of the data.
+#[derive(Clone, Copy, Debug, Default)]
+struct KnownValid(bool);
+
+impl PartialEq for KnownValid {
+    fn eq(&self, _other: &Self) -> bool {
+        true
+    }
+}
+
+impl Eq for KnownValid {}
+
 #[buildstructor::buildstructor]
 impl IpAddresses {
-    /// Builds nameserver IP address.
+    /// Builds nameserver IP addresses.
+    ///
+    /// The addresses may be given as text, as [IpAddr], or both. Each address is put
+    /// in the list of its IP version, in the form of RFC 5952, and addresses given
+    /// more than once are kept once. Text that is not an IP address is an error.
+    ///
+    /// ```rust
+    /// use icann_rdap_common::prelude::*;
+    /// use std::net::{IpAddr, Ipv6Addr};
+    ///
+    /// let ip_addresses = IpAddresses::builder()
+    ///   .address("192.0.2.1")
+    ///   .address("2001:DB8::1")
+    ///   .ip(IpAddr::V6(Ipv6Addr::new(0x2001, 0xdb8, 0, 0, 0, 0, 0, 1)))
+    ///   .build().unwrap();
+    /// assert_eq!(ip_addresses.v4s(), ["192.0.2.1"]);
+    /// assert_eq!(ip_addresses.v6s(), ["2001:db8::1"]);
+    /// ```
     #[builder(visibility = "pub")]
-    fn new(addresses: Vec<String>) -> Result<Self, RdapResponseError> {
+    fn new(addresses: Vec<String>, ips: Vec<IpAddr>) -> Result<Self, RdapResponseError> {
+        let mut parsed = Vec::with_capacity(addresses.len() + ips.len());
+        for addr in addresses {
+            match IpAddr::from_str(&addr) {
+                Ok(ip) => parsed.push(ip),
+                Err(_) => return Err(RdapResponseError::MalformedIpAddress(addr)),
+            }
+        }
+        parsed.extend(ips);
         let mut v4: Vec<String> = Vec::new();
         let mut v6: Vec<String> = Vec::new();
-        for addr in addresses {
-            let ip = IpAddr::from_str(&addr)?;
+        for (i, ip) in parsed.iter().enumerate() {
+            if parsed[..i].contains(ip) {
+                continue;
+            }
             match ip {
-                IpAddr::V4(_) => v4.push(addr),
-                IpAddr::V6(_) => v6.push(addr),
+                IpAddr::V4(_) => v4.push(ip.to_string()),
+                IpAddr::V6(_) => v6.push(ip.to_string()),
             }
         }
         Ok(Self {
             v4: to_opt_vectorstringish(v4),
             v6: to_opt_vectorstringish(v6),
+            known_valid: KnownValid(true),
         })
     }
 
@@ -48,9 +93,18 @@ impl IpAddresses {
         Self {
             v4: v4.map(VectorStringish::from),
             v6: v6.map(VectorStringish::from),
+            known_valid: KnownValid(false),
         }
     }
 
+    /// Returns true if the addresses were validated by the builder, and so are known
+    /// to be well-formed and in the lists of their IP versions.
+    ///
+    /// Addresses changed after being built are not validated again.
+    pub fn is_known_valid(&self) -> bool {
+        self.known_valid.0
+    }
+
     /// Get the IPv6 addresses.
     pub fn v6s(&self) -> &[String] {
         self.v6
@@ -170,10 +224,15 @@ impl Nameserver {
     ///   .remark(Remark::builder().title("hidden nameserver").build())
     ///   .build().unwrap();
     /// ```
+    ///
+    /// IP addresses may be given as text with `address` or as [IpAddr] with `ip`.
+    /// They are validated and deduplicated as by [IpAddresses::builder()], and text
+    /// that is not an IP address is an error.
     #[builder(visibility = "pub")]
     fn new<T: Into<String>>(
         ldh_name: T,
         addresses: Vec<String>,
+        ips: Vec<IpAddr>,
         handle: Option<String>,
         remarks: Vec<Remark>,
         links: Vec<Link>,
@@ -183,8 +242,13 @@ impl Nameserver {
         entities: Vec<Entity>,
         redacted: Option<Vec<crate::response::redacted::Redacted>>,
     ) -> Result<Self, RdapResponseError> {
-        let ip_addresses = if !addresses.is_empty() {
-            Some(IpAddresses::builder().addresses(addresses).build()?)
+        let ip_addresses = if !addresses.is_empty() || !ips.is_empty() {
+            Some(
+                IpAddresses::builder()
+                    .addresses(addresses)
+                    .ips(ips)
+                    .build()?,
+            )
         } else {
             None
         };
@@ -226,6 +290,7 @@ impl Nameserver {
     fn new_response_obj<T: Into<String>>(
         ldh_name: T,
         addresses: Vec<String>,
+        ips: Vec<IpAddr>,
         handle: Option<String>,
         remarks: Vec<Remark>,
         links: Vec<Link>,
@@ -244,6 +309,7 @@ impl Nameserver {
         let mut nameserver = Nameserver::builder()
             .ldh_name(ldh_name)
             .addresses(addresses)
+            .ips(ips)
             .and_handle(handle)
             .remarks(remarks)
             .links(links)
@@ -329,7 +395,11 @@ impl ObjectCommonFields for Nameserver {
 #[cfg(test)]
 #[allow(non_snake_case)]
 mod tests {
-    use super::Nameserver;
+    use std::net::{IpAddr, Ipv4Addr};
+
+    use crate::response::RdapResponseError;
+
+    use super::{IpAddresses, Nameserver};
 
     #[test]
     fn GIVEN_nameserver_WHEN_deserialize_THEN_success() {
@@ -396,4 +466,59 @@ mod tests {
         assert!(actual.object_common.links.is_some());
         assert!(actual.object_common.events.is_some());
     }
+
+    #[test]
+    fn GIVEN_text_and_typed_addresses_WHEN_build_THEN_placed_by_version_and_deduped() {
+        // GIVEN
+        let ip = IpAddr::V4(Ipv4Addr::new(192, 0, 2, 1));
+
+        // WHEN
+        let actual = Nameserver::builder()
+            .ldh_name("ns1.example.com")
+            .address("192.0.2.1")
+            .address("2001:DB8::0001")
+            .address("2001:db8::1")
+            .ip(ip)
+            .ip(IpAddr::V4(Ipv4Addr::new(192, 0, 2, 2)))
+            .build()
+            .expect("building nameserver");
+
+        // THEN
+        let ip_addresses = actual.ip_addresses().expect("ip addresses");
+        assert_eq!(ip_addresses.v4s(), ["192.0.2.1", "192.0.2.2"]);
+        assert_eq!(ip_addresses.v6s(), ["2001:db8::1"]);
+        assert!(ip_addresses.is_known_valid());
+    }
+
+    #[test]
+    fn GIVEN_malformed_address_WHEN_build_THEN_error() {
+        // GIVEN
+        let address = "192.0.2.300";
+
+        // WHEN
+        let actual = IpAddresses::builder().address(address).build();
+
+        // THEN
+        assert!(matches!(
+            actual,
+            Err(RdapResponseError::MalformedIpAddress(addr)) if addr == address
+        ));
+    }
+
+    #[test]
+    fn GIVEN_deserialized_addresses_WHEN_compare_to_built_THEN_equal() {
+        // GIVEN
+        let built = IpAddresses::builder()
+            .address("192.0.2.1")
+            .build()
+            .expect("building ip addresses");
+
+        // WHEN
+        let actual = serde_json::from_str::<IpAddresses>(r#"{"v4":["192.0.2.1"]}"#)
+            .expect("deserializing ip addresses");
+
+        // THEN
+        assert!(!actual.is_known_valid());
+        assert_eq!(actual, built);
+    }
 }
@@ -36,10 +36,9 @@ use {
     buildstructor::Builder,
     serde::{Deserialize, Serialize},
     std::{any::TypeId, fmt},
+    strum_macros::{Display, EnumString},
 };
 
-use crate::check::Checks;
-
 /// Redacted registered name.
 #[derive(Builder, Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
 pub struct Name {
@@ -98,6 +97,10 @@ pub enum Method {
     EmptyValue,
     PartialValue,
     ReplacementValue,
+
+    /// A method not defined by RFC 9537.
+    #[serde(other)]
+    Unrecognized,
 }
 
 /// RFC 9537 redaction structure.
@@ -153,6 +156,7 @@ impl fmt::Display for Method {
             Self::EmptyValue => write!(f, "EmptyValue"),
             Self::PartialValue => write!(f, "PartialValue"),
             Self::ReplacementValue => write!(f, "ReplacementValue"),
+            Self::Unrecognized => write!(f, "Unrecognized"),
         }
     }
 }
@@ -193,21 +197,51 @@ impl Redacted {
         self.method.as_ref()
     }
 
-    /// Get the checks from Redactions.
-    pub fn get_checks(&self, _check_params: crate::check::CheckParams<'_>) -> crate::check::Checks {
-        Checks {
-            rdap_struct: crate::check::RdapStructure::Redacted,
-            items: vec![],
-            sub_checks: vec![],
-        }
-    }
-
     /// Get the type.
     pub fn get_type(&self) -> std::any::TypeId {
         TypeId::of::<Self>()
     }
 }
 
+/// IANA registered redaction names.
+#[derive(PartialEq, Eq, Debug, EnumString, Display)]
+pub enum RedactedName {
+    #[strum(serialize = "Registry Domain ID")]
+    RegistryDomainId,
+    #[strum(serialize = "Registry Registrant ID")]
+    RegistryRegistrantId,
+    #[strum(serialize = "Registrant Name")]
+    RegistrantName,
+    #[strum(serialize = "Registrant Organization")]
+    RegistrantOrganization,
+    #[strum(serialize = "Registrant Street")]
+    RegistrantStreet,
+    #[strum(serialize = "Registrant City")]
+    RegistrantCity,
+    #[strum(serialize = "Registrant Postal Code")]
+    RegistrantPostalCode,
+    #[strum(serialize = "Registrant Phone")]
+    RegistrantPhone,
+    #[strum(serialize = "Registrant Phone Ext")]
+    RegistrantPhoneExt,
+    #[strum(serialize = "Registrant Fax")]
+    RegistrantFax,
+    #[strum(serialize = "Registrant Fax Ext")]
+    RegistrantFaxExt,
+    #[strum(serialize = "Registrant Email")]
+    RegistrantEmail,
+    #[strum(serialize = "Registry Tech ID")]
+    RegistryTechId,
+    #[strum(serialize = "Tech Name")]
+    TechName,
+    #[strum(serialize = "Tech Phone")]
+    TechPhone,
+    #[strum(serialize = "Tech Phone Ext")]
+    TechPhoneExt,
+    #[strum(serialize = "Tech Email")]
+    TechEmail,
+}
+
 #[cfg(test)]
 #[allow(non_snake_case)]
 mod tests {
@@ -328,4 +362,16 @@ mod tests {
         );
         assert_eq!(actual.method, Some(Method::Removal));
     }
+
+    #[test]
+    fn GIVEN_unknown_method_WHEN_deserialize_THEN_unrecognized() {
+        // GIVEN
+        let json = r#"{"name": {"type": "Tech Email"}, "method": "scramble"}"#;
+
+        // WHEN
+        let actual = serde_json::from_str::<Redacted>(json);
+
+        // THEN
+        assert_eq!(actual.unwrap().method, Some(Method::Unrecognized));
+    }
 }
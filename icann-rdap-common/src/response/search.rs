@@ -2,9 +2,96 @@
 use {
     crate::prelude::{Common, Extension},
     serde::{Deserialize, Serialize},
+    strum_macros::{Display, EnumString},
 };
 
-use super::{domain::Domain, entity::Entity, nameserver::Nameserver, CommonFields, ToResponse};
+use super::{
+    autnum::Autnum,
+    domain::Domain,
+    entity::Entity,
+    lenient::Numberish,
+    nameserver::Nameserver,
+    network::Network,
+    types::{Link, Links},
+    CommonFields, ToResponse,
+};
+
+/// Removes the RDAP conformance of search results, as it can only appear at
+/// the top of a response.
+fn without_conformance<T>(mut results: Vec<T>, common: fn(&mut T) -> &mut Common) -> Vec<T> {
+    for result in &mut results {
+        common(result).rdap_conformance = None;
+    }
+    results
+}
+
+/// Represents the paging metadata of search results (RFC 8977).
+///
+/// The link with a `rel` of "next" refers to the next page of results.
+#[derive(Serialize, Deserialize, Clone, PartialEq, Debug, Eq)]
+pub struct PagingMetadata {
+    #[serde(rename = "totalCount")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub total_count: Option<Numberish<u64>>,
+
+    #[serde(rename = "pageSize")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub page_size: Option<Numberish<u64>>,
+
+    #[serde(rename = "pageNumber")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub page_number: Option<Numberish<u64>>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub links: Option<Links>,
+}
+
+#[buildstructor::buildstructor]
+impl PagingMetadata {
+    /// Builds paging metadata.
+    #[builder(visibility = "pub")]
+    fn new(
+        total_count: Option<u64>,
+        page_size: Option<u64>,
+        page_number: Option<u64>,
+        links: Vec<Link>,
+    ) -> Self {
+        Self {
+            total_count: total_count.map(Numberish::from),
+            page_size: page_size.map(Numberish::from),
+            page_number: page_number.map(Numberish::from),
+            links: (!links.is_empty()).then_some(links),
+        }
+    }
+
+    /// Get the total number of results in all pages.
+    pub fn total_count(&self) -> Option<u64> {
+        self.total_count.as_ref().and_then(|n| n.as_u64())
+    }
+
+    /// Get the number of results in a page.
+    pub fn page_size(&self) -> Option<u64> {
+        self.page_size.as_ref().and_then(|n| n.as_u64())
+    }
+
+    /// Get the number of this page, starting with 1.
+    pub fn page_number(&self) -> Option<u64> {
+        self.page_number.as_ref().and_then(|n| n.as_u64())
+    }
+
+    /// Get the links.
+    pub fn links(&self) -> &[Link] {
+        self.links.as_deref().unwrap_or_default()
+    }
+
+    /// Get the `href` of the link to the next page.
+    pub fn next_link(&self) -> Option<&str> {
+        self.links()
+            .iter()
+            .find(|l| l.rel().is_some_and(|rel| rel.eq_ignore_ascii_case("next")))
+            .and_then(|l| l.href())
+    }
+}
 
 /// Represents RDAP domain search results.
 #[derive(Serialize, Deserialize, Clone, PartialEq, Debug, Eq)]
@@ -14,16 +101,24 @@ pub struct DomainSearchResults {
 
     #[serde(rename = "domainSearchResults")]
     pub results: Vec<Domain>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub paging_metadata: Option<PagingMetadata>,
 }
 
 #[buildstructor::buildstructor]
 impl DomainSearchResults {
     /// Builds a domain search result.
     #[builder(entry = "response_obj", visibility = "pub")]
-    fn new_response_obj(results: Vec<Domain>, extensions: Vec<Extension>) -> Self {
+    fn new_response_obj(
+        results: Vec<Domain>,
+        extensions: Vec<Extension>,
+        paging_metadata: Option<PagingMetadata>,
+    ) -> Self {
         Self {
             common: Common::level0().extensions(extensions).build(),
-            results,
+            results: without_conformance(results, |r| &mut r.common),
+            paging_metadata,
         }
     }
 
@@ -31,6 +126,11 @@ impl DomainSearchResults {
     pub fn results(&self) -> &[Domain] {
         self.results.as_ref()
     }
+
+    /// Get the paging metadata.
+    pub fn paging_metadata(&self) -> Option<&PagingMetadata> {
+        self.paging_metadata.as_ref()
+    }
 }
 
 impl CommonFields for DomainSearchResults {
@@ -53,16 +153,24 @@ pub struct NameserverSearchResults {
 
     #[serde(rename = "nameserverSearchResults")]
     pub results: Vec<Nameserver>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub paging_metadata: Option<PagingMetadata>,
 }
 
 #[buildstructor::buildstructor]
 impl NameserverSearchResults {
     /// Builds a nameserver search result.
     #[builder(entry = "response_obj", visibility = "pub")]
-    fn new_response_obj(results: Vec<Nameserver>, extensions: Vec<Extension>) -> Self {
+    fn new_response_obj(
+        results: Vec<Nameserver>,
+        extensions: Vec<Extension>,
+        paging_metadata: Option<PagingMetadata>,
+    ) -> Self {
         Self {
             common: Common::level0().extensions(extensions).build(),
-            results,
+            results: without_conformance(results, |r| &mut r.common),
+            paging_metadata,
         }
     }
 
@@ -70,6 +178,11 @@ impl NameserverSearchResults {
     pub fn results(&self) -> &[Nameserver] {
         self.results.as_ref()
     }
+
+    /// Get the paging metadata.
+    pub fn paging_metadata(&self) -> Option<&PagingMetadata> {
+        self.paging_metadata.as_ref()
+    }
 }
 
 impl CommonFields for NameserverSearchResults {
@@ -92,16 +205,24 @@ pub struct EntitySearchResults {
 
     #[serde(rename = "entitySearchResults")]
     pub results: Vec<Entity>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub paging_metadata: Option<PagingMetadata>,
 }
 
 #[buildstructor::buildstructor]
 impl EntitySearchResults {
     /// Builds an entity search result.
     #[builder(entry = "response_obj", visibility = "pub")]
-    fn new_response_obj(results: Vec<Entity>, extensions: Vec<Extension>) -> Self {
+    fn new_response_obj(
+        results: Vec<Entity>,
+        extensions: Vec<Extension>,
+        paging_metadata: Option<PagingMetadata>,
+    ) -> Self {
         Self {
             common: Common::level0().extensions(extensions).build(),
-            results,
+            results: without_conformance(results, |r| &mut r.common),
+            paging_metadata,
         }
     }
 
@@ -109,6 +230,11 @@ impl EntitySearchResults {
     pub fn results(&self) -> &[Entity] {
         self.results.as_ref()
     }
+
+    /// Get the paging metadata.
+    pub fn paging_metadata(&self) -> Option<&PagingMetadata> {
+        self.paging_metadata.as_ref()
+    }
 }
 
 impl CommonFields for EntitySearchResults {
@@ -122,3 +248,177 @@ impl ToResponse for EntitySearchResults {
         super::RdapResponse::EntitySearchResults(Box::new(self))
     }
 }
+
+/// Represents RDAP IP network search results (RFC 9910).
+#[derive(Serialize, Deserialize, Clone, PartialEq, Debug, Eq)]
+pub struct IpSearchResults {
+    #[serde(flatten)]
+    pub common: Common,
+
+    #[serde(rename = "ipSearchResults")]
+    pub results: Vec<Network>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub paging_metadata: Option<PagingMetadata>,
+}
+
+#[buildstructor::buildstructor]
+impl IpSearchResults {
+    /// Builds an IP network search result.
+    #[builder(entry = "response_obj", visibility = "pub")]
+    fn new_response_obj(
+        results: Vec<Network>,
+        extensions: Vec<Extension>,
+        paging_metadata: Option<PagingMetadata>,
+    ) -> Self {
+        Self {
+            common: Common::level0().extensions(extensions).build(),
+            results: without_conformance(results, |r| &mut r.common),
+            paging_metadata,
+        }
+    }
+
+    /// Get the networks in the search.
+    pub fn results(&self) -> &[Network] {
+        self.results.as_ref()
+    }
+
+    /// Get the paging metadata.
+    pub fn paging_metadata(&self) -> Option<&PagingMetadata> {
+        self.paging_metadata.as_ref()
+    }
+}
+
+impl CommonFields for IpSearchResults {
+    fn common(&self) -> &Common {
+        &self.common
+    }
+}
+
+impl ToResponse for IpSearchResults {
+    fn to_response(self) -> super::RdapResponse {
+        super::RdapResponse::IpSearchResults(Box::new(self))
+    }
+}
+
+/// Represents RDAP autnum search results (RFC 9910).
+#[derive(Serialize, Deserialize, Clone, PartialEq, Debug, Eq)]
+pub struct AutnumSearchResults {
+    #[serde(flatten)]
+    pub common: Common,
+
+    #[serde(rename = "autnumSearchResults")]
+    pub results: Vec<Autnum>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub paging_metadata: Option<PagingMetadata>,
+}
+
+#[buildstructor::buildstructor]
+impl AutnumSearchResults {
+    /// Builds an autnum search result.
+    #[builder(entry = "response_obj", visibility = "pub")]
+    fn new_response_obj(
+        results: Vec<Autnum>,
+        extensions: Vec<Extension>,
+        paging_metadata: Option<PagingMetadata>,
+    ) -> Self {
+        Self {
+            common: Common::level0().extensions(extensions).build(),
+            results: without_conformance(results, |r| &mut r.common),
+            paging_metadata,
+        }
+    }
+
+    /// Get the autnums in the search.
+    pub fn results(&self) -> &[Autnum] {
+        self.results.as_ref()
+    }
+
+    /// Get the paging metadata.
+    pub fn paging_metadata(&self) -> Option<&PagingMetadata> {
+        self.paging_metadata.as_ref()
+    }
+}
+
+impl CommonFields for AutnumSearchResults {
+    fn common(&self) -> &Common {
+        &self.common
+    }
+}
+
+impl ToResponse for AutnumSearchResults {
+    fn to_response(self) -> super::RdapResponse {
+        super::RdapResponse::AutnumSearchResults(Box::new(self))
+    }
+}
+
+/// Entity properties that may be used in reverse searches (RFC 9536).
+///
+/// These are the properties registered by IANA for reverse searches
+/// with a related resource type of "entity".
+#[derive(Debug, Clone, Copy, PartialEq, Eq, EnumString, Display)]
+#[strum(serialize_all = "lowercase")]
+pub enum ReverseSearchProperty {
+    /// The full name of the entity (vCard "fn").
+    Fn,
+
+    /// The handle of the entity.
+    Handle,
+
+    /// The email address of the entity.
+    Email,
+
+    /// The role of the entity.
+    Role,
+}
+
+/// The relations of the RIR search extension (RFC 9910) between IP networks.
+///
+/// These are both the `rel` of links between networks and the
+/// relation of a search, such as `/ips/rirSearch1/down/192.0.2.0/24`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, EnumString, Display)]
+#[strum(serialize_all = "lowercase")]
+pub enum RirSearchRelation {
+    /// The next less specific network.
+    Up,
+
+    /// The next more specific networks.
+    Down,
+
+    /// The least specific network.
+    Top,
+
+    /// The most specific networks.
+    Bottom,
+}
+
+impl RirSearchRelation {
+    /// Returns true if the relation is to a single network, otherwise
+    /// the relation is to the networks of search results.
+    pub fn is_single(&self) -> bool {
+        matches!(self, Self::Up | Self::Top)
+    }
+}
+
+#[cfg(test)]
+#[allow(non_snake_case)]
+mod tests {
+    use crate::response::{Domain, DomainSearchResults};
+
+    #[test]
+    fn GIVEN_domain_with_conformance_WHEN_build_search_results_THEN_conformance_only_at_top() {
+        // GIVEN
+        let domain = Domain::response_obj().ldh_name("foo.example").build();
+        assert!(domain.common.rdap_conformance.is_some());
+
+        // WHEN
+        let actual = DomainSearchResults::response_obj()
+            .results(vec![domain])
+            .build();
+
+        // THEN
+        assert!(actual.common.rdap_conformance.is_some());
+        assert!(actual.results[0].common.rdap_conformance.is_none());
+    }
+}
@@ -69,12 +69,16 @@ pub enum ExtensionId {
     ArinOriginAs0,
     #[strum(serialize = "artRecord")]
     ArtRecord,
+    #[strum(serialize = "autnums")]
+    Autnums,
     #[strum(serialize = "cidr0")]
     Cidr0,
     #[strum(serialize = "farv1")]
     Farv1,
     #[strum(serialize = "fred")]
     Fred,
+    #[strum(serialize = "ips")]
+    Ips,
     #[strum(serialize = "icann_rdap_response_profile_0")]
     IcannRdapResponseProfile0,
     #[strum(serialize = "icann_rdap_response_profile_1")]
@@ -83,6 +87,8 @@ pub enum ExtensionId {
     IcannRdapTechnicalImplementationGuide0,
     #[strum(serialize = "icann_rdap_technical_implementation_guide_1")]
     IcannRdapTechnicalImplementationGuide1,
+    #[strum(serialize = "jscontact")]
+    JsContact,
     #[strum(serialize = "nro_rdap_profile_0")]
     NroRdapProfile0,
     #[strum(serialize = "nro_rdap_profile_asn_flat_0")]
@@ -103,6 +109,8 @@ pub enum ExtensionId {
     RegType,
     #[strum(serialize = "reverse_search")]
     ReverseSearch,
+    #[strum(serialize = "rirSearch1")]
+    RirSearch1,
     #[strum(serialize = "sorting")]
     Sorting,
     #[strum(serialize = "subsetting")]
@@ -767,6 +775,159 @@ impl Event {
     }
 }
 
+/// IANA registered event actions.
+#[derive(PartialEq, Eq, Debug, EnumString, Display)]
+#[strum(serialize_all = "lowercase")]
+pub enum EventAction {
+    Registration,
+    Reregistration,
+    #[strum(serialize = "last changed")]
+    LastChanged,
+    Expiration,
+    Deletion,
+    Reinstantiation,
+    Transfer,
+    Locked,
+    Unlocked,
+    #[strum(serialize = "last update of RDAP database")]
+    LastUpdateOfRdapDatabase,
+    #[strum(serialize = "registrar expiration")]
+    RegistrarExpiration,
+    #[strum(serialize = "enum validation expiration")]
+    EnumValidationExpiration,
+}
+
+/// IANA registered notice and remark types.
+#[derive(PartialEq, Eq, Debug, EnumString, Display)]
+pub enum NoticeOrRemarkType {
+    #[strum(serialize = "result set truncated due to authorization")]
+    ResultSetTruncatedDueToAuthorization,
+    #[strum(serialize = "result set truncated due to excessive load")]
+    ResultSetTruncatedDueToExcessiveLoad,
+    #[strum(serialize = "result set truncated due to unexplainable reasons")]
+    ResultSetTruncatedDueToUnexplainableReasons,
+    #[strum(serialize = "object truncated due to authorization")]
+    ObjectTruncatedDueToAuthorization,
+    #[strum(serialize = "object truncated due to excessive load")]
+    ObjectTruncatedDueToExcessiveLoad,
+    #[strum(serialize = "object truncated due to unexplainable reasons")]
+    ObjectTruncatedDueToUnexplainableReasons,
+    #[strum(serialize = "object redacted due to authorization")]
+    ObjectRedactedDueToAuthorization,
+}
+
+/// IANA registered status values.
+#[derive(PartialEq, Eq, Debug, EnumString, Display)]
+#[strum(serialize_all = "lowercase")]
+pub enum StatusValue {
+    Validated,
+    #[strum(serialize = "renew prohibited")]
+    RenewProhibited,
+    #[strum(serialize = "update prohibited")]
+    UpdateProhibited,
+    #[strum(serialize = "transfer prohibited")]
+    TransferProhibited,
+    #[strum(serialize = "delete prohibited")]
+    DeleteProhibited,
+    Proxy,
+    Private,
+    Removed,
+    Obscured,
+    Associated,
+    Active,
+    Inactive,
+    Locked,
+    #[strum(serialize = "pending create")]
+    PendingCreate,
+    #[strum(serialize = "pending renew")]
+    PendingRenew,
+    #[strum(serialize = "pending transfer")]
+    PendingTransfer,
+    #[strum(serialize = "pending update")]
+    PendingUpdate,
+    #[strum(serialize = "pending delete")]
+    PendingDelete,
+    #[strum(serialize = "add period")]
+    AddPeriod,
+    #[strum(serialize = "auto renew period")]
+    AutoRenewPeriod,
+    #[strum(serialize = "client delete prohibited")]
+    ClientDeleteProhibited,
+    #[strum(serialize = "client hold")]
+    ClientHold,
+    #[strum(serialize = "client renew prohibited")]
+    ClientRenewProhibited,
+    #[strum(serialize = "client transfer prohibited")]
+    ClientTransferProhibited,
+    #[strum(serialize = "client update prohibited")]
+    ClientUpdateProhibited,
+    #[strum(serialize = "pending restore")]
+    PendingRestore,
+    #[strum(serialize = "redemption period")]
+    RedemptionPeriod,
+    #[strum(serialize = "renew period")]
+    RenewPeriod,
+    #[strum(serialize = "server delete prohibited")]
+    ServerDeleteProhibited,
+    #[strum(serialize = "server renew prohibited")]
+    ServerRenewProhibited,
+    #[strum(serialize = "server transfer prohibited")]
+    ServerTransferProhibited,
+    #[strum(serialize = "server update prohibited")]
+    ServerUpdateProhibited,
+    #[strum(serialize = "server hold")]
+    ServerHold,
+    #[strum(serialize = "transfer period")]
+    TransferPeriod,
+    Administrative,
+    Reserved,
+}
+
+impl StatusValue {
+    /// Gets the status value an EPP status maps to, as given in RFC 8056.
+    ///
+    /// ```rust
+    /// use icann_rdap_common::prelude::*;
+    ///
+    /// assert_eq!(
+    ///     StatusValue::from_epp("clientDeleteProhibited"),
+    ///     Some(StatusValue::ClientDeleteProhibited)
+    /// );
+    /// assert_eq!(StatusValue::from_epp("ok"), Some(StatusValue::Active));
+    /// assert_eq!(StatusValue::from_epp("active"), None);
+    /// ```
+    pub fn from_epp(epp_status: &str) -> Option<Self> {
+        let value = match epp_status {
+            "addPeriod" => Self::AddPeriod,
+            "autoRenewPeriod" => Self::AutoRenewPeriod,
+            "clientDeleteProhibited" => Self::ClientDeleteProhibited,
+            "clientHold" => Self::ClientHold,
+            "clientRenewProhibited" => Self::ClientRenewProhibited,
+            "clientTransferProhibited" => Self::ClientTransferProhibited,
+            "clientUpdateProhibited" => Self::ClientUpdateProhibited,
+            "inactive" => Self::Inactive,
+            "linked" => Self::Associated,
+            "ok" => Self::Active,
+            "pendingCreate" => Self::PendingCreate,
+            "pendingDelete" => Self::PendingDelete,
+            "pendingRenew" => Self::PendingRenew,
+            "pendingRestore" => Self::PendingRestore,
+            "pendingTransfer" => Self::PendingTransfer,
+            "pendingUpdate" => Self::PendingUpdate,
+            "redemptionPeriod" => Self::RedemptionPeriod,
+            "renewPeriod" => Self::RenewPeriod,
+            "serverDeleteProhibited" => Self::ServerDeleteProhibited,
+            "serverHold" => Self::ServerHold,
+            "serverRenewProhibited" => Self::ServerRenewProhibited,
+            "serverTransferProhibited" => Self::ServerTransferProhibited,
+            "serverUpdateProhibited" => Self::ServerUpdateProhibited,
+            "transferPeriod" => Self::TransferPeriod,
+            _ => return None,
+        };
+        Some(value)
+    }
+}
+
 /// An RDAP port53 type.
 pub type Port43 = String;
 
@@ -0,0 +1,8 @@
+-- Removes the tables of the RDAP objects, and all the objects with them.
+
+drop table if exists srv_help;
+drop table if exists network;
+drop table if exists autnum;
+drop table if exists nameserver;
+drop table if exists entity;
+drop table if exists domain;
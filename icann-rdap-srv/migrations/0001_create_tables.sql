@@ -0,0 +1,38 @@
+-- RDAP objects are stored as JSON documents, indexed by the keys used to look them up.
+-- Error responses (such as redirects) are stored in the same tables as the objects they replace.
+
+create table domain (
+    ldh_name text primary key,
+    unicode_name text,
+    response jsonb not null
+);
+create index domain_unicode_name_idx on domain (unicode_name);
+create index domain_ldh_name_pattern_idx on domain (ldh_name text_pattern_ops);
+
+create table entity (
+    handle text primary key,
+    response jsonb not null
+);
+
+create table nameserver (
+    ldh_name text primary key,
+    response jsonb not null
+);
+
+create table autnum (
+    start_autnum bigint not null,
+    end_autnum bigint not null,
+    response jsonb not null,
+    primary key (start_autnum, end_autnum)
+);
+
+create table network (
+    cidr cidr primary key,
+    response jsonb not null
+);
+create index network_cidr_idx on network using gist (cidr inet_ops);
+
+create table srv_help (
+    host text primary key,
+    response jsonb not null
+);
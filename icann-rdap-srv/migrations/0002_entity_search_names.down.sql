@@ -0,0 +1,4 @@
+-- Removes the words used for full-text search of entities.
+
+drop index if exists entity_search_names_idx;
+alter table entity drop column if exists search_names;
@@ -0,0 +1,6 @@
+-- The words of the full names and organization names of entities, for full-text search of entities.
+-- The words are extracted by the server when entities are stored, so entities stored before
+-- this migration are not found by full-text search until they are loaded again.
+
+alter table entity add column search_names text;
+create index entity_search_names_idx on entity using gin (to_tsvector('simple', coalesce(search_names, '')));
@@ -25,12 +25,15 @@ use {
             mem::{config::MemConfig, ops::Mem},
             CommonConfig, StoreOps,
         },
-        util::bin::check::{check_rdap, to_check_classes, CheckArgs},
+        util::bin::{
+            check::{check_rdap, to_check_classes, CheckArgs},
+            generate::Generator,
+        },
     },
     pct_str::{PctString, URIReserved},
     regex::Regex,
     std::{fs, path::PathBuf, str::FromStr},
-    tracing::{error, info},
+    tracing::{debug, error, info},
     tracing_subscriber::{
         fmt, prelude::__tracing_subscriber_SubscriberExt, util::SubscriberInitExt, EnvFilter,
     },
@@ -54,6 +57,18 @@ struct Cli {
     #[arg(long, conflicts_with = "template")]
     redirect: Option<String>,
 
+    /// HTTP status code of a redirect.
+    ///
+    /// Use 301 for resources that are permanently served elsewhere, such as a
+    /// network transferred to another RIR.
+    #[arg(
+        long,
+        requires = "redirect",
+        default_value_t = 307,
+        value_parser = clap::value_parser!(u16).range(300..400)
+    )]
+    redirect_code: u16,
+
     /// Output data as a template.
     ///
     /// When specified, the data will be output as a template file.
@@ -61,8 +76,30 @@ struct Cli {
     #[arg(long, conflicts_with = "redirect")]
     template: bool,
 
+    /// Generate synthetic data.
+    ///
+    /// When specified, this many each of fake but realistic domains, entities,
+    /// nameservers, networks and autnums are created for load testing and demos.
+    /// This cannot be used with a command, --redirect or --template.
+    #[arg(long, conflicts_with_all = ["redirect", "template"])]
+    generate: Option<usize>,
+
+    /// Seed of generated data.
+    ///
+    /// When specified, the same data is generated for the same seed.
+    #[arg(long, requires = "generate")]
+    seed: Option<u64>,
+
+    /// Base URL of the server where generated data is to be served.
+    #[arg(
+        long,
+        env = "RDAP_BASE_URL",
+        default_value = "http://localhost:3000/rdap"
+    )]
+    generate_base_url: String,
+
     #[command(subcommand)]
-    command: Commands,
+    command: Option<Commands>,
 }
 
 #[derive(Debug, Args)]
@@ -516,7 +553,29 @@ async fn do_the_work(
     storage: &dyn StoreOps,
     data_dir: &str,
 ) -> Result<(), RdapServerError> {
-    let output = match cli.command {
+    let command = match (cli.command, cli.generate) {
+        (Some(_), Some(_)) => {
+            return Err(RdapServerError::InvalidArg(
+                "--generate cannot be used with a command".to_string(),
+            ))
+        }
+        (None, Some(count)) => {
+            return generate_data(
+                count,
+                cli.seed,
+                &cli.generate_base_url,
+                &cli.check_args,
+                data_dir,
+            )
+        }
+        (None, None) => {
+            return Err(RdapServerError::InvalidArg(
+                "a command or --generate is required".to_string(),
+            ))
+        }
+        (Some(command), None) => command,
+    };
+    let output = match command {
         Commands::Entity(args) => make_entity(args, storage).await?,
         Commands::Nameserver(args) => make_nameserver(args, storage).await?,
         Commands::Domain(args) => {
@@ -552,7 +611,13 @@ async fn do_the_work(
     } else if cli.template {
         create_template_file(data_dir, &output.self_href, &output.id, &output.rdap)?;
     } else if let Some(redirect_url) = cli.redirect {
-        create_redirect_file(data_dir, &output.self_href, &output.id, &redirect_url)?;
+        create_redirect_file(
+            data_dir,
+            &output.self_href,
+            &output.id,
+            &redirect_url,
+            cli.redirect_code,
+        )?;
     } else {
         create_json_file(data_dir, &output.self_href, output.rdap)?;
     }
@@ -560,6 +625,39 @@ async fn do_the_work(
     Ok(())
 }
 
+fn generate_data(
+    count: usize,
+    seed: Option<u64>,
+    base_url: &str,
+    check_args: &CheckArgs,
+    data_dir: &str,
+) -> Result<(), RdapServerError> {
+    let generated = Generator::new(base_url, seed).generate(count)?;
+
+    let check_types = to_check_classes(check_args);
+    let mut checks_found = false;
+    for generated in &generated {
+        checks_found |= check_rdap(generated.rdap.clone(), &check_types);
+    }
+    if checks_found {
+        return Err(RdapServerError::ErrorOnChecks);
+    } else {
+        info!("Checks conducted and no issues were found.");
+    }
+
+    for generated in &generated {
+        let file_name = create_file_name(&generated.self_href, "json");
+        let path = PathBuf::from(data_dir).join(file_name);
+        fs::write(&path, serde_json::to_string_pretty(&generated.rdap)?)?;
+        debug!("JSON data written to {}.", path.to_string_lossy());
+    }
+    info!(
+        "{} generated objects written to {data_dir}.",
+        generated.len()
+    );
+    Ok(())
+}
+
 fn create_file_name(self_href: &str, extension: &str) -> String {
     let file_name = self_href
         .trim_start_matches("https://")
@@ -604,15 +702,20 @@ fn create_redirect_file(
     self_href: &str,
     id: &RdapId,
     url: &str,
+    redirect_code: u16,
 ) -> Result<(), RdapServerError> {
     let file_name = create_file_name(self_href, "template");
     let mut path = PathBuf::from(data_dir);
     path.push(file_name);
+    let title = http::StatusCode::from_u16(redirect_code)
+        .ok()
+        .and_then(|status| status.canonical_reason())
+        .unwrap_or("Redirect");
     let error = Rfc9083Error::response_obj()
-        .error_code(307)
+        .error_code(redirect_code)
         .notice(Notice(
             NoticeOrRemark::builder()
-                .title("Temporary Redirect")
+                .title(title)
                 .links(vec![Link::builder()
                     .href(url)
                     .value(self_href)
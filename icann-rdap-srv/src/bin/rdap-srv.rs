@@ -1,39 +1,173 @@
+use std::{path::Path, time::Duration};
+
 use {
+    clap::{Parser, Subcommand},
     envmnt::{get_or, get_parse_or, get_u16},
+    icann_rdap_common::VERSION,
     icann_rdap_srv::{
+        bootstrap::BootstrapRegistries,
         config::{
-            data_dir, debug_config_vars, ListenConfig, ServiceConfig, StorageType, AUTO_RELOAD,
-            BOOTSTRAP, LISTEN_ADDR, LISTEN_PORT, LOG, UPDATE_ON_BOOTSTRAP,
+            data_dir, debug_config_vars, virtual_hosts, ListenConfig, ServiceConfig, StorageType,
+            AUTO_RELOAD, BOOTSTRAP, BOOTSTRAP_REGISTRIES, COMPRESSION, COMPRESSION_CONTENT_TYPES,
+            COMPRESSION_MIN_SIZE, DENY_INVALID_RDAP, ERROR_BODIES, LISTEN_ADDR, LISTEN_PORT,
+            LISTEN_UNIX_SOCKET, LOG, OTEL, PROXY, PROXY_CACHE_TTL, PROXY_HTTPS_ONLY, RATE_LIMIT,
+            RATE_LIMIT_BURST, RELOAD_ENDPOINT, RESPONSE_FILTER, SEARCH_PAGE_SIZE,
+            SOCKET_ACTIVATION, UPDATE_ON_BOOTSTRAP, VIRTUAL_HOSTS, WATCH_DATA, WATCH_DEBOUNCE,
+            WHOIS_LISTEN_PORT,
         },
         error::RdapServerError,
+        rdap::{
+            compression::{CompressionConfig, DEFAULT_CONTENT_TYPES, DEFAULT_MIN_SIZE},
+            error_body::ErrorBodies,
+            filter::response_filter_from_file,
+            proxy::ProxyConfig,
+            rate_limit::{RateLimitConfig, DEFAULT_BURST},
+        },
         server::Listener,
+        storage::{pg::ops::Pg, watch::DEFAULT_WATCH_DEBOUNCE},
     },
     tracing_subscriber::{
-        fmt, prelude::__tracing_subscriber_SubscriberExt, util::SubscriberInitExt, EnvFilter,
+        fmt, prelude::__tracing_subscriber_SubscriberExt, util::SubscriberInitExt, EnvFilter, Layer,
     },
 };
 
+#[cfg(feature = "otel")]
+use icann_rdap_client::otel::otlp_layer;
+
+#[derive(Parser, Debug)]
+#[command(author, version = VERSION, about, long_about)]
+/// This program is an RDAP server. Configuration is taken from the environment.
+struct Cli {
+    #[command(subcommand)]
+    command: Option<Command>,
+}
+
+#[derive(Subcommand, Debug)]
+enum Command {
+    /// Manage the schema of the PostgreSQL database.
+    #[command(subcommand)]
+    Db(DbCommand),
+}
+
+#[derive(Subcommand, Debug)]
+enum DbCommand {
+    /// Apply pending schema migrations.
+    Migrate,
+
+    /// Show the status of each schema migration.
+    Status,
+
+    /// Roll back schema migrations.
+    ///
+    /// By default, only the latest applied migration is rolled back.
+    Rollback {
+        /// Roll back all migrations newer than this version.
+        ///
+        /// A version of 0 rolls back all migrations.
+        #[arg(long)]
+        to: Option<i32>,
+    },
+}
+
 #[tokio::main(flavor = "multi_thread")]
 async fn main() -> Result<(), RdapServerError> {
     dotenv::dotenv().ok();
-    tracing_subscriber::registry()
-        .with(fmt::layer())
-        .with(EnvFilter::from_env(LOG))
-        .init();
+    let cli = Cli::parse();
+    let otel: bool = get_parse_or(OTEL, false)?;
+    let subscriber =
+        tracing_subscriber::registry().with(fmt::layer().with_filter(EnvFilter::from_env(LOG)));
+    #[cfg(feature = "otel")]
+    let tracer_provider = if otel {
+        let (layer, provider) =
+            otlp_layer("rdap-srv").map_err(|e| RdapServerError::Config(e.to_string()))?;
+        subscriber
+            .with(layer.with_filter(tracing_subscriber::filter::LevelFilter::INFO))
+            .init();
+        Some(provider)
+    } else {
+        subscriber.init();
+        None
+    };
+    #[cfg(not(feature = "otel"))]
+    {
+        subscriber.init();
+        if otel {
+            tracing::warn!("{OTEL} is set but this server is built without the otel feature.");
+        }
+    }
 
     debug_config_vars();
 
+    if let Some(Command::Db(db_command)) = cli.command {
+        return do_db(db_command).await;
+    }
+
     let listen_addr = get_or(LISTEN_ADDR, "127.0.0.1");
     let listen_port = get_u16(LISTEN_PORT, 3000);
+    let listen_unix_socket = get_or(LISTEN_UNIX_SOCKET, "");
+    let whois_listen_port: u16 = get_parse_or(WHOIS_LISTEN_PORT, 0)?;
+    let socket_activation: bool = get_parse_or(SOCKET_ACTIVATION, true)?;
     let storage_type = StorageType::new_from_env()?;
     let auto_reload: bool = get_parse_or(AUTO_RELOAD, true)?;
     let bootstrap: bool = get_parse_or(BOOTSTRAP, false)?;
     let update_on_bootstrap: bool = get_parse_or(UPDATE_ON_BOOTSTRAP, false)?;
+    let bootstrap_registries = match get_or(BOOTSTRAP_REGISTRIES, "").as_str() {
+        "" => None,
+        "embedded" => Some(BootstrapRegistries::embedded()),
+        dir => Some(BootstrapRegistries::from_dir(Path::new(dir))?),
+    };
+    let search_page_size: usize = get_parse_or(SEARCH_PAGE_SIZE, 0)?;
+    let virtual_hosts = virtual_hosts(&data_dir(), &get_or(VIRTUAL_HOSTS, ""))?;
+    let error_bodies = match get_or(ERROR_BODIES, "").as_str() {
+        "" => ErrorBodies::default(),
+        path => ErrorBodies::from_file(Path::new(path))?,
+    };
+    let response_filter = match get_or(RESPONSE_FILTER, "").as_str() {
+        "" => None,
+        path => Some(response_filter_from_file(Path::new(path))?),
+    };
+    let compression = CompressionConfig::builder()
+        .enable(get_parse_or(COMPRESSION, true)?)
+        .min_size(get_parse_or(COMPRESSION_MIN_SIZE, DEFAULT_MIN_SIZE)?)
+        .content_types(CompressionConfig::parse_content_types(&get_or(
+            COMPRESSION_CONTENT_TYPES,
+            DEFAULT_CONTENT_TYPES,
+        )))
+        .build();
+    let deny_invalid_rdap: bool = get_parse_or(DENY_INVALID_RDAP, false)?;
+    let watch_data: bool = get_parse_or(WATCH_DATA, false)?;
+    let watch_debounce: u64 =
+        get_parse_or(WATCH_DEBOUNCE, DEFAULT_WATCH_DEBOUNCE.as_millis() as u64)?;
+    let reload_endpoint: bool = get_parse_or(RELOAD_ENDPOINT, false)?;
+    let proxy = if get_parse_or(PROXY, false)? {
+        Some(
+            ProxyConfig::builder()
+                .cache_ttl(Duration::from_secs(get_parse_or(PROXY_CACHE_TTL, 0)?))
+                .https_only(get_parse_or(PROXY_HTTPS_ONLY, true)?)
+                .build(),
+        )
+    } else {
+        None
+    };
+    let rate_limit: f64 = get_parse_or(RATE_LIMIT, 0.0)?;
+    let rate_limit = if rate_limit != 0.0 {
+        Some(
+            RateLimitConfig::builder()
+                .rate(rate_limit)
+                .burst(get_parse_or(RATE_LIMIT_BURST, DEFAULT_BURST)?)
+                .build()?,
+        )
+    } else {
+        None
+    };
 
     let listener = Listener::listen(
         &ListenConfig::builder()
             .ip_addr(listen_addr)
             .port(listen_port)
+            .and_unix_socket((!listen_unix_socket.is_empty()).then_some(listen_unix_socket))
+            .socket_activation(socket_activation)
+            .and_whois_port((whois_listen_port > 0).then_some(whois_listen_port))
             .build(),
     )
     .await?;
@@ -45,8 +179,84 @@ async fn main() -> Result<(), RdapServerError> {
                 .auto_reload(auto_reload)
                 .bootstrap(bootstrap)
                 .update_on_bootstrap(update_on_bootstrap)
+                .and_bootstrap_registries(bootstrap_registries)
+                .and_search_page_size((search_page_size > 0).then_some(search_page_size))
+                .virtual_hosts(virtual_hosts)
+                .error_bodies(error_bodies)
+                .and_response_filter(response_filter)
+                .compression(compression)
+                .deny_invalid_rdap(deny_invalid_rdap)
+                .watch_data(watch_data)
+                .watch_debounce(Duration::from_millis(watch_debounce))
+                .reload_endpoint(reload_endpoint)
+                .and_rate_limit(rate_limit)
+                .and_proxy(proxy)
                 .build(),
         )
         .await?;
+
+    #[cfg(feature = "otel")]
+    if let Some(provider) = tracer_provider {
+        let _ = provider.shutdown();
+    }
+    Ok(())
+}
+
+async fn do_db(db_command: DbCommand) -> Result<(), RdapServerError> {
+    let StorageType::Postgres(config) = StorageType::new_from_env()? else {
+        return Err(RdapServerError::Config(
+            "database commands require postgres storage".to_string(),
+        ));
+    };
+    let pg = Pg::new(config).await?;
+    match db_command {
+        DbCommand::Migrate => {
+            let applied = pg.migrate().await?;
+            if applied.is_empty() {
+                println!("No pending migrations.");
+            }
+            for version in applied {
+                println!("Applied migration {version}.");
+            }
+        }
+        DbCommand::Status => {
+            for status in pg.migration_status().await? {
+                let applied = match status.applied_at {
+                    Some(applied_at) => format!("applied {}", applied_at.to_rfc3339()),
+                    None => "pending".to_string(),
+                };
+                println!(
+                    "{:>4}  {:<24} {applied}",
+                    status.version,
+                    status.description.unwrap_or("(unknown to this server)")
+                );
+            }
+        }
+        DbCommand::Rollback { to } => {
+            let to_version = match to {
+                Some(to) => to,
+                None => {
+                    let applied: Vec<i32> = pg
+                        .migration_status()
+                        .await?
+                        .into_iter()
+                        .filter(|s| s.applied_at.is_some())
+                        .map(|s| s.version)
+                        .collect();
+                    match applied.as_slice() {
+                        [.., previous, _] => *previous,
+                        _ => 0,
+                    }
+                }
+            };
+            let rolled_back = pg.rollback(to_version).await?;
+            if rolled_back.is_empty() {
+                println!("No migrations to roll back.");
+            }
+            for version in rolled_back {
+                println!("Rolled back migration {version}.");
+            }
+        }
+    }
     Ok(())
 }
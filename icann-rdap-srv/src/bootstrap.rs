@@ -1,4 +1,7 @@
-use std::{path::PathBuf, time::Duration};
+use std::{
+    path::{Path, PathBuf},
+    time::Duration,
+};
 
 use {
     icann_rdap_client::{
@@ -29,8 +32,110 @@ use crate::{
 
 const IANA_JSON_SUFFIX: &str = ".iana_cache";
 
+/// The registry types from which redirects are made, in the order they are loaded.
+pub(crate) const BOOTSTRAP_TYPES: [IanaRegistryType; 5] = [
+    IanaRegistryType::RdapBootstrapDns,
+    IanaRegistryType::RdapBootstrapAsn,
+    IanaRegistryType::RdapBootstrapIpv4,
+    IanaRegistryType::RdapBootstrapIpv6,
+    IanaRegistryType::RdapObjectTags,
+];
+
+/// Bootstrap registries given to the server in place of those of IANA.
+///
+/// With these, the redirects of a bootstrapping server do not depend on IANA, which
+/// makes them deterministic for testing. Registry types not given have no redirects.
+#[derive(Debug, Clone, Default)]
+pub struct BootstrapRegistries {
+    dns: Option<IanaRegistry>,
+    asn: Option<IanaRegistry>,
+    ipv4: Option<IanaRegistry>,
+    ipv6: Option<IanaRegistry>,
+    object_tags: Option<IanaRegistry>,
+}
+
+#[buildstructor::buildstructor]
+impl BootstrapRegistries {
+    #[builder]
+    pub fn new(
+        dns: Option<IanaRegistry>,
+        asn: Option<IanaRegistry>,
+        ipv4: Option<IanaRegistry>,
+        ipv6: Option<IanaRegistry>,
+        object_tags: Option<IanaRegistry>,
+    ) -> Self {
+        Self {
+            dns,
+            asn,
+            ipv4,
+            ipv6,
+            object_tags,
+        }
+    }
+
+    /// A minimal set of registries embedded in the server, which redirect
+    /// reserved names, numbers and addresses to `rdap.example.com` and `rdap.example.net`.
+    ///
+    /// * domains: `example` and `test` to `https://rdap.example.com/`, and `invalid` to `https://rdap.example.net/`
+    /// * autnums: 64496-64511 to `https://rdap.example.com/`, and 65536-65551 to `https://rdap.example.net/`
+    /// * IPv4: 192.0.2.0/24 and 198.51.100.0/24 to `https://rdap.example.com/`, and 203.0.113.0/24 to `https://rdap.example.net/`
+    /// * IPv6: 2001:db8::/32 to `https://rdap.example.com/`
+    /// * object tags: `EXAMPLE` to `https://rdap.example.com/`
+    pub fn embedded() -> Self {
+        let parse = |json: &str| {
+            serde_json::from_str::<IanaRegistry>(json).expect("embedded bootstrap is invalid")
+        };
+        Self {
+            dns: Some(parse(include_str!("../resources/bootstrap/dns.json"))),
+            asn: Some(parse(include_str!("../resources/bootstrap/asn.json"))),
+            ipv4: Some(parse(include_str!("../resources/bootstrap/ipv4.json"))),
+            ipv6: Some(parse(include_str!("../resources/bootstrap/ipv6.json"))),
+            object_tags: Some(parse(include_str!(
+                "../resources/bootstrap/object-tags.json"
+            ))),
+        }
+    }
+
+    /// Reads the registries in a directory, which are in files named as those of IANA,
+    /// such as `dns.json`. Registries without a file are not given.
+    pub fn from_dir(dir: &Path) -> Result<Self, RdapServerError> {
+        let read = |reg_type: IanaRegistryType| -> Result<Option<IanaRegistry>, RdapServerError> {
+            let path = dir.join(reg_type.file_name());
+            if !path.exists() {
+                return Ok(None);
+            }
+            let json = std::fs::read_to_string(&path)?;
+            serde_json::from_str(&json).map(Some).map_err(|e| {
+                RdapServerError::Bootstrap(format!("{} is not a registry: {e}", path.display()))
+            })
+        };
+        Ok(Self {
+            dns: read(IanaRegistryType::RdapBootstrapDns)?,
+            asn: read(IanaRegistryType::RdapBootstrapAsn)?,
+            ipv4: read(IanaRegistryType::RdapBootstrapIpv4)?,
+            ipv6: read(IanaRegistryType::RdapBootstrapIpv6)?,
+            object_tags: read(IanaRegistryType::RdapObjectTags)?,
+        })
+    }
+
+    /// Gets the registry of a type, if given.
+    pub fn get(&self, reg_type: &IanaRegistryType) -> Option<&IanaRegistry> {
+        match reg_type {
+            IanaRegistryType::RdapBootstrapDns => self.dns.as_ref(),
+            IanaRegistryType::RdapBootstrapAsn => self.asn.as_ref(),
+            IanaRegistryType::RdapBootstrapIpv4 => self.ipv4.as_ref(),
+            IanaRegistryType::RdapBootstrapIpv6 => self.ipv6.as_ref(),
+            IanaRegistryType::RdapObjectTags => self.object_tags.as_ref(),
+        }
+    }
+}
+
 pub async fn init_bootstrap(config: &ServiceConfig) -> Result<(), RdapServerError> {
     if config.bootstrap {
+        if let Some(registries) = &config.bootstrap_registries {
+            info!("Initializing Bootstrap from given registries.");
+            return load_bootstrap_registries(config, registries).await;
+        }
         info!("Initializing IANA Bootstrap.");
         let client_config = ClientConfig::builder()
             .user_agent_suffix("icann-rdap-srv")
@@ -46,6 +151,24 @@ pub async fn init_bootstrap(config: &ServiceConfig) -> Result<(), RdapServerErro
     Ok(())
 }
 
+/// Replaces the redirects in the data directory with those of the given registries
+/// and triggers a reload (or update) of the server.
+///
+/// The redirects of registry types not given are left as they are.
+pub async fn load_bootstrap_registries(
+    config: &ServiceConfig,
+    registries: &BootstrapRegistries,
+) -> Result<(), RdapServerError> {
+    let registries = BOOTSTRAP_TYPES
+        .into_iter()
+        .filter_map(|reg_type| {
+            let registry = registries.get(&reg_type).cloned();
+            registry.map(|registry| (reg_type, registry))
+        })
+        .collect();
+    make_bootstrap(config, registries).await
+}
+
 async fn loop_bootstrap(config: ServiceConfig, client: Client) -> Result<(), RdapServerError> {
     loop {
         sleep(Duration::from_millis(60000)).await;
@@ -54,57 +177,41 @@ async fn loop_bootstrap(config: ServiceConfig, client: Client) -> Result<(), Rda
 }
 
 async fn process_bootstrap(config: &ServiceConfig, client: &Client) -> Result<(), RdapServerError> {
-    let mut new_data = false;
-    if let Some(iana_reg) =
-        fetch_iana_registry(IanaRegistryType::RdapBootstrapDns, client, &config.data_dir).await?
-    {
-        remove_previous_bootstrap(config, IanaRegistryType::RdapBootstrapDns).await?;
-        make_dns_bootstrap(config, iana_reg).await?;
-        new_data = true;
-    }
-    if let Some(iana_reg) =
-        fetch_iana_registry(IanaRegistryType::RdapBootstrapAsn, client, &config.data_dir).await?
-    {
-        remove_previous_bootstrap(config, IanaRegistryType::RdapBootstrapAsn).await?;
-        make_asn_bootstrap(config, iana_reg).await?;
-        new_data = true;
-    }
-    if let Some(iana_reg) = fetch_iana_registry(
-        IanaRegistryType::RdapBootstrapIpv4,
-        client,
-        &config.data_dir,
-    )
-    .await?
-    {
-        remove_previous_bootstrap(config, IanaRegistryType::RdapBootstrapIpv4).await?;
-        make_ip_bootstrap(config, iana_reg, IanaRegistryType::RdapBootstrapIpv4).await?;
-        new_data = true;
-    }
-    if let Some(iana_reg) = fetch_iana_registry(
-        IanaRegistryType::RdapBootstrapIpv6,
-        client,
-        &config.data_dir,
-    )
-    .await?
-    {
-        remove_previous_bootstrap(config, IanaRegistryType::RdapBootstrapIpv6).await?;
-        make_ip_bootstrap(config, iana_reg, IanaRegistryType::RdapBootstrapIpv6).await?;
-        new_data = true;
+    let mut registries = vec![];
+    for reg_type in BOOTSTRAP_TYPES {
+        if let Some(iana_reg) =
+            fetch_iana_registry(reg_type.clone(), client, &config.data_dir).await?
+        {
+            registries.push((reg_type, iana_reg));
+        }
     }
-    if let Some(iana_reg) =
-        fetch_iana_registry(IanaRegistryType::RdapObjectTags, client, &config.data_dir).await?
-    {
-        remove_previous_bootstrap(config, IanaRegistryType::RdapObjectTags).await?;
-        make_tag_registry(config, iana_reg).await?;
-        new_data = true;
+    make_bootstrap(config, registries).await
+}
+
+/// Makes the redirects of new registries, triggering a reload (or update) if there are any.
+async fn make_bootstrap(
+    config: &ServiceConfig,
+    registries: Vec<(IanaRegistryType, IanaRegistry)>,
+) -> Result<(), RdapServerError> {
+    if registries.is_empty() {
+        return Ok(());
     }
-    if new_data {
-        if config.update_on_bootstrap {
-            trigger_update(&config.data_dir).await?;
-        } else {
-            trigger_reload(&config.data_dir).await?;
+    for (reg_type, iana_reg) in registries {
+        remove_previous_bootstrap(config, reg_type.clone()).await?;
+        match reg_type {
+            IanaRegistryType::RdapBootstrapDns => make_dns_bootstrap(config, iana_reg).await?,
+            IanaRegistryType::RdapBootstrapAsn => make_asn_bootstrap(config, iana_reg).await?,
+            IanaRegistryType::RdapBootstrapIpv4 | IanaRegistryType::RdapBootstrapIpv6 => {
+                make_ip_bootstrap(config, iana_reg, reg_type).await?
+            }
+            IanaRegistryType::RdapObjectTags => make_tag_registry(config, iana_reg).await?,
         }
     }
+    if config.update_on_bootstrap {
+        trigger_update(&config.data_dir).await?;
+    } else {
+        trigger_reload(&config.data_dir).await?;
+    }
     Ok(())
 }
 
@@ -777,4 +884,74 @@ mod tests {
             "https://whois-web.as207960.net/rdap/"
         );
     }
+
+    #[tokio::test]
+    async fn GIVEN_embedded_registries_WHEN_load_bootstrap_registries_THEN_redirects_loaded() {
+        // GIVEN
+        let registries = BootstrapRegistries::embedded();
+
+        // WHEN
+        let temp = TestDir::temp();
+        let config = ServiceConfig::non_server()
+            .data_dir(temp.root().to_string_lossy().to_string())
+            .build()
+            .expect("error making service config");
+        load_bootstrap_registries(&config, &registries)
+            .await
+            .expect("unable to load bootstrap registries");
+
+        // THEN
+        let mem = new_and_init_mem(config.data_dir).await;
+        let response = mem
+            .get_domain_by_ldh("example")
+            .await
+            .expect("lookup of example");
+        let RdapResponse::ErrorResponse(error) = response else {
+            panic!("not an error response")
+        };
+        assert_eq!(get_redirect_link(*error), "https://rdap.example.com/");
+        let response = mem
+            .get_domain_by_ldh("invalid")
+            .await
+            .expect("lookup of invalid");
+        let RdapResponse::ErrorResponse(error) = response else {
+            panic!("not an error response")
+        };
+        assert_eq!(get_redirect_link(*error), "https://rdap.example.net/");
+        let response = mem.get_autnum_by_num(65540).await.expect("lookup of 65540");
+        let RdapResponse::ErrorResponse(error) = response else {
+            panic!("not an error response")
+        };
+        assert_eq!(get_redirect_link(*error), "https://rdap.example.net/");
+        let response = mem
+            .get_entity_by_handle("-EXAMPLE")
+            .await
+            .expect("lookup of -EXAMPLE");
+        let RdapResponse::ErrorResponse(error) = response else {
+            panic!("not an error response")
+        };
+        assert_eq!(get_redirect_link(*error), "https://rdap.example.com/");
+    }
+
+    #[test]
+    fn GIVEN_dir_with_dns_registry_WHEN_from_dir_THEN_only_dns_given() {
+        // GIVEN
+        let temp = TestDir::temp();
+        std::fs::write(
+            temp.root().join("dns.json"),
+            include_str!("../resources/bootstrap/dns.json"),
+        )
+        .expect("writing dns.json");
+
+        // WHEN
+        let registries = BootstrapRegistries::from_dir(temp.root()).expect("reading registries");
+
+        // THEN
+        assert!(registries
+            .get(&IanaRegistryType::RdapBootstrapDns)
+            .is_some());
+        assert!(registries
+            .get(&IanaRegistryType::RdapBootstrapAsn)
+            .is_none());
+    }
 }
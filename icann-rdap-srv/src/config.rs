@@ -1,38 +1,95 @@
+use std::{collections::BTreeMap, path::Path, time::Duration};
+
 use {
     buildstructor::Builder,
     envmnt::{get_or, get_parse_or},
+    icann_rdap_common::response::filter::ResponseFilter,
     strum_macros::Display,
     tracing::debug,
 };
 
 use crate::{
+    bootstrap::BootstrapRegistries,
     error::RdapServerError,
-    storage::{mem::config::MemConfig, pg::config::PgConfig, CommonConfig},
+    rdap::{
+        compression::CompressionConfig, error_body::ErrorBodies, proxy::ProxyConfig,
+        rate_limit::RateLimitConfig,
+    },
+    storage::{
+        backend::storage_backend, mem::config::MemConfig, pg::config::PgConfig,
+        watch::DEFAULT_WATCH_DEBOUNCE, CommonConfig,
+    },
 };
 
 pub const LOG: &str = "RDAP_SRV_LOG";
 pub const LISTEN_ADDR: &str = "RDAP_SRV_LISTEN_ADDR";
 pub const LISTEN_PORT: &str = "RDAP_SRV_LISTEN_PORT";
+pub const LISTEN_UNIX_SOCKET: &str = "RDAP_SRV_LISTEN_UNIX_SOCKET";
+pub const WHOIS_LISTEN_PORT: &str = "RDAP_SRV_WHOIS_LISTEN_PORT";
+pub const SOCKET_ACTIVATION: &str = "RDAP_SRV_SOCKET_ACTIVATION";
 pub const STORAGE: &str = "RDAP_SRV_STORAGE";
 pub const DB_URL: &str = "RDAP_SRV_DB_URL";
+pub const DB_AUTO_MIGRATE: &str = "RDAP_SRV_DB_AUTO_MIGRATE";
 pub const DATA_DIR: &str = "RDAP_SRV_DATA_DIR";
 pub const AUTO_RELOAD: &str = "RDAP_SRV_AUTO_RELOAD";
 pub const BOOTSTRAP: &str = "RDAP_SRV_BOOTSTRAP";
 pub const UPDATE_ON_BOOTSTRAP: &str = "RDAP_SRV_UPDATE_ON_BOOTSTRAP";
+pub const BOOTSTRAP_REGISTRIES: &str = "RDAP_SRV_BOOTSTRAP_REGISTRIES";
 pub const DOMAIN_SEARCH_BY_NAME_ENABLE: &str = "RDAP_SRV_DOMAIN_SEARCH_BY_NAME";
+pub const SEARCH_PAGE_SIZE: &str = "RDAP_SRV_SEARCH_PAGE_SIZE";
+pub const ENTITY_SEARCH_INDEX_ENABLE: &str = "RDAP_SRV_ENTITY_SEARCH_INDEX";
+pub const ERROR_BODIES: &str = "RDAP_SRV_ERROR_BODIES";
+pub const RESPONSE_FILTER: &str = "RDAP_SRV_RESPONSE_FILTER";
+pub const VIRTUAL_HOSTS: &str = "RDAP_SRV_VIRTUAL_HOSTS";
+pub const COMPRESSION: &str = "RDAP_SRV_COMPRESSION";
+pub const COMPRESSION_MIN_SIZE: &str = "RDAP_SRV_COMPRESSION_MIN_SIZE";
+pub const COMPRESSION_CONTENT_TYPES: &str = "RDAP_SRV_COMPRESSION_CONTENT_TYPES";
+pub const DENY_INVALID_RDAP: &str = "RDAP_SRV_DENY_INVALID_RDAP";
+pub const WATCH_DATA: &str = "RDAP_SRV_WATCH_DATA";
+pub const WATCH_DEBOUNCE: &str = "RDAP_SRV_WATCH_DEBOUNCE";
+pub const RELOAD_ENDPOINT: &str = "RDAP_SRV_RELOAD_ENDPOINT";
+pub const OTEL: &str = "RDAP_SRV_OTEL";
+pub const RATE_LIMIT: &str = "RDAP_SRV_RATE_LIMIT";
+pub const RATE_LIMIT_BURST: &str = "RDAP_SRV_RATE_LIMIT_BURST";
+pub const PROXY: &str = "RDAP_SRV_PROXY";
+pub const PROXY_CACHE_TTL: &str = "RDAP_SRV_PROXY_CACHE_TTL";
+pub const PROXY_HTTPS_ONLY: &str = "RDAP_SRV_PROXY_HTTPS_ONLY";
 
 pub fn debug_config_vars() {
     let var_list = [
         LOG,
         LISTEN_ADDR,
         LISTEN_PORT,
+        LISTEN_UNIX_SOCKET,
+        WHOIS_LISTEN_PORT,
+        SOCKET_ACTIVATION,
         STORAGE,
         DB_URL,
+        DB_AUTO_MIGRATE,
         DATA_DIR,
         AUTO_RELOAD,
         BOOTSTRAP,
         UPDATE_ON_BOOTSTRAP,
+        BOOTSTRAP_REGISTRIES,
         DOMAIN_SEARCH_BY_NAME_ENABLE,
+        SEARCH_PAGE_SIZE,
+        ENTITY_SEARCH_INDEX_ENABLE,
+        ERROR_BODIES,
+        RESPONSE_FILTER,
+        VIRTUAL_HOSTS,
+        COMPRESSION,
+        COMPRESSION_MIN_SIZE,
+        COMPRESSION_CONTENT_TYPES,
+        DENY_INVALID_RDAP,
+        WATCH_DATA,
+        WATCH_DEBOUNCE,
+        RELOAD_ENDPOINT,
+        OTEL,
+        RATE_LIMIT,
+        RATE_LIMIT_BURST,
+        PROXY,
+        PROXY_CACHE_TTL,
+        PROXY_HTTPS_ONLY,
     ];
     envmnt::vars()
         .iter()
@@ -44,6 +101,46 @@ pub fn data_dir() -> String {
     get_or(DATA_DIR, "/tmp/rdap-srv/data")
 }
 
+/// The directory in the data directory holding the data directories of virtual hosts.
+pub const VIRTUAL_HOSTS_DIR: &str = "hosts";
+
+/// Gets the data directories of virtual hosts by host name.
+///
+/// These are the directories in the `hosts` directory of the data directory, each
+/// named by its host, such as `hosts/rdap.example.com`, and those given as comma
+/// separated `host=directory` pairs, which are preferred.
+pub fn virtual_hosts(
+    data_dir: &str,
+    pairs: &str,
+) -> Result<BTreeMap<String, String>, RdapServerError> {
+    let mut hosts = BTreeMap::new();
+    let hosts_dir = Path::new(data_dir).join(VIRTUAL_HOSTS_DIR);
+    if hosts_dir.is_dir() {
+        for entry in std::fs::read_dir(hosts_dir)? {
+            let entry = entry?;
+            if entry.path().is_dir() {
+                hosts.insert(
+                    entry.file_name().to_string_lossy().to_ascii_lowercase(),
+                    entry.path().to_string_lossy().to_string(),
+                );
+            }
+        }
+    }
+    for pair in pairs
+        .split(',')
+        .map(str::trim)
+        .filter(|pair| !pair.is_empty())
+    {
+        let Some((host, dir)) = pair.split_once('=') else {
+            return Err(RdapServerError::Config(format!(
+                "virtual host '{pair}' is not host=directory"
+            )));
+        };
+        hosts.insert(host.trim().to_ascii_lowercase(), dir.trim().to_string());
+    }
+    Ok(hosts)
+}
+
 /// RDAP server listening configuration.
 #[derive(Debug, Builder, Default)]
 pub struct ListenConfig {
@@ -54,6 +151,18 @@ pub struct ListenConfig {
     /// If specified, determines the port number the server will bind to.
     /// If unspecified, the server let's the OS determine the port.
     pub port: Option<u16>,
+
+    /// If specified, the server binds a unix domain socket at this path instead
+    /// of an IP address and port. Only available on unix platforms.
+    pub unix_socket: Option<String>,
+
+    /// If true and the server is started with systemd socket activation, the server
+    /// listens on the first socket passed to it instead of binding one.
+    pub socket_activation: bool,
+
+    /// If specified, the server also answers WHOIS queries on this port of the IP address,
+    /// such as 43. A port of 0 lets the OS determine the port.
+    pub whois_port: Option<u16>,
 }
 
 /// Determines the storage type.
@@ -65,13 +174,21 @@ pub enum StorageType {
 
     /// Uses a PostgreSQL database.
     Postgres(PgConfig),
+
+    /// Uses a storage backend registered with [crate::storage::backend::register_storage_backend].
+    Backend {
+        name: String,
+        common_config: CommonConfig,
+    },
 }
 
 impl StorageType {
     pub fn new_from_env() -> Result<Self, RdapServerError> {
         let domain_search_by_name = get_parse_or(DOMAIN_SEARCH_BY_NAME_ENABLE, false)?;
+        let entity_search_index = get_parse_or(ENTITY_SEARCH_INDEX_ENABLE, false)?;
         let common_config = CommonConfig::builder()
             .domain_search_by_name_enable(domain_search_by_name)
+            .entity_search_index_enable(entity_search_index)
             .build();
         let storage = get_or(STORAGE, "memory");
         if storage == "memory" {
@@ -80,12 +197,19 @@ impl StorageType {
             ))
         } else if storage == "postgres" {
             let db_url = get_or(DB_URL, "postgresql://127.0.0.1/rdap");
+            let auto_migrate = get_parse_or(DB_AUTO_MIGRATE, true)?;
             Ok(Self::Postgres(
                 PgConfig::builder()
                     .db_url(db_url)
+                    .auto_migrate(auto_migrate)
                     .common_config(common_config)
                     .build(),
             ))
+        } else if storage_backend(&storage).is_some() {
+            Ok(Self::Backend {
+                name: storage,
+                common_config,
+            })
         } else {
             Err(RdapServerError::Config(format!(
                 "storage type of '{storage}' is invalid"
@@ -102,6 +226,44 @@ pub struct ServiceConfig {
     pub auto_reload: bool,
     pub bootstrap: bool,
     pub update_on_bootstrap: bool,
+
+    /// If specified, search results are divided into pages of this many results.
+    pub search_page_size: Option<usize>,
+
+    /// The data directories of virtual hosts by host name. Requests for these hosts
+    /// are answered from storage of their own, loaded from their data directories.
+    pub virtual_hosts: BTreeMap<String, String>,
+
+    /// Customizations of the bodies of error responses.
+    pub error_bodies: ErrorBodies,
+
+    /// If specified, the members of RDAP responses are removed or masked by this filter.
+    pub response_filter: Option<ResponseFilter>,
+
+    /// The configuration of response compression.
+    pub compression: CompressionConfig,
+
+    /// If true, RDAP having errors found by the check system is neither loaded nor served.
+    pub deny_invalid_rdap: bool,
+
+    /// If true, storage is reloaded when the files of the data directory change.
+    pub watch_data: bool,
+
+    /// The time the data directory must go unchanged before storage is reloaded.
+    pub watch_debounce: Duration,
+
+    /// If true, storage is reloaded by a POST to the `/__reload` endpoint.
+    pub reload_endpoint: bool,
+
+    /// If specified, bootstrapping redirects with these registries instead of those of IANA.
+    pub bootstrap_registries: Option<BootstrapRegistries>,
+
+    /// If specified, the requests of each client IP address are rate limited.
+    pub rate_limit: Option<RateLimitConfig>,
+
+    /// If specified, lookups not found in storage are proxied to the RDAP servers
+    /// given for them by bootstrapping.
+    pub proxy: Option<ProxyConfig>,
 }
 
 #[buildstructor::buildstructor]
@@ -122,6 +284,50 @@ impl ServiceConfig {
             auto_reload: false,
             bootstrap: false,
             update_on_bootstrap: false,
+            search_page_size: None,
+            virtual_hosts: BTreeMap::new(),
+            error_bodies: ErrorBodies::default(),
+            response_filter: None,
+            compression: CompressionConfig::default(),
+            deny_invalid_rdap: false,
+            watch_data: false,
+            watch_debounce: DEFAULT_WATCH_DEBOUNCE,
+            reload_endpoint: false,
+            bootstrap_registries: None,
+            rate_limit: None,
+            proxy: None,
         })
     }
 }
+
+#[cfg(test)]
+#[allow(non_snake_case)]
+mod tests {
+    use super::virtual_hosts;
+
+    #[test]
+    fn GIVEN_host_pairs_WHEN_virtual_hosts_THEN_hosts_lower_cased() {
+        // GIVEN
+        let pairs = "RDAP.Example.com=/data/example, rdap.other.test = /data/other,";
+
+        // WHEN
+        let actual = virtual_hosts("/no/such/data/dir", pairs).expect("virtual hosts");
+
+        // THEN
+        assert_eq!(actual.len(), 2);
+        assert_eq!(actual["rdap.example.com"], "/data/example");
+        assert_eq!(actual["rdap.other.test"], "/data/other");
+    }
+
+    #[test]
+    fn GIVEN_pair_without_directory_WHEN_virtual_hosts_THEN_error() {
+        // GIVEN
+        let pairs = "rdap.example.com";
+
+        // WHEN
+        let actual = virtual_hosts("/no/such/data/dir", pairs);
+
+        // THEN
+        assert!(actual.is_err());
+    }
+}
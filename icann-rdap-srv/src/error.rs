@@ -16,6 +16,8 @@ use {
     thiserror::Error,
 };
 
+use crate::{hooks::call_hooks, rdap::response::RDAP_HEADERS};
+
 /// Errors from the RDAP Server.
 #[derive(Debug, Error)]
 pub enum RdapServerError {
@@ -31,6 +33,8 @@ pub enum RdapServerError {
     Config(String),
     #[error(transparent)]
     SqlDb(#[from] sqlx::Error),
+    #[error("incompatible database schema: {0}")]
+    IncompatibleSchema(String),
     #[error("index data for {0} is missing or empty")]
     EmptyIndexData(String),
     #[error("file at {0} is not JSON")]
@@ -67,13 +71,14 @@ pub enum RdapServerError {
 
 impl IntoResponse for RdapServerError {
     fn into_response(self) -> Response {
+        call_hooks(|hooks| hooks.on_error(&self));
         let response = Rfc9083Error::response_obj()
             .error_code(500)
             .build()
             .to_response();
         (
             StatusCode::INTERNAL_SERVER_ERROR,
-            [("content-type", r#"application/rdap"#)],
+            RDAP_HEADERS,
             Json(response),
         )
             .into_response()
@@ -0,0 +1,229 @@
+//! Hooks into the events of the server.
+//!
+//! Deployments observe the server, such as by pushing its events to a message
+//! queue, by registering [ServerHooks] before the server is started. Every
+//! registered hook is called for each event.
+//!
+//! Hooks are called on the tasks serving requests and reloading data, so they
+//! should return quickly, handing off any slow work such as network I/O to
+//! another task.
+//!
+//! ```rust,no_run
+//! use icann_rdap_srv::hooks::{
+//!     register_server_hooks, LookupMissEvent, RequestEvent, ServerHooks,
+//! };
+//!
+//! struct LogHooks;
+//!
+//! impl ServerHooks for LogHooks {
+//!     fn on_request(&self, event: &RequestEvent) {
+//!         println!("{} {} {}", event.method, event.uri, event.status);
+//!     }
+//!
+//!     fn on_lookup_miss(&self, event: &LookupMissEvent) {
+//!         println!("not found: {}", event.uri);
+//!     }
+//! }
+//!
+//! register_server_hooks(LogHooks);
+//! ```
+
+use std::{
+    sync::{Arc, LazyLock, RwLock},
+    time::{Duration, Instant},
+};
+
+use {
+    axum::{extract::Request, middleware::Next, response::Response},
+    http::{Method, StatusCode, Uri},
+};
+
+use crate::error::RdapServerError;
+
+/// The registered hooks.
+static SERVER_HOOKS: LazyLock<RwLock<Vec<Arc<dyn ServerHooks>>>> = LazyLock::new(<_>::default);
+
+/// Hooks called on the events of the server.
+///
+/// Each hook does nothing unless implemented.
+pub trait ServerHooks: Send + Sync {
+    /// Called when a request has been answered.
+    fn on_request(&self, _event: &RequestEvent) {}
+
+    /// Called when a request for RDAP is answered with not found.
+    fn on_lookup_miss(&self, _event: &LookupMissEvent) {}
+
+    /// Called when storage has been loaded again from the data directory, or
+    /// has failed to be.
+    fn on_data_reload(&self, _event: &DataReloadEvent) {}
+
+    /// Called when an error is answered with an internal server error.
+    fn on_error(&self, _error: &RdapServerError) {}
+}
+
+/// A request that has been answered.
+#[derive(Debug, Clone)]
+pub struct RequestEvent {
+    pub method: Method,
+    pub uri: Uri,
+    pub status: StatusCode,
+
+    /// The time taken to answer the request.
+    pub elapsed: Duration,
+}
+
+/// A request for RDAP that was not found.
+#[derive(Debug, Clone)]
+pub struct LookupMissEvent {
+    pub uri: Uri,
+}
+
+/// A load of storage from the data directory after the server started.
+#[derive(Debug)]
+pub struct DataReloadEvent<'a> {
+    pub data_dir: &'a str,
+
+    /// True if the contents of storage were replaced, or false if the data was
+    /// added to storage.
+    pub replaced: bool,
+
+    /// The result of the load, which leaves storage as it was if an error.
+    pub result: Result<(), &'a RdapServerError>,
+}
+
+/// Registers hooks, which are called after those already registered.
+pub fn register_server_hooks(hooks: impl ServerHooks + 'static) {
+    SERVER_HOOKS
+        .write()
+        .expect("server hooks lock poisoned")
+        .push(Arc::new(hooks));
+}
+
+/// Calls every registered hook.
+pub(crate) fn call_hooks(call: impl Fn(&dyn ServerHooks)) {
+    // hooks are called outside the lock so that a hook may register hooks.
+    let hooks = SERVER_HOOKS
+        .read()
+        .expect("server hooks lock poisoned")
+        .clone();
+    for hook in hooks {
+        call(hook.as_ref());
+    }
+}
+
+/// Calls the hooks of a data reload with its result.
+pub(crate) fn call_reload_hooks(
+    data_dir: &str,
+    replaced: bool,
+    result: &Result<(), RdapServerError>,
+) {
+    call_hooks(|hooks| {
+        hooks.on_data_reload(&DataReloadEvent {
+            data_dir,
+            replaced,
+            result: result.as_ref().map(|_| ()),
+        })
+    });
+}
+
+/// Middleware that calls the hooks of requests.
+pub(crate) async fn observe_requests(request: Request, next: Next) -> Response {
+    if SERVER_HOOKS
+        .read()
+        .expect("server hooks lock poisoned")
+        .is_empty()
+    {
+        return next.run(request).await;
+    }
+    let start = Instant::now();
+    let method = request.method().clone();
+    let uri = request.uri().clone();
+    let response = next.run(request).await;
+    let event = RequestEvent {
+        method,
+        uri,
+        status: response.status(),
+        elapsed: start.elapsed(),
+    };
+    let miss = (event.status == StatusCode::NOT_FOUND && event.uri.path().starts_with("/rdap/"))
+        .then(|| LookupMissEvent {
+            uri: event.uri.clone(),
+        });
+    call_hooks(|hooks| {
+        hooks.on_request(&event);
+        if let Some(miss) = &miss {
+            hooks.on_lookup_miss(miss);
+        }
+    });
+    response
+}
+
+#[cfg(test)]
+#[allow(non_snake_case)]
+mod tests {
+    use std::sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc,
+    };
+
+    use crate::error::RdapServerError;
+
+    use super::{
+        call_hooks, call_reload_hooks, register_server_hooks, DataReloadEvent, ServerHooks,
+    };
+
+    const DATA_DIR: &str = "hooks-test-data";
+
+    /// Counts the events of this test, as other tests may call the hooks too.
+    #[derive(Default)]
+    struct CountingHooks {
+        reloads: Arc<AtomicUsize>,
+        failed_reloads: Arc<AtomicUsize>,
+        errors: Arc<AtomicUsize>,
+    }
+
+    impl ServerHooks for CountingHooks {
+        fn on_data_reload(&self, event: &DataReloadEvent) {
+            if event.data_dir != DATA_DIR {
+                return;
+            }
+            self.reloads.fetch_add(1, Ordering::SeqCst);
+            if event.result.is_err() {
+                self.failed_reloads.fetch_add(1, Ordering::SeqCst);
+            }
+        }
+
+        fn on_error(&self, error: &RdapServerError) {
+            if error.to_string() != RdapServerError::Config(DATA_DIR.to_string()).to_string() {
+                return;
+            }
+            self.errors.fetch_add(1, Ordering::SeqCst);
+        }
+    }
+
+    #[test]
+    fn GIVEN_registered_hooks_WHEN_called_THEN_hooks_see_events() {
+        // GIVEN
+        let hooks = CountingHooks::default();
+        let (reloads, failed_reloads, errors) = (
+            hooks.reloads.clone(),
+            hooks.failed_reloads.clone(),
+            hooks.errors.clone(),
+        );
+        register_server_hooks(hooks);
+
+        // WHEN
+        call_reload_hooks(DATA_DIR, true, &Ok(()));
+        call_reload_hooks(
+            DATA_DIR,
+            false,
+            &Err(RdapServerError::Config("bad data".to_string())),
+        );
+        call_hooks(|hooks| hooks.on_error(&RdapServerError::Config(DATA_DIR.to_string())));
+
+        // THEN
+        assert_eq!(reloads.load(Ordering::SeqCst), 2);
+        assert_eq!(failed_reloads.load(Ordering::SeqCst), 1);
+        assert_eq!(errors.load(Ordering::SeqCst), 1);
+    }
+}
@@ -1,7 +1,9 @@
 pub mod bootstrap;
 pub mod config;
 pub mod error;
+pub mod hooks;
 pub mod rdap;
 pub mod server;
 pub mod storage;
 pub mod util;
+pub mod whois;
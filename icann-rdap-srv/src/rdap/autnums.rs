@@ -0,0 +1,42 @@
+use axum::{
+    extract::{Query, State},
+    response::Response,
+};
+
+use serde::Deserialize;
+
+use crate::{error::RdapServerError, rdap::response::ResponseUtil, server::DynServiceState};
+
+use super::{paging::Paging, response::BAD_REQUEST};
+
+#[derive(Debug, Deserialize)]
+pub(crate) struct AutnumsParams {
+    name: Option<String>,
+
+    handle: Option<String>,
+}
+
+/// Searches for autnums (RFC 9910).
+#[axum_macros::debug_handler]
+#[tracing::instrument(level = "debug")]
+pub(crate) async fn autnums(
+    Query(params): Query<AutnumsParams>,
+    paging: Paging,
+    state: State<DynServiceState>,
+) -> Result<Response, RdapServerError> {
+    Ok(if let Some(name) = params.name {
+        let storage = state.get_storage().await?;
+        let results = storage.search_autnums_by_name(&name).await?;
+        paging
+            .page(results, state.get_search_page_size())
+            .response()
+    } else if let Some(handle) = params.handle {
+        let storage = state.get_storage().await?;
+        let results = storage.search_autnums_by_handle(&handle).await?;
+        paging
+            .page(results, state.get_search_page_size())
+            .response()
+    } else {
+        BAD_REQUEST.response()
+    })
+}
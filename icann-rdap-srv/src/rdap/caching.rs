@@ -0,0 +1,161 @@
+//! Validators for the caching of responses, and HEAD requests.
+//!
+//! Successful RDAP responses are given a weak entity tag derived from their JSON,
+//! weak because the bytes of the representation vary with the content coding.
+//! A request with an If-None-Match header matching the tag is answered with a 304.
+
+use {
+    axum::{
+        body::{to_bytes, Body, HttpBody},
+        extract::Request,
+        middleware::Next,
+        response::{IntoResponse, Response},
+    },
+    http::{
+        header::{
+            CACHE_CONTROL, CONTENT_LENGTH, CONTENT_LOCATION, CONTENT_TYPE, ETAG, EXPIRES,
+            IF_NONE_MATCH, LAST_MODIFIED, VARY,
+        },
+        HeaderMap, HeaderValue, Method, StatusCode,
+    },
+    icann_rdap_common::media_types::is_rdap_media_type,
+    sha2::{Digest, Sha256},
+    tracing::warn,
+};
+
+/// The headers of a 200 response that are also sent with a 304 response (RFC 9110 section 15.4.5).
+const NOT_MODIFIED_HEADERS: [http::HeaderName; 6] = [
+    CACHE_CONTROL,
+    CONTENT_LOCATION,
+    ETAG,
+    EXPIRES,
+    LAST_MODIFIED,
+    VARY,
+];
+
+/// Gets the weak entity tag of the JSON of a response.
+pub fn etag(json: &[u8]) -> String {
+    let digest = Sha256::digest(json);
+    let hex = digest[..16]
+        .iter()
+        .map(|b| format!("{b:02x}"))
+        .collect::<String>();
+    format!("W/\"{hex}\"")
+}
+
+/// Determines if an entity tag matches the value of an If-None-Match header,
+/// using the weak comparison of RFC 9110.
+pub fn etag_matches(if_none_match: &str, etag: &str) -> bool {
+    let opaque = |tag: &str| tag.trim().trim_start_matches("W/").to_string();
+    let etag = opaque(etag);
+    if_none_match
+        .split(',')
+        .any(|tag| tag.trim() == "*" || opaque(tag) == etag)
+}
+
+/// Middleware that gives successful RDAP responses an entity tag, and answers
+/// requests with a matching If-None-Match header with a 304.
+pub(crate) async fn conditional_get(request: Request, next: Next) -> Response {
+    if request.method() != Method::GET {
+        return next.run(request).await;
+    }
+    let if_none_match = request
+        .headers()
+        .get(IF_NONE_MATCH)
+        .and_then(|value| value.to_str().ok())
+        .map(str::to_string);
+    let response = next.run(request).await;
+    let is_rdap = response
+        .headers()
+        .get(CONTENT_TYPE)
+        .and_then(|value| value.to_str().ok())
+        .is_some_and(is_rdap_media_type);
+    if response.status() != StatusCode::OK || !is_rdap {
+        return response;
+    }
+
+    let (mut parts, body) = response.into_parts();
+    let bytes = match to_bytes(body, usize::MAX).await {
+        Ok(bytes) => bytes,
+        Err(e) => {
+            warn!("unable to read response body for entity tag: {e}");
+            Default::default()
+        }
+    };
+    let etag = etag(&bytes);
+    parts.headers.insert(
+        ETAG,
+        HeaderValue::from_str(&etag).expect("entity tag header"),
+    );
+    if if_none_match.is_some_and(|if_none_match| etag_matches(&if_none_match, &etag)) {
+        let mut headers = HeaderMap::new();
+        for name in NOT_MODIFIED_HEADERS {
+            for value in parts.headers.get_all(&name) {
+                headers.append(name.clone(), value.clone());
+            }
+        }
+        return (StatusCode::NOT_MODIFIED, headers).into_response();
+    }
+    Response::from_parts(parts, Body::from(bytes))
+}
+
+/// Middleware that answers a HEAD request with the headers of the response to a
+/// GET request, so that the headers of both are the same.
+pub(crate) async fn serve_head(mut request: Request, next: Next) -> Response {
+    if request.method() != Method::HEAD {
+        return next.run(request).await;
+    }
+    *request.method_mut() = Method::GET;
+    let response = next.run(request).await;
+    let (mut parts, body) = response.into_parts();
+    if let Some(size) = body.size_hint().exact() {
+        parts
+            .headers
+            .insert(CONTENT_LENGTH, HeaderValue::from(size));
+    }
+    Response::from_parts(parts, Body::empty())
+}
+
+#[cfg(test)]
+#[allow(non_snake_case)]
+mod tests {
+    use rstest::rstest;
+
+    use super::{etag, etag_matches};
+
+    #[test]
+    fn GIVEN_json_WHEN_etag_THEN_weak_tag_of_json() {
+        // GIVEN
+        let json = br#"{"objectClassName":"domain","ldhName":"foo.example"}"#;
+
+        // WHEN
+        let actual = etag(json);
+
+        // THEN
+        assert!(actual.starts_with("W/\""));
+        assert_eq!(actual.len(), 3 + 32 + 1);
+        assert_eq!(actual, etag(json));
+        assert_ne!(actual, etag(br#"{"objectClassName":"domain"}"#));
+    }
+
+    #[rstest]
+    #[case(r#"W/"abc""#, true)]
+    #[case(r#""abc""#, true)]
+    #[case(r#""xyz", W/"abc""#, true)]
+    #[case("*", true)]
+    #[case(r#""xyz""#, false)]
+    #[case("", false)]
+    fn GIVEN_if_none_match_WHEN_etag_matches_THEN_weak_comparison(
+        #[case] if_none_match: &str,
+        #[case] expected: bool,
+    ) {
+        // GIVEN
+        let etag = r#"W/"abc""#;
+
+        // WHEN
+        let actual = etag_matches(if_none_match, etag);
+
+        // THEN
+        assert_eq!(actual, expected);
+    }
+}
@@ -0,0 +1,383 @@
+//! Compression of response bodies, negotiated with the Accept-Encoding header.
+
+use std::{
+    io::Write,
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        Arc,
+    },
+};
+
+use {
+    axum::{
+        body::{to_bytes, Body, HttpBody},
+        extract::{Request, State},
+        middleware::Next,
+        response::Response,
+    },
+    buildstructor::Builder,
+    flate2::write::GzEncoder,
+    http::{
+        header::{ACCEPT_ENCODING, CONTENT_ENCODING, CONTENT_LENGTH, CONTENT_TYPE, VARY},
+        HeaderValue, StatusCode,
+    },
+    strum_macros::Display,
+    tracing::{debug, warn},
+};
+
+use crate::server::DynServiceState;
+
+/// The default size, in bytes, below which response bodies are not compressed.
+pub const DEFAULT_MIN_SIZE: usize = 1024;
+
+/// The default media types of the responses that are compressed, separated by commas.
+pub const DEFAULT_CONTENT_TYPES: &str = "application/rdap+json,application/json";
+
+/// The configuration of response compression.
+#[derive(Debug, Clone, Builder)]
+pub struct CompressionConfig {
+    /// If false, responses are never compressed.
+    pub enable: bool,
+
+    /// Response bodies smaller than this many bytes are not compressed.
+    pub min_size: usize,
+
+    /// The media types, without parameters, of the responses that are compressed.
+    pub content_types: Vec<String>,
+}
+
+impl Default for CompressionConfig {
+    fn default() -> Self {
+        Self {
+            enable: true,
+            min_size: DEFAULT_MIN_SIZE,
+            content_types: Self::parse_content_types(DEFAULT_CONTENT_TYPES),
+        }
+    }
+}
+
+impl CompressionConfig {
+    /// Parses a comma separated list of media types.
+    pub fn parse_content_types(content_types: &str) -> Vec<String> {
+        content_types
+            .split(',')
+            .map(str::trim)
+            .filter(|t| !t.is_empty())
+            .map(str::to_string)
+            .collect()
+    }
+
+    fn is_compressible(&self, content_type: &str) -> bool {
+        let essence = content_type.split(';').next().unwrap_or_default().trim();
+        self.content_types
+            .iter()
+            .any(|t| t.eq_ignore_ascii_case(essence))
+    }
+}
+
+/// Metrics of the responses that have been compressed.
+#[derive(Debug, Default)]
+pub struct CompressionMetrics {
+    responses: AtomicU64,
+    bytes_in: AtomicU64,
+    bytes_out: AtomicU64,
+}
+
+impl CompressionMetrics {
+    fn record(&self, bytes_in: usize, bytes_out: usize) {
+        self.responses.fetch_add(1, Ordering::Relaxed);
+        self.bytes_in.fetch_add(bytes_in as u64, Ordering::Relaxed);
+        self.bytes_out
+            .fetch_add(bytes_out as u64, Ordering::Relaxed);
+    }
+
+    /// The number of responses compressed.
+    pub fn responses(&self) -> u64 {
+        self.responses.load(Ordering::Relaxed)
+    }
+
+    /// The number of bytes of the compressed responses before compression.
+    pub fn bytes_in(&self) -> u64 {
+        self.bytes_in.load(Ordering::Relaxed)
+    }
+
+    /// The number of bytes of the compressed responses after compression.
+    pub fn bytes_out(&self) -> u64 {
+        self.bytes_out.load(Ordering::Relaxed)
+    }
+
+    /// The number of bytes saved by compression.
+    pub fn bytes_saved(&self) -> u64 {
+        self.bytes_in().saturating_sub(self.bytes_out())
+    }
+}
+
+/// Response compression as configured, along with its metrics.
+///
+/// Clones share the same metrics.
+#[derive(Debug, Clone, Default)]
+pub struct Compression {
+    pub config: CompressionConfig,
+    metrics: Arc<CompressionMetrics>,
+}
+
+impl Compression {
+    pub fn new(config: CompressionConfig) -> Self {
+        Self {
+            config,
+            metrics: Arc::default(),
+        }
+    }
+
+    /// Gets the metrics of the responses that have been compressed.
+    pub fn metrics(&self) -> &CompressionMetrics {
+        &self.metrics
+    }
+}
+
+/// The content codings supported, in order of preference.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Display)]
+#[strum(serialize_all = "lowercase")]
+pub enum ContentCoding {
+    Br,
+    Gzip,
+}
+
+impl ContentCoding {
+    /// Negotiates the content coding from the value of an Accept-Encoding header.
+    ///
+    /// The coding with the highest quality value is chosen, with ties going to the
+    /// coding preferred by the server. None is returned if no coding is acceptable.
+    pub fn negotiate(accept_encoding: &str) -> Option<Self> {
+        let mut chosen: Option<(Self, f32)> = None;
+        for coding in [Self::Br, Self::Gzip] {
+            let q = quality(accept_encoding, &coding.to_string());
+            if q > 0.0 && chosen.is_none_or(|(_, chosen_q)| q > chosen_q) {
+                chosen = Some((coding, q));
+            }
+        }
+        chosen.map(|(coding, _)| coding)
+    }
+
+    fn encode(self, bytes: &[u8]) -> std::io::Result<Vec<u8>> {
+        match self {
+            Self::Br => {
+                let mut compressed = vec![];
+                {
+                    // a middling quality keeps the cost of compressing dynamic responses down.
+                    let mut writer = brotli::CompressorWriter::new(&mut compressed, 4096, 5, 22);
+                    writer.write_all(bytes)?;
+                }
+                Ok(compressed)
+            }
+            Self::Gzip => {
+                let mut encoder = GzEncoder::new(vec![], flate2::Compression::default());
+                encoder.write_all(bytes)?;
+                encoder.finish()
+            }
+        }
+    }
+}
+
+/// Gets the quality value given to a coding in an Accept-Encoding header, which is
+/// 0 if the coding is not acceptable.
+fn quality(accept_encoding: &str, coding: &str) -> f32 {
+    let mut wildcard = None;
+    for entry in accept_encoding.split(',') {
+        let mut params = entry.split(';');
+        let name = params.next().unwrap_or_default().trim();
+        let q = params
+            .find_map(|param| {
+                let (key, value) = param.split_once('=')?;
+                key.trim()
+                    .eq_ignore_ascii_case("q")
+                    .then(|| value.trim().parse::<f32>().ok())?
+            })
+            .unwrap_or(1.0);
+        if name.eq_ignore_ascii_case(coding) {
+            return q;
+        } else if name == "*" {
+            wildcard = Some(q);
+        }
+    }
+    wildcard.unwrap_or(0.0)
+}
+
+/// Middleware that compresses the bodies of responses with a content coding accepted by the client.
+///
+/// Only responses of the configured media types and at least the configured size are compressed.
+pub(crate) async fn compress_response(
+    State(state): State<DynServiceState>,
+    request: Request,
+    next: Next,
+) -> Response {
+    let compression = state.get_compression();
+    if !compression.config.enable {
+        return next.run(request).await;
+    }
+    let coding = request
+        .headers()
+        .get(ACCEPT_ENCODING)
+        .and_then(|value| value.to_str().ok())
+        .and_then(ContentCoding::negotiate);
+    let mut response = next.run(request).await;
+    if response.status() == StatusCode::NOT_MODIFIED {
+        // a 304 has the Vary of the response it stands for.
+        response
+            .headers_mut()
+            .append(VARY, HeaderValue::from_static("accept-encoding"));
+        return response;
+    }
+    let compressible = response
+        .headers()
+        .get(CONTENT_TYPE)
+        .and_then(|value| value.to_str().ok())
+        .is_some_and(|content_type| compression.config.is_compressible(content_type));
+    if !compressible || response.headers().contains_key(CONTENT_ENCODING) {
+        return response;
+    }
+    // the response depends on Accept-Encoding even when it is not compressed, which caches must know.
+    response
+        .headers_mut()
+        .append(VARY, HeaderValue::from_static("accept-encoding"));
+    let Some(coding) = coding else {
+        return response;
+    };
+    if response
+        .body()
+        .size_hint()
+        .upper()
+        .is_some_and(|size| size < compression.config.min_size as u64)
+    {
+        return response;
+    }
+
+    let (mut parts, body) = response.into_parts();
+    let bytes = match to_bytes(body, usize::MAX).await {
+        Ok(bytes) => bytes,
+        Err(e) => {
+            warn!("unable to read response body for compression: {e}");
+            Default::default()
+        }
+    };
+    if bytes.len() < compression.config.min_size {
+        return Response::from_parts(parts, Body::from(bytes));
+    }
+    let compressed = match coding.encode(&bytes) {
+        Ok(compressed) if compressed.len() < bytes.len() => compressed,
+        Ok(_) => return Response::from_parts(parts, Body::from(bytes)),
+        Err(e) => {
+            warn!("unable to compress response body with {coding}: {e}");
+            return Response::from_parts(parts, Body::from(bytes));
+        }
+    };
+    let metrics = compression.metrics();
+    metrics.record(bytes.len(), compressed.len());
+    debug!(
+        "compressed response with {coding} from {} to {} bytes ({} bytes saved by {} responses)",
+        bytes.len(),
+        compressed.len(),
+        metrics.bytes_saved(),
+        metrics.responses()
+    );
+    parts.headers.insert(
+        CONTENT_ENCODING,
+        HeaderValue::from_str(&coding.to_string()).expect("content coding"),
+    );
+    parts.headers.remove(CONTENT_LENGTH);
+    Response::from_parts(parts, Body::from(compressed))
+}
+
+#[cfg(test)]
+#[allow(non_snake_case)]
+mod tests {
+    use rstest::rstest;
+
+    use super::{CompressionConfig, CompressionMetrics, ContentCoding};
+
+    #[rstest]
+    #[case("gzip", Some(ContentCoding::Gzip))]
+    #[case("gzip, deflate, br", Some(ContentCoding::Br))]
+    #[case("br;q=0.5, gzip", Some(ContentCoding::Gzip))]
+    #[case("GZIP;Q=0.8, br;q=0.8", Some(ContentCoding::Br))]
+    #[case("br;q=0, gzip;q=0", None)]
+    #[case("*", Some(ContentCoding::Br))]
+    #[case("*;q=0.1, gzip;q=0.2", Some(ContentCoding::Gzip))]
+    #[case("br;q=0, *", Some(ContentCoding::Gzip))]
+    #[case("identity", None)]
+    #[case("deflate", None)]
+    #[case("", None)]
+    fn GIVEN_accept_encoding_WHEN_negotiate_THEN_coding_chosen(
+        #[case] accept_encoding: &str,
+        #[case] expected: Option<ContentCoding>,
+    ) {
+        // GIVEN accept_encoding
+
+        // WHEN
+        let actual = ContentCoding::negotiate(accept_encoding);
+
+        // THEN
+        assert_eq!(actual, expected);
+    }
+
+    #[rstest]
+    #[case("application/rdap+json", true)]
+    #[case("application/json; charset=utf-8", true)]
+    #[case("Application/RDAP+JSON", true)]
+    #[case("text/html", false)]
+    fn GIVEN_default_config_WHEN_is_compressible_THEN_media_type_essence_matched(
+        #[case] content_type: &str,
+        #[case] expected: bool,
+    ) {
+        // GIVEN
+        let config = CompressionConfig::default();
+
+        // WHEN
+        let actual = config.is_compressible(content_type);
+
+        // THEN
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn GIVEN_content_types_WHEN_parse_content_types_THEN_trimmed_and_empty_removed() {
+        // GIVEN
+        let content_types = " application/rdap+json, ,text/plain ";
+
+        // WHEN
+        let actual = CompressionConfig::parse_content_types(content_types);
+
+        // THEN
+        assert_eq!(actual, vec!["application/rdap+json", "text/plain"]);
+    }
+
+    #[rstest]
+    #[case(ContentCoding::Br)]
+    #[case(ContentCoding::Gzip)]
+    fn GIVEN_repetitive_body_WHEN_encode_THEN_smaller(#[case] coding: ContentCoding) {
+        // GIVEN
+        let body = r#"{"objectClassName":"domain"}"#.repeat(100);
+
+        // WHEN
+        let actual = coding.encode(body.as_bytes()).expect("compressing");
+
+        // THEN
+        assert!(actual.len() < body.len());
+    }
+
+    #[test]
+    fn GIVEN_metrics_WHEN_record_THEN_bytes_saved_totaled() {
+        // GIVEN
+        let metrics = CompressionMetrics::default();
+
+        // WHEN
+        metrics.record(1000, 200);
+        metrics.record(500, 100);
+
+        // THEN
+        assert_eq!(metrics.responses(), 2);
+        assert_eq!(metrics.bytes_in(), 1500);
+        assert_eq!(metrics.bytes_out(), 300);
+        assert_eq!(metrics.bytes_saved(), 1200);
+    }
+}
@@ -3,11 +3,14 @@ use axum::{
     response::Response,
 };
 
-use serde::Deserialize;
+use {icann_rdap_common::response::ReverseSearchProperty, serde::Deserialize};
 
 use crate::{error::RdapServerError, rdap::response::ResponseUtil, server::DynServiceState};
 
-use super::response::NOT_IMPLEMENTED;
+use super::{
+    paging::Paging,
+    response::{BAD_REQUEST, NOT_IMPLEMENTED},
+};
 
 #[derive(Debug, Deserialize)]
 pub(crate) struct DomainsParams {
@@ -24,13 +27,54 @@ pub(crate) struct DomainsParams {
 #[tracing::instrument(level = "debug")]
 pub(crate) async fn domains(
     Query(params): Query<DomainsParams>,
+    paging: Paging,
     state: State<DynServiceState>,
 ) -> Result<Response, RdapServerError> {
     Ok(if let Some(name) = params.name {
         let storage = state.get_storage().await?;
         let results = storage.search_domains_by_name(&name).await?;
-        results.response()
+        paging
+            .page(results, state.get_search_page_size())
+            .response()
     } else {
         NOT_IMPLEMENTED.response()
     })
 }
+
+#[derive(Debug, Deserialize)]
+pub(crate) struct ReverseSearchParams {
+    #[serde(rename = "fn")]
+    full_name: Option<String>,
+
+    handle: Option<String>,
+
+    email: Option<String>,
+
+    role: Option<String>,
+}
+
+#[axum_macros::debug_handler]
+#[tracing::instrument(level = "debug")]
+pub(crate) async fn domains_reverse_search(
+    Query(params): Query<ReverseSearchParams>,
+    paging: Paging,
+    state: State<DynServiceState>,
+) -> Result<Response, RdapServerError> {
+    let search = [
+        (ReverseSearchProperty::Fn, params.full_name),
+        (ReverseSearchProperty::Handle, params.handle),
+        (ReverseSearchProperty::Email, params.email),
+        (ReverseSearchProperty::Role, params.role),
+    ]
+    .into_iter()
+    .find_map(|(property, value)| value.map(|value| (property, value)));
+    Ok(if let Some((property, value)) = search {
+        let storage = state.get_storage().await?;
+        let results = storage.search_domains_by_entity(property, &value).await?;
+        paging
+            .page(results, state.get_search_page_size())
+            .response()
+    } else {
+        BAD_REQUEST.response()
+    })
+}
@@ -0,0 +1,210 @@
+//! Customization of the RFC 9083 error bodies of error responses.
+
+use std::path::Path;
+
+use {
+    axum::{
+        body::{to_bytes, Body},
+        extract::{Request, State},
+        middleware::Next,
+        response::Response,
+    },
+    axum_extra::typed_header::TypedHeader,
+    headers::Host,
+    http::header::{CONTENT_LENGTH, CONTENT_TYPE},
+    icann_rdap_common::{
+        media_types::RDAP_MEDIA_TYPE,
+        response::{Notice, RdapResponse, Rfc9083Error},
+    },
+    serde::{Deserialize, Serialize},
+    tracing::warn,
+};
+
+use crate::{error::RdapServerError, server::DynServiceState};
+
+/// The largest error body that is customized. Larger bodies are passed through.
+const MAX_ERROR_BODY: usize = 64 * 1024;
+
+/// The customization of the error body for an HTTP status code.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(deny_unknown_fields)]
+pub struct ErrorBody {
+    /// The host the customization applies to. If None, it applies to all hosts.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub host: Option<String>,
+
+    /// The HTTP status code the customization applies to.
+    pub status: u16,
+
+    /// If present, replaces the title of the error.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub title: Option<String>,
+
+    /// If present, replaces the description of the error.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub description: Option<Vec<String>>,
+
+    /// If present, replaces the notices of the error.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub notices: Option<Vec<Notice>>,
+}
+
+impl ErrorBody {
+    /// Applies the customization to an error.
+    pub fn apply(&self, mut rdap_error: Rfc9083Error) -> Rfc9083Error {
+        if let Some(title) = &self.title {
+            rdap_error.title = Some(title.clone());
+        }
+        if let Some(description) = &self.description {
+            rdap_error.description = Some(description.clone());
+        }
+        if let Some(notices) = &self.notices {
+            rdap_error.common.notices = (!notices.is_empty()).then(|| notices.clone());
+        }
+        rdap_error
+    }
+}
+
+/// The customizations of error bodies, such as for 404, 429 and 500 responses.
+///
+/// These are read from a JSON array of [ErrorBody]:
+/// ```json
+/// [
+///   { "status": 404, "title": "Not Found", "description": [ "The object does not exist." ] },
+///   { "host": "rdap.example.com", "status": 500, "title": "Try Again Later" }
+/// ]
+/// ```
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(transparent)]
+pub struct ErrorBodies(pub Vec<ErrorBody>);
+
+impl ErrorBodies {
+    /// Reads error body customizations from a JSON file.
+    pub fn from_file(path: &Path) -> Result<Self, RdapServerError> {
+        let json = std::fs::read_to_string(path)?;
+        serde_json::from_str(&json).map_err(|e| {
+            RdapServerError::Config(format!("error bodies in {}: {e}", path.display()))
+        })
+    }
+
+    /// Finds the customization for a host and status code. Customizations
+    /// for the host are preferred over those for all hosts.
+    pub fn find(&self, host: Option<&str>, status: u16) -> Option<&ErrorBody> {
+        let for_status = || self.0.iter().filter(move |b| b.status == status);
+        host.and_then(|host| {
+            for_status().find(|b| {
+                b.host
+                    .as_deref()
+                    .is_some_and(|h| h.eq_ignore_ascii_case(host))
+            })
+        })
+        .or_else(|| for_status().find(|b| b.host.is_none()))
+    }
+}
+
+/// Middleware that replaces the bodies of error responses with customized RFC 9083 errors.
+///
+/// Responses are only changed if there is a customization for their host and status code.
+pub(crate) async fn customize_error_body(
+    State(state): State<DynServiceState>,
+    host: Option<TypedHeader<Host>>,
+    request: Request,
+    next: Next,
+) -> Response {
+    let response = next.run(request).await;
+    let status = response.status();
+    if !status.is_client_error() && !status.is_server_error() {
+        return response;
+    }
+    let host_name = host.as_ref().map(|h| h.hostname());
+    let Some(error_body) = state.get_error_bodies().find(host_name, status.as_u16()) else {
+        return response;
+    };
+
+    let (mut parts, body) = response.into_parts();
+    let bytes = match to_bytes(body, MAX_ERROR_BODY).await {
+        Ok(bytes) => bytes,
+        Err(e) => {
+            warn!("unable to read error body for customization: {e}");
+            Default::default()
+        }
+    };
+    // start from the error generated by the server, if there is one
+    let rdap_error = match serde_json::from_slice::<RdapResponse>(&bytes) {
+        Ok(RdapResponse::ErrorResponse(rdap_error)) => *rdap_error,
+        _ => Rfc9083Error::response_obj()
+            .error_code(status.as_u16())
+            .build(),
+    };
+    let rdap_error = error_body.apply(rdap_error);
+    let json = serde_json::to_vec(&rdap_error).expect("serializing RDAP error");
+    parts
+        .headers
+        .insert(CONTENT_TYPE, RDAP_MEDIA_TYPE.parse().expect("media type"));
+    parts.headers.remove(CONTENT_LENGTH);
+    Response::from_parts(parts, Body::from(json))
+}
+
+#[cfg(test)]
+#[allow(non_snake_case)]
+mod tests {
+    use icann_rdap_common::response::Rfc9083Error;
+
+    use super::ErrorBodies;
+
+    fn error_bodies() -> ErrorBodies {
+        serde_json::from_str(
+            r#"[
+                { "status": 404, "title": "Not Found" },
+                { "host": "rdap.example.com", "status": 404, "title": "Not Here", "notices": [] },
+                { "host": "rdap.example.com", "status": 500, "description": [ "Try again later." ] }
+            ]"#,
+        )
+        .expect("parsing error bodies")
+    }
+
+    #[test]
+    fn GIVEN_error_bodies_WHEN_find_for_host_THEN_host_preferred() {
+        // GIVEN
+        let bodies = error_bodies();
+
+        // WHEN
+        let actual = bodies.find(Some("RDAP.example.com"), 404);
+
+        // THEN
+        assert_eq!(actual.expect("body").title.as_deref(), Some("Not Here"));
+    }
+
+    #[test]
+    fn GIVEN_error_bodies_WHEN_find_for_other_host_THEN_all_hosts_used() {
+        // GIVEN
+        let bodies = error_bodies();
+
+        // WHEN
+        let not_found = bodies.find(Some("other.example.com"), 404);
+        let server_error = bodies.find(Some("other.example.com"), 500);
+
+        // THEN
+        assert_eq!(not_found.expect("body").title.as_deref(), Some("Not Found"));
+        assert!(server_error.is_none());
+    }
+
+    #[test]
+    fn GIVEN_error_body_WHEN_apply_THEN_only_given_fields_replaced() {
+        // GIVEN
+        let bodies = error_bodies();
+        let body = bodies.find(Some("rdap.example.com"), 500).expect("body");
+        let rdap_error = Rfc9083Error::response_obj().error_code(500).build();
+
+        // WHEN
+        let actual = body.apply(rdap_error);
+
+        // THEN
+        assert_eq!(actual.error_code, 500);
+        assert!(actual.title.is_none());
+        assert_eq!(
+            actual.description,
+            Some(vec!["Try again later.".to_string()])
+        );
+    }
+}
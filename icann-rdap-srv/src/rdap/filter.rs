@@ -0,0 +1,61 @@
+//! Filtering of the members of RDAP responses, such as for privacy policies.
+
+use std::path::Path;
+
+use {
+    axum::{
+        body::{to_bytes, Body},
+        extract::{Request, State},
+        middleware::Next,
+        response::Response,
+    },
+    http::header::{CONTENT_LENGTH, CONTENT_TYPE},
+    icann_rdap_common::{media_types::RDAP_MEDIA_TYPE, response::filter::ResponseFilter},
+    serde_json::Value,
+    tracing::warn,
+};
+
+use crate::{error::RdapServerError, server::DynServiceState};
+
+/// Reads a response filter from a JSON file.
+pub fn response_filter_from_file(path: &Path) -> Result<ResponseFilter, RdapServerError> {
+    let json = std::fs::read_to_string(path)?;
+    ResponseFilter::from_json(&json)
+        .map_err(|e| RdapServerError::Config(format!("response filter in {}: {e}", path.display())))
+}
+
+/// Middleware that applies the response filter, if there is one, to successful RDAP responses.
+pub(crate) async fn filter_rdap(
+    State(state): State<DynServiceState>,
+    request: Request,
+    next: Next,
+) -> Response {
+    let Some(filter) = state.get_response_filter() else {
+        return next.run(request).await;
+    };
+    let response = next.run(request).await;
+    let is_rdap = response
+        .headers()
+        .get(CONTENT_TYPE)
+        .and_then(|value| value.to_str().ok())
+        .is_some_and(|content_type| content_type.starts_with(RDAP_MEDIA_TYPE));
+    if !response.status().is_success() || !is_rdap {
+        return response;
+    }
+
+    let (mut parts, body) = response.into_parts();
+    let bytes = match to_bytes(body, usize::MAX).await {
+        Ok(bytes) => bytes,
+        Err(e) => {
+            warn!("unable to read response body for filtering: {e}");
+            Default::default()
+        }
+    };
+    let Ok(mut json) = serde_json::from_slice::<Value>(&bytes) else {
+        return Response::from_parts(parts, Body::from(bytes));
+    };
+    filter.apply_value(&mut json);
+    let json = serde_json::to_vec(&json).expect("serializing RDAP");
+    parts.headers.remove(CONTENT_LENGTH);
+    Response::from_parts(parts, Body::from(json))
+}
@@ -0,0 +1,80 @@
+use std::{net::IpAddr, str::FromStr};
+
+use {
+    axum::{
+        extract::{Path, Query, State},
+        response::Response,
+    },
+    cidr::{IpCidr, IpInet},
+    icann_rdap_common::response::RirSearchRelation,
+    serde::Deserialize,
+    tracing::debug,
+};
+
+use crate::{error::RdapServerError, rdap::response::ResponseUtil, server::DynServiceState};
+
+use super::{paging::Paging, response::BAD_REQUEST};
+
+#[derive(Debug, Deserialize)]
+pub(crate) struct IpsParams {
+    name: Option<String>,
+
+    handle: Option<String>,
+}
+
+/// Searches for IP networks (RFC 9910).
+#[axum_macros::debug_handler]
+#[tracing::instrument(level = "debug")]
+pub(crate) async fn ips(
+    Query(params): Query<IpsParams>,
+    paging: Paging,
+    state: State<DynServiceState>,
+) -> Result<Response, RdapServerError> {
+    Ok(if let Some(name) = params.name {
+        let storage = state.get_storage().await?;
+        let results = storage.search_networks_by_name(&name).await?;
+        paging
+            .page(results, state.get_search_page_size())
+            .response()
+    } else if let Some(handle) = params.handle {
+        let storage = state.get_storage().await?;
+        let results = storage.search_networks_by_handle(&handle).await?;
+        paging
+            .page(results, state.get_search_page_size())
+            .response()
+    } else {
+        BAD_REQUEST.response()
+    })
+}
+
+/// Searches for the IP networks related to an IP address or CIDR (RFC 9910).
+#[axum_macros::debug_handler]
+#[tracing::instrument(level = "debug")]
+pub(crate) async fn ips_by_relation(
+    Path((relation, netid)): Path<(String, String)>,
+    paging: Paging,
+    state: State<DynServiceState>,
+) -> Result<Response, RdapServerError> {
+    let Ok(relation) = RirSearchRelation::from_str(&relation) else {
+        return Ok(BAD_REQUEST.response());
+    };
+    let cidr = if netid.contains('/') {
+        IpInet::from_str(&netid).ok().map(|inet| inet.network())
+    } else {
+        IpAddr::from_str(&netid).ok().map(IpCidr::new_host)
+    };
+    let Some(cidr) = cidr else {
+        return Ok(BAD_REQUEST.response());
+    };
+    debug!("searching for {relation} networks of {cidr}");
+    let storage = state.get_storage().await?;
+    let results = storage
+        .search_networks_by_relation(
+            relation,
+            &format!("{}/{}", cidr.first_address(), cidr.network_length()),
+        )
+        .await?;
+    Ok(paging
+        .page(results, state.get_search_page_size())
+        .response())
+}
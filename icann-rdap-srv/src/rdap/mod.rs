@@ -4,14 +4,27 @@ use icann_rdap_common::{
 };
 
 pub mod autnum;
+pub mod autnums;
+pub mod caching;
+pub mod compression;
 pub mod domain;
 pub mod domains;
+pub mod entities;
 pub mod entity;
+pub mod error_body;
+pub mod filter;
 pub mod ip;
+pub mod ips;
 pub mod nameserver;
+pub mod nameservers;
+pub mod paging;
+pub mod proxy;
+pub mod rate_limit;
+pub mod rdap_x;
 pub mod response;
 pub mod router;
 pub mod srvhelp;
+pub mod validation;
 
 trait ToBootStrap {
     fn to_ip_bootstrap(self, ip_id: &str) -> RdapResponse;
@@ -0,0 +1,41 @@
+use axum::{
+    extract::{Query, State},
+    response::Response,
+};
+
+use serde::Deserialize;
+
+use crate::{error::RdapServerError, rdap::response::ResponseUtil, server::DynServiceState};
+
+use super::{paging::Paging, response::BAD_REQUEST};
+
+#[derive(Debug, Deserialize)]
+pub(crate) struct NameserversParams {
+    name: Option<String>,
+
+    ip: Option<String>,
+}
+
+#[axum_macros::debug_handler]
+#[tracing::instrument(level = "debug")]
+pub(crate) async fn nameservers(
+    Query(params): Query<NameserversParams>,
+    paging: Paging,
+    state: State<DynServiceState>,
+) -> Result<Response, RdapServerError> {
+    Ok(if let Some(name) = params.name {
+        let storage = state.get_storage().await?;
+        let results = storage.search_nameservers_by_name(&name).await?;
+        paging
+            .page(results, state.get_search_page_size())
+            .response()
+    } else if let Some(ip) = params.ip {
+        let storage = state.get_storage().await?;
+        let results = storage.search_nameservers_by_ip(&ip).await?;
+        paging
+            .page(results, state.get_search_page_size())
+            .response()
+    } else {
+        BAD_REQUEST.response()
+    })
+}
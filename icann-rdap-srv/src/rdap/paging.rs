@@ -0,0 +1,298 @@
+use {
+    async_trait::async_trait,
+    axum::{
+        extract::{FromRequestParts, OriginalUri},
+        response::Response,
+    },
+    http::{header::HOST, request::Parts, HeaderMap},
+    icann_rdap_common::{
+        media_types::RDAP_MEDIA_TYPE,
+        response::{ExtensionId, Link, PagingMetadata, RdapResponse},
+    },
+};
+
+use crate::rdap::response::{ResponseUtil, BAD_REQUEST};
+
+/// The paging of search results requested by a client (RFC 8977).
+///
+/// The cursor is the offset of the first result in the page. Clients are
+/// expected to treat it as opaque and only use it as given in the `next` link.
+#[derive(Debug)]
+pub(crate) struct Paging {
+    offset: Option<usize>,
+
+    /// The URL of the request without the cursor.
+    url: String,
+}
+
+#[async_trait]
+impl<S: Send + Sync> FromRequestParts<S> for Paging {
+    type Rejection = Response;
+
+    async fn from_request_parts(parts: &mut Parts, _state: &S) -> Result<Self, Self::Rejection> {
+        // nested routers strip the path prefix from the URI of the request.
+        let uri = parts
+            .extensions
+            .get::<OriginalUri>()
+            .map_or(&parts.uri, |original| &original.0);
+        let mut cursor = None;
+        let query = uri
+            .query()
+            .unwrap_or_default()
+            .split('&')
+            .filter(|param| match param.strip_prefix("cursor=") {
+                Some(value) => {
+                    cursor = Some(value);
+                    false
+                }
+                None => !param.is_empty(),
+            })
+            .collect::<Vec<&str>>();
+        let offset = cursor
+            .map(|cursor| cursor.parse::<usize>())
+            .transpose()
+            .map_err(|_| BAD_REQUEST.response())?;
+        let path_and_query = if query.is_empty() {
+            uri.path().to_string()
+        } else {
+            format!("{}?{}", uri.path(), query.join("&"))
+        };
+        let url = match parts.headers.get(HOST).and_then(|h| h.to_str().ok()) {
+            Some(host) => format!("{}://{host}{path_and_query}", scheme(&parts.headers)),
+            None => path_and_query,
+        };
+        Ok(Self { offset, url })
+    }
+}
+
+impl Paging {
+    /// Takes the requested page from search results.
+    ///
+    /// Search results are paged only when a page size is configured and either there
+    /// are more results than fit in a page or a cursor was given. Other responses are
+    /// returned as is.
+    pub(crate) fn page(
+        &self,
+        mut response: RdapResponse,
+        page_size: Option<usize>,
+    ) -> RdapResponse {
+        let Some(page_size) = page_size.filter(|size| *size > 0) else {
+            return response;
+        };
+        let offset = self.offset.unwrap_or_default();
+        let (total, common, paging_metadata) = match &mut response {
+            RdapResponse::DomainSearchResults(s) => (
+                take_page(&mut s.results, offset, page_size),
+                &mut s.common,
+                &mut s.paging_metadata,
+            ),
+            RdapResponse::NameserverSearchResults(s) => (
+                take_page(&mut s.results, offset, page_size),
+                &mut s.common,
+                &mut s.paging_metadata,
+            ),
+            RdapResponse::EntitySearchResults(s) => (
+                take_page(&mut s.results, offset, page_size),
+                &mut s.common,
+                &mut s.paging_metadata,
+            ),
+            RdapResponse::IpSearchResults(s) => (
+                take_page(&mut s.results, offset, page_size),
+                &mut s.common,
+                &mut s.paging_metadata,
+            ),
+            RdapResponse::AutnumSearchResults(s) => (
+                take_page(&mut s.results, offset, page_size),
+                &mut s.common,
+                &mut s.paging_metadata,
+            ),
+            _ => return response,
+        };
+        if self.offset.is_none() && total <= page_size {
+            return response;
+        }
+        let mut links = vec![];
+        let next_offset = offset.saturating_add(page_size);
+        if next_offset < total {
+            let separator = if self.url.contains('?') { '&' } else { '?' };
+            links.push(
+                Link::builder()
+                    .value(&self.url)
+                    .rel("next")
+                    .href(format!("{}{separator}cursor={next_offset}", self.url))
+                    .media_type(RDAP_MEDIA_TYPE)
+                    .title("Result Pagination Link")
+                    .build(),
+            );
+        }
+        *paging_metadata = Some(
+            PagingMetadata::builder()
+                .total_count(total as u64)
+                .page_size(page_size as u64)
+                .page_number((offset / page_size + 1) as u64)
+                .links(links)
+                .build(),
+        );
+        common
+            .rdap_conformance
+            .get_or_insert_with(Vec::new)
+            .push(ExtensionId::Paging.to_extension());
+        response
+    }
+}
+
+/// Gets the scheme of the request, which is that of the `X-Forwarded-Proto` header
+/// set by a TLS terminating proxy, or `http`.
+fn scheme(headers: &HeaderMap) -> &'static str {
+    let forwarded = headers
+        .get("x-forwarded-proto")
+        .and_then(|h| h.to_str().ok())
+        .and_then(|proto| proto.split(',').next())
+        .map(str::trim);
+    match forwarded {
+        Some(proto) if proto.eq_ignore_ascii_case("https") => "https",
+        _ => "http",
+    }
+}
+
+/// Replaces the results with a page of them, returning the total number of results.
+fn take_page<T>(results: &mut Vec<T>, offset: usize, page_size: usize) -> usize {
+    let total = results.len();
+    *results = results.drain(..).skip(offset).take(page_size).collect();
+    total
+}
+
+#[cfg(test)]
+#[allow(non_snake_case)]
+mod tests {
+    use icann_rdap_common::{
+        prelude::ToResponse,
+        response::{Domain, DomainSearchResults, ExtensionId, RdapResponse},
+    };
+
+    use http::HeaderMap;
+
+    use super::{scheme, Paging};
+
+    fn domains(count: usize) -> RdapResponse {
+        DomainSearchResults::response_obj()
+            .results(
+                (0..count)
+                    .map(|i| {
+                        Domain::builder()
+                            .ldh_name(format!("foo{i}.example"))
+                            .build()
+                    })
+                    .collect::<Vec<Domain>>(),
+            )
+            .build()
+            .to_response()
+    }
+
+    fn paging(offset: Option<usize>) -> Paging {
+        Paging {
+            offset,
+            url: "http://example.com/rdap/domains?name=foo*.example".to_string(),
+        }
+    }
+
+    #[test]
+    fn GIVEN_more_results_than_page_size_WHEN_page_THEN_first_page_with_next_link() {
+        // GIVEN
+        let response = domains(5);
+
+        // WHEN
+        let actual = paging(None).page(response, Some(2));
+
+        // THEN
+        assert!(actual.has_extension_id(ExtensionId::Paging));
+        let RdapResponse::DomainSearchResults(ref results) = actual else {
+            panic!("not domain search results")
+        };
+        assert_eq!(results.results().len(), 2);
+        let paging = results.paging_metadata().expect("no paging metadata");
+        assert_eq!(paging.total_count(), Some(5));
+        assert_eq!(paging.page_number(), Some(1));
+        assert_eq!(
+            paging.next_link(),
+            Some("http://example.com/rdap/domains?name=foo*.example&cursor=2")
+        );
+    }
+
+    #[test]
+    fn GIVEN_cursor_for_last_page_WHEN_page_THEN_last_page_without_next_link() {
+        // GIVEN
+        let response = domains(5);
+
+        // WHEN
+        let actual = paging(Some(4)).page(response, Some(2));
+
+        // THEN
+        let RdapResponse::DomainSearchResults(ref results) = actual else {
+            panic!("not domain search results")
+        };
+        assert_eq!(results.results().len(), 1);
+        assert_eq!(
+            results.results()[0].ldh_name.as_deref(),
+            Some("foo4.example")
+        );
+        let paging = results.paging_metadata().expect("no paging metadata");
+        assert_eq!(paging.page_number(), Some(3));
+        assert!(paging.next_link().is_none());
+    }
+
+    #[test]
+    fn GIVEN_cursor_near_max_WHEN_page_THEN_empty_page_without_next_link() {
+        // GIVEN
+        let response = domains(5);
+
+        // WHEN
+        let actual = paging(Some(usize::MAX - 1)).page(response, Some(2));
+
+        // THEN
+        let RdapResponse::DomainSearchResults(ref results) = actual else {
+            panic!("not domain search results")
+        };
+        assert!(results.results().is_empty());
+        let paging = results.paging_metadata().expect("no paging metadata");
+        assert!(paging.next_link().is_none());
+    }
+
+    #[test]
+    fn GIVEN_forwarded_proto_WHEN_scheme_THEN_forwarded_scheme() {
+        // GIVEN
+        let mut headers = HeaderMap::new();
+        headers.insert("x-forwarded-proto", "https, http".parse().unwrap());
+
+        // WHEN
+        let actual = scheme(&headers);
+
+        // THEN
+        assert_eq!(actual, "https");
+        assert_eq!(scheme(&HeaderMap::new()), "http");
+    }
+
+    #[test]
+    fn GIVEN_results_fitting_in_page_WHEN_page_THEN_not_paged() {
+        // GIVEN
+        let response = domains(2);
+
+        // WHEN
+        let actual = paging(None).page(response.clone(), Some(2));
+
+        // THEN
+        assert_eq!(actual, response);
+    }
+
+    #[test]
+    fn GIVEN_no_page_size_WHEN_page_THEN_not_paged() {
+        // GIVEN
+        let response = domains(5);
+
+        // WHEN
+        let actual = paging(None).page(response.clone(), None);
+
+        // THEN
+        assert_eq!(actual, response);
+    }
+}
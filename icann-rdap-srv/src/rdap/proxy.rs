@@ -0,0 +1,363 @@
+//! Proxying of the lookups that cannot be answered locally to the RDAP servers
+//! given for them by bootstrapping.
+
+use std::{
+    collections::HashMap,
+    str::FromStr,
+    sync::Mutex,
+    time::{Duration, Instant},
+};
+
+use {
+    axum::{
+        extract::{Request, State},
+        middleware::Next,
+        response::{IntoResponse, Response},
+        Json,
+    },
+    buildstructor::Builder,
+    http::{Method, StatusCode},
+    icann_rdap_client::{
+        http::{create_client, Client, ClientConfig},
+        iana::{BootstrapStore, MemoryBootstrapStore},
+        rdap::{rdap_bootstrapped_request, QueryType},
+    },
+    icann_rdap_common::{
+        httpdata::HttpData,
+        media_types::RDAP_MEDIA_TYPE,
+        response::{Link, Notice},
+    },
+    pct_str::PctStr,
+    serde_json::Value,
+    tracing::debug,
+};
+
+use crate::{
+    bootstrap::{BootstrapRegistries, BOOTSTRAP_TYPES},
+    error::RdapServerError,
+    rdap::response::RDAP_HEADERS,
+    server::DynServiceState,
+};
+
+/// The number of cached responses above which the expired ones are dropped.
+const MAX_CACHED_RESPONSES: usize = 10_000;
+
+/// The configuration of proxying.
+#[derive(Debug, Clone, Builder)]
+pub struct ProxyConfig {
+    /// How long proxied responses are cached and served again. They are not cached if zero.
+    pub cache_ttl: Duration,
+
+    /// If true, lookups are only proxied to RDAP servers using HTTPS.
+    pub https_only: bool,
+}
+
+#[derive(Debug, Clone)]
+struct CachedResponse {
+    status: StatusCode,
+    json: Value,
+    cached: Instant,
+}
+
+/// Proxies lookups to the RDAP servers given for them by bootstrapping, caching
+/// the responses as configured.
+///
+/// Bootstrapping uses the IANA registries, or the bootstrap registries given to the
+/// server. Proxied responses have a notice saying where they are from.
+pub struct Proxy {
+    config: ProxyConfig,
+    client: Client,
+    store: MemoryBootstrapStore,
+    cache: Mutex<HashMap<String, CachedResponse>>,
+}
+
+impl std::fmt::Debug for Proxy {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Proxy")
+            .field("config", &self.config)
+            .finish()
+    }
+}
+
+impl Proxy {
+    pub fn new(
+        config: ProxyConfig,
+        registries: Option<&BootstrapRegistries>,
+    ) -> Result<Self, RdapServerError> {
+        let client_config = ClientConfig::builder()
+            .user_agent_suffix("icann-rdap-srv")
+            .https_only(config.https_only)
+            .build();
+        let client = create_client(&client_config)?;
+        let store = MemoryBootstrapStore::new();
+        for reg_type in BOOTSTRAP_TYPES {
+            if let Some(registry) = registries.and_then(|registries| registries.get(&reg_type)) {
+                let http_data = HttpData::now().scheme("file").host("localhost").build();
+                store.put_bootstrap_registry(&reg_type, registry.clone(), http_data)?;
+            }
+        }
+        Ok(Self {
+            config,
+            client,
+            store,
+            cache: Mutex::default(),
+        })
+    }
+
+    /// Looks up a query with the RDAP server given for it by bootstrapping, giving
+    /// the status and JSON of the response, which has a notice that it is proxied.
+    pub async fn lookup(
+        &self,
+        path: &str,
+        query: &QueryType,
+    ) -> Result<(StatusCode, Value), RdapServerError> {
+        if let Some(cached) = self.cached(path, Instant::now()) {
+            return Ok((cached.status, cached.json));
+        }
+        let response = rdap_bootstrapped_request(query, &self.client, &self.store, |reg_type| {
+            debug!("fetching IANA registry {} for proxying", reg_type.url())
+        })
+        .await?;
+        let status = StatusCode::from_u16(response.http_data.status_code).unwrap_or(StatusCode::OK);
+        let mut json = serde_json::to_value(&response.rdap)?;
+        if let Some(url) = &response.http_data.request_uri {
+            add_proxy_notice(&mut json, url)?;
+        }
+        if status == StatusCode::OK {
+            self.cache(path, status, &json, Instant::now());
+        }
+        Ok((status, json))
+    }
+
+    fn cached(&self, path: &str, now: Instant) -> Option<CachedResponse> {
+        let cache = self.cache.lock().expect("proxy cache lock poisoned");
+        cache
+            .get(path)
+            .filter(|cached| now.duration_since(cached.cached) < self.config.cache_ttl)
+            .cloned()
+    }
+
+    fn cache(&self, path: &str, status: StatusCode, json: &Value, now: Instant) {
+        if self.config.cache_ttl.is_zero() {
+            return;
+        }
+        let mut cache = self.cache.lock().expect("proxy cache lock poisoned");
+        if cache.len() > MAX_CACHED_RESPONSES {
+            cache.retain(|_, cached| now.duration_since(cached.cached) < self.config.cache_ttl);
+        }
+        cache.insert(
+            path.to_string(),
+            CachedResponse {
+                status,
+                json: json.clone(),
+                cached: now,
+            },
+        );
+    }
+}
+
+/// Appends a notice to a response saying it is from another RDAP server.
+fn add_proxy_notice(json: &mut Value, url: &str) -> Result<(), RdapServerError> {
+    let notice = Notice::builder()
+        .title("Proxied Response")
+        .description_entry(
+            "This response is from another RDAP server, which is authoritative for it.",
+        )
+        .link(
+            Link::builder()
+                .value(url)
+                .href(url)
+                .rel("related")
+                .media_type(RDAP_MEDIA_TYPE)
+                .build(),
+        )
+        .build();
+    let notice = serde_json::to_value(notice)?;
+    if let Value::Object(object) = json {
+        match object.get_mut("notices") {
+            Some(Value::Array(notices)) => notices.push(notice),
+            _ => {
+                object.insert("notices".to_string(), Value::Array(vec![notice]));
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Gets the query of a lookup path, such as `/rdap/domain/example.com`.
+fn lookup_query(path: &str) -> Option<QueryType> {
+    let (class, value) = path.strip_prefix("/rdap/")?.split_once('/')?;
+    let value = PctStr::new(value).ok()?.decode();
+    match class {
+        "domain" => QueryType::domain(&value).ok(),
+        "nameserver" => QueryType::ns(&value).ok(),
+        "autnum" => QueryType::autnum(&value).ok(),
+        "entity" => Some(QueryType::entity(&value)),
+        "ip" => match QueryType::from_str(&value).ok()? {
+            query @ (QueryType::IpV4Addr(_)
+            | QueryType::IpV6Addr(_)
+            | QueryType::IpV4Cidr(_)
+            | QueryType::IpV6Cidr(_)) => Some(query),
+            _ => None,
+        },
+        _ => None,
+    }
+}
+
+/// Middleware proxying the lookups not found in storage, if the server is a proxy.
+///
+/// If a lookup cannot be proxied, the not found response is served.
+pub(crate) async fn proxy_rdap(
+    State(state): State<DynServiceState>,
+    request: Request,
+    next: Next,
+) -> Response {
+    let Some(proxy) = state.get_proxy() else {
+        return next.run(request).await;
+    };
+    let path = request.uri().path().to_string();
+    let query = matches!(*request.method(), Method::GET | Method::HEAD)
+        .then(|| lookup_query(&path))
+        .flatten();
+    let response = next.run(request).await;
+    let Some(query) = query else {
+        return response;
+    };
+    if response.status() != StatusCode::NOT_FOUND {
+        return response;
+    }
+    match proxy.lookup(&path, &query).await {
+        Ok((status, json)) => (status, RDAP_HEADERS, Json(json)).into_response(),
+        Err(e) => {
+            debug!("unable to proxy {path}: {e}");
+            response
+        }
+    }
+}
+
+#[cfg(test)]
+#[allow(non_snake_case)]
+mod tests {
+    use std::time::{Duration, Instant};
+
+    use {
+        http::StatusCode,
+        icann_rdap_client::rdap::QueryType,
+        rstest::rstest,
+        serde_json::{json, Value},
+    };
+
+    use super::{add_proxy_notice, lookup_query, Proxy, ProxyConfig};
+
+    #[rstest]
+    #[case("/rdap/domain/example.com", Some("domain"))]
+    #[case("/rdap/domain/xn--caf-dma.example", Some("domain"))]
+    #[case("/rdap/nameserver/ns1.example.com", Some("nameserver"))]
+    #[case("/rdap/autnum/64496", Some("autnum"))]
+    #[case("/rdap/entity/FOO-EXAMPLE", Some("entity"))]
+    #[case("/rdap/ip/192.0.2.1", Some("ip"))]
+    #[case("/rdap/ip/192.0.2.0/24", Some("ip"))]
+    #[case("/rdap/ip/2001%3Adb8%3A%3A1", Some("ip"))]
+    #[case("/rdap/ip/example.com", None)]
+    #[case("/rdap/domains", None)]
+    #[case("/rdap/help", None)]
+    fn GIVEN_path_WHEN_lookup_query_THEN_query_of_lookup(
+        #[case] path: &str,
+        #[case] expected: Option<&str>,
+    ) {
+        // GIVEN in parameters
+
+        // WHEN
+        let actual = lookup_query(path);
+
+        // THEN
+        let class = actual.map(|query| match query {
+            QueryType::Domain(_) | QueryType::ALabel(_) => "domain",
+            QueryType::Nameserver(_) => "nameserver",
+            QueryType::AsNumber(_) => "autnum",
+            QueryType::Entity(_) | QueryType::TaggedEntity(_) => "entity",
+            QueryType::IpV4Addr(_)
+            | QueryType::IpV6Addr(_)
+            | QueryType::IpV4Cidr(_)
+            | QueryType::IpV6Cidr(_) => "ip",
+            _ => "other",
+        });
+        assert_eq!(class, expected);
+    }
+
+    #[test]
+    fn GIVEN_response_with_notices_WHEN_add_proxy_notice_THEN_notice_appended() {
+        // GIVEN
+        let mut json = json!({
+            "objectClassName": "domain",
+            "ldhName": "example.com",
+            "notices": [{"title": "Terms of Use", "description": ["Be nice."]}]
+        });
+
+        // WHEN
+        add_proxy_notice(&mut json, "https://rdap.example.com/domain/example.com")
+            .expect("adding notice");
+
+        // THEN
+        let notices = json["notices"].as_array().expect("notices");
+        assert_eq!(notices.len(), 2);
+        assert_eq!(notices[1]["title"], "Proxied Response");
+        assert_eq!(
+            notices[1]["links"][0]["href"],
+            "https://rdap.example.com/domain/example.com"
+        );
+    }
+
+    #[test]
+    fn GIVEN_cache_ttl_WHEN_cached_THEN_served_until_expired() {
+        // GIVEN
+        let proxy = Proxy::new(
+            ProxyConfig::builder()
+                .cache_ttl(Duration::from_secs(60))
+                .https_only(true)
+                .build(),
+            None,
+        )
+        .expect("creating proxy");
+        let now = Instant::now();
+        proxy.cache(
+            "/rdap/domain/example.com",
+            StatusCode::OK,
+            &Value::Null,
+            now,
+        );
+
+        // WHEN
+        let fresh = proxy.cached("/rdap/domain/example.com", now + Duration::from_secs(59));
+        let expired = proxy.cached("/rdap/domain/example.com", now + Duration::from_secs(60));
+
+        // THEN
+        assert!(fresh.is_some());
+        assert!(expired.is_none());
+    }
+
+    #[test]
+    fn GIVEN_no_cache_ttl_WHEN_cache_THEN_not_cached() {
+        // GIVEN
+        let proxy = Proxy::new(
+            ProxyConfig::builder()
+                .cache_ttl(Duration::ZERO)
+                .https_only(true)
+                .build(),
+            None,
+        )
+        .expect("creating proxy");
+        let now = Instant::now();
+
+        // WHEN
+        proxy.cache(
+            "/rdap/domain/example.com",
+            StatusCode::OK,
+            &Value::Null,
+            now,
+        );
+
+        // THEN
+        assert!(proxy.cached("/rdap/domain/example.com", now).is_none());
+    }
+}
@@ -0,0 +1,333 @@
+//! Rate limiting of the requests of each client IP address with token buckets.
+
+use std::{
+    collections::HashMap,
+    net::{IpAddr, SocketAddr},
+    sync::Mutex,
+    time::{Duration, Instant},
+};
+
+use {
+    axum::{
+        extract::{ConnectInfo, Request, State},
+        middleware::Next,
+        response::{IntoResponse, Response},
+    },
+    http::{header::RETRY_AFTER, HeaderValue},
+    icann_rdap_common::{
+        prelude::ToResponse,
+        response::{Notice, NoticeOrRemark, Rfc9083Error},
+    },
+    tracing::debug,
+};
+
+use crate::{error::RdapServerError, rdap::response::ResponseUtil, server::DynServiceState};
+
+/// The default number of requests a client may make at once.
+pub const DEFAULT_BURST: u32 = 10;
+
+/// The number of client buckets above which the full buckets are dropped.
+const MAX_TRACKED_CLIENTS: usize = 10_000;
+
+/// The longest time a limited client is told to wait.
+const MAX_WAIT: Duration = Duration::from_secs(86_400);
+
+/// The configuration of rate limiting.
+#[derive(Debug, Clone)]
+pub struct RateLimitConfig {
+    /// The requests per second allowed to each client IP address over time.
+    pub rate: f64,
+
+    /// The requests a client IP address may make at once before being limited to the rate.
+    pub burst: u32,
+}
+
+#[buildstructor::buildstructor]
+impl RateLimitConfig {
+    /// Creates the configuration, which is an error if the rate is not a positive number.
+    #[builder(visibility = "pub")]
+    fn new(rate: f64, burst: u32) -> Result<Self, RdapServerError> {
+        if !(rate.is_finite() && rate > 0.0) {
+            return Err(RdapServerError::Config(format!(
+                "rate limit must be a positive number of requests per second, not {rate}"
+            )));
+        }
+        Ok(Self { rate, burst })
+    }
+}
+
+#[derive(Debug)]
+struct Bucket {
+    tokens: f64,
+    updated: Instant,
+}
+
+/// Limits the requests of each client IP address, as configured.
+///
+/// Each client has a bucket holding up to the burst of tokens, which is refilled
+/// at the rate. A request takes a token, and is refused if there are none.
+#[derive(Debug)]
+pub struct RateLimiter {
+    config: RateLimitConfig,
+    buckets: Mutex<HashMap<IpAddr, Bucket>>,
+}
+
+impl RateLimiter {
+    pub fn new(config: RateLimitConfig) -> Self {
+        Self {
+            config,
+            buckets: Mutex::default(),
+        }
+    }
+
+    /// Takes a token for a request from a client, or if there is none, gives
+    /// the time until there is one.
+    pub fn check(&self, ip: IpAddr) -> Result<(), Duration> {
+        self.check_at(ip, Instant::now())
+    }
+
+    fn check_at(&self, ip: IpAddr, now: Instant) -> Result<(), Duration> {
+        let burst = f64::from(self.config.burst.max(1));
+        let rate = self.config.rate;
+        let mut buckets = self.buckets.lock().expect("rate limit lock poisoned");
+        if buckets.len() > MAX_TRACKED_CLIENTS {
+            // a full bucket is no different than no bucket.
+            buckets.retain(|_, bucket| {
+                bucket.tokens + now.duration_since(bucket.updated).as_secs_f64() * rate < burst
+            });
+        }
+        let bucket = buckets.entry(ip.to_canonical()).or_insert(Bucket {
+            tokens: burst,
+            updated: now,
+        });
+        let elapsed = now.saturating_duration_since(bucket.updated).as_secs_f64();
+        bucket.tokens = (bucket.tokens + elapsed * rate).min(burst);
+        bucket.updated = now;
+        if bucket.tokens >= 1.0 {
+            bucket.tokens -= 1.0;
+            Ok(())
+        } else if rate > 0.0 {
+            Err(Duration::try_from_secs_f64((1.0 - bucket.tokens) / rate).unwrap_or(MAX_WAIT))
+        } else {
+            Err(MAX_WAIT)
+        }
+    }
+}
+
+/// Middleware refusing the requests of clients over the rate limit with a 429 error.
+///
+/// Requests without a client IP address, such as those over a unix socket, are not limited.
+pub(crate) async fn rate_limit(
+    State(state): State<DynServiceState>,
+    request: Request,
+    next: Next,
+) -> Response {
+    let Some(limiter) = state.get_rate_limiter() else {
+        return next.run(request).await;
+    };
+    let Some(ConnectInfo(addr)) = request.extensions().get::<ConnectInfo<SocketAddr>>() else {
+        return next.run(request).await;
+    };
+    match limiter.check(addr.ip()) {
+        Ok(()) => next.run(request).await,
+        Err(wait) => {
+            debug!("rate limiting {}", addr.ip());
+            too_many_requests(wait)
+        }
+    }
+}
+
+fn too_many_requests(wait: Duration) -> Response {
+    let secs = wait
+        .as_secs()
+        .saturating_add(u64::from(wait.subsec_nanos() > 0));
+    let mut response = Rfc9083Error::response_obj()
+        .error_code(429)
+        .notice(Notice(
+            NoticeOrRemark::builder()
+                .title("Too Many Requests")
+                .description_entry(format!(
+                    "The rate limit has been exceeded. Retry after {secs} seconds."
+                ))
+                .build(),
+        ))
+        .build()
+        .to_response()
+        .response()
+        .into_response();
+    response
+        .headers_mut()
+        .insert(RETRY_AFTER, HeaderValue::from(secs));
+    response
+}
+
+#[cfg(test)]
+#[allow(non_snake_case)]
+mod tests {
+    use std::{
+        net::IpAddr,
+        time::{Duration, Instant},
+    };
+
+    use {http::StatusCode, rstest::rstest};
+
+    use super::{too_many_requests, RateLimitConfig, RateLimiter};
+
+    fn limiter(rate: f64, burst: u32) -> RateLimiter {
+        RateLimiter::new(
+            RateLimitConfig::builder()
+                .rate(rate)
+                .burst(burst)
+                .build()
+                .expect("valid rate limit"),
+        )
+    }
+
+    #[test]
+    fn GIVEN_burst_WHEN_requests_at_once_THEN_limited_after_burst() {
+        // GIVEN
+        let limiter = limiter(1.0, 3);
+        let ip: IpAddr = "192.0.2.1".parse().unwrap();
+        let now = Instant::now();
+
+        // WHEN
+        let actual = (0..4)
+            .map(|_| limiter.check_at(ip, now).is_ok())
+            .collect::<Vec<_>>();
+
+        // THEN
+        assert_eq!(actual, vec![true, true, true, false]);
+    }
+
+    #[test]
+    fn GIVEN_empty_bucket_WHEN_check_THEN_wait_until_refilled() {
+        // GIVEN
+        let limiter = limiter(2.0, 1);
+        let ip: IpAddr = "192.0.2.1".parse().unwrap();
+        let now = Instant::now();
+        limiter.check_at(ip, now).expect("first request");
+
+        // WHEN
+        let actual = limiter.check_at(ip, now);
+
+        // THEN
+        assert_eq!(actual, Err(Duration::from_millis(500)));
+    }
+
+    #[rstest]
+    #[case(Duration::from_millis(499), false)]
+    #[case(Duration::from_millis(500), true)]
+    fn GIVEN_steady_rate_WHEN_requests_spaced_THEN_allowed_at_rate(
+        #[case] spacing: Duration,
+        #[case] expected: bool,
+    ) {
+        // GIVEN
+        let limiter = limiter(2.0, 1);
+        let ip: IpAddr = "192.0.2.1".parse().unwrap();
+        let start = Instant::now();
+        limiter.check_at(ip, start).expect("first request");
+
+        // WHEN
+        let actual = (1..10).all(|i| limiter.check_at(ip, start + spacing * i).is_ok());
+
+        // THEN
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn GIVEN_limited_client_WHEN_other_client_THEN_not_limited() {
+        // GIVEN
+        let limiter = limiter(1.0, 1);
+        let now = Instant::now();
+        limiter
+            .check_at("192.0.2.1".parse().unwrap(), now)
+            .expect("first request");
+
+        // WHEN
+        let actual = limiter.check_at("192.0.2.2".parse().unwrap(), now);
+
+        // THEN
+        assert!(actual.is_ok());
+    }
+
+    #[test]
+    fn GIVEN_ipv4_mapped_address_WHEN_check_THEN_same_bucket_as_ipv4() {
+        // GIVEN
+        let limiter = limiter(1.0, 1);
+        let now = Instant::now();
+        limiter
+            .check_at("192.0.2.1".parse().unwrap(), now)
+            .expect("first request");
+
+        // WHEN
+        let actual = limiter.check_at("::ffff:192.0.2.1".parse().unwrap(), now);
+
+        // THEN
+        assert!(actual.is_err());
+    }
+
+    #[tokio::test]
+    async fn GIVEN_wait_WHEN_too_many_requests_THEN_429_with_retry_after_and_notice() {
+        // GIVEN
+        let wait = Duration::from_millis(1500);
+
+        // WHEN
+        let actual = too_many_requests(wait);
+
+        // THEN
+        assert_eq!(actual.status(), StatusCode::TOO_MANY_REQUESTS);
+        assert_eq!(actual.headers()["retry-after"], "2");
+        let body = axum::body::to_bytes(actual.into_body(), usize::MAX)
+            .await
+            .expect("body");
+        let json: serde_json::Value = serde_json::from_slice(&body).expect("json");
+        assert_eq!(json["errorCode"], 429);
+        assert_eq!(
+            json["notices"][0]["description"][0],
+            "The rate limit has been exceeded. Retry after 2 seconds."
+        );
+    }
+
+    #[rstest]
+    #[case(0.0)]
+    #[case(-1.0)]
+    #[case(f64::NAN)]
+    #[case(f64::INFINITY)]
+    fn GIVEN_non_positive_rate_WHEN_build_config_THEN_error(#[case] rate: f64) {
+        // GIVEN
+        let builder = RateLimitConfig::builder().rate(rate).burst(1);
+
+        // WHEN
+        let actual = builder.build();
+
+        // THEN
+        assert!(actual.is_err());
+    }
+
+    #[test]
+    fn GIVEN_max_wait_WHEN_too_many_requests_THEN_retry_after_saturated() {
+        // GIVEN
+        let wait = Duration::MAX;
+
+        // WHEN
+        let actual = too_many_requests(wait);
+
+        // THEN
+        assert_eq!(actual.headers()["retry-after"], u64::MAX.to_string());
+    }
+
+    #[test]
+    fn GIVEN_tiny_rate_WHEN_check_empty_bucket_THEN_wait_is_bounded() {
+        // GIVEN
+        let limiter = limiter(f64::MIN_POSITIVE, 1);
+        let ip: IpAddr = "192.0.2.1".parse().unwrap();
+        let now = Instant::now();
+        limiter.check_at(ip, now).expect("first request");
+
+        // WHEN
+        let actual = limiter.check_at(ip, now);
+
+        // THEN
+        assert_eq!(actual, Err(super::MAX_WAIT));
+    }
+}
@@ -0,0 +1,256 @@
+//! Negotiation of RDAP extensions with the RDAP-X media type.
+//!
+//! A client preferring `application/rdap-x+json` in the Accept header is answered
+//! with that media type, its "extensions" parameter listing the extensions of the
+//! response. If the client lists extensions, the response is filtered to those
+//! extensions, along with `rdap_level_0`.
+
+use {
+    axum::{
+        body::{to_bytes, Body},
+        extract::Request,
+        middleware::Next,
+        response::Response,
+    },
+    http::{
+        header::{ACCEPT, CONTENT_LENGTH, CONTENT_TYPE, VARY},
+        HeaderValue,
+    },
+    icann_rdap_common::media_types::{
+        is_rdap_media_type, is_rdap_x_media_type, media_type_extensions, rdap_x_media_type,
+    },
+    serde_json::Value,
+    tracing::{debug, warn},
+};
+
+/// The extension identifier that is never filtered from responses.
+const RDAP_LEVEL_0: &str = "rdap_level_0";
+
+/// The RDAP-X media type wanted by a client.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RdapXRequest {
+    /// The extensions wanted, or None if the client did not list any.
+    pub extensions: Option<Vec<String>>,
+}
+
+impl RdapXRequest {
+    /// Gets the RDAP-X media type wanted in an Accept header, if the client prefers
+    /// it at least as much as the RDAP media type.
+    pub fn from_accept(accept: &str) -> Option<Self> {
+        let mut rdap_x = None;
+        let mut rdap_q = 0.0;
+        for entry in accept.split(',') {
+            let q = quality(entry);
+            if is_rdap_x_media_type(entry) && q > 0.0 {
+                rdap_x = Some((entry, q));
+            } else if is_rdap_media_type(entry) {
+                rdap_q = q;
+            }
+        }
+        let (entry, q) = rdap_x?;
+        (q >= rdap_q).then(|| Self {
+            extensions: media_type_extensions(entry)
+                .map(|extensions| extensions.into_iter().map(str::to_string).collect()),
+        })
+    }
+
+    fn wants(&self, extension: &str) -> bool {
+        extension == RDAP_LEVEL_0
+            || self
+                .extensions
+                .as_ref()
+                .is_none_or(|extensions| extensions.iter().any(|e| e == extension))
+    }
+
+    /// Removes the extensions not wanted from the RDAP JSON, returning the extensions
+    /// remaining in its rdapConformance.
+    ///
+    /// The members of an extension are those named with the extension identifier,
+    /// either exactly or followed by an underscore.
+    pub fn filter(&self, rdap: &mut Value) -> Vec<String> {
+        let Some(conformance) = rdap
+            .get_mut("rdapConformance")
+            .and_then(Value::as_array_mut)
+        else {
+            return vec![];
+        };
+        let mut unwanted = vec![];
+        conformance.retain(|extension| match extension.as_str() {
+            Some(extension) if !self.wants(extension) => {
+                unwanted.push(extension.to_string());
+                false
+            }
+            _ => true,
+        });
+        let remaining = conformance
+            .iter()
+            .filter_map(Value::as_str)
+            .map(str::to_string)
+            .collect();
+        if !unwanted.is_empty() {
+            debug!("removing unwanted extensions {unwanted:?}");
+            remove_members(rdap, &unwanted);
+        }
+        remaining
+    }
+}
+
+/// Gets the quality value of an entry of an Accept header.
+fn quality(entry: &str) -> f32 {
+    entry
+        .split(';')
+        .skip(1)
+        .find_map(|param| {
+            let (name, value) = param.split_once('=')?;
+            name.trim()
+                .eq_ignore_ascii_case("q")
+                .then(|| value.trim().parse::<f32>().ok())?
+        })
+        .unwrap_or(1.0)
+}
+
+fn is_member_of(name: &str, extension: &str) -> bool {
+    name.strip_prefix(extension)
+        .is_some_and(|rest| rest.is_empty() || rest.starts_with('_'))
+}
+
+fn remove_members(value: &mut Value, extensions: &[String]) {
+    match value {
+        Value::Object(members) => {
+            members.retain(|name, _| !extensions.iter().any(|e| is_member_of(name, e)));
+            members
+                .values_mut()
+                .for_each(|member| remove_members(member, extensions));
+        }
+        Value::Array(values) => values
+            .iter_mut()
+            .for_each(|value| remove_members(value, extensions)),
+        _ => {}
+    }
+}
+
+/// Middleware that answers clients preferring the RDAP-X media type with it,
+/// filtering the extensions of the response to those wanted.
+pub(crate) async fn negotiate_rdap_x(request: Request, next: Next) -> Response {
+    let rdap_x = request
+        .headers()
+        .get(ACCEPT)
+        .and_then(|value| value.to_str().ok())
+        .and_then(RdapXRequest::from_accept);
+    let mut response = next.run(request).await;
+    let is_rdap = response
+        .headers()
+        .get(CONTENT_TYPE)
+        .and_then(|value| value.to_str().ok())
+        .is_some_and(is_rdap_media_type);
+    if !is_rdap {
+        return response;
+    }
+    // the media type of RDAP responses depends on Accept, which caches must know.
+    response
+        .headers_mut()
+        .append(VARY, HeaderValue::from_static("accept"));
+    let Some(rdap_x) = rdap_x else {
+        return response;
+    };
+
+    let (mut parts, body) = response.into_parts();
+    let bytes = match to_bytes(body, usize::MAX).await {
+        Ok(bytes) => bytes,
+        Err(e) => {
+            warn!("unable to read response body for RDAP-X: {e}");
+            Default::default()
+        }
+    };
+    let Ok(mut rdap) = serde_json::from_slice::<Value>(&bytes) else {
+        return Response::from_parts(parts, Body::from(bytes));
+    };
+    let extensions = rdap_x.filter(&mut rdap);
+    let json = serde_json::to_vec(&rdap).expect("serializing RDAP");
+    match HeaderValue::from_str(&rdap_x_media_type(&extensions)) {
+        Ok(content_type) => {
+            parts.headers.insert(CONTENT_TYPE, content_type);
+        }
+        Err(e) => warn!("RDAP-X media type not used: {e}"),
+    }
+    parts.headers.remove(CONTENT_LENGTH);
+    Response::from_parts(parts, Body::from(json))
+}
+
+#[cfg(test)]
+#[allow(non_snake_case)]
+mod tests {
+    use {rstest::rstest, serde_json::json};
+
+    use super::RdapXRequest;
+
+    #[rstest]
+    #[case("application/rdap+json", None)]
+    #[case("application/rdap-x+json", Some(RdapXRequest { extensions: None }))]
+    #[case(
+        r#"application/rdap-x+json;extensions="rdap_level_0 redacted", application/rdap+json;q=0.9"#,
+        Some(RdapXRequest { extensions: Some(vec!["rdap_level_0".to_string(), "redacted".to_string()]) })
+    )]
+    #[case("application/rdap-x+json;q=0.5, application/rdap+json", None)]
+    #[case("application/rdap-x+json;q=0", None)]
+    fn GIVEN_accept_WHEN_from_accept_THEN_rdap_x_request(
+        #[case] accept: &str,
+        #[case] expected: Option<RdapXRequest>,
+    ) {
+        // GIVEN accept
+
+        // WHEN
+        let actual = RdapXRequest::from_accept(accept);
+
+        // THEN
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn GIVEN_extensions_wanted_WHEN_filter_THEN_others_removed() {
+        // GIVEN
+        let rdap_x = RdapXRequest {
+            extensions: Some(vec!["redacted".to_string()]),
+        };
+        let mut rdap = json!({
+            "rdapConformance": ["rdap_level_0", "cidr0", "redacted"],
+            "objectClassName": "ip network",
+            "cidr0_cidrs": [{ "v4prefix": "10.0.0.0", "length": 8 }],
+            "redacted": [],
+            "entities": [{ "objectClassName": "entity", "cidr0x": 1, "cidr0_other": 2 }]
+        });
+
+        // WHEN
+        let actual = rdap_x.filter(&mut rdap);
+
+        // THEN
+        assert_eq!(actual, vec!["rdap_level_0", "redacted"]);
+        assert_eq!(
+            rdap,
+            json!({
+                "rdapConformance": ["rdap_level_0", "redacted"],
+                "objectClassName": "ip network",
+                "redacted": [],
+                "entities": [{ "objectClassName": "entity", "cidr0x": 1 }]
+            })
+        );
+    }
+
+    #[test]
+    fn GIVEN_no_extensions_listed_WHEN_filter_THEN_all_kept() {
+        // GIVEN
+        let rdap_x = RdapXRequest { extensions: None };
+        let mut rdap = json!({
+            "rdapConformance": ["rdap_level_0", "cidr0"],
+            "cidr0_cidrs": []
+        });
+        let expected = rdap.clone();
+
+        // WHEN
+        let actual = rdap_x.filter(&mut rdap);
+
+        // THEN
+        assert_eq!(actual, vec!["rdap_level_0", "cidr0"]);
+        assert_eq!(rdap, expected);
+    }
+}
@@ -5,11 +5,12 @@ use {
         response::{IntoResponse, Response},
         Json,
     },
-    http::StatusCode,
+    chrono::{DateTime, Utc},
+    http::{header::LAST_MODIFIED, HeaderValue, StatusCode},
     icann_rdap_common::{
         media_types::RDAP_MEDIA_TYPE,
         prelude::ToResponse,
-        response::{RdapResponse, Rfc9083Error},
+        response::{Event, ObjectCommonFields, RdapResponse, Rfc9083Error},
     },
     tracing::warn,
 };
@@ -38,6 +39,7 @@ pub(crate) const RDAP_HEADERS: [(&str, &str); 1] = [("content-type", RDAP_MEDIA_
 pub(crate) trait ResponseUtil {
     fn status_code(&self) -> StatusCode;
     fn first_notice_link_href(&self) -> Option<&str>;
+    fn last_modified(&self) -> Option<DateTime<Utc>>;
     fn response(&self) -> Response;
 }
 
@@ -63,10 +65,34 @@ impl ResponseUtil for RdapResponse {
         }
     }
 
+    /// Gets the time an object was last changed, or registered if it has not been changed,
+    /// from its events.
+    fn last_modified(&self) -> Option<DateTime<Utc>> {
+        let events = match self {
+            Self::Entity(entity) => entity.events(),
+            Self::Domain(domain) => domain.events(),
+            Self::Nameserver(nameserver) => nameserver.events(),
+            Self::Autnum(autnum) => autnum.events(),
+            Self::Network(network) => network.events(),
+            _ => return None,
+        };
+        let latest = |action: &str| {
+            events
+                .iter()
+                .filter(|event| event.event_action() == Some(action))
+                .filter_map(Event::event_date)
+                .filter_map(|date| DateTime::parse_from_rfc3339(date).ok())
+                .map(|date| date.with_timezone(&Utc))
+                .max()
+        };
+        latest("last changed").or_else(|| latest("registration"))
+    }
+
     fn response(&self) -> Response {
         let status_code = self.status_code();
         match status_code {
             StatusCode::MULTIPLE_CHOICES
+            | StatusCode::MOVED_PERMANENTLY
             | StatusCode::FOUND
             | StatusCode::SEE_OTHER
             | StatusCode::USE_PROXY
@@ -82,6 +108,16 @@ impl ResponseUtil for RdapResponse {
                     (status_code, RDAP_HEADERS, Json(self)).into_response()
                 }
             }
+            StatusCode::OK => {
+                let mut response = (status_code, RDAP_HEADERS, Json(self)).into_response();
+                if let Some(last_modified) = self.last_modified() {
+                    let http_date = last_modified.format("%a, %d %b %Y %H:%M:%S GMT");
+                    if let Ok(value) = HeaderValue::from_str(&http_date.to_string()) {
+                        response.headers_mut().insert(LAST_MODIFIED, value);
+                    }
+                }
+                response
+            }
             _ => (status_code, RDAP_HEADERS, Json(self)).into_response(),
         }
     }
@@ -96,8 +132,9 @@ mod tests {
         http::StatusCode,
         icann_rdap_common::{
             prelude::ToResponse,
-            response::{Domain, Link, Notice, NoticeOrRemark, Rfc9083Error},
+            response::{Domain, Event, Link, Notice, NoticeOrRemark, Rfc9083Error},
         },
+        rstest::rstest,
     };
 
     use crate::rdap::response::{ResponseUtil, NOT_FOUND, NOT_IMPLEMENTED};
@@ -162,4 +199,68 @@ mod tests {
         // THEN
         assert_eq!(actual.expect("no href"), "https://other.example.com");
     }
+
+    #[rstest]
+    #[case(301)]
+    #[case(302)]
+    #[case(307)]
+    #[case(308)]
+    fn GIVEN_redirect_WHEN_exec_response_THEN_location_is_url(#[case] error_code: u16) {
+        // GIVEN
+        let redirect = Rfc9083Error::redirect()
+            .url("https://other.example.com/ip/192.0.2.0/24")
+            .error_code(error_code)
+            .build()
+            .to_response();
+
+        // WHEN
+        let actual = redirect.response().into_response();
+
+        // THEN
+        assert_eq!(actual.status().as_u16(), error_code);
+        assert_eq!(
+            actual.headers()["location"],
+            "https://other.example.com/ip/192.0.2.0/24"
+        );
+    }
+
+    #[rstest]
+    #[case(vec![("registration", "2020-01-01T00:00:00Z")], Some("2020-01-01T00:00:00+00:00"))]
+    #[case(
+        vec![
+            ("registration", "2020-01-01T00:00:00Z"),
+            ("last changed", "2023-01-01T00:00:00Z"),
+            ("last changed", "2024-01-01T09:00:00+09:00"),
+        ],
+        Some("2024-01-01T00:00:00+00:00")
+    )]
+    #[case(vec![("expiration", "2030-01-01T00:00:00Z")], None)]
+    #[case(vec![("last changed", "yesterday")], None)]
+    fn GIVEN_domain_events_WHEN_last_modified_THEN_last_changed_or_registration(
+        #[case] events: Vec<(&str, &str)>,
+        #[case] expected: Option<&str>,
+    ) {
+        // GIVEN
+        let domain = Domain::builder()
+            .ldh_name("foo.example")
+            .events(
+                events
+                    .into_iter()
+                    .map(|(action, date)| {
+                        Event::builder()
+                            .event_action(action)
+                            .event_date(date)
+                            .build()
+                    })
+                    .collect(),
+            )
+            .build()
+            .to_response();
+
+        // WHEN
+        let actual = domain.last_modified();
+
+        // THEN
+        assert_eq!(actual.map(|date| date.to_rfc3339()).as_deref(), expected);
+    }
 }
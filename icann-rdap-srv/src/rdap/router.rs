@@ -1,13 +1,16 @@
-use axum::{response::IntoResponse, routing::get, Router};
+use axum::{routing::get, Router};
 
 use super::{
     autnum::autnum_by_num,
+    autnums::autnums,
     domain::domain_by_name,
-    domains::domains,
+    domains::{domains, domains_reverse_search},
+    entities::entities,
     entity::entity_by_handle,
     ip::network_by_netid,
+    ips::{ips, ips_by_relation},
     nameserver::nameserver_by_name,
-    response::{ResponseUtil, NOT_IMPLEMENTED},
+    nameservers::nameservers,
     srvhelp::srvhelp,
 };
 
@@ -19,11 +22,14 @@ pub(crate) fn rdap_router() -> Router<crate::server::DynServiceState> {
         .route("/nameserver/:name", get(nameserver_by_name))
         .route("/entity/:handle", get(entity_by_handle))
         .route("/domains", get(domains))
-        .route("/nameservers", get(not_implemented))
-        .route("/entities", get(not_implemented))
+        .route(
+            "/domains/reverse_search/entity",
+            get(domains_reverse_search),
+        )
+        .route("/nameservers", get(nameservers))
+        .route("/entities", get(entities))
+        .route("/ips", get(ips))
+        .route("/ips/rirSearch1/:relation/*netid", get(ips_by_relation))
+        .route("/autnums", get(autnums))
         .route("/help", get(srvhelp))
 }
-
-async fn not_implemented() -> impl IntoResponse {
-    NOT_IMPLEMENTED.response()
-}
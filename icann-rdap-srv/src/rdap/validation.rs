@@ -0,0 +1,151 @@
+//! Refusal to serve RDAP having errors found by the check system.
+
+use std::{sync::Arc, time::Duration};
+
+use {
+    axum::{
+        body::{to_bytes, Body},
+        extract::{Request, State},
+        middleware::Next,
+        response::Response,
+    },
+    http::header::CONTENT_TYPE,
+    icann_rdap_client::rdap::checks_within_budget,
+    icann_rdap_common::{
+        check::{traverse_checks, CheckClass, CheckParams, Checks, GetChecks},
+        media_types::RDAP_MEDIA_TYPE,
+        prelude::ToResponse,
+        response::{RdapResponse, Rfc9083Error},
+    },
+    tracing::{error, warn},
+};
+
+use crate::{rdap::response::ResponseUtil, server::DynServiceState};
+
+/// The classes of checks that keep RDAP from being served.
+pub const DENIED_CHECK_CLASSES: &[CheckClass] = &[CheckClass::StdError];
+
+/// The most time spent finding the checks of a response before it is served.
+///
+/// Responses whose checks take longer are judged on the checks found in this time.
+pub const CHECK_TIME_BUDGET: Duration = Duration::from_secs(2);
+
+/// Returns true if the RDAP has checks of the denied classes, logging each of them.
+///
+/// The context, such as a file name or URL, is logged with the checks.
+pub fn has_denied_checks(rdap: &RdapResponse, context: &str) -> bool {
+    let checks = rdap.get_checks(CheckParams::for_rdap(rdap));
+    log_denied_checks(&checks, context)
+}
+
+fn log_denied_checks(checks: &Checks, context: &str) -> bool {
+    traverse_checks(
+        checks,
+        DENIED_CHECK_CLASSES,
+        None,
+        &mut |struct_tree, check_item| error!("{context}: {struct_tree} -> {check_item}"),
+    )
+}
+
+/// Middleware that replaces successful RDAP responses having errors found by
+/// the check system with a 500 error.
+pub(crate) async fn deny_invalid_rdap(
+    State(state): State<DynServiceState>,
+    request: Request,
+    next: Next,
+) -> Response {
+    if !state.get_deny_invalid_rdap() {
+        return next.run(request).await;
+    }
+    let uri = request.uri().to_string();
+    let response = next.run(request).await;
+    let is_rdap = response
+        .headers()
+        .get(CONTENT_TYPE)
+        .and_then(|value| value.to_str().ok())
+        .is_some_and(|content_type| content_type.starts_with(RDAP_MEDIA_TYPE));
+    if !response.status().is_success() || !is_rdap {
+        return response;
+    }
+
+    let (parts, body) = response.into_parts();
+    let bytes = match to_bytes(body, usize::MAX).await {
+        Ok(bytes) => bytes,
+        Err(e) => {
+            warn!("unable to read response body for checks: {e}");
+            Default::default()
+        }
+    };
+    let denied = match serde_json::from_slice::<RdapResponse>(&bytes) {
+        Ok(rdap) => {
+            let found = checks_within_budget(Arc::new(rdap), CHECK_TIME_BUDGET).await;
+            if found.timed_out {
+                warn!("{uri}: checks did not finish in time and are partial");
+            }
+            found
+                .checks
+                .is_some_and(|checks| log_denied_checks(&checks, &uri))
+        }
+        Err(e) => {
+            error!("{uri}: response is not RDAP: {e}");
+            true
+        }
+    };
+    if denied {
+        Rfc9083Error::response_obj()
+            .error_code(500)
+            .build()
+            .to_response()
+            .response()
+    } else {
+        Response::from_parts(parts, Body::from(bytes))
+    }
+}
+
+#[cfg(test)]
+#[allow(non_snake_case)]
+mod tests {
+    use icann_rdap_common::{
+        prelude::ToResponse,
+        response::{Domain, Link},
+    };
+
+    use super::has_denied_checks;
+
+    #[test]
+    fn GIVEN_valid_domain_WHEN_has_denied_checks_THEN_false() {
+        // GIVEN
+        let rdap = Domain::response_obj()
+            .ldh_name("foo.example")
+            .build()
+            .to_response();
+
+        // WHEN
+        let actual = has_denied_checks(&rdap, "test");
+
+        // THEN
+        assert!(!actual);
+    }
+
+    #[test]
+    fn GIVEN_domain_with_link_missing_href_WHEN_has_denied_checks_THEN_true() {
+        // GIVEN
+        let mut domain = Domain::builder().ldh_name("foo.example").build();
+        domain.object_common.links = Some(vec![Link {
+            value: Some("https://foo.example".to_string()),
+            rel: Some("self".to_string()),
+            href: None,
+            hreflang: None,
+            title: None,
+            media: None,
+            media_type: Some("application/rdap+json".to_string()),
+        }]);
+        let rdap = domain.to_response();
+
+        // WHEN
+        let actual = has_denied_checks(&rdap, "test");
+
+        // THEN
+        assert!(actual);
+    }
+}
@@ -1,45 +1,126 @@
-use std::{net::SocketAddr, sync::Arc, time::Duration};
+use std::{
+    collections::BTreeMap, fmt::Display, net::SocketAddr, path::PathBuf, sync::Arc, time::Duration,
+};
 
 use {
     async_trait::async_trait,
-    axum::{error_handling::HandleErrorLayer, Router},
-    http::{Method, StatusCode},
-    icann_rdap_common::VERSION,
+    axum::{
+        error_handling::HandleErrorLayer,
+        extract::Request,
+        middleware::{from_fn, from_fn_with_state},
+        routing::post,
+        Router,
+    },
+    http::{header::HOST, uri::Authority, Method, StatusCode},
+    icann_rdap_common::{response::filter::ResponseFilter, VERSION},
+    listenfd::ListenFd,
     tokio::net::TcpListener,
-    tower::{BoxError, ServiceBuilder},
+    tower::{BoxError, ServiceBuilder, ServiceExt},
     tower_http::{
         cors::{Any, CorsLayer},
         trace::TraceLayer,
     },
+    tracing::{info_span, Span},
 };
 
 use crate::{
     bootstrap::init_bootstrap,
     config::{ListenConfig, ServiceConfig, StorageType},
     error::RdapServerError,
-    rdap::router::rdap_router,
+    hooks::observe_requests,
+    rdap::{
+        caching::{conditional_get, serve_head},
+        compression::{compress_response, Compression},
+        error_body::{customize_error_body, ErrorBodies},
+        filter::filter_rdap,
+        proxy::{proxy_rdap, Proxy},
+        rate_limit::{rate_limit, RateLimiter},
+        rdap_x::negotiate_rdap_x,
+        router::rdap_router,
+        validation::deny_invalid_rdap,
+    },
     storage::{
+        backend::storage_backend,
         data::{load_data, reload_data},
         mem::{config::MemConfig, ops::Mem},
         pg::{config::PgConfig, ops::Pg},
-        StoreOps,
+        watch::{reload_endpoint, watch_data, DataReloader, RELOAD_ENDPOINT},
+        CommonConfig, DynStoreOps, StoreOps,
     },
+    whois::serve_whois,
 };
 
+/// The address the server is listening on.
+#[derive(Debug, Clone)]
+pub enum ListenAddr {
+    Tcp(SocketAddr),
+    Unix(PathBuf),
+}
+
+impl Display for ListenAddr {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Tcp(addr) => write!(f, "{addr}"),
+            Self::Unix(path) => write!(f, "unix:{}", path.display()),
+        }
+    }
+}
+
+enum SocketListener {
+    Tcp(TcpListener),
+    #[cfg(unix)]
+    Unix(tokio::net::UnixListener),
+}
+
 /// Holds information on the server listening.
 pub struct Listener {
-    pub local_addr: SocketAddr,
-    tcp_listener: TcpListener,
+    pub local_addr: ListenAddr,
+    socket_listener: SocketListener,
+
+    /// The address WHOIS queries are answered on, if they are.
+    pub whois_addr: Option<SocketAddr>,
+    whois_listener: Option<TcpListener>,
 }
 
 /// Starts the RDAP service.
 impl Listener {
     pub async fn listen(config: &ListenConfig) -> Result<Self, RdapServerError> {
+        let mut listener = Self::listen_rdap(config).await?;
+        if let Some(whois_port) = config.whois_port {
+            let binding = format!(
+                "{}:{whois_port}",
+                config.ip_addr.as_ref().unwrap_or(&"[::]".to_string())
+            );
+            tracing::debug!("whois tcp binding to {}", binding);
+            let whois_listener = TcpListener::bind(binding).await?;
+            let whois_addr = whois_listener.local_addr()?;
+            tracing::info!("answering WHOIS queries on {whois_addr}");
+            listener.whois_addr = Some(whois_addr);
+            listener.whois_listener = Some(whois_listener);
+        }
+        Ok(listener)
+    }
+
+    async fn listen_rdap(config: &ListenConfig) -> Result<Self, RdapServerError> {
         tracing::info!("rdap-srv version {}", VERSION);
 
         #[cfg(debug_assertions)]
         tracing::warn!("Server is running in development mode");
 
+        if config.socket_activation {
+            if let Some(listener) = Self::from_socket_activation()? {
+                tracing::info!(
+                    "listening on {} from socket activation",
+                    listener.local_addr
+                );
+                return Ok(listener);
+            }
+        }
+
+        if let Some(path) = &config.unix_socket {
+            return Self::bind_unix(PathBuf::from(path));
+        }
+
         let binding = format!(
             "{}:{}",
             config.ip_addr.as_ref().unwrap_or(&"[::]".to_string()),
@@ -48,41 +129,122 @@ impl Listener {
 
         tracing::debug!("tcp binding to {}", binding);
 
-        let listener = TcpListener::bind(binding).await?;
-        let local_addr = listener.local_addr()?;
+        Self::from_tcp(TcpListener::bind(binding).await?)
+    }
+
+    fn from_tcp(listener: TcpListener) -> Result<Self, RdapServerError> {
+        Ok(Self {
+            local_addr: ListenAddr::Tcp(listener.local_addr()?),
+            socket_listener: SocketListener::Tcp(listener),
+            whois_addr: None,
+            whois_listener: None,
+        })
+    }
+
+    #[cfg(unix)]
+    fn bind_unix(path: PathBuf) -> Result<Self, RdapServerError> {
+        use std::os::unix::fs::FileTypeExt;
+
+        // a socket left by a previous run keeps the socket from being bound.
+        if std::fs::metadata(&path).is_ok_and(|m| m.file_type().is_socket()) {
+            std::fs::remove_file(&path)?;
+        }
+        tracing::debug!("unix socket binding to {}", path.display());
         Ok(Self {
-            local_addr,
-            tcp_listener: listener,
+            socket_listener: SocketListener::Unix(tokio::net::UnixListener::bind(&path)?),
+            local_addr: ListenAddr::Unix(path),
+            whois_addr: None,
+            whois_listener: None,
         })
     }
 
+    #[cfg(not(unix))]
+    fn bind_unix(_path: PathBuf) -> Result<Self, RdapServerError> {
+        Err(RdapServerError::Config(
+            "unix sockets are not available on this platform".to_string(),
+        ))
+    }
+
+    /// Takes the first socket passed by systemd socket activation, if any.
+    fn from_socket_activation() -> Result<Option<Self>, RdapServerError> {
+        let mut listen_fd = ListenFd::from_env();
+        if listen_fd.len() == 0 {
+            return Ok(None);
+        } else if listen_fd.len() > 1 {
+            tracing::warn!(
+                "only the first of {} sockets from socket activation is used",
+                listen_fd.len()
+            );
+        }
+        #[cfg(unix)]
+        if let Ok(Some(listener)) = listen_fd.take_unix_listener(0) {
+            listener.set_nonblocking(true)?;
+            let local_addr = listener
+                .local_addr()?
+                .as_pathname()
+                .map(PathBuf::from)
+                .unwrap_or_default();
+            return Ok(Some(Self {
+                socket_listener: SocketListener::Unix(tokio::net::UnixListener::from_std(
+                    listener,
+                )?),
+                local_addr: ListenAddr::Unix(local_addr),
+                whois_addr: None,
+                whois_listener: None,
+            }));
+        }
+        let Some(listener) = listen_fd.take_tcp_listener(0)? else {
+            return Ok(None);
+        };
+        listener.set_nonblocking(true)?;
+        Self::from_tcp(TcpListener::from_std(listener)?).map(Some)
+    }
+
     pub fn rdap_base(&self) -> String {
-        if self.local_addr.is_ipv4() {
-            format!(
-                "http://{}:{}/rdap",
-                self.local_addr.ip(),
-                self.local_addr.port()
-            )
-        } else {
-            format!(
-                "http://[{}]:{}/rdap",
-                self.local_addr.ip(),
-                self.local_addr.port()
-            )
+        match &self.local_addr {
+            ListenAddr::Tcp(addr) if addr.is_ipv4() => {
+                format!("http://{}:{}/rdap", addr.ip(), addr.port())
+            }
+            ListenAddr::Tcp(addr) => format!("http://[{}]:{}/rdap", addr.ip(), addr.port()),
+            ListenAddr::Unix(_) => "http://localhost/rdap".to_string(),
         }
     }
 
     /// Starts the server using a [ServiceConfig]. This is the entry point for a CLI.
     /// This function will initiate any needed non-HTTP services and then call
-    /// call [Listener::start_with_state], which initiates the HTTP service.
+    /// call [Listener::start_with_virtual_hosts], which initiates the HTTP service.
     pub async fn start_server(self, service_config: &ServiceConfig) -> Result<(), RdapServerError> {
         init_bootstrap(service_config).await?;
         if let StorageType::Memory(config) = &service_config.storage_type {
             let app_state = AppState::new_mem(config.clone(), service_config).await?;
-            self.start_with_state(app_state).await?;
+            let mut virtual_hosts = BTreeMap::new();
+            for (host, host_config) in virtual_host_configs(service_config) {
+                let host_state = AppState::new_mem(config.clone(), &host_config).await?;
+                virtual_hosts.insert(host, host_state);
+            }
+            self.start_with_virtual_hosts(app_state, virtual_hosts)
+                .await?;
         } else if let StorageType::Postgres(config) = &service_config.storage_type {
+            if !service_config.virtual_hosts.is_empty() {
+                return Err(RdapServerError::Config(
+                    "virtual hosts are not supported with postgres storage".to_string(),
+                ));
+            }
             let app_state = AppState::new_pg(config.clone(), service_config).await?;
             self.start_with_state(app_state).await?;
+        } else if let StorageType::Backend {
+            name,
+            common_config,
+        } = &service_config.storage_type
+        {
+            let app_state = AppState::new_backend(name, *common_config, service_config).await?;
+            let mut virtual_hosts = BTreeMap::new();
+            for (host, host_config) in virtual_host_configs(service_config) {
+                let host_state = AppState::new_backend(name, *common_config, &host_config).await?;
+                virtual_hosts.insert(host, host_state);
+            }
+            self.start_with_virtual_hosts(app_state, virtual_hosts)
+                .await?;
         };
         Ok(())
     }
@@ -94,30 +256,152 @@ impl Listener {
         T: StoreOps + Clone + Send + Sync + 'static,
         AppState<T>: ServiceState,
     {
-        let app = app_router::<T>(app_state);
+        self.start_with_virtual_hosts(app_state, BTreeMap::new())
+            .await
+    }
+
+    /// Starts the HTTP server with a specific [AppState] and an [AppState] for each
+    /// virtual host, by lower case host name. Requests are served with the state of
+    /// the host they are for, or with `app_state` if that host is not a virtual host.
+    pub async fn start_with_virtual_hosts<T>(
+        self,
+        app_state: AppState<T>,
+        virtual_hosts: BTreeMap<String, AppState<T>>,
+    ) -> Result<(), RdapServerError>
+    where
+        T: StoreOps + Clone + Send + Sync + 'static,
+        AppState<T>: ServiceState,
+    {
+        if let Some(whois_listener) = self.whois_listener {
+            let state = Arc::new(app_state.clone()) as DynServiceState;
+            tokio::spawn(serve_whois(whois_listener, state));
+        }
+        let app = if virtual_hosts.is_empty() {
+            app_router::<T>(app_state)
+        } else {
+            virtual_hosts_router(
+                app_router::<T>(app_state),
+                virtual_hosts
+                    .into_iter()
+                    .map(|(host, state)| (host, app_router::<T>(state)))
+                    .collect(),
+            )
+        };
 
         tracing::debug!("listening on {}", self.local_addr);
-        // axum::Server::from_tcp(self.tcp_listener)?
-        //     .serve(app.into_make_service_with_connect_info::<SocketAddr>())
-        //     .await?;
-        axum::serve(
-            self.tcp_listener,
-            app.into_make_service_with_connect_info::<SocketAddr>(),
-        )
-        .await?;
+        match self.socket_listener {
+            SocketListener::Tcp(tcp_listener) => {
+                axum::serve(
+                    tcp_listener,
+                    app.into_make_service_with_connect_info::<SocketAddr>(),
+                )
+                .await?
+            }
+            #[cfg(unix)]
+            SocketListener::Unix(unix_listener) => serve_unix(unix_listener, app).await?,
+        };
         Ok(())
     }
 }
 
-async fn init_data(
-    store: Box<dyn StoreOps>,
-    config: &ServiceConfig,
+/// Serves HTTP on the connections to a unix socket, which [axum::serve] does not do.
+#[cfg(unix)]
+async fn serve_unix(
+    listener: tokio::net::UnixListener,
+    app: Router,
 ) -> Result<(), RdapServerError> {
-    load_data(config, &*store, false).await?;
+    use hyper_util::{
+        rt::{TokioExecutor, TokioIo},
+        server::conn::auto,
+        service::TowerToHyperService,
+    };
+
+    loop {
+        let (stream, _) = listener.accept().await?;
+        let service = TowerToHyperService::new(app.clone());
+        tokio::spawn(async move {
+            if let Err(e) = auto::Builder::new(TokioExecutor::new())
+                .serve_connection(TokioIo::new(stream), service)
+                .await
+            {
+                tracing::debug!("unix socket connection error: {e}");
+            }
+        });
+    }
+}
+
+/// Gets the configuration of each virtual host, which is the service configuration
+/// with the data directory of the host.
+fn virtual_host_configs(service_config: &ServiceConfig) -> Vec<(String, ServiceConfig)> {
+    service_config
+        .virtual_hosts
+        .iter()
+        .map(|(host, data_dir)| {
+            tracing::info!("serving virtual host {host} from {data_dir}");
+            let mut host_config = service_config.clone();
+            host_config.data_dir = data_dir.clone();
+            host_config.virtual_hosts = BTreeMap::new();
+            (host.to_ascii_lowercase(), host_config)
+        })
+        .collect()
+}
+
+/// Routes requests to the router of the host they are for, or to the default router.
+fn virtual_hosts_router(default: Router, hosts: BTreeMap<String, Router>) -> Router {
+    let hosts = Arc::new(hosts);
+    Router::new().fallback(move |request: Request| {
+        let router = request_host(&request)
+            .and_then(|host| hosts.get(&host))
+            .unwrap_or(&default)
+            .clone();
+        async move {
+            match router.oneshot(request).await {
+                Ok(response) => response,
+                Err(infallible) => match infallible {},
+            }
+        }
+    })
+}
+
+/// Gets the lower case host of a request, without any port, from its URI or
+/// otherwise its `Host` header.
+fn request_host(request: &Request) -> Option<String> {
+    let host = match request.uri().host() {
+        Some(host) => host.to_string(),
+        None => request
+            .headers()
+            .get(HOST)?
+            .to_str()
+            .ok()?
+            .parse::<Authority>()
+            .ok()?
+            .host()
+            .to_string(),
+    };
+    Some(host.to_ascii_lowercase())
+}
+
+/// Loads the data directory into storage and starts whatever reloads it,
+/// returning the reloader if storage can be reloaded without a restart.
+async fn init_data<T>(
+    store: &T,
+    config: &ServiceConfig,
+) -> Result<Option<Arc<DataReloader>>, RdapServerError>
+where
+    T: StoreOps + Clone + 'static,
+{
+    load_data(config, store, false).await?;
     if config.auto_reload {
-        tokio::spawn(reload_data(store, config.clone()));
+        tokio::spawn(reload_data(Box::new(store.clone()), config.clone()));
+    }
+    if !config.watch_data && !config.reload_endpoint {
+        return Ok(None);
     }
-    Ok(())
+    let reloader = Arc::new(DataReloader::new(Box::new(store.clone()), config.clone()));
+    if config.watch_data {
+        tokio::spawn(watch_data(reloader.clone(), config.watch_debounce));
+    }
+    Ok(Some(reloader))
 }
 
 fn app_router<T>(state: AppState<T>) -> Router
@@ -125,9 +409,23 @@ where
     T: StoreOps + Clone + Send + Sync + 'static,
     AppState<T>: ServiceState,
 {
+    let reload = state.reload_endpoint && state.reloader.is_some();
     let state = Arc::new(state) as DynServiceState;
-    Router::new()
-        .nest("/rdap", rdap_router())
+    let mut router = Router::new().nest("/rdap", rdap_router());
+    if reload {
+        router = router.route(RELOAD_ENDPOINT, post(reload_endpoint));
+    }
+    router
+        .layer(from_fn_with_state(state.clone(), deny_invalid_rdap))
+        .layer(from_fn_with_state(state.clone(), proxy_rdap))
+        .layer(from_fn_with_state(state.clone(), filter_rdap))
+        .layer(from_fn_with_state(state.clone(), customize_error_body))
+        .layer(from_fn(negotiate_rdap_x))
+        .layer(from_fn(conditional_get))
+        .layer(from_fn_with_state(state.clone(), compress_response))
+        .layer(from_fn(serve_head))
+        .layer(from_fn_with_state(state.clone(), rate_limit))
+        .layer(from_fn(observe_requests))
         .layer(
             ServiceBuilder::new()
                 .layer(HandleErrorLayer::new(|error: BoxError| async move {
@@ -141,11 +439,11 @@ where
                     }
                 }))
                 .timeout(Duration::from_secs(10))
-                .layer(TraceLayer::new_for_http())
+                .layer(TraceLayer::new_for_http().make_span_with(request_span))
                 .layer(
                     CorsLayer::new()
                         .allow_origin(Any)
-                        .allow_methods(vec![Method::GET])
+                        .allow_methods(vec![Method::GET, Method::HEAD])
                         .allow_headers(Any),
                 )
                 .into_inner(),
@@ -153,6 +451,20 @@ where
         .with_state(state)
 }
 
+/// Makes the span of a request, which with the `otel` feature continues the trace
+/// of the client if the request carries its trace context.
+fn request_span<B>(request: &http::Request<B>) -> Span {
+    let span = info_span!(
+        "request",
+        method = %request.method(),
+        uri = %request.uri(),
+        version = ?request.version(),
+    );
+    #[cfg(feature = "otel")]
+    icann_rdap_client::otel::set_parent_from_headers(&span, request.headers());
+    span
+}
+
 pub(crate) type DynServiceState = Arc<dyn ServiceState + Send + Sync>;
 
 #[async_trait]
@@ -163,6 +475,30 @@ pub trait ServiceState: std::fmt::Debug {
     /// If returns true, this indicates the server has been configured to do
     /// bootstrapping.
     fn get_bootstrap(&self) -> bool;
+
+    /// If returns a size, search results are divided into pages of that size.
+    fn get_search_page_size(&self) -> Option<usize>;
+
+    /// Gets the customizations of error bodies.
+    fn get_error_bodies(&self) -> &ErrorBodies;
+
+    /// Gets the filter of the members of RDAP responses, if there is one.
+    fn get_response_filter(&self) -> Option<&ResponseFilter>;
+
+    /// Gets the response compression.
+    fn get_compression(&self) -> &Compression;
+
+    /// If returns true, RDAP having errors found by the check system is not served.
+    fn get_deny_invalid_rdap(&self) -> bool;
+
+    /// Gets the reloader of storage, if storage can be reloaded without a restart.
+    fn get_reloader(&self) -> Option<&DataReloader>;
+
+    /// Gets the rate limiter of client requests, if requests are rate limited.
+    fn get_rate_limiter(&self) -> Option<&RateLimiter>;
+
+    /// Gets the proxy of lookups not found in storage, if the server is a proxy.
+    fn get_proxy(&self) -> Option<&Proxy>;
 }
 
 /// State that is passed to the HTTP service router and used by functions
@@ -171,6 +507,24 @@ pub trait ServiceState: std::fmt::Debug {
 pub struct AppState<T: StoreOps + Clone + Send + Sync + 'static> {
     pub storage: T,
     pub bootstrap: bool,
+    pub search_page_size: Option<usize>,
+    pub error_bodies: ErrorBodies,
+    pub response_filter: Option<ResponseFilter>,
+    pub compression: Compression,
+    pub deny_invalid_rdap: bool,
+    pub reloader: Option<Arc<DataReloader>>,
+    pub reload_endpoint: bool,
+    pub rate_limiter: Option<Arc<RateLimiter>>,
+    pub proxy: Option<Arc<Proxy>>,
+}
+
+/// Makes the proxy of a service, if it is configured to be one.
+fn new_proxy(service_config: &ServiceConfig) -> Result<Option<Arc<Proxy>>, RdapServerError> {
+    let Some(config) = &service_config.proxy else {
+        return Ok(None);
+    };
+    let proxy = Proxy::new(config.clone(), service_config.bootstrap_registries.as_ref())?;
+    Ok(Some(Arc::new(proxy)))
 }
 
 impl AppState<Mem> {
@@ -180,10 +534,22 @@ impl AppState<Mem> {
     ) -> Result<Self, RdapServerError> {
         let storage = Mem::new(config);
         storage.init().await?;
-        init_data(Box::new(storage.clone()), service_config).await?;
+        let reloader = init_data(&storage, service_config).await?;
         Ok(Self {
             storage,
             bootstrap: service_config.bootstrap,
+            search_page_size: service_config.search_page_size,
+            error_bodies: service_config.error_bodies.clone(),
+            response_filter: service_config.response_filter.clone(),
+            compression: Compression::new(service_config.compression.clone()),
+            deny_invalid_rdap: service_config.deny_invalid_rdap,
+            reloader,
+            reload_endpoint: service_config.reload_endpoint,
+            rate_limiter: service_config
+                .rate_limit
+                .clone()
+                .map(|config| Arc::new(RateLimiter::new(config))),
+            proxy: new_proxy(service_config)?,
         })
     }
 }
@@ -201,10 +567,22 @@ impl AppState<Pg> {
     ) -> Result<Self, RdapServerError> {
         let storage = Pg::new(config).await?;
         storage.init().await?;
-        init_data(Box::new(storage.clone()), service_config).await?;
+        let reloader = init_data(&storage, service_config).await?;
         Ok(Self {
             storage,
             bootstrap: service_config.bootstrap,
+            search_page_size: service_config.search_page_size,
+            error_bodies: service_config.error_bodies.clone(),
+            response_filter: service_config.response_filter.clone(),
+            compression: Compression::new(service_config.compression.clone()),
+            deny_invalid_rdap: service_config.deny_invalid_rdap,
+            reloader,
+            reload_endpoint: service_config.reload_endpoint,
+            rate_limiter: service_config
+                .rate_limit
+                .clone()
+                .map(|config| Arc::new(RateLimiter::new(config))),
+            proxy: new_proxy(service_config)?,
         })
     }
 }
@@ -215,6 +593,86 @@ impl std::fmt::Debug for AppState<Pg> {
     }
 }
 
+impl AppState<Arc<DynStoreOps>> {
+    pub async fn new_backend(
+        name: &str,
+        common_config: CommonConfig,
+        service_config: &ServiceConfig,
+    ) -> Result<Self, RdapServerError> {
+        let backend = storage_backend(name).ok_or_else(|| {
+            RdapServerError::Config(format!("storage backend '{name}' is not registered"))
+        })?;
+        let storage = backend.new_store(common_config).await?;
+        storage.init().await?;
+        let reloader = init_data(&storage, service_config).await?;
+        Ok(Self {
+            storage,
+            bootstrap: service_config.bootstrap,
+            search_page_size: service_config.search_page_size,
+            error_bodies: service_config.error_bodies.clone(),
+            response_filter: service_config.response_filter.clone(),
+            compression: Compression::new(service_config.compression.clone()),
+            deny_invalid_rdap: service_config.deny_invalid_rdap,
+            reloader,
+            reload_endpoint: service_config.reload_endpoint,
+            rate_limiter: service_config
+                .rate_limit
+                .clone()
+                .map(|config| Arc::new(RateLimiter::new(config))),
+            proxy: new_proxy(service_config)?,
+        })
+    }
+}
+
+impl std::fmt::Debug for AppState<Arc<DynStoreOps>> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("AppState<Arc<DynStoreOps>>").finish()
+    }
+}
+
+#[async_trait]
+impl ServiceState for AppState<Arc<DynStoreOps>> {
+    async fn get_storage(&self) -> Result<&dyn StoreOps, RdapServerError> {
+        Ok(&self.storage)
+    }
+
+    fn get_bootstrap(&self) -> bool {
+        self.bootstrap
+    }
+
+    fn get_search_page_size(&self) -> Option<usize> {
+        self.search_page_size
+    }
+
+    fn get_error_bodies(&self) -> &ErrorBodies {
+        &self.error_bodies
+    }
+
+    fn get_response_filter(&self) -> Option<&ResponseFilter> {
+        self.response_filter.as_ref()
+    }
+
+    fn get_compression(&self) -> &Compression {
+        &self.compression
+    }
+
+    fn get_deny_invalid_rdap(&self) -> bool {
+        self.deny_invalid_rdap
+    }
+
+    fn get_reloader(&self) -> Option<&DataReloader> {
+        self.reloader.as_deref()
+    }
+
+    fn get_rate_limiter(&self) -> Option<&RateLimiter> {
+        self.rate_limiter.as_deref()
+    }
+
+    fn get_proxy(&self) -> Option<&Proxy> {
+        self.proxy.as_deref()
+    }
+}
+
 #[async_trait]
 impl ServiceState for AppState<Pg> {
     async fn get_storage(&self) -> Result<&dyn StoreOps, RdapServerError> {
@@ -224,6 +682,38 @@ impl ServiceState for AppState<Pg> {
     fn get_bootstrap(&self) -> bool {
         self.bootstrap
     }
+
+    fn get_search_page_size(&self) -> Option<usize> {
+        self.search_page_size
+    }
+
+    fn get_error_bodies(&self) -> &ErrorBodies {
+        &self.error_bodies
+    }
+
+    fn get_response_filter(&self) -> Option<&ResponseFilter> {
+        self.response_filter.as_ref()
+    }
+
+    fn get_compression(&self) -> &Compression {
+        &self.compression
+    }
+
+    fn get_deny_invalid_rdap(&self) -> bool {
+        self.deny_invalid_rdap
+    }
+
+    fn get_reloader(&self) -> Option<&DataReloader> {
+        self.reloader.as_deref()
+    }
+
+    fn get_rate_limiter(&self) -> Option<&RateLimiter> {
+        self.rate_limiter.as_deref()
+    }
+
+    fn get_proxy(&self) -> Option<&Proxy> {
+        self.proxy.as_deref()
+    }
 }
 
 #[async_trait]
@@ -235,4 +725,36 @@ impl ServiceState for AppState<Mem> {
     fn get_bootstrap(&self) -> bool {
         self.bootstrap
     }
+
+    fn get_search_page_size(&self) -> Option<usize> {
+        self.search_page_size
+    }
+
+    fn get_error_bodies(&self) -> &ErrorBodies {
+        &self.error_bodies
+    }
+
+    fn get_response_filter(&self) -> Option<&ResponseFilter> {
+        self.response_filter.as_ref()
+    }
+
+    fn get_compression(&self) -> &Compression {
+        &self.compression
+    }
+
+    fn get_deny_invalid_rdap(&self) -> bool {
+        self.deny_invalid_rdap
+    }
+
+    fn get_reloader(&self) -> Option<&DataReloader> {
+        self.reloader.as_deref()
+    }
+
+    fn get_rate_limiter(&self) -> Option<&RateLimiter> {
+        self.rate_limiter.as_deref()
+    }
+
+    fn get_proxy(&self) -> Option<&Proxy> {
+        self.proxy.as_deref()
+    }
 }
@@ -0,0 +1,221 @@
+//! Storage backends that are registered by name.
+//!
+//! Storage implemented outside of this crate is made available to the server by
+//! registering a [StorageBackend] before the server configuration is read. The
+//! server then selects the backend when the storage type is the name of the backend.
+//!
+//! ```rust,no_run
+//! use std::sync::Arc;
+//!
+//! use async_trait::async_trait;
+//! use icann_rdap_srv::{
+//!     config::StorageType,
+//!     error::RdapServerError,
+//!     storage::{
+//!         backend::{register_storage_backend, StorageBackend},
+//!         mem::{config::MemConfig, ops::Mem},
+//!         CommonConfig, DynStoreOps,
+//!     },
+//! };
+//!
+//! struct MyBackend;
+//!
+//! #[async_trait]
+//! impl StorageBackend for MyBackend {
+//!     fn name(&self) -> &str {
+//!         "my-backend"
+//!     }
+//!
+//!     async fn new_store(
+//!         &self,
+//!         common_config: CommonConfig,
+//!     ) -> Result<Arc<DynStoreOps>, RdapServerError> {
+//!         // a real backend would implement StoreOps for its own storage.
+//!         let config = MemConfig::builder().common_config(common_config).build();
+//!         Ok(Arc::new(Mem::new(config)))
+//!     }
+//! }
+//!
+//! register_storage_backend(MyBackend);
+//! // with RDAP_SRV_STORAGE=my-backend, the storage type is the registered backend.
+//! let storage_type = StorageType::new_from_env().unwrap();
+//! ```
+
+use std::{
+    collections::HashMap,
+    sync::{Arc, LazyLock, RwLock},
+};
+
+use {
+    async_trait::async_trait,
+    icann_rdap_common::response::{RdapResponse, ReverseSearchProperty, RirSearchRelation},
+};
+
+use crate::error::RdapServerError;
+
+use super::{CommonConfig, DynStoreOps, StoreOps, StoreUpdate, TxHandle};
+
+/// The registered storage backends by name.
+static STORAGE_BACKENDS: LazyLock<RwLock<HashMap<String, Arc<dyn StorageBackend>>>> =
+    LazyLock::new(<_>::default);
+
+/// A factory of stores for a storage backend.
+#[async_trait]
+pub trait StorageBackend: Send + Sync {
+    /// The name of the backend, which is the storage type that selects it.
+    fn name(&self) -> &str;
+
+    /// Creates a new store.
+    ///
+    /// Configuration specific to the backend, such as connection strings, is
+    /// up to the backend. The server initializes the store with [StoreOps::init].
+    async fn new_store(
+        &self,
+        common_config: CommonConfig,
+    ) -> Result<Arc<DynStoreOps>, RdapServerError>;
+}
+
+/// Registers a storage backend, replacing any backend registered with the same name.
+pub fn register_storage_backend(backend: impl StorageBackend + 'static) {
+    let backend: Arc<dyn StorageBackend> = Arc::new(backend);
+    STORAGE_BACKENDS
+        .write()
+        .expect("storage backends lock poisoned")
+        .insert(backend.name().to_string(), backend);
+}
+
+/// Gets the storage backend registered with the name.
+pub fn storage_backend(name: &str) -> Option<Arc<dyn StorageBackend>> {
+    STORAGE_BACKENDS
+        .read()
+        .expect("storage backends lock poisoned")
+        .get(name)
+        .cloned()
+}
+
+/// A store created by a storage backend is used by the server like the built-in stores.
+#[async_trait]
+impl StoreOps for Arc<DynStoreOps> {
+    async fn init(&self) -> Result<(), RdapServerError> {
+        self.as_ref().init().await
+    }
+
+    async fn new_tx(&self) -> Result<Box<dyn TxHandle>, RdapServerError> {
+        self.as_ref().new_tx().await
+    }
+
+    async fn new_truncate_tx(&self) -> Result<Box<dyn TxHandle>, RdapServerError> {
+        self.as_ref().new_truncate_tx().await
+    }
+
+    async fn batch_update(
+        &self,
+        updates: &[StoreUpdate],
+        truncate: bool,
+    ) -> Result<(), RdapServerError> {
+        self.as_ref().batch_update(updates, truncate).await
+    }
+
+    async fn get_domain_by_ldh(&self, ldh: &str) -> Result<RdapResponse, RdapServerError> {
+        self.as_ref().get_domain_by_ldh(ldh).await
+    }
+
+    async fn get_domain_by_unicode(&self, unicode: &str) -> Result<RdapResponse, RdapServerError> {
+        self.as_ref().get_domain_by_unicode(unicode).await
+    }
+
+    async fn get_entity_by_handle(&self, handle: &str) -> Result<RdapResponse, RdapServerError> {
+        self.as_ref().get_entity_by_handle(handle).await
+    }
+
+    async fn get_nameserver_by_ldh(&self, ldh: &str) -> Result<RdapResponse, RdapServerError> {
+        self.as_ref().get_nameserver_by_ldh(ldh).await
+    }
+
+    async fn get_autnum_by_num(&self, num: u32) -> Result<RdapResponse, RdapServerError> {
+        self.as_ref().get_autnum_by_num(num).await
+    }
+
+    async fn get_network_by_ipaddr(&self, ipaddr: &str) -> Result<RdapResponse, RdapServerError> {
+        self.as_ref().get_network_by_ipaddr(ipaddr).await
+    }
+
+    async fn get_network_by_cidr(&self, cidr: &str) -> Result<RdapResponse, RdapServerError> {
+        self.as_ref().get_network_by_cidr(cidr).await
+    }
+
+    async fn get_srv_help(&self, host: Option<&str>) -> Result<RdapResponse, RdapServerError> {
+        self.as_ref().get_srv_help(host).await
+    }
+
+    async fn search_domains_by_name(&self, name: &str) -> Result<RdapResponse, RdapServerError> {
+        self.as_ref().search_domains_by_name(name).await
+    }
+
+    async fn search_nameservers_by_name(
+        &self,
+        name: &str,
+    ) -> Result<RdapResponse, RdapServerError> {
+        self.as_ref().search_nameservers_by_name(name).await
+    }
+
+    async fn search_nameservers_by_ip(&self, ip: &str) -> Result<RdapResponse, RdapServerError> {
+        self.as_ref().search_nameservers_by_ip(ip).await
+    }
+
+    async fn search_entities_by_fn(
+        &self,
+        full_name: &str,
+    ) -> Result<RdapResponse, RdapServerError> {
+        self.as_ref().search_entities_by_fn(full_name).await
+    }
+
+    async fn search_entities_by_handle(
+        &self,
+        handle: &str,
+    ) -> Result<RdapResponse, RdapServerError> {
+        self.as_ref().search_entities_by_handle(handle).await
+    }
+
+    async fn search_domains_by_entity(
+        &self,
+        property: ReverseSearchProperty,
+        value: &str,
+    ) -> Result<RdapResponse, RdapServerError> {
+        self.as_ref()
+            .search_domains_by_entity(property, value)
+            .await
+    }
+
+    async fn search_networks_by_name(&self, name: &str) -> Result<RdapResponse, RdapServerError> {
+        self.as_ref().search_networks_by_name(name).await
+    }
+
+    async fn search_networks_by_handle(
+        &self,
+        handle: &str,
+    ) -> Result<RdapResponse, RdapServerError> {
+        self.as_ref().search_networks_by_handle(handle).await
+    }
+
+    async fn search_autnums_by_name(&self, name: &str) -> Result<RdapResponse, RdapServerError> {
+        self.as_ref().search_autnums_by_name(name).await
+    }
+
+    async fn search_autnums_by_handle(
+        &self,
+        handle: &str,
+    ) -> Result<RdapResponse, RdapServerError> {
+        self.as_ref().search_autnums_by_handle(handle).await
+    }
+
+    async fn search_networks_by_relation(
+        &self,
+        relation: RirSearchRelation,
+        cidr: &str,
+    ) -> Result<RdapResponse, RdapServerError> {
+        self.as_ref()
+            .search_networks_by_relation(relation, cidr)
+            .await
+    }
+}
@@ -7,7 +7,7 @@ use std::{
 use {
     buildstructor::Builder,
     icann_rdap_common::{
-        prelude::Numberish,
+        prelude::{Numberish, ToResponse},
         response::{
             Autnum, Cidr0Cidr, Domain, Entity, GetSelfLink, Nameserver, Network, RdapResponse,
             SelfLink, V4Cidr, V6Cidr,
@@ -18,13 +18,15 @@ use {
     serde_json::Value,
     strum_macros::Display,
     tokio::time::sleep,
-    tracing::{debug, info, warn},
+    tracing::{debug, error, info, warn},
 };
 
 use crate::{
     config::ServiceConfig,
     error::RdapServerError,
-    storage::{StoreOps, TxHandle},
+    hooks::call_reload_hooks,
+    rdap::validation::has_denied_checks,
+    storage::{StoreOps, StoreUpdate},
 };
 
 pub const UPDATE: &str = "update";
@@ -179,11 +181,7 @@ pub async fn load_data(
     let mut json_count: usize = 0;
     let mut template_count: usize = 0;
     let mut srvhelp_count: usize = 0;
-    let mut tx = if truncate {
-        store.new_truncate_tx().await?
-    } else {
-        store.new_tx().await?
-    };
+    let mut updates: Vec<StoreUpdate> = vec![];
     let path = PathBuf::from(&config.data_dir);
     if !path.exists() || !path.is_dir() {
         warn!(
@@ -196,23 +194,30 @@ pub async fn load_data(
     let mut entries = tokio::fs::read_dir(path).await?;
     while let Some(entry) = entries.next_entry().await? {
         let entry_path = entry.path();
+        // directories, such as those of virtual hosts, are not data.
+        if entry_path.is_dir() {
+            continue;
+        }
+        let loaded_from = updates.len();
         let contents = tokio::fs::read_to_string(&entry_path).await?;
         if entry_path.extension().is_some_and(|ext| ext == "template") {
-            load_rdap_template(&contents, &entry_path.to_string_lossy(), &mut tx).await?;
+            load_rdap_template(&contents, &entry_path.to_string_lossy(), &mut updates)?;
             template_count += 1;
         } else if entry_path.extension().is_some_and(|ext| ext == "json") {
-            load_rdap(&contents, &entry_path.to_string_lossy(), &mut tx).await?;
+            load_rdap(&contents, &entry_path.to_string_lossy(), &mut updates)?;
             json_count += 1;
         } else if entry_path.extension().is_some_and(|ext| ext == "help") {
             load_srvhelp(
                 &contents,
                 &entry_path.to_string_lossy(),
                 &entry.file_name().to_string_lossy(),
-                &mut tx,
-            )
-            .await?;
+                &mut updates,
+            )?;
             srvhelp_count += 1;
         }
+        if config.deny_invalid_rdap {
+            remove_invalid_updates(&mut updates, loaded_from, &entry_path.to_string_lossy());
+        }
     }
 
     info!("{json_count} RDAP JSON files loaded.");
@@ -221,15 +226,40 @@ pub async fn load_data(
     if json_count == 0 && template_count == 0 && srvhelp_count == 0 {
         warn!("No data loaded. Server has no content to serve.");
     }
-    tx.commit().await?;
+    // all the files are read before storage is updated so that a bad file
+    // leaves storage as it was.
+    store.batch_update(&updates, truncate).await?;
     Ok(())
 }
 
+/// Removes the updates, starting at an index, that add RDAP having errors found by the check system.
+fn remove_invalid_updates(updates: &mut Vec<StoreUpdate>, from: usize, path_name: &str) {
+    let loaded = updates.split_off(from);
+    for update in loaded {
+        let rdap = match &update {
+            StoreUpdate::AddDomain(domain) => Some(domain.as_ref().clone().to_response()),
+            StoreUpdate::AddEntity(entity) => Some(entity.as_ref().clone().to_response()),
+            StoreUpdate::AddNameserver(nameserver) => {
+                Some(nameserver.as_ref().clone().to_response())
+            }
+            StoreUpdate::AddAutnum(autnum) => Some(autnum.as_ref().clone().to_response()),
+            StoreUpdate::AddNetwork(network) => Some(network.as_ref().clone().to_response()),
+            StoreUpdate::AddSrvHelp { help, .. } => Some(help.as_ref().clone().to_response()),
+            _ => None,
+        };
+        if rdap.is_some_and(|rdap| has_denied_checks(&rdap, path_name)) {
+            warn!("Not loading RDAP from {path_name} because it has errors.");
+        } else {
+            updates.push(update);
+        }
+    }
+}
+
 /// Loads the RDAP JSON files and puts them in storage.
-async fn load_rdap(
+fn load_rdap(
     contents: &str,
     path_name: &str,
-    tx: &mut Box<dyn TxHandle>,
+    updates: &mut Vec<StoreUpdate>,
 ) -> Result<(), RdapServerError> {
     debug!("loading {path_name} into storage");
     let json = serde_json::from_str::<Value>(contents);
@@ -240,23 +270,24 @@ async fn load_rdap(
     let Ok(rdap) = rdap else {
         return Err(RdapServerError::NonRdapJsonFile(path_name.to_owned()));
     };
-    match rdap {
-        RdapResponse::Entity(entity) => tx.add_entity(&entity).await,
-        RdapResponse::Domain(domain) => tx.add_domain(&domain).await,
-        RdapResponse::Nameserver(nameserver) => tx.add_nameserver(&nameserver).await,
-        RdapResponse::Autnum(autnum) => tx.add_autnum(&autnum).await,
-        RdapResponse::Network(network) => tx.add_network(&network).await,
+    let update = match rdap {
+        RdapResponse::Entity(entity) => StoreUpdate::AddEntity(entity),
+        RdapResponse::Domain(domain) => StoreUpdate::AddDomain(domain),
+        RdapResponse::Nameserver(nameserver) => StoreUpdate::AddNameserver(nameserver),
+        RdapResponse::Autnum(autnum) => StoreUpdate::AddAutnum(autnum),
+        RdapResponse::Network(network) => StoreUpdate::AddNetwork(network),
         _ => return Err(RdapServerError::NonRdapJsonFile(path_name.to_owned())),
-    }?;
+    };
+    updates.push(update);
     Ok(())
 }
 
 /// Loads the RDAP HELP files and puts them in storage.
-async fn load_srvhelp(
+fn load_srvhelp(
     contents: &str,
     path_name: &str,
     file_name: &str,
-    tx: &mut Box<dyn TxHandle>,
+    updates: &mut Vec<StoreUpdate>,
 ) -> Result<(), RdapServerError> {
     debug!("loading {path_name} into storage");
     let Some(host) = file_name.strip_suffix(".help") else {
@@ -272,18 +303,21 @@ async fn load_srvhelp(
         return Err(RdapServerError::NonRdapJsonFile(path_name.to_owned()));
     };
     match rdap {
-        RdapResponse::Help(srvhelp) => tx.add_srv_help(&srvhelp, Some(&host)).await,
+        RdapResponse::Help(srvhelp) => updates.push(StoreUpdate::AddSrvHelp {
+            help: srvhelp,
+            host: Some(host),
+        }),
         _ => return Err(RdapServerError::NonRdapJsonFile(path_name.to_owned())),
-    }?;
+    };
     Ok(())
 }
 
 /// Loads the template files, creates RDAP objects from the templates, and puts them
 /// into storage.
-async fn load_rdap_template(
+fn load_rdap_template(
     contents: &str,
     path_name: &str,
-    tx: &mut Box<dyn TxHandle>,
+    updates: &mut Vec<StoreUpdate>,
 ) -> Result<(), RdapServerError> {
     debug!("processing {path_name} template");
     let json = serde_json::from_str::<Template>(contents);
@@ -295,10 +329,10 @@ async fn load_rdap_template(
                     match &domain {
                         DomainOrError::DomainObject(domain) => {
                             let domain = make_domain_from_template(domain, id);
-                            tx.add_domain(&domain).await?;
+                            updates.push(StoreUpdate::AddDomain(Box::new(domain)));
                         }
                         DomainOrError::ErrorResponse(error) => {
-                            tx.add_domain_err(&id, error).await?;
+                            updates.push(StoreUpdate::AddDomainErr(id, error.clone()));
                         }
                     };
                 }
@@ -309,10 +343,10 @@ async fn load_rdap_template(
                     match &entity {
                         EntityOrError::EntityObject(entity) => {
                             let entity = make_entity_from_template(entity, id);
-                            tx.add_entity(&entity).await?;
+                            updates.push(StoreUpdate::AddEntity(Box::new(entity)));
                         }
                         EntityOrError::ErrorResponse(error) => {
-                            tx.add_entity_err(&id, error).await?;
+                            updates.push(StoreUpdate::AddEntityErr(id, error.clone()));
                         }
                     };
                 }
@@ -323,10 +357,10 @@ async fn load_rdap_template(
                     match &nameserver {
                         NameserverOrError::NameserverObject(nameserver) => {
                             let nameserver = make_nameserver_from_template(nameserver, id);
-                            tx.add_nameserver(&nameserver).await?;
+                            updates.push(StoreUpdate::AddNameserver(Box::new(nameserver)));
                         }
                         NameserverOrError::ErrorResponse(error) => {
-                            tx.add_nameserver_err(&id, error).await?;
+                            updates.push(StoreUpdate::AddNameserverErr(id, error.clone()));
                         }
                     };
                 }
@@ -337,10 +371,10 @@ async fn load_rdap_template(
                     match &autnum {
                         AutnumOrError::AutnumObject(autnum) => {
                             let autnum = make_autnum_from_template(autnum, id);
-                            tx.add_autnum(&autnum).await?;
+                            updates.push(StoreUpdate::AddAutnum(Box::new(autnum)));
                         }
                         AutnumOrError::ErrorResponse(error) => {
-                            tx.add_autnum_err(&id, error).await?;
+                            updates.push(StoreUpdate::AddAutnumErr(id, error.clone()));
                         }
                     };
                 }
@@ -351,10 +385,10 @@ async fn load_rdap_template(
                     match &network {
                         NetworkOrError::NetworkObject(network) => {
                             let network = make_network_from_template(network, id)?;
-                            tx.add_network(&network).await?;
+                            updates.push(StoreUpdate::AddNetwork(Box::new(network)));
                         }
                         NetworkOrError::ErrorResponse(error) => {
-                            tx.add_network_err(&id, error).await?;
+                            updates.push(StoreUpdate::AddNetworkErr(id, error.clone()));
                         }
                     };
                 }
@@ -383,7 +417,11 @@ pub(crate) async fn reload_data(
             if modified > last_time {
                 last_time = modified;
                 info!("Data being updated.");
-                load_data(&config, &*store, false).await?;
+                let result = load_data(&config, &*store, false).await;
+                call_reload_hooks(&config.data_dir, false, &result);
+                if let Err(e) = result {
+                    error!("Data not updated: {e}");
+                }
             }
         };
         let reload_meta = tokio::fs::metadata(&reload_path).await;
@@ -392,7 +430,11 @@ pub(crate) async fn reload_data(
             if modified > last_time {
                 last_time = modified;
                 info!("Data being reloaded.");
-                load_data(&config, &*store, true).await?;
+                let result = load_data(&config, &*store, true).await;
+                call_reload_hooks(&config.data_dir, true, &result);
+                if let Err(e) = result {
+                    error!("Data not reloaded: {e}");
+                }
             }
         };
     }
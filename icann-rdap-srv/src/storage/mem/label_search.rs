@@ -39,38 +39,7 @@ impl<T: Clone> SearchLabels<T> {
 
     /// Search values based on a label search
     pub(crate) fn search(&self, search: &str) -> Result<Vec<T>, RdapServerError> {
-        // search string is invalid if it doesn't have only one asterisk ('*')
-        if search.chars().filter(|c| *c == '*').count() != 1 {
-            return Err(RdapServerError::InvalidArg(
-                "Search string must contain one and only one asterisk ('*')".to_string(),
-            ));
-        }
-        // asterisk must not be followed by a character other than dot ('.')
-        let star = search
-            .find('*')
-            .expect("internal error. previous check should have caught this");
-        if star != search.chars().count() - 1
-            && search
-                .chars()
-                .nth(star + 1)
-                .expect("should have been short circuited")
-                != '.'
-        {
-            return Err(RdapServerError::InvalidArg(
-                "Search string asterisk ('*') must terminate domain label".to_string(),
-            ));
-        }
-
-        let parts = search
-            .split_once('*')
-            .expect("internal error. previous check should insure there is an asterisk");
-
-        // this is a limitation of the trie in that it requires a prefix
-        if parts.0.is_empty() {
-            return Err(RdapServerError::InvalidArg(
-                "Search string must have a prefix".to_string(),
-            ));
-        }
+        let parts = split_label_search(search)?;
 
         if let Some(trie) = self.label_suffixes.get(parts.1.trim_start_matches('.')) {
             if let Some(entries) = trie.get_suffixes_values(parts.0) {
@@ -88,6 +57,50 @@ impl<T: Clone> SearchLabels<T> {
     }
 }
 
+/// Validates a label search string as specified in RFC 9082, returning
+/// the text before and after the asterisk ('*').
+pub(crate) fn split_label_search(search: &str) -> Result<(&str, &str), RdapServerError> {
+    // search string is invalid if it doesn't have only one asterisk ('*')
+    if search.chars().filter(|c| *c == '*').count() != 1 {
+        return Err(RdapServerError::InvalidArg(
+            "Search string must contain one and only one asterisk ('*')".to_string(),
+        ));
+    }
+    // asterisk must not be followed by a character other than dot ('.')
+    let star = search
+        .find('*')
+        .expect("internal error. previous check should have caught this");
+    if star != search.chars().count() - 1
+        && search
+            .chars()
+            .nth(star + 1)
+            .expect("should have been short circuited")
+            != '.'
+    {
+        return Err(RdapServerError::InvalidArg(
+            "Search string asterisk ('*') must terminate domain label".to_string(),
+        ));
+    }
+
+    let parts = search
+        .split_once('*')
+        .expect("internal error. previous check should insure there is an asterisk");
+
+    // this is a limitation of the trie in that it requires a prefix
+    if parts.0.is_empty() {
+        return Err(RdapServerError::InvalidArg(
+            "Search string must have a prefix".to_string(),
+        ));
+    }
+
+    Ok(parts)
+}
+
+/// Returns true if a name matches the text before and after the asterisk of a label search.
+pub(crate) fn label_search_matches(prefix: &str, suffix: &str, name: &str) -> bool {
+    name.len() >= prefix.len() + suffix.len() && name.starts_with(prefix) && name.ends_with(suffix)
+}
+
 #[cfg(test)]
 #[allow(non_snake_case)]
 mod tests {
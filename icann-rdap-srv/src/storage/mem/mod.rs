@@ -1,6 +1,7 @@
 #![allow(dead_code)] // TODO remove
 
 pub mod config;
-mod label_search;
+pub(crate) mod label_search;
+pub(crate) mod name_index;
 pub mod ops;
 pub mod tx;
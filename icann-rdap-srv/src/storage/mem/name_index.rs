@@ -0,0 +1,90 @@
+use std::collections::{BTreeMap, BTreeSet};
+
+use crate::storage::SearchWord;
+
+/// An index of words to the keys of the values having them, for full-text search.
+///
+/// For entities, the words are of the full names and organization names and the keys are handles.
+#[derive(Debug, Default, Clone)]
+pub(crate) struct NameIndex {
+    words: BTreeMap<String, BTreeSet<String>>,
+}
+
+impl NameIndex {
+    /// Inserts the words of the value with the key.
+    pub(crate) fn insert(&mut self, key: &str, words: Vec<String>) {
+        for word in words {
+            self.words.entry(word).or_default().insert(key.to_owned());
+        }
+    }
+
+    /// Gets the keys, in order, of the values having all the search words.
+    ///
+    /// No keys are returned if there are no search words.
+    pub(crate) fn search(&self, search: &[SearchWord]) -> Vec<String> {
+        let mut keys: Option<BTreeSet<String>> = None;
+        for search_word in search {
+            let matches = if search_word.prefix {
+                self.words
+                    .range(search_word.word.clone()..)
+                    .take_while(|(word, _)| word.starts_with(&search_word.word))
+                    .flat_map(|(_, keys)| keys.iter().cloned())
+                    .collect::<BTreeSet<String>>()
+            } else {
+                self.words
+                    .get(&search_word.word)
+                    .cloned()
+                    .unwrap_or_default()
+            };
+            let matches = match keys {
+                Some(keys) => keys.intersection(&matches).cloned().collect(),
+                None => matches,
+            };
+            if matches.is_empty() {
+                return vec![];
+            }
+            keys = Some(matches);
+        }
+        keys.unwrap_or_default().into_iter().collect()
+    }
+}
+
+#[cfg(test)]
+#[allow(non_snake_case)]
+mod tests {
+
+    use rstest::rstest;
+
+    use crate::storage::split_search_words;
+
+    use super::NameIndex;
+
+    #[rstest]
+    #[case("bob", vec!["b"])]
+    #[case("BOB Smith", vec!["b"])]
+    #[case("acme", vec!["a", "b"])]
+    #[case("acme-corp", vec!["a"])]
+    #[case("ac*", vec!["a", "b"])]
+    #[case("acme co*", vec!["a"])]
+    #[case("ac", vec![])]
+    #[case("acme alice", vec![])]
+    #[case("*", vec![])]
+    fn GIVEN_index_WHEN_search_THEN_keys_with_all_words_found(
+        #[case] search: &str,
+        #[case] expected: Vec<&str>,
+    ) {
+        // GIVEN
+        let mut index = NameIndex::default();
+        index.insert("a", vec!["acme".to_string(), "corp".to_string()]);
+        index.insert(
+            "b",
+            vec!["acme".to_string(), "bob".to_string(), "smith".to_string()],
+        );
+
+        // WHEN
+        let actual = index.search(&split_search_words(search));
+
+        // THEN
+        assert_eq!(actual, expected);
+    }
+}
@@ -5,7 +5,11 @@ use {
     btree_range_map::RangeMap,
     icann_rdap_common::{
         prelude::ToResponse,
-        response::{Domain, DomainSearchResults, RdapResponse},
+        response::{
+            Autnum, AutnumSearchResults, Domain, DomainSearchResults, Entity, EntitySearchResults,
+            ExtensionId, IpSearchResults, Nameserver, NameserverSearchResults, Network,
+            ObjectCommonFields, RdapResponse, ReverseSearchProperty, RirSearchRelation,
+        },
     },
     ipnet::{IpNet, Ipv4Net, Ipv6Net},
     prefix_trie::PrefixMap,
@@ -15,10 +19,18 @@ use {
 use crate::{
     error::RdapServerError,
     rdap::response::{NOT_FOUND, NOT_IMPLEMENTED},
-    storage::{CommonConfig, StoreOps, TxHandle},
+    storage::{
+        entity_matches, nameserver_has_ip, search_matches, split_search_words, unique_networks,
+        CommonConfig, StoreOps, TxHandle,
+    },
 };
 
-use super::{config::MemConfig, label_search::SearchLabels, tx::MemTx};
+use super::{
+    config::MemConfig,
+    label_search::{label_search_matches, split_label_search, SearchLabels},
+    name_index::NameIndex,
+    tx::MemTx,
+};
 
 #[derive(Clone)]
 pub struct Mem {
@@ -30,6 +42,7 @@ pub struct Mem {
     pub(crate) idns: Arc<RwLock<HashMap<String, Arc<RdapResponse>>>>,
     pub(crate) nameservers: Arc<RwLock<HashMap<String, Arc<RdapResponse>>>>,
     pub(crate) entities: Arc<RwLock<HashMap<String, Arc<RdapResponse>>>>,
+    pub(crate) entities_by_name: Arc<RwLock<NameIndex>>,
     pub(crate) srvhelps: Arc<RwLock<HashMap<String, Arc<RdapResponse>>>>,
     pub(crate) config: MemConfig,
 }
@@ -45,10 +58,139 @@ impl Mem {
             idns: <_>::default(),
             nameservers: <_>::default(),
             entities: <_>::default(),
+            entities_by_name: <_>::default(),
             srvhelps: <_>::default(),
             config,
         }
     }
+
+    /// Searches entities by a property, using the same matching as reverse search.
+    async fn search_entities(
+        &self,
+        property: ReverseSearchProperty,
+        value: &str,
+    ) -> Result<RdapResponse, RdapServerError> {
+        let entities = self.entities.read().await;
+        let mut results = entities
+            .values()
+            .filter_map(|e| match e.as_ref() {
+                RdapResponse::Entity(e) => Some(e.as_ref()),
+                _ => None,
+            })
+            .filter(|e| entity_matches(e, property, value))
+            .cloned()
+            .collect::<Vec<Entity>>();
+        results.sort_by(|a, b| a.handle().cmp(&b.handle()));
+        let response = EntitySearchResults::response_obj()
+            .results(results)
+            .build()
+            .to_response();
+        Ok(response)
+    }
+
+    /// Searches entities by the words of their names using the name index.
+    async fn search_entities_by_name_index(
+        &self,
+        search: &str,
+    ) -> Result<RdapResponse, RdapServerError> {
+        let handles = self
+            .entities_by_name
+            .read()
+            .await
+            .search(&split_search_words(search));
+        let entities = self.entities.read().await;
+        let results = handles
+            .iter()
+            .filter_map(|handle| match entities.get(handle).map(Arc::as_ref) {
+                Some(RdapResponse::Entity(e)) => Some(e.as_ref().clone()),
+                _ => None,
+            })
+            .collect::<Vec<Entity>>();
+        let response = EntitySearchResults::response_obj()
+            .results(results)
+            .build()
+            .to_response();
+        Ok(response)
+    }
+}
+
+impl Mem {
+    /// Gets the networks by the CIDRs they are stored under, in order.
+    ///
+    /// A network not on a CIDR boundary is stored under many CIDRs.
+    async fn networks(&self) -> Vec<(IpNet, Arc<RdapResponse>)> {
+        let mut networks = self
+            .ip4
+            .read()
+            .await
+            .iter()
+            .map(|(net, network)| (IpNet::V4(*net), network.clone()))
+            .collect::<Vec<_>>();
+        networks.extend(
+            self.ip6
+                .read()
+                .await
+                .iter()
+                .map(|(net, network)| (IpNet::V6(*net), network.clone())),
+        );
+        networks.sort_by_key(|(net, _)| *net);
+        networks
+    }
+
+    /// Searches networks with a filter, in order.
+    async fn search_networks<F>(&self, filter: F) -> Result<RdapResponse, RdapServerError>
+    where
+        F: Fn(&Network) -> bool,
+    {
+        let results = networks_of(self.networks().await.iter().map(|(_, network)| network))
+            .into_iter()
+            .filter(|n| filter(n))
+            .collect::<Vec<Network>>();
+        Ok(ip_search_results(results))
+    }
+
+    /// Searches autnums with a filter, in order.
+    async fn search_autnums<F>(&self, filter: F) -> Result<RdapResponse, RdapServerError>
+    where
+        F: Fn(&Autnum) -> bool,
+    {
+        let autnums = self.autnums.read().await;
+        let mut results = autnums
+            .iter()
+            .filter_map(|(_, autnum)| match autnum.as_ref() {
+                RdapResponse::Autnum(a) => Some(a.as_ref()),
+                _ => None,
+            })
+            .filter(|a| filter(a))
+            .cloned()
+            .collect::<Vec<Autnum>>();
+        // an autnum is split when other autnums are within its range
+        results.dedup_by_key(|a| (a.start_autnum(), a.end_autnum()));
+        let response = AutnumSearchResults::response_obj()
+            .results(results)
+            .extension(ExtensionId::RirSearch1.to_extension())
+            .extension(ExtensionId::Autnums.to_extension())
+            .build()
+            .to_response();
+        Ok(response)
+    }
+}
+
+/// Takes the networks from responses, once for each network, ignoring errors.
+fn networks_of<'a>(responses: impl IntoIterator<Item = &'a Arc<RdapResponse>>) -> Vec<Network> {
+    unique_networks(responses.into_iter().filter_map(|r| match r.as_ref() {
+        RdapResponse::Network(n) => Some(n.as_ref().clone()),
+        _ => None,
+    }))
+}
+
+fn ip_search_results(results: Vec<Network>) -> RdapResponse {
+    IpSearchResults::response_obj()
+        .results(results)
+        .extension(ExtensionId::RirSearch1.to_extension())
+        .extension(ExtensionId::Ips.to_extension())
+        .build()
+        .to_response()
 }
 
 impl Default for Mem {
@@ -198,4 +340,174 @@ impl StoreOps for Mem {
             .to_response();
         Ok(response)
     }
+
+    async fn search_nameservers_by_name(
+        &self,
+        name: &str,
+    ) -> Result<RdapResponse, RdapServerError> {
+        let (prefix, suffix) = split_label_search(name)?;
+        let nameservers = self.nameservers.read().await;
+        let mut results = nameservers
+            .iter()
+            .filter(|(ldh, _)| label_search_matches(prefix, suffix, ldh))
+            .filter_map(|(_, ns)| match ns.as_ref() {
+                RdapResponse::Nameserver(ns) => Some(ns.as_ref().clone()),
+                _ => None,
+            })
+            .collect::<Vec<Nameserver>>();
+        results.sort_by(|a, b| a.ldh_name.cmp(&b.ldh_name));
+        let response = NameserverSearchResults::response_obj()
+            .results(results)
+            .build()
+            .to_response();
+        Ok(response)
+    }
+
+    async fn search_nameservers_by_ip(&self, ip: &str) -> Result<RdapResponse, RdapServerError> {
+        let ip = ip.parse::<IpAddr>()?;
+        let nameservers = self.nameservers.read().await;
+        let mut results = nameservers
+            .values()
+            .filter_map(|ns| match ns.as_ref() {
+                RdapResponse::Nameserver(ns) => Some(ns.as_ref()),
+                _ => None,
+            })
+            .filter(|ns| nameserver_has_ip(ns, &ip))
+            .cloned()
+            .collect::<Vec<Nameserver>>();
+        results.sort_by(|a, b| a.ldh_name.cmp(&b.ldh_name));
+        let response = NameserverSearchResults::response_obj()
+            .results(results)
+            .build()
+            .to_response();
+        Ok(response)
+    }
+
+    async fn search_entities_by_fn(
+        &self,
+        full_name: &str,
+    ) -> Result<RdapResponse, RdapServerError> {
+        if self.config.common_config.entity_search_index_enable {
+            return self.search_entities_by_name_index(full_name).await;
+        }
+        self.search_entities(ReverseSearchProperty::Fn, full_name)
+            .await
+    }
+
+    async fn search_entities_by_handle(
+        &self,
+        handle: &str,
+    ) -> Result<RdapResponse, RdapServerError> {
+        self.search_entities(ReverseSearchProperty::Handle, handle)
+            .await
+    }
+
+    async fn search_domains_by_entity(
+        &self,
+        property: ReverseSearchProperty,
+        value: &str,
+    ) -> Result<RdapResponse, RdapServerError> {
+        let domains = self.domains.read().await;
+        let mut results = domains
+            .values()
+            .filter_map(|d| match d.as_ref() {
+                RdapResponse::Domain(d) => Some(d.as_ref()),
+                _ => None,
+            })
+            .filter(|d| {
+                d.object_common
+                    .entities
+                    .as_deref()
+                    .unwrap_or_default()
+                    .iter()
+                    .any(|e| entity_matches(e, property, value))
+            })
+            .cloned()
+            .collect::<Vec<Domain>>();
+        results.sort_by(|a, b| a.ldh_name.cmp(&b.ldh_name));
+        let response = DomainSearchResults::response_obj()
+            .results(results)
+            .extension(ExtensionId::ReverseSearch.to_extension())
+            .build()
+            .to_response();
+        Ok(response)
+    }
+
+    async fn search_networks_by_name(&self, name: &str) -> Result<RdapResponse, RdapServerError> {
+        self.search_networks(|n| n.name().is_some_and(|n| search_matches(name, n)))
+            .await
+    }
+
+    async fn search_networks_by_handle(
+        &self,
+        handle: &str,
+    ) -> Result<RdapResponse, RdapServerError> {
+        self.search_networks(|n| n.handle().is_some_and(|h| search_matches(handle, h)))
+            .await
+    }
+
+    async fn search_autnums_by_name(&self, name: &str) -> Result<RdapResponse, RdapServerError> {
+        self.search_autnums(|a| a.name().is_some_and(|n| search_matches(name, n)))
+            .await
+    }
+
+    async fn search_autnums_by_handle(
+        &self,
+        handle: &str,
+    ) -> Result<RdapResponse, RdapServerError> {
+        self.search_autnums(|a| a.handle().is_some_and(|h| search_matches(handle, h)))
+            .await
+    }
+
+    async fn search_networks_by_relation(
+        &self,
+        relation: RirSearchRelation,
+        cidr: &str,
+    ) -> Result<RdapResponse, RdapServerError> {
+        let cidr = IpNet::from_str(cidr)?.trunc();
+        let networks = self.networks().await;
+        let within = networks
+            .iter()
+            .filter(|(net, _)| *net != cidr && cidr.contains(net))
+            .collect::<Vec<_>>();
+        let response = match relation {
+            RirSearchRelation::Up => networks
+                .iter()
+                .filter(|(net, _)| net.contains(&cidr))
+                .max_by_key(|(net, _)| net.prefix_len())
+                .and_then(|(found, _)| {
+                    networks
+                        .iter()
+                        .filter(|(net, _)| net != found && net.contains(found))
+                        .max_by_key(|(net, _)| net.prefix_len())
+                })
+                .map(|(_, network)| RdapResponse::clone(network)),
+            RirSearchRelation::Top => networks
+                .iter()
+                .filter(|(net, _)| net.contains(&cidr))
+                .min_by_key(|(net, _)| net.prefix_len())
+                .map(|(_, network)| RdapResponse::clone(network)),
+            RirSearchRelation::Down => Some(ip_search_results(networks_of(
+                within
+                    .iter()
+                    .filter(|(net, _)| {
+                        !within
+                            .iter()
+                            .any(|(other, _)| other != net && other.contains(net))
+                    })
+                    .map(|(_, network)| network),
+            ))),
+            RirSearchRelation::Bottom => Some(ip_search_results(networks_of(
+                within
+                    .iter()
+                    .filter(|(net, _)| {
+                        !within
+                            .iter()
+                            .any(|(other, _)| other != net && net.contains(other))
+                    })
+                    .map(|(_, network)| network),
+            ))),
+        };
+        Ok(response.unwrap_or_else(|| NOT_FOUND.clone()))
+    }
 }
@@ -14,12 +14,12 @@ use {
 use crate::{
     error::RdapServerError,
     storage::{
-        data::{AutnumId, DomainId, EntityId, NameserverId, NetworkId},
-        TxHandle,
+        data::{AutnumId, DomainId, EntityId, NameserverId, NetworkId, NetworkIdType},
+        entity_name_words, TxHandle,
     },
 };
 
-use super::{label_search::SearchLabels, ops::Mem};
+use super::{label_search::SearchLabels, name_index::NameIndex, ops::Mem};
 
 pub struct MemTx {
     mem: Mem,
@@ -32,6 +32,9 @@ pub struct MemTx {
     nameservers: HashMap<String, Arc<RdapResponse>>,
     entities: HashMap<String, Arc<RdapResponse>>,
     srvhelps: HashMap<String, Arc<RdapResponse>>,
+
+    /// Deleted domains are removed from the domain search labels when committed.
+    domains_deleted: bool,
 }
 
 impl MemTx {
@@ -57,6 +60,7 @@ impl MemTx {
             nameservers: Arc::clone(&mem.nameservers).read_owned().await.clone(),
             entities: Arc::clone(&mem.entities).read_owned().await.clone(),
             srvhelps: Arc::clone(&mem.srvhelps).read_owned().await.clone(),
+            domains_deleted: false,
         }
     }
 
@@ -72,8 +76,52 @@ impl MemTx {
             nameservers: HashMap::new(),
             entities: HashMap::new(),
             srvhelps: HashMap::new(),
+            domains_deleted: false,
         }
     }
+
+    /// Removes the unicode names of the domain with the LDH name.
+    fn remove_idns_of(&mut self, ldh_name: &str) {
+        if let Some(domain) = self.domains.get(ldh_name) {
+            self.idns.retain(|_, value| !Arc::ptr_eq(value, domain));
+        }
+    }
+}
+
+/// Gets the subnets of a network ID.
+fn network_id_subnets(network_id: &NetworkId) -> Result<IpSubnets, RdapServerError> {
+    let subnets = match &network_id.network_id {
+        NetworkIdType::Cidr(cidr) => cidr.subnets(cidr.prefix_len())?,
+        NetworkIdType::Range {
+            start_address,
+            end_address,
+        } => {
+            let start_addr = IpAddr::from_str(start_address)?;
+            let end_addr = IpAddr::from_str(end_address)?;
+            if start_addr.is_ipv4() && end_addr.is_ipv4() {
+                let IpAddr::V4(start_addr) = start_addr else {
+                    panic!("check failed")
+                };
+                let IpAddr::V4(end_addr) = end_addr else {
+                    panic!("check failed")
+                };
+                IpSubnets::from(Ipv4Subnets::new(start_addr, end_addr, 0))
+            } else if start_addr.is_ipv6() && end_addr.is_ipv6() {
+                let IpAddr::V6(start_addr) = start_addr else {
+                    panic!("check failed")
+                };
+                let IpAddr::V6(end_addr) = end_addr else {
+                    panic!("check failed")
+                };
+                IpSubnets::from(Ipv6Subnets::new(start_addr, end_addr, 0))
+            } else {
+                return Err(RdapServerError::EmptyIndexData(
+                    "mismatch ip version".to_string(),
+                ));
+            }
+        }
+    };
+    Ok(subnets)
 }
 
 #[async_trait]
@@ -111,6 +159,7 @@ impl TxHandle for MemTx {
             .ldh_name
             .as_ref()
             .ok_or_else(|| RdapServerError::EmptyIndexData("ldhName".to_string()))?;
+        self.remove_idns_of(ldh_name);
         self.domains
             .insert(ldh_name.to_owned(), domain_response.clone());
 
@@ -132,6 +181,7 @@ impl TxHandle for MemTx {
         domain_id: &DomainId,
         error: &Rfc9083Error,
     ) -> Result<(), RdapServerError> {
+        self.remove_idns_of(&domain_id.ldh_name);
         self.domains.insert(
             domain_id.ldh_name.to_owned(),
             Arc::new(error.clone().to_response()),
@@ -228,37 +278,7 @@ impl TxHandle for MemTx {
         network_id: &NetworkId,
         error: &Rfc9083Error,
     ) -> Result<(), RdapServerError> {
-        let subnets = match &network_id.network_id {
-            crate::storage::data::NetworkIdType::Cidr(cidr) => cidr.subnets(cidr.prefix_len())?,
-            crate::storage::data::NetworkIdType::Range {
-                start_address,
-                end_address,
-            } => {
-                let start_addr = IpAddr::from_str(start_address)?;
-                let end_addr = IpAddr::from_str(end_address)?;
-                if start_addr.is_ipv4() && end_addr.is_ipv4() {
-                    let IpAddr::V4(start_addr) = start_addr else {
-                        panic!("check failed")
-                    };
-                    let IpAddr::V4(end_addr) = end_addr else {
-                        panic!("check failed")
-                    };
-                    IpSubnets::from(Ipv4Subnets::new(start_addr, end_addr, 0))
-                } else if start_addr.is_ipv6() && end_addr.is_ipv6() {
-                    let IpAddr::V6(start_addr) = start_addr else {
-                        panic!("check failed")
-                    };
-                    let IpAddr::V6(end_addr) = end_addr else {
-                        panic!("check failed")
-                    };
-                    IpSubnets::from(Ipv6Subnets::new(start_addr, end_addr, 0))
-                } else {
-                    return Err(RdapServerError::EmptyIndexData(
-                        "mismatch ip version".to_string(),
-                    ));
-                }
-            }
-        };
+        let subnets = network_id_subnets(network_id)?;
         match subnets {
             IpSubnets::V4(subnets) => {
                 for net in subnets {
@@ -285,41 +305,97 @@ impl TxHandle for MemTx {
         Ok(())
     }
 
+    async fn delete_domain(&mut self, domain_id: &DomainId) -> Result<(), RdapServerError> {
+        self.remove_idns_of(&domain_id.ldh_name);
+        if self.domains.remove(&domain_id.ldh_name).is_some() {
+            self.domains_deleted = true;
+        }
+        if let Some(unicode_name) = domain_id.unicode_name.as_ref() {
+            self.idns.remove(unicode_name);
+        }
+        Ok(())
+    }
+
+    async fn delete_entity(&mut self, entity_id: &EntityId) -> Result<(), RdapServerError> {
+        self.entities.remove(&entity_id.handle);
+        Ok(())
+    }
+
+    async fn delete_nameserver(
+        &mut self,
+        nameserver_id: &NameserverId,
+    ) -> Result<(), RdapServerError> {
+        self.nameservers.remove(&nameserver_id.ldh_name);
+        Ok(())
+    }
+
+    async fn delete_autnum(&mut self, autnum_id: &AutnumId) -> Result<(), RdapServerError> {
+        self.autnums
+            .remove((autnum_id.start_autnum)..=(autnum_id.end_autnum));
+        Ok(())
+    }
+
+    async fn delete_network(&mut self, network_id: &NetworkId) -> Result<(), RdapServerError> {
+        match network_id_subnets(network_id)? {
+            IpSubnets::V4(subnets) => {
+                for net in subnets {
+                    self.ip4.remove(&net);
+                }
+            }
+            IpSubnets::V6(subnets) => {
+                for net in subnets {
+                    self.ip6.remove(&net);
+                }
+            }
+        }
+        Ok(())
+    }
+
     async fn commit(mut self: Box<Self>) -> Result<(), RdapServerError> {
-        // autnums
-        let mut autnum_g = self.mem.autnums.write().await;
-        std::mem::swap(&mut self.autnums, &mut autnum_g);
+        // the search labels cannot remove names, so they are rebuilt after deletes.
+        if self.domains_deleted && self.mem.config.common_config.domain_search_by_name_enable {
+            let mut domains_by_name = SearchLabels::builder().build();
+            for (name, value) in self.domains.iter() {
+                domains_by_name.insert(name, value.clone());
+            }
+            self.domains_by_name = domains_by_name;
+        }
 
-        // ip4
-        let mut ip4_g = self.mem.ip4.write().await;
-        std::mem::swap(&mut self.ip4, &mut ip4_g);
+        // the name index is rebuilt, like the search labels, from all the entities.
+        let entities_by_name = if self.mem.config.common_config.entity_search_index_enable {
+            let mut index = NameIndex::default();
+            for (handle, value) in self.entities.iter() {
+                if let RdapResponse::Entity(entity) = value.as_ref() {
+                    index.insert(handle, entity_name_words(entity));
+                }
+            }
+            index
+        } else {
+            NameIndex::default()
+        };
 
-        // ip6
+        // all the write locks are acquired before any data is swapped so that
+        // readers never see some of the data of this transaction without the rest.
+        let mut autnum_g = self.mem.autnums.write().await;
+        let mut ip4_g = self.mem.ip4.write().await;
         let mut ip6_g = self.mem.ip6.write().await;
-        std::mem::swap(&mut self.ip6, &mut ip6_g);
-
-        // domains
         let mut domains_g = self.mem.domains.write().await;
-        std::mem::swap(&mut self.domains, &mut domains_g);
-
-        //domains by name
         let mut domains_by_name_g = self.mem.domains_by_name.write().await;
-        std::mem::swap(&mut self.domains_by_name, &mut domains_by_name_g);
-
-        //idns
         let mut idns_g = self.mem.idns.write().await;
-        std::mem::swap(&mut self.idns, &mut idns_g);
-
-        // nameservers
         let mut nameservers_g = self.mem.nameservers.write().await;
-        std::mem::swap(&mut self.nameservers, &mut nameservers_g);
-
-        // entities
         let mut entities_g = self.mem.entities.write().await;
-        std::mem::swap(&mut self.entities, &mut entities_g);
-
-        //srvhelps
+        let mut entities_by_name_g = self.mem.entities_by_name.write().await;
         let mut srvhelps_g = self.mem.srvhelps.write().await;
+
+        std::mem::swap(&mut self.autnums, &mut autnum_g);
+        std::mem::swap(&mut self.ip4, &mut ip4_g);
+        std::mem::swap(&mut self.ip6, &mut ip6_g);
+        std::mem::swap(&mut self.domains, &mut domains_g);
+        std::mem::swap(&mut self.domains_by_name, &mut domains_by_name_g);
+        std::mem::swap(&mut self.idns, &mut idns_g);
+        std::mem::swap(&mut self.nameservers, &mut nameservers_g);
+        std::mem::swap(&mut self.entities, &mut entities_g);
+        *entities_by_name_g = entities_by_name;
         std::mem::swap(&mut self.srvhelps, &mut srvhelps_g);
 
         Ok(())
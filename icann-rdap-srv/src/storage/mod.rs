@@ -1,8 +1,11 @@
+use std::{collections::HashSet, net::IpAddr, str::FromStr};
+
 use {
     async_trait::async_trait,
     buildstructor::Builder,
     icann_rdap_common::response::{
-        Autnum, Domain, Entity, Help, Nameserver, Network, RdapResponse, Rfc9083Error,
+        Autnum, Domain, Entity, Help, Nameserver, Network, RdapResponse, ReverseSearchProperty,
+        Rfc9083Error, RirSearchRelation,
     },
 };
 
@@ -10,9 +13,11 @@ use crate::error::RdapServerError;
 
 use self::data::{AutnumId, DomainId, EntityId, NameserverId, NetworkId};
 
+pub mod backend;
 pub mod data;
 pub mod mem;
 pub mod pg;
+pub mod watch;
 
 pub type DynStoreOps = dyn StoreOps + Send + Sync;
 
@@ -28,6 +33,35 @@ pub trait StoreOps: Send + Sync {
     /// Gets a new transaction in which all the previous data has been truncated (cleared).
     async fn new_truncate_tx(&self) -> Result<Box<dyn TxHandle>, RdapServerError>;
 
+    /// Applies a batch of updates in one transaction, truncating all the previous data
+    /// first if `truncate` is true.
+    ///
+    /// If any update fails, the transaction is rolled back and storage is left as it was.
+    /// Otherwise the transaction is committed. Either way, readers never observe a
+    /// partially applied batch:
+    ///
+    /// * The memory store applies the batch to a copy of the data and swaps the copy in
+    ///   while holding the write locks of all the data, so readers see either all or none
+    ///   of the batch.
+    /// * The Postgres store applies the batch in a database transaction, so readers see
+    ///   the batch when the transaction commits.
+    async fn batch_update(
+        &self,
+        updates: &[StoreUpdate],
+        truncate: bool,
+    ) -> Result<(), RdapServerError> {
+        let mut tx = if truncate {
+            self.new_truncate_tx().await?
+        } else {
+            self.new_tx().await?
+        };
+        if let Err(e) = tx.apply(updates).await {
+            tx.rollback().await?;
+            return Err(e);
+        }
+        tx.commit().await
+    }
+
     /// Get a domain from storage using the 'ldhName' as the key.
     async fn get_domain_by_ldh(&self, ldh: &str) -> Result<RdapResponse, RdapServerError>;
 
@@ -57,11 +91,83 @@ pub trait StoreOps: Send + Sync {
 
     /// Search for domains by name.
     async fn search_domains_by_name(&self, name: &str) -> Result<RdapResponse, RdapServerError>;
+
+    /// Search for nameservers by name.
+    async fn search_nameservers_by_name(&self, name: &str)
+        -> Result<RdapResponse, RdapServerError>;
+
+    /// Search for nameservers by IP address.
+    async fn search_nameservers_by_ip(&self, ip: &str) -> Result<RdapResponse, RdapServerError>;
+
+    /// Search for entities by full name (the vCard 'fn').
+    /// A trailing '*' in the value matches any suffix.
+    async fn search_entities_by_fn(&self, full_name: &str)
+        -> Result<RdapResponse, RdapServerError>;
+
+    /// Search for entities by handle.
+    /// A trailing '*' in the value matches any suffix.
+    async fn search_entities_by_handle(
+        &self,
+        handle: &str,
+    ) -> Result<RdapResponse, RdapServerError>;
+
+    /// Reverse search (RFC 9536) for domains by a property of their entities.
+    /// A trailing '*' in the value matches any suffix.
+    async fn search_domains_by_entity(
+        &self,
+        property: ReverseSearchProperty,
+        value: &str,
+    ) -> Result<RdapResponse, RdapServerError>;
+
+    /// Search (RFC 9910) for IP networks by name.
+    /// A trailing '*' in the value matches any suffix.
+    async fn search_networks_by_name(&self, name: &str) -> Result<RdapResponse, RdapServerError>;
+
+    /// Search (RFC 9910) for IP networks by handle.
+    /// A trailing '*' in the value matches any suffix.
+    async fn search_networks_by_handle(
+        &self,
+        handle: &str,
+    ) -> Result<RdapResponse, RdapServerError>;
+
+    /// Search (RFC 9910) for autnums by name.
+    /// A trailing '*' in the value matches any suffix.
+    async fn search_autnums_by_name(&self, name: &str) -> Result<RdapResponse, RdapServerError>;
+
+    /// Search (RFC 9910) for autnums by handle.
+    /// A trailing '*' in the value matches any suffix.
+    async fn search_autnums_by_handle(&self, handle: &str)
+        -> Result<RdapResponse, RdapServerError>;
+
+    /// Search (RFC 9910) for the IP networks related to a CIDR.
+    ///
+    /// The "up" relation is the next less specific network of the network found by
+    /// looking up the CIDR, and "top" is the least specific network containing the CIDR.
+    /// These return a network. The "down" relation is the networks within the CIDR that
+    /// are not within other networks within the CIDR, and "bottom" is the networks within
+    /// the CIDR that contain no other networks. These return IP network search results.
+    async fn search_networks_by_relation(
+        &self,
+        relation: RirSearchRelation,
+        cidr: &str,
+    ) -> Result<RdapResponse, RdapServerError>;
 }
 
 /// Represents a handle to a transaction.
 /// The implementation of the transaction
 /// are dependent on the storage type.
+///
+/// Adds are upserts: an object replaces any object in storage with the same ID,
+/// including an error added in its place, and is otherwise inserted. The IDs are
+///
+/// * the `ldhName` of domains and nameservers,
+/// * the `handle` of entities,
+/// * the start and end numbers of autnums,
+/// * the CIDRs covering the address range of networks,
+/// * and the host of server help.
+///
+/// When an object is replaced, it is no longer found by the secondary keys of the
+/// object it replaced, such as the `unicodeName` of a domain. Deletes use the same IDs.
 #[async_trait]
 pub trait TxHandle: Send {
     /// Add a domain name to storage.
@@ -120,6 +226,53 @@ pub trait TxHandle: Send {
         host: Option<&str>,
     ) -> Result<(), RdapServerError>;
 
+    /// Delete a domain from storage. Deleting a domain that is not in storage is not an error.
+    async fn delete_domain(&mut self, domain_id: &DomainId) -> Result<(), RdapServerError>;
+
+    /// Delete an entity from storage. Deleting an entity that is not in storage is not an error.
+    async fn delete_entity(&mut self, entity_id: &EntityId) -> Result<(), RdapServerError>;
+
+    /// Delete a nameserver from storage. Deleting a nameserver that is not in storage is not an error.
+    async fn delete_nameserver(
+        &mut self,
+        nameserver_id: &NameserverId,
+    ) -> Result<(), RdapServerError>;
+
+    /// Delete an autnum from storage. Deleting an autnum that is not in storage is not an error.
+    async fn delete_autnum(&mut self, autnum_id: &AutnumId) -> Result<(), RdapServerError>;
+
+    /// Delete a network from storage. Deleting a network that is not in storage is not an error.
+    async fn delete_network(&mut self, network_id: &NetworkId) -> Result<(), RdapServerError>;
+
+    /// Apply a batch of updates, in order.
+    async fn apply(&mut self, updates: &[StoreUpdate]) -> Result<(), RdapServerError> {
+        for update in updates {
+            match update {
+                StoreUpdate::AddDomain(domain) => self.add_domain(domain).await,
+                StoreUpdate::AddDomainErr(id, error) => self.add_domain_err(id, error).await,
+                StoreUpdate::AddEntity(entity) => self.add_entity(entity).await,
+                StoreUpdate::AddEntityErr(id, error) => self.add_entity_err(id, error).await,
+                StoreUpdate::AddNameserver(nameserver) => self.add_nameserver(nameserver).await,
+                StoreUpdate::AddNameserverErr(id, error) => {
+                    self.add_nameserver_err(id, error).await
+                }
+                StoreUpdate::AddAutnum(autnum) => self.add_autnum(autnum).await,
+                StoreUpdate::AddAutnumErr(id, error) => self.add_autnum_err(id, error).await,
+                StoreUpdate::AddNetwork(network) => self.add_network(network).await,
+                StoreUpdate::AddNetworkErr(id, error) => self.add_network_err(id, error).await,
+                StoreUpdate::AddSrvHelp { help, host } => {
+                    self.add_srv_help(help, host.as_deref()).await
+                }
+                StoreUpdate::DeleteDomain(id) => self.delete_domain(id).await,
+                StoreUpdate::DeleteEntity(id) => self.delete_entity(id).await,
+                StoreUpdate::DeleteNameserver(id) => self.delete_nameserver(id).await,
+                StoreUpdate::DeleteAutnum(id) => self.delete_autnum(id).await,
+                StoreUpdate::DeleteNetwork(id) => self.delete_network(id).await,
+            }?;
+        }
+        Ok(())
+    }
+
     /// Commit the transaction.
     async fn commit(self: Box<Self>) -> Result<(), RdapServerError>;
 
@@ -127,16 +280,161 @@ pub trait TxHandle: Send {
     async fn rollback(self: Box<Self>) -> Result<(), RdapServerError>;
 }
 
+/// An update to storage, applied in a batch with [StoreOps::batch_update].
+#[derive(Debug, Clone)]
+pub enum StoreUpdate {
+    AddDomain(Box<Domain>),
+    AddDomainErr(DomainId, Rfc9083Error),
+    AddEntity(Box<Entity>),
+    AddEntityErr(EntityId, Rfc9083Error),
+    AddNameserver(Box<Nameserver>),
+    AddNameserverErr(NameserverId, Rfc9083Error),
+    AddAutnum(Box<Autnum>),
+    AddAutnumErr(AutnumId, Rfc9083Error),
+    AddNetwork(Box<Network>),
+    AddNetworkErr(NetworkId, Rfc9083Error),
+    AddSrvHelp {
+        help: Box<Help>,
+        host: Option<String>,
+    },
+    DeleteDomain(DomainId),
+    DeleteEntity(EntityId),
+    DeleteNameserver(NameserverId),
+    DeleteAutnum(AutnumId),
+    DeleteNetwork(NetworkId),
+}
+
 /// Common configuration for storage back ends.
 #[derive(Debug, Clone, Copy, Builder)]
 pub struct CommonConfig {
     pub domain_search_by_name_enable: bool,
+
+    /// If true, entity searches by full name use an index of the words of the
+    /// full names and organization names of entities instead of matching every entity.
+    pub entity_search_index_enable: bool,
 }
 
 impl Default for CommonConfig {
     fn default() -> Self {
         Self {
             domain_search_by_name_enable: true,
+            entity_search_index_enable: false,
         }
     }
 }
+
+/// Returns true if the entity has the property matching the value.
+pub(crate) fn entity_matches(
+    entity: &Entity,
+    property: ReverseSearchProperty,
+    value: &str,
+) -> bool {
+    match property {
+        ReverseSearchProperty::Fn => entity
+            .contact()
+            .and_then(|c| c.full_name())
+            .is_some_and(|full_name| search_matches(value, full_name)),
+        ReverseSearchProperty::Handle => entity
+            .object_common
+            .handle
+            .as_ref()
+            .is_some_and(|handle| search_matches(value, handle)),
+        ReverseSearchProperty::Email => entity.contact().is_some_and(|c| {
+            c.emails()
+                .iter()
+                .any(|email| search_matches(value, &email.email))
+        }),
+        ReverseSearchProperty::Role => entity
+            .roles()
+            .iter()
+            .any(|role| search_matches(value, role)),
+    }
+}
+
+/// Removes the networks already seen, keeping the order.
+///
+/// Networks not on a CIDR boundary are stored under many CIDRs and so are found more than once.
+pub(crate) fn unique_networks(networks: impl IntoIterator<Item = Network>) -> Vec<Network> {
+    let mut seen = HashSet::new();
+    networks
+        .into_iter()
+        .filter(|n| seen.insert((n.start_address.clone(), n.end_address.clone())))
+        .collect()
+}
+
+/// Returns true if the nameserver has the IP address.
+pub(crate) fn nameserver_has_ip(nameserver: &Nameserver, ip: &IpAddr) -> bool {
+    nameserver.ip_addresses().is_some_and(|addrs| {
+        addrs
+            .v4s()
+            .iter()
+            .chain(addrs.v6s())
+            .any(|a| IpAddr::from_str(a).is_ok_and(|a| a == *ip))
+    })
+}
+
+/// Gets the words of the full name and organization names of an entity, which
+/// are the words indexed for full-text search of entities.
+pub(crate) fn entity_name_words(entity: &Entity) -> Vec<String> {
+    let Some(contact) = entity.contact() else {
+        return vec![];
+    };
+    let mut words = contact
+        .full_name()
+        .into_iter()
+        .chain(contact.organizational_names().iter().map(String::as_str))
+        .flat_map(split_words)
+        .collect::<Vec<String>>();
+    words.sort();
+    words.dedup();
+    words
+}
+
+/// A word of a full-text search.
+#[derive(Debug, PartialEq, Eq)]
+pub(crate) struct SearchWord {
+    pub(crate) word: String,
+
+    /// If true, the word matches any word starting with it.
+    pub(crate) prefix: bool,
+}
+
+/// Splits a full-text search into words, where a trailing '*' makes a word a prefix.
+///
+/// The words are split in the same way as indexed words, so "Acme-Corp*" is the
+/// word "acme" and the prefix "corp".
+pub(crate) fn split_search_words(search: &str) -> Vec<SearchWord> {
+    search
+        .split_whitespace()
+        .flat_map(|part| {
+            let prefix = part.ends_with('*');
+            let mut words = split_words(part.trim_end_matches('*'))
+                .map(|word| SearchWord {
+                    word,
+                    prefix: false,
+                })
+                .collect::<Vec<SearchWord>>();
+            if let Some(last) = words.last_mut() {
+                last.prefix = prefix;
+            }
+            words
+        })
+        .collect()
+}
+
+/// Splits text into lowercase words of letters and digits.
+fn split_words(text: &str) -> impl Iterator<Item = String> + '_ {
+    text.split(|c: char| !c.is_alphanumeric())
+        .filter(|word| !word.is_empty())
+        .map(str::to_lowercase)
+}
+
+/// Case insensitive match of a search value, where a trailing '*' matches any suffix.
+pub(crate) fn search_matches(search: &str, value: &str) -> bool {
+    let search = search.to_lowercase();
+    let value = value.to_lowercase();
+    match search.strip_suffix('*') {
+        Some(prefix) => value.starts_with(prefix),
+        None => value == search,
+    }
+}
@@ -5,5 +5,10 @@ use crate::storage::CommonConfig;
 #[derive(Debug, Builder, Clone)]
 pub struct PgConfig {
     pub db_url: String,
+
+    /// If true, pending schema migrations are applied when the storage is initialized.
+    /// Otherwise, the storage fails to initialize if the schema is not current.
+    pub auto_migrate: bool,
+
     pub common_config: CommonConfig,
 }
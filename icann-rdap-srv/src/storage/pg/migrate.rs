@@ -0,0 +1,272 @@
+//! Database schema migrations.
+//!
+//! Migrations are embedded in the server and recorded in the `schema_version` table
+//! as they are applied. A server only serves from a database having exactly the
+//! migrations it knows about.
+
+use {
+    chrono::{DateTime, Utc},
+    sqlx::{query, query_as, query_scalar, Executor, PgPool, Postgres, Transaction},
+    tracing::info,
+};
+
+use crate::error::RdapServerError;
+
+/// A schema migration.
+#[derive(Debug)]
+pub struct Migration {
+    /// The version of the schema once the migration is applied.
+    pub version: i32,
+
+    /// A short description of the migration.
+    pub description: &'static str,
+
+    /// The SQL applying the migration.
+    up: &'static str,
+
+    /// The SQL rolling back the migration.
+    down: &'static str,
+}
+
+/// Schema migrations, in the order they are to be applied.
+pub const MIGRATIONS: &[Migration] = &[
+    Migration {
+        version: 1,
+        description: "create tables",
+        up: include_str!("../../../migrations/0001_create_tables.sql"),
+        down: include_str!("../../../migrations/0001_create_tables.down.sql"),
+    },
+    Migration {
+        version: 2,
+        description: "entity search names",
+        up: include_str!("../../../migrations/0002_entity_search_names.sql"),
+        down: include_str!("../../../migrations/0002_entity_search_names.down.sql"),
+    },
+];
+
+/// Arbitrary key for the advisory lock that keeps concurrent servers from migrating at the same time.
+const MIGRATION_LOCK: i64 = 0x7264_6170;
+
+/// The status of a schema migration in a database.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MigrationStatus {
+    pub version: i32,
+
+    /// The description of the migration, or None if the migration is unknown to this server.
+    pub description: Option<&'static str>,
+
+    /// When the migration was applied, or None if it is pending.
+    pub applied_at: Option<DateTime<Utc>>,
+}
+
+/// Begins a transaction holding the migration lock, creating the `schema_version` table
+/// if it does not exist.
+async fn begin_locked(pg_pool: &PgPool) -> Result<Transaction<'_, Postgres>, RdapServerError> {
+    let mut db_tx = pg_pool.begin().await?;
+    query("select pg_advisory_xact_lock($1)")
+        .bind(MIGRATION_LOCK)
+        .execute(&mut *db_tx)
+        .await?;
+    db_tx
+        .execute(
+            "create table if not exists schema_version (
+                version integer primary key,
+                applied_at timestamptz not null default now()
+            )",
+        )
+        .await?;
+    Ok(db_tx)
+}
+
+async fn applied_versions(
+    db_tx: &mut Transaction<'_, Postgres>,
+) -> Result<Vec<i32>, RdapServerError> {
+    Ok(
+        query_scalar("select version from schema_version order by version")
+            .fetch_all(&mut **db_tx)
+            .await?,
+    )
+}
+
+/// Applies any schema migrations that have not yet been applied to the database,
+/// returning the versions applied.
+pub(crate) async fn migrate(pg_pool: &PgPool) -> Result<Vec<i32>, RdapServerError> {
+    let mut db_tx = begin_locked(pg_pool).await?;
+    let applied = applied_versions(&mut db_tx).await?;
+    let mut newly_applied = vec![];
+    for migration in MIGRATIONS {
+        if applied.contains(&migration.version) {
+            continue;
+        }
+        info!(
+            "Applying database schema migration {} ({}).",
+            migration.version, migration.description
+        );
+        // executing the string directly uses the simple query protocol, which allows many statements
+        db_tx.execute(migration.up).await?;
+        query("insert into schema_version (version) values ($1)")
+            .bind(migration.version)
+            .execute(&mut *db_tx)
+            .await?;
+        newly_applied.push(migration.version);
+    }
+    db_tx.commit().await?;
+    Ok(newly_applied)
+}
+
+/// Rolls back the applied schema migrations having a version greater than `to_version`,
+/// newest first, returning the versions rolled back.
+///
+/// Migrations unknown to this server cannot be rolled back.
+pub(crate) async fn rollback(
+    pg_pool: &PgPool,
+    to_version: i32,
+) -> Result<Vec<i32>, RdapServerError> {
+    let mut db_tx = begin_locked(pg_pool).await?;
+    let applied = applied_versions(&mut db_tx).await?;
+    let mut rolled_back = vec![];
+    for version in applied.into_iter().rev().filter(|v| *v > to_version) {
+        let Some(migration) = MIGRATIONS.iter().find(|m| m.version == version) else {
+            return Err(RdapServerError::IncompatibleSchema(format!(
+                "migration {version} is unknown to this server and cannot be rolled back"
+            )));
+        };
+        info!(
+            "Rolling back database schema migration {} ({}).",
+            migration.version, migration.description
+        );
+        db_tx.execute(migration.down).await?;
+        query("delete from schema_version where version = $1")
+            .bind(version)
+            .execute(&mut *db_tx)
+            .await?;
+        rolled_back.push(version);
+    }
+    db_tx.commit().await?;
+    Ok(rolled_back)
+}
+
+/// Gets the status of every migration known to this server or applied to the database.
+pub(crate) async fn migration_status(
+    pg_pool: &PgPool,
+) -> Result<Vec<MigrationStatus>, RdapServerError> {
+    let mut db_tx = begin_locked(pg_pool).await?;
+    let applied: Vec<(i32, DateTime<Utc>)> =
+        query_as("select version, applied_at from schema_version order by version")
+            .fetch_all(&mut *db_tx)
+            .await?;
+    db_tx.commit().await?;
+    let mut statuses: Vec<MigrationStatus> = MIGRATIONS
+        .iter()
+        .map(|m| MigrationStatus {
+            version: m.version,
+            description: Some(m.description),
+            applied_at: applied
+                .iter()
+                .find(|(v, _)| *v == m.version)
+                .map(|(_, at)| *at),
+        })
+        .collect();
+    statuses.extend(
+        applied
+            .iter()
+            .filter(|(v, _)| !MIGRATIONS.iter().any(|m| m.version == *v))
+            .map(|(version, at)| MigrationStatus {
+                version: *version,
+                description: None,
+                applied_at: Some(*at),
+            }),
+    );
+    statuses.sort_by_key(|s| s.version);
+    Ok(statuses)
+}
+
+/// Checks that the database has exactly the migrations known to this server.
+pub(crate) async fn check_schema(pg_pool: &PgPool) -> Result<(), RdapServerError> {
+    let mut db_tx = begin_locked(pg_pool).await?;
+    let applied = applied_versions(&mut db_tx).await?;
+    db_tx.commit().await?;
+    match schema_incompatibility(&applied) {
+        Some(reason) => Err(RdapServerError::IncompatibleSchema(reason)),
+        None => Ok(()),
+    }
+}
+
+/// Describes why a database with the applied migrations cannot be used by this server,
+/// or None if it can.
+fn schema_incompatibility(applied: &[i32]) -> Option<String> {
+    let unknown: Vec<String> = applied
+        .iter()
+        .filter(|v| !MIGRATIONS.iter().any(|m| m.version == **v))
+        .map(|v| v.to_string())
+        .collect();
+    if !unknown.is_empty() {
+        return Some(format!(
+            "database has migrations unknown to this server ({}); upgrade the server or roll back the database",
+            unknown.join(", ")
+        ));
+    }
+    let pending: Vec<String> = MIGRATIONS
+        .iter()
+        .filter(|m| !applied.contains(&m.version))
+        .map(|m| m.version.to_string())
+        .collect();
+    if !pending.is_empty() {
+        return Some(format!(
+            "database is missing migrations ({}); run 'rdap-srv db migrate'",
+            pending.join(", ")
+        ));
+    }
+    None
+}
+
+#[cfg(test)]
+#[allow(non_snake_case)]
+mod tests {
+    use rstest::rstest;
+
+    use super::{schema_incompatibility, MIGRATIONS};
+
+    #[test]
+    fn GIVEN_all_migrations_applied_WHEN_schema_incompatibility_THEN_none() {
+        // GIVEN
+        let applied: Vec<i32> = MIGRATIONS.iter().map(|m| m.version).collect();
+
+        // WHEN
+        let actual = schema_incompatibility(&applied);
+
+        // THEN
+        assert!(actual.is_none());
+    }
+
+    #[rstest]
+    #[case(&[], "missing migrations (1, 2)")]
+    #[case(&[1], "missing migrations (2)")]
+    #[case(&[1, 2, 99], "unknown to this server (99)")]
+    fn GIVEN_other_migrations_applied_WHEN_schema_incompatibility_THEN_reason(
+        #[case] applied: &[i32],
+        #[case] expected: &str,
+    ) {
+        // GIVEN applied
+
+        // WHEN
+        let actual = schema_incompatibility(applied);
+
+        // THEN
+        assert!(actual.expect("incompatible").contains(expected));
+    }
+
+    #[test]
+    fn GIVEN_migrations_WHEN_versions_THEN_ascending() {
+        // GIVEN
+        let versions: Vec<i32> = MIGRATIONS.iter().map(|m| m.version).collect();
+
+        // WHEN
+        let mut sorted = versions.clone();
+        sorted.sort();
+        sorted.dedup();
+
+        // THEN
+        assert_eq!(versions, sorted);
+    }
+}
@@ -1,5 +1,4 @@
-#![allow(dead_code)] // TODO remove
-
 pub mod config;
+pub mod migrate;
 pub mod ops;
 pub mod tx;
@@ -1,30 +1,196 @@
-#![allow(clippy::diverging_sub_expression)]
+use std::{net::IpAddr, str::FromStr};
+
 use {
     async_trait::async_trait,
-    icann_rdap_common::response::RdapResponse,
-    sqlx::{query, PgPool},
+    icann_rdap_common::{
+        prelude::ToResponse,
+        response::{
+            Autnum, AutnumSearchResults, Domain, DomainSearchResults, Entity, EntitySearchResults,
+            ExtensionId, IpSearchResults, Nameserver, NameserverSearchResults, Network,
+            RdapResponse, ReverseSearchProperty, RirSearchRelation,
+        },
+    },
+    ipnet::IpNet,
+    sqlx::{query, query_scalar, types::Json, PgPool},
     tracing::{debug, info},
 };
 
 use crate::{
     error::RdapServerError,
-    storage::{StoreOps, TxHandle},
+    rdap::response::{NOT_FOUND, NOT_IMPLEMENTED},
+    storage::{
+        entity_matches, mem::label_search::split_label_search, nameserver_has_ip,
+        split_search_words, unique_networks, StoreOps, TxHandle,
+    },
 };
 
-use super::{config::PgConfig, tx::PgTx};
+use super::{
+    config::PgConfig,
+    migrate::{check_schema, migrate, migration_status, rollback, MigrationStatus},
+    tx::PgTx,
+};
 
 #[derive(Clone)]
 pub struct Pg {
     pg_pool: PgPool,
+    config: PgConfig,
 }
 
 impl Pg {
     pub async fn new(config: PgConfig) -> Result<Self, RdapServerError> {
         let pg_pool = PgPool::connect(&config.db_url).await?;
-        Ok(Self { pg_pool })
+        Ok(Self { pg_pool, config })
+    }
+
+    /// Applies any pending schema migrations, returning the versions applied.
+    pub async fn migrate(&self) -> Result<Vec<i32>, RdapServerError> {
+        migrate(&self.pg_pool).await
+    }
+
+    /// Rolls back the schema migrations newer than `to_version`, returning the versions rolled back.
+    pub async fn rollback(&self, to_version: i32) -> Result<Vec<i32>, RdapServerError> {
+        rollback(&self.pg_pool, to_version).await
+    }
+
+    /// Gets the status of the schema migrations.
+    pub async fn migration_status(&self) -> Result<Vec<MigrationStatus>, RdapServerError> {
+        migration_status(&self.pg_pool).await
+    }
+
+    /// Gets a single response using a query with a single text parameter.
+    async fn get_response(&self, sql: &str, param: &str) -> Result<RdapResponse, RdapServerError> {
+        let result: Option<Json<RdapResponse>> = query_scalar(sql)
+            .bind(param)
+            .fetch_optional(&self.pg_pool)
+            .await?;
+        match result {
+            Some(Json(response)) => Ok(response),
+            None => Ok(NOT_FOUND.clone()),
+        }
+    }
+
+    /// Gets the responses of a query with a single text parameter.
+    async fn get_responses(
+        &self,
+        sql: &str,
+        param: &str,
+    ) -> Result<Vec<Json<RdapResponse>>, RdapServerError> {
+        Ok(query_scalar(sql)
+            .bind(param)
+            .fetch_all(&self.pg_pool)
+            .await?)
+    }
+
+    /// Searches entities by the words of their names using full-text search.
+    async fn search_entities_by_search_names(
+        &self,
+        search: &str,
+    ) -> Result<RdapResponse, RdapServerError> {
+        // the words are only letters and digits, so they need no escaping in the query.
+        let ts_query = split_search_words(search)
+            .iter()
+            .map(|w| {
+                if w.prefix {
+                    format!("{}:*", w.word)
+                } else {
+                    w.word.clone()
+                }
+            })
+            .collect::<Vec<String>>()
+            .join(" & ");
+        let responses: Vec<Json<RdapResponse>> = if ts_query.is_empty() {
+            vec![]
+        } else {
+            query_scalar(
+                "select response from entity
+                 where to_tsvector('simple', coalesce(search_names, '')) @@ to_tsquery('simple', $1)
+                 order by handle",
+            )
+            .bind(ts_query)
+            .fetch_all(&self.pg_pool)
+            .await?
+        };
+        let response = EntitySearchResults::response_obj()
+            .results(entities(responses).collect::<Vec<Entity>>())
+            .build()
+            .to_response();
+        Ok(response)
     }
 }
 
+/// Escapes the characters that have special meaning in a SQL LIKE pattern.
+fn escape_like(s: &str) -> String {
+    s.replace('\\', "\\\\")
+        .replace('%', "\\%")
+        .replace('_', "\\_")
+}
+
+/// Makes a SQL LIKE pattern of a search value, where a trailing '*' matches any suffix.
+fn search_pattern(search: &str) -> String {
+    match search.strip_suffix('*') {
+        Some(prefix) => format!("{}%", escape_like(prefix)),
+        None => escape_like(search),
+    }
+}
+
+/// Takes the domains from a set of responses, ignoring errors.
+fn domains(responses: Vec<Json<RdapResponse>>) -> impl Iterator<Item = Domain> {
+    responses.into_iter().filter_map(|Json(r)| match r {
+        RdapResponse::Domain(d) => Some(*d),
+        _ => None,
+    })
+}
+
+/// Takes the nameservers from a set of responses, ignoring errors.
+fn nameservers(responses: Vec<Json<RdapResponse>>) -> impl Iterator<Item = Nameserver> {
+    responses.into_iter().filter_map(|Json(r)| match r {
+        RdapResponse::Nameserver(n) => Some(*n),
+        _ => None,
+    })
+}
+
+/// Takes the networks from a set of responses, once for each network, ignoring errors.
+fn networks(responses: Vec<Json<RdapResponse>>) -> Vec<Network> {
+    unique_networks(responses.into_iter().filter_map(|Json(r)| match r {
+        RdapResponse::Network(n) => Some(*n),
+        _ => None,
+    }))
+}
+
+/// Takes the autnums from a set of responses, ignoring errors.
+fn autnums(responses: Vec<Json<RdapResponse>>) -> impl Iterator<Item = Autnum> {
+    responses.into_iter().filter_map(|Json(r)| match r {
+        RdapResponse::Autnum(a) => Some(*a),
+        _ => None,
+    })
+}
+
+fn ip_search_results(results: Vec<Network>) -> RdapResponse {
+    IpSearchResults::response_obj()
+        .results(results)
+        .extension(ExtensionId::RirSearch1.to_extension())
+        .extension(ExtensionId::Ips.to_extension())
+        .build()
+        .to_response()
+}
+
+fn autnum_search_results(results: Vec<Autnum>) -> RdapResponse {
+    AutnumSearchResults::response_obj()
+        .results(results)
+        .extension(ExtensionId::RirSearch1.to_extension())
+        .extension(ExtensionId::Autnums.to_extension())
+        .build()
+        .to_response()
+}
+
+/// Takes the entities from a set of responses, ignoring errors.
+fn entities(responses: Vec<Json<RdapResponse>>) -> impl Iterator<Item = Entity> {
+    responses.into_iter().filter_map(|Json(r)| match r {
+        RdapResponse::Entity(e) => Some(*e),
+        _ => None,
+    })
+}
+
 #[async_trait]
 impl StoreOps for Pg {
     async fn init(&self) -> Result<(), RdapServerError> {
@@ -32,6 +198,10 @@ impl StoreOps for Pg {
         let mut conn = self.pg_pool.acquire().await?;
         query("select 1").fetch_one(&mut *conn).await?;
         info!("Database connection test is successful.");
+        if self.config.auto_migrate {
+            migrate(&self.pg_pool).await?;
+        }
+        check_schema(&self.pg_pool).await?;
         Ok(())
     }
 
@@ -43,36 +213,290 @@ impl StoreOps for Pg {
         Ok(Box::new(PgTx::new_truncate(&self.pg_pool).await?))
     }
 
-    async fn get_domain_by_ldh(&self, _ldh: &str) -> Result<RdapResponse, RdapServerError> {
-        todo!()
+    async fn get_domain_by_ldh(&self, ldh: &str) -> Result<RdapResponse, RdapServerError> {
+        self.get_response("select response from domain where ldh_name = $1", ldh)
+            .await
+    }
+
+    async fn get_domain_by_unicode(&self, unicode: &str) -> Result<RdapResponse, RdapServerError> {
+        self.get_response(
+            "select response from domain where unicode_name = $1 limit 1",
+            unicode,
+        )
+        .await
+    }
+
+    async fn get_entity_by_handle(&self, handle: &str) -> Result<RdapResponse, RdapServerError> {
+        self.get_response("select response from entity where handle = $1", handle)
+            .await
+    }
+
+    async fn get_nameserver_by_ldh(&self, ldh: &str) -> Result<RdapResponse, RdapServerError> {
+        self.get_response("select response from nameserver where ldh_name = $1", ldh)
+            .await
+    }
+
+    async fn get_autnum_by_num(&self, num: u32) -> Result<RdapResponse, RdapServerError> {
+        let result: Option<Json<RdapResponse>> = query_scalar(
+            "select response from autnum where start_autnum <= $1 and end_autnum >= $1
+             order by end_autnum - start_autnum limit 1",
+        )
+        .bind(i64::from(num))
+        .fetch_optional(&self.pg_pool)
+        .await?;
+        match result {
+            Some(Json(response)) => Ok(response),
+            None => Ok(NOT_FOUND.clone()),
+        }
+    }
+
+    async fn get_network_by_ipaddr(&self, ipaddr: &str) -> Result<RdapResponse, RdapServerError> {
+        let addr = ipaddr.parse::<IpAddr>()?;
+        self.get_network_by_cidr(&IpNet::from(addr).to_string())
+            .await
+    }
+
+    async fn get_network_by_cidr(&self, cidr: &str) -> Result<RdapResponse, RdapServerError> {
+        // normalize the host bits, which postgres will not accept in the cidr type.
+        let net = IpNet::from_str(cidr)?.trunc();
+        self.get_response(
+            "select response from network where cidr >>= $1::cidr
+             order by masklen(cidr) desc limit 1",
+            &net.to_string(),
+        )
+        .await
+    }
+
+    async fn get_srv_help(&self, host: Option<&str>) -> Result<RdapResponse, RdapServerError> {
+        let host = host.unwrap_or("..default");
+        self.get_response("select response from srv_help where host = $1", host)
+            .await
+    }
+
+    async fn search_domains_by_name(&self, name: &str) -> Result<RdapResponse, RdapServerError> {
+        if !self.config.common_config.domain_search_by_name_enable {
+            return Ok(NOT_IMPLEMENTED.clone());
+        }
+        //else
+        let (prefix, suffix) = split_label_search(name)?;
+        let pattern = format!("{}%{}", escape_like(prefix), escape_like(suffix));
+        let responses: Vec<Json<RdapResponse>> =
+            query_scalar("select response from domain where ldh_name like $1 order by ldh_name")
+                .bind(pattern)
+                .fetch_all(&self.pg_pool)
+                .await?;
+        let response = DomainSearchResults::response_obj()
+            .results(domains(responses).collect::<Vec<Domain>>())
+            .build()
+            .to_response();
+        Ok(response)
     }
 
-    async fn get_domain_by_unicode(&self, _unicode: &str) -> Result<RdapResponse, RdapServerError> {
-        todo!()
+    async fn search_nameservers_by_name(
+        &self,
+        name: &str,
+    ) -> Result<RdapResponse, RdapServerError> {
+        let (prefix, suffix) = split_label_search(name)?;
+        let pattern = format!("{}%{}", escape_like(prefix), escape_like(suffix));
+        let responses: Vec<Json<RdapResponse>> = query_scalar(
+            "select response from nameserver where ldh_name like $1 order by ldh_name",
+        )
+        .bind(pattern)
+        .fetch_all(&self.pg_pool)
+        .await?;
+        let response = NameserverSearchResults::response_obj()
+            .results(nameservers(responses).collect::<Vec<Nameserver>>())
+            .build()
+            .to_response();
+        Ok(response)
     }
 
-    async fn get_entity_by_handle(&self, _handle: &str) -> Result<RdapResponse, RdapServerError> {
-        todo!()
+    async fn search_nameservers_by_ip(&self, ip: &str) -> Result<RdapResponse, RdapServerError> {
+        let ip = ip.parse::<IpAddr>()?;
+        // addresses have many textual forms, so they are compared here.
+        let responses: Vec<Json<RdapResponse>> = query_scalar(
+            "select response from nameserver where response ? 'ipAddresses' order by ldh_name",
+        )
+        .fetch_all(&self.pg_pool)
+        .await?;
+        let results = nameservers(responses)
+            .filter(|ns| nameserver_has_ip(ns, &ip))
+            .collect::<Vec<Nameserver>>();
+        let response = NameserverSearchResults::response_obj()
+            .results(results)
+            .build()
+            .to_response();
+        Ok(response)
     }
 
-    async fn get_nameserver_by_ldh(&self, _ldh: &str) -> Result<RdapResponse, RdapServerError> {
-        todo!()
+    async fn search_entities_by_fn(
+        &self,
+        full_name: &str,
+    ) -> Result<RdapResponse, RdapServerError> {
+        if self.config.common_config.entity_search_index_enable {
+            return self.search_entities_by_search_names(full_name).await;
+        }
+        // the full name is in the vCard, so entity matching is done here.
+        let responses: Vec<Json<RdapResponse>> = query_scalar(
+            "select response from entity where response ? 'vcardArray' order by handle",
+        )
+        .fetch_all(&self.pg_pool)
+        .await?;
+        let results = entities(responses)
+            .filter(|e| entity_matches(e, ReverseSearchProperty::Fn, full_name))
+            .collect::<Vec<Entity>>();
+        let response = EntitySearchResults::response_obj()
+            .results(results)
+            .build()
+            .to_response();
+        Ok(response)
     }
 
-    async fn get_autnum_by_num(&self, _num: u32) -> Result<RdapResponse, RdapServerError> {
-        todo!()
+    async fn search_entities_by_handle(
+        &self,
+        handle: &str,
+    ) -> Result<RdapResponse, RdapServerError> {
+        let responses: Vec<Json<RdapResponse>> =
+            query_scalar("select response from entity where handle ilike $1 order by handle")
+                .bind(search_pattern(handle))
+                .fetch_all(&self.pg_pool)
+                .await?;
+        let response = EntitySearchResults::response_obj()
+            .results(entities(responses).collect::<Vec<Entity>>())
+            .build()
+            .to_response();
+        Ok(response)
     }
 
-    async fn get_network_by_ipaddr(&self, _ipaddr: &str) -> Result<RdapResponse, RdapServerError> {
-        todo!()
+    async fn search_domains_by_entity(
+        &self,
+        property: ReverseSearchProperty,
+        value: &str,
+    ) -> Result<RdapResponse, RdapServerError> {
+        // the jsonb containment narrows the candidates, entity matching is done here
+        // so that the semantics are the same as the other storage back ends.
+        let responses: Vec<Json<RdapResponse>> = query_scalar(
+            "select response from domain
+             where response @> '{\"objectClassName\": \"domain\"}' and response ? 'entities'
+             order by ldh_name",
+        )
+        .fetch_all(&self.pg_pool)
+        .await?;
+        let results = domains(responses)
+            .filter(|d| {
+                d.object_common
+                    .entities
+                    .as_deref()
+                    .unwrap_or_default()
+                    .iter()
+                    .any(|e| entity_matches(e, property, value))
+            })
+            .collect::<Vec<Domain>>();
+        let response = DomainSearchResults::response_obj()
+            .results(results)
+            .extension(ExtensionId::ReverseSearch.to_extension())
+            .build()
+            .to_response();
+        Ok(response)
     }
-    async fn get_network_by_cidr(&self, _cidr: &str) -> Result<RdapResponse, RdapServerError> {
-        todo!()
+
+    async fn search_networks_by_name(&self, name: &str) -> Result<RdapResponse, RdapServerError> {
+        let responses = self
+            .get_responses(
+                "select response from network where response->>'name' ilike $1 order by cidr",
+                &search_pattern(name),
+            )
+            .await?;
+        Ok(ip_search_results(networks(responses)))
+    }
+
+    async fn search_networks_by_handle(
+        &self,
+        handle: &str,
+    ) -> Result<RdapResponse, RdapServerError> {
+        let responses = self
+            .get_responses(
+                "select response from network where response->>'handle' ilike $1 order by cidr",
+                &search_pattern(handle),
+            )
+            .await?;
+        Ok(ip_search_results(networks(responses)))
     }
-    async fn get_srv_help(&self, _host: Option<&str>) -> Result<RdapResponse, RdapServerError> {
-        todo!()
+
+    async fn search_autnums_by_name(&self, name: &str) -> Result<RdapResponse, RdapServerError> {
+        let responses = self
+            .get_responses(
+                "select response from autnum where response->>'name' ilike $1
+                 order by start_autnum, end_autnum",
+                &search_pattern(name),
+            )
+            .await?;
+        Ok(autnum_search_results(autnums(responses).collect()))
     }
-    async fn search_domains_by_name(&self, _name: &str) -> Result<RdapResponse, RdapServerError> {
-        todo!()
+
+    async fn search_autnums_by_handle(
+        &self,
+        handle: &str,
+    ) -> Result<RdapResponse, RdapServerError> {
+        let responses = self
+            .get_responses(
+                "select response from autnum where response->>'handle' ilike $1
+                 order by start_autnum, end_autnum",
+                &search_pattern(handle),
+            )
+            .await?;
+        Ok(autnum_search_results(autnums(responses).collect()))
+    }
+
+    async fn search_networks_by_relation(
+        &self,
+        relation: RirSearchRelation,
+        cidr: &str,
+    ) -> Result<RdapResponse, RdapServerError> {
+        // normalize the host bits, which postgres will not accept in the cidr type.
+        let cidr = IpNet::from_str(cidr)?.trunc().to_string();
+        match relation {
+            RirSearchRelation::Up => {
+                self.get_response(
+                    "select response from network
+                     where cidr >> (select cidr from network where cidr >>= $1::cidr
+                                    order by masklen(cidr) desc limit 1)
+                     order by masklen(cidr) desc limit 1",
+                    &cidr,
+                )
+                .await
+            }
+            RirSearchRelation::Top => {
+                self.get_response(
+                    "select response from network where cidr >>= $1::cidr
+                     order by masklen(cidr) limit 1",
+                    &cidr,
+                )
+                .await
+            }
+            RirSearchRelation::Down => {
+                let responses = self
+                    .get_responses(
+                        "select n.response from network n where n.cidr << $1::cidr
+                         and not exists (select 1 from network p
+                                         where p.cidr << $1::cidr and p.cidr >> n.cidr)
+                         order by n.cidr",
+                        &cidr,
+                    )
+                    .await?;
+                Ok(ip_search_results(networks(responses)))
+            }
+            RirSearchRelation::Bottom => {
+                let responses = self
+                    .get_responses(
+                        "select n.response from network n where n.cidr << $1::cidr
+                         and not exists (select 1 from network c where c.cidr << n.cidr)
+                         order by n.cidr",
+                        &cidr,
+                    )
+                    .await?;
+                Ok(ip_search_results(networks(responses)))
+            }
+        }
     }
 }
@@ -0,0 +1,213 @@
+//! Reloading of storage from the data directory without restarting the server.
+//!
+//! Storage is reloaded either by watching the data directory for changes to the
+//! RDAP JSON, template and server help files, or on demand with the `/__reload`
+//! endpoint. Either way, all the files are read before storage is swapped, so
+//! queries see either the old or the new data and never a mix of both.
+
+use std::{
+    collections::BTreeMap,
+    path::{Path, PathBuf},
+    sync::Arc,
+    time::{Duration, SystemTime},
+};
+
+use {
+    axum::{extract::State, http::StatusCode, response::IntoResponse},
+    tokio::{sync::Mutex, time::sleep},
+    tracing::{debug, error, info},
+};
+
+use crate::{
+    config::ServiceConfig, error::RdapServerError, hooks::call_reload_hooks,
+    server::DynServiceState, storage::data::load_data,
+};
+
+use super::StoreOps;
+
+/// How often the data directory is checked for changes.
+const POLL_INTERVAL: Duration = Duration::from_millis(500);
+
+/// The default time the data directory must go unchanged before storage is reloaded.
+pub const DEFAULT_WATCH_DEBOUNCE: Duration = Duration::from_millis(2000);
+
+/// The path of the endpoint that reloads storage.
+pub const RELOAD_ENDPOINT: &str = "/__reload";
+
+/// Reloads storage from the data directory, one reload at a time.
+pub struct DataReloader {
+    store: Box<dyn StoreOps>,
+    config: ServiceConfig,
+    reloading: Mutex<()>,
+}
+
+impl std::fmt::Debug for DataReloader {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("DataReloader")
+            .field("data_dir", &self.config.data_dir)
+            .finish()
+    }
+}
+
+impl DataReloader {
+    pub fn new(store: Box<dyn StoreOps>, config: ServiceConfig) -> Self {
+        Self {
+            store,
+            config,
+            reloading: Mutex::new(()),
+        }
+    }
+
+    /// Replaces the contents of storage with the files of the data directory.
+    ///
+    /// If any file cannot be loaded, storage is left as it was.
+    pub async fn reload(&self) -> Result<(), RdapServerError> {
+        let _reloading = self.reloading.lock().await;
+        info!("Data being reloaded from {}.", self.config.data_dir);
+        let result = load_data(&self.config, &*self.store, true).await;
+        call_reload_hooks(&self.config.data_dir, true, &result);
+        result
+    }
+}
+
+/// The modification times and sizes of the data files in a directory.
+#[derive(Debug, Default, PartialEq, Eq)]
+pub(crate) struct DataSnapshot(BTreeMap<PathBuf, (Option<SystemTime>, u64)>);
+
+impl DataSnapshot {
+    /// Takes a snapshot of the files loaded by [load_data]. A directory that
+    /// does not exist has an empty snapshot.
+    pub(crate) async fn take(data_dir: &Path) -> Result<Self, RdapServerError> {
+        let mut files = BTreeMap::new();
+        let Ok(mut entries) = tokio::fs::read_dir(data_dir).await else {
+            return Ok(Self(files));
+        };
+        while let Some(entry) = entries.next_entry().await? {
+            let path = entry.path();
+            let is_data = path
+                .extension()
+                .is_some_and(|ext| ext == "json" || ext == "template" || ext == "help");
+            if !is_data {
+                continue;
+            }
+            // a file removed since the directory was read is left out, like a file never there.
+            if let Ok(meta) = entry.metadata().await {
+                files.insert(path, (meta.modified().ok(), meta.len()));
+            }
+        }
+        Ok(Self(files))
+    }
+}
+
+/// Watches the data directory, reloading storage once changes to the data files
+/// have stopped for the debounce window.
+///
+/// The directory is polled rather than watched with file system notifications,
+/// which do not arrive for some network file systems and container volumes.
+pub(crate) async fn watch_data(
+    reloader: Arc<DataReloader>,
+    debounce: Duration,
+) -> Result<(), RdapServerError> {
+    let data_dir = PathBuf::from(&reloader.config.data_dir);
+    let mut last = DataSnapshot::take(&data_dir).await?;
+    loop {
+        sleep(POLL_INTERVAL).await;
+        let mut current = DataSnapshot::take(&data_dir).await?;
+        if current == last {
+            continue;
+        }
+        debug!("changes in {} being debounced", data_dir.display());
+        // files are often written over several steps, such as by rsync or a data generator,
+        // so storage is not reloaded until the directory settles.
+        loop {
+            sleep(debounce).await;
+            let settled = DataSnapshot::take(&data_dir).await?;
+            if settled == current {
+                break;
+            }
+            current = settled;
+        }
+        last = current;
+        if let Err(e) = reloader.reload().await {
+            error!("Data not reloaded: {e}");
+        }
+    }
+}
+
+/// Handler of the endpoint that reloads storage.
+pub(crate) async fn reload_endpoint(State(state): State<DynServiceState>) -> impl IntoResponse {
+    let Some(reloader) = state.get_reloader() else {
+        return (StatusCode::NOT_FOUND, String::new());
+    };
+    match reloader.reload().await {
+        Ok(()) => (StatusCode::OK, "Data reloaded.\n".to_string()),
+        Err(e) => {
+            error!("Data not reloaded: {e}");
+            (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                format!("Data not reloaded: {e}\n"),
+            )
+        }
+    }
+}
+
+#[cfg(test)]
+#[allow(non_snake_case)]
+mod tests {
+    use test_dir::{DirBuilder, TestDir};
+
+    use super::DataSnapshot;
+
+    #[tokio::test]
+    async fn GIVEN_missing_dir_WHEN_take_snapshot_THEN_empty() {
+        // GIVEN
+        let temp = TestDir::temp();
+
+        // WHEN
+        let actual = DataSnapshot::take(&temp.path("missing"))
+            .await
+            .expect("taking snapshot");
+
+        // THEN
+        assert_eq!(actual, DataSnapshot::default());
+    }
+
+    #[tokio::test]
+    async fn GIVEN_data_file_changed_WHEN_take_snapshot_THEN_differs() {
+        // GIVEN
+        let temp = TestDir::temp();
+        std::fs::write(temp.path("foo.json"), "{}").expect("writing file");
+        let before = DataSnapshot::take(temp.root())
+            .await
+            .expect("taking snapshot");
+        std::fs::write(temp.path("foo.json"), "{ }").expect("writing file");
+
+        // WHEN
+        let actual = DataSnapshot::take(temp.root())
+            .await
+            .expect("taking snapshot");
+
+        // THEN
+        assert_ne!(actual, before);
+    }
+
+    #[tokio::test]
+    async fn GIVEN_other_files_changed_WHEN_take_snapshot_THEN_same() {
+        // GIVEN
+        let temp = TestDir::temp();
+        std::fs::write(temp.path("foo.template"), "{}").expect("writing file");
+        let before = DataSnapshot::take(temp.root())
+            .await
+            .expect("taking snapshot");
+        std::fs::write(temp.path("reload"), "").expect("writing file");
+        std::fs::write(temp.path("notes.txt"), "notes").expect("writing file");
+
+        // WHEN
+        let actual = DataSnapshot::take(temp.root())
+            .await
+            .expect("taking snapshot");
+
+        // THEN
+        assert_eq!(actual, before);
+    }
+}
@@ -1,7 +1,7 @@
 use {
     clap::{Args, ValueEnum},
     icann_rdap_common::{
-        check::{traverse_checks, CheckClass, CheckParams, GetChecks},
+        check::{traverse_checks, CheckClass, CheckParams, GetChecks, ProfileId},
         response::RdapResponse,
     },
     tracing::error,
@@ -51,6 +51,7 @@ pub fn check_rdap(rdap: RdapResponse, check_types: &[CheckClass]) -> bool {
         root: &rdap,
         parent_type: rdap.get_type(),
         allow_unreg_ext: true,
+        profiles: ProfileId::declared_by(&rdap),
     });
     traverse_checks(
         &checks,
@@ -0,0 +1,399 @@
+//! Generation of synthetic RDAP data for load testing and demos.
+
+use {
+    chrono::{DateTime, Duration, TimeZone, Utc},
+    icann_rdap_client::rdap::QueryType,
+    icann_rdap_common::{
+        contact::{Contact, PostalAddress},
+        media_types::RDAP_MEDIA_TYPE,
+        prelude::{RdapResponse, ToResponse, VectorStringish},
+        response::{Autnum, Domain, Entity, Event, Events, Link, Nameserver, Network, ToChild},
+    },
+    rand::{rngs::StdRng, seq::SliceRandom, Rng, SeedableRng},
+};
+
+use crate::error::RdapServerError;
+
+const GIVEN_NAMES: [&str; 12] = [
+    "Alice", "Bob", "Carol", "Dave", "Erin", "Frank", "Grace", "Heidi", "Ivan", "Judy", "Mallory",
+    "Trent",
+];
+
+const SURNAMES: [&str; 10] = [
+    "Smith", "Garcia", "Nguyen", "Tanaka", "Okafor", "Muller", "Rossi", "Silva", "Kowalski",
+    "Haddad",
+];
+
+const WORDS: [&str; 16] = [
+    "blue", "river", "stone", "cloud", "maple", "harbor", "summit", "cedar", "falcon", "meadow",
+    "copper", "lantern", "orbit", "willow", "anchor", "prairie",
+];
+
+const ORG_KINDS: [&str; 5] = ["Networks", "Hosting", "Telecom", "Systems", "Labs"];
+
+/// Cities with their regions and countries.
+const PLACES: [(&str, &str, &str, &str); 6] = [
+    ("Springfield", "Illinois", "IL", "US"),
+    ("Toronto", "Ontario", "ON", "CA"),
+    ("Osaka", "Osaka", "27", "JP"),
+    ("Lyon", "Auvergne-Rhone-Alpes", "ARA", "FR"),
+    ("Porto", "Porto", "13", "PT"),
+    ("Nairobi", "Nairobi", "30", "KE"),
+];
+
+const DOMAIN_STATUSES: [&str; 4] = [
+    "active",
+    "client transfer prohibited",
+    "client delete prohibited",
+    "server update prohibited",
+];
+
+/// The reserved TLDs of generated domains and nameservers.
+const TLDS: [&str; 2] = ["example", "test"];
+
+/// The start of the private use 32-bit autonomous system numbers of generated autnums.
+const FIRST_AUTNUM: u32 = 4_200_000_000;
+
+/// A generated RDAP object.
+#[derive(Debug)]
+pub struct Generated {
+    pub self_href: String,
+    pub rdap: RdapResponse,
+}
+
+/// Generates fake but realistic RDAP objects.
+///
+/// Objects are numbered so that the names, handles, addresses and numbers of
+/// objects of the same type are unique. The rest, such as contacts and dates,
+/// is random, and the same for a given seed.
+pub struct Generator {
+    base_url: String,
+    rng: StdRng,
+}
+
+impl Generator {
+    /// Creates a generator of objects served at the base URL. If a seed is given,
+    /// the same objects are generated for the same seed.
+    pub fn new(base_url: &str, seed: Option<u64>) -> Self {
+        let rng = match seed {
+            Some(seed) => StdRng::seed_from_u64(seed),
+            None => StdRng::from_entropy(),
+        };
+        Self {
+            base_url: base_url.to_string(),
+            rng,
+        }
+    }
+
+    /// Generates `count` each of entities, nameservers, domains, networks and autnums.
+    ///
+    /// Domains refer to the generated entities and nameservers, and networks and
+    /// autnums to the generated entities.
+    pub fn generate(&mut self, count: usize) -> Result<Vec<Generated>, RdapServerError> {
+        let entities = (0..count)
+            .map(|i| self.entity(i))
+            .collect::<Result<Vec<_>, _>>()?;
+        let nameservers = (0..count)
+            .map(|i| self.nameserver(i))
+            .collect::<Result<Vec<_>, _>>()?;
+        let mut generated = vec![];
+        for i in 0..count {
+            let registrant = &entities[i];
+            let technical = &entities[(i + 1) % count];
+            let ns = [&nameservers[i], &nameservers[(i + 1) % count]];
+            generated.push(self.domain(i, registrant, technical, ns)?);
+            generated.push(self.network(i, registrant)?);
+            generated.push(self.autnum(i, technical)?);
+        }
+        generated.extend(entities.into_iter().map(|(self_href, entity)| Generated {
+            self_href,
+            rdap: entity.to_response(),
+        }));
+        generated.extend(
+            nameservers
+                .into_iter()
+                .map(|(self_href, nameserver)| Generated {
+                    self_href,
+                    rdap: nameserver.to_response(),
+                }),
+        );
+        Ok(generated)
+    }
+
+    fn entity(&mut self, i: usize) -> Result<(String, Entity), RdapServerError> {
+        let handle = format!("GEN{i}-RDAP");
+        let self_href = self.self_href(QueryType::Entity(handle.clone()))?;
+        let given_name = self.choose(&GIVEN_NAMES);
+        let surname = self.choose(&SURNAMES);
+        let organization = format!(
+            "{} {}",
+            capitalize(self.choose(&WORDS)),
+            self.choose(&ORG_KINDS)
+        );
+        let (locality, region_name, region_code, country_code) = self.choose(&PLACES);
+        let postal_address = PostalAddress::builder()
+            .street_parts(vec![format!(
+                "{} {} Street",
+                self.rng.gen_range(1..2000),
+                capitalize(self.choose(&WORDS))
+            )])
+            .locality(locality)
+            .region_name(region_name)
+            .region_code(region_code)
+            .country_code(country_code)
+            .postal_code(format!("{:05}", self.rng.gen_range(0..100_000)))
+            .build();
+        let contact = Contact::builder()
+            .kind("individual")
+            .full_name(format!("{given_name} {surname}"))
+            .organization_names(vec![organization])
+            .build()
+            .set_emails(&[format!(
+                "{}.{}@{}",
+                given_name.to_lowercase(),
+                surname.to_lowercase(),
+                self.domain_name(i)
+            )])
+            .add_voice_phones(&[format!("+1.555{:07}", self.rng.gen_range(0..10_000_000))])
+            .set_postal_address(postal_address);
+        let entity = Entity::response_obj()
+            .handle(handle)
+            .contact(contact)
+            .statuses(vec!["active".to_string()])
+            .events(self.events(None))
+            .links(vec![self_link(&self_href)])
+            .build();
+        Ok((self_href, entity))
+    }
+
+    fn nameserver(&mut self, i: usize) -> Result<(String, Nameserver), RdapServerError> {
+        let ldh_name = format!("ns{}.{}", i % 2 + 1, self.host_name(i / 2));
+        let self_href = self.self_href(QueryType::ns(&ldh_name)?)?;
+        let nameserver = Nameserver::response_obj()
+            .ldh_name(ldh_name)
+            .addresses(vec![
+                format!("192.0.2.{}", i % 254 + 1),
+                format!("2001:db8:ffff:{:x}::1", i % 65_536),
+            ])
+            .statuses(vec!["active".to_string()])
+            .events(self.events(None))
+            .links(vec![self_link(&self_href)])
+            .build()?;
+        Ok((self_href, nameserver))
+    }
+
+    fn domain(
+        &mut self,
+        i: usize,
+        registrant: &(String, Entity),
+        technical: &(String, Entity),
+        nameservers: [&(String, Nameserver); 2],
+    ) -> Result<Generated, RdapServerError> {
+        let ldh_name = self.domain_name(i);
+        let self_href = self.self_href(QueryType::domain(&ldh_name)?)?;
+        let registered = self.registration_date();
+        let mut events = self.events(Some(registered));
+        events.push(
+            Event::builder()
+                .event_action("expiration")
+                .event_date(
+                    (registered + Duration::days(365 * self.rng.gen_range(1..11))).to_rfc3339(),
+                )
+                .build(),
+        );
+        let mut statuses = vec![DOMAIN_STATUSES[0].to_string()];
+        statuses.extend(
+            DOMAIN_STATUSES[1..]
+                .iter()
+                .filter(|_| self.rng.gen_bool(0.5))
+                .map(|status| status.to_string()),
+        );
+        let domain = Domain::response_obj()
+            .ldh_name(ldh_name.clone())
+            .handle(format!("GEN{i}-DOM"))
+            .nameservers(
+                nameservers
+                    .iter()
+                    .map(|(_, nameserver)| nameserver.clone().to_child())
+                    .collect(),
+            )
+            .entities(vec![
+                with_role(&registrant.1, "registrant"),
+                with_role(&technical.1, "technical"),
+            ])
+            .statuses(statuses)
+            .events(events)
+            .links(vec![self_link(&self_href)])
+            .build();
+        Ok(Generated {
+            self_href,
+            rdap: domain.to_response(),
+        })
+    }
+
+    fn network(
+        &mut self,
+        i: usize,
+        registrant: &(String, Entity),
+    ) -> Result<Generated, RdapServerError> {
+        // IPv4 networks in 10.0.0.0/8 until they run out, then IPv6 networks in 2001:db8::/32
+        let cidr = if i < 65_536 {
+            format!("10.{}.{}.0/24", i / 256, i % 256)
+        } else {
+            let i = i - 65_536;
+            format!("2001:db8:{:x}:{:x}::/64", i / 65_536, i % 65_536)
+        };
+        let self_href = match &cidr {
+            cidr if cidr.contains(':') => self.self_href(QueryType::ipv6cidr(cidr)?)?,
+            cidr => self.self_href(QueryType::ipv4cidr(cidr)?)?,
+        };
+        let network = Network::response_obj()
+            .cidr(cidr)
+            .handle(format!("NET-GEN{i}"))
+            .name(format!("{}-NET-{i}", self.choose(&WORDS).to_uppercase()))
+            .network_type("ASSIGNED")
+            .country(self.choose(&PLACES).3)
+            .entities(vec![with_role(&registrant.1, "registrant")])
+            .statuses(vec!["active".to_string()])
+            .events(self.events(None))
+            .links(vec![self_link(&self_href)])
+            .build()?;
+        Ok(Generated {
+            self_href,
+            rdap: network.to_response(),
+        })
+    }
+
+    fn autnum(
+        &mut self,
+        i: usize,
+        technical: &(String, Entity),
+    ) -> Result<Generated, RdapServerError> {
+        let number = FIRST_AUTNUM + i as u32;
+        let self_href = self.self_href(QueryType::AsNumber(number))?;
+        let autnum = Autnum::response_obj()
+            .autnum_range(number..number)
+            .handle(format!("AS{number}"))
+            .name(format!("{}-AS", self.choose(&WORDS).to_uppercase()))
+            .autnum_type("DIRECT ALLOCATION")
+            .country(self.choose(&PLACES).3)
+            .entities(vec![with_role(&technical.1, "technical")])
+            .statuses(vec!["active".to_string()])
+            .events(self.events(None))
+            .links(vec![self_link(&self_href)])
+            .build();
+        Ok(Generated {
+            self_href,
+            rdap: autnum.to_response(),
+        })
+    }
+
+    /// The registration and last changed events, registered at the given date or a random one.
+    fn events(&mut self, registered: Option<DateTime<Utc>>) -> Events {
+        let registered = registered.unwrap_or_else(|| self.registration_date());
+        let changed = registered + Duration::days(self.rng.gen_range(0..1000));
+        vec![
+            Event::builder()
+                .event_action("registration")
+                .event_date(registered.to_rfc3339())
+                .build(),
+            Event::builder()
+                .event_action("last changed")
+                .event_date(changed.to_rfc3339())
+                .build(),
+        ]
+    }
+
+    fn registration_date(&mut self) -> DateTime<Utc> {
+        let start = Utc.with_ymd_and_hms(2000, 1, 1, 0, 0, 0).unwrap();
+        start + Duration::seconds(self.rng.gen_range(0..20 * 365 * 24 * 60 * 60))
+    }
+
+    fn domain_name(&self, i: usize) -> String {
+        format!(
+            "{}{}{i}.{}",
+            WORDS[i % WORDS.len()],
+            WORDS[(i / WORDS.len()) % WORDS.len()],
+            TLDS[i % TLDS.len()]
+        )
+    }
+
+    fn host_name(&self, i: usize) -> String {
+        format!("dns{i}.{}-hosting.{}", WORDS[i % WORDS.len()], TLDS[0])
+    }
+
+    fn self_href(&self, query: QueryType) -> Result<String, RdapServerError> {
+        Ok(query.query_url(&self.base_url)?)
+    }
+
+    fn choose<T: Copy>(&mut self, items: &[T]) -> T {
+        *items.choose(&mut self.rng).expect("nothing to choose from")
+    }
+}
+
+fn with_role(entity: &Entity, role: &str) -> Entity {
+    let mut entity = entity.clone();
+    entity.roles = Some(VectorStringish::from(role.to_string()));
+    entity.to_child()
+}
+
+fn self_link(self_href: &str) -> Link {
+    Link::builder()
+        .value(self_href)
+        .href(self_href)
+        .rel("self")
+        .media_type(RDAP_MEDIA_TYPE)
+        .build()
+}
+
+fn capitalize(word: &str) -> String {
+    let mut chars = word.chars();
+    chars
+        .next()
+        .map(|first| first.to_uppercase().chain(chars).collect())
+        .unwrap_or_default()
+}
+
+#[cfg(test)]
+#[allow(non_snake_case)]
+mod tests {
+    use std::collections::HashSet;
+
+    use super::Generator;
+
+    #[test]
+    fn GIVEN_count_WHEN_generate_THEN_count_of_each_with_unique_self_links() {
+        // GIVEN
+        let mut generator = Generator::new("https://rdap.example.com/rdap", Some(7));
+
+        // WHEN
+        let generated = generator.generate(20).expect("generating data");
+
+        // THEN
+        assert_eq!(generated.len(), 100);
+        let self_hrefs = generated
+            .iter()
+            .map(|generated| generated.self_href.as_str())
+            .collect::<HashSet<_>>();
+        assert_eq!(self_hrefs.len(), 100);
+    }
+
+    #[test]
+    fn GIVEN_same_seed_WHEN_generate_THEN_same_data() {
+        // GIVEN
+        let mut first = Generator::new("https://rdap.example.com/rdap", Some(7));
+        let mut second = Generator::new("https://rdap.example.com/rdap", Some(7));
+
+        // WHEN
+        let first = first.generate(3).expect("generating data");
+        let second = second.generate(3).expect("generating data");
+
+        // THEN
+        assert_eq!(
+            serde_json::to_string(&first.iter().map(|g| &g.rdap).collect::<Vec<_>>())
+                .expect("serializing"),
+            serde_json::to_string(&second.iter().map(|g| &g.rdap).collect::<Vec<_>>())
+                .expect("serializing"),
+        );
+    }
+}
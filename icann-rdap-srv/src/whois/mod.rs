@@ -0,0 +1,101 @@
+//! A WHOIS (RFC 3912) gateway answering classic WHOIS queries from storage,
+//! with the responses rendered from RDAP objects.
+
+use std::{net::SocketAddr, time::Duration};
+
+use {
+    icann_rdap_common::{
+        prelude::ToResponse,
+        response::{RdapResponse, Rfc9083Error},
+    },
+    tokio::{
+        io::{AsyncBufReadExt, AsyncReadExt, AsyncWriteExt, BufReader},
+        net::{TcpListener, TcpStream},
+        time::timeout,
+    },
+    tracing::{debug, warn},
+};
+
+use crate::{
+    error::RdapServerError,
+    server::{DynServiceState, ServiceState},
+};
+
+pub mod query;
+pub mod render;
+
+use self::{query::WhoisQuery, render::to_whois_text};
+
+/// The longest query read from a client.
+const MAX_QUERY_LENGTH: u64 = 1024;
+
+/// The time a client has to send its query.
+const QUERY_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Answers the WHOIS queries of the connections to a listener.
+pub(crate) async fn serve_whois(listener: TcpListener, state: DynServiceState) {
+    loop {
+        let (stream, addr) = match listener.accept().await {
+            Ok(accepted) => accepted,
+            Err(e) => {
+                warn!("unable to accept WHOIS connection: {e}");
+                continue;
+            }
+        };
+        let state = state.clone();
+        tokio::spawn(async move {
+            if let Err(e) = answer(stream, addr, state.as_ref()).await {
+                debug!("WHOIS connection from {addr}: {e}");
+            }
+        });
+    }
+}
+
+/// Reads the one query of a connection, and writes the answer.
+async fn answer(
+    stream: TcpStream,
+    addr: SocketAddr,
+    state: &(dyn ServiceState + Send + Sync),
+) -> Result<(), RdapServerError> {
+    let (reader, mut writer) = stream.into_split();
+    let mut query = String::new();
+    timeout(
+        QUERY_TIMEOUT,
+        BufReader::new(reader.take(MAX_QUERY_LENGTH)).read_line(&mut query),
+    )
+    .await
+    .map_err(|_| std::io::Error::from(std::io::ErrorKind::TimedOut))??;
+    let query = query.trim();
+    debug!("WHOIS query \"{query}\" from {addr}");
+
+    let limited = state
+        .get_rate_limiter()
+        .is_some_and(|limiter| limiter.check(addr.ip()).is_err());
+    let rdap = if limited {
+        error_rdap(429)
+    } else {
+        match WhoisQuery::parse(query) {
+            Some(whois_query) => {
+                let rdap = whois_query.lookup(state.get_storage().await?).await?;
+                match state.get_response_filter() {
+                    Some(filter) => filter.apply(&rdap)?,
+                    None => rdap,
+                }
+            }
+            None => error_rdap(400),
+        }
+    };
+
+    // WHOIS lines end with CRLF.
+    let text = to_whois_text(query, &rdap).replace('\n', "\r\n");
+    writer.write_all(text.as_bytes()).await?;
+    writer.shutdown().await?;
+    Ok(())
+}
+
+fn error_rdap(error_code: u16) -> RdapResponse {
+    Rfc9083Error::response_obj()
+        .error_code(error_code)
+        .build()
+        .to_response()
+}
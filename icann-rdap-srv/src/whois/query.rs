@@ -0,0 +1,142 @@
+//! Parsing of WHOIS queries into storage lookups.
+
+use std::str::FromStr;
+
+use {icann_rdap_client::rdap::QueryType, icann_rdap_common::response::RdapResponse};
+
+use crate::{error::RdapServerError, storage::StoreOps};
+
+/// A WHOIS query for an object in storage.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum WhoisQuery {
+    Domain(String),
+    Nameserver(String),
+    Entity(String),
+    Autnum(u32),
+    IpAddr(String),
+    Cidr(String),
+    Help,
+}
+
+impl WhoisQuery {
+    /// Parses a classic WHOIS query.
+    ///
+    /// A query is a value, such as `example.com`, `AS64496`, `192.0.2.1` or `FOO-EXAMPLE`,
+    /// whose type is guessed as the `rdap` client does. The type may also be given with a
+    /// keyword before the value: `domain`, `nameserver` (or `ns` or `host`), `entity`
+    /// (or `contact` or `handle`), `autnum` (or `as`) and `ip` (or `net`). An empty query
+    /// or `help` is for the help of the server. Gives `None` if a query is not understood.
+    pub fn parse(query: &str) -> Option<Self> {
+        // "=" asks some WHOIS servers for exact matches, which are all there are here.
+        let query = query.trim().trim_start_matches('=').trim();
+        if query.is_empty() || query.eq_ignore_ascii_case("help") {
+            return Some(Self::Help);
+        }
+        let (keyword, value) = match query.split_once(char::is_whitespace) {
+            Some((keyword, value)) => (Some(keyword.to_ascii_lowercase()), value.trim()),
+            None => (None, query),
+        };
+        let query_type = match keyword.as_deref() {
+            None => QueryType::from_str(value).ok()?,
+            Some("domain") => QueryType::domain(value).ok()?,
+            Some("nameserver" | "ns" | "host") => QueryType::ns(value).ok()?,
+            Some("entity" | "contact" | "handle") => QueryType::Entity(value.to_string()),
+            Some("autnum" | "as") => QueryType::autnum(value).ok()?,
+            Some("ip" | "net") => match QueryType::from_str(value).ok()? {
+                query_type @ (QueryType::IpV4Addr(_)
+                | QueryType::IpV6Addr(_)
+                | QueryType::IpV4Cidr(_)
+                | QueryType::IpV6Cidr(_)) => query_type,
+                _ => return None,
+            },
+            Some(_) => return None,
+        };
+        let name = || value.trim_end_matches('.').to_ascii_lowercase();
+        match query_type {
+            QueryType::Domain(_) | QueryType::ALabel(_) => Some(Self::Domain(name())),
+            QueryType::Nameserver(_) => Some(Self::Nameserver(name())),
+            QueryType::Entity(_) | QueryType::TaggedEntity(_) => {
+                Some(Self::Entity(value.to_string()))
+            }
+            QueryType::AsNumber(autnum) => Some(Self::Autnum(autnum)),
+            QueryType::IpV4Addr(addr) => Some(Self::IpAddr(addr.to_string())),
+            QueryType::IpV6Addr(addr) => Some(Self::IpAddr(addr.to_string())),
+            QueryType::IpV4Cidr(cidr) => Some(Self::Cidr(cidr.to_string())),
+            QueryType::IpV6Cidr(cidr) => Some(Self::Cidr(cidr.to_string())),
+            _ => None,
+        }
+    }
+
+    /// Looks up the object of the query in storage.
+    ///
+    /// As only names starting with `ns` are guessed to be of nameservers, names not
+    /// found as domains are looked up as nameservers.
+    pub async fn lookup(&self, storage: &dyn StoreOps) -> Result<RdapResponse, RdapServerError> {
+        let found = |rdap: &RdapResponse| {
+            !matches!(rdap, RdapResponse::ErrorResponse(_)) || rdap.is_redirect()
+        };
+        match self {
+            Self::Domain(name) => {
+                let domain = storage.get_domain_by_ldh(name).await?;
+                if found(&domain) {
+                    return Ok(domain);
+                }
+                let domain = storage.get_domain_by_unicode(name).await?;
+                if found(&domain) {
+                    return Ok(domain);
+                }
+                let nameserver = storage.get_nameserver_by_ldh(name).await?;
+                if found(&nameserver) {
+                    Ok(nameserver)
+                } else {
+                    Ok(domain)
+                }
+            }
+            Self::Nameserver(name) => storage.get_nameserver_by_ldh(name).await,
+            Self::Entity(handle) => storage.get_entity_by_handle(handle).await,
+            Self::Autnum(autnum) => storage.get_autnum_by_num(*autnum).await,
+            Self::IpAddr(addr) => storage.get_network_by_ipaddr(addr).await,
+            Self::Cidr(cidr) => storage.get_network_by_cidr(cidr).await,
+            Self::Help => storage.get_srv_help(None).await,
+        }
+    }
+}
+
+#[cfg(test)]
+#[allow(non_snake_case)]
+mod tests {
+    use rstest::rstest;
+
+    use super::WhoisQuery;
+
+    #[rstest]
+    #[case("example.com", Some(WhoisQuery::Domain("example.com".to_string())))]
+    #[case("EXAMPLE.com.\r\n", Some(WhoisQuery::Domain("example.com".to_string())))]
+    #[case("=example.com", Some(WhoisQuery::Domain("example.com".to_string())))]
+    #[case("domain ns1.example.com", Some(WhoisQuery::Domain("ns1.example.com".to_string())))]
+    #[case("ns1.example.com", Some(WhoisQuery::Nameserver("ns1.example.com".to_string())))]
+    #[case("host foo.example.com", Some(WhoisQuery::Nameserver("foo.example.com".to_string())))]
+    #[case("AS64496", Some(WhoisQuery::Autnum(64496)))]
+    #[case("as 64496", Some(WhoisQuery::Autnum(64496)))]
+    #[case("192.0.2.1", Some(WhoisQuery::IpAddr("192.0.2.1".to_string())))]
+    #[case("net 2001:db8::1", Some(WhoisQuery::IpAddr("2001:db8::1".to_string())))]
+    #[case("192.0.2.0/24", Some(WhoisQuery::Cidr("192.0.2.0/24".to_string())))]
+    #[case("FOO-EXAMPLE", Some(WhoisQuery::Entity("FOO-EXAMPLE".to_string())))]
+    #[case("contact foo", Some(WhoisQuery::Entity("foo".to_string())))]
+    #[case("", Some(WhoisQuery::Help))]
+    #[case("HELP", Some(WhoisQuery::Help))]
+    #[case("ip example.com", None)]
+    #[case("frobnicate example.com", None)]
+    fn GIVEN_whois_query_WHEN_parse_THEN_lookup(
+        #[case] query: &str,
+        #[case] expected: Option<WhoisQuery>,
+    ) {
+        // GIVEN in parameters
+
+        // WHEN
+        let actual = WhoisQuery::parse(query);
+
+        // THEN
+        assert_eq!(actual, expected);
+    }
+}
@@ -0,0 +1,173 @@
+//! Rendering of RDAP responses as WHOIS text.
+
+use std::fmt::Write;
+
+use {
+    icann_rdap_client::{
+        gtld::{GtldParams, ToGtldWhois},
+        whois::ToWhois,
+    },
+    icann_rdap_common::response::{Notice, RdapResponse, Rfc9083Error},
+};
+
+/// Renders a response to a WHOIS query as key/value text.
+///
+/// Domains are rendered as gTLD WHOIS, as with the `gtld-whois` output of the `rdap`
+/// client, and the other objects as with its `whois` output. Notices, such as terms
+/// of service, follow as comments.
+pub fn to_whois_text(query: &str, rdap: &RdapResponse) -> String {
+    let mut text = match rdap {
+        RdapResponse::Domain(_) => {
+            let mut params = GtldParams {
+                root: rdap,
+                parent_type: rdap.get_type(),
+                label: String::new(),
+            };
+            rdap.to_gtld_whois(&mut params).trim_start().to_string()
+        }
+        RdapResponse::ErrorResponse(error) => error_text(query, error),
+        _ => rdap.to_whois(),
+    };
+    let notices = match rdap {
+        RdapResponse::Domain(domain) => domain.common.notices.as_deref(),
+        RdapResponse::Nameserver(nameserver) => nameserver.common.notices.as_deref(),
+        RdapResponse::Entity(entity) => entity.common.notices.as_deref(),
+        RdapResponse::Autnum(autnum) => autnum.common.notices.as_deref(),
+        RdapResponse::Network(network) => network.common.notices.as_deref(),
+        RdapResponse::Help(help) => help.common.notices.as_deref(),
+        _ => None,
+    };
+    for notice in notices.unwrap_or_default() {
+        text.push('\n');
+        text.push_str(&notice_text(notice));
+    }
+    text
+}
+
+fn error_text(query: &str, error: &Rfc9083Error) -> String {
+    let href = error
+        .common
+        .notices
+        .iter()
+        .flatten()
+        .flat_map(|notice| notice.links())
+        .find_map(|link| link.href());
+    match (error.error_code(), href) {
+        (_, Some(href)) if error.is_redirect() => {
+            format!("% The object of \"{query}\" is served by {href}\n")
+        }
+        (400, _) => format!("% Query \"{query}\" is not understood.\n"),
+        (404, _) => format!("No match for \"{query}\".\n"),
+        (429, _) => "% Query rate exceeded.\n".to_string(),
+        (error_code, _) => format!("% Query \"{query}\" failed with error {error_code}.\n"),
+    }
+}
+
+fn notice_text(notice: &Notice) -> String {
+    let mut text = String::new();
+    if let Some(title) = notice.title() {
+        let _ = writeln!(text, "% {title}");
+    }
+    for description in notice.description() {
+        let _ = writeln!(text, "% {description}");
+    }
+    for href in notice.links().iter().filter_map(|link| link.href()) {
+        let _ = writeln!(text, "% {href}");
+    }
+    text
+}
+
+#[cfg(test)]
+#[allow(non_snake_case)]
+mod tests {
+    use icann_rdap_common::{
+        prelude::ToResponse,
+        response::{Autnum, Domain, Network, Notice, Rfc9083Error},
+    };
+
+    use super::to_whois_text;
+
+    #[test]
+    fn GIVEN_domain_WHEN_to_whois_text_THEN_gtld_whois() {
+        // GIVEN
+        let domain = Domain::response_obj()
+            .ldh_name("foo.example")
+            .status("active")
+            .notice(Notice::builder().title("Terms of Use").build())
+            .build()
+            .to_response();
+
+        // WHEN
+        let actual = to_whois_text("foo.example", &domain);
+
+        // THEN
+        assert!(actual.starts_with("Domain Name: foo.example\n"));
+        assert!(actual.contains("Domain Status: active\n"));
+        assert!(actual.ends_with("\n% Terms of Use\n"));
+    }
+
+    #[test]
+    fn GIVEN_autnum_WHEN_to_whois_text_THEN_key_values() {
+        // GIVEN
+        let autnum = Autnum::builder()
+            .autnum_range(64496..64496)
+            .handle("AS64496-EXAMPLE")
+            .build()
+            .to_response();
+
+        // WHEN
+        let actual = to_whois_text("AS64496", &autnum);
+
+        // THEN
+        assert!(actual.starts_with("ASNumber: 64496\nASHandle: AS64496-EXAMPLE\n"));
+    }
+
+    #[test]
+    fn GIVEN_network_WHEN_to_whois_text_THEN_key_values() {
+        // GIVEN
+        let network = Network::builder()
+            .cidr("192.0.2.0/24")
+            .build()
+            .expect("building network")
+            .to_response();
+
+        // WHEN
+        let actual = to_whois_text("192.0.2.1", &network);
+
+        // THEN
+        assert!(actual.starts_with("NetRange: 192.0.2.0 - 192.0.2.255\nCIDR: 192.0.2.0/24\n"));
+    }
+
+    #[test]
+    fn GIVEN_not_found_WHEN_to_whois_text_THEN_no_match() {
+        // GIVEN
+        let error = Rfc9083Error::response_obj()
+            .error_code(404)
+            .build()
+            .to_response();
+
+        // WHEN
+        let actual = to_whois_text("foo.example", &error);
+
+        // THEN
+        assert_eq!(actual, "No match for \"foo.example\".\n");
+    }
+
+    #[test]
+    fn GIVEN_redirect_WHEN_to_whois_text_THEN_served_by_url() {
+        // GIVEN
+        let redirect = Rfc9083Error::redirect()
+            .url("https://rdap.example.net/domain/foo.example")
+            .build()
+            .to_response();
+
+        // WHEN
+        let actual = to_whois_text("foo.example", &redirect);
+
+        // THEN
+        assert_eq!(
+            actual,
+            "% The object of \"foo.example\" is served by https://rdap.example.net/domain/foo.example\n"
+        );
+    }
+}
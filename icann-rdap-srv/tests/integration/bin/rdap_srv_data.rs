@@ -279,3 +279,53 @@ fn make_foo1234() -> RdapSrvDataTestJig {
     assert.success();
     test_jig.new_cmd()
 }
+
+#[test]
+fn GIVEN_generate_WHEN_invoked_THEN_objects_stored_in_data_dir() {
+    // GIVEN
+    let mut test_jig = RdapSrvDataTestJig::new();
+
+    // WHEN
+    test_jig
+        .cmd
+        .arg("--data-dir")
+        .arg(test_jig.source_dir.root())
+        .arg("--generate")
+        .arg("3")
+        .arg("--seed")
+        .arg("1");
+
+    // THEN
+    let assert = test_jig.cmd.assert();
+    assert.success();
+    assert_eq!(
+        test_jig
+            .source_dir
+            .root()
+            .read_dir()
+            .expect("source directory does not exist")
+            .count(),
+        15
+    );
+}
+
+#[test]
+fn GIVEN_generate_and_command_WHEN_invoked_THEN_error() {
+    // GIVEN
+    let mut test_jig = RdapSrvDataTestJig::new();
+
+    // WHEN
+    test_jig
+        .cmd
+        .arg("--generate")
+        .arg("3")
+        .arg("entity")
+        .arg("--handle")
+        .arg("foo1234")
+        .arg("--full-name")
+        .arg("Joe User");
+
+    // THEN
+    let assert = test_jig.cmd.assert();
+    assert.failure();
+}
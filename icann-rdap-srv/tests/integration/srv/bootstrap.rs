@@ -6,9 +6,12 @@ use {
         rdap::{rdap_request, QueryType},
     },
     icann_rdap_common::response::Rfc9083Error,
-    icann_rdap_srv::storage::{
-        data::{AutnumId, DomainId, EntityId, NetworkId, NetworkIdType},
-        StoreOps,
+    icann_rdap_srv::{
+        bootstrap::BootstrapRegistries,
+        storage::{
+            data::{AutnumId, DomainId, EntityId, NetworkId, NetworkIdType},
+            StoreOps,
+        },
     },
 };
 
@@ -381,3 +384,59 @@ async fn GIVEN_bootstrap_with_no_specific_tag_WHEN_query_entity_THEN_should_pani
     // THEN
     response.expect("this should be 404"); // SHOLD PANIC
 }
+
+#[tokio::test]
+async fn GIVEN_embedded_registries_WHEN_query_domain_THEN_redirect_to_registry_url() {
+    // GIVEN
+    let test_srv = SrvTestJig::new_bootstrap_registries(BootstrapRegistries::embedded()).await;
+
+    // WHEN
+    let client_config = ClientConfig::builder()
+        .https_only(false)
+        .follow_redirects(false)
+        .build();
+    let client = create_client(&client_config).expect("creating client");
+    let query = QueryType::domain("foo.example").expect("invalid domain name");
+    let response = rdap_request(&test_srv.rdap_base, &query, &client)
+        .await
+        .expect("quering server");
+
+    // THEN
+    assert!(response.rdap.is_redirect());
+    assert_eq!(
+        response
+            .http_data
+            .location
+            .as_ref()
+            .expect("no location header information"),
+        "https://rdap.example.com/domain/foo.example"
+    );
+}
+
+#[tokio::test]
+async fn GIVEN_embedded_registries_WHEN_query_ip_THEN_redirect_to_registry_url() {
+    // GIVEN
+    let test_srv = SrvTestJig::new_bootstrap_registries(BootstrapRegistries::embedded()).await;
+
+    // WHEN
+    let client_config = ClientConfig::builder()
+        .https_only(false)
+        .follow_redirects(false)
+        .build();
+    let client = create_client(&client_config).expect("creating client");
+    let query = QueryType::ipv4("203.0.113.1").expect("invalid IP address");
+    let response = rdap_request(&test_srv.rdap_base, &query, &client)
+        .await
+        .expect("quering server");
+
+    // THEN
+    assert!(response.rdap.is_redirect());
+    assert_eq!(
+        response
+            .http_data
+            .location
+            .as_ref()
+            .expect("no location header information"),
+        "https://rdap.example.net/ip/203.0.113.1"
+    );
+}
@@ -0,0 +1,117 @@
+#![allow(non_snake_case)]
+
+use {
+    icann_rdap_common::response::{Domain, Event},
+    icann_rdap_srv::storage::StoreOps,
+};
+
+use crate::test_jig::SrvTestJig;
+
+async fn new_srv_with_domain() -> SrvTestJig {
+    let test_srv = SrvTestJig::new().await;
+    let mut tx = test_srv.mem.new_tx().await.expect("new transaction");
+    tx.add_domain(
+        &Domain::builder()
+            .ldh_name("foo.example")
+            .events(vec![
+                Event::builder()
+                    .event_action("registration")
+                    .event_date("2020-01-01T00:00:00Z")
+                    .build(),
+                Event::builder()
+                    .event_action("last changed")
+                    .event_date("2024-03-04T05:06:07Z")
+                    .build(),
+            ])
+            .build(),
+    )
+    .await
+    .expect("add domain in tx");
+    tx.commit().await.expect("tx commit");
+    test_srv
+}
+
+#[tokio::test]
+async fn GIVEN_domain_WHEN_get_THEN_etag_and_last_modified() {
+    // GIVEN
+    let test_srv = new_srv_with_domain().await;
+
+    // WHEN
+    let response = reqwest::Client::new()
+        .get(format!("{}/domain/foo.example", test_srv.rdap_base))
+        .send()
+        .await
+        .expect("querying server");
+
+    // THEN
+    assert_eq!(response.status().as_u16(), 200);
+    let etag = response.headers()["etag"].to_str().expect("etag");
+    assert!(etag.starts_with("W/\""));
+    assert_eq!(
+        response.headers()["last-modified"],
+        "Mon, 04 Mar 2024 05:06:07 GMT"
+    );
+}
+
+#[tokio::test]
+async fn GIVEN_domain_WHEN_head_THEN_headers_of_get_without_body() {
+    // GIVEN
+    let test_srv = new_srv_with_domain().await;
+    let client = reqwest::Client::new();
+    let url = format!("{}/domain/foo.example", test_srv.rdap_base);
+    let get = client.get(&url).send().await.expect("querying server");
+
+    // WHEN
+    let head = client.head(&url).send().await.expect("querying server");
+
+    // THEN
+    assert_eq!(head.status().as_u16(), 200);
+    assert_eq!(head.headers()["etag"], get.headers()["etag"]);
+    assert_eq!(
+        head.headers()["content-length"],
+        get.headers()["content-length"]
+    );
+    let body = head.bytes().await.expect("reading body");
+    assert!(body.is_empty());
+}
+
+#[tokio::test]
+async fn GIVEN_matching_if_none_match_WHEN_get_THEN_304() {
+    // GIVEN
+    let test_srv = new_srv_with_domain().await;
+    let client = reqwest::Client::new();
+    let url = format!("{}/domain/foo.example", test_srv.rdap_base);
+    let get = client.get(&url).send().await.expect("querying server");
+    let etag = get.headers()["etag"].clone();
+
+    // WHEN
+    let response = client
+        .get(&url)
+        .header("if-none-match", etag.clone())
+        .send()
+        .await
+        .expect("querying server");
+
+    // THEN
+    assert_eq!(response.status().as_u16(), 304);
+    assert_eq!(response.headers()["etag"], etag);
+    let body = response.bytes().await.expect("reading body");
+    assert!(body.is_empty());
+}
+
+#[tokio::test]
+async fn GIVEN_other_if_none_match_WHEN_get_THEN_200() {
+    // GIVEN
+    let test_srv = new_srv_with_domain().await;
+
+    // WHEN
+    let response = reqwest::Client::new()
+        .get(format!("{}/domain/foo.example", test_srv.rdap_base))
+        .header("if-none-match", "W/\"0000\"")
+        .send()
+        .await
+        .expect("querying server");
+
+    // THEN
+    assert_eq!(response.status().as_u16(), 200);
+}
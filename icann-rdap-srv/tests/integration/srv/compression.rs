@@ -0,0 +1,108 @@
+#![allow(non_snake_case)]
+
+use std::io::Read;
+
+use {
+    flate2::read::GzDecoder,
+    icann_rdap_common::response::{Domain, Entity},
+    icann_rdap_srv::{rdap::compression::CompressionConfig, storage::StoreOps},
+};
+
+use crate::test_jig::SrvTestJig;
+
+async fn new_srv_with_domain(min_size: usize) -> SrvTestJig {
+    let compression = CompressionConfig {
+        min_size,
+        ..Default::default()
+    };
+    let test_srv = SrvTestJig::new_compression(compression).await;
+    let mut tx = test_srv.mem.new_tx().await.expect("new transaction");
+    // many entities, so that the response is large enough to be smaller compressed
+    let entities = (0..50)
+        .map(|i| {
+            Entity::builder()
+                .handle(format!("CONTACT-{i}"))
+                .role("technical")
+                .build()
+        })
+        .collect();
+    let domain = Domain::response_obj()
+        .ldh_name("foo.example")
+        .entities(entities)
+        .build();
+    tx.add_domain(&domain).await.expect("add domain in tx");
+    tx.commit().await.expect("tx commit");
+    test_srv
+}
+
+#[tokio::test]
+async fn GIVEN_server_WHEN_query_accepting_gzip_THEN_gzip_response() {
+    // GIVEN
+    let test_srv = new_srv_with_domain(10).await;
+
+    // WHEN
+    let response = reqwest::Client::new()
+        .get(format!("{}/domain/foo.example", test_srv.rdap_base))
+        .header("accept-encoding", "gzip")
+        .send()
+        .await
+        .expect("quering server");
+
+    // THEN
+    assert_eq!(response.status().as_u16(), 200);
+    assert_eq!(response.headers()["content-encoding"], "gzip");
+    assert!(response
+        .headers()
+        .get_all("vary")
+        .iter()
+        .any(|vary| vary == "accept-encoding"));
+    let bytes = response.bytes().await.expect("reading body");
+    let mut json = String::new();
+    GzDecoder::new(bytes.as_ref())
+        .read_to_string(&mut json)
+        .expect("decompressing body");
+    let json: serde_json::Value = serde_json::from_str(&json).expect("parsing domain");
+    assert_eq!(json["ldhName"], "foo.example");
+}
+
+#[tokio::test]
+async fn GIVEN_server_WHEN_query_without_accept_encoding_THEN_uncompressed_response() {
+    // GIVEN
+    let test_srv = new_srv_with_domain(10).await;
+
+    // WHEN
+    let response = reqwest::get(format!("{}/domain/foo.example", test_srv.rdap_base))
+        .await
+        .expect("quering server");
+
+    // THEN
+    assert_eq!(response.status().as_u16(), 200);
+    assert!(response.headers().get("content-encoding").is_none());
+    assert!(response
+        .headers()
+        .get_all("vary")
+        .iter()
+        .any(|vary| vary == "accept-encoding"));
+    let json: serde_json::Value = response.json().await.expect("parsing domain");
+    assert_eq!(json["ldhName"], "foo.example");
+}
+
+#[tokio::test]
+async fn GIVEN_server_with_large_min_size_WHEN_query_accepting_gzip_THEN_uncompressed_response() {
+    // GIVEN
+    let test_srv = new_srv_with_domain(1024 * 1024).await;
+
+    // WHEN
+    let response = reqwest::Client::new()
+        .get(format!("{}/domain/foo.example", test_srv.rdap_base))
+        .header("accept-encoding", "gzip")
+        .send()
+        .await
+        .expect("quering server");
+
+    // THEN
+    assert_eq!(response.status().as_u16(), 200);
+    assert!(response.headers().get("content-encoding").is_none());
+    let json: serde_json::Value = response.json().await.expect("parsing domain");
+    assert_eq!(json["ldhName"], "foo.example");
+}
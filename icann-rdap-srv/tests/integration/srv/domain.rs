@@ -6,7 +6,7 @@ use {
         rdap::{rdap_request, QueryType},
         RdapClientError,
     },
-    icann_rdap_common::response::Domain,
+    icann_rdap_common::response::{Domain, Entity, RdapResponse, ReverseSearchProperty},
     icann_rdap_srv::storage::{CommonConfig, StoreOps},
 };
 
@@ -72,6 +72,7 @@ async fn GIVEN_server_with_domain_and_search_disabled_WHEN_query_domain_THEN_sta
     // GIVEN
     let common_config = CommonConfig::builder()
         .domain_search_by_name_enable(false)
+        .entity_search_index_enable(false)
         .build();
     let test_srv = SrvTestJig::new_common_config(common_config).await;
     let mut tx = test_srv.mem.new_tx().await.expect("new transaction");
@@ -101,6 +102,7 @@ async fn GIVEN_server_with_domain_and_search_enabled_WHEN_query_domain_THEN_stat
     // GIVEN
     let common_config = CommonConfig::builder()
         .domain_search_by_name_enable(true)
+        .entity_search_index_enable(false)
         .build();
     let test_srv = SrvTestJig::new_common_config(common_config).await;
     let mut tx = test_srv.mem.new_tx().await.expect("new transaction");
@@ -123,3 +125,37 @@ async fn GIVEN_server_with_domain_and_search_enabled_WHEN_query_domain_THEN_stat
     // THEN
     assert_eq!(response.http_data.status_code, 200);
 }
+
+#[tokio::test]
+async fn GIVEN_server_with_domain_and_entity_WHEN_reverse_search_THEN_status_code_200() {
+    // GIVEN
+    let test_srv = SrvTestJig::new().await;
+    let mut tx = test_srv.mem.new_tx().await.expect("new transaction");
+    tx.add_domain(
+        &Domain::builder()
+            .ldh_name("foo.example")
+            .entity(Entity::builder().handle("bob-example").build())
+            .build(),
+    )
+    .await
+    .expect("add domain in tx");
+    tx.commit().await.expect("tx commit");
+
+    // WHEN
+    let client_config = ClientConfig::builder()
+        .https_only(false)
+        .follow_redirects(false)
+        .build();
+    let client = create_client(&client_config).expect("creating client");
+    let query = QueryType::DomainReverseSearch(ReverseSearchProperty::Handle, "bob-*".to_string());
+    let response = rdap_request(&test_srv.rdap_base, &query, &client)
+        .await
+        .expect("quering server");
+
+    // THEN
+    assert_eq!(response.http_data.status_code, 200);
+    assert!(matches!(
+        response.rdap,
+        RdapResponse::DomainSearchResults(_)
+    ));
+}
@@ -0,0 +1,49 @@
+#![allow(non_snake_case)]
+
+use icann_rdap_srv::rdap::error_body::ErrorBodies;
+
+use crate::test_jig::SrvTestJig;
+
+#[tokio::test]
+async fn GIVEN_server_with_error_body_WHEN_query_missing_domain_THEN_customized_error() {
+    // GIVEN
+    let error_bodies: ErrorBodies = serde_json::from_str(
+        r#"[{ "status": 404, "title": "Not Found", "description": [ "No such object." ] }]"#,
+    )
+    .expect("parsing error bodies");
+    let test_srv = SrvTestJig::new_error_bodies(error_bodies).await;
+
+    // WHEN
+    let response = reqwest::get(format!("{}/domain/foo.example", test_srv.rdap_base))
+        .await
+        .expect("quering server");
+
+    // THEN
+    assert_eq!(response.status().as_u16(), 404);
+    assert_eq!(response.headers()["content-type"], "application/rdap+json");
+    let json: serde_json::Value = response.json().await.expect("parsing error");
+    assert_eq!(json["errorCode"], 404);
+    assert_eq!(json["title"], "Not Found");
+    assert_eq!(json["description"][0], "No such object.");
+}
+
+#[tokio::test]
+async fn GIVEN_server_without_error_body_for_host_WHEN_query_missing_domain_THEN_generated_error() {
+    // GIVEN
+    let error_bodies: ErrorBodies = serde_json::from_str(
+        r#"[{ "host": "rdap.example.com", "status": 404, "title": "Not Found" }]"#,
+    )
+    .expect("parsing error bodies");
+    let test_srv = SrvTestJig::new_error_bodies(error_bodies).await;
+
+    // WHEN
+    let response = reqwest::get(format!("{}/domain/foo.example", test_srv.rdap_base))
+        .await
+        .expect("quering server");
+
+    // THEN
+    assert_eq!(response.status().as_u16(), 404);
+    let json: serde_json::Value = response.json().await.expect("parsing error");
+    assert_eq!(json["errorCode"], 404);
+    assert!(json.get("title").is_none());
+}
@@ -0,0 +1,39 @@
+#![allow(non_snake_case)]
+
+use {
+    icann_rdap_common::response::{filter::ResponseFilter, Domain},
+    icann_rdap_srv::storage::StoreOps,
+};
+
+use crate::test_jig::SrvTestJig;
+
+#[tokio::test]
+async fn GIVEN_server_with_response_filter_WHEN_query_domain_THEN_members_filtered() {
+    // GIVEN
+    let filter = ResponseFilter::from_json(r#"{"remove": ["port43"], "mask": ["status"]}"#)
+        .expect("parsing filter");
+    let test_srv = SrvTestJig::new_response_filter(filter).await;
+    let mut tx = test_srv.mem.new_tx().await.expect("new transaction");
+    tx.add_domain(
+        &Domain::builder()
+            .ldh_name("foo.example")
+            .status("active")
+            .port_43("whois.foo.example".to_string())
+            .build(),
+    )
+    .await
+    .expect("add domain in tx");
+    tx.commit().await.expect("tx commit");
+
+    // WHEN
+    let response = reqwest::get(format!("{}/domain/foo.example", test_srv.rdap_base))
+        .await
+        .expect("quering server");
+
+    // THEN
+    assert_eq!(response.status().as_u16(), 200);
+    let json: serde_json::Value = response.json().await.expect("parsing domain");
+    assert_eq!(json["ldhName"], "foo.example");
+    assert_eq!(json["status"][0], "REDACTED");
+    assert!(json.get("port43").is_none());
+}
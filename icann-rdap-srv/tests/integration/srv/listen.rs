@@ -0,0 +1,66 @@
+#![allow(non_snake_case)]
+
+#[cfg(unix)]
+#[tokio::test]
+async fn GIVEN_server_on_unix_socket_WHEN_query_THEN_http_response() {
+    use {
+        icann_rdap_srv::{
+            config::ListenConfig,
+            rdap::{compression::Compression, error_body::ErrorBodies},
+            server::{AppState, Listener},
+            storage::mem::ops::Mem,
+        },
+        tokio::{
+            io::{AsyncReadExt, AsyncWriteExt},
+            net::UnixStream,
+        },
+    };
+
+    // GIVEN
+    let path = std::env::temp_dir().join(format!("rdap-srv-{}.sock", std::process::id()));
+    let listener = Listener::listen(
+        &ListenConfig::builder()
+            .unix_socket(path.to_string_lossy())
+            .socket_activation(false)
+            .build(),
+    )
+    .await
+    .expect("listening on unix socket");
+    let app_state = AppState {
+        storage: Mem::default(),
+        bootstrap: false,
+        search_page_size: None,
+        error_bodies: ErrorBodies::default(),
+        response_filter: None,
+        compression: Compression::default(),
+        deny_invalid_rdap: false,
+        reloader: None,
+        reload_endpoint: false,
+        rate_limiter: None,
+        proxy: None,
+    };
+    tokio::spawn(async move {
+        listener
+            .start_with_state(app_state)
+            .await
+            .expect("starting server");
+    });
+
+    // WHEN
+    let mut stream = UnixStream::connect(&path)
+        .await
+        .expect("connecting to unix socket");
+    stream
+        .write_all(b"GET /rdap/domain/foo.example HTTP/1.1\r\nHost: localhost\r\nConnection: close\r\n\r\n")
+        .await
+        .expect("writing request");
+    let mut response = String::new();
+    stream
+        .read_to_string(&mut response)
+        .await
+        .expect("reading response");
+
+    // THEN
+    assert!(response.starts_with("HTTP/1.1 404"));
+    assert!(response.contains("application/rdap+json"));
+}
@@ -1,4 +1,16 @@
 mod bootstrap;
+mod caching;
+mod compression;
 mod domain;
+mod error_body;
+mod filter;
+mod listen;
+mod proxy;
+mod rate_limit;
+mod rdap_x;
 mod redirect;
+mod reload;
 mod srvhelp;
+mod validation;
+mod virtual_host;
+mod whois;
@@ -0,0 +1,63 @@
+#![allow(non_snake_case)]
+
+use {
+    icann_rdap_common::{iana::IanaRegistry, response::Domain},
+    icann_rdap_srv::{bootstrap::BootstrapRegistries, storage::StoreOps},
+};
+
+use crate::test_jig::SrvTestJig;
+
+/// Makes the registries bootstrapping the `example` TLD to a server.
+fn example_registries(rdap_base: &str) -> BootstrapRegistries {
+    let dns: IanaRegistry = serde_json::from_str(&format!(
+        r#"{{
+            "version": "1.0",
+            "publication": "2024-01-07T10:11:12Z",
+            "services": [[["example"], ["{rdap_base}/"]]]
+        }}"#
+    ))
+    .expect("parsing registry");
+    BootstrapRegistries::builder().dns(dns).build()
+}
+
+#[tokio::test]
+async fn GIVEN_proxy_WHEN_query_domain_of_other_server_THEN_proxied_with_notice() {
+    // GIVEN
+    let upstream = SrvTestJig::new().await;
+    let mut tx = upstream.mem.new_tx().await.expect("new transaction");
+    tx.add_domain(&Domain::builder().ldh_name("foo.example").build())
+        .await
+        .expect("add domain in tx");
+    tx.commit().await.expect("tx commit");
+    let test_srv = SrvTestJig::new_proxy(example_registries(&upstream.rdap_base)).await;
+
+    // WHEN
+    let response = reqwest::get(format!("{}/domain/foo.example", test_srv.rdap_base))
+        .await
+        .expect("quering server");
+
+    // THEN
+    assert_eq!(response.status().as_u16(), 200);
+    let json: serde_json::Value = response.json().await.expect("parsing domain");
+    assert_eq!(json["ldhName"], "foo.example");
+    let notices = json["notices"].as_array().expect("notices");
+    assert_eq!(
+        notices.last().expect("proxy notice")["title"],
+        "Proxied Response"
+    );
+}
+
+#[tokio::test]
+async fn GIVEN_proxy_WHEN_query_domain_not_bootstrapped_THEN_not_found() {
+    // GIVEN
+    let upstream = SrvTestJig::new().await;
+    let test_srv = SrvTestJig::new_proxy(example_registries(&upstream.rdap_base)).await;
+
+    // WHEN
+    let response = reqwest::get(format!("{}/domain/foo.test", test_srv.rdap_base))
+        .await
+        .expect("quering server");
+
+    // THEN
+    assert_eq!(response.status().as_u16(), 404);
+}
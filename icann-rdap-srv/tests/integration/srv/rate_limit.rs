@@ -0,0 +1,62 @@
+#![allow(non_snake_case)]
+
+use icann_rdap_srv::rdap::rate_limit::RateLimitConfig;
+
+use crate::test_jig::SrvTestJig;
+
+#[tokio::test]
+async fn GIVEN_rate_limit_WHEN_burst_exceeded_THEN_429_with_notice() {
+    // GIVEN
+    let test_srv = SrvTestJig::new_rate_limit(
+        RateLimitConfig::builder()
+            .rate(0.1)
+            .burst(2)
+            .build()
+            .expect("valid rate limit"),
+    )
+    .await;
+    let client = reqwest::Client::new();
+    let url = format!("{}/domain/foo.example", test_srv.rdap_base);
+
+    // WHEN
+    let mut statuses = vec![];
+    for _ in 0..3 {
+        let response = client.get(&url).send().await.expect("querying server");
+        statuses.push(response.status().as_u16());
+    }
+    let response = client.get(&url).send().await.expect("querying server");
+
+    // THEN
+    assert_eq!(statuses, vec![404, 404, 429]);
+    assert_eq!(response.status().as_u16(), 429);
+    assert_eq!(response.headers()["retry-after"], "10");
+    let json: serde_json::Value = response.json().await.expect("parsing error");
+    assert_eq!(json["errorCode"], 429);
+    assert_eq!(json["notices"][0]["title"], "Too Many Requests");
+}
+
+#[tokio::test]
+async fn GIVEN_rate_limit_WHEN_requests_at_rate_THEN_not_limited() {
+    // GIVEN
+    let test_srv = SrvTestJig::new_rate_limit(
+        RateLimitConfig::builder()
+            .rate(20.0)
+            .burst(1)
+            .build()
+            .expect("valid rate limit"),
+    )
+    .await;
+    let client = reqwest::Client::new();
+    let url = format!("{}/domain/foo.example", test_srv.rdap_base);
+
+    // WHEN
+    let mut statuses = vec![];
+    for _ in 0..3 {
+        let response = client.get(&url).send().await.expect("querying server");
+        statuses.push(response.status().as_u16());
+        tokio::time::sleep(std::time::Duration::from_millis(100)).await;
+    }
+
+    // THEN
+    assert_eq!(statuses, vec![404, 404, 404]);
+}
@@ -0,0 +1,76 @@
+#![allow(non_snake_case)]
+
+use {
+    icann_rdap_common::response::{ExtensionId, Network},
+    icann_rdap_srv::storage::StoreOps,
+};
+
+use crate::test_jig::SrvTestJig;
+
+async fn new_srv_with_network() -> SrvTestJig {
+    let test_srv = SrvTestJig::new().await;
+    let mut tx = test_srv.mem.new_tx().await.expect("new transaction");
+    let mut network = Network::builder()
+        .cidr("10.0.0.0/24")
+        .build()
+        .expect("cidr parsing");
+    network.common.rdap_conformance = Some(vec![
+        ExtensionId::RdapLevel0.to_extension(),
+        ExtensionId::Cidr0.to_extension(),
+    ]);
+    tx.add_network(&network).await.expect("add network in tx");
+    tx.commit().await.expect("tx commit");
+    test_srv
+}
+
+#[tokio::test]
+async fn GIVEN_server_WHEN_query_accepting_rdap_x_with_extensions_THEN_filtered_response() {
+    // GIVEN
+    let test_srv = new_srv_with_network().await;
+
+    // WHEN
+    let response = reqwest::Client::new()
+        .get(format!("{}/ip/10.0.0.1", test_srv.rdap_base))
+        .header(
+            "accept",
+            r#"application/rdap-x+json;extensions="rdap_level_0", application/rdap+json;q=0.9"#,
+        )
+        .send()
+        .await
+        .expect("quering server");
+
+    // THEN
+    assert_eq!(response.status().as_u16(), 200);
+    assert_eq!(
+        response.headers()["content-type"],
+        r#"application/rdap-x+json;extensions="rdap_level_0""#
+    );
+    let json: serde_json::Value = response.json().await.expect("parsing network");
+    assert_eq!(json["rdapConformance"], serde_json::json!(["rdap_level_0"]));
+    assert!(json.get("cidr0_cidrs").is_none());
+}
+
+#[tokio::test]
+async fn GIVEN_server_WHEN_query_accepting_rdap_THEN_rdap_response_varying_on_accept() {
+    // GIVEN
+    let test_srv = new_srv_with_network().await;
+
+    // WHEN
+    let response = reqwest::Client::new()
+        .get(format!("{}/ip/10.0.0.1", test_srv.rdap_base))
+        .header("accept", "application/rdap+json")
+        .send()
+        .await
+        .expect("quering server");
+
+    // THEN
+    assert_eq!(response.status().as_u16(), 200);
+    assert_eq!(response.headers()["content-type"], "application/rdap+json");
+    assert!(response
+        .headers()
+        .get_all("vary")
+        .iter()
+        .any(|vary| vary == "accept"));
+    let json: serde_json::Value = response.json().await.expect("parsing network");
+    assert!(json.get("cidr0_cidrs").is_some());
+}
@@ -5,7 +5,7 @@ use {
         http::{create_client, ClientConfig},
         rdap::{rdap_request, QueryType},
     },
-    icann_rdap_common::response::{Link, Notice, NoticeOrRemark, Rfc9083Error},
+    icann_rdap_common::response::{Domain, Link, Notice, NoticeOrRemark, Rfc9083Error},
     icann_rdap_srv::storage::{
         data::{AutnumId, DomainId, EntityId, NameserverId, NetworkId, NetworkIdType},
         StoreOps,
@@ -262,6 +262,47 @@ async fn GIVEN_network_cidr_error_with_first_link_href_WHEN_query_THEN_status_co
     );
 }
 
+#[tokio::test]
+async fn GIVEN_network_permanent_redirect_WHEN_query_THEN_status_code_is_301() {
+    // GIVEN
+    let test_srv = SrvTestJig::new().await;
+    let mut tx = test_srv.mem.new_tx().await.expect("new transaction");
+    tx.add_network_err(
+        &NetworkId {
+            network_id: NetworkIdType::Cidr("192.0.2.0/24".parse().expect("parsing cidr")),
+        },
+        &Rfc9083Error::redirect()
+            .url("https://rdap.other-rir.example/ip/192.0.2.0/24")
+            .error_code(301)
+            .build(),
+    )
+    .await
+    .expect("add redirect in tx");
+    tx.commit().await.expect("tx commit");
+
+    // WHEN
+    let client_config = ClientConfig::builder()
+        .https_only(false)
+        .follow_redirects(false)
+        .build();
+    let client = create_client(&client_config).expect("creating client");
+    let query = QueryType::ipv4("192.0.2.1").expect("invalid IP address");
+    let response = rdap_request(&test_srv.rdap_base, &query, &client)
+        .await
+        .expect("quering server");
+
+    // THEN
+    assert_eq!(response.http_data.status_code, 301);
+    assert_eq!(
+        response
+            .http_data
+            .location
+            .as_ref()
+            .expect("no location header information"),
+        "https://rdap.other-rir.example/ip/192.0.2.0/24"
+    );
+}
+
 #[tokio::test]
 async fn GIVEN_network_addrs_error_with_first_link_href_WHEN_query_THEN_status_code_is_redirect() {
     // GIVEN
@@ -313,3 +354,54 @@ async fn GIVEN_network_addrs_error_with_first_link_href_WHEN_query_THEN_status_c
         "https://other.example.com"
     );
 }
+
+#[tokio::test]
+async fn GIVEN_domain_error_redirecting_to_domain_WHEN_query_following_redirects_THEN_redirect_is_recorded(
+) {
+    // GIVEN
+    let test_srv = SrvTestJig::new().await;
+    let target = format!("{}/domain/bar.example", test_srv.rdap_base);
+    let mut tx = test_srv.mem.new_tx().await.expect("new transaction");
+    tx.add_domain(&Domain::builder().ldh_name("bar.example").build())
+        .await
+        .expect("add domain in tx");
+    tx.add_domain_err(
+        &DomainId {
+            ldh_name: "foo.example".to_string(),
+            unicode_name: None,
+        },
+        &Rfc9083Error::response_obj()
+            .error_code(307)
+            .notice(Notice(
+                NoticeOrRemark::builder()
+                    .links(vec![Link::builder()
+                        .href(&target)
+                        .value(&target)
+                        .rel("about")
+                        .build()])
+                    .build(),
+            ))
+            .build(),
+    )
+    .await
+    .expect("add redirect in tx");
+    tx.commit().await.expect("tx commit");
+
+    // WHEN
+    let client_config = ClientConfig::builder()
+        .https_only(false)
+        .follow_redirects(true)
+        .build();
+    let client = create_client(&client_config).expect("creating client");
+    let query = QueryType::domain("foo.example").expect("invalid domain name");
+    let response = rdap_request(&test_srv.rdap_base, &query, &client)
+        .await
+        .expect("quering server");
+
+    // THEN
+    assert_eq!(response.http_data.status_code, 200);
+    assert_eq!(response.http_data.redirects.len(), 1);
+    let redirect = &response.http_data.redirects[0];
+    assert_eq!(redirect.status_code, 307);
+    assert_eq!(redirect.location.as_deref(), Some(target.as_str()));
+}
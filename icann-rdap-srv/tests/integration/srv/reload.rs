@@ -0,0 +1,73 @@
+#![allow(non_snake_case)]
+
+use {
+    icann_rdap_common::response::Domain,
+    test_dir::{DirBuilder, TestDir},
+};
+
+use crate::test_jig::SrvTestJig;
+
+fn reload_url(rdap_base: &str) -> String {
+    format!("{}/__reload", rdap_base.trim_end_matches("/rdap"))
+}
+
+#[tokio::test]
+async fn GIVEN_new_domain_file_WHEN_post_reload_THEN_domain_served() {
+    // GIVEN
+    let temp = TestDir::temp();
+    let test_srv = SrvTestJig::new_reload_endpoint(&temp).await;
+    let domain = Domain::builder().ldh_name("foo.example").build();
+    std::fs::write(
+        temp.path("foo_example.json"),
+        serde_json::to_string(&domain).expect("serializing domain"),
+    )
+    .expect("writing file");
+
+    // WHEN
+    let reload = reqwest::Client::new()
+        .post(reload_url(&test_srv.rdap_base))
+        .send()
+        .await
+        .expect("posting reload");
+
+    // THEN
+    assert_eq!(reload.status().as_u16(), 200);
+    let response = reqwest::get(format!("{}/domain/foo.example", test_srv.rdap_base))
+        .await
+        .expect("quering server");
+    assert_eq!(response.status().as_u16(), 200);
+}
+
+#[tokio::test]
+async fn GIVEN_bad_data_file_WHEN_post_reload_THEN_status_code_500() {
+    // GIVEN
+    let temp = TestDir::temp();
+    let test_srv = SrvTestJig::new_reload_endpoint(&temp).await;
+    std::fs::write(temp.path("bad.json"), "not json").expect("writing file");
+
+    // WHEN
+    let reload = reqwest::Client::new()
+        .post(reload_url(&test_srv.rdap_base))
+        .send()
+        .await
+        .expect("posting reload");
+
+    // THEN
+    assert_eq!(reload.status().as_u16(), 500);
+}
+
+#[tokio::test]
+async fn GIVEN_server_without_reload_endpoint_WHEN_post_reload_THEN_status_code_404() {
+    // GIVEN
+    let test_srv = SrvTestJig::new().await;
+
+    // WHEN
+    let reload = reqwest::Client::new()
+        .post(reload_url(&test_srv.rdap_base))
+        .send()
+        .await
+        .expect("posting reload");
+
+    // THEN
+    assert_eq!(reload.status().as_u16(), 404);
+}
@@ -0,0 +1,58 @@
+#![allow(non_snake_case)]
+
+use {
+    icann_rdap_common::response::{Domain, Link},
+    icann_rdap_srv::storage::StoreOps,
+};
+
+use crate::test_jig::SrvTestJig;
+
+#[tokio::test]
+async fn GIVEN_server_denying_invalid_rdap_WHEN_query_valid_domain_THEN_status_code_200() {
+    // GIVEN
+    let test_srv = SrvTestJig::new_deny_invalid_rdap().await;
+    let mut tx = test_srv.mem.new_tx().await.expect("new transaction");
+    tx.add_domain(&Domain::response_obj().ldh_name("foo.example").build())
+        .await
+        .expect("add domain in tx");
+    tx.commit().await.expect("tx commit");
+
+    // WHEN
+    let response = reqwest::get(format!("{}/domain/foo.example", test_srv.rdap_base))
+        .await
+        .expect("quering server");
+
+    // THEN
+    assert_eq!(response.status().as_u16(), 200);
+    let json: serde_json::Value = response.json().await.expect("parsing domain");
+    assert_eq!(json["ldhName"], "foo.example");
+}
+
+#[tokio::test]
+async fn GIVEN_server_denying_invalid_rdap_WHEN_query_invalid_domain_THEN_status_code_500() {
+    // GIVEN
+    let test_srv = SrvTestJig::new_deny_invalid_rdap().await;
+    let mut domain = Domain::response_obj().ldh_name("foo.example").build();
+    domain.object_common.links = Some(vec![Link {
+        value: Some("https://foo.example".to_string()),
+        rel: Some("self".to_string()),
+        href: None,
+        hreflang: None,
+        title: None,
+        media: None,
+        media_type: Some("application/rdap+json".to_string()),
+    }]);
+    let mut tx = test_srv.mem.new_tx().await.expect("new transaction");
+    tx.add_domain(&domain).await.expect("add domain in tx");
+    tx.commit().await.expect("tx commit");
+
+    // WHEN
+    let response = reqwest::get(format!("{}/domain/foo.example", test_srv.rdap_base))
+        .await
+        .expect("quering server");
+
+    // THEN
+    assert_eq!(response.status().as_u16(), 500);
+    let json: serde_json::Value = response.json().await.expect("parsing error");
+    assert_eq!(json["errorCode"], 500);
+}
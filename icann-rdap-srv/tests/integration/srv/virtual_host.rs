@@ -0,0 +1,61 @@
+#![allow(non_snake_case)]
+
+use {
+    icann_rdap_common::response::Domain,
+    icann_rdap_srv::storage::{mem::ops::Mem, StoreOps},
+};
+
+use crate::test_jig::SrvTestJig;
+
+async fn add_domain(mem: &Mem, ldh_name: &str) {
+    let mut tx = mem.new_tx().await.expect("new transaction");
+    tx.add_domain(&Domain::builder().ldh_name(ldh_name).build())
+        .await
+        .expect("add domain in tx");
+    tx.commit().await.expect("tx commit");
+}
+
+async fn get_status(rdap_base: &str, host: Option<&str>, ldh_name: &str) -> u16 {
+    let client = reqwest::Client::new();
+    let mut request = client.get(format!("{rdap_base}/domain/{ldh_name}"));
+    if let Some(host) = host {
+        request = request.header("Host", host);
+    }
+    request
+        .send()
+        .await
+        .expect("quering server")
+        .status()
+        .as_u16()
+}
+
+#[tokio::test]
+async fn GIVEN_virtual_host_WHEN_query_domains_THEN_each_host_serves_its_own() {
+    // GIVEN
+    let host_mem = Mem::default();
+    let test_srv = SrvTestJig::new_virtual_host("rdap.other.test", host_mem.clone()).await;
+    add_domain(&test_srv.mem, "foo.example").await;
+    add_domain(&host_mem, "bar.example").await;
+
+    // WHEN
+    let default_foo = get_status(&test_srv.rdap_base, None, "foo.example").await;
+    let default_bar = get_status(&test_srv.rdap_base, None, "bar.example").await;
+    let host_foo = get_status(
+        &test_srv.rdap_base,
+        Some("RDAP.Other.test:80"),
+        "foo.example",
+    )
+    .await;
+    let host_bar = get_status(
+        &test_srv.rdap_base,
+        Some("RDAP.Other.test:80"),
+        "bar.example",
+    )
+    .await;
+
+    // THEN
+    assert_eq!(default_foo, 200);
+    assert_eq!(default_bar, 404);
+    assert_eq!(host_foo, 404);
+    assert_eq!(host_bar, 200);
+}
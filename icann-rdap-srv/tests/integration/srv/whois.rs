@@ -0,0 +1,76 @@
+#![allow(non_snake_case)]
+
+use {
+    icann_rdap_common::response::{Autnum, Domain, Nameserver},
+    icann_rdap_srv::storage::StoreOps,
+};
+
+use crate::test_jig::SrvWhoisTestJig;
+
+#[tokio::test]
+async fn GIVEN_domain_in_mem_WHEN_whois_query_THEN_domain_text() {
+    // GIVEN
+    let test_srv = SrvWhoisTestJig::new().await;
+    let mut tx = test_srv.mem.new_tx().await.expect("new transaction");
+    tx.add_domain(&Domain::builder().ldh_name("foo.example").build())
+        .await
+        .expect("add domain in tx");
+    tx.commit().await.expect("tx commit");
+
+    // WHEN
+    let answer = test_srv.query("FOO.example").await;
+
+    // THEN
+    assert!(answer.starts_with("Domain Name: foo.example\r\n"));
+}
+
+#[tokio::test]
+async fn GIVEN_nameserver_in_mem_WHEN_whois_query_THEN_nameserver_text() {
+    // GIVEN
+    let test_srv = SrvWhoisTestJig::new().await;
+    let mut tx = test_srv.mem.new_tx().await.expect("new transaction");
+    tx.add_nameserver(
+        &Nameserver::builder()
+            .ldh_name("ns1.dns.foo.example")
+            .build()
+            .expect("building nameserver"),
+    )
+    .await
+    .expect("add nameserver in tx");
+    tx.commit().await.expect("tx commit");
+
+    // WHEN
+    let answer = test_srv.query("ns1.dns.foo.example").await;
+
+    // THEN
+    assert_eq!(answer, "Name Server: ns1.dns.foo.example\r\n");
+}
+
+#[tokio::test]
+async fn GIVEN_autnum_in_mem_WHEN_whois_query_THEN_autnum_text() {
+    // GIVEN
+    let test_srv = SrvWhoisTestJig::new().await;
+    let mut tx = test_srv.mem.new_tx().await.expect("new transaction");
+    tx.add_autnum(&Autnum::builder().autnum_range(700..710).build())
+        .await
+        .expect("add autnum in tx");
+    tx.commit().await.expect("tx commit");
+
+    // WHEN
+    let answer = test_srv.query("AS705").await;
+
+    // THEN
+    assert_eq!(answer, "ASNumber: 700 - 710\r\n");
+}
+
+#[tokio::test]
+async fn GIVEN_empty_mem_WHEN_whois_query_THEN_no_match() {
+    // GIVEN
+    let test_srv = SrvWhoisTestJig::new().await;
+
+    // WHEN
+    let answer = test_srv.query("foo.example").await;
+
+    // THEN
+    assert_eq!(answer, "No match for \"foo.example\".\r\n");
+}
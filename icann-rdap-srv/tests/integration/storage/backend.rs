@@ -0,0 +1,70 @@
+#![allow(non_snake_case)]
+
+use std::sync::Arc;
+
+use {
+    async_trait::async_trait,
+    icann_rdap_common::response::{Domain, RdapResponse},
+    icann_rdap_srv::{
+        error::RdapServerError,
+        storage::{
+            backend::{register_storage_backend, storage_backend, StorageBackend},
+            mem::{config::MemConfig, ops::Mem},
+            CommonConfig, DynStoreOps, StoreOps,
+        },
+    },
+};
+
+struct TestBackend;
+
+#[async_trait]
+impl StorageBackend for TestBackend {
+    fn name(&self) -> &str {
+        "test-backend"
+    }
+
+    async fn new_store(
+        &self,
+        common_config: CommonConfig,
+    ) -> Result<Arc<DynStoreOps>, RdapServerError> {
+        let config = MemConfig::builder().common_config(common_config).build();
+        Ok(Arc::new(Mem::new(config)))
+    }
+}
+
+#[tokio::test]
+async fn GIVEN_registered_backend_WHEN_new_store_THEN_store_is_usable() {
+    // GIVEN
+    register_storage_backend(TestBackend);
+
+    // WHEN
+    let store = storage_backend("test-backend")
+        .expect("backend not registered")
+        .new_store(CommonConfig::default())
+        .await
+        .expect("new store");
+
+    // THEN
+    store.init().await.expect("init");
+    let mut tx = store.new_tx().await.expect("new transaction");
+    tx.add_domain(&Domain::builder().ldh_name("foo.example").build())
+        .await
+        .expect("add domain in tx");
+    tx.commit().await.expect("tx commit");
+    let actual = store
+        .get_domain_by_ldh("foo.example")
+        .await
+        .expect("getting domain");
+    assert!(matches!(actual, RdapResponse::Domain(_)));
+}
+
+#[test]
+fn GIVEN_no_registered_backend_WHEN_storage_backend_THEN_none() {
+    // GIVEN no registration
+
+    // WHEN
+    let actual = storage_backend("no-such-backend");
+
+    // THEN
+    assert!(actual.is_none());
+}
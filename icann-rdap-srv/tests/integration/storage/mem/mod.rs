@@ -2,15 +2,17 @@
 
 use {
     icann_rdap_common::{
+        contact::Contact,
         prelude::Numberish,
         response::{
             Autnum, Common, Domain, Entity, Help, Nameserver, Network, Notice, NoticeOrRemark,
-            ObjectCommon, RdapResponse,
+            ObjectCommon, RdapResponse, ReverseSearchProperty, Rfc9083Error, RirSearchRelation,
         },
     },
     icann_rdap_srv::storage::{
+        data::{DomainId, EntityId, NetworkId, NetworkIdType},
         mem::{config::MemConfig, ops::Mem},
-        CommonConfig, StoreOps,
+        CommonConfig, StoreOps, StoreUpdate,
     },
     rstest::rstest,
 };
@@ -143,6 +145,7 @@ async fn GIVEN_domain_in_mem_but_search_not_enabled_WHEN_search_domain_by_name_T
         .common_config(
             CommonConfig::builder()
                 .domain_search_by_name_enable(false)
+                .entity_search_index_enable(false)
                 .build(),
         )
         .build();
@@ -170,6 +173,270 @@ async fn GIVEN_domain_in_mem_but_search_not_enabled_WHEN_search_domain_by_name_T
     };
 }
 
+#[rstest]
+#[case(ReverseSearchProperty::Fn, "bobby*")]
+#[case(ReverseSearchProperty::Handle, "BOB-EXAMPLE")]
+#[case(ReverseSearchProperty::Email, "bobby@example.com")]
+#[case(ReverseSearchProperty::Role, "registrant")]
+#[tokio::test]
+async fn GIVEN_domain_with_entity_in_mem_WHEN_reverse_search_THEN_domain_returned(
+    #[case] property: ReverseSearchProperty,
+    #[case] value: &str,
+) {
+    // GIVEN
+    let mem = Mem::default();
+    let mut tx = mem.new_tx().await.expect("new transaction");
+    let entity = Entity::builder()
+        .handle("bob-example")
+        .contact(
+            Contact::builder()
+                .full_name("Bobby Tables")
+                .email(
+                    icann_rdap_common::contact::Email::builder()
+                        .email("bobby@example.com")
+                        .build(),
+                )
+                .build(),
+        )
+        .role("registrant".to_string())
+        .build();
+    tx.add_domain(
+        &Domain::builder()
+            .ldh_name("foo.example")
+            .entity(entity)
+            .build(),
+    )
+    .await
+    .expect("add domain in tx");
+    tx.add_domain(&Domain::builder().ldh_name("bar.example").build())
+        .await
+        .expect("add domain in tx");
+    tx.commit().await.expect("tx commit");
+
+    // WHEN
+    let actual = mem
+        .search_domains_by_entity(property, value)
+        .await
+        .expect("reverse search");
+
+    // THEN
+    let RdapResponse::DomainSearchResults(domains) = actual else {
+        panic!()
+    };
+    assert_eq!(domains.results.len(), 1);
+    assert_eq!(
+        domains
+            .results
+            .first()
+            .expect("at least one")
+            .ldh_name
+            .as_deref(),
+        Some("foo.example")
+    );
+}
+
+#[tokio::test]
+async fn GIVEN_nameservers_in_mem_WHEN_search_nameservers_by_name_THEN_nameserver_returned() {
+    // GIVEN
+    let mem = Mem::default();
+    let mut tx = mem.new_tx().await.expect("new transaction");
+    tx.add_nameserver(
+        &Nameserver::builder()
+            .ldh_name("ns1.foo.example")
+            .build()
+            .expect("nameserver"),
+    )
+    .await
+    .expect("add nameserver in tx");
+    tx.add_nameserver(
+        &Nameserver::builder()
+            .ldh_name("ns1.bar.example")
+            .build()
+            .expect("nameserver"),
+    )
+    .await
+    .expect("add nameserver in tx");
+    tx.commit().await.expect("tx commit");
+
+    // WHEN
+    let actual = mem
+        .search_nameservers_by_name("ns1.fo*.example")
+        .await
+        .expect("searching nameservers");
+
+    // THEN
+    let RdapResponse::NameserverSearchResults(nameservers) = actual else {
+        panic!()
+    };
+    assert_eq!(nameservers.results.len(), 1);
+    assert_eq!(
+        nameservers.results[0].ldh_name.as_deref(),
+        Some("ns1.foo.example")
+    );
+}
+
+#[tokio::test]
+async fn GIVEN_nameserver_in_mem_WHEN_search_nameservers_by_ip_THEN_nameserver_returned() {
+    // GIVEN
+    let mem = Mem::default();
+    let mut tx = mem.new_tx().await.expect("new transaction");
+    tx.add_nameserver(
+        &Nameserver::builder()
+            .ldh_name("ns1.foo.example")
+            .address("2001:db8::0001")
+            .build()
+            .expect("nameserver"),
+    )
+    .await
+    .expect("add nameserver in tx");
+    tx.commit().await.expect("tx commit");
+
+    // WHEN
+    let actual = mem
+        .search_nameservers_by_ip("2001:db8::1")
+        .await
+        .expect("searching nameservers");
+
+    // THEN
+    let RdapResponse::NameserverSearchResults(nameservers) = actual else {
+        panic!()
+    };
+    assert_eq!(nameservers.results.len(), 1);
+}
+
+#[rstest]
+#[case("Bobby*", true)]
+#[case("bobby tables", true)]
+#[case("Bob", false)]
+#[tokio::test]
+async fn GIVEN_entity_in_mem_WHEN_search_entities_by_fn_THEN_entity_returned_if_matched(
+    #[case] full_name: &str,
+    #[case] expected: bool,
+) {
+    // GIVEN
+    let mem = Mem::default();
+    let mut tx = mem.new_tx().await.expect("new transaction");
+    tx.add_entity(
+        &Entity::builder()
+            .handle("bob-example")
+            .contact(Contact::builder().full_name("Bobby Tables").build())
+            .build(),
+    )
+    .await
+    .expect("add entity in tx");
+    tx.commit().await.expect("tx commit");
+
+    // WHEN
+    let actual = mem
+        .search_entities_by_fn(full_name)
+        .await
+        .expect("searching entities");
+
+    // THEN
+    let RdapResponse::EntitySearchResults(entities) = actual else {
+        panic!()
+    };
+    assert_eq!(!entities.results.is_empty(), expected);
+}
+
+#[rstest]
+#[case("tables", vec!["bob-example"])]
+#[case("Acme", vec!["bob-example", "ann-example"])]
+#[case("ann acme*", vec!["ann-example"])]
+#[case("Bobby Tables", vec!["bob-example"])]
+#[case("Bobby Smith", vec![])]
+#[case("tab", vec![])]
+#[tokio::test]
+async fn GIVEN_entities_in_mem_with_name_index_WHEN_search_entities_by_fn_THEN_entities_with_words_returned(
+    #[case] full_name: &str,
+    #[case] expected: Vec<&str>,
+) {
+    // GIVEN
+    let mem = Mem::new(
+        MemConfig::builder()
+            .common_config(
+                CommonConfig::builder()
+                    .domain_search_by_name_enable(false)
+                    .entity_search_index_enable(true)
+                    .build(),
+            )
+            .build(),
+    );
+    let mut tx = mem.new_tx().await.expect("new transaction");
+    tx.add_entity(
+        &Entity::builder()
+            .handle("bob-example")
+            .contact(
+                Contact::builder()
+                    .full_name("Bobby Tables")
+                    .organization_names(vec!["Acme".to_string()])
+                    .build(),
+            )
+            .build(),
+    )
+    .await
+    .expect("add entity in tx");
+    tx.add_entity(
+        &Entity::builder()
+            .handle("ann-example")
+            .contact(
+                Contact::builder()
+                    .full_name("Ann Other")
+                    .organization_names(vec!["Acme-Corp".to_string()])
+                    .build(),
+            )
+            .build(),
+    )
+    .await
+    .expect("add entity in tx");
+    tx.commit().await.expect("tx commit");
+
+    // WHEN
+    let actual = mem
+        .search_entities_by_fn(full_name)
+        .await
+        .expect("searching entities");
+
+    // THEN
+    let RdapResponse::EntitySearchResults(entities) = actual else {
+        panic!()
+    };
+    let mut handles = entities
+        .results
+        .iter()
+        .filter_map(|e| e.object_common.handle.as_deref())
+        .collect::<Vec<&str>>();
+    handles.sort();
+    let mut expected = expected;
+    expected.sort();
+    assert_eq!(handles, expected);
+}
+
+#[tokio::test]
+async fn GIVEN_entities_in_mem_WHEN_search_entities_by_handle_THEN_entities_returned() {
+    // GIVEN
+    let mem = Mem::default();
+    let mut tx = mem.new_tx().await.expect("new transaction");
+    for handle in ["bob-example", "bobby-example", "alice-example"] {
+        tx.add_entity(&Entity::builder().handle(handle).build())
+            .await
+            .expect("add entity in tx");
+    }
+    tx.commit().await.expect("tx commit");
+
+    // WHEN
+    let actual = mem
+        .search_entities_by_handle("BOB*")
+        .await
+        .expect("searching entities");
+
+    // THEN
+    let RdapResponse::EntitySearchResults(entities) = actual else {
+        panic!()
+    };
+    assert_eq!(entities.results.len(), 2);
+}
+
 #[tokio::test]
 async fn GIVEN_no_domain_in_mem_WHEN_lookup_domain_by_ldh_THEN_404_returned() {
     // GIVEN
@@ -742,3 +1009,384 @@ async fn GIVEN_help_in_mem_WHEN_lookup_help_with_host_THEN_get_host_help() {
         "bar"
     );
 }
+
+#[tokio::test]
+async fn GIVEN_domains_in_mem_WHEN_batch_update_with_delete_THEN_batch_applied() {
+    // GIVEN
+    let mem = Mem::default();
+    let mut tx = mem.new_tx().await.expect("new transaction");
+    tx.add_domain(&Domain::builder().ldh_name("foo.example").build())
+        .await
+        .expect("add domain in tx");
+    tx.add_domain(&Domain::builder().ldh_name("bar.example").build())
+        .await
+        .expect("add domain in tx");
+    tx.commit().await.expect("tx commit");
+
+    // WHEN
+    mem.batch_update(
+        &[
+            StoreUpdate::DeleteDomain(DomainId::builder().ldh_name("foo.example").build()),
+            StoreUpdate::AddDomain(Box::new(Domain::builder().ldh_name("baz.example").build())),
+        ],
+        false,
+    )
+    .await
+    .expect("batch update");
+
+    // THEN
+    let actual = mem
+        .get_domain_by_ldh("foo.example")
+        .await
+        .expect("getting domain by ldh");
+    let RdapResponse::ErrorResponse(error) = actual else {
+        panic!()
+    };
+    assert_eq!(error.error_code, 404);
+    let actual = mem
+        .get_domain_by_ldh("baz.example")
+        .await
+        .expect("getting domain by ldh");
+    assert!(matches!(actual, RdapResponse::Domain(_)));
+    let actual = mem
+        .search_domains_by_name("fo*.example")
+        .await
+        .expect("searching domains");
+    let RdapResponse::DomainSearchResults(domains) = actual else {
+        panic!()
+    };
+    assert!(domains.results.is_empty());
+}
+
+#[tokio::test]
+async fn GIVEN_batch_with_bad_update_WHEN_batch_update_THEN_storage_unchanged() {
+    // GIVEN
+    let mem = Mem::default();
+    let mut tx = mem.new_tx().await.expect("new transaction");
+    tx.add_domain(&Domain::builder().ldh_name("foo.example").build())
+        .await
+        .expect("add domain in tx");
+    tx.commit().await.expect("tx commit");
+    // domains without an ldhName cannot be stored.
+    let mut nameless = Domain::builder().ldh_name("baz.example").build();
+    nameless.ldh_name = None;
+
+    // WHEN
+    let actual = mem
+        .batch_update(
+            &[
+                StoreUpdate::AddDomain(Box::new(Domain::builder().ldh_name("bar.example").build())),
+                StoreUpdate::AddDomain(Box::new(nameless)),
+            ],
+            true,
+        )
+        .await;
+
+    // THEN
+    assert!(actual.is_err());
+    let actual = mem
+        .get_domain_by_ldh("foo.example")
+        .await
+        .expect("getting domain by ldh");
+    assert!(matches!(actual, RdapResponse::Domain(_)));
+    let actual = mem
+        .get_domain_by_ldh("bar.example")
+        .await
+        .expect("getting domain by ldh");
+    assert!(matches!(actual, RdapResponse::ErrorResponse(_)));
+}
+
+#[tokio::test]
+async fn GIVEN_network_in_mem_WHEN_batch_update_with_delete_THEN_network_deleted() {
+    // GIVEN
+    let mem = Mem::default();
+    let mut tx = mem.new_tx().await.expect("new transaction");
+    tx.add_network(
+        &Network::builder()
+            .cidr("192.168.0.0/24")
+            .build()
+            .expect("cidr parsing"),
+    )
+    .await
+    .expect("add network in tx");
+    tx.commit().await.expect("tx commit");
+
+    // WHEN
+    mem.batch_update(
+        &[StoreUpdate::DeleteNetwork(
+            NetworkId::builder()
+                .network_id(NetworkIdType::Cidr(
+                    "192.168.0.0/24".parse().expect("parsing cidr"),
+                ))
+                .build(),
+        )],
+        false,
+    )
+    .await
+    .expect("batch update");
+
+    // THEN
+    let actual = mem
+        .get_network_by_ipaddr("192.168.0.1")
+        .await
+        .expect("getting network by ip address");
+    let RdapResponse::ErrorResponse(error) = actual else {
+        panic!()
+    };
+    assert_eq!(error.error_code, 404);
+}
+
+#[tokio::test]
+async fn GIVEN_idn_in_mem_WHEN_upsert_with_other_unicode_name_THEN_old_unicode_name_not_found() {
+    // GIVEN
+    let mem = Mem::default();
+    let mut tx = mem.new_tx().await.expect("new transaction");
+    tx.add_domain(
+        &Domain::idn()
+            .unicode_name("foo.example")
+            .ldh_name("foo.example")
+            .build(),
+    )
+    .await
+    .expect("add domain in tx");
+    tx.commit().await.expect("tx commit");
+
+    // WHEN
+    let mut tx = mem.new_tx().await.expect("new transaction");
+    tx.add_domain(
+        &Domain::idn()
+            .unicode_name("bar.example")
+            .ldh_name("foo.example")
+            .build(),
+    )
+    .await
+    .expect("add domain in tx");
+    tx.commit().await.expect("tx commit");
+
+    // THEN
+    let actual = mem
+        .get_domain_by_unicode("foo.example")
+        .await
+        .expect("getting domain by unicode");
+    let RdapResponse::ErrorResponse(error) = actual else {
+        panic!()
+    };
+    assert_eq!(error.error_code, 404);
+    let actual = mem
+        .get_domain_by_unicode("bar.example")
+        .await
+        .expect("getting domain by unicode");
+    assert!(matches!(actual, RdapResponse::Domain(_)));
+}
+
+#[tokio::test]
+async fn GIVEN_domain_err_in_mem_WHEN_upsert_domain_THEN_domain_returned() {
+    // GIVEN
+    let mem = Mem::default();
+    let mut tx = mem.new_tx().await.expect("new transaction");
+    tx.add_domain_err(
+        &DomainId::builder().ldh_name("foo.example").build(),
+        &Rfc9083Error::response_obj().error_code(307).build(),
+    )
+    .await
+    .expect("add domain err in tx");
+    tx.commit().await.expect("tx commit");
+
+    // WHEN
+    let mut tx = mem.new_tx().await.expect("new transaction");
+    tx.add_domain(&Domain::builder().ldh_name("foo.example").build())
+        .await
+        .expect("add domain in tx");
+    tx.commit().await.expect("tx commit");
+
+    // THEN
+    let actual = mem
+        .get_domain_by_ldh("foo.example")
+        .await
+        .expect("getting domain by ldh");
+    assert!(matches!(actual, RdapResponse::Domain(_)));
+}
+
+#[tokio::test]
+async fn GIVEN_entity_in_mem_WHEN_delete_entity_THEN_entity_not_found() {
+    // GIVEN
+    let mem = Mem::default();
+    let mut tx = mem.new_tx().await.expect("new transaction");
+    tx.add_entity(&Entity::builder().handle("foo").build())
+        .await
+        .expect("add entity in tx");
+    tx.commit().await.expect("tx commit");
+
+    // WHEN
+    let mut tx = mem.new_tx().await.expect("new transaction");
+    tx.delete_entity(&EntityId::builder().handle("foo").build())
+        .await
+        .expect("delete entity in tx");
+    tx.commit().await.expect("tx commit");
+
+    // THEN
+    let actual = mem
+        .get_entity_by_handle("foo")
+        .await
+        .expect("getting entity by handle");
+    let RdapResponse::ErrorResponse(error) = actual else {
+        panic!()
+    };
+    assert_eq!(error.error_code, 404);
+}
+
+async fn mem_with_networks(cidrs: &[&str]) -> Mem {
+    let mem = Mem::default();
+    let mut tx = mem.new_tx().await.expect("new transaction");
+    for cidr in cidrs {
+        tx.add_network(
+            &Network::builder()
+                .cidr(*cidr)
+                .handle(format!("NET-{cidr}"))
+                .build()
+                .expect("cidr parsing"),
+        )
+        .await
+        .expect("add network in tx");
+    }
+    tx.commit().await.expect("tx commit");
+    mem
+}
+
+#[rstest]
+#[case(RirSearchRelation::Up, "10.1.1.0/24", "NET-10.1.0.0/16")]
+#[case(RirSearchRelation::Up, "10.1.1.1/32", "NET-10.1.0.0/16")]
+#[case(RirSearchRelation::Top, "10.1.1.0/24", "NET-10.0.0.0/8")]
+#[case(RirSearchRelation::Top, "10.0.0.0/8", "NET-10.0.0.0/8")]
+#[tokio::test]
+async fn GIVEN_contained_networks_in_mem_WHEN_search_up_or_top_THEN_network_returned(
+    #[case] relation: RirSearchRelation,
+    #[case] cidr: &str,
+    #[case] expected: &str,
+) {
+    // GIVEN
+    let mem = mem_with_networks(&["10.0.0.0/8", "10.1.0.0/16", "10.1.1.0/24", "10.2.0.0/16"]).await;
+
+    // WHEN
+    let actual = mem
+        .search_networks_by_relation(relation, cidr)
+        .await
+        .expect("searching networks by relation");
+
+    // THEN
+    let RdapResponse::Network(network) = actual else {
+        panic!("not a network")
+    };
+    assert_eq!(network.object_common.handle.as_deref(), Some(expected));
+}
+
+#[rstest]
+#[case(RirSearchRelation::Up, "10.0.0.0/8")]
+#[case(RirSearchRelation::Top, "192.168.0.0/24")]
+#[tokio::test]
+async fn GIVEN_networks_in_mem_WHEN_search_up_or_top_without_network_THEN_404_returned(
+    #[case] relation: RirSearchRelation,
+    #[case] cidr: &str,
+) {
+    // GIVEN
+    let mem = mem_with_networks(&["10.0.0.0/8", "10.1.0.0/16"]).await;
+
+    // WHEN
+    let actual = mem
+        .search_networks_by_relation(relation, cidr)
+        .await
+        .expect("searching networks by relation");
+
+    // THEN
+    let RdapResponse::ErrorResponse(error) = actual else {
+        panic!("not an error")
+    };
+    assert_eq!(error.error_code, 404)
+}
+
+#[rstest]
+#[case(RirSearchRelation::Down, "10.0.0.0/8", &["NET-10.1.0.0/16", "NET-10.2.0.0/16"])]
+#[case(RirSearchRelation::Down, "10.1.0.0/16", &["NET-10.1.1.0/24"])]
+#[case(RirSearchRelation::Bottom, "10.0.0.0/8", &["NET-10.1.1.0/24", "NET-10.2.0.0/16"])]
+#[case(RirSearchRelation::Bottom, "10.1.1.0/24", &[])]
+#[tokio::test]
+async fn GIVEN_contained_networks_in_mem_WHEN_search_down_or_bottom_THEN_networks_returned(
+    #[case] relation: RirSearchRelation,
+    #[case] cidr: &str,
+    #[case] expected: &[&str],
+) {
+    // GIVEN
+    let mem = mem_with_networks(&["10.0.0.0/8", "10.1.0.0/16", "10.1.1.0/24", "10.2.0.0/16"]).await;
+
+    // WHEN
+    let actual = mem
+        .search_networks_by_relation(relation, cidr)
+        .await
+        .expect("searching networks by relation");
+
+    // THEN
+    let RdapResponse::IpSearchResults(networks) = actual else {
+        panic!("not IP search results")
+    };
+    let handles = networks
+        .results
+        .iter()
+        .filter_map(|n| n.object_common.handle.as_deref())
+        .collect::<Vec<&str>>();
+    assert_eq!(handles, expected);
+}
+
+#[tokio::test]
+async fn GIVEN_networks_in_mem_WHEN_search_networks_by_handle_THEN_networks_returned() {
+    // GIVEN
+    let mem = mem_with_networks(&["10.0.0.0/8", "10.1.0.0/16", "192.168.0.0/16"]).await;
+
+    // WHEN
+    let actual = mem
+        .search_networks_by_handle("net-10.*")
+        .await
+        .expect("searching networks by handle");
+
+    // THEN
+    let RdapResponse::IpSearchResults(networks) = actual else {
+        panic!("not IP search results")
+    };
+    assert_eq!(networks.results.len(), 2);
+}
+
+#[tokio::test]
+async fn GIVEN_autnums_in_mem_WHEN_search_autnums_by_name_THEN_autnums_returned() {
+    // GIVEN
+    let mem = Mem::default();
+    let mut tx = mem.new_tx().await.expect("new transaction");
+    tx.add_autnum(
+        &Autnum::builder()
+            .autnum_range(700..710)
+            .name("EXAMPLE-AS")
+            .build(),
+    )
+    .await
+    .expect("add autnum in tx");
+    tx.add_autnum(
+        &Autnum::builder()
+            .autnum_range(800..810)
+            .name("OTHER-AS")
+            .build(),
+    )
+    .await
+    .expect("add autnum in tx");
+    tx.commit().await.expect("tx commit");
+
+    // WHEN
+    let actual = mem
+        .search_autnums_by_name("example*")
+        .await
+        .expect("searching autnums by name");
+
+    // THEN
+    let RdapResponse::AutnumSearchResults(autnums) = actual else {
+        panic!("not autnum search results")
+    };
+    assert_eq!(autnums.results.len(), 1);
+    assert_eq!(autnums.results[0].name(), Some("EXAMPLE-AS"));
+}
@@ -1,2 +1,3 @@
+mod backend;
 mod data;
 mod mem;
@@ -1,15 +1,29 @@
 use {
     assert_cmd::Command,
+    icann_rdap_common::response::filter::ResponseFilter,
     icann_rdap_srv::{
-        config::ListenConfig,
+        bootstrap::{load_bootstrap_registries, BootstrapRegistries},
+        config::{ListenConfig, ServiceConfig, StorageType},
+        rdap::{
+            compression::{Compression, CompressionConfig},
+            error_body::ErrorBodies,
+            proxy::{Proxy, ProxyConfig},
+            rate_limit::{RateLimitConfig, RateLimiter},
+        },
         server::{AppState, Listener},
         storage::{
+            data::load_data,
             mem::{config::MemConfig, ops::Mem},
+            watch::DataReloader,
             CommonConfig,
         },
     },
-    std::time::Duration,
+    std::{collections::BTreeMap, net::SocketAddr, sync::Arc, time::Duration},
     test_dir::{DirBuilder, TestDir},
+    tokio::{
+        io::{AsyncReadExt, AsyncWriteExt},
+        net::TcpStream,
+    },
 };
 
 pub struct RdapSrvStoreTestJig {
@@ -86,6 +100,15 @@ impl SrvTestJig {
         let app_state = AppState {
             storage: mem.clone(),
             bootstrap: false,
+            search_page_size: None,
+            error_bodies: ErrorBodies::default(),
+            response_filter: None,
+            compression: Compression::default(),
+            deny_invalid_rdap: false,
+            reloader: None,
+            reload_endpoint: false,
+            rate_limiter: None,
+            proxy: None,
         };
         let _ = tracing_subscriber::fmt().with_test_writer().try_init();
         let listener = Listener::listen(&ListenConfig::default())
@@ -107,6 +130,230 @@ impl SrvTestJig {
         let app_state = AppState {
             storage: mem.clone(),
             bootstrap: false,
+            search_page_size: None,
+            error_bodies: ErrorBodies::default(),
+            response_filter: None,
+            compression: Compression::default(),
+            deny_invalid_rdap: false,
+            reloader: None,
+            reload_endpoint: false,
+            rate_limiter: None,
+            proxy: None,
+        };
+        let _ = tracing_subscriber::fmt().with_test_writer().try_init();
+        let listener = Listener::listen(&ListenConfig::default())
+            .await
+            .expect("listening on interface");
+        let rdap_base = listener.rdap_base();
+        tokio::spawn(async move {
+            listener
+                .start_with_state(app_state)
+                .await
+                .expect("starting server");
+        });
+        Self { mem, rdap_base }
+    }
+
+    pub async fn new_error_bodies(error_bodies: ErrorBodies) -> Self {
+        let mem = Mem::default();
+        let app_state = AppState {
+            storage: mem.clone(),
+            bootstrap: false,
+            search_page_size: None,
+            error_bodies,
+            response_filter: None,
+            compression: Compression::default(),
+            deny_invalid_rdap: false,
+            reloader: None,
+            reload_endpoint: false,
+            rate_limiter: None,
+            proxy: None,
+        };
+        let _ = tracing_subscriber::fmt().with_test_writer().try_init();
+        let listener = Listener::listen(&ListenConfig::default())
+            .await
+            .expect("listening on interface");
+        let rdap_base = listener.rdap_base();
+        tokio::spawn(async move {
+            listener
+                .start_with_state(app_state)
+                .await
+                .expect("starting server");
+        });
+        Self { mem, rdap_base }
+    }
+
+    pub async fn new_response_filter(response_filter: ResponseFilter) -> Self {
+        let mem = Mem::default();
+        let app_state = AppState {
+            storage: mem.clone(),
+            bootstrap: false,
+            search_page_size: None,
+            error_bodies: ErrorBodies::default(),
+            response_filter: Some(response_filter),
+            compression: Compression::default(),
+            deny_invalid_rdap: false,
+            reloader: None,
+            reload_endpoint: false,
+            rate_limiter: None,
+            proxy: None,
+        };
+        let _ = tracing_subscriber::fmt().with_test_writer().try_init();
+        let listener = Listener::listen(&ListenConfig::default())
+            .await
+            .expect("listening on interface");
+        let rdap_base = listener.rdap_base();
+        tokio::spawn(async move {
+            listener
+                .start_with_state(app_state)
+                .await
+                .expect("starting server");
+        });
+        Self { mem, rdap_base }
+    }
+
+    pub async fn new_virtual_host(host: &str, host_mem: Mem) -> Self {
+        let mem = Mem::default();
+        let app_state = |storage: Mem| AppState {
+            storage,
+            bootstrap: false,
+            search_page_size: None,
+            error_bodies: ErrorBodies::default(),
+            response_filter: None,
+            compression: Compression::default(),
+            deny_invalid_rdap: false,
+            reloader: None,
+            reload_endpoint: false,
+            rate_limiter: None,
+            proxy: None,
+        };
+        let virtual_hosts = BTreeMap::from([(host.to_string(), app_state(host_mem))]);
+        let app_state = app_state(mem.clone());
+        let _ = tracing_subscriber::fmt().with_test_writer().try_init();
+        let listener = Listener::listen(&ListenConfig::default())
+            .await
+            .expect("listening on interface");
+        let rdap_base = listener.rdap_base();
+        tokio::spawn(async move {
+            listener
+                .start_with_virtual_hosts(app_state, virtual_hosts)
+                .await
+                .expect("starting server");
+        });
+        Self { mem, rdap_base }
+    }
+
+    pub async fn new_compression(compression: CompressionConfig) -> Self {
+        let mem = Mem::default();
+        let app_state = AppState {
+            storage: mem.clone(),
+            bootstrap: false,
+            search_page_size: None,
+            error_bodies: ErrorBodies::default(),
+            response_filter: None,
+            compression: Compression::new(compression),
+            deny_invalid_rdap: false,
+            reloader: None,
+            reload_endpoint: false,
+            rate_limiter: None,
+            proxy: None,
+        };
+        let _ = tracing_subscriber::fmt().with_test_writer().try_init();
+        let listener = Listener::listen(&ListenConfig::default())
+            .await
+            .expect("listening on interface");
+        let rdap_base = listener.rdap_base();
+        tokio::spawn(async move {
+            listener
+                .start_with_state(app_state)
+                .await
+                .expect("starting server");
+        });
+        Self { mem, rdap_base }
+    }
+
+    pub async fn new_rate_limit(rate_limit: RateLimitConfig) -> Self {
+        let mem = Mem::default();
+        let app_state = AppState {
+            storage: mem.clone(),
+            bootstrap: false,
+            search_page_size: None,
+            error_bodies: ErrorBodies::default(),
+            response_filter: None,
+            compression: Compression::default(),
+            deny_invalid_rdap: false,
+            reloader: None,
+            reload_endpoint: false,
+            rate_limiter: Some(Arc::new(RateLimiter::new(rate_limit))),
+            proxy: None,
+        };
+        let _ = tracing_subscriber::fmt().with_test_writer().try_init();
+        let listener = Listener::listen(&ListenConfig::default())
+            .await
+            .expect("listening on interface");
+        let rdap_base = listener.rdap_base();
+        tokio::spawn(async move {
+            listener
+                .start_with_state(app_state)
+                .await
+                .expect("starting server");
+        });
+        Self { mem, rdap_base }
+    }
+
+    pub async fn new_deny_invalid_rdap() -> Self {
+        let mem = Mem::default();
+        let app_state = AppState {
+            storage: mem.clone(),
+            bootstrap: false,
+            search_page_size: None,
+            error_bodies: ErrorBodies::default(),
+            response_filter: None,
+            compression: Compression::default(),
+            deny_invalid_rdap: true,
+            reloader: None,
+            reload_endpoint: false,
+            rate_limiter: None,
+            proxy: None,
+        };
+        let _ = tracing_subscriber::fmt().with_test_writer().try_init();
+        let listener = Listener::listen(&ListenConfig::default())
+            .await
+            .expect("listening on interface");
+        let rdap_base = listener.rdap_base();
+        tokio::spawn(async move {
+            listener
+                .start_with_state(app_state)
+                .await
+                .expect("starting server");
+        });
+        Self { mem, rdap_base }
+    }
+
+    pub async fn new_reload_endpoint(data_dir: &TestDir) -> Self {
+        let mem = Mem::default();
+        let service_config = ServiceConfig::non_server()
+            .data_dir(data_dir.root().to_string_lossy())
+            .storage_type(StorageType::Memory(
+                MemConfig::builder()
+                    .common_config(CommonConfig::default())
+                    .build(),
+            ))
+            .build()
+            .expect("service config");
+        let reloader = DataReloader::new(Box::new(mem.clone()), service_config);
+        let app_state = AppState {
+            storage: mem.clone(),
+            bootstrap: false,
+            search_page_size: None,
+            error_bodies: ErrorBodies::default(),
+            response_filter: None,
+            compression: Compression::default(),
+            deny_invalid_rdap: false,
+            reloader: Some(Arc::new(reloader)),
+            reload_endpoint: true,
+            rate_limiter: None,
+            proxy: None,
         };
         let _ = tracing_subscriber::fmt().with_test_writer().try_init();
         let listener = Listener::listen(&ListenConfig::default())
@@ -127,6 +374,15 @@ impl SrvTestJig {
         let app_state = AppState {
             storage: mem.clone(),
             bootstrap: true,
+            search_page_size: None,
+            error_bodies: ErrorBodies::default(),
+            response_filter: None,
+            compression: Compression::default(),
+            deny_invalid_rdap: false,
+            reloader: None,
+            reload_endpoint: false,
+            rate_limiter: None,
+            proxy: None,
         };
         let _ = tracing_subscriber::fmt().with_test_writer().try_init();
         let listener = Listener::listen(&ListenConfig::default())
@@ -141,4 +397,148 @@ impl SrvTestJig {
         });
         Self { mem, rdap_base }
     }
+
+    /// Starts a server proxying the lookups it does not have to the servers
+    /// given by the bootstrap registries.
+    pub async fn new_proxy(registries: BootstrapRegistries) -> Self {
+        let mem = Mem::default();
+        let proxy = Proxy::new(
+            ProxyConfig::builder()
+                .cache_ttl(Duration::from_secs(60))
+                .https_only(false)
+                .build(),
+            Some(&registries),
+        )
+        .expect("creating proxy");
+        let app_state = AppState {
+            storage: mem.clone(),
+            bootstrap: false,
+            search_page_size: None,
+            error_bodies: ErrorBodies::default(),
+            response_filter: None,
+            compression: Compression::default(),
+            deny_invalid_rdap: false,
+            reloader: None,
+            reload_endpoint: false,
+            rate_limiter: None,
+            proxy: Some(Arc::new(proxy)),
+        };
+        let _ = tracing_subscriber::fmt().with_test_writer().try_init();
+        let listener = Listener::listen(&ListenConfig::default())
+            .await
+            .expect("listening on interface");
+        let rdap_base = listener.rdap_base();
+        tokio::spawn(async move {
+            listener
+                .start_with_state(app_state)
+                .await
+                .expect("starting server");
+        });
+        Self { mem, rdap_base }
+    }
+
+    /// Starts a bootstrapping server with redirects from the given registries
+    /// instead of those of IANA.
+    pub async fn new_bootstrap_registries(registries: BootstrapRegistries) -> Self {
+        let mem = Mem::default();
+        let data_dir = TestDir::temp();
+        let service_config = ServiceConfig::non_server()
+            .data_dir(data_dir.root().to_string_lossy())
+            .storage_type(StorageType::Memory(
+                MemConfig::builder()
+                    .common_config(CommonConfig::default())
+                    .build(),
+            ))
+            .build()
+            .expect("service config");
+        load_bootstrap_registries(&service_config, &registries)
+            .await
+            .expect("loading bootstrap registries");
+        load_data(&service_config, &mem, false)
+            .await
+            .expect("loading data");
+        let app_state = AppState {
+            storage: mem.clone(),
+            bootstrap: true,
+            search_page_size: None,
+            error_bodies: ErrorBodies::default(),
+            response_filter: None,
+            compression: Compression::default(),
+            deny_invalid_rdap: false,
+            reloader: None,
+            reload_endpoint: false,
+            rate_limiter: None,
+            proxy: None,
+        };
+        let _ = tracing_subscriber::fmt().with_test_writer().try_init();
+        let listener = Listener::listen(&ListenConfig::default())
+            .await
+            .expect("listening on interface");
+        let rdap_base = listener.rdap_base();
+        tokio::spawn(async move {
+            listener
+                .start_with_state(app_state)
+                .await
+                .expect("starting server");
+        });
+        Self { mem, rdap_base }
+    }
+}
+
+pub struct SrvWhoisTestJig {
+    pub mem: Mem,
+    pub whois_addr: SocketAddr,
+}
+
+impl SrvWhoisTestJig {
+    pub async fn new() -> Self {
+        let mem = Mem::default();
+        let app_state = AppState {
+            storage: mem.clone(),
+            bootstrap: false,
+            search_page_size: None,
+            error_bodies: ErrorBodies::default(),
+            response_filter: None,
+            compression: Compression::default(),
+            deny_invalid_rdap: false,
+            reloader: None,
+            reload_endpoint: false,
+            rate_limiter: None,
+            proxy: None,
+        };
+        let _ = tracing_subscriber::fmt().with_test_writer().try_init();
+        let listen_config = ListenConfig::builder()
+            .ip_addr("127.0.0.1")
+            .socket_activation(false)
+            .whois_port(0)
+            .build();
+        let listener = Listener::listen(&listen_config)
+            .await
+            .expect("listening on interface");
+        let whois_addr = listener.whois_addr.expect("listening for WHOIS");
+        tokio::spawn(async move {
+            listener
+                .start_with_state(app_state)
+                .await
+                .expect("starting server");
+        });
+        Self { mem, whois_addr }
+    }
+
+    /// Sends a WHOIS query, giving the answer.
+    pub async fn query(&self, query: &str) -> String {
+        let mut stream = TcpStream::connect(self.whois_addr)
+            .await
+            .expect("connecting to WHOIS");
+        stream
+            .write_all(format!("{query}\r\n").as_bytes())
+            .await
+            .expect("sending query");
+        let mut answer = String::new();
+        stream
+            .read_to_string(&mut answer)
+            .await
+            .expect("reading answer");
+        answer
+    }
 }